@@ -0,0 +1,115 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index};
+
+pub(crate) fn binary_value_impl(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    let fields = match data {
+        Data::Struct(data) => data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "BinaryValue can only be derived for structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "BinaryValue can not be derived for structs without fields",
+        ));
+    }
+
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let count = types.len();
+
+    // The accessors of the fields, eg. `self.a` or `self.0`.
+    let accessors: Vec<TokenStream2> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        })
+        .collect();
+
+    let values: Vec<_> = (0..count).map(|i| format_ident!("value_{}", i)).collect();
+
+    let construct = match &fields {
+        Fields::Named(_) => quote!(Self { #(#accessors: #values.try_into()?),* }),
+        _ => quote!(Self(#(#values.try_into()?),*)),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics mpz_circuits::types::BinaryValue for #ident #ty_generics #where_clause {
+            fn value_types() -> Vec<mpz_circuits::types::ValueType> {
+                vec![#(<#types as mpz_circuits::types::StaticValueType>::value_type()),*]
+            }
+
+            fn into_values(self) -> Vec<mpz_circuits::types::Value> {
+                vec![#(self.#accessors.into()),*]
+            }
+
+            fn from_values(
+                values: Vec<mpz_circuits::types::Value>,
+            ) -> Result<Self, mpz_circuits::types::TypeError> {
+                use mpz_circuits::types::TypeError;
+
+                if values.len() != #count {
+                    return Err(TypeError::InvalidValueCount {
+                        expected: #count,
+                        actual: values.len(),
+                    });
+                }
+
+                for (expected, value) in Self::value_types().into_iter().zip(&values) {
+                    let actual = value.value_type();
+                    if actual != expected {
+                        return Err(TypeError::UnexpectedType { expected, actual });
+                    }
+                }
+
+                let mut values = values.into_iter();
+                #(let #values = values.next().expect("value count was checked");)*
+
+                Ok(#construct)
+            }
+        }
+
+        impl #impl_generics From<#ident #ty_generics> for Vec<mpz_circuits::types::Value> #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                mpz_circuits::types::BinaryValue::into_values(value)
+            }
+        }
+
+        impl #impl_generics TryFrom<Vec<mpz_circuits::types::Value>> for #ident #ty_generics #where_clause {
+            type Error = mpz_circuits::types::TypeError;
+
+            fn try_from(values: Vec<mpz_circuits::types::Value>) -> Result<Self, Self::Error> {
+                mpz_circuits::types::BinaryValue::from_values(values)
+            }
+        }
+    })
+}
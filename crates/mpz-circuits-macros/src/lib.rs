@@ -1,3 +1,4 @@
+mod binary_value;
 mod evaluate;
 pub(crate) mod map;
 mod test;
@@ -23,3 +24,8 @@ pub fn evaluate(item: TokenStream) -> TokenStream {
 pub fn test_circ(item: TokenStream) -> TokenStream {
     test::test_impl(item)
 }
+
+#[proc_macro_derive(BinaryValue)]
+pub fn binary_value(item: TokenStream) -> TokenStream {
+    binary_value::binary_value_impl(item)
+}
@@ -0,0 +1,360 @@
+//! Arithmetic circuits over a field.
+//!
+//! An arithmetic circuit is built from addition, multiplication and constant gates over a
+//! [`Field`], using the same [`Node`] model as binary circuits.
+
+use mpz_fields::Field;
+
+use crate::{
+    components::{Feed, Node, Sink},
+    BuilderError,
+};
+
+/// An error that can occur when evaluating an arithmetic circuit.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ArithmeticCircuitError {
+    #[error("Invalid number of inputs: expected {0}, got {1}")]
+    InvalidInputCount(usize, usize),
+}
+
+/// An arithmetic gate.
+#[derive(Debug, Clone, Copy)]
+#[allow(missing_docs)]
+pub enum ArithmeticGate<F> {
+    /// Addition gate.
+    Add {
+        x: Node<Sink>,
+        y: Node<Sink>,
+        z: Node<Feed>,
+    },
+    /// Multiplication gate.
+    Mul {
+        x: Node<Sink>,
+        y: Node<Sink>,
+        z: Node<Feed>,
+    },
+    /// Constant gate, which outputs a fixed field element.
+    Const { z: Node<Feed>, value: F },
+}
+
+impl<F> ArithmeticGate<F> {
+    /// Returns the x input of the gate.
+    pub fn x(&self) -> Option<Node<Sink>> {
+        match self {
+            ArithmeticGate::Add { x, .. } => Some(*x),
+            ArithmeticGate::Mul { x, .. } => Some(*x),
+            ArithmeticGate::Const { .. } => None,
+        }
+    }
+
+    /// Returns the y input of the gate.
+    pub fn y(&self) -> Option<Node<Sink>> {
+        match self {
+            ArithmeticGate::Add { y, .. } => Some(*y),
+            ArithmeticGate::Mul { y, .. } => Some(*y),
+            ArithmeticGate::Const { .. } => None,
+        }
+    }
+
+    /// Returns the z output of the gate.
+    pub fn z(&self) -> Node<Feed> {
+        match self {
+            ArithmeticGate::Add { z, .. } => *z,
+            ArithmeticGate::Mul { z, .. } => *z,
+            ArithmeticGate::Const { z, .. } => *z,
+        }
+    }
+}
+
+/// An arithmetic circuit.
+#[derive(Debug, Clone)]
+pub struct ArithmeticCircuit<F> {
+    inputs: Vec<Node<Feed>>,
+    outputs: Vec<Node<Feed>>,
+    gates: Vec<ArithmeticGate<F>>,
+    feed_count: usize,
+    mul_count: usize,
+}
+
+impl<F: Field> ArithmeticCircuit<F> {
+    /// Returns a reference to the inputs of the circuit.
+    pub fn inputs(&self) -> &[Node<Feed>] {
+        &self.inputs
+    }
+
+    /// Returns a reference to the outputs of the circuit.
+    pub fn outputs(&self) -> &[Node<Feed>] {
+        &self.outputs
+    }
+
+    /// Returns a reference to the gates of the circuit.
+    pub fn gates(&self) -> &[ArithmeticGate<F>] {
+        &self.gates
+    }
+
+    /// Returns the number of feeds in the circuit.
+    pub fn feed_count(&self) -> usize {
+        self.feed_count
+    }
+
+    /// Returns the number of multiplication gates in the circuit.
+    pub fn mul_count(&self) -> usize {
+        self.mul_count
+    }
+
+    /// Evaluate the circuit with the given inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The inputs to the circuit
+    ///
+    /// # Returns
+    ///
+    /// The outputs of the circuit.
+    pub fn evaluate(&self, values: &[F]) -> Result<Vec<F>, ArithmeticCircuitError> {
+        if values.len() != self.inputs.len() {
+            return Err(ArithmeticCircuitError::InvalidInputCount(
+                self.inputs.len(),
+                values.len(),
+            ));
+        }
+
+        let mut feeds: Vec<Option<F>> = vec![None; self.feed_count];
+
+        for (input, value) in self.inputs.iter().zip(values) {
+            feeds[input.id] = Some(*value);
+        }
+
+        for gate in self.gates.iter() {
+            match gate {
+                ArithmeticGate::Add { x, y, z } => {
+                    let x = feeds[x.id].expect("Feed should be set");
+                    let y = feeds[y.id].expect("Feed should be set");
+
+                    feeds[z.id] = Some(x + y);
+                }
+                ArithmeticGate::Mul { x, y, z } => {
+                    let x = feeds[x.id].expect("Feed should be set");
+                    let y = feeds[y.id].expect("Feed should be set");
+
+                    feeds[z.id] = Some(x * y);
+                }
+                ArithmeticGate::Const { z, value } => {
+                    feeds[z.id] = Some(*value);
+                }
+            }
+        }
+
+        Ok(self
+            .outputs
+            .iter()
+            .map(|node| feeds[node.id].expect("Feed should be set"))
+            .collect())
+    }
+}
+
+/// An arithmetic circuit builder.
+///
+/// # Example
+///
+/// The following example builds a circuit which computes `a * b + 1`.
+///
+/// ```
+/// use mpz_circuits::arithmetic::ArithmeticCircuitBuilder;
+/// use mpz_fields::{p256::P256, Field};
+///
+/// let mut builder = ArithmeticCircuitBuilder::<P256>::new();
+///
+/// let a = builder.add_input();
+/// let b = builder.add_input();
+/// let one = builder.add_constant(P256::one());
+///
+/// let c = builder.mul(a, b);
+/// let d = builder.add(c, one);
+///
+/// builder.add_output(d);
+///
+/// let circ = builder.build().unwrap();
+///
+/// let a = P256::one() + P256::one();
+/// let output = circ.evaluate(&[a, a]).unwrap();
+///
+/// assert_eq!(output, vec![a * a + P256::one()]);
+/// ```
+#[derive(Debug)]
+pub struct ArithmeticCircuitBuilder<F> {
+    inputs: Vec<Node<Feed>>,
+    outputs: Vec<Node<Feed>>,
+    gates: Vec<ArithmeticGate<F>>,
+    feed_id: usize,
+    mul_count: usize,
+}
+
+impl<F> Default for ArithmeticCircuitBuilder<F> {
+    fn default() -> Self {
+        Self {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            gates: Vec::new(),
+            feed_id: 0,
+            mul_count: 0,
+        }
+    }
+}
+
+impl<F: Field> ArithmeticCircuitBuilder<F> {
+    /// Creates a new arithmetic circuit builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new input to the circuit.
+    pub fn add_input(&mut self) -> Node<Feed> {
+        let input = self.add_feed();
+        self.inputs.push(input);
+
+        input
+    }
+
+    /// Adds a constant to the circuit.
+    pub fn add_constant(&mut self, value: F) -> Node<Feed> {
+        let z = self.add_feed();
+        self.gates.push(ArithmeticGate::Const { z, value });
+
+        z
+    }
+
+    /// Adds an addition gate to the circuit, returning its output.
+    pub fn add(&mut self, x: Node<Feed>, y: Node<Feed>) -> Node<Feed> {
+        let z = self.add_feed();
+        self.gates.push(ArithmeticGate::Add {
+            x: x.into(),
+            y: y.into(),
+            z,
+        });
+
+        z
+    }
+
+    /// Adds a multiplication gate to the circuit, returning its output.
+    pub fn mul(&mut self, x: Node<Feed>, y: Node<Feed>) -> Node<Feed> {
+        let z = self.add_feed();
+        self.gates.push(ArithmeticGate::Mul {
+            x: x.into(),
+            y: y.into(),
+            z,
+        });
+        self.mul_count += 1;
+
+        z
+    }
+
+    /// Adds an output to the circuit.
+    pub fn add_output(&mut self, node: Node<Feed>) {
+        self.outputs.push(node);
+    }
+
+    /// Builds the circuit.
+    ///
+    /// Returns an error if a gate or an output refers to a node which was not created by this
+    /// builder before it.
+    pub fn build(self) -> Result<ArithmeticCircuit<F>, BuilderError> {
+        for gate in &self.gates {
+            let z = gate.z().id;
+            for x in [gate.x(), gate.y()].into_iter().flatten() {
+                if x.id >= z {
+                    return Err(BuilderError::MissingWire(x.id));
+                }
+            }
+        }
+
+        if let Some(node) = self.outputs.iter().find(|node| node.id >= self.feed_id) {
+            return Err(BuilderError::MissingWire(node.id));
+        }
+
+        Ok(ArithmeticCircuit {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            gates: self.gates,
+            feed_count: self.feed_id,
+            mul_count: self.mul_count,
+        })
+    }
+
+    fn add_feed(&mut self) -> Node<Feed> {
+        let feed = Node::<Feed>::new(self.feed_id);
+        self.feed_id += 1;
+
+        feed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+
+    /// Builds a circuit computing the inner product of two vectors of length `n`.
+    fn inner_product<F: Field>(n: usize) -> ArithmeticCircuit<F> {
+        let mut builder = ArithmeticCircuitBuilder::new();
+
+        let a: Vec<_> = (0..n).map(|_| builder.add_input()).collect();
+        let b: Vec<_> = (0..n).map(|_| builder.add_input()).collect();
+
+        let mut acc = builder.add_constant(F::zero());
+        for (a, b) in a.into_iter().zip(b) {
+            let prod = builder.mul(a, b);
+            acc = builder.add(acc, prod);
+        }
+
+        builder.add_output(acc);
+        builder.build().unwrap()
+    }
+
+    fn test_inner_product<F: Field>() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let circ = inner_product::<F>(8);
+
+        assert_eq!(circ.mul_count(), 8);
+
+        let inputs: Vec<F> = (0..16).map(|_| F::rand(&mut rng)).collect();
+        let (a, b) = inputs.split_at(8);
+
+        let expected = a.iter().zip(b).fold(F::zero(), |acc, (a, b)| acc + *a * *b);
+
+        assert_eq!(circ.evaluate(&inputs).unwrap(), vec![expected]);
+    }
+
+    #[test]
+    fn test_arithmetic_circuit() {
+        test_inner_product::<P256>();
+        test_inner_product::<Gf2_128>();
+    }
+
+    #[test]
+    fn test_arithmetic_circuit_invalid_input_count() {
+        let circ = inner_product::<P256>(2);
+
+        assert!(matches!(
+            circ.evaluate(&[P256::one()]),
+            Err(ArithmeticCircuitError::InvalidInputCount(4, 1))
+        ));
+    }
+
+    #[test]
+    fn test_arithmetic_builder_foreign_node() {
+        let mut other = ArithmeticCircuitBuilder::<P256>::new();
+        let foreign = (0..4).map(|_| other.add_input()).last().unwrap();
+
+        let mut builder = ArithmeticCircuitBuilder::<P256>::new();
+        let a = builder.add_input();
+        let b = builder.add(a, foreign);
+        builder.add_output(b);
+
+        assert!(matches!(builder.build(), Err(BuilderError::MissingWire(3))));
+    }
+}
@@ -2,7 +2,7 @@ use itybity::{BitIterable, IntoBits};
 
 use crate::{
     components::{Feed, Gate, Node},
-    types::{BinaryLength, BinaryRepr, ToBinaryRepr, ValueType},
+    types::{BinaryLength, BinaryRepr, BinaryValue, ToBinaryRepr, ValueType},
     Circuit, Tracer,
 };
 use std::{cell::RefCell, collections::HashMap, mem::discriminant};
@@ -15,6 +15,8 @@ pub enum BuilderError {
     MissingWire(usize),
     #[error("error appending circuit: {0}")]
     AppendError(String),
+    #[error("circuit exceeds the gate limit: limit {limit}, got {actual}")]
+    GateLimitExceeded { limit: usize, actual: usize },
 }
 
 /// A circuit builder.
@@ -57,6 +59,19 @@ impl CircuitBuilder {
         }
     }
 
+    /// Creates a new circuit builder which rejects circuits with more than `limit` gates.
+    ///
+    /// The limit is checked by [`CircuitBuilder::repeat`] after every iteration, and when the
+    /// circuit is built.
+    pub fn with_gate_limit(limit: usize) -> Self {
+        Self {
+            state: RefCell::new(BuilderState {
+                gate_limit: Some(limit),
+                ..Default::default()
+            }),
+        }
+    }
+
     /// Returns a reference to the internal state of the builder
     pub fn state(&self) -> &RefCell<BuilderState> {
         &self.state
@@ -94,6 +109,18 @@ impl CircuitBuilder {
         value
     }
 
+    /// Adds a new input to the circuit for each value of the provided type, see [`BinaryValue`].
+    ///
+    /// # Returns
+    ///
+    /// The binary encoded forms of the inputs.
+    pub fn add_inputs<T: BinaryValue>(&self) -> Vec<BinaryRepr> {
+        T::value_types()
+            .into_iter()
+            .map(|typ| self.add_input_by_type(typ))
+            .collect()
+    }
+
     /// Adds a new array input to the circuit of the provided type
     ///
     /// # Returns
@@ -173,6 +200,63 @@ impl CircuitBuilder {
         self.state.borrow_mut().append(circ, builder_inputs)
     }
 
+    /// Repeats `f` `n` times, passing the state returned by each iteration to the next one.
+    ///
+    /// The loop is unrolled, so the circuit contains the gates of every iteration. If the builder
+    /// has a gate limit, the limit is checked after every iteration so that a runaway loop fails
+    /// early instead of exhausting memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of iterations.
+    /// * `init` - The state passed to the first iteration.
+    /// * `f` - The body of the loop.
+    ///
+    /// # Returns
+    ///
+    /// The state returned by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// The following example computes a counter incremented 4 times.
+    ///
+    /// ```
+    /// use mpz_circuits::{CircuitBuilder, ops::WrappingAdd};
+    ///
+    /// let builder = CircuitBuilder::new();
+    ///
+    /// let a = builder.add_input::<u8>();
+    /// let counter = builder
+    ///     .repeat(4, a, |builder, counter| {
+    ///         counter.wrapping_add(builder.get_constant(1u8))
+    ///     })
+    ///     .unwrap();
+    ///
+    /// builder.add_output(counter);
+    ///
+    /// let circ = builder.build().unwrap();
+    /// let output: u8 = circ.evaluate(&[1u8.into()]).unwrap()[0]
+    ///     .clone()
+    ///     .try_into()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(output, 5);
+    /// ```
+    pub fn repeat<'a, S>(
+        &'a self,
+        n: usize,
+        init: S,
+        mut f: impl FnMut(&'a CircuitBuilder, S) -> S,
+    ) -> Result<S, BuilderError> {
+        let mut state = init;
+        for _ in 0..n {
+            state = f(self, state);
+            self.state.borrow().check_gate_limit()?;
+        }
+
+        Ok(state)
+    }
+
     /// Builds the circuit
     pub fn build(self) -> Result<Circuit, BuilderError> {
         self.state.into_inner().build()
@@ -189,6 +273,8 @@ pub struct BuilderState {
 
     and_count: usize,
     xor_count: usize,
+
+    gate_limit: Option<usize>,
 }
 
 impl Default for BuilderState {
@@ -201,6 +287,7 @@ impl Default for BuilderState {
             gates: vec![],
             and_count: 0,
             xor_count: 0,
+            gate_limit: None,
         }
     }
 }
@@ -233,6 +320,17 @@ impl BuilderState {
         T::new_bin_repr(&nodes).expect("Value should have correct bit length")
     }
 
+    /// Returns an error if the circuit exceeds the gate limit.
+    fn check_gate_limit(&self) -> Result<(), BuilderError> {
+        match self.gate_limit {
+            Some(limit) if self.gates.len() > limit => Err(BuilderError::GateLimitExceeded {
+                limit,
+                actual: self.gates.len(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
     /// Adds a feed to the circuit.
     pub(crate) fn add_feed(&mut self) -> Node<Feed> {
         let feed = Node::<Feed>::new(self.feed_id);
@@ -415,6 +513,14 @@ impl BuilderState {
                     let new_z = self.add_inv_gate(*new_x);
                     feed_map.insert(*z, new_z);
                 }
+                Gate::Const { z, value } => {
+                    let new_z = if *value {
+                        self.get_const_one()
+                    } else {
+                        self.get_const_zero()
+                    };
+                    feed_map.insert(*z, new_z);
+                }
             }
         }
 
@@ -431,6 +537,25 @@ impl BuilderState {
 
     /// Builds the circuit.
     pub(crate) fn build(mut self) -> Result<Circuit, BuilderError> {
+        // Outputs which are constant are produced by constant gates, as the reserved
+        // constant nodes are removed from the circuit.
+        let mut const_feeds: [Option<Node<Feed>>; 2] = [None; 2];
+        for output in self.outputs.iter_mut() {
+            for node in output.iter_mut() {
+                let id = node.id();
+                if id < 2 {
+                    *node = *const_feeds[id].get_or_insert_with(|| {
+                        let z = Node::<Feed>::new(self.feed_id);
+                        self.feed_id += 1;
+                        self.gates.push(Gate::Const { z, value: id == 1 });
+                        z
+                    });
+                }
+            }
+        }
+
+        self.check_gate_limit()?;
+
         // Shift all the node ids to the left by 2 to eliminate
         // the reserved constant nodes (which should be factored out during building)
         self.inputs.iter_mut().for_each(|input| input.shift_left(2));
@@ -442,7 +567,7 @@ impl BuilderState {
         Ok(Circuit {
             inputs: self.inputs,
             outputs: self.outputs,
-            gates: self.gates,
+            gates: self.gates.into(),
             feed_count: self.feed_id,
             and_count: self.and_count,
             xor_count: self.xor_count,
@@ -510,4 +635,95 @@ mod test {
         // a + (a + b) = 2a + b
         assert_eq!(d, 3u8);
     }
+
+    #[test]
+    fn test_build_constant_outputs() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+
+        builder.add_output(a & builder.get_constant(0x0fu8));
+        builder.add_output(builder.get_constant(0xa5u8));
+
+        let circ = builder.build().unwrap();
+
+        // Constant output bits share one constant gate per value.
+        let const_count = circ
+            .gates()
+            .iter()
+            .filter(|gate| matches!(gate, Gate::Const { .. }))
+            .count();
+        assert_eq!(const_count, 2);
+
+        let output = circ.evaluate(&[0xffu8.into()]).unwrap();
+
+        assert_eq!(output, vec![0x0fu8.into(), 0xa5u8.into()]);
+    }
+
+    #[test]
+    fn test_repeat() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        // Fibonacci-style recurrence over a pair of values.
+        let (_, c) = builder
+            .repeat(10, (a, b), |_, (x, y)| (y, x.wrapping_add(y)))
+            .unwrap();
+
+        // Zero iterations return the initial state.
+        let d = builder.repeat(0, a, |_, x| x.wrapping_add(x)).unwrap();
+
+        builder.add_output(c);
+        builder.add_output(d);
+
+        let circ = builder.build().unwrap();
+
+        let (mut x, mut y) = (3u8, 7u8);
+        for _ in 0..10 {
+            (x, y) = (y, x.wrapping_add(y));
+        }
+
+        let output = circ.evaluate(&[3u8.into(), 7u8.into()]).unwrap();
+
+        assert_eq!(output, vec![y.into(), 3u8.into()]);
+    }
+
+    #[test]
+    fn test_repeat_gate_limit() {
+        let limit = 1000;
+        let builder = CircuitBuilder::with_gate_limit(limit);
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let err = builder
+            .repeat(1_000_000, a, |_, x| x.wrapping_add(b))
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            BuilderError::GateLimitExceeded { limit: 1000, .. }
+        ));
+
+        // The loop stops at the first iteration which exceeds the limit.
+        let gate_count = build_adder().gates().len();
+        assert!(builder.state().borrow().gates.len() <= limit + gate_count);
+    }
+
+    #[test]
+    fn test_build_gate_limit() {
+        let builder = CircuitBuilder::with_gate_limit(1);
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        builder.add_output(a.wrapping_add(b));
+
+        assert!(matches!(
+            builder.build(),
+            Err(BuilderError::GateLimitExceeded { limit: 1, .. })
+        ));
+    }
 }
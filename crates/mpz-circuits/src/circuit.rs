@@ -1,7 +1,10 @@
+use std::ops::Deref;
+
 use itybity::IntoBits;
 
 use crate::{
     components::Gate,
+    optimize::{self, OptimizationReport},
     types::{BinaryRepr, TypeError, Value},
 };
 
@@ -23,13 +26,75 @@ pub enum CircuitError {
 pub struct Circuit {
     pub(crate) inputs: Vec<BinaryRepr>,
     pub(crate) outputs: Vec<BinaryRepr>,
-    pub(crate) gates: Vec<Gate>,
+    pub(crate) gates: Gates,
     pub(crate) feed_count: usize,
 
     pub(crate) and_count: usize,
     pub(crate) xor_count: usize,
 }
 
+/// The gates of a circuit, either owned or mapped from a file.
+#[derive(Debug, Clone)]
+pub(crate) enum Gates {
+    Owned(Vec<Gate>),
+    #[cfg(feature = "mmap")]
+    Mapped(crate::mmap::MappedGates),
+}
+
+impl Gates {
+    /// Returns the gates as an owned vector, copying them if they are mapped.
+    pub(crate) fn into_vec(self) -> Vec<Gate> {
+        match self {
+            Gates::Owned(gates) => gates,
+            #[cfg(feature = "mmap")]
+            Gates::Mapped(gates) => gates.to_vec(),
+        }
+    }
+}
+
+impl Deref for Gates {
+    type Target = [Gate];
+
+    fn deref(&self) -> &[Gate] {
+        match self {
+            Gates::Owned(gates) => gates.as_slice(),
+            #[cfg(feature = "mmap")]
+            Gates::Mapped(gates) => gates.deref(),
+        }
+    }
+}
+
+impl From<Vec<Gate>> for Gates {
+    fn from(gates: Vec<Gate>) -> Self {
+        Gates::Owned(gates)
+    }
+}
+
+impl<'a> IntoIterator for &'a Gates {
+    type Item = &'a Gate;
+    type IntoIter = std::slice::Iter<'a, Gate>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Gates are serialized as a sequence regardless of how they are stored, so the format is the
+// same as that of a vector.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Gates {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Gates {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Gate>::deserialize(deserializer).map(Gates::Owned)
+    }
+}
+
 impl Circuit {
     /// Returns a reference to the inputs of the circuit.
     pub fn inputs(&self) -> &[BinaryRepr] {
@@ -109,6 +174,13 @@ impl Circuit {
         self
     }
 
+    /// Returns an optimized copy of the circuit, along with a report of the gate-count reduction.
+    ///
+    /// See [`optimize`](crate::optimize) for the passes which are applied.
+    pub fn optimized(&self) -> (Circuit, OptimizationReport) {
+        optimize::optimize(self)
+    }
+
     /// Evaluate the circuit with the given inputs.
     ///
     /// # Arguments
@@ -160,6 +232,9 @@ impl Circuit {
 
                     feeds[z.id] = Some(!x);
                 }
+                Gate::Const { z, value } => {
+                    feeds[z.id] = Some(*value);
+                }
             }
         }
 
@@ -188,7 +263,7 @@ impl IntoIterator for Circuit {
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.gates.into_iter()
+        self.gates.into_vec().into_iter()
     }
 }
 
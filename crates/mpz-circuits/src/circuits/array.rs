@@ -0,0 +1,249 @@
+//! Circuits for indexing into arrays with a secret index.
+//!
+//! Reads are a multiplexer tree over the bits of the index, and writes decode the index into a
+//! one-hot selector for each element. Both cost about one AND gate per bit of each element, plus
+//! a few gates to handle indices which are out of range.
+//!
+//! Indices are `u32` values. Reading an out of range index returns zero, and writing to one
+//! leaves the array unchanged.
+
+use std::cell::RefCell;
+
+use crate::{
+    ops::binary::select_nbit,
+    types::{BinaryRepr, ValueType, U32},
+    BuilderState, Circuit, CircuitBuilder, Feed, Node, Tracer,
+};
+
+/// Returns the number of index bits needed to address an array of the provided length.
+fn index_bits(len: usize) -> usize {
+    len.next_power_of_two().trailing_zeros() as usize
+}
+
+/// Returns a node which is 1 if none of the provided bits are set.
+fn none_set(state: &mut BuilderState, bits: &[Node<Feed>]) -> Node<Feed> {
+    let mut acc = state.get_const_one();
+    for bit in bits {
+        let not_bit = state.add_inv_gate(*bit);
+        acc = state.add_and_gate(acc, not_bit);
+    }
+    acc
+}
+
+/// Checks that the array is not empty and that all elements have the same type, returning the
+/// element type.
+fn elem_type(array: &[BinaryRepr]) -> ValueType {
+    let typ = array.first().expect("array must not be empty").value_type();
+    assert!(
+        array.iter().all(|elem| elem.value_type() == typ),
+        "array elements must have the same type"
+    );
+    typ
+}
+
+/// Reads an element of an array at a secret index.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `array` - The elements of the array.
+/// * `index` - The index to read.
+///
+/// # Returns
+///
+/// The element at the index, or zero if the index is out of range.
+pub fn array_read_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    array: &[BinaryRepr],
+    index: Tracer<'a, U32>,
+) -> BinaryRepr {
+    let typ = elem_type(array);
+    let index = index.to_inner().nodes();
+    let depth = index_bits(array.len());
+
+    let mut state = state.borrow_mut();
+    let zero = vec![state.get_const_zero(); typ.len()];
+
+    // Indices past the end of the array select zero.
+    let mut level = array
+        .iter()
+        .map(|elem| elem.iter().copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for bit in &index[..depth] {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => select_nbit(&mut state, a, b, *bit),
+                [a] => select_nbit(&mut state, a, &zero, *bit),
+                _ => unreachable!("chunks are not empty"),
+            })
+            .collect();
+    }
+
+    let in_range = none_set(&mut state, &index[depth..]);
+    let nodes = level
+        .pop()
+        .expect("tree has a root")
+        .into_iter()
+        .map(|node| state.add_and_gate(node, in_range))
+        .collect::<Vec<_>>();
+
+    typ.to_bin_repr(&nodes)
+        .expect("element should have correct bit length")
+}
+
+/// Writes an element of an array at a secret index.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `array` - The elements of the array.
+/// * `index` - The index to write.
+/// * `value` - The value to write.
+///
+/// # Returns
+///
+/// The elements of the updated array. The array is unchanged if the index is out of range.
+pub fn array_write_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    array: &[BinaryRepr],
+    index: Tracer<'a, U32>,
+    value: BinaryRepr,
+) -> Vec<BinaryRepr> {
+    let typ = elem_type(array);
+    assert_eq!(
+        value.value_type(),
+        typ,
+        "value must have the type of the array elements"
+    );
+    let index = index.to_inner().nodes();
+    let depth = index_bits(array.len());
+
+    let mut state = state.borrow_mut();
+
+    // Decode the index into a one-hot selector, starting from the most significant bit so
+    // that the selectors end up in index order.
+    let mut selectors = vec![none_set(&mut state, &index[depth..])];
+    for bit in index[..depth].iter().rev() {
+        selectors = selectors
+            .into_iter()
+            .flat_map(|selector| {
+                let high = state.add_and_gate(selector, *bit);
+                let low = state.add_xor_gate(selector, high);
+                [low, high]
+            })
+            .collect();
+    }
+
+    let value = value.iter().copied().collect::<Vec<_>>();
+    array
+        .iter()
+        .zip(selectors)
+        .map(|(elem, selector)| {
+            let elem = elem.iter().copied().collect::<Vec<_>>();
+            let nodes = select_nbit(&mut state, &elem, &value, selector);
+            typ.to_bin_repr(&nodes)
+                .expect("element should have correct bit length")
+        })
+        .collect()
+}
+
+/// Builds a circuit which reads an element of an array at a secret index.
+///
+/// The circuit has the following signature:
+///
+/// `fn(array: [T; len], index: u32) -> T`
+///
+/// # Arguments
+///
+/// * `typ` - The type of the array elements.
+/// * `len` - The length of the array.
+pub fn build_array_read(typ: ValueType, len: usize) -> Circuit {
+    let builder = CircuitBuilder::new();
+
+    let BinaryRepr::Array(array) = builder.add_input_by_type(ValueType::Array(Box::new(typ), len))
+    else {
+        unreachable!("input is an array");
+    };
+    let index = builder.add_input::<u32>();
+
+    let value = array_read_trace(builder.state(), &array, index);
+    builder.add_output(value);
+
+    builder.build().expect("circuit should be valid")
+}
+
+/// Builds a circuit which writes an element of an array at a secret index.
+///
+/// The circuit has the following signature:
+///
+/// `fn(array: [T; len], index: u32, value: T) -> [T; len]`
+///
+/// # Arguments
+///
+/// * `typ` - The type of the array elements.
+/// * `len` - The length of the array.
+pub fn build_array_write(typ: ValueType, len: usize) -> Circuit {
+    let builder = CircuitBuilder::new();
+
+    let BinaryRepr::Array(array) =
+        builder.add_input_by_type(ValueType::Array(Box::new(typ.clone()), len))
+    else {
+        unreachable!("input is an array");
+    };
+    let index = builder.add_input::<u32>();
+    let value = builder.add_input_by_type(typ);
+
+    let array = array_write_trace(builder.state(), &array, index, value);
+    builder.add_output(BinaryRepr::Array(array));
+
+    builder.build().expect("circuit should be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{StaticValueType, Value};
+
+    use super::*;
+
+    const ARRAY: [u16; 5] = [3, 1, 4, 1, 5];
+
+    /// Indices at the ends of the array, in the padding of the multiplexer tree, and out of
+    /// range of the tree.
+    const INDICES: [u32; 5] = [0, 2, 4, 6, 1 << 20];
+
+    #[test]
+    fn test_array_read() {
+        let circ = build_array_read(u16::value_type(), ARRAY.len());
+
+        for index in INDICES {
+            let output = circ
+                .evaluate(&[ARRAY.into(), index.into()])
+                .unwrap()
+                .pop()
+                .unwrap();
+
+            let expected = ARRAY.get(index as usize).copied().unwrap_or_default();
+            assert_eq!(output, Value::from(expected));
+        }
+    }
+
+    #[test]
+    fn test_array_write() {
+        let circ = build_array_write(u16::value_type(), ARRAY.len());
+
+        for index in INDICES {
+            let output = circ
+                .evaluate(&[ARRAY.into(), index.into(), 9u16.into()])
+                .unwrap()
+                .pop()
+                .unwrap();
+
+            let mut expected = ARRAY;
+            if let Some(elem) = expected.get_mut(index as usize) {
+                *elem = 9;
+            }
+            assert_eq!(output, Value::from(expected));
+        }
+    }
+}
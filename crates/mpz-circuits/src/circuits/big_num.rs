@@ -1,15 +1,292 @@
 //! Circuits for arithmetic with large numbers.
+//!
+//! Numbers are encoded as arrays of bytes in big-endian order, so a 256-bit number is a
+//! `[u8; 32]`. The moduli are public constants, which lets the builder fold away most of the
+//! gates involving them.
 
 use std::cell::RefCell;
 
 use itybity::IntoBits;
 
 use crate::{
-    ops::binary::{switch_nbit, wrapping_add_nbit, wrapping_sub_nbit},
-    types::U8,
-    BuilderState, Node, Tracer,
+    ops::binary::{select_nbit, wrapping_add_nbit, wrapping_sub_nbit},
+    types::{Bit, U8},
+    BuilderState, Circuit, CircuitBuilder, Feed, Node, Tracer,
 };
 
+/// Returns the bits of a big-endian number in LSB0 order.
+fn to_bits<const N: usize>(value: [Tracer<'_, U8>; N]) -> Vec<Node<Feed>> {
+    value
+        .into_iter()
+        .rev()
+        .flat_map(|byte| byte.to_inner().nodes())
+        .collect()
+}
+
+/// Returns the bits of a constant big-endian number in LSB0 order.
+fn const_to_bits<const N: usize>(value: [u8; N]) -> Vec<Node<Feed>> {
+    value
+        .into_iter()
+        .rev()
+        .flat_map(|byte| byte.into_iter_lsb0())
+        .map(|bit| Node::new(bit as usize))
+        .collect()
+}
+
+/// Returns the big-endian number with the provided bits in LSB0 order.
+fn from_bits<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    bits: &[Node<Feed>],
+) -> [Tracer<'a, U8>; N] {
+    assert_eq!(bits.len(), N * 8);
+
+    let mut bytes: [_; N] = std::array::from_fn(|i| {
+        Tracer::new(
+            state,
+            U8::new(bits[i * 8..(i + 1) * 8].try_into().expect("byte is 8 bits")),
+        )
+    });
+
+    bytes.reverse();
+    bytes
+}
+
+/// Add two numbers.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The first number encoded as an array of bytes in big-endian order.
+/// * `b` - The second number encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// (a + b) % 2^(8 * N), and the carry bit.
+pub fn nbyte_add_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+) -> ([Tracer<'a, U8>; N], Tracer<'a, Bit>) {
+    let mut a_bits = to_bits(a);
+    let mut b_bits = to_bits(b);
+
+    // Tack on an extra bit to absorb the carry
+    a_bits.push(Node::new(0));
+    b_bits.push(Node::new(0));
+
+    let mut sum = wrapping_add_nbit(&mut state.borrow_mut(), &a_bits, &b_bits);
+    let carry = sum.pop().expect("sum has a carry bit");
+
+    (
+        from_bits(state, &sum),
+        Tracer::new(state, Bit::new([carry])),
+    )
+}
+
+/// Multiply two numbers.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The first number encoded as an array of bytes in big-endian order.
+/// * `b` - The second number encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// The high and low halves of a * b, each encoded as an array of bytes in big-endian order.
+pub fn nbyte_mul_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+) -> ([Tracer<'a, U8>; N], [Tracer<'a, U8>; N]) {
+    let bit_len = N * 8;
+    let a_bits = to_bits(a);
+    let b_bits = to_bits(b);
+
+    let product = {
+        let mut state = state.borrow_mut();
+        let zero = state.get_const_zero();
+
+        // Schoolbook multiplication, adding a * b_i * 2^i for every bit of b. After adding the
+        // i-th partial product the accumulator is less than 2^(N * 8 + i), so each addition only
+        // needs to span N * 8 + 1 bits.
+        let mut acc = vec![zero; 2 * bit_len];
+        for (i, b_i) in b_bits.into_iter().enumerate() {
+            let mut partial = a_bits
+                .iter()
+                .map(|a_j| state.add_and_gate(*a_j, b_i))
+                .collect::<Vec<_>>();
+            partial.push(zero);
+
+            let sum = wrapping_add_nbit(&mut state, &acc[i..=i + bit_len], &partial);
+            acc[i..=i + bit_len].copy_from_slice(&sum);
+        }
+
+        acc
+    };
+
+    let (low, high) = product.split_at(bit_len);
+
+    (from_bits(state, high), from_bits(state, low))
+}
+
+/// Reduces a number modulo a constant modulus.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `high` - The high half of the number encoded as an array of bytes in big-endian order.
+/// * `low` - The low half of the number encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// (high * 2^(8 * N) + low) % modulus
+///
+/// # Panics
+///
+/// Panics if the modulus is zero.
+pub fn nbyte_reduce_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    high: [Tracer<'a, U8>; N],
+    low: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    assert!(modulus.iter().any(|byte| *byte != 0), "modulus is zero");
+
+    let bit_len = N * 8;
+    let mut value = to_bits(low);
+    value.extend(to_bits(high));
+
+    // The partial remainder is always less than the modulus, but it needs an extra bit after it
+    // is shifted.
+    let mut modulus = const_to_bits(modulus);
+    modulus.push(Node::new(0));
+
+    let rem = {
+        let mut state = state.borrow_mut();
+
+        // Restoring long division, shifting in the bits of the value starting from the most
+        // significant one.
+        let mut rem = vec![state.get_const_zero(); bit_len];
+        for bit in value.into_iter().rev() {
+            let mut shifted = Vec::with_capacity(bit_len + 1);
+            shifted.push(bit);
+            shifted.extend_from_slice(&rem);
+
+            let (diff, underflow) = wrapping_sub_nbit(&mut state, &shifted, &modulus);
+
+            // if shifted < modulus { shifted } else { shifted - modulus }
+            rem = select_nbit(&mut state, &diff[..bit_len], &shifted[..bit_len], underflow);
+        }
+
+        rem
+    };
+
+    from_bits(state, &rem)
+}
+
+/// Multiply two numbers modulo a constant modulus.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The first number encoded as an array of bytes in big-endian order.
+/// * `b` - The second number encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// (a * b) % modulus
+///
+/// # Panics
+///
+/// Panics if the modulus is zero.
+pub fn nbyte_mul_mod_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    let (high, low) = nbyte_mul_trace(state, a, b);
+    nbyte_reduce_trace(state, high, low, modulus)
+}
+
+/// Builds a circuit to add two 256-bit numbers modulo a constant modulus.
+///
+/// The summands must be in the range [0, modulus).
+///
+/// # Returns a circuit with the following signature:
+///
+/// `fn(a: [u8; 32], b: [u8; 32]) -> [u8; 32]`
+pub fn build_add_mod_256(modulus: [u8; 32]) -> Circuit {
+    let builder = CircuitBuilder::new();
+    let a = builder.add_array_input::<u8, 32>();
+    let b = builder.add_array_input::<u8, 32>();
+
+    let sum = nbyte_add_mod_trace(builder.state(), a, b, modulus);
+
+    builder.add_output(sum);
+    builder.build().expect("circuit is valid")
+}
+
+/// Builds a circuit to multiply two 256-bit numbers.
+///
+/// # Returns a circuit with the following signature:
+///
+/// `fn(a: [u8; 32], b: [u8; 32]) -> [u8; 64]`
+pub fn build_mul_256() -> Circuit {
+    let builder = CircuitBuilder::new();
+    let a = builder.add_array_input::<u8, 32>();
+    let b = builder.add_array_input::<u8, 32>();
+
+    let (high, low) = nbyte_mul_trace(builder.state(), a, b);
+
+    builder.add_output(high.into_iter().chain(low).collect::<Vec<_>>());
+    builder.build().expect("circuit is valid")
+}
+
+/// Builds a circuit to reduce a 512-bit number modulo a constant 256-bit modulus.
+///
+/// # Returns a circuit with the following signature:
+///
+/// `fn(value: [u8; 64]) -> [u8; 32]`
+///
+/// # Panics
+///
+/// Panics if the modulus is zero.
+pub fn build_reduce_512(modulus: [u8; 32]) -> Circuit {
+    let builder = CircuitBuilder::new();
+    let value = builder.add_array_input::<u8, 64>();
+
+    let high: [_; 32] = value[..32].try_into().expect("high half is 32 bytes");
+    let low: [_; 32] = value[32..].try_into().expect("low half is 32 bytes");
+    let rem = nbyte_reduce_trace(builder.state(), high, low, modulus);
+
+    builder.add_output(rem);
+    builder.build().expect("circuit is valid")
+}
+
+/// Builds a circuit to multiply two 256-bit numbers modulo a constant modulus.
+///
+/// # Returns a circuit with the following signature:
+///
+/// `fn(a: [u8; 32], b: [u8; 32]) -> [u8; 32]`
+///
+/// # Panics
+///
+/// Panics if the modulus is zero.
+pub fn build_mul_mod_256(modulus: [u8; 32]) -> Circuit {
+    let builder = CircuitBuilder::new();
+    let a = builder.add_array_input::<u8, 32>();
+    let b = builder.add_array_input::<u8, 32>();
+
+    let product = nbyte_mul_mod_trace(builder.state(), a, b, modulus);
+
+    builder.add_output(product);
+    builder.build().expect("circuit is valid")
+}
+
 /// Add two numbers modulo a constant modulus.
 ///
 /// This circuit assumes that the summands are in the range [0, modulus).
@@ -61,7 +338,7 @@ pub fn nbyte_add_mod_trace<'a, const N: usize>(
     let (rem, underflow) = wrapping_sub_nbit(&mut state.borrow_mut(), &sum, &modulus_bits);
 
     // if sum < modulus { sum } else { sum - modulus }
-    let mut sum_reduced = switch_nbit(&mut state.borrow_mut(), &rem, &sum, underflow);
+    let mut sum_reduced = select_nbit(&mut state.borrow_mut(), &rem, &sum, underflow);
 
     // Pop off the extra bit
     sum_reduced.pop();
@@ -82,11 +359,40 @@ pub fn nbyte_add_mod_trace<'a, const N: usize>(
 #[cfg(test)]
 mod tests {
     use mpz_circuits_macros::evaluate;
-
-    use crate::CircuitBuilder;
+    use num_bigint::BigUint;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
 
     use super::*;
 
+    /// The order of the P-256 base field.
+    const P256_MODULUS: [u8; 32] = [
+        0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff,
+    ];
+
+    fn to_bytes<const N: usize>(value: &BigUint) -> [u8; N] {
+        let bytes = value.to_bytes_be();
+        let mut out = [0u8; N];
+        out[N - bytes.len()..].copy_from_slice(&bytes);
+        out
+    }
+
+    /// Returns random numbers less than the modulus, including the edge cases.
+    fn sample(modulus: &BigUint) -> Vec<BigUint> {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let mut values = vec![
+            BigUint::from(0u8),
+            BigUint::from(1u8),
+            modulus - 1u8,
+            modulus - 2u8,
+        ];
+        values.extend((0..4).map(|_| BigUint::from_bytes_be(&rng.gen::<[u8; 32]>()) % modulus));
+        values
+    }
+
     #[test]
     fn test_nbyte_add_mod() {
         let builder = CircuitBuilder::new();
@@ -112,4 +418,115 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_nbyte_add() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 32>();
+        let b = builder.add_array_input::<u8, 32>();
+
+        let (sum, carry) = nbyte_add_trace(builder.state(), a, b);
+
+        builder.add_output(sum.map(|v| v.to_inner()));
+        builder.add_output(carry.to_inner());
+
+        let circ = builder.build().unwrap();
+
+        let max = BigUint::from(1u8) << 256;
+        for a in sample(&max) {
+            for b in sample(&max) {
+                let expected = &a + &b;
+
+                let (sum, carry): ([u8; 32], bool) = evaluate!(
+                    circ,
+                    fn(to_bytes::<32>(&a), to_bytes::<32>(&b)) -> ([u8; 32], bool)
+                )
+                .unwrap();
+
+                assert_eq!(sum, to_bytes::<32>(&(&expected % &max)));
+                assert_eq!(carry, expected >= max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_mod_256() {
+        let circ = build_add_mod_256(P256_MODULUS);
+        let modulus = BigUint::from_bytes_be(&P256_MODULUS);
+
+        for a in sample(&modulus) {
+            for b in sample(&modulus) {
+                let sum: [u8; 32] = evaluate!(
+                    circ,
+                    fn(to_bytes::<32>(&a), to_bytes::<32>(&b)) -> [u8; 32]
+                )
+                .unwrap();
+
+                assert_eq!(sum, to_bytes::<32>(&((&a + &b) % &modulus)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_256() {
+        let circ = build_mul_256();
+        let max = BigUint::from(1u8) << 256;
+
+        for a in sample(&max) {
+            for b in sample(&max) {
+                let product: [u8; 64] = evaluate!(
+                    circ,
+                    fn(to_bytes::<32>(&a), to_bytes::<32>(&b)) -> [u8; 64]
+                )
+                .unwrap();
+
+                assert_eq!(product, to_bytes::<64>(&(&a * &b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce_512() {
+        let circ = build_reduce_512(P256_MODULUS);
+        let modulus = BigUint::from_bytes_be(&P256_MODULUS);
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let max = (BigUint::from(1u8) << 512) - 1u8;
+        let mut values = vec![BigUint::from(0u8), modulus.clone(), max];
+        values.extend((0..4).map(|_| {
+            let bytes: Vec<u8> = (0..64).map(|_| rng.gen()).collect();
+            BigUint::from_bytes_be(&bytes)
+        }));
+
+        for value in values {
+            let rem: [u8; 32] = evaluate!(circ, fn(to_bytes::<64>(&value)) -> [u8; 32]).unwrap();
+
+            assert_eq!(rem, to_bytes::<32>(&(&value % &modulus)));
+        }
+    }
+
+    #[test]
+    fn test_mul_mod_256() {
+        let circ = build_mul_mod_256(P256_MODULUS);
+        let modulus = BigUint::from_bytes_be(&P256_MODULUS);
+
+        for a in sample(&modulus) {
+            for b in sample(&modulus) {
+                let product: [u8; 32] = evaluate!(
+                    circ,
+                    fn(to_bytes::<32>(&a), to_bytes::<32>(&b)) -> [u8; 32]
+                )
+                .unwrap();
+
+                assert_eq!(product, to_bytes::<32>(&((&a * &b) % &modulus)));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus is zero")]
+    fn test_reduce_zero_modulus() {
+        build_reduce_512([0u8; 32]);
+    }
 }
@@ -0,0 +1,241 @@
+//! Circuits for variable-length byte strings.
+//!
+//! Circuits have a fixed shape, so a byte string whose length is only known at runtime is
+//! represented by a [`VarBytes`] value: an array of `max_len` bytes zero-padded at the end, along
+//! with the actual length as a `u32`. This way a circuit can be built once for the maximum length
+//! and reused for any shorter string.
+//!
+//! Padding provided by a party can not be trusted to be zero, so circuits should mask the tail of
+//! the array with [`bytes_mask_trace`] before using it. Masking costs about 32 AND gates per byte
+//! to compare the index with the length, plus one per bit.
+
+use std::cell::RefCell;
+
+use crate::{
+    types::{BinaryRepr, TypeError, Value, ValueType, U32, U8},
+    BuilderState, Circuit, CircuitBuilder, Feed, Node, Tracer,
+};
+
+/// A variable-length byte string of up to `max_len` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VarBytes {
+    max_len: usize,
+}
+
+impl VarBytes {
+    /// Creates a new variable-length byte string type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is zero or does not fit in a `u32`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_len` - The maximum length of the string in bytes.
+    pub fn new(max_len: usize) -> Self {
+        assert!(max_len > 0, "maximum length must not be zero");
+        assert!(
+            u32::try_from(max_len).is_ok(),
+            "maximum length must fit in a u32"
+        );
+
+        Self { max_len }
+    }
+
+    /// Returns the maximum length of the string in bytes.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Returns the types of the values representing the string: the padded bytes followed by
+    /// the length.
+    pub fn value_types(&self) -> [ValueType; 2] {
+        [ValueType::new_array::<u8>(self.max_len), ValueType::U32]
+    }
+
+    /// Adds a variable-length byte string input to the circuit.
+    ///
+    /// This adds two inputs to the circuit, see [`VarBytes::value_types`].
+    ///
+    /// # Returns
+    ///
+    /// The padded bytes and the length.
+    pub fn add_input<'a>(&self, builder: &'a CircuitBuilder) -> (Vec<U8>, Tracer<'a, U32>) {
+        let bytes = builder
+            .add_vec_input::<u8>(self.max_len)
+            .into_iter()
+            .map(|byte| byte.to_inner())
+            .collect();
+        let len = builder.add_input::<u32>();
+
+        (bytes, len)
+    }
+
+    /// Encodes a byte string into the values representing it, see [`VarBytes::value_types`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte string.
+    pub fn encode(&self, bytes: &[u8]) -> Result<[Value; 2], TypeError> {
+        if bytes.len() > self.max_len {
+            return Err(TypeError::InvalidByteLength {
+                expected: self.max_len,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut padded = bytes.to_vec();
+        padded.resize(self.max_len, 0);
+
+        Ok([padded.into(), Value::U32(bytes.len() as u32)])
+    }
+
+    /// Decodes a byte string from the values representing it, see [`VarBytes::value_types`].
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The padded bytes and the length.
+    pub fn decode(&self, values: [Value; 2]) -> Result<Vec<u8>, TypeError> {
+        let [bytes, len] = values;
+
+        let [expected, _] = self.value_types();
+        if bytes.value_type() != expected {
+            return Err(TypeError::UnexpectedType {
+                expected,
+                actual: bytes.value_type(),
+            });
+        }
+
+        let mut bytes: Vec<u8> = bytes.try_into()?;
+        let len = u32::try_from(len)? as usize;
+
+        if len > self.max_len {
+            return Err(TypeError::InvalidByteLength {
+                expected: self.max_len,
+                actual: len,
+            });
+        }
+
+        bytes.truncate(len);
+
+        Ok(bytes)
+    }
+}
+
+/// Returns a node which is 1 if the constant `c` is less than `x`.
+fn const_lt(state: &mut BuilderState, c: u32, x: &[Node<Feed>; 32]) -> Node<Feed> {
+    // Compares from the least significant bit up, `None` being a constant 0.
+    let mut lt: Option<Node<Feed>> = None;
+    for (i, bit) in x.iter().enumerate() {
+        lt = if (c >> i) & 1 == 1 {
+            // c_i = 1: x is only greater if x_i = 1 and the lower bits are greater.
+            lt.map(|lt| state.add_and_gate(*bit, lt))
+        } else {
+            // c_i = 0: x is greater if x_i = 1, or if the lower bits are greater.
+            Some(match lt {
+                Some(lt) => {
+                    let and = state.add_and_gate(*bit, lt);
+                    let xor = state.add_xor_gate(*bit, lt);
+                    state.add_xor_gate(xor, and)
+                }
+                None => *bit,
+            })
+        };
+    }
+
+    lt.unwrap_or_else(|| state.get_const_zero())
+}
+
+/// Masks the bytes of a variable-length byte string past its length.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `bytes` - The padded bytes of the string.
+/// * `len` - The length of the string.
+///
+/// # Returns
+///
+/// The bytes of the string, with every byte at an index greater than or equal to `len` set to
+/// zero.
+pub fn bytes_mask_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    bytes: &[U8],
+    len: Tracer<'a, U32>,
+) -> Vec<U8> {
+    let len = len.to_inner().nodes();
+
+    let mut state = state.borrow_mut();
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| {
+            let keep = const_lt(&mut state, i as u32, &len);
+            U8::new(byte.nodes().map(|node| state.add_and_gate(node, keep)))
+        })
+        .collect()
+}
+
+/// Builds a circuit which masks the bytes of a variable-length byte string past its length.
+///
+/// The circuit has the following signature:
+///
+/// `fn(bytes: [u8; max_len], len: u32) -> [u8; max_len]`
+///
+/// # Arguments
+///
+/// * `max_len` - The maximum length of the string in bytes.
+pub fn build_bytes_mask(max_len: usize) -> Circuit {
+    let builder = CircuitBuilder::new();
+
+    let (bytes, len) = VarBytes::new(max_len).add_input(&builder);
+
+    let bytes = bytes_mask_trace(builder.state(), &bytes, len);
+    builder.add_output(BinaryRepr::from(bytes));
+
+    builder.build().expect("circuit should be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_var_bytes_encode() {
+        let ty = VarBytes::new(8);
+
+        let values = ty.encode(b"hello").unwrap();
+        assert_eq!(values[0], Value::from(*b"hello\0\0\0"));
+        assert_eq!(ty.decode(values).unwrap(), b"hello");
+
+        assert!(matches!(
+            ty.encode(b"too long!"),
+            Err(TypeError::InvalidByteLength {
+                expected: 8,
+                actual: 9
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bytes_mask() {
+        let circ = build_bytes_mask(6);
+        let bytes = [1u8, 2, 3, 4, 5, 6];
+
+        // Lengths inside the string, at its end, and past it.
+        for len in [0u32, 1, 3, 5, 6, 7, u32::MAX] {
+            let output = circ
+                .evaluate(&[bytes.into(), len.into()])
+                .unwrap()
+                .pop()
+                .unwrap();
+
+            let mut expected = bytes;
+            expected
+                .iter_mut()
+                .skip(len as usize)
+                .for_each(|byte| *byte = 0);
+            assert_eq!(output, Value::from(expected));
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Pre-built circuits for MPC.
 
+pub mod array;
 pub mod big_num;
+pub mod bytes;
 
 use once_cell::sync::Lazy;
 use std::{cell::RefCell, sync::Arc};
@@ -10,6 +12,9 @@ use crate::{
     BuilderState, Circuit, CircuitBuilder, Tracer,
 };
 
+#[cfg(feature = "sha1")]
+use crate::ops::WrappingAdd;
+
 /// AES-128 circuit.
 ///
 /// The circuit has the following signature:
@@ -32,6 +37,14 @@ pub static SHA256_COMPRESS: Lazy<Arc<Circuit>> = Lazy::new(|| {
     Arc::new(bincode::deserialize(bytes).unwrap())
 });
 
+/// SHA-1 compression circuit.
+///
+/// The circuit has the following signature:
+///
+/// `fn(state: [u32; 5], msg: [u8; 64]) -> [u32; 5]`
+#[cfg(feature = "sha1")]
+pub static SHA1_COMPRESS: Lazy<Arc<Circuit>> = Lazy::new(|| Arc::new(build_sha1_compress()));
+
 /// AES-128 circuit trace.
 ///
 /// This function is a wrapper around the AES-128 circuit that can be used to append
@@ -195,6 +208,271 @@ pub fn sha256_trace<'a>(
     hash.map(|value| Tracer::new(builder_state, value.try_into().unwrap()))
 }
 
+/// The initial state of SHA-1.
+#[cfg(feature = "sha1")]
+pub const SHA1_INITIAL_STATE: [u32; 5] =
+    [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Rotates the bits of a traced `u32` to the left, which requires no gates.
+#[cfg(feature = "sha1")]
+fn rotate_left_u32(value: Tracer<'_, U32>, n: u32) -> Tracer<'_, U32> {
+    let mut nodes = value.to_inner().nodes();
+    // Bits are LSB0, so we rotate right
+    nodes.rotate_right(n as usize);
+
+    Tracer::new(value.state, U32::new(nodes))
+}
+
+/// Builds the SHA-1 compression circuit.
+///
+/// The round functions are written to use a single AND gate per bit:
+///
+/// * `Ch(b, c, d) = d ^ (b & (c ^ d))`
+/// * `Maj(b, c, d) = c ^ ((b ^ c) & (c ^ d))`
+#[cfg(feature = "sha1")]
+fn build_sha1_compress() -> Circuit {
+    let builder = CircuitBuilder::new();
+    let state = builder.add_array_input::<u32, 5>();
+    let msg = builder.add_array_input::<u8, 64>();
+
+    let mut w = msg
+        .chunks_exact(4)
+        .map(|word| Tracer::<U32>::from_be_bytes(word.try_into().expect("word is 4 bytes")))
+        .collect::<Vec<_>>();
+    for t in 16..80 {
+        w.push(rotate_left_u32(
+            w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16],
+            1,
+        ));
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = state;
+    for (t, w) in w.into_iter().enumerate() {
+        let (f, k) = match t {
+            0..=19 => (d ^ (b & (c ^ d)), 0x5a827999u32),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => (c ^ ((b ^ c) & (c ^ d)), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+
+        let temp = rotate_left_u32(a, 5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w);
+
+        e = d;
+        d = c;
+        c = rotate_left_u32(b, 30);
+        b = a;
+        a = temp;
+    }
+
+    let output: [_; 5] = std::array::from_fn(|i| state[i].wrapping_add([a, b, c, d, e][i]));
+
+    builder.add_output(output);
+
+    builder.build().expect("circuit is valid")
+}
+
+/// SHA-1 compression circuit trace.
+///
+/// This function is a wrapper around the SHA-1 compression circuit that can be used to append
+/// it to other circuits.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `state` - The SHA-1 state.
+/// * `msg` - The message to compress.
+///
+/// # Returns
+///
+/// The SHA-1 state after compression.
+#[cfg(feature = "sha1")]
+pub fn sha1_compress_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    state: [Tracer<'a, U32>; 5],
+    msg: [Tracer<'a, U8>; 64],
+) -> [Tracer<'a, U32>; 5] {
+    let mut outputs = builder_state
+        .borrow_mut()
+        .append(&SHA1_COMPRESS, &[state.into(), msg.into()])
+        .expect("sha 1 should append successfully");
+
+    let BinaryRepr::Array(output) = outputs.pop().unwrap() else {
+        panic!("sha 1 should have array output");
+    };
+
+    let output: [_; 5] = output.try_into().unwrap();
+
+    output.map(|value| Tracer::new(builder_state, value.try_into().unwrap()))
+}
+
+/// Builds a circuit to compute the SHA-1 hash of a message.
+///
+/// # Arguments
+///
+/// * `pos` - The number of bytes in the message that have already been hashed.
+/// * `msg_len` - The total length of the hashed message.
+///
+/// # Returns a circuit with the following signature:
+///
+/// `fn(state: [u32; 5], msg: [u8; msg_len]) -> [u8; 20]`
+#[cfg(feature = "sha1")]
+pub fn build_sha1(pos: usize, msg_len: usize) -> Circuit {
+    let builder = CircuitBuilder::new();
+    let mut state = builder.add_array_input::<u32, 5>();
+    let mut msg = builder.add_vec_input::<u8>(msg_len);
+
+    // SHA-1 uses the same padding as SHA-256.
+    let padding = md_padding(pos, msg_len);
+    msg.extend(padding.into_iter().map(|value| builder.get_constant(value)));
+
+    debug_assert!(msg.len() % 64 == 0);
+
+    for block in msg.chunks(64) {
+        state = sha1_compress_trace(
+            builder.state(),
+            state,
+            block.try_into().expect("block is 64 bytes"),
+        );
+    }
+
+    let hash = state
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .collect::<Vec<_>>();
+    let hash: [_; 20] = hash.try_into().expect("hash is 20 bytes");
+
+    builder.add_output(hash);
+
+    builder.build().expect("circuit is valid")
+}
+
+/// SHA-1 circuit trace.
+///
+/// This function is a wrapper around the SHA-1 circuit that can be used
+/// to compute the hash of an arbitrary length message.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `state` - The SHA-1 state.
+/// * `pos` - The number of bytes processed in the current state.
+/// * `msg` - The message to hash.
+#[cfg(feature = "sha1")]
+pub fn sha1_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    state: [Tracer<'a, U32>; 5],
+    pos: usize,
+    msg: &[Tracer<'a, U8>],
+) -> [Tracer<'a, U8>; 20] {
+    let circ = build_sha1(pos, msg.len());
+    let mut outputs = builder_state
+        .borrow_mut()
+        .append(&circ, &[state.into(), msg.to_vec().into()])
+        .expect("circuit should append successfully");
+
+    let BinaryRepr::Array(hash) = outputs.pop().unwrap() else {
+        panic!("circuit should have array output");
+    };
+
+    let hash: [_; 20] = hash.try_into().expect("hash should be 20 bytes");
+
+    hash.map(|value| Tracer::new(builder_state, value.try_into().unwrap()))
+}
+
+/// Returns the Merkle–Damgård padding of a message, as used by SHA-1 and SHA-256.
+///
+/// # Arguments
+///
+/// * `pos` - The number of bytes in the message that have already been hashed.
+/// * `msg_len` - The length of the message.
+#[cfg(feature = "sha1")]
+fn md_padding(pos: usize, msg_len: usize) -> Vec<u8> {
+    let bit_len = ((msg_len + pos) * 8) as u64;
+
+    // The message is padded to a multiple of 64 bytes, with at least 9 bytes of padding.
+    let padded_len = (msg_len + 9).div_ceil(64) * 64;
+
+    // A single '1' bit, then '0' bits, then the length in bits as a 64-bit big-endian integer.
+    let mut padding = vec![0x80];
+    padding.resize(padded_len - msg_len - 8, 0);
+    padding.extend_from_slice(&bit_len.to_be_bytes());
+
+    padding
+}
+
+/// Reference SHA-1 compression function implementation.
+///
+/// # Arguments
+///
+/// * `state` - The SHA-1 state.
+/// * `msg` - The message to compress.
+#[cfg(feature = "sha1")]
+pub fn sha1_compress(state: [u32; 5], msg: [u8; 64]) -> [u32; 5] {
+    let mut w = [0u32; 80];
+    for (w, word) in w.iter_mut().zip(msg.chunks_exact(4)) {
+        *w = u32::from_be_bytes(word.try_into().unwrap());
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = state;
+    for (t, w) in w.into_iter().enumerate() {
+        let (f, k) = match t {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    let mut state = state;
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e]) {
+        *s = s.wrapping_add(v);
+    }
+    state
+}
+
+/// Reference SHA-1 implementation.
+///
+/// # Arguments
+///
+/// * `state` - The SHA-1 state.
+/// * `pos` - The number of bytes processed in the current state.
+/// * `msg` - The message to hash.
+#[cfg(feature = "sha1")]
+pub fn sha1(mut state: [u32; 5], pos: usize, msg: &[u8]) -> [u8; 20] {
+    let mut msg = msg.to_vec();
+    msg.extend(md_padding(pos, msg.len()));
+
+    for block in msg.chunks_exact(64) {
+        state = sha1_compress(state, block.try_into().unwrap());
+    }
+
+    let mut out: [u8; 20] = [0; 20];
+    for (chunk, v) in out.chunks_exact_mut(4).zip(state.iter()) {
+        chunk.copy_from_slice(&v.to_be_bytes());
+    }
+    out
+}
+
 /// Reference SHA256 compression function implementation.
 ///
 /// # Arguments
@@ -242,13 +520,17 @@ pub fn sha256(mut state: [u32; 8], pos: usize, msg: &[u8]) -> [u8; 32] {
 mod tests {
     use super::*;
 
-    use mpz_circuits_macros::test_circ;
+    use mpz_circuits_macros::{evaluate, test_circ};
 
     static SHA2_INITIAL_STATE: [u32; 8] = [
         0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
         0x5be0cd19,
     ];
 
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
     #[test]
     #[cfg(feature = "aes")]
     fn test_aes128() {
@@ -280,6 +562,73 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_sha256_vectors() {
+        // Circuits cannot take an empty array input, so the empty message is not covered.
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ),
+        ];
+
+        for (msg, expected) in vectors {
+            let circ = build_sha256(0, msg.len());
+            let hash: [u8; 32] = evaluate!(circ, fn(SHA2_INITIAL_STATE, msg) -> [u8; 32]).unwrap();
+
+            assert_eq!(hex(&hash), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha1")]
+    fn test_sha1_compress() {
+        test_circ!(
+            SHA1_COMPRESS,
+            sha1_compress,
+            fn(SHA1_INITIAL_STATE, [69u8; 64]) -> [u32; 5]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sha1")]
+    fn test_sha1() {
+        for len in [5, 55, 56, 64, 100] {
+            let msg = vec![42u8; len];
+            let circ = build_sha1(0, len);
+            let reference = |state, msg| sha1(state, 0, msg);
+
+            test_circ!(circ, reference, fn(SHA1_INITIAL_STATE, msg.as_slice()) -> [u8; 20]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha1")]
+    fn test_sha1_vectors() {
+        // Circuits cannot take an empty array input, so the empty message is not covered.
+        let vectors: [(&[u8], &str); 2] = [
+            (b"abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            ),
+        ];
+
+        for (msg, expected) in vectors {
+            assert_eq!(hex(&sha1(SHA1_INITIAL_STATE, 0, msg)), expected);
+
+            let circ = build_sha1(0, msg.len());
+            let hash: [u8; 20] = evaluate!(circ, fn(SHA1_INITIAL_STATE, msg) -> [u8; 20]).unwrap();
+
+            assert_eq!(hex(&hash), expected);
+        }
+    }
+
     #[test]
     #[cfg(feature = "sha2")]
     fn test_sha256() {
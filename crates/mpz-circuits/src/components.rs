@@ -20,6 +20,8 @@ pub enum Gate {
     },
     /// Inverter gate.
     Inv { x: Node<Sink>, z: Node<Feed> },
+    /// Constant gate, which outputs a fixed bit.
+    Const { z: Node<Feed>, value: bool },
 }
 
 impl Gate {
@@ -29,15 +31,17 @@ impl Gate {
             Gate::Xor { .. } => GateType::Xor,
             Gate::And { .. } => GateType::And,
             Gate::Inv { .. } => GateType::Inv,
+            Gate::Const { .. } => GateType::Const,
         }
     }
 
     /// Returns the x input of the gate.
-    pub fn x(&self) -> Node<Sink> {
+    pub fn x(&self) -> Option<Node<Sink>> {
         match self {
-            Gate::Xor { x, .. } => *x,
-            Gate::And { x, .. } => *x,
-            Gate::Inv { x, .. } => *x,
+            Gate::Xor { x, .. } => Some(*x),
+            Gate::And { x, .. } => Some(*x),
+            Gate::Inv { x, .. } => Some(*x),
+            Gate::Const { .. } => None,
         }
     }
 
@@ -47,6 +51,7 @@ impl Gate {
             Gate::Xor { y, .. } => Some(*y),
             Gate::And { y, .. } => Some(*y),
             Gate::Inv { .. } => None,
+            Gate::Const { .. } => None,
         }
     }
 
@@ -56,6 +61,7 @@ impl Gate {
             Gate::Xor { z, .. } => *z,
             Gate::And { z, .. } => *z,
             Gate::Inv { z, .. } => *z,
+            Gate::Const { z, .. } => *z,
         }
     }
 
@@ -77,6 +83,9 @@ impl Gate {
                 x.id -= offset;
                 z.id -= offset;
             }
+            Gate::Const { z, .. } => {
+                z.id -= offset;
+            }
         }
     }
 }
@@ -90,6 +99,8 @@ pub enum GateType {
     And,
     /// Inverter gate.
     Inv,
+    /// Constant gate.
+    Const,
 }
 
 /// A feed in a circuit.
@@ -105,6 +116,7 @@ pub struct Sink;
 /// A node in a circuit.
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
 pub struct Node<T> {
     pub(crate) id: usize,
     _pd: std::marker::PhantomData<T>,
@@ -0,0 +1,187 @@
+use std::io::Write;
+
+use crate::{components::Gate, Circuit};
+
+impl Circuit {
+    /// Writes the circuit in Bristol-fashion format.
+    ///
+    /// See `https://homes.esat.kuleuven.be/~nsmart/MPC/` for more information.
+    ///
+    /// Input wires are numbered in the order of the circuit inputs, followed by the gate outputs
+    /// in the order of the gates. The format requires the outputs to be the last wires of the
+    /// circuit, so each output bit is copied onto a new wire with an `EQW` gate.
+    ///
+    /// The circuit is written line by line, so `writer` should be buffered.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the circuit to.
+    pub fn to_bristol(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let input_len: usize = self.inputs.iter().map(|input| input.len()).sum();
+        let output_len: usize = self.outputs.iter().map(|output| output.len()).sum();
+
+        let gate_count = self.gates.len() + output_len;
+        let wire_count = input_len + gate_count;
+
+        // Maps node ids to wire numbers.
+        let mut wires = vec![0; self.feed_count];
+        let mut next_wire = 0;
+        for node in self.inputs.iter().flat_map(|input| input.iter()) {
+            wires[node.id()] = next_wire;
+            next_wire += 1;
+        }
+
+        writeln!(writer, "{} {}", gate_count, wire_count)?;
+        write!(writer, "{}", self.inputs.len())?;
+        for input in &self.inputs {
+            write!(writer, " {}", input.len())?;
+        }
+        writeln!(writer)?;
+        write!(writer, "{}", self.outputs.len())?;
+        for output in &self.outputs {
+            write!(writer, " {}", output.len())?;
+        }
+        writeln!(writer)?;
+        writeln!(writer)?;
+
+        for gate in &self.gates {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    wires[z.id()] = next_wire;
+                    writeln!(
+                        writer,
+                        "2 1 {} {} {} XOR",
+                        wires[x.id()],
+                        wires[y.id()],
+                        next_wire
+                    )?;
+                }
+                Gate::And { x, y, z } => {
+                    wires[z.id()] = next_wire;
+                    writeln!(
+                        writer,
+                        "2 1 {} {} {} AND",
+                        wires[x.id()],
+                        wires[y.id()],
+                        next_wire
+                    )?;
+                }
+                Gate::Inv { x, z } => {
+                    wires[z.id()] = next_wire;
+                    writeln!(writer, "1 1 {} {} INV", wires[x.id()], next_wire)?;
+                }
+                Gate::Const { z, value } => {
+                    wires[z.id()] = next_wire;
+                    writeln!(writer, "1 1 {} {} EQ", *value as u8, next_wire)?;
+                }
+            }
+            next_wire += 1;
+        }
+
+        // Copy the outputs onto the last wires. Outputs may be input wires, or wires
+        // which are used more than once, so they can not be renumbered in place.
+        for node in self.outputs.iter().flat_map(|output| output.iter()) {
+            writeln!(writer, "1 1 {} {} EQW", wires[node.id()], next_wire)?;
+            next_wire += 1;
+        }
+
+        debug_assert_eq!(next_wire, wire_count);
+
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ops::WrappingAdd, CircuitBuilder};
+
+    #[test]
+    fn test_to_bristol() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<bool>();
+        let b = builder.add_input::<bool>();
+        builder.add_output(a & b);
+        builder.add_output(a);
+        let circ = builder.build().unwrap();
+
+        let mut bytes = Vec::new();
+        circ.to_bristol(&mut bytes).unwrap();
+
+        let expected = "\
+3 5
+2 1 1
+2 1 1
+
+2 1 0 1 2 AND
+1 1 2 3 EQW
+1 1 0 4 EQW
+";
+
+        assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "parse")]
+    fn test_to_bristol_round_trip() {
+        use mpz_circuits_macros::evaluate;
+
+        use crate::{types::ValueType, Circuit};
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = a.wrapping_add(b);
+        builder.add_output(c ^ a);
+        let circ = builder.build().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("circuit.txt");
+        let file = std::fs::File::create(&path).unwrap();
+        circ.to_bristol(std::io::BufWriter::new(file)).unwrap();
+
+        let parsed = Circuit::parse(
+            path.to_str().unwrap(),
+            &[ValueType::U8, ValueType::U8],
+            &[ValueType::U8],
+        )
+        .unwrap();
+
+        for (a, b) in [(1u8, 2u8), (200, 100), (255, 255)] {
+            let expected = evaluate!(circ, fn(a, b) -> u8).unwrap();
+            let output = evaluate!(parsed, fn(a, b) -> u8).unwrap();
+
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parse")]
+    fn test_to_bristol_constants_round_trip() {
+        use crate::{types::ValueType, Circuit};
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        builder.add_output(a & builder.get_constant(0x0fu8));
+        builder.add_output(builder.get_constant(0xa5u8));
+        let circ = builder.build().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("circuit.txt");
+        let file = std::fs::File::create(&path).unwrap();
+        circ.to_bristol(std::io::BufWriter::new(file)).unwrap();
+
+        let parsed = Circuit::parse(
+            path.to_str().unwrap(),
+            &[ValueType::U8],
+            &[ValueType::U8, ValueType::U8],
+        )
+        .unwrap();
+
+        for a in [0u8, 0x3c, 0xff] {
+            let expected = circ.evaluate(&[a.into()]).unwrap();
+            let output = parsed.evaluate(&[a.into()]).unwrap();
+
+            assert_eq!(output, expected);
+        }
+    }
+}
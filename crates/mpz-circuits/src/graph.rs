@@ -0,0 +1,289 @@
+//! Export of circuits as graphs, for visualization and debugging.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{components::GateType, Circuit};
+
+/// Options for exporting a circuit as a graph.
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+    max_gates: Option<usize>,
+}
+
+impl GraphOptions {
+    /// Creates new options which export the whole circuit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of gates to export.
+    ///
+    /// Gates are exported in topological order, so the exported graph is a prefix of the
+    /// circuit. Outputs which depend on gates past the cap are omitted.
+    pub fn max_gates(mut self, max_gates: usize) -> Self {
+        self.max_gates = Some(max_gates);
+        self
+    }
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    /// A bit of a circuit input.
+    Input {
+        input: usize,
+        bit: usize,
+    },
+    /// A feed which is not produced by an input or a gate, eg. a constant.
+    Feed(usize),
+    Gate(GateType),
+    /// A bit of a circuit output.
+    Output {
+        output: usize,
+        bit: usize,
+    },
+}
+
+impl NodeKind {
+    fn label(&self) -> String {
+        match self {
+            NodeKind::Input { input, bit } => format!("in{input}[{bit}]"),
+            NodeKind::Feed(id) => format!("feed {id}"),
+            NodeKind::Gate(GateType::And) => "AND".to_string(),
+            NodeKind::Gate(GateType::Xor) => "XOR".to_string(),
+            NodeKind::Gate(GateType::Inv) => "INV".to_string(),
+            NodeKind::Gate(GateType::Const) => "CONST".to_string(),
+            NodeKind::Output { output, bit } => format!("out{output}[{bit}]"),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            NodeKind::Input { .. } => "input",
+            NodeKind::Feed(_) => "feed",
+            NodeKind::Gate(_) => "gate",
+            NodeKind::Output { .. } => "output",
+        }
+    }
+}
+
+/// Intermediate representation of an exported circuit.
+struct Graph {
+    nodes: Vec<(String, NodeKind)>,
+    edges: Vec<(String, String)>,
+    gate_count: usize,
+    total_gate_count: usize,
+}
+
+impl Graph {
+    fn new(circ: &Circuit, opts: &GraphOptions) -> Self {
+        let total_gate_count = circ.gates.len();
+        let gate_count = opts
+            .max_gates
+            .map_or(total_gate_count, |max| max.min(total_gate_count));
+
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        // Maps a feed id to the id of the graph node which produces it.
+        let mut producers: HashMap<usize, String> = HashMap::new();
+
+        for (input, repr) in circ.inputs.iter().enumerate() {
+            for (bit, node) in repr.iter().enumerate() {
+                let id = format!("in{input}_{bit}");
+                producers.insert(node.id, id.clone());
+                nodes.push((id, NodeKind::Input { input, bit }));
+            }
+        }
+
+        for (idx, gate) in circ.gates[..gate_count].iter().enumerate() {
+            let id = format!("g{idx}");
+            for x in gate.x().into_iter().chain(gate.y()) {
+                let producer = producers.entry(x.id).or_insert_with(|| {
+                    let id = format!("f{}", x.id);
+                    nodes.push((id.clone(), NodeKind::Feed(x.id)));
+                    id
+                });
+                edges.push((producer.clone(), id.clone()));
+            }
+            producers.insert(gate.z().id, id.clone());
+            nodes.push((id, NodeKind::Gate(gate.gate_type())));
+        }
+
+        for (output, repr) in circ.outputs.iter().enumerate() {
+            for (bit, node) in repr.iter().enumerate() {
+                // Skip outputs produced by gates past the cap.
+                let Some(producer) = producers.get(&node.id) else {
+                    continue;
+                };
+                let id = format!("out{output}_{bit}");
+                edges.push((producer.clone(), id.clone()));
+                nodes.push((id, NodeKind::Output { output, bit }));
+            }
+        }
+
+        Self {
+            nodes,
+            edges,
+            gate_count,
+            total_gate_count,
+        }
+    }
+
+    fn is_truncated(&self) -> bool {
+        self.gate_count < self.total_gate_count
+    }
+}
+
+impl Circuit {
+    /// Exports the circuit as a graph in the Graphviz DOT format.
+    ///
+    /// Inputs and outputs are annotated with their index and bit position, eg. `in0[3]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - The export options.
+    pub fn to_dot(&self, opts: &GraphOptions) -> String {
+        let graph = Graph::new(self, opts);
+
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+        if graph.is_truncated() {
+            writeln!(
+                dot,
+                "    // truncated: {} of {} gates shown",
+                graph.gate_count, graph.total_gate_count
+            )
+            .unwrap();
+        }
+
+        for (id, kind) in &graph.nodes {
+            let shape = match kind {
+                NodeKind::Input { .. } | NodeKind::Output { .. } => "box",
+                NodeKind::Feed(_) => "plaintext",
+                NodeKind::Gate(_) => "ellipse",
+            };
+            writeln!(dot, "    {id} [label=\"{}\", shape={shape}];", kind.label()).unwrap();
+        }
+
+        for (from, to) in &graph.edges {
+            writeln!(dot, "    {from} -> {to};").unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the circuit as a graph in JSON format.
+    ///
+    /// The graph is an object with `nodes`, `edges` and `truncated` fields. Each node has an
+    /// `id`, a `kind` (`input`, `feed`, `gate` or `output`) and a `label`, and each edge is a
+    /// pair of node ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - The export options.
+    pub fn to_json_graph(&self, opts: &GraphOptions) -> String {
+        let graph = Graph::new(self, opts);
+
+        let nodes = graph
+            .nodes
+            .iter()
+            .map(|(id, kind)| {
+                format!(
+                    "{{\"id\":\"{id}\",\"kind\":\"{}\",\"label\":\"{}\"}}",
+                    kind.kind(),
+                    kind.label()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let edges = graph
+            .edges
+            .iter()
+            .map(|(from, to)| format!("[\"{from}\",\"{to}\"]"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"nodes\":[{nodes}],\"edges\":[{edges}],\"truncated\":{}}}",
+            graph.is_truncated()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ops::WrappingAdd, CircuitBuilder};
+
+    use super::*;
+
+    fn build_circ() -> Circuit {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<bool>();
+        let b = builder.add_input::<bool>();
+
+        let c = (a & b) ^ b;
+
+        builder.add_output(c);
+
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let circ = build_circ();
+
+        let dot = circ.to_dot(&GraphOptions::new());
+
+        assert_eq!(
+            dot,
+            "digraph circuit {\n    \
+                rankdir=LR;\n    \
+                in0_0 [label=\"in0[0]\", shape=box];\n    \
+                in1_0 [label=\"in1[0]\", shape=box];\n    \
+                g0 [label=\"AND\", shape=ellipse];\n    \
+                g1 [label=\"XOR\", shape=ellipse];\n    \
+                out0_0 [label=\"out0[0]\", shape=box];\n    \
+                in0_0 -> g0;\n    \
+                in1_0 -> g0;\n    \
+                g0 -> g1;\n    \
+                in1_0 -> g1;\n    \
+                g1 -> out0_0;\n\
+            }\n"
+        );
+    }
+
+    #[test]
+    fn test_to_json_graph() {
+        let circ = build_circ();
+
+        let json = circ.to_json_graph(&GraphOptions::new());
+
+        assert!(json.starts_with("{\"nodes\":[{\"id\":\"in0_0\",\"kind\":\"input\""));
+        assert!(json.contains("{\"id\":\"g0\",\"kind\":\"gate\",\"label\":\"AND\"}"));
+        assert!(json.contains("[\"g1\",\"out0_0\"]"));
+        assert!(json.ends_with("\"truncated\":false}"));
+    }
+
+    #[test]
+    fn test_graph_max_gates() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.wrapping_add(b));
+        let circ = builder.build().unwrap();
+
+        let dot = circ.to_dot(&GraphOptions::new().max_gates(4));
+
+        assert!(dot.contains(&format!(
+            "truncated: 4 of {} gates shown",
+            circ.gates().len()
+        )));
+        assert!(dot.contains("g3 "));
+        assert!(!dot.contains("g4 "));
+
+        let json = circ.to_json_graph(&GraphOptions::new().max_gates(4));
+
+        assert!(json.ends_with("\"truncated\":true}"));
+    }
+}
@@ -3,13 +3,21 @@
 
 extern crate self as mpz_circuits;
 
+#[cfg(feature = "arithmetic")]
+pub mod arithmetic;
 mod builder;
 mod circuit;
 pub mod circuits;
 pub(crate) mod components;
+mod export;
+mod graph;
+#[cfg(feature = "mmap")]
+mod mmap;
 pub mod ops;
+pub mod optimize;
 #[cfg(feature = "parse")]
 mod parse;
+pub mod random;
 mod tracer;
 pub mod types;
 
@@ -20,6 +28,9 @@ pub use circuit::{Circuit, CircuitError};
 #[doc(hidden)]
 pub use components::{Feed, Node, Sink};
 pub use components::{Gate, GateType};
+pub use graph::GraphOptions;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapError;
 pub use tracer::Tracer;
 
 pub use once_cell;
@@ -180,3 +191,36 @@ pub use mpz_circuits_macros::evaluate;
 /// test_circ!(circ, wrapping_add, fn(1u8, 2u8) -> u8);
 /// ```
 pub use mpz_circuits_macros::test_circ;
+
+/// A derive macro which implements [`BinaryValue`](crate::types::BinaryValue) for a struct,
+/// mapping each of its fields to one input or output of a circuit.
+///
+/// The fields must be primitive types or arrays of primitive types. Conversions from the struct
+/// into `Vec<Value>` and back are implemented as well.
+///
+/// # Example
+///
+/// ```
+/// use mpz_circuits::{types::BinaryValue, BinaryValue, CircuitBuilder};
+///
+/// #[derive(Debug, PartialEq, BinaryValue)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let builder = CircuitBuilder::new();
+/// let x = builder.add_input::<u32>();
+/// let y = builder.add_input::<u32>();
+///
+/// // Swaps the coordinates.
+/// builder.add_output(y);
+/// builder.add_output(x);
+///
+/// let circ = builder.build().unwrap();
+///
+/// let outputs = circ.evaluate(&Point { x: 1, y: 2 }.into_values()).unwrap();
+///
+/// assert_eq!(Point::from_values(outputs).unwrap(), Point { x: 2, y: 1 });
+/// ```
+pub use mpz_circuits_macros::BinaryValue;
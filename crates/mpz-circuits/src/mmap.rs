@@ -0,0 +1,329 @@
+//! Memory-mapped circuit storage.
+//!
+//! Large precompiled circuits can be written to a file with [`Circuit::write_mmap`] and mapped
+//! back with [`Circuit::open_mmap`]. The gates of a mapped circuit are read directly from the
+//! page cache, so processes which map the same file share a single copy of them. Only the
+//! inputs and outputs of the circuit are loaded into memory.
+//!
+//! The gates are stored in their in-memory representation, so a file can only be mapped on a
+//! platform with the same pointer width and endianness as the one it was written on.
+//!
+//! # Format
+//!
+//! | Offset | Size                       | Contents                                          |
+//! |--------|----------------------------|---------------------------------------------------|
+//! | 0      | 8                          | Magic bytes `mpzcirc\0`                           |
+//! | 8      | 4                          | Format version, little-endian                     |
+//! | 12     | 1                          | Pointer width in bytes                            |
+//! | 13     | 1                          | Endianness, 0 for little-endian and 1 for big     |
+//! | 14     | 2                          | Reserved, zero                                    |
+//! | 16     | 8                          | Gate count, little-endian                         |
+//! | 24     | 8                          | Metadata length, little-endian                    |
+//! | 32     | gate count * 4 * ptr width | Gates                                             |
+//! |        | metadata length            | Inputs, outputs and counts, encoded with bincode  |
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    mem::{align_of, size_of},
+    ops::Deref,
+    path::Path,
+    sync::Arc,
+};
+
+use memmap2::Mmap;
+
+use crate::{circuit::Gates, components::Gate, types::BinaryRepr, Circuit};
+
+const MAGIC: [u8; 8] = *b"mpzcirc\0";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 32;
+
+/// The size of a word in a gate record.
+const WORD: usize = size_of::<usize>();
+/// The size of a gate record.
+const GATE_LEN: usize = 4 * WORD;
+
+// A gate is a tag followed by up to three node ids, or a node id and a bool for constant
+// gates. See the layout of `#[repr(u8)]` enums in the Rust reference.
+const _: () = assert!(size_of::<Gate>() == GATE_LEN);
+const _: () = assert!(align_of::<Gate>() == WORD);
+
+const TAG_XOR: u8 = 0;
+const TAG_AND: u8 = 1;
+const TAG_INV: u8 = 2;
+const TAG_CONST: u8 = 3;
+
+#[cfg(target_endian = "little")]
+const ENDIANNESS: u8 = 0;
+#[cfg(target_endian = "big")]
+const ENDIANNESS: u8 = 1;
+
+/// An error that can occur when mapping a circuit.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum MmapError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("file is not a mapped circuit")]
+    InvalidMagic,
+    #[error("unsupported format version: {0}")]
+    UnsupportedVersion(u32),
+    #[error("circuit was written with pointer width {pointer_width} and endianness {endianness}, which does not match this platform")]
+    UnsupportedPlatform { pointer_width: u8, endianness: u8 },
+    #[error("file is truncated")]
+    Truncated,
+    #[error("invalid gate at index {0}")]
+    InvalidGate(usize),
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(#[from] bincode::Error),
+}
+
+/// Gates of a circuit, borrowed from a memory-mapped file.
+#[derive(Debug, Clone)]
+pub(crate) struct MappedGates {
+    map: Arc<Mmap>,
+    len: usize,
+}
+
+impl Deref for MappedGates {
+    type Target = [Gate];
+
+    fn deref(&self) -> &[Gate] {
+        let bytes = &self.map[HEADER_LEN..HEADER_LEN + self.len * GATE_LEN];
+
+        // SAFETY: The gates were validated when the file was mapped, and the caller of
+        // `Circuit::open_mmap` guarantees that the file is not modified while it is mapped. The
+        // mapping is page aligned and the header length is a multiple of the alignment of a
+        // gate, which is also checked when mapping.
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<Gate>(), self.len) }
+    }
+}
+
+/// Returns the word at the provided index of a gate record.
+fn word(record: &[u8], idx: usize) -> usize {
+    usize::from_ne_bytes(
+        record[idx * WORD..(idx + 1) * WORD]
+            .try_into()
+            .expect("word should fit"),
+    )
+}
+
+/// Checks that a gate record is a valid gate whose nodes are all less than `feed_count`.
+fn validate_gate(record: &[u8], feed_count: usize) -> bool {
+    let in_range = |ids: &[usize]| ids.iter().all(|id| *id < feed_count);
+
+    match record[0] {
+        TAG_XOR | TAG_AND => in_range(&[word(record, 1), word(record, 2), word(record, 3)]),
+        TAG_INV => in_range(&[word(record, 1), word(record, 2)]),
+        TAG_CONST => record[2 * WORD] <= 1 && in_range(&[word(record, 1)]),
+        _ => false,
+    }
+}
+
+/// Sets the word at the provided index of a gate record.
+fn set_word(record: &mut [u8], idx: usize, value: usize) {
+    record[idx * WORD..(idx + 1) * WORD].copy_from_slice(&value.to_ne_bytes());
+}
+
+/// Encodes a gate with its in-memory representation, with zeroed padding.
+fn encode_gate(gate: &Gate) -> [u8; GATE_LEN] {
+    let mut record = [0u8; GATE_LEN];
+
+    match gate {
+        Gate::Xor { x, y, z } | Gate::And { x, y, z } => {
+            record[0] = if matches!(gate, Gate::Xor { .. }) {
+                TAG_XOR
+            } else {
+                TAG_AND
+            };
+            set_word(&mut record, 1, x.id());
+            set_word(&mut record, 2, y.id());
+            set_word(&mut record, 3, z.id());
+        }
+        Gate::Inv { x, z } => {
+            record[0] = TAG_INV;
+            set_word(&mut record, 1, x.id());
+            set_word(&mut record, 2, z.id());
+        }
+        Gate::Const { z, value } => {
+            record[0] = TAG_CONST;
+            set_word(&mut record, 1, z.id());
+            record[2 * WORD] = *value as u8;
+        }
+    }
+
+    record
+}
+
+impl Circuit {
+    /// Writes the circuit in a format which can be mapped with [`Circuit::open_mmap`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the circuit to.
+    pub fn write_mmap<W: Write>(&self, writer: W) -> Result<(), MmapError> {
+        let mut writer = io::BufWriter::new(writer);
+
+        let metadata = bincode::serialize(&(
+            &self.inputs,
+            &self.outputs,
+            self.feed_count,
+            self.and_count,
+            self.xor_count,
+        ))?;
+
+        let mut header = [0u8; HEADER_LEN];
+        header[0..8].copy_from_slice(&MAGIC);
+        header[8..12].copy_from_slice(&VERSION.to_le_bytes());
+        header[12] = WORD as u8;
+        header[13] = ENDIANNESS;
+        header[16..24].copy_from_slice(&(self.gates.len() as u64).to_le_bytes());
+        header[24..32].copy_from_slice(&(metadata.len() as u64).to_le_bytes());
+
+        writer.write_all(&header)?;
+        for gate in self.gates.iter() {
+            writer.write_all(&encode_gate(gate))?;
+        }
+        writer.write_all(&metadata)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Maps a circuit written with [`Circuit::write_mmap`].
+    ///
+    /// The gates are validated once, and are then read directly from the mapping without being
+    /// copied.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to map.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the circuit, or any clone of it, is
+    /// alive.
+    pub unsafe fn open_mmap(path: impl AsRef<Path>) -> Result<Circuit, MmapError> {
+        let file = File::open(path)?;
+        // SAFETY: Upheld by the caller.
+        let map = unsafe { Mmap::map(&file)? };
+
+        let header = map.get(..HEADER_LEN).ok_or(MmapError::Truncated)?;
+        if header[0..8] != MAGIC {
+            return Err(MmapError::InvalidMagic);
+        }
+
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        if version != VERSION {
+            return Err(MmapError::UnsupportedVersion(version));
+        }
+
+        let (pointer_width, endianness) = (header[12], header[13]);
+        if pointer_width as usize != WORD
+            || endianness != ENDIANNESS
+            || map.as_ptr() as usize % align_of::<Gate>() != 0
+        {
+            return Err(MmapError::UnsupportedPlatform {
+                pointer_width,
+                endianness,
+            });
+        }
+
+        let gate_count = u64::from_le_bytes(header[16..24].try_into().unwrap());
+        let metadata_len = u64::from_le_bytes(header[24..32].try_into().unwrap());
+
+        let gates_len = usize::try_from(gate_count)
+            .ok()
+            .and_then(|count| count.checked_mul(GATE_LEN))
+            .ok_or(MmapError::Truncated)?;
+        let metadata_start = HEADER_LEN + gates_len;
+        let metadata_end = usize::try_from(metadata_len)
+            .ok()
+            .and_then(|len| metadata_start.checked_add(len))
+            .ok_or(MmapError::Truncated)?;
+
+        let metadata = map
+            .get(metadata_start..metadata_end)
+            .ok_or(MmapError::Truncated)?;
+        let (inputs, outputs, feed_count, and_count, xor_count): (
+            Vec<BinaryRepr>,
+            Vec<BinaryRepr>,
+            usize,
+            usize,
+            usize,
+        ) = bincode::deserialize(metadata)?;
+
+        for (idx, record) in map[HEADER_LEN..metadata_start]
+            .chunks_exact(GATE_LEN)
+            .enumerate()
+        {
+            if !validate_gate(record, feed_count) {
+                return Err(MmapError::InvalidGate(idx));
+            }
+        }
+
+        Ok(Circuit {
+            inputs,
+            outputs,
+            gates: Gates::Mapped(MappedGates {
+                map: Arc::new(map),
+                len: gates_len / GATE_LEN,
+            }),
+            feed_count,
+            and_count,
+            xor_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{circuits::AES128, types::Value};
+
+    use super::*;
+
+    fn write_temp(circ: &Circuit, name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("mpz-circuits-{name}-{}", std::process::id()));
+        circ.write_mmap(File::create(&path).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_mmap_aes() {
+        let path = write_temp(&AES128, "aes");
+        let mapped = unsafe { Circuit::open_mmap(&path) }.unwrap();
+
+        assert!(matches!(mapped.gates, Gates::Mapped(_)));
+        assert_eq!(mapped.gates().len(), AES128.gates().len());
+        assert!(mapped
+            .gates()
+            .iter()
+            .zip(AES128.gates())
+            .all(|(a, b)| encode_gate(a) == encode_gate(b)));
+        assert_eq!(mapped.and_count(), AES128.and_count());
+
+        let inputs = [Value::from([42u8; 16]), Value::from([69u8; 16])];
+        assert_eq!(
+            mapped.evaluate(&inputs).unwrap(),
+            AES128.evaluate(&inputs).unwrap()
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mmap_invalid_gate() {
+        let path = write_temp(&AES128, "invalid");
+
+        // Corrupt the tag of the first gate.
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[HEADER_LEN] = 0xff;
+        std::fs::write(&path, bytes).unwrap();
+
+        let err = unsafe { Circuit::open_mmap(&path) }.unwrap_err();
+        assert!(matches!(err, MmapError::InvalidGate(0)));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -148,10 +148,10 @@ pub(crate) fn wrapping_sub_nbit(
     (diff, underflow)
 }
 
-/// Switch between two nbit values.
+/// Selects between two nbit values, using one AND gate per bit.
 ///
 /// If `toggle` is 0, the result is `a`, otherwise it is `b`.
-pub(crate) fn switch_nbit(
+pub(crate) fn select_nbit(
     state: &mut BuilderState,
     a: &[Node<Feed>],
     b: &[Node<Feed>],
@@ -159,18 +159,95 @@ pub(crate) fn switch_nbit(
 ) -> Vec<Node<Feed>> {
     assert_eq!(a.len(), b.len());
 
-    let not_toggle = state.add_inv_gate(toggle);
-
     a.iter()
         .zip(b)
         .map(|(a, b)| {
-            let a_and_not_toggle = state.add_and_gate(*a, not_toggle);
-            let b_and_toggle = state.add_and_gate(*b, toggle);
-            state.add_xor_gate(a_and_not_toggle, b_and_toggle)
+            // a ^ (toggle & (a ^ b))
+            let diff = state.add_xor_gate(*a, *b);
+            let diff = state.add_and_gate(diff, toggle);
+            state.add_xor_gate(*a, diff)
         })
         .collect()
 }
 
+/// Conditionally swaps two nbit values, using one AND gate per bit.
+///
+/// If `swap` is 0, the result is `(a, b)`, otherwise it is `(b, a)`.
+pub(crate) fn cswap_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+    swap: Node<Feed>,
+) -> (Vec<Node<Feed>>, Vec<Node<Feed>>) {
+    assert_eq!(a.len(), b.len());
+
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            // The same masked difference is applied to both values.
+            let diff = state.add_xor_gate(*a, *b);
+            let diff = state.add_and_gate(diff, swap);
+            (state.add_xor_gate(*a, diff), state.add_xor_gate(*b, diff))
+        })
+        .unzip()
+}
+
+/// Divide two nbit values using restoring division.
+///
+/// Returns the quotient and the remainder. Division by zero yields a quotient with all bits
+/// set and a remainder equal to the dividend.
+pub(crate) fn div_rem_nbit<const N: usize>(
+    state: &mut BuilderState,
+    a: [Node<Feed>; N],
+    b: [Node<Feed>; N],
+) -> ([Node<Feed>; N], [Node<Feed>; N]) {
+    let const_zero = state.get_const_zero();
+
+    // The partial remainder is always less than the divisor, but it needs an extra bit
+    // after it is shifted.
+    let mut divisor = b.to_vec();
+    divisor.push(const_zero);
+
+    let mut rem = [const_zero; N];
+    let mut quotient = [const_zero; N];
+    for i in (0..N).rev() {
+        // Shift the next bit of the dividend into the partial remainder. Bits are LSB0.
+        let mut shifted = Vec::with_capacity(N + 1);
+        shifted.push(a[i]);
+        shifted.extend_from_slice(&rem);
+
+        let (diff, underflow) = wrapping_sub_nbit(state, &shifted, &divisor);
+
+        // The quotient bit is set if the divisor could be subtracted.
+        quotient[i] = state.add_inv_gate(underflow);
+
+        // Restore the partial remainder if the subtraction underflowed.
+        rem = select_nbit(state, &diff[..N], &shifted[..N], underflow)
+            .try_into()
+            .expect("remainder should be N bits");
+    }
+
+    (quotient, rem)
+}
+
+/// Compares two signed nbit values in two's complement.
+///
+/// Returns the bit indicating whether `a` is less than `b`.
+pub(crate) fn signed_lt_nbit<const N: usize>(
+    state: &mut BuilderState,
+    mut a: [Node<Feed>; N],
+    mut b: [Node<Feed>; N],
+) -> Node<Feed> {
+    // Flipping the sign bits maps the signed values onto unsigned values in the same order, so
+    // the comparison reduces to checking whether the unsigned subtraction underflows.
+    a[N - 1] = state.add_inv_gate(a[N - 1]);
+    b[N - 1] = state.add_inv_gate(b[N - 1]);
+
+    let (_, underflow) = const_wrapping_sub_nbit(state, a, b);
+
+    underflow
+}
+
 /// Bitwise XOR of two nbit values.
 pub(crate) fn xor_nbit<const N: usize>(
     state: &mut BuilderState,
@@ -324,7 +401,36 @@ mod tests {
     }
 
     #[test]
-    fn test_switch_nbit() {
+    fn test_div_rem() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>().to_inner();
+        let b = builder.add_input::<u8>().to_inner();
+
+        let (quotient, rem) = div_rem_nbit(&mut builder.state().borrow_mut(), a.nodes(), b.nodes());
+
+        builder.add_output(U8::new(quotient));
+        builder.add_output(U8::new(rem));
+
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..=255 {
+            for b in 1u8..=255 {
+                let (quotient, rem): (u8, u8) = evaluate!(circ, fn(a, b) -> (u8, u8)).unwrap();
+
+                assert_eq!(quotient, a / b);
+                assert_eq!(rem, a % b);
+            }
+
+            let (quotient, rem): (u8, u8) = evaluate!(circ, fn(a, 0u8) -> (u8, u8)).unwrap();
+
+            assert_eq!(quotient, u8::MAX);
+            assert_eq!(rem, a);
+        }
+    }
+
+    #[test]
+    fn test_select_nbit() {
         let builder = CircuitBuilder::new();
 
         let a = builder.add_input::<u8>().to_inner();
@@ -332,7 +438,7 @@ mod tests {
         let toggle = builder.add_input::<bool>().to_inner();
 
         let out = U8::new(
-            switch_nbit(
+            select_nbit(
                 &mut builder.state().borrow_mut(),
                 a.nodes().as_slice(),
                 b.nodes().as_slice(),
@@ -346,6 +452,8 @@ mod tests {
 
         let circ = builder.build().unwrap();
 
+        assert_eq!(circ.and_count(), 8);
+
         let a = 42u8;
         let b = 69u8;
 
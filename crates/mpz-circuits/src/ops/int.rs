@@ -0,0 +1,140 @@
+use std::ops::Shr;
+
+use crate::{
+    types::{Bit, I128, I16, I32, I64, I8},
+    Tracer,
+};
+
+use super::binary;
+
+macro_rules! impl_shr_int {
+    ($ty:ident, $len:expr) => {
+        impl<'a> Shr<usize> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            /// Arithmetic shift right, which fills the msbs with the sign bit.
+            fn shr(self, rhs: usize) -> Self::Output {
+                assert!(rhs <= $len);
+
+                let mut nodes = self.to_inner().nodes();
+                let sign = nodes[$len - 1];
+                // Bits are LSB0, so we rotate left
+                nodes.rotate_left(rhs);
+                // Replace the msbs with the sign bit
+                nodes[$len - rhs..].iter_mut().for_each(|node| *node = sign);
+
+                let value = <$ty>::new(nodes);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+impl_shr_int!(I8, 8);
+impl_shr_int!(I16, 16);
+impl_shr_int!(I32, 32);
+impl_shr_int!(I64, 64);
+impl_shr_int!(I128, 128);
+
+macro_rules! impl_cmp_int {
+    ($ty:ident, $len:expr) => {
+        impl<'a> Tracer<'a, $ty> {
+            /// Returns whether this value is less than `rhs`.
+            pub fn lt(self, rhs: Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                let mut state = self.state.borrow_mut();
+
+                let node = binary::signed_lt_nbit::<$len>(
+                    &mut state,
+                    self.to_inner().nodes(),
+                    rhs.to_inner().nodes(),
+                );
+
+                drop(state);
+
+                Tracer::new(self.state, Bit::new([node]))
+            }
+
+            /// Returns whether this value is greater than `rhs`.
+            pub fn gt(self, rhs: Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                rhs.lt(self)
+            }
+
+            /// Returns whether this value is less than or equal to `rhs`.
+            pub fn le(self, rhs: Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !rhs.lt(self)
+            }
+
+            /// Returns whether this value is greater than or equal to `rhs`.
+            pub fn ge(self, rhs: Tracer<'a, $ty>) -> Tracer<'a, Bit> {
+                !self.lt(rhs)
+            }
+        }
+    };
+}
+
+impl_cmp_int!(I8, 8);
+impl_cmp_int!(I16, 16);
+impl_cmp_int!(I32, 32);
+impl_cmp_int!(I64, 64);
+impl_cmp_int!(I128, 128);
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::{evaluate, test_circ};
+
+    use crate::{ops::WrappingAdd, CircuitBuilder};
+
+    #[test]
+    fn test_shr() {
+        for shift in [0, 1, 3, 7, 8] {
+            let builder = CircuitBuilder::new();
+            let a = builder.add_input::<i8>();
+            builder.add_output(a >> shift);
+            let circ = builder.build().unwrap();
+
+            for a in i8::MIN..=i8::MAX {
+                let expected = (a as i16 >> shift) as i8;
+                let output: i8 = evaluate!(circ, fn(a) -> i8).unwrap();
+                assert_eq!(output, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmp() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i8>();
+        let b = builder.add_input::<i8>();
+        builder.add_output(a.lt(b));
+        builder.add_output(a.gt(b));
+        builder.add_output(a.le(b));
+        builder.add_output(a.ge(b));
+        let circ = builder.build().unwrap();
+
+        for a in i8::MIN..=i8::MAX {
+            for b in [i8::MIN, -1, 0, 1, 42, i8::MAX] {
+                let output: (bool, bool, bool, bool) =
+                    evaluate!(circ, fn(a, b) -> (bool, bool, bool, bool)).unwrap();
+                assert_eq!(output, (a < b, a > b, a <= b, a >= b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrapping_add() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i32>();
+        let b = builder.add_input::<i32>();
+        builder.add_output(a.wrapping_add(b));
+        let circ = builder.build().unwrap();
+
+        fn wrapping_add(a: i32, b: i32) -> i32 {
+            a.wrapping_add(b)
+        }
+
+        test_circ!(circ, wrapping_add, fn(-5i32, 3i32) -> i32);
+        test_circ!(circ, wrapping_add, fn(i32::MAX, 1i32) -> i32);
+        test_circ!(circ, wrapping_add, fn(i32::MIN, -1i32) -> i32);
+    }
+}
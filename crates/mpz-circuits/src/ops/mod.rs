@@ -1,6 +1,8 @@
 //! Operations on binary encoded types.
 
 pub(crate) mod binary;
+mod int;
+mod select;
 mod uint;
 
 /// Addition of two integers using so called "wrapping addition", which
@@ -0,0 +1,158 @@
+use crate::{
+    types::{Bit, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8},
+    Tracer,
+};
+
+use super::binary;
+
+macro_rules! impl_select {
+    ($ty:ident) => {
+        impl<'a> Tracer<'a, $ty> {
+            /// Returns `self` if `toggle` is 0, otherwise `rhs`.
+            ///
+            /// Costs one AND gate per bit.
+            pub fn select(self, rhs: Tracer<'a, $ty>, toggle: Tracer<'a, Bit>) -> Self {
+                let mut state = self.state.borrow_mut();
+
+                let nodes = binary::select_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                    toggle.node(),
+                );
+
+                drop(state);
+
+                let value = <$ty>::new(nodes.try_into().expect("length should be preserved"));
+
+                Tracer::new(self.state, value)
+            }
+
+            /// Returns `(self, rhs)` if `swap` is 0, otherwise `(rhs, self)`.
+            ///
+            /// Costs one AND gate per bit, shared between both outputs.
+            pub fn cswap(self, rhs: Tracer<'a, $ty>, swap: Tracer<'a, Bit>) -> (Self, Self) {
+                let mut state = self.state.borrow_mut();
+
+                let (a, b) = binary::cswap_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                    swap.node(),
+                );
+
+                drop(state);
+
+                let a = <$ty>::new(a.try_into().expect("length should be preserved"));
+                let b = <$ty>::new(b.try_into().expect("length should be preserved"));
+
+                (Tracer::new(self.state, a), Tracer::new(self.state, b))
+            }
+        }
+    };
+}
+
+impl_select!(Bit);
+impl_select!(U8);
+impl_select!(U16);
+impl_select!(U32);
+impl_select!(U64);
+impl_select!(U128);
+impl_select!(I8);
+impl_select!(I16);
+impl_select!(I32);
+impl_select!(I64);
+impl_select!(I128);
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::evaluate;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::CircuitBuilder;
+
+    #[test]
+    fn test_select() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u32>();
+        let b = builder.add_input::<u32>();
+        let toggle = builder.add_input::<bool>();
+
+        let out = a.select(b, toggle);
+
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 32);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        for _ in 0..256 {
+            let (a, b, toggle): (u32, u32, bool) = rng.gen();
+
+            let out: u32 = evaluate!(circ, fn(a, b, toggle) -> u32).unwrap();
+
+            assert_eq!(out, if toggle { b } else { a });
+        }
+    }
+
+    #[test]
+    fn test_cswap() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<i64>();
+        let b = builder.add_input::<i64>();
+        let swap = builder.add_input::<bool>();
+
+        let (a_out, b_out) = a.cswap(b, swap);
+
+        builder.add_output(a_out);
+        builder.add_output(b_out);
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 64);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        for _ in 0..256 {
+            let (a, b, swap): (i64, i64, bool) = rng.gen();
+
+            let (a_out, b_out): (i64, i64) = evaluate!(circ, fn(a, b, swap) -> (i64, i64)).unwrap();
+
+            if swap {
+                assert_eq!((a_out, b_out), (b, a));
+            } else {
+                assert_eq!((a_out, b_out), (a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_bit() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<bool>();
+        let b = builder.add_input::<bool>();
+        let toggle = builder.add_input::<bool>();
+
+        let out = a.select(b, toggle);
+
+        builder.add_output(out);
+
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 1);
+
+        for a in [false, true] {
+            for b in [false, true] {
+                for toggle in [false, true] {
+                    let out: bool = evaluate!(circ, fn(a, b, toggle) -> bool).unwrap();
+
+                    assert_eq!(out, if toggle { b } else { a });
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use std::ops::{BitAnd, BitOr, BitXor, Div, Not, Rem, Shl, Shr};
 
 use crate::{
-    types::{BinaryRepr, U128, U16, U32, U64, U8},
+    types::{BinaryRepr, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8},
     Tracer,
 };
 
@@ -56,6 +56,13 @@ impl_wrapping_add_uint!(U16, u16, 16);
 impl_wrapping_add_uint!(U32, u32, 32);
 impl_wrapping_add_uint!(U64, u64, 64);
 impl_wrapping_add_uint!(U128, u128, 128);
+// Signed integers are in two's complement, so these operations are the same as for unsigned
+// integers.
+impl_wrapping_add_uint!(I8, i8, 8);
+impl_wrapping_add_uint!(I16, i16, 16);
+impl_wrapping_add_uint!(I32, i32, 32);
+impl_wrapping_add_uint!(I64, i64, 64);
+impl_wrapping_add_uint!(I128, i128, 128);
 
 macro_rules! impl_wrapping_sub_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -108,6 +115,101 @@ impl_wrapping_sub_uint!(U16, u16, 16);
 impl_wrapping_sub_uint!(U32, u32, 32);
 impl_wrapping_sub_uint!(U64, u64, 64);
 impl_wrapping_sub_uint!(U128, u128, 128);
+impl_wrapping_sub_uint!(I8, i8, 8);
+impl_wrapping_sub_uint!(I16, i16, 16);
+impl_wrapping_sub_uint!(I32, i32, 32);
+impl_wrapping_sub_uint!(I64, i64, 64);
+impl_wrapping_sub_uint!(I128, i128, 128);
+
+macro_rules! impl_div_rem_uint {
+    ($ty:ident, $const_ty:ident, $len:expr) => {
+        impl<'a> Div<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn div(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let (nodes, _) = binary::div_rem_nbit::<$len>(
+                    &mut state,
+                    self.to_inner().nodes(),
+                    rhs.to_inner().nodes(),
+                );
+
+                let value = <$ty>::new(nodes);
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> Div<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn div(self, rhs: $const_ty) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let rhs = state.get_constant::<$const_ty>(rhs);
+
+                let (nodes, _) =
+                    binary::div_rem_nbit::<$len>(&mut state, self.to_inner().nodes(), rhs.nodes());
+
+                let value = <$ty>::new(nodes);
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> Rem<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn rem(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let (_, nodes) = binary::div_rem_nbit::<$len>(
+                    &mut state,
+                    self.to_inner().nodes(),
+                    rhs.to_inner().nodes(),
+                );
+
+                let value = <$ty>::new(nodes);
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+
+        impl<'a> Rem<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn rem(self, rhs: $const_ty) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let rhs = state.get_constant::<$const_ty>(rhs);
+
+                let (_, nodes) =
+                    binary::div_rem_nbit::<$len>(&mut state, self.to_inner().nodes(), rhs.nodes());
+
+                let value = <$ty>::new(nodes);
+
+                drop(state);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+// Division by zero yields a quotient with all bits set and a remainder equal to the dividend,
+// as a circuit can not panic.
+impl_div_rem_uint!(U8, u8, 8);
+impl_div_rem_uint!(U16, u16, 16);
+impl_div_rem_uint!(U32, u32, 32);
+impl_div_rem_uint!(U64, u64, 64);
+impl_div_rem_uint!(U128, u128, 128);
 
 impl<'a> BitXor for Tracer<'a, BinaryRepr> {
     type Output = Tracer<'a, BinaryRepr>;
@@ -138,6 +240,26 @@ impl<'a> BitXor for Tracer<'a, BinaryRepr> {
                 let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
                 Tracer::new(self.state, c.into())
             }
+            (BinaryRepr::I8(a), BinaryRepr::I8(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I16(a), BinaryRepr::I16(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I32(a), BinaryRepr::I32(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I64(a), BinaryRepr::I64(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I128(a), BinaryRepr::I128(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
             (BinaryRepr::Array(a), BinaryRepr::Array(b)) => Tracer::new(
                 self.state,
                 BinaryRepr::Array(
@@ -202,6 +324,11 @@ impl_bitxor_uint!(U16, u16, 16);
 impl_bitxor_uint!(U32, u32, 32);
 impl_bitxor_uint!(U64, u64, 64);
 impl_bitxor_uint!(U128, u128, 128);
+impl_bitxor_uint!(I8, i8, 8);
+impl_bitxor_uint!(I16, i16, 16);
+impl_bitxor_uint!(I32, i32, 32);
+impl_bitxor_uint!(I64, i64, 64);
+impl_bitxor_uint!(I128, i128, 128);
 
 macro_rules! impl_bit_and_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -250,6 +377,11 @@ impl_bit_and_uint!(U16, u16, 16);
 impl_bit_and_uint!(U32, u32, 32);
 impl_bit_and_uint!(U64, u64, 64);
 impl_bit_and_uint!(U128, u128, 128);
+impl_bit_and_uint!(I8, i8, 8);
+impl_bit_and_uint!(I16, i16, 16);
+impl_bit_and_uint!(I32, i32, 32);
+impl_bit_and_uint!(I64, i64, 64);
+impl_bit_and_uint!(I128, i128, 128);
 
 macro_rules! impl_bit_or_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -298,6 +430,11 @@ impl_bit_or_uint!(U16, u16, 16);
 impl_bit_or_uint!(U32, u32, 32);
 impl_bit_or_uint!(U64, u64, 64);
 impl_bit_or_uint!(U128, u128, 128);
+impl_bit_or_uint!(I8, i8, 8);
+impl_bit_or_uint!(I16, i16, 16);
+impl_bit_or_uint!(I32, i32, 32);
+impl_bit_or_uint!(I64, i64, 64);
+impl_bit_or_uint!(I128, i128, 128);
 
 macro_rules! impl_shl_uint {
     ($ty:ident, $len:expr) => {
@@ -330,6 +467,11 @@ impl_shl_uint!(U16, 16);
 impl_shl_uint!(U32, 32);
 impl_shl_uint!(U64, 64);
 impl_shl_uint!(U128, 128);
+impl_shl_uint!(I8, 8);
+impl_shl_uint!(I16, 16);
+impl_shl_uint!(I32, 32);
+impl_shl_uint!(I64, 64);
+impl_shl_uint!(I128, 128);
 
 macro_rules! impl_shr_uint {
     ($ty:ident, $len:expr) => {
@@ -386,6 +528,11 @@ impl_neg_uint!(U16);
 impl_neg_uint!(U32);
 impl_neg_uint!(U64);
 impl_neg_uint!(U128);
+impl_neg_uint!(I8);
+impl_neg_uint!(I16);
+impl_neg_uint!(I32);
+impl_neg_uint!(I64);
+impl_neg_uint!(I128);
 
 macro_rules! impl_convert_bytes {
     ($ty:ident, $len:expr) => {
@@ -424,3 +571,43 @@ impl_convert_bytes!(U16, 2);
 impl_convert_bytes!(U32, 4);
 impl_convert_bytes!(U64, 8);
 impl_convert_bytes!(U128, 16);
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::test_circ;
+
+    use crate::CircuitBuilder;
+
+    #[test]
+    fn test_div_rem() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u32>();
+        let b = builder.add_input::<u32>();
+        builder.add_output(a / b);
+        builder.add_output(a % b);
+        let circ = builder.build().unwrap();
+
+        fn div_rem(a: u32, b: u32) -> (u32, u32) {
+            (a / b, a % b)
+        }
+
+        test_circ!(circ, div_rem, fn(1_000_003u32, 97u32) -> (u32, u32));
+        test_circ!(circ, div_rem, fn(42u32, 69u32) -> (u32, u32));
+        test_circ!(circ, div_rem, fn(u32::MAX, 1u32) -> (u32, u32));
+    }
+
+    #[test]
+    fn test_div_rem_const() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u64>();
+        builder.add_output(a / 10u64);
+        builder.add_output(a % 10u64);
+        let circ = builder.build().unwrap();
+
+        fn div_rem_10(a: u64) -> (u64, u64) {
+            (a / 10, a % 10)
+        }
+
+        test_circ!(circ, div_rem_10, fn(123_456_789u64) -> (u64, u64));
+    }
+}
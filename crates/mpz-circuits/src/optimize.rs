@@ -0,0 +1,598 @@
+//! Optimization passes for binary circuits.
+//!
+//! Every pass returns a new circuit which computes the same function, with the same inputs and
+//! outputs, as the original. The feeds of the new circuit are renumbered, so node ids are not
+//! preserved.
+//!
+//! [`optimize`] runs all passes, see also [`Circuit::optimized`].
+
+use std::collections::HashMap;
+
+use crate::{
+    components::{Feed, Gate, Node},
+    types::BinaryRepr,
+    Circuit,
+};
+
+/// Gate counts of a circuit before and after optimization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizationReport {
+    /// Number of gates before optimization.
+    pub gates_before: usize,
+    /// Number of gates after optimization.
+    pub gates_after: usize,
+    /// Number of AND gates before optimization.
+    pub and_before: usize,
+    /// Number of AND gates after optimization.
+    pub and_after: usize,
+    /// Number of XOR gates before optimization.
+    pub xor_before: usize,
+    /// Number of XOR gates after optimization.
+    pub xor_after: usize,
+}
+
+impl OptimizationReport {
+    fn new(before: &Circuit, after: &Circuit) -> Self {
+        Self {
+            gates_before: before.gates.len(),
+            gates_after: after.gates.len(),
+            and_before: before.and_count,
+            and_after: after.and_count,
+            xor_before: before.xor_count,
+            xor_after: after.xor_count,
+        }
+    }
+
+    /// Returns the number of gates which were removed.
+    pub fn removed_gates(&self) -> usize {
+        self.gates_before.saturating_sub(self.gates_after)
+    }
+
+    /// Returns the number of AND gates which were removed.
+    pub fn removed_and_gates(&self) -> usize {
+        self.and_before.saturating_sub(self.and_after)
+    }
+}
+
+/// Runs all optimization passes on a circuit.
+///
+/// Returns the optimized circuit and a report of the gate-count reduction.
+pub fn optimize(circ: &Circuit) -> (Circuit, OptimizationReport) {
+    let optimized = propagate_constants(circ);
+    let optimized = rebalance_xor_chains(&optimized);
+    // Cancelled XOR chains may produce new constants.
+    let optimized = propagate_constants(&optimized);
+    let optimized = eliminate_dead_gates(&optimized);
+
+    let report = OptimizationReport::new(circ, &optimized);
+
+    (optimized, report)
+}
+
+/// The value of a feed during constant propagation.
+#[derive(Debug, Clone, Copy)]
+enum Signal {
+    Const(bool),
+    Node(Node<Feed>),
+}
+
+/// Propagates constants through the circuit.
+///
+/// Gates with constant inputs are folded, eg. `x ⊕ 0 = x`, `x ∧ 1 = x` and `x ∧ 0 = 0`. Gates
+/// whose inputs are the same feed are simplified, and double inversions are removed. Outputs
+/// which turn out to be constant are produced by constant gates.
+pub fn propagate_constants(circ: &Circuit) -> Circuit {
+    let mut signals: Vec<Option<Signal>> = vec![None; circ.feed_count];
+    for node in circ.inputs.iter().flat_map(|input| input.iter()) {
+        signals[node.id] = Some(Signal::Node(*node));
+    }
+
+    let signal = |signals: &[Option<Signal>], id: usize| {
+        signals[id].expect("gates should be topologically sorted")
+    };
+
+    let mut gates = Vec::with_capacity(circ.gates.len());
+    // Maps the output of each inverter to its input.
+    let mut inverses: HashMap<usize, Node<Feed>> = HashMap::new();
+    let mut invert = |gates: &mut Vec<Gate>, x: Node<Feed>, z: Node<Feed>| {
+        if let Some(x) = inverses.get(&x.id) {
+            return Signal::Node(*x);
+        }
+
+        gates.push(Gate::Inv { x: x.into(), z });
+        inverses.insert(z.id, x);
+        Signal::Node(z)
+    };
+
+    for gate in &circ.gates {
+        let out = match *gate {
+            Gate::Const { value, .. } => Signal::Const(value),
+            Gate::Inv { x, z } => match signal(&signals, x.id) {
+                Signal::Const(value) => Signal::Const(!value),
+                Signal::Node(x) => invert(&mut gates, x, z),
+            },
+            Gate::Xor { x, y, z } => match (signal(&signals, x.id), signal(&signals, y.id)) {
+                (Signal::Const(a), Signal::Const(b)) => Signal::Const(a ^ b),
+                (Signal::Const(false), other) | (other, Signal::Const(false)) => other,
+                (Signal::Const(true), Signal::Node(x)) | (Signal::Node(x), Signal::Const(true)) => {
+                    invert(&mut gates, x, z)
+                }
+                (Signal::Node(x), Signal::Node(y)) if x == y => Signal::Const(false),
+                (Signal::Node(x), Signal::Node(y)) => {
+                    gates.push(Gate::Xor {
+                        x: x.into(),
+                        y: y.into(),
+                        z,
+                    });
+                    Signal::Node(z)
+                }
+            },
+            Gate::And { x, y, z } => match (signal(&signals, x.id), signal(&signals, y.id)) {
+                (Signal::Const(false), _) | (_, Signal::Const(false)) => Signal::Const(false),
+                (Signal::Const(true), other) | (other, Signal::Const(true)) => other,
+                (Signal::Node(x), Signal::Node(y)) if x == y => Signal::Node(x),
+                (Signal::Node(x), Signal::Node(y)) => {
+                    gates.push(Gate::And {
+                        x: x.into(),
+                        y: y.into(),
+                        z,
+                    });
+                    Signal::Node(z)
+                }
+            },
+        };
+
+        signals[gate.z().id] = Some(out);
+    }
+
+    // Constant outputs are produced by constant gates, one for each value.
+    let mut feed_count = circ.feed_count;
+    let mut constants: [Option<Node<Feed>>; 2] = [None; 2];
+    let outputs = circ
+        .outputs
+        .iter()
+        .map(|output| {
+            map_repr(output, |node| match signal(&signals, node.id) {
+                Signal::Node(node) => node,
+                Signal::Const(value) => *constants[value as usize].get_or_insert_with(|| {
+                    let z = Node::new(feed_count);
+                    feed_count += 1;
+                    gates.push(Gate::Const { z, value });
+                    z
+                }),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    compact(&circ.inputs, &gates, &outputs)
+}
+
+/// Removes gates which do not contribute to any output of the circuit.
+pub fn eliminate_dead_gates(circ: &Circuit) -> Circuit {
+    let mut live = vec![false; circ.feed_count];
+    for node in circ.outputs.iter().flat_map(|output| output.iter()) {
+        live[node.id] = true;
+    }
+
+    for gate in circ.gates.iter().rev() {
+        if live[gate.z().id] {
+            for x in gate.x().into_iter().chain(gate.y()) {
+                live[x.id] = true;
+            }
+        }
+    }
+
+    let gates: Vec<Gate> = circ
+        .gates
+        .iter()
+        .filter(|gate| live[gate.z().id])
+        .copied()
+        .collect();
+
+    compact(&circ.inputs, &gates, &circ.outputs)
+}
+
+/// Rebalances chains of XOR gates into trees of logarithmic depth.
+///
+/// A chain is a tree of XOR gates whose intermediate results are not used anywhere else. The
+/// leaves of each chain are collected, and leaves which appear an even number of times cancel
+/// out, so this pass may also reduce the number of gates.
+pub fn rebalance_xor_chains(circ: &Circuit) -> Circuit {
+    // Count the uses of every feed, and the uses as an input to an XOR gate.
+    let mut uses = vec![0usize; circ.feed_count];
+    let mut xor_uses = vec![0usize; circ.feed_count];
+    for node in circ.outputs.iter().flat_map(|output| output.iter()) {
+        uses[node.id] += 1;
+    }
+    for gate in &circ.gates {
+        for x in gate.x().into_iter().chain(gate.y()) {
+            uses[x.id] += 1;
+            if let Gate::Xor { .. } = gate {
+                xor_uses[x.id] += 1;
+            }
+        }
+    }
+
+    // The inputs of the XOR gates, by output feed.
+    let xor_inputs: HashMap<usize, [usize; 2]> = circ
+        .gates
+        .iter()
+        .filter_map(|gate| match gate {
+            Gate::Xor { x, y, z } => Some((z.id, [x.id, y.id])),
+            _ => None,
+        })
+        .collect();
+
+    // An XOR gate is internal to a chain if its output is only used by another XOR gate.
+    let is_internal = |id: usize| uses[id] == 1 && xor_uses[id] == 1;
+
+    let mut feed_count = circ.feed_count;
+    let mut gates = Vec::with_capacity(circ.gates.len());
+    // Chains which reduce to a single leaf are replaced by that leaf.
+    let mut aliases: HashMap<usize, usize> = HashMap::new();
+    let resolve = |aliases: &HashMap<usize, usize>, id: usize| *aliases.get(&id).unwrap_or(&id);
+
+    for gate in &circ.gates {
+        let gate = match *gate {
+            Gate::Xor { z, .. } if is_internal(z.id) => continue,
+            Gate::Xor { x, y, z } => {
+                // Collect the leaves of the chain, cancelling out pairs.
+                let mut leaves: Vec<usize> = Vec::new();
+                let mut parity: HashMap<usize, bool> = HashMap::new();
+                let mut stack = vec![y.id, x.id];
+                while let Some(id) = stack.pop() {
+                    match xor_inputs.get(&id) {
+                        Some([x, y]) if is_internal(id) => {
+                            stack.push(*y);
+                            stack.push(*x);
+                        }
+                        _ => {
+                            let id = resolve(&aliases, id);
+                            let odd = parity.entry(id).or_insert_with(|| {
+                                leaves.push(id);
+                                false
+                            });
+                            *odd = !*odd;
+                        }
+                    }
+                }
+                leaves.retain(|id| parity[id]);
+
+                match leaves.len() {
+                    0 => {
+                        gates.push(Gate::Const { z, value: false });
+                    }
+                    1 => {
+                        aliases.insert(z.id, leaves[0]);
+                    }
+                    _ => {
+                        let mut layer: Vec<Node<Feed>> =
+                            leaves.into_iter().map(Node::new).collect();
+                        while layer.len() > 1 {
+                            let mut next = Vec::with_capacity(layer.len().div_ceil(2));
+                            for pair in layer.chunks(2) {
+                                match pair {
+                                    [x, y] => {
+                                        // The root of the tree keeps the output feed of the chain.
+                                        let out = if layer.len() == 2 {
+                                            z
+                                        } else {
+                                            let out = Node::new(feed_count);
+                                            feed_count += 1;
+                                            out
+                                        };
+                                        gates.push(Gate::Xor {
+                                            x: (*x).into(),
+                                            y: (*y).into(),
+                                            z: out,
+                                        });
+                                        next.push(out);
+                                    }
+                                    [x] => next.push(*x),
+                                    _ => unreachable!("chunks have at most 2 elements"),
+                                }
+                            }
+                            layer = next;
+                        }
+                    }
+                }
+
+                continue;
+            }
+            Gate::And { x, y, z } => Gate::And {
+                x: Node::new(resolve(&aliases, x.id)),
+                y: Node::new(resolve(&aliases, y.id)),
+                z,
+            },
+            Gate::Inv { x, z } => Gate::Inv {
+                x: Node::new(resolve(&aliases, x.id)),
+                z,
+            },
+            Gate::Const { z, value } => Gate::Const { z, value },
+        };
+
+        gates.push(gate);
+    }
+
+    let outputs: Vec<BinaryRepr> = circ
+        .outputs
+        .iter()
+        .map(|output| map_repr(output, |node| Node::new(resolve(&aliases, node.id))))
+        .collect();
+
+    compact(&circ.inputs, &gates, &outputs)
+}
+
+/// Maps the nodes of a binary representation.
+fn map_repr(repr: &BinaryRepr, f: impl FnMut(&Node<Feed>) -> Node<Feed>) -> BinaryRepr {
+    let nodes: Vec<Node<Feed>> = repr.iter().map(f).collect();
+    repr.value_type()
+        .to_bin_repr(&nodes)
+        .expect("mapped representation should have the same length")
+}
+
+/// Builds a circuit from topologically sorted gates, renumbering the feeds consecutively.
+///
+/// Inputs are numbered first, followed by the outputs of the gates in order.
+fn compact(inputs: &[BinaryRepr], gates: &[Gate], outputs: &[BinaryRepr]) -> Circuit {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let define = |ids: &mut HashMap<usize, usize>, node: &Node<Feed>| {
+        let id = ids.len();
+        ids.insert(node.id, id);
+        Node::<Feed>::new(id)
+    };
+    let get = |ids: &HashMap<usize, usize>, id: usize| {
+        ids.get(&id)
+            .copied()
+            .expect("gates should be topologically sorted")
+    };
+
+    let inputs: Vec<BinaryRepr> = inputs
+        .iter()
+        .map(|input| map_repr(input, |node| define(&mut ids, node)))
+        .collect();
+
+    let mut and_count = 0;
+    let mut xor_count = 0;
+    let gates: Vec<Gate> = gates
+        .iter()
+        .map(|gate| match *gate {
+            Gate::Xor { x, y, z } => {
+                xor_count += 1;
+                let (x, y) = (get(&ids, x.id), get(&ids, y.id));
+                Gate::Xor {
+                    x: Node::new(x),
+                    y: Node::new(y),
+                    z: define(&mut ids, &z),
+                }
+            }
+            Gate::And { x, y, z } => {
+                and_count += 1;
+                let (x, y) = (get(&ids, x.id), get(&ids, y.id));
+                Gate::And {
+                    x: Node::new(x),
+                    y: Node::new(y),
+                    z: define(&mut ids, &z),
+                }
+            }
+            Gate::Inv { x, z } => {
+                let x = get(&ids, x.id);
+                Gate::Inv {
+                    x: Node::new(x),
+                    z: define(&mut ids, &z),
+                }
+            }
+            Gate::Const { z, value } => Gate::Const {
+                z: define(&mut ids, &z),
+                value,
+            },
+        })
+        .collect();
+
+    let outputs: Vec<BinaryRepr> = outputs
+        .iter()
+        .map(|output| map_repr(output, |node| Node::new(get(&ids, node.id))))
+        .collect();
+
+    Circuit {
+        inputs,
+        outputs,
+        gates: gates.into(),
+        feed_count: ids.len(),
+        and_count,
+        xor_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::{
+        random::{RandomCircuit, RandomCircuitConfig},
+        types::{Value, ValueType},
+    };
+
+    fn xor(x: usize, y: usize, z: usize) -> Gate {
+        Gate::Xor {
+            x: Node::new(x),
+            y: Node::new(y),
+            z: Node::new(z),
+        }
+    }
+
+    fn and(x: usize, y: usize, z: usize) -> Gate {
+        Gate::And {
+            x: Node::new(x),
+            y: Node::new(y),
+            z: Node::new(z),
+        }
+    }
+
+    fn bit(id: usize) -> BinaryRepr {
+        ValueType::Bit.to_bin_repr(&[Node::new(id)]).unwrap()
+    }
+
+    /// Creates a circuit with `input_count` bit inputs, numbered first.
+    fn circuit(input_count: usize, gates: Vec<Gate>, outputs: &[usize]) -> Circuit {
+        Circuit {
+            inputs: (0..input_count).map(bit).collect(),
+            outputs: outputs.iter().copied().map(bit).collect(),
+            feed_count: gates
+                .iter()
+                .map(|gate| gate.z().id + 1)
+                .max()
+                .unwrap_or(0)
+                .max(input_count),
+            and_count: gates
+                .iter()
+                .filter(|g| matches!(g, Gate::And { .. }))
+                .count(),
+            xor_count: gates
+                .iter()
+                .filter(|g| matches!(g, Gate::Xor { .. }))
+                .count(),
+            gates: gates.into(),
+        }
+    }
+
+    /// Asserts that two circuits with bit inputs compute the same function.
+    fn assert_equivalent(a: &Circuit, b: &Circuit) {
+        let input_count = a.inputs().len();
+        for bits in 0..1usize << input_count {
+            let inputs: Vec<Value> = (0..input_count)
+                .map(|i| Value::Bit((bits >> i) & 1 == 1))
+                .collect();
+            assert_eq!(a.evaluate(&inputs).unwrap(), b.evaluate(&inputs).unwrap());
+        }
+    }
+
+    /// Returns the depth of the circuit.
+    fn depth(circ: &Circuit) -> usize {
+        let mut depths = vec![0; circ.feed_count()];
+        for gate in circ.gates() {
+            depths[gate.z().id] = gate
+                .x()
+                .into_iter()
+                .chain(gate.y())
+                .map(|x| depths[x.id] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+        depths.into_iter().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_propagate_constants() {
+        let circ = circuit(
+            2,
+            vec![
+                Gate::Const {
+                    z: Node::new(2),
+                    value: true,
+                },
+                // !a
+                xor(0, 2, 3),
+                // a
+                Gate::Inv {
+                    x: Node::new(3),
+                    z: Node::new(4),
+                },
+                // a & b
+                and(4, 1, 5),
+                Gate::Const {
+                    z: Node::new(6),
+                    value: false,
+                },
+                // 0
+                and(5, 6, 7),
+                // b
+                xor(7, 1, 8),
+            ],
+            &[5, 8, 7],
+        );
+
+        let propagated = propagate_constants(&circ);
+        assert_equivalent(&circ, &propagated);
+        assert_eq!(propagated.and_count(), 1);
+        assert_eq!(propagated.xor_count(), 0);
+
+        let (optimized, report) = circ.optimized();
+        assert_equivalent(&circ, &optimized);
+        // The AND gate and the constant output remain.
+        assert_eq!(optimized.gates().len(), 2);
+        assert_eq!(report.gates_before, 7);
+        assert_eq!(report.gates_after, 2);
+        assert_eq!(report.removed_gates(), 5);
+        assert_eq!(report.removed_and_gates(), 1);
+    }
+
+    #[test]
+    fn test_eliminate_dead_gates() {
+        let circ = circuit(3, vec![and(0, 1, 3), xor(1, 2, 4), and(3, 4, 5)], &[4]);
+
+        let eliminated = eliminate_dead_gates(&circ);
+        assert_equivalent(&circ, &eliminated);
+        assert_eq!(eliminated.gates().len(), 1);
+        assert_eq!(eliminated.and_count(), 0);
+        assert_eq!(eliminated.feed_count(), 4);
+    }
+
+    #[test]
+    fn test_rebalance_xor_chains() {
+        // a ^ b ^ c ^ d ^ e ^ f ^ g
+        let gates = (0..6)
+            .map(|i| {
+                if i == 0 {
+                    xor(0, 1, 7)
+                } else {
+                    xor(6 + i, i + 1, 7 + i)
+                }
+            })
+            .collect();
+        let circ = circuit(7, gates, &[12]);
+        assert_eq!(depth(&circ), 6);
+
+        let rebalanced = rebalance_xor_chains(&circ);
+        assert_equivalent(&circ, &rebalanced);
+        assert_eq!(rebalanced.xor_count(), 6);
+        assert_eq!(depth(&rebalanced), 3);
+
+        // Intermediate results which are used elsewhere are kept.
+        let circ = circuit(3, vec![xor(0, 1, 3), xor(3, 2, 4)], &[4, 3]);
+        let rebalanced = rebalance_xor_chains(&circ);
+        assert_equivalent(&circ, &rebalanced);
+        assert_eq!(rebalanced.xor_count(), 2);
+
+        // (a ^ b) ^ (a ^ c) = b ^ c
+        let circ = circuit(3, vec![xor(0, 1, 3), xor(0, 2, 4), xor(3, 4, 5)], &[5]);
+        let rebalanced = rebalance_xor_chains(&circ);
+        assert_equivalent(&circ, &rebalanced);
+        assert_eq!(rebalanced.xor_count(), 1);
+
+        // (a ^ b) ^ (a ^ b) = 0
+        let circ = circuit(2, vec![xor(0, 1, 2), xor(0, 1, 3), xor(2, 3, 4)], &[4]);
+        let (optimized, _) = circ.optimized();
+        assert_equivalent(&circ, &optimized);
+        assert_eq!(optimized.xor_count(), 0);
+    }
+
+    #[test]
+    fn test_optimize_random() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let random = RandomCircuit::new(&mut rng, &RandomCircuitConfig::default());
+        let circ = random.circuit();
+
+        let (optimized, report) = circ.optimized();
+
+        assert_eq!(
+            optimized.evaluate(random.inputs()).unwrap(),
+            random.outputs()
+        );
+        assert_eq!(report.gates_after, optimized.gates().len());
+        assert!(report.gates_after <= report.gates_before);
+        assert!(report.and_after <= report.and_before);
+    }
+}
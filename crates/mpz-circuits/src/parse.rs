@@ -1,12 +1,12 @@
 use crate::{
-    components::{Feed, GateType, Node},
+    components::{Feed, Node},
     types::ValueType,
     Circuit, CircuitBuilder,
 };
 use regex::{Captures, Regex};
 use std::collections::HashMap;
 
-static GATE_PATTERN: &str = r"(?P<input_count>\d+)\s(?P<output_count>\d+)\s(?P<xref>\d+)\s(?:(?P<yref>\d+)\s)?(?P<zref>\d+)\s(?P<gate>INV|AND|XOR)";
+static GATE_PATTERN: &str = r"(?P<input_count>\d+)\s(?P<output_count>\d+)\s(?P<xref>\d+)\s(?:(?P<yref>\d+)\s)?(?P<zref>\d+)\s(?P<gate>INV|AND|XOR|EQW|EQ)";
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -18,6 +18,8 @@ pub enum ParseError {
     UninitializedFeed(usize),
     #[error("unsupported gate type: {0}")]
     UnsupportedGateType(String),
+    #[error("invalid constant: {0}")]
+    InvalidConstant(usize),
     #[error(transparent)]
     BuilderError(#[from] crate::BuilderError),
 }
@@ -69,7 +71,7 @@ impl Circuit {
             feed_ids.push(zref);
 
             match gate_type {
-                GateType::Xor => {
+                UncheckedGateType::Xor => {
                     let new_x = feed_map
                         .get(&xref)
                         .ok_or(ParseError::UninitializedFeed(xref))?;
@@ -79,7 +81,7 @@ impl Circuit {
                     let new_z = state.add_xor_gate(*new_x, *new_y);
                     feed_map.insert(zref, new_z);
                 }
-                GateType::And => {
+                UncheckedGateType::And => {
                     let new_x = feed_map
                         .get(&xref)
                         .ok_or(ParseError::UninitializedFeed(xref))?;
@@ -89,18 +91,36 @@ impl Circuit {
                     let new_z = state.add_and_gate(*new_x, *new_y);
                     feed_map.insert(zref, new_z);
                 }
-                GateType::Inv => {
+                UncheckedGateType::Inv => {
                     let new_x = feed_map
                         .get(&xref)
                         .ok_or(ParseError::UninitializedFeed(xref))?;
                     let new_z = state.add_inv_gate(*new_x);
                     feed_map.insert(zref, new_z);
                 }
+                UncheckedGateType::Const => {
+                    // The input of an EQ gate is the constant itself, not a wire.
+                    let new_z = match xref {
+                        0 => state.get_const_zero(),
+                        1 => state.get_const_one(),
+                        _ => return Err(ParseError::InvalidConstant(xref)),
+                    };
+                    feed_map.insert(zref, new_z);
+                }
+                UncheckedGateType::Copy => {
+                    // An EQW gate copies a wire, so the output is the same feed as the input.
+                    let new_x = feed_map
+                        .get(&xref)
+                        .ok_or(ParseError::UninitializedFeed(xref))?;
+                    feed_map.insert(zref, *new_x);
+                }
             }
         }
         drop(state);
         feed_ids.sort();
 
+        // The outputs are the last wires, so they are taken from the end in reverse order.
+        let mut reprs = Vec::with_capacity(outputs.len());
         for output in outputs.iter().rev() {
             let feeds = feed_ids
                 .drain(feed_ids.len() - output.len()..)
@@ -111,8 +131,11 @@ impl Circuit {
                 })
                 .collect::<Vec<Node<Feed>>>();
 
-            let output = output.to_bin_repr(&feeds).unwrap();
-            builder.add_output(output);
+            reprs.push(output.to_bin_repr(&feeds).unwrap());
+        }
+
+        for repr in reprs.into_iter().rev() {
+            builder.add_output(repr);
         }
 
         Ok(builder.build()?)
@@ -123,7 +146,18 @@ struct UncheckedGate {
     xref: usize,
     yref: Option<usize>,
     zref: usize,
-    gate_type: GateType,
+    gate_type: UncheckedGateType,
+}
+
+/// Gate types which can appear in a Bristol-fashion circuit.
+enum UncheckedGateType {
+    Xor,
+    And,
+    Inv,
+    /// `EQ`, which sets a wire to a constant.
+    Const,
+    /// `EQW`, which copies a wire.
+    Copy,
 }
 
 impl UncheckedGate {
@@ -137,9 +171,11 @@ impl UncheckedGate {
         let gate_type = captures.name("gate").unwrap().as_str();
 
         let gate_type = match gate_type {
-            "XOR" => GateType::Xor,
-            "AND" => GateType::And,
-            "INV" => GateType::Inv,
+            "XOR" => UncheckedGateType::Xor,
+            "AND" => UncheckedGateType::And,
+            "INV" => UncheckedGateType::Inv,
+            "EQ" => UncheckedGateType::Const,
+            "EQW" => UncheckedGateType::Copy,
             _ => return Err(ParseError::UnsupportedGateType(gate_type.to_string())),
         };
 
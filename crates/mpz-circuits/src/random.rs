@@ -0,0 +1,223 @@
+//! Random circuit generation.
+//!
+//! Random circuits are useful for fuzzing protocols which operate on circuits, and for
+//! benchmarking how they scale across different circuit shapes.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    types::{Value, ValueType},
+    Circuit, CircuitBuilder, Feed, Node,
+};
+
+/// Configuration for generating a [`RandomCircuit`].
+#[derive(Debug, Clone)]
+pub struct RandomCircuitConfig {
+    /// Types of the circuit inputs.
+    pub inputs: Vec<ValueType>,
+    /// Types of the circuit outputs.
+    pub outputs: Vec<ValueType>,
+    /// Number of gates in the circuit.
+    pub gate_count: usize,
+    /// Depth of the circuit, ie the number of gate layers.
+    pub depth: usize,
+    /// Fraction of gates which are AND gates.
+    pub and_ratio: f64,
+    /// Fraction of gates which are INV gates.
+    pub inv_ratio: f64,
+}
+
+impl Default for RandomCircuitConfig {
+    fn default() -> Self {
+        Self {
+            inputs: vec![ValueType::new::<[u8; 16]>(), ValueType::new::<[u8; 16]>()],
+            outputs: vec![ValueType::new::<[u8; 16]>()],
+            gate_count: 1024,
+            depth: 32,
+            and_ratio: 0.5,
+            inv_ratio: 0.1,
+        }
+    }
+}
+
+/// A random circuit, along with random inputs and the corresponding plaintext outputs.
+#[derive(Debug, Clone)]
+pub struct RandomCircuit {
+    circ: Circuit,
+    inputs: Vec<Value>,
+    outputs: Vec<Value>,
+}
+
+impl RandomCircuit {
+    /// Generates a new random circuit.
+    ///
+    /// The gates of the circuit are arranged in `depth` layers, where every gate takes at least
+    /// one of its inputs from the previous layer. The outputs of the circuit are taken from the
+    /// last layer first.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The rng to use.
+    /// * `config` - The configuration of the circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuit has no inputs, if `depth` is zero or greater than `gate_count`,
+    /// or if the gate ratios exceed 1.
+    pub fn new<R: Rng>(rng: &mut R, config: &RandomCircuitConfig) -> Self {
+        assert!(!config.inputs.is_empty(), "circuit must have inputs");
+        assert!(
+            config.depth > 0 && config.depth <= config.gate_count,
+            "depth must be non-zero and not greater than the gate count"
+        );
+        assert!(
+            config.and_ratio >= 0.0
+                && config.inv_ratio >= 0.0
+                && config.and_ratio + config.inv_ratio <= 1.0,
+            "gate ratios must be non-negative and sum to at most 1"
+        );
+
+        let builder = CircuitBuilder::new();
+
+        let mut wires: Vec<Node<Feed>> = Vec::with_capacity(config.gate_count);
+        for ty in &config.inputs {
+            let input = builder.add_input_by_type(ty.clone());
+            wires.extend(input.iter().copied());
+        }
+
+        let mut prev_layer = wires.clone();
+        {
+            let mut state = builder.state().borrow_mut();
+            for layer in 0..config.depth {
+                let layer_size = config.gate_count / config.depth
+                    + usize::from(layer < config.gate_count % config.depth);
+
+                let mut layer_wires = Vec::with_capacity(layer_size);
+                for _ in 0..layer_size {
+                    let x = *prev_layer.choose(rng).expect("layer is not empty");
+                    let y = *wires.choose(rng).expect("wires are not empty");
+
+                    let r: f64 = rng.gen();
+                    let z = if r < config.and_ratio {
+                        state.add_and_gate(x, y)
+                    } else if r < config.and_ratio + config.inv_ratio {
+                        state.add_inv_gate(x)
+                    } else {
+                        state.add_xor_gate(x, y)
+                    };
+
+                    layer_wires.push(z);
+                }
+
+                wires.extend_from_slice(&layer_wires);
+                prev_layer = layer_wires;
+            }
+        }
+
+        let output_len: usize = config.outputs.iter().map(|ty| ty.len()).sum();
+
+        prev_layer.shuffle(rng);
+        let mut output_wires: Vec<_> = prev_layer.into_iter().take(output_len).collect();
+        while output_wires.len() < output_len {
+            output_wires.push(*wires.choose(rng).expect("wires are not empty"));
+        }
+
+        let mut output_wires = output_wires.as_slice();
+        for ty in &config.outputs {
+            let (nodes, rest) = output_wires.split_at(ty.len());
+            builder.add_output(
+                ty.to_bin_repr(nodes)
+                    .expect("output should have correct bit length"),
+            );
+            output_wires = rest;
+        }
+
+        let circ = builder
+            .build()
+            .expect("random circuit should be well-formed");
+
+        let inputs: Vec<Value> = config
+            .inputs
+            .iter()
+            .map(|ty| Value::random(rng, ty))
+            .collect();
+
+        let outputs = circ
+            .evaluate(&inputs)
+            .expect("random circuit should evaluate");
+
+        Self {
+            circ,
+            inputs,
+            outputs,
+        }
+    }
+
+    /// Returns the circuit.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circ
+    }
+
+    /// Returns the random inputs to the circuit.
+    pub fn inputs(&self) -> &[Value] {
+        &self.inputs
+    }
+
+    /// Returns the outputs of the circuit evaluated on the random inputs.
+    pub fn outputs(&self) -> &[Value] {
+        &self.outputs
+    }
+
+    /// Returns the circuit, inputs and outputs.
+    pub fn into_parts(self) -> (Circuit, Vec<Value>, Vec<Value>) {
+        (self.circ, self.inputs, self.outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_random_circuit() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let config = RandomCircuitConfig {
+            gate_count: 100,
+            depth: 10,
+            and_ratio: 1.0,
+            inv_ratio: 0.0,
+            ..Default::default()
+        };
+
+        let random = RandomCircuit::new(&mut rng, &config);
+        let circ = random.circuit();
+
+        assert_eq!(circ.gates().len(), 100);
+        assert_eq!(circ.and_count(), 100);
+        assert_eq!(circ.inputs().len(), 2);
+        assert_eq!(circ.outputs().len(), 1);
+        assert_eq!(random.outputs()[0].value_type(), config.outputs[0]);
+        assert_eq!(circ.evaluate(random.inputs()).unwrap(), random.outputs());
+    }
+
+    #[test]
+    fn test_random_circuit_more_outputs_than_last_layer() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let config = RandomCircuitConfig {
+            outputs: vec![ValueType::new::<u64>(), ValueType::new::<bool>()],
+            gate_count: 32,
+            depth: 16,
+            ..Default::default()
+        };
+
+        let random = RandomCircuit::new(&mut rng, &config);
+
+        assert_eq!(random.circuit().gates().len(), 32);
+        assert_eq!(random.outputs().len(), 2);
+    }
+}
@@ -20,6 +20,12 @@ pub enum TypeError {
         expected: ValueType,
         actual: ValueType,
     },
+    #[error("Invalid byte length: expected: {expected}, actual: {actual}")]
+    InvalidByteLength { expected: usize, actual: usize },
+    #[error("Type can not be represented as bytes: {0}")]
+    NotByteAligned(ValueType),
+    #[error("Invalid value count: expected: {expected}, actual: {actual}")]
+    InvalidValueCount { expected: usize, actual: usize },
 }
 
 /// A type that can be represented in binary form.
@@ -52,6 +58,21 @@ pub trait BinaryLength {
     const LEN: usize;
 }
 
+/// A type which is represented by several values, eg. a struct with a value for each field.
+///
+/// Each value corresponds to one input or output of a circuit. This trait can be derived for
+/// structs whose fields are [`StaticValueType`]s, see [`BinaryValue`](macro@crate::BinaryValue).
+pub trait BinaryValue: Sized {
+    /// Returns the types of the values, in order.
+    fn value_types() -> Vec<ValueType>;
+
+    /// Converts the type into its values.
+    fn into_values(self) -> Vec<Value>;
+
+    /// Creates the type from its values.
+    fn from_values(values: Vec<Value>) -> Result<Self, TypeError>;
+}
+
 /// A binary representation of a type.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -65,6 +86,11 @@ pub enum BinaryRepr {
     U64(U64),
     U128(U128),
     Array(Vec<BinaryRepr>),
+    I8(I8),
+    I16(I16),
+    I32(I32),
+    I64(I64),
+    I128(I128),
 }
 
 impl BinaryRepr {
@@ -77,6 +103,11 @@ impl BinaryRepr {
             BinaryRepr::U32(_) => ValueType::U32,
             BinaryRepr::U64(_) => ValueType::U64,
             BinaryRepr::U128(_) => ValueType::U128,
+            BinaryRepr::I8(_) => ValueType::I8,
+            BinaryRepr::I16(_) => ValueType::I16,
+            BinaryRepr::I32(_) => ValueType::I32,
+            BinaryRepr::I64(_) => ValueType::I64,
+            BinaryRepr::I128(_) => ValueType::I128,
             BinaryRepr::Array(v) => ValueType::Array(Box::new(v[0].value_type()), v.len()),
         }
     }
@@ -91,6 +122,11 @@ impl BinaryRepr {
             BinaryRepr::U32(U32 { .. }) => 32,
             BinaryRepr::U64(U64 { .. }) => 64,
             BinaryRepr::U128(U128 { .. }) => 128,
+            BinaryRepr::I8(I8 { .. }) => 8,
+            BinaryRepr::I16(I16 { .. }) => 16,
+            BinaryRepr::I32(I32 { .. }) => 32,
+            BinaryRepr::I64(I64 { .. }) => 64,
+            BinaryRepr::I128(I128 { .. }) => 128,
             BinaryRepr::Array(v) => v.iter().map(|v| v.len()).sum(),
         }
     }
@@ -104,6 +140,11 @@ impl BinaryRepr {
             BinaryRepr::U32(v) => Box::new(v.0.iter()),
             BinaryRepr::U64(v) => Box::new(v.0.iter()),
             BinaryRepr::U128(v) => Box::new(v.0.iter()),
+            BinaryRepr::I8(v) => Box::new(v.0.iter()),
+            BinaryRepr::I16(v) => Box::new(v.0.iter()),
+            BinaryRepr::I32(v) => Box::new(v.0.iter()),
+            BinaryRepr::I64(v) => Box::new(v.0.iter()),
+            BinaryRepr::I128(v) => Box::new(v.0.iter()),
             BinaryRepr::Array(v) => Box::new(v.iter().flat_map(|v| v.iter())),
         }
     }
@@ -117,6 +158,11 @@ impl BinaryRepr {
             BinaryRepr::U32(v) => Box::new(v.0.iter_mut()),
             BinaryRepr::U64(v) => Box::new(v.0.iter_mut()),
             BinaryRepr::U128(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I8(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I16(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I32(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I64(v) => Box::new(v.0.iter_mut()),
+            BinaryRepr::I128(v) => Box::new(v.0.iter_mut()),
             BinaryRepr::Array(v) => Box::new(v.iter_mut().flat_map(|v| v.iter_mut())),
         }
     }
@@ -130,6 +176,11 @@ impl BinaryRepr {
             BinaryRepr::U32(v) => v.shift_left(offset),
             BinaryRepr::U64(v) => v.shift_left(offset),
             BinaryRepr::U128(v) => v.shift_left(offset),
+            BinaryRepr::I8(v) => v.shift_left(offset),
+            BinaryRepr::I16(v) => v.shift_left(offset),
+            BinaryRepr::I32(v) => v.shift_left(offset),
+            BinaryRepr::I64(v) => v.shift_left(offset),
+            BinaryRepr::I128(v) => v.shift_left(offset),
             BinaryRepr::Array(v) => v.iter_mut().for_each(|v| v.shift_left(offset)),
         }
     }
@@ -157,6 +208,11 @@ impl BinaryRepr {
             BinaryRepr::U32(_) => Ok(Value::U32(u32::from_lsb0_iter(bits.iter().copied()))),
             BinaryRepr::U64(_) => Ok(Value::U64(u64::from_lsb0_iter(bits.iter().copied()))),
             BinaryRepr::U128(_) => Ok(Value::U128(u128::from_lsb0_iter(bits.iter().copied()))),
+            BinaryRepr::I8(_) => Ok(Value::I8(i8::from_lsb0_iter(bits.iter().copied()))),
+            BinaryRepr::I16(_) => Ok(Value::I16(i16::from_lsb0_iter(bits.iter().copied()))),
+            BinaryRepr::I32(_) => Ok(Value::I32(i32::from_lsb0_iter(bits.iter().copied()))),
+            BinaryRepr::I64(_) => Ok(Value::I64(i64::from_lsb0_iter(bits.iter().copied()))),
+            BinaryRepr::I128(_) => Ok(Value::I128(i128::from_lsb0_iter(bits.iter().copied()))),
             BinaryRepr::Array(v) => Ok(Value::Array(
                 v.iter()
                     .zip(bits.chunks(v[0].len()))
@@ -176,6 +232,11 @@ impl Display for BinaryRepr {
             BinaryRepr::U32(v) => write!(f, "U32({:?})", v.0),
             BinaryRepr::U64(v) => write!(f, "U64({:?})", v.0),
             BinaryRepr::U128(v) => write!(f, "U128({:?})", v.0),
+            BinaryRepr::I8(v) => write!(f, "I8({:?})", v.0),
+            BinaryRepr::I16(v) => write!(f, "I16({:?})", v.0),
+            BinaryRepr::I32(v) => write!(f, "I32({:?})", v.0),
+            BinaryRepr::I64(v) => write!(f, "I64({:?})", v.0),
+            BinaryRepr::I128(v) => write!(f, "I128({:?})", v.0),
             BinaryRepr::Array(v) => write!(f, "Array({:?})", v),
         }
     }
@@ -426,6 +487,11 @@ define_binary_value!(u16, U16, 16);
 define_binary_value!(u32, U32, 32);
 define_binary_value!(u64, U64, 64);
 define_binary_value!(u128, U128, 128);
+define_binary_value!(i8, I8, 8);
+define_binary_value!(i16, I16, 16);
+define_binary_value!(i32, I32, 32);
+define_binary_value!(i64, I64, 64);
+define_binary_value!(i128, I128, 128);
 
 /// A value type that can be encoded into a binary representation.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -439,6 +505,11 @@ pub enum ValueType {
     U64,
     U128,
     Array(Box<ValueType>, usize),
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
 }
 
 impl ValueType {
@@ -462,6 +533,11 @@ impl ValueType {
             ValueType::U32 => 32,
             ValueType::U64 => 64,
             ValueType::U128 => 128,
+            ValueType::I8 => 8,
+            ValueType::I16 => 16,
+            ValueType::I32 => 32,
+            ValueType::I64 => 64,
+            ValueType::I128 => 128,
             ValueType::Array(ty, len) => ty.len() * len,
         }
     }
@@ -485,6 +561,11 @@ impl ValueType {
             ValueType::U32 => BinaryRepr::U32(U32::new(nodes.try_into().unwrap())),
             ValueType::U64 => BinaryRepr::U64(U64::new(nodes.try_into().unwrap())),
             ValueType::U128 => BinaryRepr::U128(U128::new(nodes.try_into().unwrap())),
+            ValueType::I8 => BinaryRepr::I8(I8::new(nodes.try_into().unwrap())),
+            ValueType::I16 => BinaryRepr::I16(I16::new(nodes.try_into().unwrap())),
+            ValueType::I32 => BinaryRepr::I32(I32::new(nodes.try_into().unwrap())),
+            ValueType::I64 => BinaryRepr::I64(I64::new(nodes.try_into().unwrap())),
+            ValueType::I128 => BinaryRepr::I128(I128::new(nodes.try_into().unwrap())),
             ValueType::Array(ty, _) => BinaryRepr::Array(
                 nodes
                     .chunks(ty.len())
@@ -506,6 +587,11 @@ impl Display for ValueType {
             ValueType::U32 => write!(f, "U32"),
             ValueType::U64 => write!(f, "U64"),
             ValueType::U128 => write!(f, "U128"),
+            ValueType::I8 => write!(f, "I8"),
+            ValueType::I16 => write!(f, "I16"),
+            ValueType::I32 => write!(f, "I32"),
+            ValueType::I64 => write!(f, "I64"),
+            ValueType::I128 => write!(f, "I128"),
             ValueType::Array(ty, len) => write!(f, "Array<{}, {}>", ty, len),
         }
     }
@@ -533,9 +619,97 @@ impl_value_type!(u16, U16);
 impl_value_type!(u32, U32);
 impl_value_type!(u64, U64);
 impl_value_type!(u128, U128);
+impl_value_type!(i8, I8);
+impl_value_type!(i16, I16);
+impl_value_type!(i32, I32);
+impl_value_type!(i64, I64);
+impl_value_type!(i128, I128);
+
+/// The order in which the bits of an integer are assigned to the wires of a circuit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitOrder {
+    /// The first wire carries the least significant bit.
+    #[default]
+    Lsb0,
+    /// The first wire carries the most significant bit.
+    Msb0,
+}
+
+/// The order of the bytes of a multi-byte integer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ByteOrder {
+    /// The least significant byte comes first.
+    #[default]
+    Little,
+    /// The most significant byte comes first.
+    Big,
+}
+
+/// A convention for converting between bytes and [`Value`]s.
+///
+/// The byte order determines how the bytes of an integer are combined, and the bit order
+/// determines how the bits of the integer are mapped to the wires of a circuit. The circuits in
+/// this crate expect [`BitOrder::Lsb0`], so values converted with [`BitOrder::Msb0`] have their
+/// bits reversed.
+///
+/// The default convention is [`BitOrder::Lsb0`] with [`ByteOrder::Little`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Endianness {
+    /// The bit order.
+    pub bit_order: BitOrder,
+    /// The byte order.
+    pub byte_order: ByteOrder,
+}
+
+impl Endianness {
+    /// Creates a new convention.
+    pub fn new(bit_order: BitOrder, byte_order: ByteOrder) -> Self {
+        Self {
+            bit_order,
+            byte_order,
+        }
+    }
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}/{:?}", self.bit_order, self.byte_order)
+    }
+}
+
+macro_rules! int_from_bytes {
+    ($ty:ty, $bytes:expr, $endianness:expr) => {{
+        let bytes = $bytes.try_into().expect("length should match");
+        let v = match $endianness.byte_order {
+            ByteOrder::Little => <$ty>::from_le_bytes(bytes),
+            ByteOrder::Big => <$ty>::from_be_bytes(bytes),
+        };
+        match $endianness.bit_order {
+            BitOrder::Lsb0 => v,
+            BitOrder::Msb0 => v.reverse_bits(),
+        }
+    }};
+}
+
+macro_rules! int_to_bytes {
+    ($v:expr, $endianness:expr) => {{
+        let v = match $endianness.bit_order {
+            BitOrder::Lsb0 => $v,
+            BitOrder::Msb0 => $v.reverse_bits(),
+        };
+        match $endianness.byte_order {
+            ByteOrder::Little => v.to_le_bytes().to_vec(),
+            ByteOrder::Big => v.to_be_bytes().to_vec(),
+        }
+    }};
+}
 
 /// A value that can be encoded into a binary representation.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 #[allow(missing_docs)]
 pub enum Value {
@@ -546,6 +720,11 @@ pub enum Value {
     U64(u64),
     U128(u128),
     Array(Vec<Value>),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
 }
 
 impl Value {
@@ -558,6 +737,11 @@ impl Value {
             ValueType::U32 => Value::U32(rng.gen()),
             ValueType::U64 => Value::U64(rng.gen()),
             ValueType::U128 => Value::U128(rng.gen()),
+            ValueType::I8 => Value::I8(rng.gen()),
+            ValueType::I16 => Value::I16(rng.gen()),
+            ValueType::I32 => Value::I32(rng.gen()),
+            ValueType::I64 => Value::I64(rng.gen()),
+            ValueType::I128 => Value::I128(rng.gen()),
             ValueType::Array(ty, len) => Value::Array(
                 (0..*len)
                     .map(|_| Value::random(rng, ty))
@@ -575,9 +759,102 @@ impl Value {
             Value::U32(_) => ValueType::U32,
             Value::U64(_) => ValueType::U64,
             Value::U128(_) => ValueType::U128,
+            Value::I8(_) => ValueType::I8,
+            Value::I16(_) => ValueType::I16,
+            Value::I32(_) => ValueType::I32,
+            Value::I64(_) => ValueType::I64,
+            Value::I128(_) => ValueType::I128,
             Value::Array(v) => ValueType::Array(Box::new(v[0].value_type()), v.len()),
         }
     }
+
+    /// Creates a value of the given type from its byte representation.
+    ///
+    /// Bits, and arrays of bits, are not byte aligned and are not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The type of the value.
+    /// * `bytes` - The bytes of the value.
+    /// * `endianness` - The convention the bytes are in.
+    pub fn from_bytes(
+        ty: &ValueType,
+        bytes: &[u8],
+        endianness: Endianness,
+    ) -> Result<Self, TypeError> {
+        if !is_byte_aligned(ty) {
+            return Err(TypeError::NotByteAligned(ty.clone()));
+        }
+
+        if bytes.len() * 8 != ty.len() {
+            return Err(TypeError::InvalidByteLength {
+                expected: ty.len() / 8,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(Self::from_bytes_unchecked(ty, bytes, endianness))
+    }
+
+    fn from_bytes_unchecked(ty: &ValueType, bytes: &[u8], endianness: Endianness) -> Self {
+        match ty {
+            ValueType::U8 => Value::U8(int_from_bytes!(u8, bytes, endianness)),
+            ValueType::U16 => Value::U16(int_from_bytes!(u16, bytes, endianness)),
+            ValueType::U32 => Value::U32(int_from_bytes!(u32, bytes, endianness)),
+            ValueType::U64 => Value::U64(int_from_bytes!(u64, bytes, endianness)),
+            ValueType::U128 => Value::U128(int_from_bytes!(u128, bytes, endianness)),
+            ValueType::I8 => Value::I8(int_from_bytes!(i8, bytes, endianness)),
+            ValueType::I16 => Value::I16(int_from_bytes!(i16, bytes, endianness)),
+            ValueType::I32 => Value::I32(int_from_bytes!(i32, bytes, endianness)),
+            ValueType::I64 => Value::I64(int_from_bytes!(i64, bytes, endianness)),
+            ValueType::I128 => Value::I128(int_from_bytes!(i128, bytes, endianness)),
+            ValueType::Array(ty, _) => Value::Array(
+                bytes
+                    .chunks(ty.len() / 8)
+                    .map(|bytes| Self::from_bytes_unchecked(ty, bytes, endianness))
+                    .collect(),
+            ),
+            ValueType::Bit => unreachable!("bits are not byte aligned"),
+        }
+    }
+
+    /// Returns the byte representation of the value.
+    ///
+    /// Bits, and arrays of bits, are not byte aligned and are not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `endianness` - The convention to return the bytes in.
+    pub fn to_bytes(&self, endianness: Endianness) -> Result<Vec<u8>, TypeError> {
+        Ok(match self {
+            Value::Bit(_) => return Err(TypeError::NotByteAligned(self.value_type())),
+            Value::U8(v) => int_to_bytes!(*v, endianness),
+            Value::U16(v) => int_to_bytes!(*v, endianness),
+            Value::U32(v) => int_to_bytes!(*v, endianness),
+            Value::U64(v) => int_to_bytes!(*v, endianness),
+            Value::U128(v) => int_to_bytes!(*v, endianness),
+            Value::I8(v) => int_to_bytes!(*v, endianness),
+            Value::I16(v) => int_to_bytes!(*v, endianness),
+            Value::I32(v) => int_to_bytes!(*v, endianness),
+            Value::I64(v) => int_to_bytes!(*v, endianness),
+            Value::I128(v) => int_to_bytes!(*v, endianness),
+            Value::Array(v) => {
+                let mut bytes = Vec::new();
+                for v in v {
+                    bytes.extend(v.to_bytes(endianness)?);
+                }
+                bytes
+            }
+        })
+    }
+}
+
+fn is_byte_aligned(ty: &ValueType) -> bool {
+    match ty {
+        ValueType::Bit => false,
+        ValueType::Array(ty, _) => is_byte_aligned(ty),
+        _ => true,
+    }
 }
 
 impl IntoBits for Value {
@@ -592,6 +869,11 @@ impl IntoBits for Value {
             Value::U32(v) => v.into_lsb0_vec(),
             Value::U64(v) => v.into_lsb0_vec(),
             Value::U128(v) => v.into_lsb0_vec(),
+            Value::I8(v) => v.into_lsb0_vec(),
+            Value::I16(v) => v.into_lsb0_vec(),
+            Value::I32(v) => v.into_lsb0_vec(),
+            Value::I64(v) => v.into_lsb0_vec(),
+            Value::I128(v) => v.into_lsb0_vec(),
             Value::Array(v) => v.into_iter().flat_map(|v| v.into_iter_lsb0()).collect(),
         }
         .into_iter()
@@ -605,6 +887,11 @@ impl IntoBits for Value {
             Value::U32(v) => v.into_msb0_vec(),
             Value::U64(v) => v.into_msb0_vec(),
             Value::U128(v) => v.into_msb0_vec(),
+            Value::I8(v) => v.into_msb0_vec(),
+            Value::I16(v) => v.into_msb0_vec(),
+            Value::I32(v) => v.into_msb0_vec(),
+            Value::I64(v) => v.into_msb0_vec(),
+            Value::I128(v) => v.into_msb0_vec(),
             Value::Array(v) => v.into_iter().flat_map(|v| v.into_iter_msb0()).collect(),
         }
         .into_iter()
@@ -620,6 +907,11 @@ impl Display for Value {
             Value::U32(v) => write!(f, "U32({})", v),
             Value::U64(v) => write!(f, "U64({})", v),
             Value::U128(v) => write!(f, "U128({})", v),
+            Value::I8(v) => write!(f, "I8({})", v),
+            Value::I16(v) => write!(f, "I16({})", v),
+            Value::I32(v) => write!(f, "I32({})", v),
+            Value::I64(v) => write!(f, "I64({})", v),
+            Value::I128(v) => write!(f, "I128({})", v),
             Value::Array(v) => write!(f, "Array({:?})", v),
         }
     }
@@ -636,6 +928,11 @@ impl BitXor for Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -663,6 +960,11 @@ impl BitXor<&Value> for &Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -690,6 +992,11 @@ impl BitXor<&Value> for Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -717,6 +1024,11 @@ impl BitXor<Value> for &Value {
             (Value::U32(a), Value::U32(b)) => Value::U32(a ^ b),
             (Value::U64(a), Value::U64(b)) => Value::U64(a ^ b),
             (Value::U128(a), Value::U128(b)) => Value::U128(a ^ b),
+            (Value::I8(a), Value::I8(b)) => Value::I8(a ^ b),
+            (Value::I16(a), Value::I16(b)) => Value::I16(a ^ b),
+            (Value::I32(a), Value::I32(b)) => Value::I32(a ^ b),
+            (Value::I64(a), Value::I64(b)) => Value::I64(a ^ b),
+            (Value::I128(a), Value::I128(b)) => Value::I128(a ^ b),
             (Value::Array(a), Value::Array(b)) => Value::Array(
                 a.iter()
                     .zip(b.iter())
@@ -767,8 +1079,9 @@ impl_convert_bytes!(U128, 16);
 
 #[cfg(test)]
 mod tests {
-    use mpz_circuits_macros::{test_circ, trace};
+    use mpz_circuits_macros::{test_circ, trace, BinaryValue};
 
+    use super::*;
     use crate::CircuitBuilder;
 
     #[trace]
@@ -781,6 +1094,64 @@ mod tests {
         a.to_le_bytes()
     }
 
+    #[test]
+    fn test_value_bytes() {
+        let lsb0_le = Endianness::default();
+        let lsb0_be = Endianness::new(BitOrder::Lsb0, ByteOrder::Big);
+        let msb0_be = Endianness::new(BitOrder::Msb0, ByteOrder::Big);
+
+        let bytes = [0x01, 0x02, 0x03, 0x04];
+
+        let value = Value::from_bytes(&ValueType::U32, &bytes, lsb0_le).unwrap();
+        assert_eq!(value, Value::U32(0x04030201));
+
+        let value = Value::from_bytes(&ValueType::U32, &bytes, lsb0_be).unwrap();
+        assert_eq!(value, Value::U32(0x01020304));
+
+        let value = Value::from_bytes(&ValueType::U32, &bytes, msb0_be).unwrap();
+        assert_eq!(value, Value::U32(0x01020304u32.reverse_bits()));
+
+        // The byte order has no effect on byte arrays.
+        let value = Value::from_bytes(&ValueType::new_array::<u8>(4), &bytes, lsb0_be).unwrap();
+        assert_eq!(value, Value::from(bytes));
+
+        let value = Value::from_bytes(&ValueType::new_array::<u8>(4), &bytes, msb0_be).unwrap();
+        assert_eq!(value, Value::from(bytes.map(u8::reverse_bits)));
+
+        for endianness in [lsb0_le, lsb0_be, msb0_be] {
+            for ty in [
+                ValueType::U32,
+                ValueType::I64,
+                ValueType::new_array::<u16>(2),
+            ] {
+                let value = Value::random(&mut rand::thread_rng(), &ty);
+                let bytes = value.to_bytes(endianness).unwrap();
+                assert_eq!(Value::from_bytes(&ty, &bytes, endianness).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_value_bytes_invalid() {
+        let endianness = Endianness::default();
+
+        assert!(matches!(
+            Value::from_bytes(&ValueType::U32, &[0u8; 3], endianness),
+            Err(TypeError::InvalidByteLength {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            Value::from_bytes(&ValueType::new_array::<bool>(8), &[0u8], endianness),
+            Err(TypeError::NotByteAligned(_))
+        ));
+        assert!(matches!(
+            Value::Bit(true).to_bytes(endianness),
+            Err(TypeError::NotByteAligned(_))
+        ));
+    }
+
     #[test]
     fn test_convert_bytes() {
         let builder = CircuitBuilder::new();
@@ -799,4 +1170,79 @@ mod tests {
 
         test_circ!(circ, to_le_bytes, fn(69u128) -> [u8; 16]);
     }
+
+    #[derive(Debug, PartialEq, BinaryValue)]
+    struct Named {
+        a: u8,
+        b: [u16; 2],
+        c: bool,
+    }
+
+    #[derive(Debug, PartialEq, BinaryValue)]
+    struct Unnamed(u64, [u8; 4]);
+
+    #[test]
+    fn test_binary_value() {
+        assert_eq!(
+            Named::value_types(),
+            vec![
+                ValueType::U8,
+                ValueType::new_array::<u16>(2),
+                ValueType::Bit
+            ]
+        );
+
+        let named = Named {
+            a: 1,
+            b: [2, 3],
+            c: true,
+        };
+        let values: Vec<Value> = named.into();
+        assert_eq!(
+            values,
+            vec![Value::U8(1), Value::from([2u16, 3]), Value::Bit(true)]
+        );
+        assert_eq!(
+            Named::try_from(values).unwrap(),
+            Named {
+                a: 1,
+                b: [2, 3],
+                c: true,
+            }
+        );
+
+        let unnamed = Unnamed(42, [1, 2, 3, 4]);
+        assert_eq!(
+            Unnamed::from_values(unnamed.into_values()).unwrap(),
+            Unnamed(42, [1, 2, 3, 4])
+        );
+
+        assert!(matches!(
+            Unnamed::from_values(vec![Value::U64(42)]),
+            Err(TypeError::InvalidValueCount {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            Unnamed::from_values(vec![Value::U64(42), Value::from([1u8, 2])]),
+            Err(TypeError::UnexpectedType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_binary_value_circuit() {
+        let builder = CircuitBuilder::new();
+        let inputs = builder.add_inputs::<Unnamed>();
+        for input in inputs.into_iter().rev() {
+            builder.add_output(input);
+        }
+        let circ = builder.build().unwrap();
+
+        let outputs = circ
+            .evaluate(&Unnamed(42, [1, 2, 3, 4]).into_values())
+            .unwrap();
+
+        assert_eq!(outputs, vec![Value::from([1u8, 2, 3, 4]), Value::U64(42)]);
+    }
 }
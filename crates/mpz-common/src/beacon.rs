@@ -0,0 +1,162 @@
+//! Public randomness beacons.
+//!
+//! Protocol challenges, such as the weights of a consistency check, are usually derived from a
+//! seed which the parties agree on with a coin-toss. Some deployments additionally require these
+//! challenges to depend on an external source of public randomness, so that an auditor can check
+//! that they were not chosen by either party.
+//!
+//! An application can provide such a source by implementing [`Beacon`]. Protocols which support it
+//! derive their challenges with a [`ChallengeDeriver`], which mixes the beacon's randomness into
+//! the agreed seed. Without a beacon, the seed is used as is.
+//!
+//! # Security
+//!
+//! The beacon is only mixed into the seed, it never replaces it. A biased or compromised beacon
+//! can therefore not weaken the challenges below the guarantees of the coin-toss.
+
+use std::{error::Error, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use mpz_core::{hash::SecureHash, Block};
+
+use crate::{tag::Tag, Counter};
+
+/// Domain separator for challenges derived with beacon randomness.
+const CHALLENGE_DOMAIN: &[u8] = b"mpz-common beacon challenge";
+
+/// An error returned by a [`Beacon`].
+#[derive(Debug, thiserror::Error)]
+#[error("beacon error: {0}")]
+pub struct BeaconError(Box<dyn Error + Send + Sync>);
+
+impl BeaconError {
+    /// Creates a new beacon error.
+    pub fn new<E>(source: E) -> Self
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        Self(source.into())
+    }
+}
+
+/// A source of public randomness, such as a randomness beacon.
+#[async_trait]
+pub trait Beacon: fmt::Debug + Send + Sync {
+    /// Returns the public randomness for `label`.
+    ///
+    /// All parties must receive the same randomness for the same label, and the randomness must
+    /// not be predictable before the label is requested.
+    async fn randomness(&self, label: &str) -> Result<[u8; 32], BeaconError>;
+}
+
+/// Derives protocol challenges, optionally mixing in randomness from a [`Beacon`].
+///
+/// Each derivation uses a label made of the tag of the protocol instance, the name of the
+/// challenge and the number of challenges derived so far, so the parties must derive their
+/// challenges in the same order.
+#[derive(Debug, Clone, Default)]
+pub struct ChallengeDeriver {
+    beacon: Option<Arc<dyn Beacon>>,
+    counter: Counter,
+}
+
+impl ChallengeDeriver {
+    /// Creates a new deriver which mixes in randomness from `beacon`.
+    pub fn new(beacon: Arc<dyn Beacon>) -> Self {
+        Self {
+            beacon: Some(beacon),
+            counter: Counter::default(),
+        }
+    }
+
+    /// Returns the beacon, if any.
+    pub fn beacon(&self) -> Option<&Arc<dyn Beacon>> {
+        self.beacon.as_ref()
+    }
+
+    /// Sets the beacon to mix into subsequent challenges.
+    pub fn set_beacon(&mut self, beacon: Arc<dyn Beacon>) {
+        self.beacon = Some(beacon);
+    }
+
+    /// Derives a challenge seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag of the protocol instance.
+    /// * `name` - The name of the challenge.
+    /// * `seed` - The seed agreed on by the parties.
+    pub async fn derive(
+        &mut self,
+        tag: &Tag,
+        name: &str,
+        seed: Block,
+    ) -> Result<Block, BeaconError> {
+        let label = format!("{tag}/{name}/{}", self.counter.next());
+
+        let Some(beacon) = &self.beacon else {
+            return Ok(seed);
+        };
+
+        let randomness = beacon.randomness(&label).await?;
+        let hash = (CHALLENGE_DOMAIN, seed, label, randomness).hash();
+
+        let bytes: [u8; 16] = hash.as_bytes()[..16]
+            .try_into()
+            .expect("hash is at least 16 bytes");
+
+        Ok(Block::new(bytes))
+    }
+}
+
+/// Test utilities.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils {
+    use super::*;
+
+    /// A beacon which derives its randomness from a fixed seed.
+    #[derive(Debug)]
+    pub struct TestBeacon(pub u8);
+
+    #[async_trait]
+    impl Beacon for TestBeacon {
+        async fn randomness(&self, label: &str) -> Result<[u8; 32], BeaconError> {
+            Ok(*(self.0, label).hash().as_bytes())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::{test_utils::TestBeacon, *};
+
+    #[test]
+    fn test_challenge_without_beacon() {
+        let tag = Tag::new("test");
+        let seed = Block::ONES;
+
+        let challenge = block_on(ChallengeDeriver::default().derive(&tag, "check", seed)).unwrap();
+
+        assert_eq!(challenge, seed);
+    }
+
+    #[test]
+    fn test_challenge_with_beacon() {
+        let tag = Tag::new("test");
+        let seed = Block::ONES;
+
+        let mut a = ChallengeDeriver::new(Arc::new(TestBeacon(0)));
+        let mut b = ChallengeDeriver::new(Arc::new(TestBeacon(0)));
+        let mut c = ChallengeDeriver::new(Arc::new(TestBeacon(1)));
+
+        let first = block_on(a.derive(&tag, "check", seed)).unwrap();
+        assert_ne!(first, seed);
+        assert_eq!(first, block_on(b.derive(&tag, "check", seed)).unwrap());
+        assert_ne!(first, block_on(c.derive(&tag, "check", seed)).unwrap());
+
+        // Each derivation uses a fresh label.
+        assert_ne!(first, block_on(a.derive(&tag, "check", seed)).unwrap());
+    }
+}
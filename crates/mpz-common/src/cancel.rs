@@ -0,0 +1,162 @@
+//! Cancellation of protocol execution.
+//!
+//! Every [`Context`](crate::Context) carries a [`CancellationToken`] which is shared with all of
+//! its forks and the threads spawned by the same executor. Cancelling the token, for example after
+//! the connection to the peer was dropped, causes long-running protocol loops to return a
+//! [`CancelledError`] at their next await point instead of waiting on messages which will never
+//! arrive.
+//!
+//! Protocols race their I/O against the token with [`CancellationToken::run`].
+
+use core::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context as TaskContext, Poll, Waker},
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use futures::future::{select, Either};
+
+/// The operation was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("operation was cancelled")]
+pub struct CancelledError;
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// A token which signals that execution should be cancelled.
+///
+/// Clones of a token share their state, cancelling one of them cancels all of them.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<Inner>);
+
+impl CancellationToken {
+    /// Creates a new token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token, waking all tasks waiting on it.
+    pub fn cancel(&self) {
+        if self.0.cancelled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let wakers = std::mem::take(&mut *self.0.wakers.lock().unwrap());
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Returns `true` if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns an error if the token has been cancelled.
+    pub fn check(&self) -> Result<(), CancelledError> {
+        if self.is_cancelled() {
+            Err(CancelledError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns a future which resolves once the token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled(self.clone())
+    }
+
+    /// Awaits `fut`, returning an error if the token is cancelled first.
+    ///
+    /// If the token is already cancelled, `fut` is not polled.
+    pub async fn run<F: Future>(&self, fut: F) -> Result<F::Output, CancelledError> {
+        self.check()?;
+
+        match select(pin!(fut), self.cancelled()).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(CancelledError),
+        }
+    }
+}
+
+/// A future which resolves once a [`CancellationToken`] is cancelled.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Cancelled(CancellationToken);
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<()> {
+        let inner = &(self.0).0;
+        if inner.cancelled.load(Ordering::Acquire) {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = inner.wakers.lock().unwrap();
+        // Check again while holding the lock, the token may have been cancelled in between.
+        if inner.cancelled.load(Ordering::Acquire) {
+            return Poll::Ready(());
+        }
+
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, future::pending};
+
+    use super::*;
+
+    #[test]
+    fn test_cancel_shared() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.check(), Err(CancelledError));
+    }
+
+    #[test]
+    fn test_run_completes() {
+        let token = CancellationToken::new();
+
+        assert_eq!(block_on(token.run(async { 42 })), Ok(42));
+    }
+
+    #[test]
+    fn test_run_already_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        assert_eq!(block_on(token.run(async { 42 })), Err(CancelledError));
+    }
+
+    #[tokio::test]
+    async fn test_run_cancelled_while_pending() {
+        let token = CancellationToken::new();
+
+        let task = tokio::spawn({
+            let token = token.clone();
+            async move { token.run(pending::<()>()).await }
+        });
+
+        tokio::task::yield_now().await;
+        token.cancel();
+
+        assert_eq!(task.await.unwrap(), Err(CancelledError));
+    }
+}
@@ -0,0 +1,150 @@
+//! Negotiation of the serialization codec used for wire messages.
+//!
+//! Both parties must frame their I/O channel with the same codec. [`negotiate_codec`] runs a
+//! short handshake over the raw byte stream, before it is framed, so that each party can state
+//! which codecs it supports and which it prefers.
+//!
+//! Bincode is fast to encode and decode, while postcard uses variable-length integers which
+//! produce noticeably smaller frames for the many small messages exchanged in OT-heavy phases.
+
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Errors that can occur during codec negotiation.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum CodecError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("peer sent an invalid codec handshake: {0:?}")]
+    InvalidHandshake([u8; 2]),
+    #[error("no codec is supported by both parties")]
+    NoCommonCodec,
+}
+
+/// A serialization codec for wire messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodecKind {
+    /// Bincode, optimized for encoding speed.
+    Bincode,
+    /// Postcard, optimized for message size.
+    Postcard,
+}
+
+impl CodecKind {
+    /// All codecs, in order of fallback priority.
+    pub const ALL: [CodecKind; 2] = [CodecKind::Bincode, CodecKind::Postcard];
+
+    fn to_byte(self) -> u8 {
+        match self {
+            CodecKind::Bincode => 0,
+            CodecKind::Postcard => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(CodecKind::Bincode),
+            1 => Some(CodecKind::Postcard),
+            _ => None,
+        }
+    }
+
+    fn mask(self) -> u8 {
+        1 << self.to_byte()
+    }
+}
+
+/// Negotiates the codec used for wire messages with the peer.
+///
+/// Both parties send their preferred codec and the set of codecs they support. If both parties
+/// prefer the same codec it is selected, otherwise the first codec in [`CodecKind::ALL`] supported
+/// by both parties is selected. The preferred codec is always considered supported.
+///
+/// This must be called on the raw I/O stream before it is framed.
+///
+/// # Arguments
+///
+/// * `io` - The raw I/O stream to the peer.
+/// * `preferred` - The codec preferred by this party.
+/// * `supported` - The codecs supported by this party.
+pub async fn negotiate_codec<Io: AsyncRead + AsyncWrite + Unpin>(
+    io: &mut Io,
+    preferred: CodecKind,
+    supported: &[CodecKind],
+) -> Result<CodecKind, CodecError> {
+    let mask = supported
+        .iter()
+        .fold(preferred.mask(), |mask, codec| mask | codec.mask());
+
+    io.write_all(&[preferred.to_byte(), mask]).await?;
+    io.flush().await?;
+
+    let mut handshake = [0u8; 2];
+    io.read_exact(&mut handshake).await?;
+
+    let [peer_preferred, peer_mask] = handshake;
+    let peer_preferred = CodecKind::from_byte(peer_preferred)
+        .filter(|codec| peer_mask & codec.mask() != 0)
+        .ok_or(CodecError::InvalidHandshake(handshake))?;
+
+    if peer_preferred == preferred {
+        return Ok(preferred);
+    }
+
+    CodecKind::ALL
+        .into_iter()
+        .find(|codec| mask & peer_mask & codec.mask() != 0)
+        .ok_or(CodecError::NoCommonCodec)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::compat::TokioAsyncReadCompatExt;
+
+    use super::*;
+
+    async fn negotiate(
+        a: (CodecKind, &[CodecKind]),
+        b: (CodecKind, &[CodecKind]),
+    ) -> (Result<CodecKind, CodecError>, Result<CodecKind, CodecError>) {
+        let (io_a, io_b) = tokio::io::duplex(8);
+        let (mut io_a, mut io_b) = (io_a.compat(), io_b.compat());
+
+        tokio::join!(
+            negotiate_codec(&mut io_a, a.0, a.1),
+            negotiate_codec(&mut io_b, b.0, b.1)
+        )
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_codec_same_preference() {
+        let (a, b) = negotiate(
+            (CodecKind::Postcard, &CodecKind::ALL),
+            (CodecKind::Postcard, &[]),
+        )
+        .await;
+
+        assert_eq!(a.unwrap(), CodecKind::Postcard);
+        assert_eq!(b.unwrap(), CodecKind::Postcard);
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_codec_fallback() {
+        let (a, b) = negotiate(
+            (CodecKind::Postcard, &CodecKind::ALL),
+            (CodecKind::Bincode, &CodecKind::ALL),
+        )
+        .await;
+
+        assert_eq!(a.unwrap(), CodecKind::Bincode);
+        assert_eq!(b.unwrap(), CodecKind::Bincode);
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_codec_no_common_codec() {
+        let (a, b) = negotiate((CodecKind::Postcard, &[]), (CodecKind::Bincode, &[])).await;
+
+        assert!(matches!(a, Err(CodecError::NoCommonCodec)));
+        assert!(matches!(b, Err(CodecError::NoCommonCodec)));
+    }
+}
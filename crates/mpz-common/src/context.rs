@@ -5,7 +5,11 @@ use async_trait::async_trait;
 use scoped_futures::ScopedBoxFuture;
 use serio::{IoSink, IoStream};
 
-use crate::ThreadId;
+use crate::{
+    cancel::{CancellationToken, Cancelled},
+    metrics::Metrics,
+    ThreadId,
+};
 
 /// An error for types that implement [`Context`].
 #[derive(Debug, thiserror::Error)]
@@ -58,6 +62,22 @@ pub trait Context: Send + Sync {
     /// Returns a mutable reference to the thread's I/O channel.
     fn io_mut(&mut self) -> &mut Self::Io;
 
+    /// Returns the cancellation token of the thread.
+    ///
+    /// The token is shared with all forks of the thread and the other threads of its executor.
+    fn cancel_token(&self) -> &CancellationToken;
+
+    /// Returns the communication metrics of the thread's executor.
+    ///
+    /// The metrics are shared with all forks of the thread and the other threads of its executor,
+    /// use [`Metrics::thread`] to query the metrics of a single thread.
+    fn metrics(&self) -> &Metrics;
+
+    /// Returns a future which resolves once the thread is cancelled.
+    fn cancelled(&self) -> Cancelled {
+        self.cancel_token().cancelled()
+    }
+
     /// Executes a task that may block the thread.
     ///
     /// If CPU multi-threading is available, the task is executed on a separate thread. Otherwise,
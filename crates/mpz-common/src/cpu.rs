@@ -1,6 +1,7 @@
 //! CPU backend shim.
 
 use cfg_if::cfg_if;
+use futures::{future::BoxFuture, Future};
 
 cfg_if! {
     if #[cfg(feature = "force-st")] {
@@ -12,12 +13,51 @@ cfg_if! {
     }
 }
 
+/// A backend which CPU-bound work of an executor is offloaded to.
+///
+/// Implementations can integrate with the blocking pool of an async runtime. [`CpuBackend`] is
+/// used by default, and [`InlineBackend`] keeps all work on the thread of the executor.
+pub trait Offload: Send + Sync + 'static {
+    /// Executes a future on the backend.
+    fn offload<F>(&self, fut: F) -> BoxFuture<'static, F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send;
+}
+
+impl Offload for CpuBackend {
+    fn offload<F>(&self, fut: F) -> BoxFuture<'static, F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send,
+    {
+        Box::pin(CpuBackend::blocking_async(fut))
+    }
+}
+
+/// A backend which executes work inline on the thread of the executor.
+///
+/// This is intended for executors driven on a single thread, where offloading work to another
+/// thread is undesirable.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InlineBackend;
+
+impl Offload for InlineBackend {
+    fn offload<F>(&self, fut: F) -> BoxFuture<'static, F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send,
+    {
+        Box::pin(fut)
+    }
+}
+
 #[cfg(any(feature = "force-st", not(feature = "rayon")))]
 mod st {
     use futures::Future;
 
     /// A single-threaded CPU backend.
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy)]
     pub struct SingleThreadedBackend;
 
     impl SingleThreadedBackend {
@@ -67,7 +107,7 @@ mod rayon_backend {
     use pollster::block_on;
 
     /// A Rayon CPU backend.
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy)]
     pub struct RayonBackend;
 
     impl RayonBackend {
@@ -3,13 +3,18 @@ use async_trait::async_trait;
 use scoped_futures::ScopedBoxFuture;
 use serio::{Sink, Stream};
 
-use crate::{context::Context, cpu::CpuBackend, ContextError, ThreadId};
+use crate::{
+    cancel::CancellationToken, context::Context, cpu::CpuBackend, metrics::Metrics, ContextError,
+    ThreadId,
+};
 
 /// A dummy executor.
 #[derive(Debug, Default)]
 pub struct DummyExecutor {
     id: ThreadId,
     io: DummyIo,
+    cancel: CancellationToken,
+    metrics: Metrics,
 }
 
 /// A dummy I/O.
@@ -75,6 +80,14 @@ impl Context for DummyExecutor {
         &mut self.io
     }
 
+    fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
     async fn blocking<F, R>(&mut self, f: F) -> Result<R, ContextError>
     where
         F: for<'a> FnOnce(&'a mut Self) -> ScopedBoxFuture<'static, 'a, R> + Send + 'static,
@@ -83,6 +96,8 @@ impl Context for DummyExecutor {
         let mut ctx = Self {
             id: self.id.clone(),
             io: DummyIo,
+            cancel: self.cancel.clone(),
+            metrics: self.metrics.clone(),
         };
 
         Ok(CpuBackend::blocking_async(async move { f(&mut ctx).await }).await)
@@ -41,7 +41,123 @@ mod test_utils {
 
         (exec_0, exec_1)
     }
+
+    /// A party in a full mesh of test executors.
+    #[derive(Debug)]
+    pub struct TestParty<E> {
+        id: usize,
+        peers: Vec<Option<E>>,
+    }
+
+    impl<E> TestParty<E> {
+        /// Returns the index of this party in the mesh.
+        pub fn id(&self) -> usize {
+            self.id
+        }
+
+        /// Returns the executor connected to the given peer.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `peer` is this party or is out of bounds.
+        pub fn peer(&mut self, peer: usize) -> &mut E {
+            self.peers[peer]
+                .as_mut()
+                .expect("party is not connected to itself")
+        }
+
+        /// Returns an iterator over the peers of this party and their executors.
+        pub fn peers(&mut self) -> impl Iterator<Item = (usize, &mut E)> {
+            self.peers
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(id, exec)| exec.as_mut().map(|exec| (id, exec)))
+        }
+    }
+
+    /// Creates a full mesh of `n` parties, connecting every pair of parties with executors
+    /// created by `pair`.
+    fn mesh<E>(n: usize, mut pair: impl FnMut() -> (E, E)) -> Vec<TestParty<E>> {
+        assert!(n >= 2, "a mesh requires at least two parties");
+
+        let mut parties: Vec<_> = (0..n)
+            .map(|id| TestParty {
+                id,
+                peers: (0..n).map(|_| None).collect(),
+            })
+            .collect();
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let (exec_i, exec_j) = pair();
+                parties[i].peers[j] = Some(exec_i);
+                parties[j].peers[i] = Some(exec_j);
+            }
+        }
+
+        parties
+    }
+
+    /// Creates a full mesh of `n` parties, connected pairwise by single-threaded executors
+    /// with memory I/O channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of parties, at least 2.
+    /// * `io_buffer` - The size of the I/O buffer (channel capacity).
+    pub fn test_st_mesh(n: usize, io_buffer: usize) -> Vec<TestParty<TestSTExecutor>> {
+        mesh(n, || test_st_executor(io_buffer))
+    }
+
+    /// Creates a full mesh of `n` parties, connected pairwise by multi-threaded executors
+    /// with multiplexed I/O channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of parties, at least 2.
+    /// * `io_buffer` - The size of the I/O buffer (channel capacity).
+    pub fn test_mt_mesh(n: usize, io_buffer: usize) -> Vec<TestParty<TestMTExecutor>> {
+        mesh(n, || test_mt_executor(io_buffer))
+    }
 }
 
 #[cfg(any(test, feature = "test-utils"))]
 pub use test_utils::*;
+
+#[cfg(test)]
+mod tests {
+    use futures::future::join_all;
+    use serio::{stream::IoStreamExt, SinkExt};
+
+    use crate::Context;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_st_mesh_exchange() {
+        let parties = test_st_mesh(4, 8);
+
+        let received = join_all(parties.into_iter().map(|mut party| async move {
+            let id = party.id();
+
+            for (_, ctx) in party.peers() {
+                ctx.io_mut().send(id).await.unwrap();
+            }
+
+            let mut received = Vec::new();
+            for (peer, ctx) in party.peers() {
+                let msg: usize = ctx.io_mut().expect_next().await.unwrap();
+                assert_eq!(msg, peer);
+                received.push(msg);
+            }
+
+            received
+        }))
+        .await;
+
+        for (id, received) in received.into_iter().enumerate() {
+            let expected: Vec<usize> = (0..4).filter(|peer| *peer != id).collect();
+            assert_eq!(received, expected);
+        }
+    }
+}
@@ -1,25 +1,38 @@
-use std::pin::Pin;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use async_trait::async_trait;
-use futures::{stream::FuturesOrdered, Future, StreamExt};
+use futures::{future::join_all, stream::FuturesOrdered, Future, StreamExt};
 use scoped_futures::ScopedBoxFuture;
 use serio::IoDuplex;
 use uid_mux::FramedUidMux;
 
 use crate::{
+    cancel::CancellationToken,
     context::{ContextError, ErrorKind},
     cpu::CpuBackend,
+    metrics::{MeteredIo, Metrics},
     Context, ThreadId,
 };
 
+/// Maximum number of child threads of a single context.
 const MAX_THREADS: usize = 255;
 
 /// A multi-threaded executor.
 #[derive(Debug)]
 pub struct MTExecutor<M> {
-    id: ThreadId,
+    /// Index of the next top-level thread.
+    next: u64,
     mux: M,
-    max_concurrency: usize,
+    /// Max degree of concurrency, shared with all threads of the executor.
+    max_concurrency: Arc<AtomicUsize>,
+    cancel: CancellationToken,
+    metrics: Metrics,
 }
 
 impl<M> MTExecutor<M>
@@ -32,36 +45,81 @@ where
     /// # Arguments
     ///
     /// * `mux` - The multiplexer used by the executor.
-    /// * `concurrency` - The max degree of concurrency to use.
+    /// * `concurrency` - The initial max degree of concurrency to use, see
+    ///   [`set_max_concurrency`](Self::set_max_concurrency).
     pub fn new(mux: M, max_concurrency: usize) -> Self {
         Self {
-            id: ThreadId::default(),
+            next: 0,
             mux,
-            max_concurrency,
+            max_concurrency: Arc::new(AtomicUsize::new(max_concurrency)),
+            cancel: CancellationToken::new(),
+            metrics: Metrics::new(),
         }
     }
 
+    /// Returns the cancellation token shared by all threads of the executor.
+    ///
+    /// Cancelling the token cancels all threads, including those created afterwards.
+    pub fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// Returns the communication metrics shared by all threads of the executor.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Returns the max degree of concurrency of the threads.
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency.load(Ordering::Relaxed)
+    }
+
+    /// Sets the max degree of concurrency of the threads.
+    ///
+    /// This applies to existing threads as well as threads created afterwards. It is a hint of
+    /// how much work to run at once, a thread grows its pool of child threads on demand when
+    /// it is asked to run more tasks concurrently, see [`MTContext::map`].
+    pub fn set_max_concurrency(&mut self, max_concurrency: usize) {
+        self.max_concurrency
+            .store(max_concurrency, Ordering::Relaxed);
+    }
+
     /// Returns a future that yields a new thread context.
+    ///
+    /// Threads are created on demand, there is no limit on how many threads an executor can
+    /// create.
     pub fn new_thread(&mut self) -> NewThread<M, <M as FramedUidMux<ThreadId>>::Framed> {
-        let id = self.id.increment_in_place().ok_or_else(|| {
-            ContextError::new(
-                ErrorKind::Thread,
-                "exceeded maximum number of threads (255)",
-            )
-        });
+        let id = ThreadId::top_level(self.next);
+        self.spawn(id)
+    }
+
+    /// Returns a future that yields a new thread context with the provided label.
+    ///
+    /// The label is only used for tracing, see [`ThreadId::label`].
+    pub fn new_thread_with_label(
+        &mut self,
+        label: &str,
+    ) -> NewThread<M, <M as FramedUidMux<ThreadId>>::Framed> {
+        let id = ThreadId::top_level(self.next).with_label(label);
+        self.spawn(id)
+    }
+
+    fn spawn(&mut self, id: ThreadId) -> NewThread<M, <M as FramedUidMux<ThreadId>>::Framed> {
+        self.next += 1;
 
         let mux = self.mux.clone();
-        let concurrency = self.max_concurrency;
+        let concurrency = self.max_concurrency.clone();
+        let cancel = self.cancel.clone();
+        let metrics = self.metrics.clone();
 
         NewThread {
             fut: Box::pin(async move {
-                let id = id?;
                 let io = mux
                     .open_framed(&id)
                     .await
                     .map_err(|e| ContextError::new(ErrorKind::Mux, e))?;
 
-                Ok(MTContext::new(id, mux, io, concurrency))
+                Ok(MTContext::new(id, mux, io, concurrency, cancel, metrics))
             }),
         }
     }
@@ -96,28 +154,45 @@ pub struct MTContext<M, Io> {
     // `Option` to allow us to take the state out of the struct and send it
     // to another thread in `Context::blocking`.
     inner: Option<Inner<M, Io>>,
-    max_concurrency: usize,
+    max_concurrency: Arc<AtomicUsize>,
+    cancel: CancellationToken,
+    metrics: Metrics,
 }
 
 #[derive(Debug)]
 struct Inner<M, Io> {
-    io: Io,
+    io: MeteredIo<Io>,
     // Child threads are created lazily, and are cached for reuse.
     children: Children<M, Io>,
 }
 
 impl<M, Io> MTContext<M, Io> {
-    fn new(id: ThreadId, mux: M, io: Io, max_concurrency: usize) -> Self {
+    fn new(
+        id: ThreadId,
+        mux: M,
+        io: Io,
+        max_concurrency: Arc<AtomicUsize>,
+        cancel: CancellationToken,
+        metrics: Metrics,
+    ) -> Self {
         let child_id = id.fork();
+        let io = metrics.meter(&id, io);
 
         Self {
             id,
             mux,
             inner: Some(Inner {
                 io,
-                children: Children::new(child_id, max_concurrency),
+                children: Children::new(
+                    child_id,
+                    max_concurrency.clone(),
+                    cancel.clone(),
+                    metrics.clone(),
+                ),
             }),
             max_concurrency,
+            cancel,
+            metrics,
         }
     }
 
@@ -136,6 +211,51 @@ impl<M, Io> MTContext<M, Io> {
     }
 }
 
+impl<M, Io> MTContext<M, Io>
+where
+    M: FramedUidMux<ThreadId, Framed = Io> + Clone + Send + Sync + 'static,
+    M::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    Io: IoDuplex + Send + Sync + Unpin + 'static,
+{
+    /// Forks the thread and applies the provided closure to each item concurrently, each on its
+    /// own child thread.
+    ///
+    /// The pool of child threads grows on demand, so more items than the
+    /// [max concurrency](Context::max_concurrency) can be processed at once, up to 255.
+    pub async fn map<'a, I, F, R>(&'a mut self, items: Vec<I>, f: F) -> Result<Vec<R>, ContextError>
+    where
+        I: Send + 'a,
+        F: for<'b> Fn(&'b mut Self, I) -> ScopedBoxFuture<'a, 'b, R> + Send + Sync + 'a,
+        R: Send + 'a,
+    {
+        // We temporarily take the state to avoid borrowing issues.
+        let mut inner = self
+            .inner
+            .take()
+            .expect("context is never left uninitialized");
+
+        if inner.children.len() < items.len() {
+            if let Err(e) = inner.children.alloc(&self.mux, items.len()).await {
+                self.inner = Some(inner);
+                return Err(e);
+            }
+        }
+
+        let f = &f;
+        let output = join_all(
+            items
+                .into_iter()
+                .zip(inner.children.slots.iter_mut())
+                .map(|(item, child)| f(child, item)),
+        )
+        .await;
+
+        self.inner = Some(inner);
+
+        Ok(output)
+    }
+}
+
 #[async_trait]
 impl<M, Io> Context for MTContext<M, Io>
 where
@@ -143,7 +263,7 @@ where
     M::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     Io: IoDuplex + Send + Sync + Unpin + 'static,
 {
-    type Io = Io;
+    type Io = MeteredIo<Io>;
 
     fn id(&self) -> &ThreadId {
         &self.id
@@ -157,6 +277,14 @@ where
         &mut self.inner_mut().io
     }
 
+    fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
     async fn blocking<F, R>(&mut self, f: F) -> Result<R, ContextError>
     where
         F: for<'a> FnOnce(&'a mut Self) -> ScopedBoxFuture<'static, 'a, R> + Send + 'static,
@@ -166,7 +294,9 @@ where
             id: self.id.clone(),
             mux: self.mux.clone(),
             inner: self.inner.take(),
-            max_concurrency: self.max_concurrency,
+            max_concurrency: self.max_concurrency.clone(),
+            cancel: self.cancel.clone(),
+            metrics: self.metrics.clone(),
         };
 
         let (inner, output) = CpuBackend::blocking_async(async move {
@@ -248,20 +378,29 @@ where
 struct Children<M, Io> {
     id: ThreadId,
     slots: Vec<MTContext<M, Io>>,
-    max_concurrency: usize,
+    max_concurrency: Arc<AtomicUsize>,
+    cancel: CancellationToken,
+    metrics: Metrics,
 }
 
 impl<M, Io> Children<M, Io> {
-    fn new(id: ThreadId, max_concurrency: usize) -> Self {
+    fn new(
+        id: ThreadId,
+        max_concurrency: Arc<AtomicUsize>,
+        cancel: CancellationToken,
+        metrics: Metrics,
+    ) -> Self {
         Self {
             id,
             slots: Vec::new(),
             max_concurrency,
+            cancel,
+            metrics,
         }
     }
 
     fn max_concurrency(&self) -> usize {
-        self.max_concurrency
+        self.max_concurrency.load(Ordering::Relaxed)
     }
 }
 
@@ -299,7 +438,14 @@ where
                         .await
                         .map_err(|e| ContextError::new(ErrorKind::Mux, e))?;
 
-                    Ok(MTContext::new(id, mux.clone(), io, self.max_concurrency))
+                    Ok(MTContext::new(
+                        id,
+                        mux.clone(),
+                        io,
+                        self.max_concurrency.clone(),
+                        self.cancel.clone(),
+                        self.metrics.clone(),
+                    ))
                 });
             }
 
@@ -388,6 +534,86 @@ mod tests {
         assert!(ctx_b.inner.is_some());
     }
 
+    #[tokio::test]
+    async fn test_mt_executor_many_threads() {
+        let (mut exec_a, mut exec_b) = test_mt_executor(8);
+
+        let mut ids = std::collections::HashSet::new();
+        for _ in 0..300 {
+            let (mut ctx_a, mut ctx_b) =
+                futures::try_join!(exec_a.new_thread(), exec_b.new_thread()).unwrap();
+
+            assert_eq!(ctx_a.id(), ctx_b.id());
+            assert!(ids.insert(ctx_a.id().clone()));
+
+            // Child threads must not collide with other top-level threads either.
+            let (child_a, child_b) = ctx_a
+                .join(
+                    scoped!(|ctx| ctx.id().clone()),
+                    scoped!(|ctx| ctx.id().clone()),
+                )
+                .await
+                .unwrap();
+            assert!(ids.insert(child_a));
+            assert!(ids.insert(child_b));
+
+            let (_, received) = futures::try_join!(ctx_a.io_mut().send(1u8), async {
+                ctx_b.io_mut().expect_next::<u8>().await
+            })
+            .unwrap();
+            assert_eq!(received, 1u8);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mt_executor_label() {
+        let (mut exec_a, _) = test_mt_executor(8);
+
+        exec_a.set_max_concurrency(16);
+        let mut ctx = exec_a.new_thread_with_label("deap").await.unwrap();
+
+        assert_eq!(ctx.id().label(), Some("deap"));
+        assert_eq!(ctx.max_concurrency(), 16);
+
+        let (label, _) = ctx
+            .join(
+                scoped!(|ctx| ctx.id().label().map(String::from)),
+                scoped!(|_ctx| ()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(label.as_deref(), Some("deap"));
+    }
+
+    #[tokio::test]
+    async fn test_mt_executor_cancel() {
+        let (mut exec_a, _) = test_mt_executor(8);
+
+        let mut ctx = exec_a.new_thread().await.unwrap();
+        let cancel = exec_a.cancel_token().clone();
+
+        // The peer never sends, so the receive would otherwise hang forever.
+        let (received, _) = ctx
+            .join(
+                scoped!(|ctx| async move {
+                    let cancel = ctx.cancel_token().clone();
+                    cancel.run(ctx.io_mut().expect_next::<u8>()).await
+                }),
+                scoped!(|_ctx| cancel.cancel()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(received.unwrap_err(), crate::cancel::CancelledError);
+        assert!(ctx.cancel_token().is_cancelled());
+        assert!(exec_a
+            .new_thread()
+            .await
+            .unwrap()
+            .cancel_token()
+            .is_cancelled());
+    }
+
     #[tokio::test]
     // Tests that the mt executor polls futures concurrently.
     async fn test_mt_executor_concurrency() {
@@ -421,4 +647,41 @@ mod tests {
         .unwrap()
         .unwrap();
     }
+
+    #[tokio::test]
+    // Tests that a running context can execute more tasks concurrently than its max
+    // concurrency.
+    async fn test_mt_executor_oversubscribe() {
+        let (mut exec_a, _) = test_mt_executor(8);
+
+        let mut ctx = exec_a.new_thread().await.unwrap();
+
+        // Existing threads follow the executor's concurrency.
+        exec_a.set_max_concurrency(2);
+        assert_eq!(ctx.max_concurrency(), 2);
+
+        // The tasks wait on each other, so they would deadlock unless all of them are polled
+        // concurrently.
+        let barrier = Arc::new(Barrier::new(16));
+        let ids = ctx
+            .map(
+                vec![barrier; 16],
+                scoped!(|ctx, barrier| async move {
+                    barrier.wait().await;
+                    ctx.id().clone()
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            ids.iter().collect::<std::collections::HashSet<_>>().len(),
+            16
+        );
+
+        // The pool is capped.
+        assert!(ctx
+            .map(vec![(); MAX_THREADS + 1], scoped!(|_ctx, _item| ()))
+            .await
+            .is_err());
+    }
 }
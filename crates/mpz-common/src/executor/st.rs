@@ -4,23 +4,36 @@ use scoped_futures::ScopedBoxFuture;
 use serio::{IoSink, IoStream};
 
 use crate::{
+    cancel::CancellationToken,
     context::{Context, ContextError},
-    cpu::CpuBackend,
+    cpu::{CpuBackend, Offload},
+    metrics::{MeteredIo, Metrics},
     ThreadId,
 };
 
 /// A single-threaded executor.
-pub struct STExecutor<Io> {
+///
+/// The executor does not spawn any tasks, so it can be driven by any async runtime. CPU-bound
+/// work in [`Context::blocking`] is offloaded to the provided [`Offload`] backend, which is
+/// [`CpuBackend`] by default. Use [`InlineBackend`](crate::cpu::InlineBackend) to run it on the
+/// thread of the executor instead.
+///
+/// [`Context`] requires the executor and its I/O channel to be `Send`, so `!Send` I/O types are
+/// not supported.
+pub struct STExecutor<Io, O = CpuBackend> {
     id: ThreadId,
     // Ideally "scoped futures" would exist, but they don't, so we use an
     // `Option` to allow us to take the state out of the struct and send it
     // to another thread in `Context::blocking`.
     inner: Option<Inner<Io>>,
+    offload: O,
+    cancel: CancellationToken,
+    metrics: Metrics,
 }
 
 #[derive(Debug)]
 struct Inner<Io> {
-    io: Io,
+    io: MeteredIo<Io>,
 }
 
 impl<Io> STExecutor<Io>
@@ -34,12 +47,45 @@ where
     /// * `io` - The I/O channel used by the executor.
     #[inline]
     pub fn new(io: Io) -> Self {
+        Self::with_offload(io, CpuBackend)
+    }
+}
+
+impl<Io, O> STExecutor<Io, O>
+where
+    Io: IoSink + IoStream + Send + Unpin + 'static,
+    O: Offload,
+{
+    /// Creates a new single-threaded executor which offloads CPU-bound work to the provided
+    /// backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `io` - The I/O channel used by the executor.
+    /// * `offload` - The backend for CPU-bound work.
+    #[inline]
+    pub fn with_offload(io: Io, offload: O) -> Self {
+        let id = ThreadId::default();
+        let metrics = Metrics::new();
+        let io = metrics.meter(&id, io);
+
         Self {
-            id: ThreadId::default(),
+            id,
             inner: Some(Inner { io }),
+            offload,
+            cancel: CancellationToken::new(),
+            metrics,
         }
     }
 
+    /// Sets the cancellation token of the executor.
+    ///
+    /// This allows the token to be shared with other executors or with the code which owns the
+    /// connection to the peer.
+    pub fn set_cancel_token(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+
     #[inline]
     fn inner(&mut self) -> &mut Inner<Io> {
         self.inner
@@ -49,11 +95,12 @@ where
 }
 
 #[async_trait]
-impl<Io> Context for STExecutor<Io>
+impl<Io, O> Context for STExecutor<Io, O>
 where
     Io: IoSink + IoStream + Send + Sync + Unpin + 'static,
+    O: Offload + Clone,
 {
-    type Io = Io;
+    type Io = MeteredIo<Io>;
 
     fn id(&self) -> &ThreadId {
         &self.id
@@ -67,6 +114,14 @@ where
         &mut self.inner().io
     }
 
+    fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
     async fn blocking<F, R>(&mut self, f: F) -> Result<R, ContextError>
     where
         F: for<'a> FnOnce(&'a mut Self) -> ScopedBoxFuture<'static, 'a, R> + Send + 'static,
@@ -75,13 +130,18 @@ where
         let mut ctx = Self {
             id: self.id.clone(),
             inner: self.inner.take(),
+            offload: self.offload.clone(),
+            cancel: self.cancel.clone(),
+            metrics: self.metrics.clone(),
         };
 
-        let (inner, output) = CpuBackend::blocking_async(async move {
-            let output = f(&mut ctx).await;
-            (ctx.inner, output)
-        })
-        .await;
+        let (inner, output) = self
+            .offload
+            .offload(async move {
+                let output = f(&mut ctx).await;
+                (ctx.inner, output)
+            })
+            .await;
 
         self.inner = inner;
 
@@ -177,4 +237,46 @@ mod tests {
             assert!(ctx.inner.is_some());
         });
     }
+
+    #[test]
+    fn test_st_executor_local_runtime() {
+        use std::{rc::Rc, thread};
+
+        use futures::{executor::LocalPool, task::LocalSpawnExt};
+        use serio::{stream::IoStreamExt, SinkExt};
+
+        use crate::cpu::InlineBackend;
+
+        let (io_0, io_1) = duplex(1);
+        let mut ctx_0 = STExecutor::with_offload(io_0, InlineBackend);
+        let mut ctx_1 = STExecutor::with_offload(io_1, InlineBackend);
+
+        let mut pool = LocalPool::new();
+        let spawner = pool.spawner();
+        let thread_id = thread::current().id();
+
+        // The tasks hold an `Rc`, so they can only run on a local executor.
+        let state = Rc::new(42u8);
+        let state_0 = state.clone();
+        spawner
+            .spawn_local(async move {
+                ctx_0.io_mut().send(*state_0).await.unwrap();
+            })
+            .unwrap();
+
+        spawner
+            .spawn_local(async move {
+                let msg: u8 = ctx_1.io_mut().expect_next().await.unwrap();
+                assert_eq!(msg, *state);
+
+                let offload_thread_id = ctx_1
+                    .blocking(scoped!(|_ctx| thread::current().id()))
+                    .await
+                    .unwrap();
+                assert_eq!(offload_thread_id, thread_id);
+            })
+            .unwrap();
+
+        pool.run();
+    }
 }
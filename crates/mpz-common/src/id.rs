@@ -1,14 +1,24 @@
 use core::fmt;
+use std::{cmp::Ordering, hash::Hash, sync::Arc};
+
+/// Prefix of the IDs of top-level threads which do not fit in a single byte.
+const EXTENDED_PREFIX: u8 = u8::MAX;
 
 /// A logical thread identifier.
 ///
 /// Every thread is assigned a unique identifier, which can be forked to create a child thread.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ThreadId(Box<[u8]>);
+///
+/// A thread ID can carry a human-readable label for tracing. The label is inherited by forked and
+/// incremented IDs, and does not take part in comparisons or hashing.
+#[derive(Debug, Clone)]
+pub struct ThreadId {
+    id: Box<[u8]>,
+    label: Option<Arc<str>>,
+}
 
 impl Default for ThreadId {
     fn default() -> Self {
-        Self(vec![0].into())
+        Self::new(0)
     }
 }
 
@@ -16,13 +26,48 @@ impl ThreadId {
     /// Creates a new thread ID with the provided ID.
     #[inline]
     pub fn new(id: u8) -> Self {
-        Self(vec![id].into())
+        Self {
+            id: vec![id].into(),
+            label: None,
+        }
+    }
+
+    /// Returns the ID of the `index`-th top-level thread.
+    ///
+    /// The first 255 threads have single byte IDs. Later threads are prefixed with `0xff`, which
+    /// is never assigned as a single byte ID, followed by the index in big-endian, so they can not
+    /// collide with the others or their children.
+    pub(crate) fn top_level(index: u64) -> Self {
+        if index < EXTENDED_PREFIX as u64 {
+            return Self::new(index as u8);
+        }
+
+        let mut id = Vec::with_capacity(9);
+        id.push(EXTENDED_PREFIX);
+        id.extend_from_slice(&index.to_be_bytes());
+
+        Self {
+            id: id.into(),
+            label: None,
+        }
+    }
+
+    /// Returns the thread ID with the provided label.
+    pub fn with_label(mut self, label: impl Into<Arc<str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Returns the label of the thread, if any.
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
     }
 
     /// Returns the thread ID as a byte slice.
     #[inline]
     pub fn as_bytes(&self) -> &[u8] {
-        &self.0
+        &self.id
     }
 
     /// Increments the thread ID, returning `None` if the ID overflows.
@@ -30,7 +75,7 @@ impl ThreadId {
     pub fn increment(&self) -> Option<Self> {
         let mut next = self.clone();
 
-        let id = next.0.last_mut()?;
+        let id = next.id.last_mut()?;
         *id = id.checked_add(1)?;
 
         Some(next)
@@ -41,7 +86,7 @@ impl ThreadId {
     pub fn increment_in_place(&mut self) -> Option<Self> {
         let prev = self.clone();
 
-        let id = self.0.last_mut()?;
+        let id = self.id.last_mut()?;
         *id = id.checked_add(1)?;
 
         Some(prev)
@@ -50,10 +95,39 @@ impl ThreadId {
     /// Forks the thread ID.
     #[inline]
     pub fn fork(&self) -> Self {
-        let mut id = vec![0; self.0.len() + 1];
-        id[0..self.0.len()].copy_from_slice(&self.0);
+        let mut id = vec![0; self.id.len() + 1];
+        id[0..self.id.len()].copy_from_slice(&self.id);
 
-        Self(id.into())
+        Self {
+            id: id.into(),
+            label: self.label.clone(),
+        }
+    }
+}
+
+impl PartialEq for ThreadId {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for ThreadId {}
+
+impl PartialOrd for ThreadId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ThreadId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Hash for ThreadId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
     }
 }
 
@@ -66,13 +140,21 @@ impl AsRef<[u8]> for ThreadId {
 
 impl fmt::Display for ThreadId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, byte) in self.0.iter().enumerate() {
+        if let Some(label) = &self.label {
+            write!(f, "{label} (")?;
+        }
+
+        for (i, byte) in self.id.iter().enumerate() {
             if i > 0 {
                 write!(f, "/")?;
             }
             write!(f, "{}", byte)?;
         }
 
+        if self.label.is_some() {
+            write!(f, ")")?;
+        }
+
         Ok(())
     }
 }
@@ -116,4 +198,25 @@ mod tests {
         assert_eq!(id.increment().unwrap().as_bytes(), &[2]);
         assert_eq!(id.fork().as_bytes(), &[1, 0]);
     }
+
+    #[test]
+    fn test_thread_id_label() {
+        let id = ThreadId::new(1).fork().with_label("deap");
+        let child = id.fork();
+
+        assert_eq!(id, ThreadId::new(1).fork());
+        assert_eq!(child.label(), Some("deap"));
+        assert_eq!(id.to_string(), "deap (1/0)");
+        assert_eq!(ThreadId::new(1).to_string(), "1");
+    }
+
+    #[test]
+    fn test_thread_id_top_level() {
+        assert_eq!(ThreadId::top_level(3), ThreadId::new(3));
+        assert_eq!(
+            ThreadId::top_level(255).as_bytes(),
+            &[255, 0, 0, 0, 0, 0, 0, 0, 255]
+        );
+        assert_ne!(ThreadId::top_level(255).fork(), ThreadId::top_level(256));
+    }
 }
@@ -1,16 +1,39 @@
 //! Ideal functionality utilities.
+//!
+//! Ideal functionalities model the latency of the protocols they stand in for. Each call
+//! consumes a number of communication rounds, which are counted so that tests can assert the
+//! round complexity of protocols built on top of them. A functionality has a default latency,
+//! which calls standing in for cheaper or more expensive operations can override.
 
 use futures::channel::oneshot;
 use std::{
     any::Any,
     collections::HashMap,
-    sync::{Arc, Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
 };
 
 use crate::{Context, ThreadId};
 
 type BoxAny = Box<dyn Any + Send + 'static>;
 
+/// Counts the communication rounds consumed by calls to an ideal functionality.
+#[derive(Debug)]
+struct Rounds {
+    /// The number of rounds consumed by a call which does not specify its latency.
+    latency: usize,
+    /// The number of rounds consumed so far.
+    count: AtomicUsize,
+}
+
+impl Rounds {
+    fn record(&self, latency: usize) {
+        self.count.fetch_add(latency, Ordering::Relaxed);
+    }
+}
+
 #[derive(Debug, Default)]
 struct Buffer {
     alice: HashMap<ThreadId, (BoxAny, oneshot::Sender<BoxAny>)>,
@@ -22,6 +45,7 @@ struct Buffer {
 pub struct Alice<F> {
     f: Arc<Mutex<F>>,
     buffer: Arc<Mutex<Buffer>>,
+    rounds: Arc<Rounds>,
 }
 
 impl<F> Clone for Alice<F> {
@@ -29,6 +53,7 @@ impl<F> Clone for Alice<F> {
         Self {
             f: self.f.clone(),
             buffer: self.buffer.clone(),
+            rounds: self.rounds.clone(),
         }
     }
 }
@@ -39,8 +64,42 @@ impl<F> Alice<F> {
         self.f.lock().unwrap()
     }
 
-    /// Calls the ideal functionality.
+    /// Returns the number of communication rounds consumed by calls to the functionality.
+    ///
+    /// The count is shared by both parties and all threads.
+    pub fn rounds(&self) -> usize {
+        self.rounds.count.load(Ordering::Relaxed)
+    }
+
+    /// Resets the number of communication rounds consumed to zero.
+    pub fn reset_rounds(&self) {
+        self.rounds.count.store(0, Ordering::Relaxed);
+    }
+
+    /// Calls the ideal functionality, consuming the default number of communication rounds.
     pub async fn call<Ctx, C, IA, IB, OA, OB>(&mut self, ctx: &mut Ctx, input: IA, call: C) -> OA
+    where
+        Ctx: Context,
+        C: FnOnce(&mut F, IA, IB) -> (OA, OB),
+        IA: Send + 'static,
+        IB: Send + 'static,
+        OA: Send + 'static,
+        OB: Send + 'static,
+    {
+        let latency = self.rounds.latency;
+        self.call_with_latency(ctx, input, latency, call).await
+    }
+
+    /// Calls the ideal functionality, consuming `latency` communication rounds.
+    ///
+    /// Both parties must pass the same latency.
+    pub async fn call_with_latency<Ctx, C, IA, IB, OA, OB>(
+        &mut self,
+        ctx: &mut Ctx,
+        input: IA,
+        latency: usize,
+        call: C,
+    ) -> OA
     where
         Ctx: Context,
         C: FnOnce(&mut F, IA, IB) -> (OA, OB),
@@ -60,6 +119,7 @@ impl<F> Alice<F> {
                     call(&mut self.f.lock().unwrap(), input, input_bob);
 
                 _ = ret_bob.send(Box::new(output_bob));
+                self.rounds.record(latency);
 
                 return output_alice;
             }
@@ -83,6 +143,7 @@ impl<F> Alice<F> {
 pub struct Bob<F> {
     f: Arc<Mutex<F>>,
     buffer: Arc<Mutex<Buffer>>,
+    rounds: Arc<Rounds>,
 }
 
 impl<F> Clone for Bob<F> {
@@ -90,6 +151,7 @@ impl<F> Clone for Bob<F> {
         Self {
             f: self.f.clone(),
             buffer: self.buffer.clone(),
+            rounds: self.rounds.clone(),
         }
     }
 }
@@ -100,8 +162,42 @@ impl<F> Bob<F> {
         self.f.lock().unwrap()
     }
 
-    /// Calls the ideal functionality.
+    /// Returns the number of communication rounds consumed by calls to the functionality.
+    ///
+    /// The count is shared by both parties and all threads.
+    pub fn rounds(&self) -> usize {
+        self.rounds.count.load(Ordering::Relaxed)
+    }
+
+    /// Resets the number of communication rounds consumed to zero.
+    pub fn reset_rounds(&self) {
+        self.rounds.count.store(0, Ordering::Relaxed);
+    }
+
+    /// Calls the ideal functionality, consuming the default number of communication rounds.
     pub async fn call<Ctx, C, IA, IB, OA, OB>(&mut self, ctx: &mut Ctx, input: IB, call: C) -> OB
+    where
+        Ctx: Context,
+        C: FnOnce(&mut F, IA, IB) -> (OA, OB),
+        IA: Send + 'static,
+        IB: Send + 'static,
+        OA: Send + 'static,
+        OB: Send + 'static,
+    {
+        let latency = self.rounds.latency;
+        self.call_with_latency(ctx, input, latency, call).await
+    }
+
+    /// Calls the ideal functionality, consuming `latency` communication rounds.
+    ///
+    /// Both parties must pass the same latency.
+    pub async fn call_with_latency<Ctx, C, IA, IB, OA, OB>(
+        &mut self,
+        ctx: &mut Ctx,
+        input: IB,
+        latency: usize,
+        call: C,
+    ) -> OB
     where
         Ctx: Context,
         C: FnOnce(&mut F, IA, IB) -> (OA, OB),
@@ -121,6 +217,7 @@ impl<F> Bob<F> {
                     call(&mut self.f.lock().unwrap(), input_alice, input);
 
                 _ = ret_alice.send(Box::new(output_alice));
+                self.rounds.record(latency);
 
                 return output_bob;
             }
@@ -140,16 +237,35 @@ impl<F> Bob<F> {
 }
 
 /// Creates an ideal functionality, returning the perspectives of Alice and Bob.
+///
+/// Each call to the functionality consumes a single communication round.
 pub fn ideal_f2p<F>(f: F) -> (Alice<F>, Bob<F>) {
+    ideal_f2p_with_latency(f, 1)
+}
+
+/// Creates an ideal functionality with the given default latency, returning the perspectives of
+/// Alice and Bob.
+///
+/// # Arguments
+///
+/// * `f` - The ideal functionality.
+/// * `latency` - The number of communication rounds consumed by a call which does not specify
+///   its own latency.
+pub fn ideal_f2p_with_latency<F>(f: F, latency: usize) -> (Alice<F>, Bob<F>) {
     let f = Arc::new(Mutex::new(f));
     let buffer = Arc::new(Mutex::new(Buffer::default()));
+    let rounds = Arc::new(Rounds {
+        latency,
+        count: AtomicUsize::new(0),
+    });
 
     (
         Alice {
             f: f.clone(),
             buffer: buffer.clone(),
+            rounds: rounds.clone(),
         },
-        Bob { f, buffer },
+        Bob { f, buffer, rounds },
     )
 }
 
@@ -175,6 +291,52 @@ mod test {
         assert_eq!(output_b, 3);
     }
 
+    #[test]
+    fn test_ideal_rounds() {
+        let (mut alice, mut bob) = ideal_f2p_with_latency((), 2);
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+
+        futures::executor::block_on(async {
+            for _ in 0..3 {
+                futures::join!(
+                    alice.call(&mut ctx_a, (), |&mut (), (), ()| ((), ())),
+                    bob.call(&mut ctx_b, (), |&mut (), (), ()| ((), ())),
+                );
+            }
+        });
+
+        assert_eq!(alice.rounds(), 6);
+        assert_eq!(bob.rounds(), 6);
+
+        alice.reset_rounds();
+
+        assert_eq!(bob.rounds(), 0);
+    }
+
+    #[test]
+    fn test_ideal_rounds_per_call() {
+        let (mut alice, mut bob) = ideal_f2p_with_latency((), 2);
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+
+        futures::executor::block_on(async {
+            futures::join!(
+                alice.call(&mut ctx_a, (), |&mut (), (), ()| ((), ())),
+                bob.call(&mut ctx_b, (), |&mut (), (), ()| ((), ())),
+            );
+            futures::join!(
+                alice.call_with_latency(&mut ctx_a, (), 0, |&mut (), (), ()| ((), ())),
+                bob.call_with_latency(&mut ctx_b, (), 0, |&mut (), (), ()| ((), ())),
+            );
+            futures::join!(
+                alice.call_with_latency(&mut ctx_a, (), 1, |&mut (), (), ()| ((), ())),
+                bob.call_with_latency(&mut ctx_b, (), 1, |&mut (), (), ()| ((), ())),
+            );
+        });
+
+        assert_eq!(alice.rounds(), 3);
+        assert_eq!(bob.rounds(), 3);
+    }
+
     #[test]
     #[should_panic]
     fn test_ideal_wrong_input_type() {
@@ -3,7 +3,8 @@
 //! This crate provides various common functionalities needed for modeling protocol execution, I/O,
 //! and multi-threading.
 //!
-//! This crate does not provide any cryptographic primitives, see `mpz-core` for that.
+//! This crate does not provide any cryptographic primitives, see `mpz-core` for that. The only
+//! exception is [`seal`], which encrypts secret protocol artifacts at rest.
 
 #![deny(
     unsafe_code,
@@ -14,14 +15,24 @@
     clippy::all
 )]
 
+pub mod beacon;
+pub mod cancel;
+pub mod codec;
 mod context;
 pub mod cpu;
 pub mod executor;
 mod id;
 #[cfg(any(test, feature = "ideal"))]
 pub mod ideal;
+pub mod metrics;
+pub mod poison;
+#[cfg(feature = "seal")]
+pub mod seal;
+pub mod store;
 #[cfg(feature = "sync")]
 pub mod sync;
+pub mod tag;
+pub mod timeout;
 
 use async_trait::async_trait;
 pub use context::{Context, ContextError};
@@ -36,6 +47,24 @@ pub trait Allocate {
     fn alloc(&mut self, count: usize);
 }
 
+/// A functionality which must be set up before it can be used.
+///
+/// Protocol cores use the type-state pattern so that using them before setup is caught at compile
+/// time. IO wrappers which hold their state internally implement this trait instead, so that
+/// readiness can be checked ahead of time rather than encountering a state error.
+pub trait Ready {
+    /// Returns `true` if the functionality has been set up and is ready to use.
+    fn is_ready(&self) -> bool;
+}
+
+/// A functionality whose state can be poisoned by an operation which did not complete.
+///
+/// A poisoned functionality can not be used again, see [`poison`] for details.
+pub trait Poison {
+    /// Returns `true` if the functionality is poisoned.
+    fn is_poisoned(&self) -> bool;
+}
+
 /// A functionality in the pre-processing model.
 #[async_trait]
 pub trait Preprocess<Ctx>: Allocate {
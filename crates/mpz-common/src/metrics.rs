@@ -0,0 +1,414 @@
+//! Communication metrics of thread contexts.
+//!
+//! Every executor wraps the I/O channel of its threads in a [`MeteredIo`], which records the
+//! traffic of the thread in the [`Metrics`] shared by all threads of the executor. The metrics are
+//! queried with [`Context::metrics`](crate::Context::metrics), for example to compare the
+//! bandwidth and round complexity of protocol variants.
+//!
+//! A round is counted each time a thread receives a message after having sent one, which is the
+//! number of times it had to wait on the peer.
+//!
+//! The size of a message is its length when serialized with bincode, which is the size of the
+//! frame payload with the bincode codec. A received message is already deserialized when it
+//! reaches the context, so only the sender can measure its size: the bytes received on a thread
+//! are the bytes sent by the peer on the same thread.
+//!
+//! A thread is listed in the metrics while its I/O channel is alive. Once the channel is dropped,
+//! usually together with its context, the entry of the thread is removed and its traffic is only
+//! kept in [`Metrics::total`]. The metrics of an executor which spawns many short-lived threads
+//! therefore stay bounded.
+
+use core::{
+    ops::{Add, AddAssign},
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use serio::{Sink, Stream};
+
+use crate::ThreadId;
+
+/// Communication metrics of a thread.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadMetrics {
+    /// Number of messages sent.
+    pub messages_sent: u64,
+    /// Number of messages received.
+    pub messages_received: u64,
+    /// Number of bytes sent.
+    pub bytes_sent: u64,
+    /// Number of flushes of the I/O channel.
+    pub flushes: u64,
+    /// Number of communication rounds.
+    pub rounds: u64,
+}
+
+impl Add for ThreadMetrics {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for ThreadMetrics {
+    fn add_assign(&mut self, rhs: Self) {
+        self.messages_sent += rhs.messages_sent;
+        self.messages_received += rhs.messages_received;
+        self.bytes_sent += rhs.bytes_sent;
+        self.flushes += rhs.flushes;
+        self.rounds += rhs.rounds;
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    flushes: AtomicU64,
+    rounds: AtomicU64,
+    // Whether a message was sent since the last message was received.
+    sent: AtomicBool,
+}
+
+impl Counters {
+    fn get(&self) -> ThreadMetrics {
+        ThreadMetrics {
+            messages_sent: self.messages_sent.load(Ordering::Relaxed),
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            flushes: self.flushes.load(Ordering::Relaxed),
+            rounds: self.rounds.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.messages_sent.store(0, Ordering::Relaxed);
+        self.messages_received.store(0, Ordering::Relaxed);
+        self.bytes_sent.store(0, Ordering::Relaxed);
+        self.flushes.store(0, Ordering::Relaxed);
+        self.rounds.store(0, Ordering::Relaxed);
+        self.sent.store(false, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    threads: BTreeMap<ThreadId, Arc<Counters>>,
+    /// Traffic of the threads whose I/O channel was dropped.
+    retired: ThreadMetrics,
+}
+
+/// Communication metrics of the threads of an executor.
+///
+/// Clones of the metrics share their state.
+#[derive(Debug, Default, Clone)]
+pub struct Metrics(Arc<Mutex<Inner>>);
+
+impl Metrics {
+    /// Creates new empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the metrics of a thread, if its I/O channel is alive.
+    pub fn thread(&self, id: &ThreadId) -> Option<ThreadMetrics> {
+        self.0
+            .lock()
+            .unwrap()
+            .threads
+            .get(id)
+            .map(|counters| counters.get())
+    }
+
+    /// Returns the metrics of every thread whose I/O channel is alive, ordered by thread ID.
+    pub fn threads(&self) -> Vec<(ThreadId, ThreadMetrics)> {
+        self.0
+            .lock()
+            .unwrap()
+            .threads
+            .iter()
+            .map(|(id, counters)| (id.clone(), counters.get()))
+            .collect()
+    }
+
+    /// Returns the sum of the metrics of all threads, including the threads which were dropped.
+    pub fn total(&self) -> ThreadMetrics {
+        let inner = self.0.lock().unwrap();
+        inner
+            .threads
+            .values()
+            .fold(inner.retired, |total, counters| total + counters.get())
+    }
+
+    /// Resets the metrics of all threads to zero.
+    pub fn reset(&self) {
+        let mut inner = self.0.lock().unwrap();
+        inner.threads.values().for_each(|counters| counters.reset());
+        inner.retired = ThreadMetrics::default();
+    }
+
+    /// Wraps the I/O channel of a thread so that its traffic is recorded.
+    pub(crate) fn meter<Io>(&self, id: &ThreadId, io: Io) -> MeteredIo<Io> {
+        let counters = self
+            .0
+            .lock()
+            .unwrap()
+            .threads
+            .entry(id.clone())
+            .or_default()
+            .clone();
+
+        MeteredIo {
+            io,
+            registration: Registration {
+                metrics: self.clone(),
+                id: id.clone(),
+                counters,
+            },
+        }
+    }
+}
+
+/// The entry of a thread in [`Metrics`], which is retired when the I/O channel is dropped.
+#[derive(Debug)]
+struct Registration {
+    metrics: Metrics,
+    id: ThreadId,
+    counters: Arc<Counters>,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut inner = self.metrics.0.lock().unwrap();
+
+        // Channels of the same thread share their counters, which are only retired with the last
+        // of them. Counters are only cloned under the lock, so the count is accurate.
+        if Arc::strong_count(&self.counters) == 2 {
+            inner.threads.remove(&self.id);
+            inner.retired += self.counters.get();
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// An I/O channel which records its traffic in [`Metrics`].
+    #[derive(Debug)]
+    pub struct MeteredIo<Io> {
+        #[pin]
+        io: Io,
+        registration: Registration,
+    }
+}
+
+impl<Io> MeteredIo<Io> {
+    /// Returns a reference to the inner I/O channel.
+    pub fn get_ref(&self) -> &Io {
+        &self.io
+    }
+
+    /// Returns a mutable reference to the inner I/O channel.
+    ///
+    /// Traffic sent or received directly on the inner channel is not recorded.
+    pub fn get_mut(&mut self) -> &mut Io {
+        &mut self.io
+    }
+}
+
+impl<Io: Sink> Sink for MeteredIo<Io> {
+    type Error = Io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().io.poll_ready(cx)
+    }
+
+    fn start_send<Item: serio::Serialize>(
+        self: Pin<&mut Self>,
+        item: Item,
+    ) -> Result<(), Self::Error> {
+        let this = self.project();
+        let len = bincode::serialized_size(&item).unwrap_or_default();
+
+        this.io.start_send(item)?;
+
+        this.registration
+            .counters
+            .messages_sent
+            .fetch_add(1, Ordering::Relaxed);
+        this.registration
+            .counters
+            .bytes_sent
+            .fetch_add(len, Ordering::Relaxed);
+        this.registration
+            .counters
+            .sent
+            .store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let poll = this.io.poll_flush(cx);
+
+        if let Poll::Ready(Ok(())) = poll {
+            this.registration
+                .counters
+                .flushes
+                .fetch_add(1, Ordering::Relaxed);
+        }
+
+        poll
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().io.poll_close(cx)
+    }
+}
+
+impl<Io: Stream> Stream for MeteredIo<Io> {
+    type Error = Io::Error;
+
+    fn poll_next<Item: serio::Deserialize>(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<Item, Self::Error>>> {
+        let this = self.project();
+        let poll = this.io.poll_next(cx);
+
+        if let Poll::Ready(Some(Ok(_))) = &poll {
+            this.registration
+                .counters
+                .messages_received
+                .fetch_add(1, Ordering::Relaxed);
+            if this
+                .registration
+                .counters
+                .sent
+                .swap(false, Ordering::Relaxed)
+            {
+                this.registration
+                    .counters
+                    .rounds
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serio::{stream::IoStreamExt, SinkExt};
+
+    use crate::{
+        executor::{test_mt_executor, test_st_executor},
+        Context,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_st() {
+        let (mut ctx_0, mut ctx_1) = test_st_executor(8);
+
+        // Two round trips, the second one with two messages in each direction.
+        ctx_0.io_mut().send(1u64).await.unwrap();
+        let _: u64 = ctx_1.io_mut().expect_next().await.unwrap();
+        ctx_1.io_mut().send(2u64).await.unwrap();
+        let _: u64 = ctx_0.io_mut().expect_next().await.unwrap();
+
+        ctx_0.io_mut().feed([0u8; 4]).await.unwrap();
+        ctx_0.io_mut().send([0u8; 4]).await.unwrap();
+        let _: [u8; 4] = ctx_1.io_mut().expect_next().await.unwrap();
+        let _: [u8; 4] = ctx_1.io_mut().expect_next().await.unwrap();
+
+        let metrics = ctx_0.metrics().thread(ctx_0.id()).unwrap();
+        assert_eq!(
+            metrics,
+            ThreadMetrics {
+                messages_sent: 3,
+                messages_received: 1,
+                bytes_sent: 16,
+                flushes: 2,
+                rounds: 1,
+            }
+        );
+
+        let metrics = ctx_1.metrics().total();
+        assert_eq!(metrics.messages_sent, 1);
+        assert_eq!(metrics.messages_received, 3);
+        assert_eq!(metrics.bytes_sent, 8);
+        assert_eq!(metrics.rounds, 1);
+
+        ctx_0.metrics().reset();
+        assert_eq!(ctx_0.metrics().total(), ThreadMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_retired() {
+        let (mut ctx_0, mut ctx_1) = test_st_executor(8);
+
+        ctx_0.io_mut().send(1u64).await.unwrap();
+        let _: u64 = ctx_1.io_mut().expect_next().await.unwrap();
+
+        let id = ctx_0.id().clone();
+        let metrics = ctx_0.metrics().clone();
+        assert!(metrics.thread(&id).is_some());
+
+        // The thread is removed with its context, but its traffic remains in the total.
+        drop(ctx_0);
+
+        assert_eq!(metrics.thread(&id), None);
+        assert!(metrics.threads().is_empty());
+        assert_eq!(metrics.total().messages_sent, 1);
+        assert_eq!(metrics.total().bytes_sent, 8);
+
+        metrics.reset();
+        assert_eq!(metrics.total(), ThreadMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_mt() {
+        let (mut exec_0, mut exec_1) = test_mt_executor(8);
+
+        let (mut ctx_0, mut ctx_1) =
+            futures::try_join!(exec_0.new_thread(), exec_1.new_thread()).unwrap();
+
+        let (res_0, res_1) = futures::join!(
+            ctx_0.join(
+                crate::scoped!(|ctx| async move { ctx.io_mut().send(0u8).await.unwrap() }),
+                crate::scoped!(|ctx| async move { ctx.io_mut().send(0u16).await.unwrap() }),
+            ),
+            ctx_1.join(
+                crate::scoped!(|ctx| async move {
+                    let _: u8 = ctx.io_mut().expect_next().await.unwrap();
+                }),
+                crate::scoped!(|ctx| async move {
+                    let _: u16 = ctx.io_mut().expect_next().await.unwrap();
+                }),
+            ),
+        );
+        res_0.unwrap();
+        res_1.unwrap();
+
+        // Each child thread is recorded separately, in the metrics shared by the executor.
+        let threads = exec_0.metrics().threads();
+        let bytes: Vec<_> = threads
+            .iter()
+            .map(|(_, metrics)| metrics.bytes_sent)
+            .collect();
+        assert_eq!(bytes, [0, 1, 2]);
+        assert_eq!(ctx_1.metrics().total().messages_received, 2);
+    }
+}
@@ -0,0 +1,107 @@
+//! Poisoning of state shared across protocol rounds.
+//!
+//! A protocol future which is dropped part way through a round, for example because a
+//! [`timeout`](crate::timeout::timeout) expired, can leave its state inconsistent with that of
+//! the peer: keys may have been consumed, or a message may be partially written to the stream.
+//! Retrying with that state would silently produce wrong results, so a functionality instead
+//! poisons itself and refuses to run further rounds.
+//!
+//! A [`PoisonFlag`] hands out a [`PoisonGuard`] at the start of each round, which poisons the
+//! flag if it is dropped before [`PoisonGuard::disarm`] is called.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A functionality was poisoned by a cancelled operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("state was poisoned by a cancelled operation")]
+pub struct PoisonedError;
+
+/// A flag which records whether an operation was cancelled part way through.
+#[derive(Debug, Default)]
+pub struct PoisonFlag(Arc<AtomicBool>);
+
+impl PoisonFlag {
+    /// Creates a new flag which is not poisoned.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the flag is poisoned.
+    pub fn is_poisoned(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Poisons the flag.
+    pub fn poison(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Returns a guard for an operation, or an error if the flag is already poisoned.
+    ///
+    /// The guard does not borrow the flag, so it can be held across mutable uses of the state
+    /// the flag protects.
+    pub fn guard(&self) -> Result<PoisonGuard, PoisonedError> {
+        if self.is_poisoned() {
+            return Err(PoisonedError);
+        }
+
+        Ok(PoisonGuard(Some(self.0.clone())))
+    }
+}
+
+/// A guard which poisons its [`PoisonFlag`] when dropped, unless it is disarmed.
+#[derive(Debug)]
+#[must_use = "dropping the guard immediately poisons the flag"]
+pub struct PoisonGuard(Option<Arc<AtomicBool>>);
+
+impl PoisonGuard {
+    /// Disarms the guard, marking the operation as complete.
+    pub fn disarm(mut self) {
+        self.0.take();
+    }
+}
+
+impl Drop for PoisonGuard {
+    fn drop(&mut self) {
+        if let Some(flag) = self.0.take() {
+            flag.store(true, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{future::pending, FutureExt};
+
+    use super::*;
+
+    #[test]
+    fn test_poison_guard_disarm() {
+        let flag = PoisonFlag::new();
+
+        flag.guard().unwrap().disarm();
+
+        assert!(!flag.is_poisoned());
+    }
+
+    #[test]
+    fn test_poison_guard_cancelled() {
+        let flag = PoisonFlag::new();
+
+        let fut = async {
+            let guard = flag.guard()?;
+            pending::<()>().await;
+            guard.disarm();
+            Ok::<_, PoisonedError>(())
+        };
+
+        // Poll the future once and drop it part way through.
+        assert!(fut.now_or_never().is_none());
+
+        assert!(flag.is_poisoned());
+        assert_eq!(flag.guard().unwrap_err(), PoisonedError);
+    }
+}
@@ -0,0 +1,318 @@
+//! Encryption at rest for secret protocol artifacts.
+//!
+//! Protocol state which is persisted between sessions, such as OT extension state, preprocessed
+//! correlations or garbled circuits, is secret. Sealing encrypts and authenticates it with a key
+//! provided by the application, so that it does not hit the disk in plaintext.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! | Field        | Size      | Description                                   |
+//! |--------------|-----------|-----------------------------------------------|
+//! | magic        | 8 bytes   | [`MAGIC`]                                     |
+//! | version      | 2 bytes   | [`FORMAT_VERSION`]                            |
+//! | label length | 2 bytes   | Length of the label                           |
+//! | label        | variable  | Label of the artifact, UTF-8                  |
+//! | nonce        | 24 bytes  | Random XChaCha20-Poly1305 nonce               |
+//! | ciphertext   | variable  | Encrypted artifact, followed by a 16 byte tag |
+//!
+//! The header is authenticated as associated data. The label binds the ciphertext to the kind of
+//! artifact it contains, so that an artifact can not be opened as another one, eg. the state of
+//! an OT sender as the state of an OT receiver.
+//!
+//! # Key management
+//!
+//! Nonces are random, so a key can be used to seal a practically unlimited number of artifacts.
+//! Storing and rotating the key is left to the application.
+
+use core::fmt;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use rand_core::{CryptoRng, RngCore};
+use serde::{de::DeserializeOwned, Serialize};
+use zeroize::Zeroize;
+
+/// Magic bytes at the start of a sealed artifact.
+pub const MAGIC: [u8; 8] = *b"MPZSEALD";
+/// Current version of the sealing format.
+pub const FORMAT_VERSION: u16 = 1;
+
+const NONCE_LEN: usize = 24;
+
+/// Errors that can occur while sealing or opening an artifact.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SealError {
+    #[error("invalid magic bytes, not a sealed artifact")]
+    InvalidMagic,
+    #[error("unsupported format version: {0}")]
+    UnsupportedVersion(u16),
+    #[error("sealed artifact is truncated")]
+    Truncated,
+    #[error("label is too long: {0} bytes")]
+    LabelTooLong(usize),
+    #[error("label mismatch, expected: {expected}, actual: {actual}")]
+    LabelMismatch { expected: String, actual: String },
+    #[error("failed to authenticate the sealed artifact, wrong key or corrupted data")]
+    Authentication,
+    #[error("failed to serialize the artifact: {0}")]
+    Serialize(#[from] bincode::Error),
+}
+
+/// A key used to seal artifacts at rest.
+///
+/// The key is zeroized when dropped.
+#[derive(Clone)]
+pub struct SealingKey([u8; 32]);
+
+impl fmt::Debug for SealingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SealingKey {{ .. }}")
+    }
+}
+
+impl Drop for SealingKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl SealingKey {
+    /// Creates a new sealing key.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Generates a random sealing key.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut key = Self([0u8; 32]);
+        rng.fill_bytes(&mut key.0);
+        key
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+impl From<[u8; 32]> for SealingKey {
+    fn from(key: [u8; 32]) -> Self {
+        Self::new(key)
+    }
+}
+
+fn header(label: &str) -> Result<Vec<u8>, SealError> {
+    let label_len = u16::try_from(label.len()).map_err(|_| SealError::LabelTooLong(label.len()))?;
+
+    let mut header = Vec::with_capacity(12 + label.len());
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    header.extend_from_slice(&label_len.to_le_bytes());
+    header.extend_from_slice(label.as_bytes());
+
+    Ok(header)
+}
+
+/// Seals an artifact.
+///
+/// # Arguments
+///
+/// * `key` - The sealing key.
+/// * `label` - The label of the artifact.
+/// * `plaintext` - The artifact to seal.
+/// * `rng` - The random number generator used to sample the nonce.
+pub fn seal<R: RngCore + CryptoRng>(
+    key: &SealingKey,
+    label: &str,
+    plaintext: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, SealError> {
+    let mut sealed = header(label)?;
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+
+    let ciphertext = key
+        .cipher()
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &sealed,
+            },
+        )
+        .expect("artifact should not exceed the maximum length of XChaCha20-Poly1305");
+
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+
+    Ok(sealed)
+}
+
+/// Opens a sealed artifact.
+///
+/// # Arguments
+///
+/// * `key` - The sealing key.
+/// * `label` - The expected label of the artifact.
+/// * `sealed` - The sealed artifact.
+pub fn open(key: &SealingKey, label: &str, sealed: &[u8]) -> Result<Vec<u8>, SealError> {
+    let take = |bytes: &mut &[u8], n: usize| -> Result<Vec<u8>, SealError> {
+        if bytes.len() < n {
+            return Err(SealError::Truncated);
+        }
+        let (head, tail) = bytes.split_at(n);
+        *bytes = tail;
+        Ok(head.to_vec())
+    };
+
+    let mut bytes = sealed;
+
+    if take(&mut bytes, MAGIC.len())? != MAGIC {
+        return Err(SealError::InvalidMagic);
+    }
+
+    let version = u16::from_le_bytes(take(&mut bytes, 2)?.try_into().unwrap());
+    if version != FORMAT_VERSION {
+        return Err(SealError::UnsupportedVersion(version));
+    }
+
+    let label_len = u16::from_le_bytes(take(&mut bytes, 2)?.try_into().unwrap()) as usize;
+    let actual = take(&mut bytes, label_len)?;
+    if actual != label.as_bytes() {
+        return Err(SealError::LabelMismatch {
+            expected: label.to_string(),
+            actual: String::from_utf8_lossy(&actual).into_owned(),
+        });
+    }
+
+    let header = &sealed[..sealed.len() - bytes.len()];
+    let nonce = take(&mut bytes, NONCE_LEN)?;
+
+    key.cipher()
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: bytes,
+                aad: header,
+            },
+        )
+        .map_err(|_| SealError::Authentication)
+}
+
+/// Serializes and seals an artifact.
+///
+/// # Arguments
+///
+/// * `key` - The sealing key.
+/// * `label` - The label of the artifact.
+/// * `value` - The artifact to seal.
+/// * `rng` - The random number generator used to sample the nonce.
+pub fn seal_value<T: Serialize, R: RngCore + CryptoRng>(
+    key: &SealingKey,
+    label: &str,
+    value: &T,
+    rng: &mut R,
+) -> Result<Vec<u8>, SealError> {
+    seal(key, label, &bincode::serialize(value)?, rng)
+}
+
+/// Opens and deserializes a sealed artifact.
+///
+/// # Arguments
+///
+/// * `key` - The sealing key.
+/// * `label` - The expected label of the artifact.
+/// * `sealed` - The sealed artifact.
+pub fn open_value<T: DeserializeOwned>(
+    key: &SealingKey,
+    label: &str,
+    sealed: &[u8],
+) -> Result<T, SealError> {
+    Ok(bincode::deserialize(&open(key, label, sealed)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const LABEL: &str = "mpz-ot/kos/sender";
+
+    #[test]
+    fn test_seal() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = SealingKey::random(&mut rng);
+
+        let artifact = (42u64, vec![69u8; 100]);
+        let sealed = seal_value(&key, LABEL, &artifact, &mut rng).unwrap();
+
+        // The artifact is not stored in plaintext.
+        assert!(!sealed.windows(100).any(|window| window == [69u8; 100]));
+
+        let opened: (u64, Vec<u8>) = open_value(&key, LABEL, &sealed).unwrap();
+        assert_eq!(opened, artifact);
+
+        // Nonces are random, so sealing twice gives different ciphertexts.
+        assert_ne!(
+            seal_value(&key, LABEL, &artifact, &mut rng).unwrap(),
+            sealed
+        );
+    }
+
+    #[test]
+    fn test_seal_wrong_key() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = SealingKey::random(&mut rng);
+        let other = SealingKey::random(&mut rng);
+
+        let sealed = seal(&key, LABEL, b"secret", &mut rng).unwrap();
+
+        assert!(matches!(
+            open(&other, LABEL, &sealed),
+            Err(SealError::Authentication)
+        ));
+    }
+
+    #[test]
+    fn test_seal_wrong_label() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = SealingKey::random(&mut rng);
+
+        let sealed = seal(&key, LABEL, b"secret", &mut rng).unwrap();
+
+        assert!(matches!(
+            open(&key, "mpz-ot/kos/receiver", &sealed),
+            Err(SealError::LabelMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_seal_tampered() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = SealingKey::random(&mut rng);
+
+        let sealed = seal(&key, LABEL, b"secret", &mut rng).unwrap();
+
+        // Every byte of the sealed artifact is authenticated.
+        for i in 0..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert!(open(&key, LABEL, &tampered).is_err());
+        }
+
+        assert!(matches!(
+            open(&key, LABEL, &sealed[..sealed.len() - 1]),
+            Err(SealError::Authentication)
+        ));
+        assert!(matches!(
+            open(&key, LABEL, &sealed[..10]),
+            Err(SealError::Truncated)
+        ));
+    }
+}
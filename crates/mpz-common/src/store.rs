@@ -0,0 +1,253 @@
+//! Storage for preprocessed correlated randomness.
+//!
+//! Functionalities in the preprocessing model, such as OLE, OT or VOPE, generate correlations
+//! ahead of time and consume them later. [`CorrelatedStore`] wraps the cache which holds the
+//! correlations, and takes care of the bookkeeping around it:
+//!
+//! - Allocations are accumulated with [`Allocate`] until the next refill.
+//! - Refills are performed with an async callback which generates the allocated correlations.
+//! - The number of correlations reserved and consumed by each thread is tracked.
+//! - Consuming more correlations than are available returns [`InsufficientCorrelations`].
+
+use std::collections::HashMap;
+
+use scoped_futures::ScopedBoxFuture;
+
+use crate::{Allocate, Context, ThreadId};
+
+/// Not enough correlations are available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("insufficient correlations: requested {requested}, available {available}")]
+pub struct InsufficientCorrelations {
+    /// Number of correlations requested.
+    pub requested: usize,
+    /// Number of correlations available.
+    pub available: usize,
+}
+
+/// Number of correlations used by a thread.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StoreCounts {
+    /// Number of correlations generated by refills on the thread.
+    pub reserved: usize,
+    /// Number of correlations consumed on the thread.
+    pub consumed: usize,
+}
+
+/// A store of preprocessed correlated randomness.
+///
+/// The correlations themselves are held by `T`, usually the core of a protocol, which the store
+/// passes to the refill and consume callbacks.
+#[derive(Debug)]
+pub struct CorrelatedStore<T> {
+    inner: T,
+    /// Number of correlations allocated since the last refill.
+    pending: usize,
+    /// Number of correlations available to consume.
+    available: usize,
+    counts: HashMap<ThreadId, StoreCounts>,
+}
+
+impl<T: Default> Default for CorrelatedStore<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> CorrelatedStore<T> {
+    /// Creates a new, empty store.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            pending: 0,
+            available: 0,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns a reference to the cache.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the number of correlations allocated since the last refill.
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Returns the number of correlations available to consume.
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    /// Returns the number of correlations reserved and consumed by a thread.
+    pub fn counts(&self, id: &ThreadId) -> StoreCounts {
+        self.counts.get(id).copied().unwrap_or_default()
+    }
+
+    /// Generates all the pending correlations.
+    ///
+    /// The callback is passed the cache and the number of correlations to generate, which it
+    /// must add to the cache. It is not called if no correlations are pending.
+    ///
+    /// Returns the number of correlations which were generated. If the callback fails, the
+    /// correlations remain pending.
+    pub async fn refill<'u, Ctx, F, E>(&mut self, ctx: &mut Ctx, f: F) -> Result<usize, E>
+    where
+        Ctx: Context,
+        F: for<'a> FnOnce(&'a mut Ctx, &'a mut T, usize) -> ScopedBoxFuture<'u, 'a, Result<(), E>>
+            + Send
+            + 'u,
+    {
+        let count = self.pending;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        // The pending correlations are only cleared once they have been generated, so a failed
+        // refill can be retried.
+        f(ctx, &mut self.inner, count).await?;
+
+        self.pending = 0;
+        self.available += count;
+        self.counts.entry(ctx.id().clone()).or_default().reserved += count;
+
+        Ok(count)
+    }
+
+    /// Consumes correlations from the cache.
+    ///
+    /// The callback is passed the cache, from which it must remove `count` correlations. It is
+    /// not called if fewer than `count` correlations are available.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The thread consuming the correlations.
+    /// * `count` - The number of correlations to consume.
+    /// * `f` - Callback which consumes the correlations.
+    pub fn consume<F, R>(
+        &mut self,
+        id: &ThreadId,
+        count: usize,
+        f: F,
+    ) -> Result<R, InsufficientCorrelations>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        if count > self.available {
+            return Err(InsufficientCorrelations {
+                requested: count,
+                available: self.available,
+            });
+        }
+
+        let output = f(&mut self.inner);
+
+        self.available -= count;
+        self.counts.entry(id.clone()).or_default().consumed += count;
+
+        Ok(output)
+    }
+}
+
+impl<T> Allocate for CorrelatedStore<T> {
+    fn alloc(&mut self, count: usize) {
+        self.pending += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scoped_futures::ScopedFutureExt;
+
+    use super::*;
+    use crate::executor::test_st_executor;
+
+    #[tokio::test]
+    async fn test_correlated_store() {
+        let (mut ctx, _) = test_st_executor(8);
+        let mut store = CorrelatedStore::<Vec<usize>>::default();
+
+        store.alloc(3);
+        store.alloc(2);
+        assert_eq!(store.pending(), 5);
+
+        let count = store
+            .refill(&mut ctx, |_, cache, count| {
+                async move {
+                    cache.extend(0..count);
+                    Ok::<_, InsufficientCorrelations>(())
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(count, 5);
+        assert_eq!(store.pending(), 0);
+        assert_eq!(store.available(), 5);
+
+        let id = ctx.id().clone();
+        let taken = store
+            .consume(&id, 4, |cache| cache.drain(..4).collect::<Vec<_>>())
+            .unwrap();
+        assert_eq!(taken, vec![0, 1, 2, 3]);
+
+        assert_eq!(
+            store.consume(&id, 2, |_| ()).unwrap_err(),
+            InsufficientCorrelations {
+                requested: 2,
+                available: 1
+            }
+        );
+
+        assert_eq!(
+            store.counts(&id),
+            StoreCounts {
+                reserved: 5,
+                consumed: 4
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_correlated_store_refill_error() {
+        let (mut ctx, _) = test_st_executor(8);
+        let mut store = CorrelatedStore::<Vec<usize>>::default();
+
+        store.alloc(3);
+
+        let err = store
+            .refill(&mut ctx, |_, _, count| {
+                async move {
+                    Err(InsufficientCorrelations {
+                        requested: count,
+                        available: 0,
+                    })
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.requested, 3);
+        assert_eq!(store.pending(), 3);
+        assert_eq!(store.available(), 0);
+
+        // The failed refill can be retried.
+        let count = store
+            .refill(&mut ctx, |_, cache, count| {
+                async move {
+                    cache.extend(0..count);
+                    Ok::<_, InsufficientCorrelations>(())
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(store.pending(), 0);
+        assert_eq!(store.available(), 3);
+    }
+}
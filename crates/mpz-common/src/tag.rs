@@ -0,0 +1,267 @@
+//! Purpose tags for protocol instances.
+//!
+//! Every instance of a sub-protocol, eg. an OT sender, is constructed with a [`Tag`] describing
+//! its purpose in the application. Before its first message, an instance sends a digest of its
+//! tag and its side of the protocol to the peer, and checks the digest sent back.
+//!
+//! An instance which is wired to the counterpart of a different purpose, to a peer playing the
+//! same side, or whose own messages are reflected back at it, therefore fails at the first
+//! message with a [`TagError`].
+//!
+//! Protocols also mix the tag into their transcripts, with [`Tag::domain`] for key derivations
+//! and [`Tag::bind_seed`] for seeds agreed on with a coin-toss. Messages of one instance are
+//! therefore meaningless to an instance of a different purpose, even if the digest exchange is
+//! bypassed.
+
+use std::{borrow::Cow, fmt};
+
+use mpz_core::{hash::SecureHash, utils::blake3, Block};
+use serio::{stream::IoStreamExt, SinkExt};
+
+use crate::Context;
+
+/// Domain separator of tag digests.
+const TAG_DOMAIN: &[u8] = b"mpz-tag";
+
+/// Domain separator of seeds bound to a tag.
+const SEED_DOMAIN: &[u8] = b"mpz-tag seed";
+
+/// The byte identifying the domain of a tag in its digest, which differs from every [`Side`].
+const DOMAIN_BYTE: u8 = 2;
+
+/// The purpose of a protocol instance, eg. `"prf/key-share"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tag(Cow<'static, str>);
+
+impl Tag {
+    /// Creates a new tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `purpose` - The purpose of the instance, unique within the application.
+    pub fn new(purpose: impl Into<Cow<'static, str>>) -> Self {
+        Self(purpose.into())
+    }
+
+    /// Returns the purpose of the instance.
+    pub fn purpose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the tag of a sub-protocol of the instance, eg. its base OT.
+    pub fn child(&self, name: &str) -> Self {
+        Self(format!("{}/{}", self.0, name).into())
+    }
+
+    /// Returns the domain of the instance, which both sides of the protocol agree on.
+    ///
+    /// Protocols hash it into their key derivations.
+    pub fn domain(&self) -> [u8; 32] {
+        self.hash(DOMAIN_BYTE)
+    }
+
+    /// Derives a seed bound to the instance from a seed agreed on by the parties, eg. with a
+    /// coin-toss.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the seed, eg. `"kos/check"`.
+    /// * `seed` - The seed agreed on by the parties.
+    pub fn bind_seed(&self, name: &str, seed: Block) -> Block {
+        let hash = (SEED_DOMAIN, self.domain(), name, seed).hash();
+
+        let bytes: [u8; 16] = hash.as_bytes()[..16]
+            .try_into()
+            .expect("hash is at least 16 bytes");
+
+        Block::new(bytes)
+    }
+
+    /// Returns the digest of the tag for the provided side of the protocol.
+    fn digest(&self, side: Side) -> [u8; 32] {
+        self.hash(side as u8)
+    }
+
+    fn hash(&self, kind: u8) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(TAG_DOMAIN.len() + 1 + self.0.len());
+        bytes.extend_from_slice(TAG_DOMAIN);
+        bytes.push(kind);
+        bytes.extend_from_slice(self.0.as_bytes());
+
+        blake3(&bytes)
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A side of a two-party protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The sending side, eg. the OT sender.
+    Sender,
+    /// The receiving side, eg. the OT receiver.
+    Receiver,
+}
+
+impl Side {
+    /// Returns the side of the peer.
+    pub fn peer(self) -> Self {
+        match self {
+            Side::Sender => Side::Receiver,
+            Side::Receiver => Side::Sender,
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Sender => write!(f, "sender"),
+            Side::Receiver => write!(f, "receiver"),
+        }
+    }
+}
+
+/// An error binding a protocol instance to its tag.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum TagError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("peer is not the {expected} of instance \"{tag}\"")]
+    Mismatch { tag: Tag, expected: Side },
+}
+
+/// Binds a protocol instance to its tag.
+#[derive(Debug, Clone)]
+pub struct TagBinding {
+    tag: Tag,
+    side: Side,
+    bound: bool,
+}
+
+impl TagBinding {
+    /// Creates a new binding.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag of the instance.
+    /// * `side` - The side of the protocol played by the instance.
+    pub fn new(tag: Tag, side: Side) -> Self {
+        Self {
+            tag,
+            side,
+            bound: false,
+        }
+    }
+
+    /// Returns the tag of the instance.
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Returns `true` if the tag has been checked by the peer.
+    pub fn is_bound(&self) -> bool {
+        self.bound
+    }
+
+    /// Exchanges the tag with the peer, unless this was already done.
+    ///
+    /// Instances call this before sending their first message.
+    pub async fn bind<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), TagError> {
+        if self.bound {
+            return Ok(());
+        }
+
+        let io = ctx.io_mut();
+        io.send(self.tag.digest(self.side)).await?;
+        let digest: [u8; 32] = io.expect_next().await?;
+
+        if digest != self.tag.digest(self.side.peer()) {
+            return Err(TagError::Mismatch {
+                tag: self.tag.clone(),
+                expected: self.side.peer(),
+            });
+        }
+
+        self.bound = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::executor::test_st_executor;
+
+    async fn bind(a: &mut TagBinding, b: &mut TagBinding) -> (bool, bool) {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (a, b) = futures::join!(a.bind(&mut ctx_a), b.bind(&mut ctx_b));
+
+        (a.is_ok(), b.is_ok())
+    }
+
+    #[tokio::test]
+    async fn test_tag_binding() {
+        let mut sender = TagBinding::new(Tag::new("test"), Side::Sender);
+        let mut receiver = TagBinding::new(Tag::new("test"), Side::Receiver);
+
+        assert_eq!(bind(&mut sender, &mut receiver).await, (true, true));
+        assert!(sender.is_bound() && receiver.is_bound());
+    }
+
+    #[tokio::test]
+    async fn test_tag_binding_purpose_mismatch() {
+        let mut sender = TagBinding::new(Tag::new("a"), Side::Sender);
+        let mut receiver = TagBinding::new(Tag::new("b"), Side::Receiver);
+
+        assert_eq!(bind(&mut sender, &mut receiver).await, (false, false));
+    }
+
+    #[tokio::test]
+    async fn test_tag_binding_side_mismatch() {
+        let mut a = TagBinding::new(Tag::new("test"), Side::Sender);
+        let mut b = TagBinding::new(Tag::new("test"), Side::Sender);
+
+        assert_eq!(bind(&mut a, &mut b).await, (false, false));
+    }
+
+    #[test]
+    fn test_tag_bind_seed() {
+        let seed = Block::ONES;
+
+        let a = Tag::new("a");
+        let b = Tag::new("b");
+
+        assert_eq!(a.bind_seed("check", seed), a.bind_seed("check", seed));
+        assert_ne!(a.bind_seed("check", seed), b.bind_seed("check", seed));
+        assert_ne!(a.bind_seed("check", seed), a.bind_seed("delta", seed));
+        assert_ne!(a.domain(), b.domain());
+    }
+
+    #[tokio::test]
+    async fn test_tag_binding_reflection() {
+        let (mut ctx, mut peer) = test_st_executor(8);
+        let mut binding = TagBinding::new(Tag::new("test"), Side::Sender);
+
+        // Reflect the instance's own digest back at it.
+        peer.io_mut()
+            .send(Tag::new("test").digest(Side::Sender))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            binding.bind(&mut ctx).await,
+            Err(TagError::Mismatch {
+                expected: Side::Receiver,
+                ..
+            })
+        ));
+    }
+}
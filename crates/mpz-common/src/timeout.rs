@@ -0,0 +1,93 @@
+//! Timeouts for protocol phases.
+//!
+//! Protocols which wait on messages from a peer would otherwise wait forever if the peer stalls.
+//! Each phase of a protocol can be bounded with [`timeout`], which returns a [`TimeoutError`]
+//! identifying the phase which expired.
+
+use core::{fmt, future::Future, pin::pin, time::Duration};
+
+use futures::future::{select, Either};
+use futures_timer::Delay;
+
+/// A protocol phase which can time out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Base OT setup.
+    BaseOT,
+    /// OT extension.
+    Extension,
+    /// Streaming of a garbled circuit.
+    GarbleStream,
+    /// Decoding of output values.
+    Decode,
+    /// Protocol finalization.
+    Finalize,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::BaseOT => write!(f, "base OT"),
+            Phase::Extension => write!(f, "extension"),
+            Phase::GarbleStream => write!(f, "garbling stream"),
+            Phase::Decode => write!(f, "decode"),
+            Phase::Finalize => write!(f, "finalize"),
+        }
+    }
+}
+
+/// A protocol phase did not complete in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{phase} phase timed out after {duration:?}")]
+pub struct TimeoutError {
+    /// The phase which timed out.
+    pub phase: Phase,
+    /// The duration which elapsed.
+    pub duration: Duration,
+}
+
+/// Awaits `fut`, returning an error if it does not complete within `duration`.
+///
+/// # Arguments
+///
+/// * `phase` - The protocol phase the future belongs to.
+/// * `duration` - The maximum duration to wait.
+/// * `fut` - The future to await.
+pub async fn timeout<F: Future>(
+    phase: Phase,
+    duration: Duration,
+    fut: F,
+) -> Result<F::Output, TimeoutError> {
+    match select(pin!(fut), Delay::new(duration)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(TimeoutError { phase, duration }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout_completes() {
+        let output = timeout(Phase::Decode, Duration::from_secs(1), async { 42 })
+            .await
+            .unwrap();
+
+        assert_eq!(output, 42);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_expires() {
+        let err = timeout(
+            Phase::Finalize,
+            Duration::from_millis(10),
+            futures::future::pending::<()>(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.phase, Phase::Finalize);
+        assert_eq!(err.duration, Duration::from_millis(10));
+    }
+}
@@ -1,6 +1,9 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 
-use mpz_core::{aes::AesEncryptor, block::Block};
+use mpz_core::{
+    aes::{AesEncryptor, FIXED_KEY_AES},
+    block::Block,
+};
 
 #[allow(clippy::all)]
 fn criterion_benchmark(c: &mut Criterion) {
@@ -39,5 +42,63 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn fixed_key_aes_benchmark(c: &mut Criterion) {
+    let cipher = &(*FIXED_KEY_AES);
+
+    // Hashing the 4 labels of a half-gate garbled AND gate.
+    let mut group = c.benchmark_group("fixed_key_aes::and_gate");
+    group.throughput(Throughput::Elements(1));
+
+    let tweaks = rand::random::<[Block; 4]>();
+    let blocks = rand::random::<[Block; 4]>();
+
+    group.bench_function("tccr", |bench| {
+        bench.iter(|| {
+            let h: [Block; 4] = std::array::from_fn(|i| cipher.tccr(tweaks[i], blocks[i]));
+            black_box(h);
+        });
+    });
+
+    group.bench_function("tccr_many::<4>", |bench| {
+        bench.iter(|| {
+            let mut h = black_box(blocks);
+            cipher.tccr_many(&tweaks, &mut h);
+            black_box(h);
+        });
+    });
+
+    drop(group);
+
+    // Hashing the labels of many AND gates with different pipelining widths.
+    const LEN: usize = 1024;
+
+    let mut group = c.benchmark_group("fixed_key_aes::tccr_blocks");
+    group.throughput(Throughput::Elements(LEN as u64));
+
+    let tweaks: Vec<Block> = (0..LEN).map(|_| rand::random()).collect();
+    let blocks: Vec<Block> = (0..LEN).map(|_| rand::random()).collect();
+
+    group.bench_function("1", |bench| {
+        let mut h = blocks.clone();
+        bench.iter(|| {
+            cipher.tccr_blocks::<1>(&tweaks, black_box(&mut h));
+        });
+    });
+
+    group.bench_function("4", |bench| {
+        let mut h = blocks.clone();
+        bench.iter(|| {
+            cipher.tccr_blocks::<4>(&tweaks, black_box(&mut h));
+        });
+    });
+
+    group.bench_function("8", |bench| {
+        let mut h = blocks.clone();
+        bench.iter(|| {
+            cipher.tccr_blocks::<8>(&tweaks, black_box(&mut h));
+        });
+    });
+}
+
+criterion_group!(benches, criterion_benchmark, fixed_key_aes_benchmark);
 criterion_main!(benches);
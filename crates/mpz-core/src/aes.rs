@@ -11,6 +11,13 @@ pub const FIXED_KEY: [u8; 16] = [
     69, 42, 69, 42, 69, 42, 69, 42, 69, 42, 69, 42, 69, 42, 69, 42,
 ];
 
+/// Default number of blocks processed together by the batched methods of [`FixedKeyAes`].
+///
+/// AES hardware instructions (AES-NI, ARMv8 crypto extensions) have a latency of several cycles
+/// per round but can start a new round every cycle, so independent blocks must be interleaved
+/// to keep the pipeline full.
+pub const PIPELINE_WIDTH: usize = 8;
+
 /// Fixed-key AES cipher
 pub static FIXED_KEY_AES: Lazy<FixedKeyAes> = Lazy::new(|| FixedKeyAes {
     aes: Aes128Enc::new_from_slice(&FIXED_KEY).unwrap(),
@@ -22,6 +29,27 @@ pub struct FixedKeyAes {
 }
 
 impl FixedKeyAes {
+    /// Encrypts many blocks in-place.
+    #[inline]
+    pub fn encrypt_many_blocks<const N: usize>(&self, blocks: &mut [Block; N]) {
+        self.aes
+            .encrypt_blocks(Block::as_generic_array_mut_slice(blocks));
+    }
+
+    /// Encrypts a slice of blocks in-place, `W` blocks at a time.
+    ///
+    /// `W` is the pipelining width, usually 4 or 8, see [`PIPELINE_WIDTH`].
+    #[inline]
+    pub fn encrypt_blocks<const W: usize>(&self, blocks: &mut [Block]) {
+        let mut chunks = blocks.chunks_exact_mut(W);
+        for chunk in &mut chunks {
+            self.encrypt_many_blocks::<W>(chunk.try_into().expect("chunk has W blocks"));
+        }
+
+        self.aes
+            .encrypt_blocks(Block::as_generic_array_mut_slice(chunks.into_remainder()));
+    }
+
     /// Tweakable circular correlation-robust hash function instantiated
     /// using fixed-key AES.
     ///
@@ -70,6 +98,47 @@ impl FixedKeyAes {
             .for_each(|(a, b)| *a ^= *b);
     }
 
+    /// Tweakable circular correlation-robust hash function instantiated
+    /// using fixed-key AES, applied to a slice of blocks `W` blocks at a time.
+    ///
+    /// `W` is the pipelining width, usually 4 or 8, see [`PIPELINE_WIDTH`].
+    ///
+    /// See [`FixedKeyAes::tccr`] for more details.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweaks` - The tweaks to use for each block in `blocks`.
+    /// * `blocks` - The blocks to hash in-place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tweaks` and `blocks` have different lengths.
+    #[inline]
+    pub fn tccr_blocks<const W: usize>(&self, tweaks: &[Block], blocks: &mut [Block]) {
+        assert_eq!(
+            tweaks.len(),
+            blocks.len(),
+            "tweaks and blocks must have the same length"
+        );
+
+        let mut chunks = blocks.chunks_exact_mut(W);
+        let mut tweak_chunks = tweaks.chunks_exact(W);
+        for (chunk, tweaks) in (&mut chunks).zip(&mut tweak_chunks) {
+            self.tccr_many::<W>(
+                tweaks.try_into().expect("chunk has W tweaks"),
+                chunk.try_into().expect("chunk has W blocks"),
+            );
+        }
+
+        for (block, tweak) in chunks
+            .into_remainder()
+            .iter_mut()
+            .zip(tweak_chunks.remainder())
+        {
+            *block = self.tccr(*tweak, *block);
+        }
+    }
+
     /// Correlation-robust hash function instantiated using fixed-key AES
     /// (cf. <https://eprint.iacr.org/2019/074>, §7.2).
     ///
@@ -102,6 +171,28 @@ impl FixedKeyAes {
             .for_each(|(a, b)| *a ^= *b);
     }
 
+    /// Correlation-robust hash function instantiated using fixed-key AES, applied to a slice of
+    /// blocks `W` blocks at a time.
+    ///
+    /// `W` is the pipelining width, usually 4 or 8, see [`PIPELINE_WIDTH`].
+    ///
+    /// See [`FixedKeyAes::cr`] for more details.
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks` - The blocks to hash in-place.
+    #[inline]
+    pub fn cr_blocks<const W: usize>(&self, blocks: &mut [Block]) {
+        let mut chunks = blocks.chunks_exact_mut(W);
+        for chunk in &mut chunks {
+            self.cr_many::<W>(chunk.try_into().expect("chunk has W blocks"));
+        }
+
+        for block in chunks.into_remainder() {
+            *block = self.cr(*block);
+        }
+    }
+
     /// Circular correlation-robust hash function instantiated using fixed-key AES
     /// (cf.<https://eprint.iacr.org/2019/074>, §7.3).
     ///
@@ -211,3 +302,49 @@ fn aes_test() {
         ]
     );
 }
+
+#[test]
+fn fixed_key_aes_batch_test() {
+    let cipher = &(*FIXED_KEY_AES);
+
+    for len in [0, 3, 8, 13] {
+        let blocks: Vec<Block> = (0..len).map(|_| rand::random()).collect();
+        let tweaks: Vec<Block> = (0..len).map(|_| rand::random()).collect();
+
+        let expected_enc: Vec<Block> = blocks
+            .iter()
+            .map(|block| {
+                let mut block = *block;
+                cipher.aes.encrypt_block(block.as_generic_array_mut());
+                block
+            })
+            .collect();
+        let expected_tccr: Vec<Block> = blocks
+            .iter()
+            .zip(&tweaks)
+            .map(|(block, tweak)| cipher.tccr(*tweak, *block))
+            .collect();
+        let expected_cr: Vec<Block> = blocks.iter().map(|block| cipher.cr(*block)).collect();
+
+        let mut enc_4 = blocks.clone();
+        cipher.encrypt_blocks::<4>(&mut enc_4);
+        let mut enc_8 = blocks.clone();
+        cipher.encrypt_blocks::<8>(&mut enc_8);
+        assert_eq!(enc_4, expected_enc);
+        assert_eq!(enc_8, expected_enc);
+
+        let mut tccr_4 = blocks.clone();
+        cipher.tccr_blocks::<4>(&tweaks, &mut tccr_4);
+        let mut tccr_8 = blocks.clone();
+        cipher.tccr_blocks::<8>(&tweaks, &mut tccr_8);
+        assert_eq!(tccr_4, expected_tccr);
+        assert_eq!(tccr_8, expected_tccr);
+
+        let mut cr_4 = blocks.clone();
+        cipher.cr_blocks::<4>(&mut cr_4);
+        let mut cr_8 = blocks.clone();
+        cipher.cr_blocks::<8>(&mut cr_8);
+        assert_eq!(cr_4, expected_cr);
+        assert_eq!(cr_8, expected_cr);
+    }
+}
@@ -0,0 +1,367 @@
+//! A binary Merkle tree using Blake3.
+//!
+//! The tree can be used as a vector commitment, where the root commits to the
+//! ordered list of leaves. Proofs can open any subset of the leaves at once.
+//!
+//! Leaf and internal nodes are hashed with distinct prefixes so that an internal node
+//! can never be passed off as a leaf. If a level has an odd number of nodes, the last
+//! node is carried up to the next level unchanged. The root is the hash of the top node
+//! together with the number of leaves, so a proof can not claim a different tree size than
+//! the one which was committed to.
+//!
+//! Note that the tree is binding but not hiding. Leaves with low entropy should be
+//! committed to using [`HashCommit`](crate::commit::HashCommit) first.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{Hash, SecureHash};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+const ROOT_PREFIX: u8 = 2;
+
+/// Errors that can occur when using a Merkle tree.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum MerkleError {
+    #[error("no leaves were provided")]
+    NoLeaves,
+    #[error("leaf index {index} out of bounds, tree has {len} leaves")]
+    InvalidIndex { index: usize, len: usize },
+    #[error("invalid merkle proof")]
+    InvalidProof,
+}
+
+/// A binary Merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleTree {
+    /// Nodes of the tree, from the leaves up to the root.
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Creates a new Merkle tree from the provided leaves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no leaves are provided.
+    pub fn new(leaves: &[Hash]) -> Self {
+        assert!(
+            !leaves.is_empty(),
+            "merkle tree must have at least one leaf"
+        );
+
+        let mut levels = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+        while levels.last().expect("levels is not empty").len() > 1 {
+            let level = levels
+                .last()
+                .expect("levels is not empty")
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [node] => *node,
+                    _ => unreachable!("chunks are at most 2 nodes"),
+                })
+                .collect();
+            levels.push(level);
+        }
+
+        Self { levels }
+    }
+
+    /// Creates a new Merkle tree committing to the provided values.
+    ///
+    /// Each leaf is the [`SecureHash`] of the corresponding value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no values are provided.
+    pub fn from_values<T: SecureHash>(values: &[T]) -> Self {
+        Self::new(&values.iter().map(|value| value.hash()).collect::<Vec<_>>())
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> Hash {
+        hash_root(
+            self.len(),
+            &self.levels.last().expect("levels is not empty")[0],
+        )
+    }
+
+    /// Returns the number of leaves in the tree.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns a proof opening the leaves at the provided indices.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The indices of the leaves to open.
+    pub fn proof(&self, indices: &[usize]) -> Result<MerkleProof, MerkleError> {
+        if indices.is_empty() {
+            return Err(MerkleError::NoLeaves);
+        }
+
+        let mut known = indices.to_vec();
+        known.sort_unstable();
+        known.dedup();
+
+        if let Some(&index) = known.last() {
+            if index >= self.len() {
+                return Err(MerkleError::InvalidIndex {
+                    index,
+                    len: self.len(),
+                });
+            }
+        }
+
+        let mut hashes = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let mut next = Vec::with_capacity(known.len());
+            let mut i = 0;
+            while i < known.len() {
+                let index = known[i];
+                if index % 2 == 0 {
+                    if index + 1 < level.len() {
+                        if known.get(i + 1) == Some(&(index + 1)) {
+                            i += 1;
+                        } else {
+                            hashes.push(level[index + 1]);
+                        }
+                    }
+                } else {
+                    hashes.push(level[index - 1]);
+                }
+                next.push(index / 2);
+                i += 1;
+            }
+            known = next;
+        }
+
+        Ok(MerkleProof {
+            leaf_count: self.len(),
+            hashes,
+        })
+    }
+}
+
+/// A proof opening a subset of the leaves of a [`MerkleTree`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    leaf_count: usize,
+    hashes: Vec<Hash>,
+}
+
+impl MerkleProof {
+    /// Returns the number of leaves in the tree the proof was created for.
+    ///
+    /// The leaf count is bound by the root, so a proof with a different leaf count than the
+    /// committed tree fails to verify.
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Verifies that the provided leaves are in the tree with the given root.
+    ///
+    /// The leaves must be the same set of leaves which were used to create the proof,
+    /// but may be provided in any order.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the tree.
+    /// * `leaves` - The opened leaves, as pairs of index and leaf.
+    pub fn verify(&self, root: &Hash, leaves: &[(usize, Hash)]) -> Result<(), MerkleError> {
+        if leaves.is_empty() {
+            return Err(MerkleError::NoLeaves);
+        }
+
+        let mut known = leaves
+            .iter()
+            .map(|(index, leaf)| {
+                if *index >= self.leaf_count {
+                    return Err(MerkleError::InvalidIndex {
+                        index: *index,
+                        len: self.leaf_count,
+                    });
+                }
+                Ok((*index, hash_leaf(leaf)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        known.sort_unstable_by_key(|(index, _)| *index);
+
+        // The same leaf may be provided more than once, but it must be consistent.
+        for pair in known.windows(2) {
+            if pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1 {
+                return Err(MerkleError::InvalidProof);
+            }
+        }
+        known.dedup_by_key(|(index, _)| *index);
+
+        let mut hashes = self.hashes.iter();
+        let mut level_len = self.leaf_count;
+        while level_len > 1 {
+            let mut next = Vec::with_capacity(known.len());
+            let mut i = 0;
+            while i < known.len() {
+                let (index, node) = known[i];
+                let parent = if index % 2 == 0 {
+                    if index + 1 < level_len {
+                        let sibling = match known.get(i + 1) {
+                            Some((sibling_index, sibling)) if *sibling_index == index + 1 => {
+                                i += 1;
+                                *sibling
+                            }
+                            _ => *hashes.next().ok_or(MerkleError::InvalidProof)?,
+                        };
+                        hash_node(&node, &sibling)
+                    } else {
+                        node
+                    }
+                } else {
+                    let sibling = hashes.next().ok_or(MerkleError::InvalidProof)?;
+                    hash_node(sibling, &node)
+                };
+                next.push((index / 2, parent));
+                i += 1;
+            }
+            known = next;
+            level_len = level_len.div_ceil(2);
+        }
+
+        if hashes.next().is_some()
+            || known.len() != 1
+            || &hash_root(self.leaf_count, &known[0].1) != root
+        {
+            return Err(MerkleError::InvalidProof);
+        }
+
+        Ok(())
+    }
+}
+
+fn hash_leaf(leaf: &Hash) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(leaf.as_bytes());
+    Hash::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+fn hash_root(leaf_count: usize, top: &Hash) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[ROOT_PREFIX]);
+    hasher.update(&(leaf_count as u64).to_le_bytes());
+    hasher.update(top.as_bytes());
+    Hash::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    Hash::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<Hash> {
+        (0..n as u64).map(|i| i.hash()).collect()
+    }
+
+    #[rstest]
+    #[case::single(1, vec![0])]
+    #[case::pair(2, vec![1])]
+    #[case::odd(7, vec![6])]
+    #[case::batch(13, vec![0, 3, 4, 12])]
+    #[case::all(5, vec![0, 1, 2, 3, 4])]
+    #[case::unordered(16, vec![9, 2, 15, 2])]
+    fn test_merkle_proof(#[case] n: usize, #[case] indices: Vec<usize>) {
+        let leaves = leaves(n);
+        let tree = MerkleTree::new(&leaves);
+        let proof = tree.proof(&indices).unwrap();
+
+        let opened = indices.iter().map(|&i| (i, leaves[i])).collect::<Vec<_>>();
+
+        proof.verify(&tree.root(), &opened).unwrap();
+    }
+
+    #[test]
+    fn test_merkle_proof_wrong_leaf() {
+        let leaves = leaves(10);
+        let tree = MerkleTree::new(&leaves);
+        let proof = tree.proof(&[2, 5]).unwrap();
+
+        let err = proof
+            .verify(&tree.root(), &[(2, leaves[2]), (5, leaves[6])])
+            .unwrap_err();
+
+        assert!(matches!(err, MerkleError::InvalidProof));
+    }
+
+    #[test]
+    fn test_merkle_proof_wrong_index() {
+        let leaves = leaves(10);
+        let tree = MerkleTree::new(&leaves);
+        let proof = tree.proof(&[2]).unwrap();
+
+        let err = proof.verify(&tree.root(), &[(3, leaves[2])]).unwrap_err();
+
+        assert!(matches!(err, MerkleError::InvalidProof));
+    }
+
+    #[test]
+    fn test_merkle_proof_missing_leaf() {
+        let leaves = leaves(10);
+        let tree = MerkleTree::new(&leaves);
+        let proof = tree.proof(&[2, 7]).unwrap();
+
+        let err = proof.verify(&tree.root(), &[(2, leaves[2])]).unwrap_err();
+
+        assert!(matches!(err, MerkleError::InvalidProof));
+    }
+
+    #[test]
+    fn test_merkle_proof_wrong_leaf_count() {
+        let leaves = leaves(4);
+        let tree = MerkleTree::new(&leaves);
+        let proof = tree.proof(&[0]).unwrap();
+
+        // A tree with 3 leaves hashes the first leaf up to the root with the same siblings, so
+        // only the root can bind the leaf count.
+        let proof = MerkleProof {
+            leaf_count: 3,
+            ..proof
+        };
+
+        let err = proof.verify(&tree.root(), &[(0, leaves[0])]).unwrap_err();
+
+        assert!(matches!(err, MerkleError::InvalidProof));
+    }
+
+    #[test]
+    fn test_merkle_proof_out_of_bounds() {
+        let tree = MerkleTree::new(&leaves(4));
+
+        let err = tree.proof(&[4]).unwrap_err();
+
+        assert!(matches!(
+            err,
+            MerkleError::InvalidIndex { index: 4, len: 4 }
+        ));
+    }
+
+    #[test]
+    fn test_merkle_leaf_is_not_node() {
+        let leaves = leaves(2);
+        let tree = MerkleTree::new(&leaves);
+
+        // A tree with a single leaf whose value is the root must not share the root.
+        assert_ne!(MerkleTree::new(&[tree.root()]).root(), tree.root());
+    }
+}
@@ -1,5 +1,9 @@
 //! This module provides a hash commitment scheme for types which implement
 //! [`CanonicalSerialize`](crate::serialize::CanonicalSerialize)
+//!
+//! It also provides a [Merkle tree](merkle) which can be used as a vector commitment.
+
+pub mod merkle;
 
 use crate::{
     hash::{Hash, SecureHash},
@@ -12,6 +12,8 @@ pub mod prg;
 pub mod prp;
 pub mod serialize;
 pub mod tkprp;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod transcript;
 pub mod utils;
 
 pub use block::{Block, BlockSerialize};
@@ -4,6 +4,7 @@
 use crate::{prp::Prp, Block};
 use rand::{seq::SliceRandom, thread_rng};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 /// An LPN encoder.
 ///
 /// The `seed` defines a sparse binary matrix `A` with at most `D` non-zero values in each row.
@@ -39,6 +40,11 @@ impl<const D: usize> LpnEncoder<D> {
         Self { seed, k, mask }
     }
 
+    /// Returns the seed of the matrix `A`.
+    pub fn seed(&self) -> Block {
+        self.seed
+    }
+
     /// Compute 4 rows as a batch, this is for the `compute` function.
     #[inline]
     fn compute_four_rows_indep(&self, y: &mut [Block], x: &[Block], pos: usize, prp: &Prp) {
@@ -113,7 +119,7 @@ impl<const D: usize> LpnEncoder<D> {
 }
 
 /// Lpn paramters
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct LpnParameters {
     /// The length of output vecotrs.
     pub n: usize,
@@ -0,0 +1,265 @@
+//! Recorded protocol transcripts for wire compatibility tests.
+//!
+//! A transcript is the sequence of messages exchanged by the parties of a protocol, encoded with
+//! bincode as they are on the wire. Compatibility tests run a protocol and pass every message
+//! through a [`Transcript`], which replays it against the transcript recorded for the current
+//! crate version:
+//!
+//! * every message must have the recorded label and encoded size,
+//! * every recorded message must still decode, and re-encode to the same bytes.
+//!
+//! Transcripts are only recorded when the [`RECORD_ENV`] environment variable is set. Otherwise
+//! they are replayed and never written, and a missing transcript fails the test, so running the
+//! tests never modifies the tree. The transcript of every released crate version is committed
+//! alongside the crate, and transcripts of previous versions are kept so the history of the wire
+//! format stays in the tree.
+//!
+//! A change to the wire format therefore fails the test until the crate version is bumped,
+//! which gives downstream deployments a guarantee that peers running the same version can talk
+//! to each other during rolling upgrades.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The environment variable which forces transcripts to be recorded again.
+pub const RECORD_ENV: &str = "MPZ_RECORD_TRANSCRIPTS";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Frame {
+    label: String,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Record,
+    Replay,
+}
+
+/// A protocol transcript which is either being recorded or replayed.
+///
+/// # Panics
+///
+/// This type is meant to be used in tests, so any mismatch with the recorded transcript panics.
+#[derive(Debug)]
+pub struct Transcript {
+    path: PathBuf,
+    mode: Mode,
+    frames: Vec<Frame>,
+    cursor: usize,
+}
+
+impl Transcript {
+    /// Opens the transcript of a protocol.
+    ///
+    /// The transcript is stored at `<dir>/<name>-<version>.bin`. It is recorded if [`RECORD_ENV`]
+    /// is set, and replayed otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory the transcripts are stored in.
+    /// * `name` - The name of the protocol.
+    /// * `version` - The version of the crate implementing the protocol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transcript is replayed and its file does not exist.
+    pub fn open(dir: impl AsRef<Path>, name: &str, version: &str) -> Self {
+        if std::env::var_os(RECORD_ENV).is_some() {
+            Self::record(dir, name, version)
+        } else {
+            Self::replay(dir, name, version)
+        }
+    }
+
+    /// Records the transcript of a protocol, overwriting any existing recording when it is
+    /// finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory the transcripts are stored in.
+    /// * `name` - The name of the protocol.
+    /// * `version` - The version of the crate implementing the protocol.
+    pub fn record(dir: impl AsRef<Path>, name: &str, version: &str) -> Self {
+        Self {
+            path: dir.as_ref().join(format!("{name}-{version}.bin")),
+            mode: Mode::Record,
+            frames: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Replays the recorded transcript of a protocol. A replayed transcript is never written.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory the transcripts are stored in.
+    /// * `name` - The name of the protocol.
+    /// * `version` - The version of the crate implementing the protocol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transcript does not exist.
+    pub fn replay(dir: impl AsRef<Path>, name: &str, version: &str) -> Self {
+        let path = dir.as_ref().join(format!("{name}-{version}.bin"));
+
+        let bytes = fs::read(&path).unwrap_or_else(|err| {
+            panic!(
+                "failed to read transcript {}, set {RECORD_ENV} to record it: {err}",
+                path.display()
+            )
+        });
+        let frames = bincode::deserialize(&bytes)
+            .unwrap_or_else(|err| panic!("malformed transcript {}: {err}", path.display()));
+
+        Self {
+            path,
+            mode: Mode::Replay,
+            frames,
+            cursor: 0,
+        }
+    }
+
+    /// Returns `true` if the transcript is being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.mode == Mode::Record
+    }
+
+    /// Passes a message through the transcript, returning it unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label of the message, unique within the protocol.
+    /// * `msg` - The message.
+    pub fn exchange<T: Serialize + DeserializeOwned>(&mut self, label: &str, msg: T) -> T {
+        let bytes = bincode::serialize(&msg).expect("message should serialize");
+
+        match self.mode {
+            Mode::Record => self.frames.push(Frame {
+                label: label.to_string(),
+                bytes,
+            }),
+            Mode::Replay => {
+                let Some(frame) = self.frames.get(self.cursor) else {
+                    panic!(
+                        "{}: unexpected message `{label}` after the end of the transcript",
+                        self.path.display()
+                    );
+                };
+
+                assert_eq!(
+                    frame.label,
+                    label,
+                    "{}: message {} changed, bump the crate version",
+                    self.path.display(),
+                    self.cursor
+                );
+                assert_eq!(
+                    frame.bytes.len(),
+                    bytes.len(),
+                    "{}: encoded size of `{label}` changed, bump the crate version",
+                    self.path.display()
+                );
+
+                let recorded: T = bincode::deserialize(&frame.bytes).unwrap_or_else(|err| {
+                    panic!(
+                        "{}: recorded `{label}` no longer decodes, bump the crate version: {err}",
+                        self.path.display()
+                    )
+                });
+                assert!(
+                    bincode::serialize(&recorded).expect("message should serialize") == frame.bytes,
+                    "{}: encoding of `{label}` changed, bump the crate version",
+                    self.path.display()
+                );
+            }
+        }
+
+        self.cursor += 1;
+
+        msg
+    }
+
+    /// Finishes the transcript, writing it to disk if it was recorded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a replayed transcript has messages which were not exchanged.
+    pub fn finish(self) {
+        match self.mode {
+            Mode::Record => {
+                if let Some(dir) = self.path.parent() {
+                    fs::create_dir_all(dir).expect("transcript directory should be writable");
+                }
+
+                let bytes = bincode::serialize(&self.frames).expect("transcript should serialize");
+                fs::write(&self.path, bytes).unwrap_or_else(|err| {
+                    panic!("failed to write transcript {}: {err}", self.path.display())
+                });
+            }
+            Mode::Replay => assert_eq!(
+                self.cursor,
+                self.frames.len(),
+                "{}: fewer messages were exchanged than recorded, bump the crate version",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::Block;
+
+    fn run(mut transcript: Transcript, extra: bool) {
+        let block = transcript.exchange("block", Block::ONES);
+        assert_eq!(block, Block::ONES);
+
+        transcript.exchange("blocks", vec![Block::ZERO; 4]);
+
+        if extra {
+            transcript.exchange("extra", 42u8);
+        }
+
+        transcript.finish();
+    }
+
+    #[test]
+    fn test_transcript_replay() {
+        let dir = TempDir::new().unwrap();
+
+        run(Transcript::record(dir.path(), "test", "0.1.0"), false);
+        let path = dir.path().join("test-0.1.0.bin");
+        let recorded = fs::read(&path).unwrap();
+
+        // Replays the recorded transcript without writing it again.
+        run(Transcript::replay(dir.path(), "test", "0.1.0"), false);
+
+        assert_eq!(fs::read(&path).unwrap(), recorded);
+    }
+
+    #[test]
+    #[should_panic(expected = "after the end of the transcript")]
+    fn test_transcript_replay_extra_message() {
+        let dir = TempDir::new().unwrap();
+
+        run(Transcript::record(dir.path(), "test", "0.1.0"), false);
+        run(Transcript::replay(dir.path(), "test", "0.1.0"), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read transcript")]
+    fn test_transcript_replay_missing() {
+        let dir = TempDir::new().unwrap();
+
+        run(Transcript::replay(dir.path(), "test", "0.1.0"), false);
+    }
+}
@@ -0,0 +1,205 @@
+//! This module implements the scalar field of Curve25519, which is also the scalar field of
+//! Ristretto255.
+
+use std::ops::{Add, Mul, Neg};
+
+use ark_curve25519::{Fr, FrConfig};
+use ark_ff::{BigInt, BigInteger, Field as ArkField, FpConfig, MontBackend, One, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use hybrid_array::Array;
+use itybity::{BitLength, FromBitIterator, GetBit, Lsb0, Msb0};
+use num_bigint::ToBigUint;
+use rand::{distributions::Standard, prelude::Distribution};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use typenum::{U253, U32};
+
+use crate::{Field, FieldError};
+
+/// A type for holding scalars of Curve25519.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "[u8; 32]")]
+#[serde(try_from = "[u8; 32]")]
+pub struct Curve25519Scalar(pub(crate) Fr);
+
+opaque_debug::implement!(Curve25519Scalar);
+
+impl Curve25519Scalar {
+    /// Creates a new field element, returning `None` if the value is not a valid element.
+    pub fn new(value: impl ToBigUint) -> Option<Self> {
+        value
+            .to_biguint()
+            .map(|input| Curve25519Scalar(Fr::from(input)))
+    }
+}
+
+impl From<Curve25519Scalar> for [u8; 32] {
+    fn from(value: Curve25519Scalar) -> Self {
+        let mut bytes = [0u8; 32];
+
+        value
+            .0
+            .serialize_with_mode(&mut bytes[..], Compress::No)
+            .expect("field element should be 32 bytes");
+
+        bytes
+    }
+}
+
+impl TryFrom<[u8; 32]> for Curve25519Scalar {
+    type Error = FieldError;
+
+    /// Converts little-endian bytes into a Curve25519Scalar field element.
+    fn try_from(value: [u8; 32]) -> Result<Self, Self::Error> {
+        Fr::deserialize_with_mode(&value[..], Compress::No, Validate::Yes)
+            .map(Curve25519Scalar)
+            .map_err(|err| FieldError(Box::new(Curve25519ScalarError(err))))
+    }
+}
+
+impl TryFrom<Array<u8, U32>> for Curve25519Scalar {
+    type Error = FieldError;
+
+    fn try_from(value: Array<u8, U32>) -> Result<Self, Self::Error> {
+        let inner: [u8; 32] = value.into();
+
+        Curve25519Scalar::try_from(inner)
+    }
+}
+
+impl Distribution<Curve25519Scalar> for Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Curve25519Scalar {
+        Curve25519Scalar(self.sample(rng))
+    }
+}
+
+impl Add for Curve25519Scalar {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Mul for Curve25519Scalar {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Neg for Curve25519Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Field for Curve25519Scalar {
+    type BitSize = U253;
+
+    type ByteSize = U32;
+
+    fn zero() -> Self {
+        Curve25519Scalar(<Fr as Zero>::zero())
+    }
+
+    fn one() -> Self {
+        Curve25519Scalar(<Fr as One>::one())
+    }
+
+    fn two_pow(rhs: u32) -> Self {
+        let mut out = <Fr as One>::one();
+        for _ in 0..rhs {
+            MontBackend::<FrConfig, 4>::double_in_place(&mut out);
+        }
+
+        Curve25519Scalar(out)
+    }
+
+    fn inverse(self) -> Self {
+        Curve25519Scalar(ArkField::inverse(&self.0).expect("Unable to invert field element"))
+    }
+
+    fn to_le_bytes(&self) -> Vec<u8> {
+        BigInt::to_bytes_le(&MontBackend::<FrConfig, 4>::into_bigint(self.0))
+    }
+
+    fn to_be_bytes(&self) -> Vec<u8> {
+        BigInt::to_bytes_be(&MontBackend::<FrConfig, 4>::into_bigint(self.0))
+    }
+}
+
+impl BitLength for Curve25519Scalar {
+    const BITS: usize = 253;
+}
+
+impl GetBit<Lsb0> for Curve25519Scalar {
+    fn get_bit(&self, index: usize) -> bool {
+        MontBackend::<FrConfig, 4>::into_bigint(self.0).get_bit(index)
+    }
+}
+
+impl GetBit<Msb0> for Curve25519Scalar {
+    fn get_bit(&self, index: usize) -> bool {
+        MontBackend::<FrConfig, 4>::into_bigint(self.0).get_bit(252 - index)
+    }
+}
+
+impl FromBitIterator for Curve25519Scalar {
+    fn from_lsb0_iter(iter: impl IntoIterator<Item = bool>) -> Self {
+        Curve25519Scalar(BigInt::from_bits_le(&iter.into_iter().collect::<Vec<bool>>()).into())
+    }
+
+    fn from_msb0_iter(iter: impl IntoIterator<Item = bool>) -> Self {
+        Curve25519Scalar(BigInt::from_bits_be(&iter.into_iter().collect::<Vec<bool>>()).into())
+    }
+}
+
+/// Helper type because [`SerializationError`] does not implement std::error::Error.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct Curve25519ScalarError(SerializationError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpz_core::{prg::Prg, Block};
+    use rand::{Rng, SeedableRng};
+
+    use crate::tests::{test_field_basic, test_field_bit_ops, test_field_compute_product_repeated};
+
+    #[test]
+    fn test_curve25519_scalar_basic() {
+        test_field_basic::<Curve25519Scalar>();
+        assert_eq!(Curve25519Scalar::new(0).unwrap(), Curve25519Scalar::zero());
+        assert_eq!(Curve25519Scalar::new(1).unwrap(), Curve25519Scalar::one());
+    }
+
+    #[test]
+    fn test_curve25519_scalar_compute_product_repeated() {
+        test_field_compute_product_repeated::<Curve25519Scalar>();
+    }
+
+    #[test]
+    fn test_curve25519_scalar_bit_ops() {
+        test_field_bit_ops::<Curve25519Scalar>();
+    }
+
+    #[test]
+    fn test_curve25519_scalar_serialize() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for _ in 0..32 {
+            let a = Curve25519Scalar(rng.gen());
+            let bytes: [u8; 32] = a.into();
+            let b = Curve25519Scalar::try_from(bytes).unwrap();
+
+            assert_eq!(a, b);
+        }
+    }
+}
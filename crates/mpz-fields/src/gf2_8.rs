@@ -0,0 +1,248 @@
+//! This module implements the extension field GF(2^8) used by AES.
+
+use hybrid_array::Array;
+use itybity::{BitLength, FromBitIterator, GetBit, Lsb0, Msb0};
+use rand::{distributions::Standard, prelude::Distribution};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Neg};
+
+use typenum::{U1, U8};
+
+use crate::{Field, FieldError};
+
+/// A type for holding field elements of Gf(2^8).
+///
+/// Elements are reduced by the AES polynomial `x^8 + x^4 + x^3 + x + 1`, so that the field is
+/// the one in which the AES S-box is defined.
+///
+/// Note that the field is small: checks whose soundness depends on the size of the field, such as
+/// the consistency check of malicious OLE, only fail with probability `1 - 2^-8` for a cheating
+/// party.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gf2_8(pub(crate) u8);
+
+opaque_debug::implement!(Gf2_8);
+
+impl Gf2_8 {
+    /// Creates a new field element from a u8,
+    /// mapping the integer to the corresponding polynomial.
+    ///
+    /// For example, 5u8 is mapped to the polynomial `1 + x^2`.
+    pub fn new(input: u8) -> Self {
+        Gf2_8(input)
+    }
+
+    /// Returns the field element as a u8.
+    pub fn to_inner(self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for Gf2_8 {
+    fn from(value: u8) -> Self {
+        Gf2_8(value)
+    }
+}
+
+impl From<Gf2_8> for u8 {
+    fn from(value: Gf2_8) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Array<u8, U1>> for Gf2_8 {
+    type Error = FieldError;
+
+    fn try_from(value: Array<u8, U1>) -> Result<Self, Self::Error> {
+        let [inner]: [u8; 1] = value.into();
+
+        Ok(Gf2_8(inner))
+    }
+}
+
+impl Distribution<Gf2_8> for Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Gf2_8 {
+        Gf2_8(self.sample(rng))
+    }
+}
+
+impl Add for Gf2_8 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Gf2_8 {
+    /// Multiplies the element by `x`, see FIPS 197, section 4.2.1.
+    fn xtime(self) -> Self {
+        // The reduction of `x^8` is `x^4 + x^3 + x + 1`.
+        const R: u8 = 0x1b;
+
+        Gf2_8((self.0 << 1) ^ ((self.0 >> 7).wrapping_neg() & R))
+    }
+}
+
+impl Mul for Gf2_8 {
+    type Output = Self;
+
+    /// Galois field multiplication of two bytes reduced by the AES polynomial.
+    ///
+    /// The multiplication runs in constant time, as the operands may be secret shares.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut x = self;
+        let mut z = 0u8;
+
+        for i in 0..8 {
+            z ^= x.0 & ((rhs.0 >> i) & 1).wrapping_neg();
+            x = x.xtime();
+        }
+
+        Gf2_8(z)
+    }
+}
+
+impl Neg for Gf2_8 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl Field for Gf2_8 {
+    type BitSize = U8;
+
+    type ByteSize = U1;
+
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn one() -> Self {
+        Self::new(1)
+    }
+
+    /// Returns `x^rhs`, reduced by the AES polynomial.
+    fn two_pow(rhs: u32) -> Self {
+        // The multiplicative group has order 255, so `x^255 = 1`.
+        (0..rhs % 255).fold(Self::one(), |acc, _| acc.xtime())
+    }
+
+    /// Galois field inversion of a byte, computing `x^254`.
+    ///
+    /// Zero is mapped to zero, as in the AES S-box.
+    fn inverse(self) -> Self {
+        let mut a = self;
+        let mut out = Self::one();
+        for _ in 0..7 {
+            a = a * a;
+            out = out * a;
+        }
+        out
+    }
+
+    fn to_le_bytes(&self) -> Vec<u8> {
+        vec![self.0]
+    }
+
+    fn to_be_bytes(&self) -> Vec<u8> {
+        vec![self.0]
+    }
+}
+
+impl BitLength for Gf2_8 {
+    const BITS: usize = 8;
+}
+
+impl GetBit<Lsb0> for Gf2_8 {
+    fn get_bit(&self, index: usize) -> bool {
+        GetBit::<Lsb0>::get_bit(&self.0, index)
+    }
+}
+
+impl GetBit<Msb0> for Gf2_8 {
+    fn get_bit(&self, index: usize) -> bool {
+        GetBit::<Msb0>::get_bit(&self.0, index)
+    }
+}
+
+impl FromBitIterator for Gf2_8 {
+    fn from_lsb0_iter(iter: impl IntoIterator<Item = bool>) -> Self {
+        Self(u8::from_lsb0_iter(iter))
+    }
+
+    fn from_msb0_iter(iter: impl IntoIterator<Item = bool>) -> Self {
+        Self(u8::from_msb0_iter(iter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gf2_8;
+    use crate::{
+        tests::{test_field_bit_ops, test_field_compute_product_repeated},
+        Field,
+    };
+
+    #[test]
+    fn test_gf2_8_basic() {
+        // The random element of the generic test could be zero, which has no inverse, so every
+        // non-zero element is checked instead.
+        for x in 1..=u8::MAX {
+            let x = Gf2_8::new(x);
+
+            assert_eq!(x + Gf2_8::zero(), x);
+            assert_eq!(x * Gf2_8::zero(), Gf2_8::zero());
+            assert_eq!(x * Gf2_8::one(), x);
+            assert_eq!(x * x.inverse(), Gf2_8::one());
+            assert_eq!(x + -x, Gf2_8::zero());
+        }
+        assert_eq!(Gf2_8::new(0), Gf2_8::zero());
+        assert_eq!(Gf2_8::new(1), Gf2_8::one());
+    }
+
+    #[test]
+    fn test_gf2_8_compute_product_repeated() {
+        test_field_compute_product_repeated::<Gf2_8>();
+    }
+
+    #[test]
+    fn test_gf2_8_bit_ops() {
+        test_field_bit_ops::<Gf2_8>();
+    }
+
+    #[test]
+    fn test_gf2_8_mul() {
+        // Test vectors from FIPS 197, section 4.2.
+        let a = Gf2_8::new(0x57);
+
+        assert_eq!(a * Gf2_8::new(0x83), Gf2_8::new(0xc1));
+        assert_eq!(a * Gf2_8::new(0x13), Gf2_8::new(0xfe));
+        assert_eq!(a * Gf2_8::new(0x02), Gf2_8::new(0xae));
+    }
+
+    #[test]
+    fn test_gf2_8_two_pow() {
+        let two = Gf2_8::new(2);
+
+        let mut expected = Gf2_8::one();
+        for rhs in 0..600 {
+            assert_eq!(Gf2_8::two_pow(rhs), expected);
+            expected = expected * two;
+        }
+
+        // `x^8` is reduced by the AES polynomial.
+        assert_eq!(Gf2_8::two_pow(8), Gf2_8::new(0x1b));
+        assert_eq!(Gf2_8::two_pow(u32::MAX), Gf2_8::two_pow(u32::MAX % 255));
+    }
+
+    #[test]
+    fn test_gf2_8_inverse() {
+        // The inverse used by the AES S-box, see FIPS 197, section 5.1.1.
+        assert_eq!(Gf2_8::new(0x53).inverse(), Gf2_8::new(0xca));
+        assert_eq!(Gf2_8::zero().inverse(), Gf2_8::zero());
+    }
+}
@@ -4,8 +4,11 @@
 #![deny(clippy::all)]
 #![forbid(unsafe_code)]
 
+pub mod curve25519;
 pub mod gf2_128;
+pub mod gf2_8;
 pub mod p256;
+pub mod p384;
 
 use std::{
     error::Error,
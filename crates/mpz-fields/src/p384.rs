@@ -0,0 +1,221 @@
+//! This module implements the prime field of P384.
+
+use std::ops::{Add, Mul, Neg};
+
+use ark_ff::{BigInt, BigInteger, Field as ArkField, FpConfig, MontBackend, One, Zero};
+use ark_secp384r1::{fq::Fq, FqConfig};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use hybrid_array::Array;
+use itybity::{BitLength, FromBitIterator, GetBit, Lsb0, Msb0};
+use num_bigint::ToBigUint;
+use rand::{distributions::Standard, prelude::Distribution};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use typenum::{U384, U48};
+
+use crate::{Field, FieldError};
+
+/// A type for holding field elements of P384.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "Vec<u8>")]
+#[serde(try_from = "Vec<u8>")]
+pub struct P384(pub(crate) Fq);
+
+opaque_debug::implement!(P384);
+
+impl P384 {
+    /// Creates a new field element, returning `None` if the value is not a valid element.
+    pub fn new(value: impl ToBigUint) -> Option<Self> {
+        value.to_biguint().map(|input| P384(Fq::from(input)))
+    }
+}
+
+impl From<P384> for [u8; 48] {
+    fn from(value: P384) -> Self {
+        let mut bytes = [0u8; 48];
+
+        value
+            .0
+            .serialize_with_mode(&mut bytes[..], Compress::No)
+            .expect("field element should be 48 bytes");
+
+        bytes
+    }
+}
+
+impl TryFrom<[u8; 48]> for P384 {
+    type Error = FieldError;
+
+    /// Converts little-endian bytes into a P384 field element.
+    fn try_from(value: [u8; 48]) -> Result<Self, Self::Error> {
+        Fq::deserialize_with_mode(&value[..], Compress::No, Validate::Yes)
+            .map(P384)
+            .map_err(|err| FieldError(Box::new(P384Error(err))))
+    }
+}
+
+// Serde only supports arrays of up to 32 elements, so elements are serialized as a vector.
+impl From<P384> for Vec<u8> {
+    fn from(value: P384) -> Self {
+        <[u8; 48]>::from(value).to_vec()
+    }
+}
+
+impl TryFrom<Vec<u8>> for P384 {
+    type Error = FieldError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        let bytes: [u8; 48] = value
+            .try_into()
+            .map_err(|_| FieldError(Box::new(P384Error(SerializationError::InvalidData))))?;
+
+        P384::try_from(bytes)
+    }
+}
+
+impl TryFrom<Array<u8, U48>> for P384 {
+    type Error = FieldError;
+
+    fn try_from(value: Array<u8, U48>) -> Result<Self, Self::Error> {
+        let inner: [u8; 48] = value.into();
+
+        P384::try_from(inner)
+    }
+}
+
+impl Distribution<P384> for Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> P384 {
+        P384(self.sample(rng))
+    }
+}
+
+impl Add for P384 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Mul for P384 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Neg for P384 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Field for P384 {
+    type BitSize = U384;
+
+    type ByteSize = U48;
+
+    fn zero() -> Self {
+        P384(<Fq as Zero>::zero())
+    }
+
+    fn one() -> Self {
+        P384(<Fq as One>::one())
+    }
+
+    fn two_pow(rhs: u32) -> Self {
+        let mut out = <Fq as One>::one();
+        for _ in 0..rhs {
+            MontBackend::<FqConfig, 6>::double_in_place(&mut out);
+        }
+
+        P384(out)
+    }
+
+    fn inverse(self) -> Self {
+        P384(ArkField::inverse(&self.0).expect("Unable to invert field element"))
+    }
+
+    fn to_le_bytes(&self) -> Vec<u8> {
+        BigInt::to_bytes_le(&MontBackend::<FqConfig, 6>::into_bigint(self.0))
+    }
+
+    fn to_be_bytes(&self) -> Vec<u8> {
+        BigInt::to_bytes_be(&MontBackend::<FqConfig, 6>::into_bigint(self.0))
+    }
+}
+
+impl BitLength for P384 {
+    const BITS: usize = 384;
+}
+
+impl GetBit<Lsb0> for P384 {
+    fn get_bit(&self, index: usize) -> bool {
+        MontBackend::<FqConfig, 6>::into_bigint(self.0).get_bit(index)
+    }
+}
+
+impl GetBit<Msb0> for P384 {
+    fn get_bit(&self, index: usize) -> bool {
+        MontBackend::<FqConfig, 6>::into_bigint(self.0).get_bit(383 - index)
+    }
+}
+
+impl FromBitIterator for P384 {
+    fn from_lsb0_iter(iter: impl IntoIterator<Item = bool>) -> Self {
+        P384(BigInt::from_bits_le(&iter.into_iter().collect::<Vec<bool>>()).into())
+    }
+
+    fn from_msb0_iter(iter: impl IntoIterator<Item = bool>) -> Self {
+        P384(BigInt::from_bits_be(&iter.into_iter().collect::<Vec<bool>>()).into())
+    }
+}
+
+/// Helper type because [`SerializationError`] does not implement std::error::Error.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct P384Error(SerializationError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpz_core::{prg::Prg, Block};
+    use rand::{Rng, SeedableRng};
+
+    use crate::tests::{test_field_basic, test_field_bit_ops, test_field_compute_product_repeated};
+
+    #[test]
+    fn test_p384_basic() {
+        test_field_basic::<P384>();
+        assert_eq!(P384::new(0).unwrap(), P384::zero());
+        assert_eq!(P384::new(1).unwrap(), P384::one());
+    }
+
+    #[test]
+    fn test_p384_compute_product_repeated() {
+        test_field_compute_product_repeated::<P384>();
+    }
+
+    #[test]
+    fn test_p384_bit_ops() {
+        test_field_bit_ops::<P384>();
+    }
+
+    #[test]
+    fn test_p384_serialize() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for _ in 0..32 {
+            let a = P384(rng.gen());
+            let bytes: [u8; 48] = a.into();
+            let b = P384::try_from(bytes).unwrap();
+
+            assert_eq!(a, b);
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use mpz_circuits::circuits::AES128;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mpz_circuits::{
+    circuits::AES128,
+    random::{RandomCircuit, RandomCircuitConfig},
+};
 use mpz_garble_core::{ChaChaEncoder, Encoder, Evaluator, Generator};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
 
 fn criterion_benchmark(c: &mut Criterion) {
     let mut gb_group = c.benchmark_group("garble");
@@ -43,6 +48,20 @@ fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    #[cfg(feature = "parallel")]
+    gb_group.bench_function("aes128_batched_parallel", |b| {
+        let mut gen = Generator::default();
+        b.iter(|| {
+            let mut gen_iter = gen
+                .generate_batched_parallel(&AES128, encoder.delta(), full_inputs.clone())
+                .unwrap();
+
+            let _: Vec<_> = gen_iter.by_ref().collect();
+
+            black_box(gen_iter.finish().unwrap())
+        })
+    });
+
     gb_group.bench_function("aes128_with_hash", |b| {
         let mut gen = Generator::default();
         b.iter(|| {
@@ -80,6 +99,44 @@ fn criterion_benchmark(c: &mut Criterion) {
             black_box(ev_consumer.finish().unwrap());
         })
     });
+
+    drop(ev_group);
+
+    let mut random_group = c.benchmark_group("garble_random");
+
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    for (gate_count, depth) in [(1 << 12, 16), (1 << 12, 1 << 10), (1 << 16, 64)] {
+        let config = RandomCircuitConfig {
+            gate_count,
+            depth,
+            ..Default::default()
+        };
+        let random = RandomCircuit::new(&mut rng, &config);
+        let circ = random.circuit();
+
+        let full_inputs = circ
+            .inputs()
+            .iter()
+            .map(|value| encoder.encode_by_type(0, &value.value_type()))
+            .collect::<Vec<_>>();
+
+        random_group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{gate_count}x{depth}")),
+            &full_inputs,
+            |b, full_inputs| {
+                let mut gen = Generator::default();
+                b.iter(|| {
+                    let mut gen_iter = gen
+                        .generate_batched(circ, encoder.delta(), full_inputs.clone())
+                        .unwrap();
+
+                    let _: Vec<_> = gen_iter.by_ref().collect();
+
+                    black_box(gen_iter.finish().unwrap())
+                })
+            },
+        );
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,251 @@
+//! Deterministic garbling for reproducibility audits of standalone executions.
+//!
+//! All the randomness used to garble a standalone circuit execution, the global offset and the
+//! input labels, can be derived from an encoder seed, a digest of the circuit and the index of
+//! the execution. Anyone holding the seed can then re-garble the circuit and check that it
+//! reproduces the same transcript hash, which is the hash of the encrypted gates computed by
+//! [`EncryptedGateIter::enable_hasher`](crate::EncryptedGateIter::enable_hasher).
+//!
+//! Only executions garbled with [`DeterministicExecution::garble`] can be audited this way. A
+//! [`Generator`] used by a VM garbles every execution under the single delta of its encoder, so
+//! that the outputs of one circuit can be used as the inputs of another, and the labels of an
+//! execution depend on the values it was linked to. Such executions are instead checked by
+//! re-garbling each of them with [`Generator::regarble`] once the encoder seed is revealed.
+//!
+//! # Security
+//!
+//! Revealing the encoder seed reveals the delta and all the labels of every execution derived
+//! from it. It must only be revealed once the garbled circuits no longer need to be private.
+
+use blake3::Hasher;
+use mpz_circuits::{types::BinaryRepr, Circuit, Gate};
+use mpz_core::hash::Hash;
+
+use crate::{
+    encoding::{state, EncodedValue},
+    ChaChaEncoder, Encoder, Generator, GeneratorError, GeneratorOutput,
+};
+
+const CIRCUIT_DIGEST_CONTEXT: &str = "mpz-garble-core circuit digest";
+const EXECUTION_SEED_CONTEXT: &str = "mpz-garble-core execution seed";
+
+/// Errors that can occur when auditing a garbled circuit.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum AuditError {
+    #[error(transparent)]
+    GeneratorError(#[from] GeneratorError),
+    #[error("re-garbled transcript hash does not match: expected {expected:?}, got {actual:?}")]
+    TranscriptMismatch { expected: Hash, actual: Hash },
+}
+
+/// Returns a digest which uniquely identifies the structure of a circuit.
+///
+/// The digest covers the inputs, gates and outputs of the circuit, so two circuits with the
+/// same digest are garbled identically.
+pub fn circuit_digest(circ: &Circuit) -> Hash {
+    fn update_repr(hasher: &mut Hasher, repr: &BinaryRepr) {
+        hasher.update(&(repr.len() as u64).to_le_bytes());
+        for node in repr.iter() {
+            hasher.update(&(node.id() as u64).to_le_bytes());
+        }
+    }
+
+    let mut hasher = Hasher::new_derive_key(CIRCUIT_DIGEST_CONTEXT);
+
+    hasher.update(&(circ.feed_count() as u64).to_le_bytes());
+
+    hasher.update(&(circ.inputs().len() as u64).to_le_bytes());
+    for input in circ.inputs() {
+        update_repr(&mut hasher, input);
+    }
+
+    hasher.update(&(circ.gates().len() as u64).to_le_bytes());
+    for gate in circ.gates() {
+        let tag: u8 = match gate {
+            Gate::Xor { .. } => 0,
+            Gate::And { .. } => 1,
+            Gate::Inv { .. } => 2,
+            Gate::Const { value: false, .. } => 3,
+            Gate::Const { value: true, .. } => 4,
+        };
+        hasher.update(&[tag]);
+
+        for node in [gate.x().map(|x| x.id()), gate.y().map(|y| y.id())]
+            .into_iter()
+            .flatten()
+            .chain([gate.z().id()])
+        {
+            hasher.update(&(node as u64).to_le_bytes());
+        }
+    }
+
+    hasher.update(&(circ.outputs().len() as u64).to_le_bytes());
+    for output in circ.outputs() {
+        update_repr(&mut hasher, output);
+    }
+
+    Hash::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+/// Derives the seed used to garble the `index`-th standalone execution of a circuit.
+///
+/// Each execution has its own delta, so its labels can not be chained into other executions.
+///
+/// # Arguments
+///
+/// * `encoder_seed` - The seed of the generator's encoder.
+/// * `digest` - The digest of the circuit, see [`circuit_digest`].
+/// * `index` - The index of the execution.
+pub fn execution_seed(encoder_seed: [u8; 32], digest: &Hash, index: u64) -> [u8; 32] {
+    let mut hasher = Hasher::new_derive_key(EXECUTION_SEED_CONTEXT);
+    hasher.update(&encoder_seed);
+    hasher.update(digest.as_bytes());
+    hasher.update(&index.to_le_bytes());
+
+    hasher.finalize().into()
+}
+
+/// A standalone circuit execution whose garbling randomness is derived deterministically.
+#[derive(Debug)]
+pub struct DeterministicExecution<'a> {
+    circ: &'a Circuit,
+    digest: Hash,
+    index: u64,
+    encoder: ChaChaEncoder,
+}
+
+impl<'a> DeterministicExecution<'a> {
+    /// Creates a new execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoder_seed` - The seed of the generator's encoder.
+    /// * `circ` - The circuit to garble.
+    /// * `index` - The index of the execution.
+    pub fn new(encoder_seed: [u8; 32], circ: &'a Circuit, index: u64) -> Self {
+        let digest = circuit_digest(circ);
+        let encoder = ChaChaEncoder::new(execution_seed(encoder_seed, &digest, index));
+
+        Self {
+            circ,
+            digest,
+            index,
+            encoder,
+        }
+    }
+
+    /// Returns the digest of the circuit.
+    pub fn digest(&self) -> &Hash {
+        &self.digest
+    }
+
+    /// Returns the index of the execution.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the encoder from which the delta and input labels are derived.
+    pub fn encoder(&self) -> &ChaChaEncoder {
+        &self.encoder
+    }
+
+    /// Returns the full encodings of the inputs of the circuit.
+    ///
+    /// The encoding of the `i`-th input is derived from stream id `i` of the encoder.
+    pub fn inputs(&self) -> Vec<EncodedValue<state::Full>> {
+        self.circ
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| self.encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect()
+    }
+
+    /// Garbles the circuit, returning the encoded outputs and the transcript hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen` - The generator to use.
+    /// * `privacy_free` - Whether to garble in privacy-free mode.
+    pub fn garble(
+        &self,
+        gen: &mut Generator,
+        privacy_free: bool,
+    ) -> Result<GeneratorOutput, GeneratorError> {
+        gen.regarble(self.circ, self.encoder.delta(), self.inputs(), privacy_free)
+    }
+
+    /// Re-garbles the circuit and checks that it reproduces the expected transcript hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `privacy_free` - Whether the circuit was garbled in privacy-free mode.
+    /// * `expected` - The expected transcript hash.
+    pub fn verify(&self, privacy_free: bool, expected: &Hash) -> Result<(), AuditError> {
+        let GeneratorOutput { hash, .. } = self.garble(&mut Generator::default(), privacy_free)?;
+        let actual = hash.expect("hasher is enabled");
+
+        if &actual != expected {
+            return Err(AuditError::TranscriptMismatch {
+                expected: *expected,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::circuits::AES128;
+
+    use super::*;
+
+    const SEED: [u8; 32] = [42u8; 32];
+
+    #[test]
+    fn test_deterministic_garbling() {
+        let exec = DeterministicExecution::new(SEED, &AES128, 0);
+
+        let mut gen = Generator::default();
+        let a = exec.garble(&mut gen, false).unwrap();
+        let b = DeterministicExecution::new(SEED, &AES128, 0)
+            .garble(&mut gen, false)
+            .unwrap();
+
+        assert_eq!(a.hash, b.hash);
+        assert_eq!(a.outputs, b.outputs);
+
+        exec.verify(false, &a.hash.unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_deterministic_garbling_index() {
+        let mut gen = Generator::default();
+        let a = DeterministicExecution::new(SEED, &AES128, 0)
+            .garble(&mut gen, false)
+            .unwrap();
+        let b = DeterministicExecution::new(SEED, &AES128, 1)
+            .garble(&mut gen, false)
+            .unwrap();
+
+        assert_ne!(a.hash, b.hash);
+
+        let err = DeterministicExecution::new(SEED, &AES128, 1)
+            .verify(false, &a.hash.unwrap())
+            .unwrap_err();
+
+        assert!(matches!(err, AuditError::TranscriptMismatch { .. }));
+    }
+
+    #[test]
+    fn test_circuit_digest() {
+        assert_eq!(circuit_digest(&AES128), circuit_digest(&AES128));
+        assert_ne!(
+            circuit_digest(&AES128),
+            circuit_digest(&(**AES128).clone().reverse_inputs())
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Conversion of garbled circuit encodings into IT-MACs.
+//!
+//! With Free-XOR, the active label of a bit `x` is `W_0 + x * Δ`, which is exactly an IT-MAC of
+//! `x` under `Δ` with key `W_0`, see [`mpz_zk_core::auth`]. An encoded value can thus be used as
+//! an authenticated value of a zero-knowledge proof in which the evaluator is the prover and the
+//! generator is the verifier, provided that the proof uses the same global correlation as the
+//! garbled circuit, ie the random COTs of the proof are correlated with
+//! [`Delta`](crate::encoding::Delta).
+//!
+//! The evaluator must know the bits it is authenticated for, so the generator sends it the
+//! [`Decoding`] of the value. The evaluator does not learn `Δ`, so it can not forge a MAC for a
+//! different bit. A generator which sends an incorrect decoding only causes the proofs of the
+//! evaluator to fail.
+
+use itybity::IntoBits;
+use mpz_zk_core::auth::{AuthBit, AuthKey};
+
+use crate::encoding::{state, Decoding, EncodedValue, ValueError};
+
+/// Returns the IT-MAC keys of the bits of an encoded value, held by the generator.
+///
+/// The keys are authenticated under the [delta](EncodedValue::delta) of the value.
+///
+/// # Arguments
+///
+/// * `value` - The full encoding of the value.
+pub fn encoding_keys(value: &EncodedValue<state::Full>) -> Vec<AuthKey> {
+    value
+        .iter()
+        .map(|low| AuthKey::new(low.to_inner()))
+        .collect()
+}
+
+/// Returns the IT-MAC authenticated bits of an encoded value, held by the evaluator.
+///
+/// # Arguments
+///
+/// * `value` - The active encoding of the value.
+/// * `decoding` - The decoding of the value, sent by the generator.
+pub fn encoding_bits(
+    value: &EncodedValue<state::Active>,
+    decoding: &Decoding,
+) -> Result<Vec<AuthBit>, ValueError> {
+    let bits = value.decode(decoding)?.into_iter_lsb0();
+
+    Ok(value
+        .iter()
+        .zip(bits)
+        .map(|(active, bit)| AuthBit::new(bit, active.to_inner()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::types::{StaticValueType, Value};
+
+    use crate::{ChaChaEncoder, Encoder};
+
+    use super::*;
+
+    #[test]
+    fn test_encoding_bridge() {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+        let delta = *encoder.delta();
+
+        let full = encoder.encode_by_type(0, &<[u8; 4]>::value_type());
+        let active = full.select([1u8, 2, 3, 255]).unwrap();
+
+        let keys = encoding_keys(&full);
+        let bits = encoding_bits(&active, &full.decoding()).unwrap();
+
+        let expected: Vec<bool> = Value::from([1u8, 2, 3, 255]).into_iter_lsb0().collect();
+        assert_eq!(
+            bits.iter().map(|bit| bit.bit()).collect::<Vec<_>>(),
+            expected
+        );
+
+        for (key, bit) in keys.iter().zip(&bits) {
+            key.verify(bit.bit(), bit.mac(), delta).unwrap();
+        }
+
+        // Linear operations on the converted values stay consistent.
+        let sum = bits[0] ^ bits[9];
+        let key = keys[0] ^ keys[9];
+        key.verify(sum.bit(), sum.mac(), delta).unwrap();
+
+        // A MAC does not authenticate the other bit.
+        assert!(keys[0]
+            .verify(!bits[0].bit(), bits[0].mac(), delta)
+            .is_err());
+    }
+}
@@ -3,14 +3,13 @@ use std::ops::Index;
 use mpz_core::Block;
 use serde::{Deserialize, Serialize};
 
-use crate::{EncodingCommitment, DEFAULT_BATCH_SIZE};
+use crate::EncodingCommitment;
 
 /// Encrypted gate truth table
 ///
 /// For the half-gate garbling scheme a truth table will typically have 2 rows, except for in
-/// privacy-free garbling mode where it will be reduced to 1.
-///
-/// We do not yet support privacy-free garbling.
+/// privacy-free garbling mode where it will be reduced to 1. In privacy-free mode each encrypted
+/// gate holds the rows of two AND gates.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct EncryptedGate(#[serde(with = "serde_arrays")] pub(crate) [Block; 2]);
 
@@ -25,6 +24,14 @@ impl EncryptedGate {
         bytes[16..].copy_from_slice(&self.0[1].to_bytes());
         bytes
     }
+
+    pub(crate) fn from_bytes(bytes: [u8; 32]) -> Self {
+        let mut low = [0u8; 16];
+        let mut high = [0u8; 16];
+        low.copy_from_slice(&bytes[..16]);
+        high.copy_from_slice(&bytes[16..]);
+        Self([Block::new(low), Block::new(high)])
+    }
 }
 
 impl Index<usize> for EncryptedGate {
@@ -37,22 +44,34 @@ impl Index<usize> for EncryptedGate {
 
 /// A batch of encrypted gates.
 ///
-/// # Parameters
-///
-/// - `N`: The size of a batch.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct EncryptedGateBatch<const N: usize = DEFAULT_BATCH_SIZE>(
-    #[serde(with = "serde_arrays")] [EncryptedGate; N],
-);
+/// The batched generators emit [`DEFAULT_BATCH_SIZE`](crate::DEFAULT_BATCH_SIZE) gates per
+/// batch unless configured otherwise. The last batch of a circuit may be smaller.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedGateBatch(Vec<EncryptedGate>);
 
-impl<const N: usize> EncryptedGateBatch<N> {
+impl EncryptedGateBatch {
     /// Creates a new batch of encrypted gates.
-    pub fn new(batch: [EncryptedGate; N]) -> Self {
+    pub fn new(batch: Vec<EncryptedGate>) -> Self {
         Self(batch)
     }
 
-    /// Returns the inner array.
-    pub fn into_array(self) -> [EncryptedGate; N] {
+    /// Returns the number of encrypted gates in the batch.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the encrypted gates of the batch.
+    pub fn gates(&self) -> &[EncryptedGate] {
+        &self.0
+    }
+
+    /// Returns the inner vector of encrypted gates.
+    pub fn into_vec(self) -> Vec<EncryptedGate> {
         self.0
     }
 }
@@ -8,6 +8,9 @@ use super::{state, value::Encode, Delta, EncodedValue, Label};
 
 const DELTA_STREAM_ID: u64 = u64::MAX;
 
+/// Context string of the key derivation of child encoder seeds.
+const CHILD_CONTEXT: &str = "mpz-garble-core chacha encoder child";
+
 /// This trait is used to encode values using a global offset (delta).
 ///
 /// Implementations of this trait should be _idempotent_, meaning that calling
@@ -61,6 +64,30 @@ impl ChaChaEncoder {
         Self { seed, delta }
     }
 
+    /// Returns a child encoder for the provided label.
+    ///
+    /// The child uses the same delta, but its encodings are independent of the encodings of the
+    /// parent and of children with other labels. This way each subsystem, or each thread encoding
+    /// values on its own, can use its own child without coordinating stream ids with the others.
+    ///
+    /// The seed of the child is derived from the seed of the parent, so revealing it does not
+    /// reveal the encodings of the parent. It does reveal the shared delta however.
+    ///
+    /// Note that [`ChaChaEncoder::new`] derives the delta from the seed, so a child can not be
+    /// recreated from its seed alone, it must be derived from its parent again.
+    ///
+    /// * `label` - Label of the child
+    pub fn child(&self, label: &str) -> Self {
+        let mut key_material = Vec::with_capacity(self.seed.len() + label.len());
+        key_material.extend_from_slice(&self.seed);
+        key_material.extend_from_slice(label.as_bytes());
+
+        Self {
+            seed: blake3::derive_key(CHILD_CONTEXT, &key_material),
+            delta: self.delta,
+        }
+    }
+
     /// Returns the ChaChaRng for the provided stream id
     ///
     /// * `id` - Id of value
@@ -105,6 +132,11 @@ impl Encoder for ChaChaEncoder {
             ValueType::U32 => self.encode::<u32>(id).into(),
             ValueType::U64 => self.encode::<u64>(id).into(),
             ValueType::U128 => self.encode::<u128>(id).into(),
+            ValueType::I8 => self.encode::<i8>(id).into(),
+            ValueType::I16 => self.encode::<i16>(id).into(),
+            ValueType::I32 => self.encode::<i32>(id).into(),
+            ValueType::I64 => self.encode::<i64>(id).into(),
+            ValueType::I128 => self.encode::<i128>(id).into(),
             ValueType::Array(_, _) => {
                 let mut rng = self.get_rng(id);
 
@@ -140,6 +172,8 @@ mod test {
     #[case::u64(PhantomData::<u64>)]
     #[case::u64(PhantomData::<u64>)]
     #[case::u128(PhantomData::<u128>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i64(PhantomData::<i64>)]
     fn test_encoder_idempotent<T: Encode + BinaryLength + Default>(
         encoder: ChaChaEncoder,
         #[case] _pd: PhantomData<T>,
@@ -151,4 +185,17 @@ mod test {
 
         assert_eq!(encoded, encoded2);
     }
+
+    #[rstest]
+    fn test_encoder_child(encoder: ChaChaEncoder) {
+        let child = encoder.child("a");
+
+        assert_eq!(child.delta(), encoder.delta());
+        assert_ne!(child.encode::<u64>(0), encoder.encode::<u64>(0));
+        assert_ne!(child.encode::<u64>(0), encoder.child("b").encode::<u64>(0));
+        assert_ne!(child.encode::<u64>(0), child.child("a").encode::<u64>(0));
+
+        // Children are deterministic.
+        assert_eq!(child.encode::<u64>(0), encoder.child("a").encode::<u64>(0));
+    }
 }
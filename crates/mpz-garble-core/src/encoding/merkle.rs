@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+
+use mpz_core::{
+    commit::merkle::{MerkleError, MerkleProof, MerkleTree},
+    hash::{Hash, SecureHash},
+};
+
+use crate::encoding::{state, EncodedValue, EncodingCommitment, ValueError};
+
+/// A Merkle tree commitment to the encodings of the elements of an array.
+///
+/// Unlike [`EncodingCommitment`], which must be sent in full to verify any part of an array, the
+/// evaluator only receives the [root](MerkleEncodingRoot) of the tree. The generator can then
+/// [open](MerkleEncodingCommitment::open) individual elements with a proof logarithmic in the
+/// length of the array.
+///
+/// Each leaf is the hash of the [`EncodingCommitment`] of an element, so the same security
+/// considerations apply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleEncodingCommitment {
+    elements: Vec<EncodingCommitment>,
+    tree: MerkleTree,
+}
+
+impl MerkleEncodingCommitment {
+    pub(crate) fn new(value: &EncodedValue<state::Full>) -> Result<Self, ValueError> {
+        let EncodedValue::Array(values) = value else {
+            return Err(ValueError::ExpectedArray(value.value_type()));
+        };
+
+        let elements: Vec<_> = values.iter().map(|value| value.commit()).collect();
+        let tree = MerkleTree::from_values(&elements);
+
+        Ok(Self { elements, tree })
+    }
+
+    /// Returns the root of the commitment, which is sent to the evaluator.
+    pub fn root(&self) -> MerkleEncodingRoot {
+        MerkleEncodingRoot {
+            root: self.tree.root(),
+            len: self.elements.len(),
+        }
+    }
+
+    /// Returns the number of elements in the array.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Opens the commitments to the elements at the provided indices.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The indices of the elements to open.
+    pub fn open(&self, indices: &[usize]) -> Result<MerkleEncodingOpening, ValueError> {
+        let proof = self.tree.proof(indices)?;
+
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let elements = indices
+            .into_iter()
+            .map(|index| (index, self.elements[index].clone()))
+            .collect();
+
+        Ok(MerkleEncodingOpening { elements, proof })
+    }
+}
+
+/// The root of a [`MerkleEncodingCommitment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleEncodingRoot {
+    root: Hash,
+    len: usize,
+}
+
+impl MerkleEncodingRoot {
+    /// Returns the number of elements in the committed array.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Verifies the active encodings of elements of the array against an opening.
+    ///
+    /// Every provided element must be contained in the opening.
+    ///
+    /// # Arguments
+    ///
+    /// * `opening` - The opening sent by the generator.
+    /// * `active` - The active encodings of the elements, as pairs of index and encoding.
+    pub fn verify(
+        &self,
+        opening: &MerkleEncodingOpening,
+        active: &[(usize, EncodedValue<state::Active>)],
+    ) -> Result<(), ValueError> {
+        if opening.proof.leaf_count() != self.len {
+            return Err(ValueError::InvalidCommitment);
+        }
+
+        let leaves: Vec<_> = opening
+            .elements
+            .iter()
+            .map(|(index, commitment)| (*index, commitment.hash()))
+            .collect();
+        opening.proof.verify(&self.root, &leaves)?;
+
+        for (index, value) in active {
+            let (_, commitment) = opening
+                .elements
+                .iter()
+                .find(|(opened, _)| opened == index)
+                .ok_or(ValueError::InvalidCommitment)?;
+
+            commitment.verify(value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An opening of elements of a [`MerkleEncodingCommitment`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleEncodingOpening {
+    elements: Vec<(usize, EncodingCommitment)>,
+    proof: MerkleProof,
+}
+
+impl MerkleEncodingOpening {
+    /// Returns the indices of the opened elements.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.elements.iter().map(|(index, _)| *index)
+    }
+}
+
+impl From<MerkleError> for ValueError {
+    fn from(err: MerkleError) -> Self {
+        match err {
+            MerkleError::InvalidProof => ValueError::InvalidCommitment,
+            err => ValueError::MerkleError(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::types::{StaticValueType, ValueType};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+    use rstest::*;
+
+    use crate::{ChaChaEncoder, Encoder};
+
+    use super::*;
+
+    #[fixture]
+    fn encoder() -> ChaChaEncoder {
+        ChaChaEncoder::new([0u8; 32])
+    }
+
+    #[rstest]
+    fn test_merkle_encoding_commitment(encoder: ChaChaEncoder) {
+        let mut rng = ChaCha12Rng::from_seed([0u8; 32]);
+        let value: [u32; 37] = std::array::from_fn(|_| rng.gen());
+
+        let encoded = encoder.encode_by_type(0, &<[u32; 37]>::value_type());
+        let commitment = encoded.commit_merkle().unwrap();
+        let root = commitment.root();
+        assert_eq!(root.len(), 37);
+
+        let EncodedValue::Array(active) = encoded.select(value).unwrap() else {
+            unreachable!()
+        };
+
+        let indices = [3, 36, 17];
+        let opening = commitment.open(&indices).unwrap();
+        let opened: Vec<_> = indices.iter().map(|&i| (i, active[i].clone())).collect();
+        root.verify(&opening, &opened).unwrap();
+
+        // The active encoding of a different element does not match.
+        let err = root
+            .verify(&opening, &[(3, active[4].clone())])
+            .unwrap_err();
+        assert!(matches!(err, ValueError::InvalidCommitment));
+
+        // Elements which were not opened can not be verified.
+        let err = root
+            .verify(&opening, &[(4, active[4].clone())])
+            .unwrap_err();
+        assert!(matches!(err, ValueError::InvalidCommitment));
+    }
+
+    #[rstest]
+    fn test_merkle_encoding_commitment_wrong_root(encoder: ChaChaEncoder) {
+        let ty = ValueType::new_array::<u8>(8);
+        let encoded = encoder.encode_by_type(0, &ty);
+        let other = encoder.encode_by_type(1, &ty);
+
+        let EncodedValue::Array(active) = encoded.select(vec![0u8; 8]).unwrap() else {
+            unreachable!()
+        };
+
+        let opening = encoded.commit_merkle().unwrap().open(&[5]).unwrap();
+        let root = other.commit_merkle().unwrap().root();
+
+        let err = root
+            .verify(&opening, &[(5, active[5].clone())])
+            .unwrap_err();
+        assert!(matches!(err, ValueError::InvalidCommitment));
+    }
+
+    #[rstest]
+    fn test_merkle_encoding_commitment_not_array(encoder: ChaChaEncoder) {
+        let encoded = encoder.encode_by_type(0, &u64::value_type());
+
+        assert!(matches!(
+            encoded.commit_merkle(),
+            Err(ValueError::ExpectedArray(_))
+        ));
+    }
+}
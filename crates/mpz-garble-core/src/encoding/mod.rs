@@ -14,6 +14,7 @@
 
 mod encoder;
 mod equality;
+mod merkle;
 mod ops;
 mod value;
 
@@ -28,6 +29,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 
 pub use encoder::{ChaChaEncoder, Encoder};
 pub use equality::EqualityCheck;
+pub use merkle::{MerkleEncodingCommitment, MerkleEncodingOpening, MerkleEncodingRoot};
 pub use value::{Decoding, Encode, EncodedValue, EncodingCommitment, ValueError};
 
 /// Global binary offset used by the Free-XOR technique to create label
@@ -279,6 +281,12 @@ impl Label {
     /// Length of a label in bytes
     pub const LEN: usize = Block::LEN;
 
+    /// Active label of a constant wire.
+    ///
+    /// The value of a constant wire is public, so the evaluator uses a fixed active label and
+    /// the generator chooses the 0-bit label accordingly.
+    pub(crate) const CONSTANT: Self = Self(Block::ZERO);
+
     /// Creates a new label
     #[inline]
     pub fn new(value: Block) -> Self {
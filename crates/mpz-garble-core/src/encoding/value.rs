@@ -3,10 +3,10 @@ use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use std::ops::BitXor;
 
-use mpz_circuits::types::{StaticValueType, TypeError, Value, ValueType};
+use mpz_circuits::types::{Endianness, StaticValueType, TypeError, Value, ValueType};
 use mpz_core::{hash::DomainSeparatedHash, impl_domain_separated_hash, Block};
 
-use crate::encoding::{state, Delta, Label, LabelState, Labels};
+use crate::encoding::{state, Delta, Label, LabelState, Labels, MerkleEncodingCommitment};
 
 /// Error related to encoded values.
 #[derive(Debug, thiserror::Error)]
@@ -20,6 +20,10 @@ pub enum ValueError {
     InvalidActiveEncoding,
     #[error("invalid commitment")]
     InvalidCommitment,
+    #[error("expected an array, got: {0:?}")]
+    ExpectedArray(ValueType),
+    #[error(transparent)]
+    MerkleError(mpz_core::commit::merkle::MerkleError),
 }
 
 /// A trait for encoding values.
@@ -92,6 +96,13 @@ macro_rules! define_encoded_value {
                 EncodingCommitment::new(self)
             }
 
+            /// Returns a Merkle tree commitment to the encodings of the elements of an array.
+            ///
+            /// Returns an error if the value is not an array.
+            pub fn commit_merkle(&self) -> Result<MerkleEncodingCommitment, ValueError> {
+                MerkleEncodingCommitment::new(self)
+            }
+
             /// Creates an encoded value from a value type and a list of labels.
             pub fn from_labels(
                 value_type: ValueType,
@@ -165,6 +176,20 @@ macro_rules! define_encoded_value {
                 Ok(active)
             }
 
+            /// Returns the active encoding of a value given by its bytes.
+            ///
+            /// # Arguments
+            ///
+            /// * `bytes` - The bytes of the value.
+            /// * `endianness` - The convention the bytes are in.
+            pub fn select_bytes(
+                &self,
+                bytes: &[u8],
+                endianness: Endianness,
+            ) -> Result<EncodedValue<state::Active>, ValueError> {
+                self.select(Value::from_bytes(&self.value_type(), bytes, endianness)?)
+            }
+
             /// Verifies that the active encoding is authentic.
             pub fn verify(&self, active: &EncodedValue<state::Active>) -> Result<(), ValueError> {
                 match (self, active) {
@@ -260,6 +285,16 @@ macro_rules! define_encoded_value {
                 Ok(value)
             }
 
+            /// Decodes an encoded value into its bytes using decoding information.
+            ///
+            /// # Arguments
+            ///
+            /// * `decoding` - The decoding information.
+            /// * `endianness` - The convention to return the bytes in.
+            pub fn decode_bytes(&self, decoding: &Decoding, endianness: Endianness) -> Result<Vec<u8>, ValueError> {
+                Ok(self.decode(decoding)?.to_bytes(endianness)?)
+            }
+
             /// Recovers the full encoding of a value using the decoding and delta.
             pub fn recover(&self, decoding: &Decoding, delta: Delta) -> Result<EncodedValue<state::Full>, TypeError> {
                 match (self, decoding) {
@@ -306,7 +341,7 @@ macro_rules! define_encoded_value {
     };
 }
 
-define_encoded_value!(Bit, U8, U16, U32, U64, U128);
+define_encoded_value!(Bit, U8, U16, U32, U64, U128, I8, I16, I32, I64, I128);
 
 macro_rules! define_encoded_variant {
     ($EncodedTy:ident, $PlaintextTy:ty, $len:expr) => {
@@ -459,6 +494,11 @@ define_encoded_variant!(U16, u16, 16);
 define_encoded_variant!(U32, u32, 32);
 define_encoded_variant!(U64, u64, 64);
 define_encoded_variant!(U128, u128, 128);
+define_encoded_variant!(I8, i8, 8);
+define_encoded_variant!(I16, i16, 16);
+define_encoded_variant!(I32, i32, 32);
+define_encoded_variant!(I64, i64, 64);
+define_encoded_variant!(I128, i128, 128);
 
 macro_rules! define_decoding {
     ($( ($EncodedTy:ident, $DecodingTy:ident) ),*) => {
@@ -503,7 +543,12 @@ define_decoding!(
     (U16, U16Decoding),
     (U32, U32Decoding),
     (U64, U64Decoding),
-    (U128, U128Decoding)
+    (U128, U128Decoding),
+    (I8, I8Decoding),
+    (I16, I16Decoding),
+    (I32, I32Decoding),
+    (I64, I64Decoding),
+    (I128, I128Decoding)
 );
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -610,6 +655,11 @@ define_decoding_info_variant!(U16Decoding, U16, u16);
 define_decoding_info_variant!(U32Decoding, U32, u32);
 define_decoding_info_variant!(U64Decoding, U64, u64);
 define_decoding_info_variant!(U128Decoding, U128, u128);
+define_decoding_info_variant!(I8Decoding, I8, i8);
+define_decoding_info_variant!(I16Decoding, I16, i16);
+define_decoding_info_variant!(I32Decoding, I32, i32);
+define_decoding_info_variant!(I64Decoding, I64, i64);
+define_decoding_info_variant!(I128Decoding, I128, i128);
 
 #[derive(Serialize)]
 struct LabelCommit(Label);
@@ -701,7 +751,12 @@ define_encoding_commitment!(
     (U16, U16Commitment),
     (U32, U32Commitment),
     (U64, U64Commitment),
-    (U128, U128Commitment)
+    (U128, U128Commitment),
+    (I8, I8Commitment),
+    (I16, I16Commitment),
+    (I32, I32Commitment),
+    (I64, I64Commitment),
+    (I128, I128Commitment)
 );
 
 macro_rules! define_encoding_commitment_variant {
@@ -776,6 +831,11 @@ define_encoding_commitment_variant!(U16Commitment, U16, 16);
 define_encoding_commitment_variant!(U32Commitment, U32, 32);
 define_encoding_commitment_variant!(U64Commitment, U64, 64);
 define_encoding_commitment_variant!(U128Commitment, U128, 128);
+define_encoding_commitment_variant!(I8Commitment, I8, 8);
+define_encoding_commitment_variant!(I16Commitment, I16, 16);
+define_encoding_commitment_variant!(I32Commitment, I32, 32);
+define_encoding_commitment_variant!(I64Commitment, I64, 64);
+define_encoding_commitment_variant!(I128Commitment, I128, 128);
 
 #[cfg(test)]
 mod tests {
@@ -810,6 +870,12 @@ mod tests {
     #[case::u32_array(PhantomData::<[u32; 16]>)]
     #[case::u64_array(PhantomData::<[u64; 16]>)]
     #[case::u128_array(PhantomData::<[u128; 16]>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i16(PhantomData::<i16>)]
+    #[case::i32(PhantomData::<i32>)]
+    #[case::i64(PhantomData::<i64>)]
+    #[case::i128(PhantomData::<i128>)]
+    #[case::i32_array(PhantomData::<[i32; 16]>)]
     fn test_encoding<T>(encoder: ChaChaEncoder, #[case] _pd: PhantomData<T>)
     where
         Standard: Distribution<T>,
@@ -833,4 +899,41 @@ mod tests {
         assert_eq!(decoded_value.value_type(), T::value_type());
         assert_eq!(decoded_value, value.into());
     }
+
+    #[rstest]
+    fn test_encoding_bytes(encoder: ChaChaEncoder) {
+        use mpz_circuits::types::{BitOrder, ByteOrder};
+
+        let bytes = [0x01u8, 0x02, 0x03, 0x04];
+        let ty = ValueType::new_array::<u16>(2);
+
+        let encoded = encoder.encode_by_type(0, &ty);
+        let decoding = encoded.decoding();
+
+        for endianness in [
+            Endianness::default(),
+            Endianness::new(BitOrder::Msb0, ByteOrder::Big),
+        ] {
+            let active = encoded.select_bytes(&bytes, endianness).unwrap();
+
+            assert_eq!(
+                active.decode(&decoding).unwrap(),
+                Value::from_bytes(&ty, &bytes, endianness).unwrap()
+            );
+            assert_eq!(
+                active.decode_bytes(&decoding, endianness).unwrap(),
+                bytes.to_vec()
+            );
+        }
+
+        // Decoding with a different convention than the one used to select the encoding
+        // yields different bytes.
+        let active = encoded.select_bytes(&bytes, Endianness::default()).unwrap();
+        assert_ne!(
+            active
+                .decode_bytes(&decoding, Endianness::new(BitOrder::Lsb0, ByteOrder::Big))
+                .unwrap(),
+            bytes.to_vec()
+        );
+    }
 }
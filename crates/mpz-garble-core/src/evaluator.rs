@@ -2,17 +2,21 @@ use core::fmt;
 
 use blake3::Hasher;
 
+use itybity::IntoBits;
+use serde::{Deserialize, Serialize};
+
 use crate::{
     circuit::EncryptedGate,
     encoding::{state, EncodedValue, Label},
-    EncryptedGateBatch, DEFAULT_BATCH_SIZE,
+    generator::{and_gate_tweaks, clear_lsb, AndBatch, AND_BATCH_SIZE},
+    EncryptedGateBatch,
 };
 use mpz_circuits::{
-    types::{BinaryRepr, TypeError},
+    types::{BinaryRepr, TypeError, Value},
     Circuit, CircuitError, Gate,
 };
 use mpz_core::{
-    aes::{FixedKeyAes, FIXED_KEY_AES},
+    aes::{FixedKeyAes, FIXED_KEY_AES, PIPELINE_WIDTH},
     hash::Hash,
     Block,
 };
@@ -27,6 +31,28 @@ pub enum EvaluatorError {
     CircuitError(#[from] CircuitError),
     #[error("evaluator not finished")]
     NotFinished,
+    #[error("batch padding is not canonical")]
+    InvalidPadding,
+    #[error("incorrect number of encrypted gates, expected: {expected}, actual: {actual}")]
+    IncorrectGateCount { expected: usize, actual: usize },
+    #[error("can not checkpoint an evaluation which hashes the encrypted gates")]
+    CheckpointHasher,
+    #[error("checkpoint does not match the circuit")]
+    CheckpointMismatch,
+}
+
+/// Computes the output label of a half-gate AND gate from the hashes of its input labels.
+#[inline]
+fn half_gate(x: Block, y: Block, encrypted_gate: &EncryptedGate, h: [Block; 2]) -> Label {
+    let s_a = x.lsb();
+    let s_b = y.lsb();
+
+    let [hx, hy] = h;
+
+    let w_g = hx ^ (encrypted_gate[0] & Block::SELECT_MASK[s_a]);
+    let w_e = hy ^ (Block::SELECT_MASK[s_b] & (encrypted_gate[1] ^ x));
+
+    Label::new(w_g ^ w_e)
 }
 
 /// Evaluates half-gate garbled AND gate
@@ -41,21 +67,66 @@ pub(crate) fn and_gate(
     let x = x.to_inner();
     let y = y.to_inner();
 
-    let s_a = x.lsb();
-    let s_b = y.lsb();
+    let mut h = [x, y];
+    cipher.tccr_many(&and_gate_tweaks(gid), &mut h);
 
-    let j = Block::new((gid as u128).to_be_bytes());
-    let k = Block::new(((gid + 1) as u128).to_be_bytes());
+    half_gate(x, y, encrypted_gate, h)
+}
 
-    let mut h = [x, y];
-    cipher.tccr_many(&[j, k], &mut h);
+/// Evaluates a batch of half-gate garbled AND gates with consecutive gate ids, starting at `gid`.
+///
+/// The gates must be independent, ie. no gate may take the output of another gate in the batch
+/// as input.
+///
+/// # Panics
+///
+/// Panics if more than [`AND_BATCH_SIZE`] gates are provided, or if `inputs` and `outputs` have
+/// different lengths.
+#[inline]
+pub(crate) fn and_gate_batch(
+    cipher: &FixedKeyAes,
+    inputs: &[(Label, Label, EncryptedGate)],
+    gid: usize,
+    outputs: &mut [Label],
+) {
+    assert!(inputs.len() <= AND_BATCH_SIZE);
+    assert_eq!(inputs.len(), outputs.len());
+
+    let n = 2 * inputs.len();
+
+    let mut h = [Block::ZERO; 2 * AND_BATCH_SIZE];
+    let mut tweaks = [Block::ZERO; 2 * AND_BATCH_SIZE];
+    for (i, (x, y, _)) in inputs.iter().enumerate() {
+        h[2 * i..2 * i + 2].copy_from_slice(&[x.to_inner(), y.to_inner()]);
+        tweaks[2 * i..2 * i + 2].copy_from_slice(&and_gate_tweaks(gid + 2 * i));
+    }
 
-    let [hx, hy] = h;
+    cipher.tccr_blocks::<PIPELINE_WIDTH>(&tweaks[..n], &mut h[..n]);
 
-    let w_g = hx ^ (encrypted_gate[0] & Block::SELECT_MASK[s_a]);
-    let w_e = hy ^ (Block::SELECT_MASK[s_b] & (encrypted_gate[1] ^ x));
+    for (((x, y, encrypted_gate), h), output) in inputs.iter().zip(h.chunks_exact(2)).zip(outputs) {
+        *output = half_gate(
+            x.to_inner(),
+            y.to_inner(),
+            encrypted_gate,
+            h.try_into().expect("chunk has 2 blocks"),
+        );
+    }
+}
 
-    Label::new(w_g ^ w_e)
+/// Evaluates privacy-free garbled AND gate, where `a` is the value of `x`.
+#[inline]
+pub(crate) fn and_gate_privacy_free(
+    cipher: &FixedKeyAes,
+    x: &Label,
+    y: &Label,
+    a: bool,
+    row: Block,
+    gid: usize,
+) -> Label {
+    let j = Block::new((gid as u128).to_be_bytes());
+    let hx = clear_lsb(cipher.tccr(j, x.to_inner()));
+
+    Label::new(hx ^ (Block::SELECT_MASK[a as usize] & (row ^ y.to_inner())))
 }
 
 /// Output of the evaluator.
@@ -67,11 +138,61 @@ pub struct EvaluatorOutput {
     pub hash: Option<Hash>,
 }
 
+/// Checkpoint of a garbled circuit evaluation.
+///
+/// A checkpoint holds the active labels of the feeds which are still needed to evaluate the rest
+/// of the circuit, so an evaluation can be interrupted and resumed later with
+/// [`Evaluator::resume`], for example in another session after writing the checkpoint to disk.
+/// Its size is proportional to the width of the circuit at the checkpoint rather than to the
+/// size of the circuit.
+///
+/// The labels are as secret as the active encodings of the evaluation, so a checkpoint must be
+/// stored accordingly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluatorCheckpoint {
+    /// Number of gates in the circuit.
+    gate_count: usize,
+    /// Number of feeds in the circuit.
+    feed_count: usize,
+    /// Number of gates evaluated.
+    position: usize,
+    /// Current gate id.
+    gid: usize,
+    /// Number of AND gates evaluated.
+    counter: usize,
+    /// Whether the circuit was garbled in privacy-free mode.
+    privacy_free: bool,
+    /// Active labels of the live feeds.
+    labels: Vec<(usize, Label)>,
+    /// Plaintext values of the live feeds, used in privacy-free mode.
+    values: Vec<(usize, bool)>,
+}
+
+impl EvaluatorCheckpoint {
+    /// Returns the number of gates which were evaluated.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of AND gates which were evaluated, ie. the number of encrypted gates
+    /// the generator must skip when resuming.
+    pub fn and_count(&self) -> usize {
+        self.counter
+    }
+
+    /// Returns the number of active labels in the checkpoint.
+    pub fn label_count(&self) -> usize {
+        self.labels.len()
+    }
+}
+
 /// Garbled circuit evaluator.
 #[derive(Debug, Default)]
 pub struct Evaluator {
     /// Buffer for the active labels.
     buffer: Vec<Label>,
+    /// Buffer for the plaintext values, used in privacy-free mode.
+    values: Vec<bool>,
 }
 
 impl Evaluator {
@@ -85,6 +206,33 @@ impl Evaluator {
         &'a mut self,
         circ: &'a Circuit,
         inputs: Vec<EncodedValue<state::Active>>,
+    ) -> Result<EncryptedGateConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+        self.evaluate_inner(circ, inputs, None)
+    }
+
+    /// Returns a consumer over the encrypted gates of a circuit garbled in privacy-free mode.
+    ///
+    /// See [`Generator::generate_privacy_free`](crate::Generator::generate_privacy_free).
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The input values to the circuit.
+    /// * `values` - The plaintext input values to the circuit.
+    pub fn evaluate_privacy_free<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        inputs: Vec<EncodedValue<state::Active>>,
+        values: Vec<Value>,
+    ) -> Result<EncryptedGateConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+        self.evaluate_inner(circ, inputs, Some(values))
+    }
+
+    fn evaluate_inner<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        inputs: Vec<EncodedValue<state::Active>>,
+        values: Option<Vec<Value>>,
     ) -> Result<EncryptedGateConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
         if inputs.len() != circ.inputs().len() {
             return Err(CircuitError::InvalidInputCount(
@@ -111,12 +259,109 @@ impl Evaluator {
             }
         }
 
-        Ok(EncryptedGateConsumer::new(
+        let privacy_free = values.is_some();
+        if let Some(values) = values {
+            if values.len() != circ.inputs().len() {
+                return Err(CircuitError::InvalidInputCount(
+                    circ.inputs().len(),
+                    values.len(),
+                ))?;
+            }
+
+            if circ.feed_count() > self.values.len() {
+                self.values.resize(circ.feed_count(), false);
+            }
+
+            for (value, input) in values.into_iter().zip(circ.inputs()) {
+                if value.value_type() != input.value_type() {
+                    return Err(TypeError::UnexpectedType {
+                        expected: input.value_type(),
+                        actual: value.value_type(),
+                    })?;
+                }
+
+                for (bit, node) in value.into_iter_lsb0().zip(input.iter()) {
+                    self.values[node.id()] = bit;
+                }
+            }
+        }
+
+        let mut consumer = EncryptedGateConsumer::new(
             circ.gates().iter(),
             circ.outputs(),
-            &mut self.buffer,
+            &mut self.buffer[..circ.feed_count()],
+            &mut self.values,
             circ.and_count(),
-        ))
+        );
+        consumer.privacy_free = privacy_free;
+
+        Ok(consumer)
+    }
+
+    /// Returns a consumer over the remaining encrypted gates of a circuit, resuming the
+    /// evaluation from a checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit which was being evaluated.
+    /// * `checkpoint` - The checkpoint of the evaluation.
+    pub fn resume<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        checkpoint: EvaluatorCheckpoint,
+    ) -> Result<EncryptedGateConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+        let EvaluatorCheckpoint {
+            gate_count,
+            feed_count,
+            position,
+            gid,
+            counter,
+            privacy_free,
+            labels,
+            values,
+        } = checkpoint;
+
+        if gate_count != circ.gates().len()
+            || feed_count != circ.feed_count()
+            || position > gate_count
+            || counter > circ.and_count()
+            || labels.iter().any(|(id, _)| *id >= feed_count)
+            || values.iter().any(|(id, _)| *id >= feed_count)
+        {
+            return Err(EvaluatorError::CheckpointMismatch);
+        }
+
+        if feed_count > self.buffer.len() {
+            self.buffer.resize(feed_count, Default::default());
+        }
+
+        for (id, label) in labels {
+            self.buffer[id] = label;
+        }
+
+        if privacy_free {
+            if feed_count > self.values.len() {
+                self.values.resize(feed_count, false);
+            }
+
+            for (id, value) in values {
+                self.values[id] = value;
+            }
+        }
+
+        let mut consumer = EncryptedGateConsumer::new(
+            circ.gates()[position..].iter(),
+            circ.outputs(),
+            &mut self.buffer[..circ.feed_count()],
+            &mut self.values,
+            circ.and_count(),
+        );
+        consumer.position = position;
+        consumer.gid = gid;
+        consumer.counter = counter;
+        consumer.privacy_free = privacy_free;
+
+        Ok(consumer)
     }
 
     /// Returns a consumer over batched encrypted gates of a circuit.
@@ -130,7 +375,45 @@ impl Evaluator {
         circ: &'a Circuit,
         inputs: Vec<EncodedValue<state::Active>>,
     ) -> Result<EncryptedGateBatchConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
-        self.evaluate(circ, inputs).map(EncryptedGateBatchConsumer)
+        self.evaluate(circ, inputs)
+            .map(EncryptedGateBatchConsumer::new)
+    }
+
+    /// Returns a consumer over batched encrypted gates of a circuit garbled in privacy-free mode.
+    ///
+    /// See [`Evaluator::evaluate_privacy_free`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The input values to the circuit.
+    /// * `values` - The plaintext input values to the circuit.
+    pub fn evaluate_privacy_free_batched<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        inputs: Vec<EncodedValue<state::Active>>,
+        values: Vec<Value>,
+    ) -> Result<EncryptedGateBatchConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+        self.evaluate_privacy_free(circ, inputs, values)
+            .map(EncryptedGateBatchConsumer::new)
+    }
+
+    /// Returns a consumer over the remaining batched encrypted gates of a circuit, resuming the
+    /// evaluation from a checkpoint.
+    ///
+    /// See [`Evaluator::resume`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit which was being evaluated.
+    /// * `checkpoint` - The checkpoint of the evaluation.
+    pub fn resume_batched<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        checkpoint: EvaluatorCheckpoint,
+    ) -> Result<EncryptedGateBatchConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+        self.resume(circ, checkpoint)
+            .map(EncryptedGateBatchConsumer::new)
     }
 }
 
@@ -140,8 +423,12 @@ pub struct EncryptedGateConsumer<'a, I: Iterator> {
     cipher: &'static FixedKeyAes,
     /// Buffer for the active labels.
     labels: &'a mut [Label],
+    /// Buffer for the plaintext values, used in privacy-free mode.
+    values: &'a mut [bool],
     /// Iterator over the gates.
     gates: I,
+    /// Number of gates taken from the iterator, including those before a checkpoint.
+    position: usize,
     /// Circuit outputs.
     outputs: &'a [BinaryRepr],
     /// Current gate id.
@@ -154,6 +441,12 @@ pub struct EncryptedGateConsumer<'a, I: Iterator> {
     and_count: usize,
     /// Whether the entire circuit has been garbled.
     complete: bool,
+    /// Whether the circuit was garbled in privacy-free mode.
+    privacy_free: bool,
+    /// Index of the next privacy-free row in the current encrypted gate.
+    row: usize,
+    /// Independent AND gates waiting to be evaluated together.
+    batch: AndBatch<(Label, Label, EncryptedGate)>,
 }
 
 impl<'a, I: Iterator> fmt::Debug for EncryptedGateConsumer<'a, I> {
@@ -166,17 +459,70 @@ impl<'a, I> EncryptedGateConsumer<'a, I>
 where
     I: Iterator<Item = &'a Gate>,
 {
-    fn new(gates: I, outputs: &'a [BinaryRepr], labels: &'a mut [Label], and_count: usize) -> Self {
+    fn new(
+        gates: I,
+        outputs: &'a [BinaryRepr],
+        labels: &'a mut [Label],
+        values: &'a mut [bool],
+        and_count: usize,
+    ) -> Self {
         Self {
             cipher: &(*FIXED_KEY_AES),
             gates,
+            position: 0,
             outputs,
             labels,
+            values,
             gid: 1,
             hasher: None,
             counter: 0,
             and_count,
             complete: false,
+            privacy_free: false,
+            row: 0,
+            batch: AndBatch::default(),
+        }
+    }
+
+    /// Evaluates the AND gates in the batch.
+    #[inline]
+    fn flush(&mut self) {
+        let len = self.batch.len;
+        match len {
+            0 => return,
+            // Gates on the critical path of the circuit are often alone in the batch.
+            1 => {
+                let (x, y, encrypted_gate) = &self.batch.inputs[0];
+                self.labels[self.batch.outputs[0]] =
+                    and_gate(self.cipher, x, y, encrypted_gate, self.gid);
+                self.gid += 2;
+                self.batch.len = 0;
+                return;
+            }
+            _ => {}
+        }
+
+        let mut outputs = [Label::default(); AND_BATCH_SIZE];
+        and_gate_batch(
+            self.cipher,
+            &self.batch.inputs[..len],
+            self.gid,
+            &mut outputs[..len],
+        );
+
+        for (z, label) in self.batch.outputs[..len].iter().zip(outputs) {
+            self.labels[*z] = label;
+        }
+
+        self.gid += 2 * len;
+        self.batch.len = 0;
+    }
+
+    /// Evaluates the batch if the node is one of its outputs.
+    #[inline]
+    fn flush_if_produces(&mut self, node: usize) {
+        if self.batch.produces(node) {
+            self.flush();
         }
     }
 
@@ -185,6 +531,14 @@ where
         self.hasher = Some(Hasher::new());
     }
 
+    /// Returns the hash of the encrypted gates evaluated so far, if hashing is enabled.
+    pub fn digest(&self) -> Option<Hash> {
+        self.hasher.as_ref().map(|hasher| {
+            let hash: [u8; 32] = hasher.finalize().into();
+            Hash::from(hash)
+        })
+    }
+
     /// Returns `true` if the evaluator wants more encrypted gates.
     #[inline]
     pub fn wants_gates(&self) -> bool {
@@ -195,35 +549,73 @@ where
     #[inline]
     pub fn next(&mut self, encrypted_gate: EncryptedGate) {
         while let Some(gate) = self.gates.next() {
+            self.position += 1;
             match gate {
                 Gate::Xor {
                     x: node_x,
                     y: node_y,
                     z: node_z,
                 } => {
+                    self.flush_if_produces(node_x.id());
+                    self.flush_if_produces(node_y.id());
+
                     let x = self.labels[node_x.id()];
                     let y = self.labels[node_y.id()];
                     self.labels[node_z.id()] = x ^ y;
+
+                    if self.privacy_free {
+                        self.values[node_z.id()] =
+                            self.values[node_x.id()] ^ self.values[node_y.id()];
+                    }
                 }
                 Gate::And {
                     x: node_x,
                     y: node_y,
                     z: node_z,
                 } => {
+                    self.flush_if_produces(node_x.id());
+                    self.flush_if_produces(node_y.id());
+
                     let x = self.labels[node_x.id()];
                     let y = self.labels[node_y.id()];
-                    let z = and_gate(self.cipher, &x, &y, &encrypted_gate, self.gid);
-                    self.labels[node_z.id()] = z;
 
-                    self.gid += 2;
+                    let consumed = if self.privacy_free {
+                        let a = self.values[node_x.id()];
+                        let z = and_gate_privacy_free(
+                            self.cipher,
+                            &x,
+                            &y,
+                            a,
+                            encrypted_gate[self.row],
+                            self.gid,
+                        );
+                        self.labels[node_z.id()] = z;
+                        self.values[node_z.id()] = a & self.values[node_y.id()];
+                        self.gid += 2;
+
+                        // Each encrypted gate holds the rows of two AND gates.
+                        self.row ^= 1;
+                        self.row == 0
+                    } else {
+                        self.batch.push((x, y, encrypted_gate), node_z.id());
+                        if self.batch.is_full() {
+                            self.flush();
+                        }
+
+                        true
+                    };
+
                     self.counter += 1;
 
-                    if let Some(hasher) = &mut self.hasher {
-                        hasher.update(&encrypted_gate.to_bytes());
+                    let consumed = consumed || !self.wants_gates();
+                    if consumed {
+                        if let Some(hasher) = &mut self.hasher {
+                            hasher.update(&encrypted_gate.to_bytes());
+                        }
                     }
 
                     // If we have more AND gates to evaluate, return.
-                    if self.wants_gates() {
+                    if consumed && self.wants_gates() {
                         return;
                     }
                 }
@@ -231,12 +623,26 @@ where
                     x: node_x,
                     z: node_z,
                 } => {
+                    self.flush_if_produces(node_x.id());
+
                     let x = self.labels[node_x.id()];
                     self.labels[node_z.id()] = x;
+
+                    if self.privacy_free {
+                        self.values[node_z.id()] = !self.values[node_x.id()];
+                    }
+                }
+                Gate::Const { z: node_z, value } => {
+                    self.labels[node_z.id()] = Label::CONSTANT;
+
+                    if self.privacy_free {
+                        self.values[node_z.id()] = *value;
+                    }
                 }
             }
         }
 
+        self.flush();
         self.complete = true;
     }
 
@@ -264,48 +670,183 @@ where
 
         Ok(EvaluatorOutput {
             outputs,
-            hash: self.hasher.as_ref().map(|hasher| {
-                let hash: [u8; 32] = hasher.finalize().into();
-                Hash::from(hash)
-            }),
+            hash: self.digest(),
+        })
+    }
+}
+
+impl<'a, I> EncryptedGateConsumer<'a, I>
+where
+    I: Iterator<Item = &'a Gate> + Clone,
+{
+    /// Returns a checkpoint of the evaluation, which can be resumed with [`Evaluator::resume`].
+    ///
+    /// The checkpoint is taken between encrypted gates, so the generator must resume sending from
+    /// the encrypted gate following the last one passed to [`next`](Self::next).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if hashing of the encrypted gates is enabled, as the state of the hasher
+    /// can not be persisted.
+    pub fn checkpoint(&mut self) -> Result<EvaluatorCheckpoint, EvaluatorError> {
+        if self.hasher.is_some() {
+            return Err(EvaluatorError::CheckpointHasher);
+        }
+
+        self.flush();
+
+        // Circuits are in SSA form, so a feed read by a remaining gate is live unless it is
+        // written by a remaining gate.
+        let mut live = vec![false; self.labels.len()];
+        let mut pending = vec![false; self.labels.len()];
+        for gate in self.gates.clone() {
+            for node in [gate.x(), gate.y()].into_iter().flatten() {
+                if !pending[node.id()] {
+                    live[node.id()] = true;
+                }
+            }
+            pending[gate.z().id()] = true;
+        }
+        for node in self.outputs.iter().flat_map(|output| output.iter()) {
+            if !pending[node.id()] {
+                live[node.id()] = true;
+            }
+        }
+
+        let live_ids = live
+            .iter()
+            .enumerate()
+            .filter_map(|(id, &live)| live.then_some(id));
+
+        let labels = live_ids.clone().map(|id| (id, self.labels[id])).collect();
+        let values = if self.privacy_free {
+            live_ids.map(|id| (id, self.values[id])).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(EvaluatorCheckpoint {
+            gate_count: self.position + self.gates.clone().count(),
+            feed_count: self.labels.len(),
+            position: self.position,
+            gid: self.gid,
+            counter: self.counter,
+            privacy_free: self.privacy_free,
+            labels,
+            values,
         })
     }
 }
 
 /// Consumer returned by [`Evaluator::evaluate_batched`].
 #[derive(Debug)]
-pub struct EncryptedGateBatchConsumer<'a, I: Iterator, const N: usize = DEFAULT_BATCH_SIZE>(
-    EncryptedGateConsumer<'a, I>,
-);
+pub struct EncryptedGateBatchConsumer<'a, I: Iterator> {
+    inner: EncryptedGateConsumer<'a, I>,
+    /// Whether to verify that batch padding is canonical.
+    strict_padding: bool,
+    /// Whether non-canonical batch padding was received.
+    invalid_padding: bool,
+    /// Number of encrypted gates evaluated, excluding padding.
+    consumed: usize,
+}
 
-impl<'a, I, const N: usize> EncryptedGateBatchConsumer<'a, I, N>
+impl<'a, I> EncryptedGateBatchConsumer<'a, I>
 where
     I: Iterator<Item = &'a Gate>,
 {
+    fn new(inner: EncryptedGateConsumer<'a, I>) -> Self {
+        Self {
+            inner,
+            strict_padding: false,
+            invalid_padding: false,
+            consumed: 0,
+        }
+    }
+
     /// Enables hashing of the encrypted gates.
     pub fn enable_hasher(&mut self) {
-        self.0.enable_hasher()
+        self.inner.enable_hasher()
+    }
+
+    /// Enables verification that any padding after the last gate is canonical, ie. zero.
+    ///
+    /// Padding is not evaluated or hashed, so without this check a generator could use it
+    /// as a covert channel. With it, the hash of the encrypted gates determines the entire
+    /// transcript.
+    pub fn enable_strict_padding(&mut self) {
+        self.strict_padding = true;
     }
 
     /// Returns `true` if the evaluator wants more encrypted gates.
     pub fn wants_gates(&self) -> bool {
-        self.0.wants_gates()
+        self.inner.wants_gates()
     }
 
     /// Evaluates the next batch of gates in the circuit.
+    ///
+    /// Batches may be of any size.
     #[inline]
-    pub fn next(&mut self, batch: EncryptedGateBatch<N>) {
-        for encrypted_gate in batch.into_array() {
-            self.0.next(encrypted_gate);
-            if !self.0.wants_gates() {
-                // Skipping any remaining gates which may have been used to pad the last batch.
-                return;
+    pub fn next(&mut self, batch: EncryptedGateBatch) {
+        let mut gates = batch.into_vec().into_iter();
+        for encrypted_gate in gates.by_ref() {
+            self.inner.next(encrypted_gate);
+            self.consumed += 1;
+            if !self.inner.wants_gates() {
+                break;
             }
         }
+
+        // Any remaining gates are padding after the end of the circuit.
+        if self.strict_padding && !is_canonical_padding(gates.as_slice()) {
+            self.invalid_padding = true;
+        }
+    }
+
+    /// Returns the number of encrypted gates evaluated so far and their hash, if hashing is
+    /// enabled.
+    ///
+    /// Padding is neither counted nor hashed, so the digest can be compared with the digest of
+    /// a [`Generator`](crate::Generator) which returned the same number of encrypted gates.
+    pub fn digest(&self) -> Option<(usize, Hash)> {
+        self.inner.digest().map(|hash| (self.consumed, hash))
+    }
+
+    /// Returns a checkpoint of the evaluation, which can be resumed with
+    /// [`Evaluator::resume_batched`].
+    ///
+    /// See [`EncryptedGateConsumer::checkpoint`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if hashing of the encrypted gates is enabled, or if strict padding is
+    /// enabled and non-canonical padding was received.
+    pub fn checkpoint(&mut self) -> Result<EvaluatorCheckpoint, EvaluatorError>
+    where
+        I: Clone,
+    {
+        if self.invalid_padding {
+            return Err(EvaluatorError::InvalidPadding);
+        }
+
+        self.inner.checkpoint()
     }
 
     /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if strict padding is enabled and non-canonical padding was received.
     pub fn finish(self) -> Result<EvaluatorOutput, EvaluatorError> {
-        self.0.finish()
+        if self.invalid_padding {
+            return Err(EvaluatorError::InvalidPadding);
+        }
+
+        self.inner.finish()
     }
 }
+
+/// Returns `true` if the provided batch padding is canonical, ie. every gate is zero.
+#[inline]
+pub fn is_canonical_padding(padding: &[EncryptedGate]) -> bool {
+    padding.iter().all(|gate| *gate == EncryptedGate::default())
+}
@@ -1,4 +1,8 @@
+#[cfg(feature = "parallel")]
+mod parallel;
+
 use core::fmt;
+use std::collections::VecDeque;
 
 use blake3::Hasher;
 
@@ -12,11 +16,14 @@ use mpz_circuits::{
     Circuit, CircuitError, Gate,
 };
 use mpz_core::{
-    aes::{FixedKeyAes, FIXED_KEY_AES},
+    aes::{FixedKeyAes, FIXED_KEY_AES, PIPELINE_WIDTH},
     hash::Hash,
     Block,
 };
 
+#[cfg(feature = "parallel")]
+pub use parallel::ParallelEncryptedGateBatchIter;
+
 /// Errors that can occur during garbled circuit generation.
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -29,6 +36,54 @@ pub enum GeneratorError {
     NotFinished,
 }
 
+/// Returns the 0-bit label of a constant wire.
+///
+/// The 0-bit label is chosen such that the active label is [`Label::CONSTANT`].
+#[inline]
+pub(crate) fn constant_label(value: bool, delta: &Delta) -> Label {
+    if value {
+        Label::CONSTANT ^ *delta
+    } else {
+        Label::CONSTANT
+    }
+}
+
+/// Number of half-gate AND gates garbled together.
+///
+/// The tweakable hashes of a batch are computed in a single pass through the fixed-key cipher,
+/// which keeps the AES pipeline full, see [`PIPELINE_WIDTH`].
+pub(crate) const AND_BATCH_SIZE: usize = PIPELINE_WIDTH;
+
+/// Returns the tweaks of the half-gate AND gate with the provided gate id.
+#[inline]
+pub(crate) fn and_gate_tweaks(gid: usize) -> [Block; 2] {
+    [
+        Block::new((gid as u128).to_be_bytes()),
+        Block::new(((gid + 1) as u128).to_be_bytes()),
+    ]
+}
+
+/// Computes the garbled rows of a half-gate AND gate from the hashes of its input labels.
+#[inline]
+fn half_gate(x_0: Block, y_0: Block, delta: Block, h: [Block; 4]) -> (Label, EncryptedGate) {
+    let p_a = x_0.lsb();
+    let p_b = y_0.lsb();
+
+    let [hx_0, hy_0, hx_1, hy_1] = h;
+
+    // Garbled row of generator half-gate
+    let t_g = hx_0 ^ hx_1 ^ (Block::SELECT_MASK[p_b] & delta);
+    let w_g = hx_0 ^ (Block::SELECT_MASK[p_a] & t_g);
+
+    // Garbled row of evaluator half-gate
+    let t_e = hy_0 ^ hy_1 ^ x_0;
+    let w_e = hy_0 ^ (Block::SELECT_MASK[p_b] & (t_e ^ x_0));
+
+    let z_0 = Label::new(w_g ^ w_e);
+
+    (z_0, EncryptedGate::new([t_g, t_e]))
+}
+
 /// Computes half-gate garbled AND gate
 #[inline]
 pub(crate) fn and_gate(
@@ -40,31 +95,107 @@ pub(crate) fn and_gate(
 ) -> (Label, EncryptedGate) {
     let delta = delta.into_inner();
     let x_0 = x_0.to_inner();
-    let x_1 = x_0 ^ delta;
     let y_0 = y_0.to_inner();
-    let y_1 = y_0 ^ delta;
-
-    let p_a = x_0.lsb();
-    let p_b = y_0.lsb();
-    let j = Block::new((gid as u128).to_be_bytes());
-    let k = Block::new(((gid + 1) as u128).to_be_bytes());
+    let [j, k] = and_gate_tweaks(gid);
 
-    let mut h = [x_0, y_0, x_1, y_1];
+    let mut h = [x_0, y_0, x_0 ^ delta, y_0 ^ delta];
     cipher.tccr_many(&[j, k, j, k], &mut h);
 
-    let [hx_0, hy_0, hx_1, hy_1] = h;
+    half_gate(x_0, y_0, delta, h)
+}
 
-    // Garbled row of generator half-gate
-    let t_g = hx_0 ^ hx_1 ^ (Block::SELECT_MASK[p_b] & delta);
-    let w_g = hx_0 ^ (Block::SELECT_MASK[p_a] & t_g);
+/// Computes a batch of half-gate garbled AND gates with consecutive gate ids, starting at `gid`.
+///
+/// The gates must be independent, ie. no gate may take the output of another gate in the batch
+/// as input.
+///
+/// # Panics
+///
+/// Panics if more than [`AND_BATCH_SIZE`] gates are provided, or if `inputs` and `outputs` have
+/// different lengths.
+#[inline]
+pub(crate) fn and_gate_batch(
+    cipher: &FixedKeyAes,
+    inputs: &[(Label, Label)],
+    delta: &Delta,
+    gid: usize,
+    outputs: &mut [(Label, EncryptedGate)],
+) {
+    assert!(inputs.len() <= AND_BATCH_SIZE);
+    assert_eq!(inputs.len(), outputs.len());
 
-    // Garbled row of evaluator half-gate
-    let t_e = hy_0 ^ hy_1 ^ x_0;
-    let w_e = hy_0 ^ (Block::SELECT_MASK[p_b] & (t_e ^ x_0));
+    let delta = delta.into_inner();
+    let n = 4 * inputs.len();
 
-    let z_0 = Label::new(w_g ^ w_e);
+    let mut h = [Block::ZERO; 4 * AND_BATCH_SIZE];
+    let mut tweaks = [Block::ZERO; 4 * AND_BATCH_SIZE];
+    for (i, (x_0, y_0)) in inputs.iter().enumerate() {
+        let (x_0, y_0) = (x_0.to_inner(), y_0.to_inner());
+        let [j, k] = and_gate_tweaks(gid + 2 * i);
 
-    (z_0, EncryptedGate::new([t_g, t_e]))
+        h[4 * i..4 * i + 4].copy_from_slice(&[x_0, y_0, x_0 ^ delta, y_0 ^ delta]);
+        tweaks[4 * i..4 * i + 4].copy_from_slice(&[j, k, j, k]);
+    }
+
+    cipher.tccr_blocks::<PIPELINE_WIDTH>(&tweaks[..n], &mut h[..n]);
+
+    for (((x_0, y_0), h), output) in inputs.iter().zip(h.chunks_exact(4)).zip(outputs) {
+        *output = half_gate(
+            x_0.to_inner(),
+            y_0.to_inner(),
+            delta,
+            h.try_into().expect("chunk has 4 blocks"),
+        );
+    }
+}
+
+/// Clears the pointer bit of a block.
+#[inline]
+pub(crate) fn clear_lsb(block: Block) -> Block {
+    block ^ (Block::SELECT_MASK[block.lsb()] & Block::ONE)
+}
+
+/// Computes privacy-free garbled AND gate
+///
+/// The evaluator must know the value of `x`. Only a single row is required, and the pointer
+/// bit of the 0-bit output label is always 0.
+#[inline]
+pub(crate) fn and_gate_privacy_free(
+    cipher: &FixedKeyAes,
+    x_0: &Label,
+    y_0: &Label,
+    delta: &Delta,
+    gid: usize,
+) -> (Label, Block) {
+    let x_0 = x_0.to_inner();
+    let x_1 = x_0 ^ delta.into_inner();
+    let y_0 = y_0.to_inner();
+
+    let j = Block::new((gid as u128).to_be_bytes());
+
+    let mut h = [x_0, x_1];
+    cipher.tccr_many(&[j, j], &mut h);
+
+    let [hx_0, hx_1] = h.map(clear_lsb);
+
+    (Label::new(hx_0), hx_0 ^ hx_1 ^ y_0)
+}
+
+/// Collects the full encodings of the circuit outputs from the label buffer.
+fn encode_outputs(
+    outputs: &[BinaryRepr],
+    labels: &[Label],
+    delta: Delta,
+) -> Vec<EncodedValue<state::Full>> {
+    outputs
+        .iter()
+        .map(|output| {
+            let output_labels: Vec<Label> = output.iter().map(|node| labels[node.id()]).collect();
+
+            EncodedValue::<state::Full>::from_labels(output.value_type(), delta, &output_labels)
+                .expect("encoding should be correct")
+        })
+        .collect()
 }
 
 /// Output of the generator.
@@ -145,7 +276,123 @@ impl Generator {
         inputs: Vec<EncodedValue<state::Full>>,
     ) -> Result<EncryptedGateBatchIter<'_, std::slice::Iter<'_, Gate>>, GeneratorError> {
         self.generate(circ, delta, inputs)
-            .map(EncryptedGateBatchIter)
+            .map(EncryptedGateBatchIter::new)
+    }
+
+    /// Returns an iterator over batched encrypted gates of a circuit, garbled using multiple
+    /// threads.
+    ///
+    /// The batches are identical to those returned by [`Generator::generate_batched`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    #[cfg(feature = "parallel")]
+    pub fn generate_batched_parallel<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<ParallelEncryptedGateBatchIter<'_>, GeneratorError> {
+        self.generate(circ, delta, inputs)
+            .map(ParallelEncryptedGateBatchIter::new)
+    }
+
+    /// Returns an iterator over the encrypted gates of a circuit, garbled in privacy-free mode.
+    ///
+    /// Privacy-free garbled circuits only provide authenticity, and require that the evaluator
+    /// knows the values of all the inputs. They are suitable for proving statements in
+    /// zero-knowledge, and only require one row per AND gate. The rows of two AND gates are
+    /// packed into each [`EncryptedGate`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    pub fn generate_privacy_free<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<EncryptedGateIter<'_, std::slice::Iter<'_, Gate>>, GeneratorError> {
+        self.generate(circ, delta, inputs).map(|mut iter| {
+            iter.privacy_free = true;
+            iter
+        })
+    }
+
+    /// Returns an iterator over batched encrypted gates of a circuit, garbled in privacy-free
+    /// mode.
+    ///
+    /// See [`Generator::generate_privacy_free`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    pub fn generate_privacy_free_batched<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<EncryptedGateBatchIter<'_, std::slice::Iter<'_, Gate>>, GeneratorError> {
+        self.generate_privacy_free(circ, delta, inputs)
+            .map(EncryptedGateBatchIter::new)
+    }
+
+    /// Returns an iterator over batched encrypted gates of a circuit, garbled in privacy-free
+    /// mode using multiple threads.
+    ///
+    /// See [`Generator::generate_privacy_free`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    #[cfg(feature = "parallel")]
+    pub fn generate_privacy_free_batched_parallel<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<ParallelEncryptedGateBatchIter<'_>, GeneratorError> {
+        self.generate_privacy_free(circ, delta, inputs)
+            .map(ParallelEncryptedGateBatchIter::new)
+    }
+
+    /// Garbles a circuit in full, discarding the encrypted gates, and returns the encoded
+    /// outputs along with the hash of the encrypted gates.
+    ///
+    /// This is used to check that a garbled circuit was generated from the given delta and
+    /// inputs, by comparing the hash against that of the original.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    /// * `privacy_free` - Whether the circuit was garbled in privacy-free mode.
+    pub fn regarble(
+        &mut self,
+        circ: &Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+        privacy_free: bool,
+    ) -> Result<GeneratorOutput, GeneratorError> {
+        let mut iter = if privacy_free {
+            self.generate_privacy_free(circ, delta, inputs)?
+        } else {
+            self.generate(circ, delta, inputs)?
+        };
+
+        iter.enable_hasher();
+        for _ in iter.by_ref() {}
+        iter.finish()
     }
 }
 
@@ -171,6 +418,45 @@ pub struct EncryptedGateIter<'a, I> {
     and_count: usize,
     /// Whether the entire circuit has been garbled.
     complete: bool,
+    /// Whether to garble in privacy-free mode.
+    privacy_free: bool,
+    /// Privacy-free row waiting to be packed with the next one.
+    pending: Option<Block>,
+    /// Independent AND gates waiting to be garbled together.
+    batch: AndBatch<(Label, Label)>,
+    /// Encrypted gates which have been garbled but not yet returned.
+    ready: VecDeque<EncryptedGate>,
+}
+
+/// Independent AND gates waiting to be processed together.
+#[derive(Debug, Default)]
+pub(crate) struct AndBatch<T> {
+    /// The inputs of each gate.
+    pub(crate) inputs: [T; AND_BATCH_SIZE],
+    /// The output node of each gate.
+    pub(crate) outputs: [usize; AND_BATCH_SIZE],
+    /// Number of gates in the batch.
+    pub(crate) len: usize,
+}
+
+impl<T> AndBatch<T> {
+    #[inline]
+    pub(crate) fn is_full(&self) -> bool {
+        self.len == AND_BATCH_SIZE
+    }
+
+    /// Returns `true` if the node is the output of a gate in the batch.
+    #[inline]
+    pub(crate) fn produces(&self, node: usize) -> bool {
+        self.outputs[..self.len].contains(&node)
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, input: T, z: usize) {
+        self.inputs[self.len] = input;
+        self.outputs[self.len] = z;
+        self.len += 1;
+    }
 }
 
 impl<'a, I> fmt::Debug for EncryptedGateIter<'a, I> {
@@ -201,6 +487,10 @@ where
             counter: 0,
             and_count,
             complete: false,
+            privacy_free: false,
+            pending: None,
+            batch: AndBatch::default(),
+            ready: VecDeque::with_capacity(2 * AND_BATCH_SIZE),
         }
     }
 
@@ -212,49 +502,73 @@ where
     /// Returns `true` if the generator has more encrypted gates to generate.
     #[inline]
     pub fn has_gates(&self) -> bool {
-        self.counter != self.and_count
+        self.counter != self.and_count || !self.ready.is_empty()
     }
 
-    /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
-    pub fn finish(mut self) -> Result<GeneratorOutput, GeneratorError> {
-        if self.has_gates() {
-            return Err(GeneratorError::NotFinished);
-        }
+    /// Returns the hash of the encrypted gates returned so far, if hashing is enabled.
+    pub fn digest(&self) -> Option<Hash> {
+        self.hasher.as_ref().map(|hasher| {
+            let hash: [u8; 32] = hasher.finalize().into();
+            Hash::from(hash)
+        })
+    }
 
-        // Finish computing any "free" gates.
-        if !self.complete {
-            assert_eq!(self.next(), None);
-        }
+    /// Queues an encrypted gate to be returned.
+    #[inline]
+    fn emit(&mut self, encrypted_gate: EncryptedGate) {
+        self.ready.push_back(encrypted_gate);
+    }
 
-        let outputs = self
-            .outputs
-            .iter()
-            .map(|output| {
-                let labels: Vec<Label> = output.iter().map(|node| self.labels[node.id()]).collect();
+    /// Garbles the AND gates in the batch.
+    #[inline]
+    fn flush(&mut self) {
+        let len = self.batch.len;
+        match len {
+            0 => return,
+            // Gates on the critical path of the circuit are often alone in the batch.
+            1 => {
+                let (x_0, y_0) = self.batch.inputs[0];
+                let (z_0, encrypted_gate) =
+                    and_gate(self.cipher, &x_0, &y_0, &self.delta, self.gid);
+                self.labels[self.batch.outputs[0]] = z_0;
+                self.emit(encrypted_gate);
+                self.gid += 2;
+                self.counter += 1;
+                self.batch.len = 0;
+                return;
+            }
+            _ => {}
+        }
 
-                EncodedValue::<state::Full>::from_labels(output.value_type(), self.delta, &labels)
-                    .expect("encoding should be correct")
-            })
-            .collect();
+        let mut garbled = [(Label::default(), EncryptedGate::default()); AND_BATCH_SIZE];
+        and_gate_batch(
+            self.cipher,
+            &self.batch.inputs[..len],
+            &self.delta,
+            self.gid,
+            &mut garbled[..len],
+        );
+
+        for (i, (z_0, encrypted_gate)) in garbled[..len].iter().copied().enumerate() {
+            self.labels[self.batch.outputs[i]] = z_0;
+            self.emit(encrypted_gate);
+        }
 
-        Ok(GeneratorOutput {
-            outputs,
-            hash: self.hasher.as_ref().map(|hasher| {
-                let hash: [u8; 32] = hasher.finalize().into();
-                Hash::from(hash)
-            }),
-        })
+        self.gid += 2 * len;
+        self.counter += len;
+        self.batch.len = 0;
     }
-}
-
-impl<'a, I> Iterator for EncryptedGateIter<'a, I>
-where
-    I: Iterator<Item = &'a Gate>,
-{
-    type Item = EncryptedGate;
 
+    /// Garbles the batch if the node is one of its outputs.
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
+    fn flush_if_produces(&mut self, node: usize) {
+        if self.batch.produces(node) {
+            self.flush();
+        }
+    }
+
+    /// Garbles gates until enough encrypted gates are ready, or the circuit is complete.
+    fn garble(&mut self) {
         while let Some(gate) = self.gates.next() {
             match gate {
                 Gate::Xor {
@@ -262,6 +576,9 @@ where
                     y: node_y,
                     z: node_z,
                 } => {
+                    self.flush_if_produces(node_x.id());
+                    self.flush_if_produces(node_y.id());
+
                     let x_0 = self.labels[node_x.id()];
                     let y_0 = self.labels[node_y.id()];
                     self.labels[node_z.id()] = x_0 ^ y_0;
@@ -271,74 +588,160 @@ where
                     y: node_y,
                     z: node_z,
                 } => {
+                    self.flush_if_produces(node_x.id());
+                    self.flush_if_produces(node_y.id());
+
                     let x_0 = self.labels[node_x.id()];
                     let y_0 = self.labels[node_y.id()];
-                    let (z_0, encrypted_gate) =
-                        and_gate(self.cipher, &x_0, &y_0, &self.delta, self.gid);
-                    self.labels[node_z.id()] = z_0;
-
-                    self.gid += 2;
-                    self.counter += 1;
-
-                    if let Some(hasher) = &mut self.hasher {
-                        hasher.update(&encrypted_gate.to_bytes());
-                    }
-
-                    // If we have generated all AND gates, we can compute
-                    // the rest of the "free" gates.
-                    if !self.has_gates() {
-                        assert!(self.next().is_none());
 
-                        self.complete = true;
+                    if self.privacy_free {
+                        let (z_0, row) =
+                            and_gate_privacy_free(self.cipher, &x_0, &y_0, &self.delta, self.gid);
+                        self.labels[node_z.id()] = z_0;
+
+                        self.gid += 2;
+                        self.counter += 1;
+
+                        // Pack the rows of two AND gates into each encrypted gate.
+                        match self.pending.take() {
+                            Some(first) => self.emit(EncryptedGate::new([first, row])),
+                            None if self.counter != self.and_count => self.pending = Some(row),
+                            None => self.emit(EncryptedGate::new([row, Block::ZERO])),
+                        }
+                    } else {
+                        self.batch.push((x_0, y_0), node_z.id());
+
+                        if self.batch.is_full() {
+                            self.flush();
+                        }
                     }
-
-                    return Some(encrypted_gate);
                 }
                 Gate::Inv {
                     x: node_x,
                     z: node_z,
                 } => {
+                    self.flush_if_produces(node_x.id());
+
                     let x_0 = self.labels[node_x.id()];
                     self.labels[node_z.id()] = x_0 ^ self.delta;
                 }
+                Gate::Const { z: node_z, value } => {
+                    self.labels[node_z.id()] = constant_label(*value, &self.delta);
+                }
+            }
+
+            if self.ready.len() >= AND_BATCH_SIZE {
+                return;
             }
         }
 
-        None
+        self.flush();
+        self.complete = true;
+    }
+
+    /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
+    pub fn finish(mut self) -> Result<GeneratorOutput, GeneratorError> {
+        if self.has_gates() {
+            return Err(GeneratorError::NotFinished);
+        }
+
+        // Finish computing any "free" gates.
+        if !self.complete {
+            assert_eq!(self.next(), None);
+        }
+
+        Ok(GeneratorOutput {
+            hash: self.digest(),
+            outputs: encode_outputs(self.outputs, self.labels, self.delta),
+        })
+    }
+}
+
+impl<'a, I> Iterator for EncryptedGateIter<'a, I>
+where
+    I: Iterator<Item = &'a Gate>,
+{
+    type Item = EncryptedGate;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(encrypted_gate) = self.ready.pop_front() {
+                // Gates are hashed as they are returned, so that the digest covers exactly
+                // the gates returned so far.
+                if let Some(hasher) = &mut self.hasher {
+                    hasher.update(&encrypted_gate.to_bytes());
+                }
+
+                return Some(encrypted_gate);
+            }
+
+            if self.complete {
+                return None;
+            }
+
+            self.garble();
+        }
     }
 }
 
 /// Iterator returned by [`Generator::generate_batched`].
 #[derive(Debug)]
-pub struct EncryptedGateBatchIter<'a, I: Iterator, const N: usize = DEFAULT_BATCH_SIZE>(
-    EncryptedGateIter<'a, I>,
-);
+pub struct EncryptedGateBatchIter<'a, I: Iterator> {
+    inner: EncryptedGateIter<'a, I>,
+    /// Number of encrypted gates per batch.
+    batch_size: usize,
+}
 
-impl<'a, I, const N: usize> EncryptedGateBatchIter<'a, I, N>
+impl<'a, I> EncryptedGateBatchIter<'a, I>
 where
     I: Iterator<Item = &'a Gate>,
 {
+    fn new(inner: EncryptedGateIter<'a, I>) -> Self {
+        Self {
+            inner,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Sets the number of encrypted gates per batch, defaults to [`DEFAULT_BATCH_SIZE`].
+    ///
+    /// The last batch of the circuit may contain fewer gates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        assert!(batch_size > 0, "batch size must be non-zero");
+        self.batch_size = batch_size;
+    }
+
     /// Enables hashing of the encrypted gates.
     pub fn enable_hasher(&mut self) {
-        self.0.enable_hasher()
+        self.inner.enable_hasher()
+    }
+
+    /// Returns the hash of the encrypted gates returned so far, if hashing is enabled.
+    pub fn digest(&self) -> Option<Hash> {
+        self.inner.digest()
     }
 
     /// Returns `true` if the generator has more encrypted gates to generate.
     pub fn has_gates(&self) -> bool {
-        self.0.has_gates()
+        self.inner.has_gates()
     }
 
     /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
     pub fn finish(self) -> Result<GeneratorOutput, GeneratorError> {
-        self.0.finish()
+        self.inner.finish()
     }
 }
 
-impl<'a, I, const N: usize> Iterator for EncryptedGateBatchIter<'a, I, N>
+impl<'a, I> Iterator for EncryptedGateBatchIter<'a, I>
 where
     I: Iterator<Item = &'a Gate>,
 {
-    type Item = EncryptedGateBatch<N>;
+    type Item = EncryptedGateBatch;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -346,16 +749,7 @@ where
             return None;
         }
 
-        let mut batch = [EncryptedGate::default(); N];
-        let mut i = 0;
-        for gate in self.0.by_ref() {
-            batch[i] = gate;
-            i += 1;
-
-            if i == N {
-                break;
-            }
-        }
+        let batch: Vec<EncryptedGate> = self.inner.by_ref().take(self.batch_size).collect();
 
         Some(EncryptedGateBatch::new(batch))
     }
@@ -0,0 +1,348 @@
+//! Multi-threaded garbling.
+//!
+//! The circuit is garbled in windows of consecutive gates. The gates of each window are
+//! partitioned into layers such that every gate only depends on gates in earlier layers (or
+//! earlier windows), and the gates of a layer are garbled in parallel. The encrypted gates of a
+//! window are then emitted in circuit order, so the batches are identical to those produced by
+//! the sequential generator.
+
+use std::collections::VecDeque;
+
+use blake3::Hasher;
+use mpz_circuits::{types::BinaryRepr, Gate};
+use mpz_core::{aes::FixedKeyAes, hash::Hash, Block};
+use rayon::prelude::*;
+
+use crate::{
+    circuit::EncryptedGate,
+    encoding::{Delta, Label},
+    generator::{
+        and_gate, and_gate_privacy_free, constant_label, encode_outputs, EncryptedGateIter,
+        GeneratorError, GeneratorOutput,
+    },
+    EncryptedGateBatch, DEFAULT_BATCH_SIZE,
+};
+
+/// Number of AND gates garbled per window.
+const WINDOW_SIZE: usize = 1 << 14;
+
+/// Minimum number of gates garbled by each thread within a layer.
+const MIN_LAYER_CHUNK: usize = 64;
+
+/// Result of garbling a single gate.
+enum Garbled {
+    /// XOR, INV or constant gate.
+    Free,
+    /// Half-gate garbled AND gate.
+    And(EncryptedGate),
+    /// Row of a privacy-free garbled AND gate.
+    PrivacyFree(Block),
+}
+
+/// Iterator returned by [`Generator::generate_batched_parallel`](crate::Generator::generate_batched_parallel).
+pub struct ParallelEncryptedGateBatchIter<'a> {
+    /// Cipher to use to encrypt the gates.
+    cipher: &'static FixedKeyAes,
+    /// Global offset.
+    delta: Delta,
+    /// Buffer for the 0-bit labels.
+    labels: &'a mut [Label],
+    /// Gates of the circuit.
+    gates: &'a [Gate],
+    /// Circuit outputs.
+    outputs: &'a [BinaryRepr],
+    /// Position of the next gate to garble.
+    pos: usize,
+    /// Current gate id.
+    gid: usize,
+    /// Layer of each node produced in the current window, offset by one.
+    ///
+    /// Nodes which are not produced in the current window are 0.
+    node_layers: Vec<usize>,
+    /// Encrypted gates which have been garbled but not yet emitted.
+    queue: VecDeque<EncryptedGate>,
+    /// Hasher to use to hash the encrypted gates.
+    hasher: Option<Hasher>,
+    /// Number of AND gates generated.
+    counter: usize,
+    /// Number of AND gates in the circuit.
+    and_count: usize,
+    /// Whether to garble in privacy-free mode.
+    privacy_free: bool,
+    /// Privacy-free row waiting to be packed with the next one.
+    pending: Option<Block>,
+    /// Number of encrypted gates per batch.
+    batch_size: usize,
+}
+
+impl<'a> std::fmt::Debug for ParallelEncryptedGateBatchIter<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ParallelEncryptedGateBatchIter {{ .. }}")
+    }
+}
+
+impl<'a> ParallelEncryptedGateBatchIter<'a> {
+    pub(super) fn new(iter: EncryptedGateIter<'a, std::slice::Iter<'a, Gate>>) -> Self {
+        let EncryptedGateIter {
+            cipher,
+            delta,
+            labels,
+            gates,
+            outputs,
+            gid,
+            and_count,
+            privacy_free,
+            ..
+        } = iter;
+
+        Self {
+            cipher,
+            delta,
+            node_layers: vec![0; labels.len()],
+            labels,
+            gates: gates.as_slice(),
+            outputs,
+            pos: 0,
+            gid,
+            queue: VecDeque::new(),
+            hasher: None,
+            counter: 0,
+            and_count,
+            privacy_free,
+            pending: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Sets the number of encrypted gates per batch, defaults to [`DEFAULT_BATCH_SIZE`].
+    ///
+    /// The last batch of the circuit may contain fewer gates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        assert!(batch_size > 0, "batch size must be non-zero");
+        self.batch_size = batch_size;
+    }
+
+    /// Enables hashing of the encrypted gates.
+    pub fn enable_hasher(&mut self) {
+        self.hasher = Some(Hasher::new());
+    }
+
+    /// Returns `true` if the generator has more encrypted gates to generate.
+    pub fn has_gates(&self) -> bool {
+        self.counter != self.and_count || !self.queue.is_empty()
+    }
+
+    /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
+    pub fn finish(mut self) -> Result<GeneratorOutput, GeneratorError> {
+        if self.has_gates() {
+            return Err(GeneratorError::NotFinished);
+        }
+
+        // Finish computing any "free" gates.
+        while self.pos < self.gates.len() {
+            self.garble_window();
+        }
+
+        Ok(GeneratorOutput {
+            outputs: encode_outputs(self.outputs, self.labels, self.delta),
+            hash: self.hasher.as_ref().map(|hasher| {
+                let hash: [u8; 32] = hasher.finalize().into();
+                Hash::from(hash)
+            }),
+        })
+    }
+
+    /// Garbles the next window of gates, pushing the encrypted gates onto the queue.
+    fn garble_window(&mut self) {
+        let start = self.pos;
+
+        // Partition the window into layers of independent gates, recording the gate id of
+        // each AND gate.
+        let mut layers: Vec<Vec<(usize, usize)>> = Vec::new();
+        let mut and_gates = 0;
+        for gate in &self.gates[start..] {
+            let layer = gate
+                .x()
+                .into_iter()
+                .chain(gate.y())
+                .map(|node| self.node_layers[node.id()])
+                .max()
+                .unwrap_or(0);
+            self.node_layers[gate.z().id()] = layer + 1;
+
+            if layer == layers.len() {
+                layers.push(Vec::new());
+            }
+
+            let gid = self.gid + 2 * and_gates;
+            layers[layer].push((self.pos, gid));
+            self.pos += 1;
+
+            if let Gate::And { .. } = gate {
+                and_gates += 1;
+                if and_gates == WINDOW_SIZE {
+                    break;
+                }
+            }
+        }
+
+        let mut and_rows = Vec::with_capacity(and_gates);
+        and_rows.resize_with(and_gates, || Garbled::Free);
+
+        for layer in layers {
+            let cipher = self.cipher;
+            let delta = self.delta;
+            let privacy_free = self.privacy_free;
+            let labels = &*self.labels;
+            let gates = self.gates;
+
+            let garbled: Vec<(Label, Garbled)> = layer
+                .par_iter()
+                .with_min_len(MIN_LAYER_CHUNK)
+                .map(|&(pos, gid)| match gates[pos] {
+                    Gate::Xor { x, y, .. } => (labels[x.id()] ^ labels[y.id()], Garbled::Free),
+                    Gate::And { x, y, .. } => {
+                        let x_0 = labels[x.id()];
+                        let y_0 = labels[y.id()];
+                        if privacy_free {
+                            let (z_0, row) = and_gate_privacy_free(cipher, &x_0, &y_0, &delta, gid);
+                            (z_0, Garbled::PrivacyFree(row))
+                        } else {
+                            let (z_0, encrypted_gate) = and_gate(cipher, &x_0, &y_0, &delta, gid);
+                            (z_0, Garbled::And(encrypted_gate))
+                        }
+                    }
+                    Gate::Inv { x, .. } => (labels[x.id()] ^ delta, Garbled::Free),
+                    Gate::Const { value, .. } => (constant_label(value, &delta), Garbled::Free),
+                })
+                .collect();
+
+            for (&(pos, gid), (z_0, garbled)) in layer.iter().zip(garbled) {
+                self.labels[gates[pos].z().id()] = z_0;
+                if !matches!(garbled, Garbled::Free) {
+                    and_rows[(gid - self.gid) / 2] = garbled;
+                }
+            }
+        }
+
+        // Reset the layers of the nodes produced in this window.
+        for gate in &self.gates[start..self.pos] {
+            self.node_layers[gate.z().id()] = 0;
+        }
+
+        self.gid += 2 * and_gates;
+        self.counter += and_gates;
+
+        for garbled in and_rows {
+            let encrypted_gate = match garbled {
+                Garbled::And(encrypted_gate) => encrypted_gate,
+                // Pack the rows of two AND gates into each encrypted gate.
+                Garbled::PrivacyFree(row) => match self.pending.take() {
+                    Some(first) => EncryptedGate::new([first, row]),
+                    None => {
+                        self.pending = Some(row);
+                        continue;
+                    }
+                },
+                Garbled::Free => unreachable!("all AND gates in the window are garbled"),
+            };
+
+            self.push(encrypted_gate);
+        }
+
+        if self.counter == self.and_count {
+            if let Some(row) = self.pending.take() {
+                self.push(EncryptedGate::new([row, Block::ZERO]));
+            }
+        }
+    }
+
+    fn push(&mut self, encrypted_gate: EncryptedGate) {
+        if let Some(hasher) = &mut self.hasher {
+            hasher.update(&encrypted_gate.to_bytes());
+        }
+
+        self.queue.push_back(encrypted_gate);
+    }
+}
+
+impl<'a> Iterator for ParallelEncryptedGateBatchIter<'a> {
+    type Item = EncryptedGateBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.queue.len() < self.batch_size && self.counter != self.and_count {
+            self.garble_window();
+        }
+
+        if self.queue.is_empty() {
+            return None;
+        }
+
+        let count = self.queue.len().min(self.batch_size);
+
+        Some(EncryptedGateBatch::new(self.queue.drain(..count).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ChaChaEncoder, Encoder, Generator};
+    use mpz_circuits::circuits::AES128;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::half_gates(false)]
+    #[case::privacy_free(true)]
+    fn test_parallel_generator(#[case] privacy_free: bool) {
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let inputs: Vec<_> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let mut gen = Generator::default();
+        let mut gen_parallel = Generator::default();
+
+        let (mut iter, mut iter_parallel) = if privacy_free {
+            (
+                gen.generate_privacy_free_batched(&AES128, encoder.delta(), inputs.clone())
+                    .unwrap(),
+                gen_parallel
+                    .generate_privacy_free_batched_parallel(&AES128, encoder.delta(), inputs)
+                    .unwrap(),
+            )
+        } else {
+            (
+                gen.generate_batched(&AES128, encoder.delta(), inputs.clone())
+                    .unwrap(),
+                gen_parallel
+                    .generate_batched_parallel(&AES128, encoder.delta(), inputs)
+                    .unwrap(),
+            )
+        };
+
+        iter.enable_hasher();
+        iter_parallel.enable_hasher();
+
+        let batches: Vec<_> = iter.by_ref().map(|batch| batch.into_vec()).collect();
+        let batches_parallel: Vec<_> = iter_parallel
+            .by_ref()
+            .map(|batch| batch.into_vec())
+            .collect();
+
+        assert_eq!(batches, batches_parallel);
+        assert!(!iter_parallel.has_gates());
+
+        let output = iter.finish().unwrap();
+        let output_parallel = iter_parallel.finish().unwrap();
+
+        assert_eq!(output.outputs, output_parallel.outputs);
+        assert_eq!(output.hash, output_parallel.hash);
+    }
+}
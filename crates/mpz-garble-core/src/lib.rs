@@ -47,37 +47,43 @@
 #![deny(missing_docs, unreachable_pub, unused_must_use)]
 #![deny(clippy::all)]
 
+pub mod audit;
+pub mod bridge;
 pub(crate) mod circuit;
 pub mod encoding;
 mod evaluator;
 mod generator;
+pub mod persist;
 
 pub use circuit::{EncryptedGate, EncryptedGateBatch, GarbledCircuit};
 pub use encoding::{
     state as encoding_state, ChaChaEncoder, Decoding, Delta, Encode, EncodedValue, Encoder,
-    EncodingCommitment, EqualityCheck, Label, ValueError,
+    EncodingCommitment, EqualityCheck, Label, MerkleEncodingCommitment, MerkleEncodingOpening,
+    MerkleEncodingRoot, ValueError,
 };
 pub use evaluator::{
-    EncryptedGateBatchConsumer, EncryptedGateConsumer, Evaluator, EvaluatorError, EvaluatorOutput,
+    is_canonical_padding, EncryptedGateBatchConsumer, EncryptedGateConsumer, Evaluator,
+    EvaluatorCheckpoint, EvaluatorError, EvaluatorOutput,
 };
+#[cfg(feature = "parallel")]
+pub use generator::ParallelEncryptedGateBatchIter;
 pub use generator::{
     EncryptedGateBatchIter, EncryptedGateIter, Generator, GeneratorError, GeneratorOutput,
 };
+pub use persist::{GarbledCircuitReader, GarbledCircuitWriter, PersistError};
 
 const KB: usize = 1024;
 const BYTES_PER_GATE: usize = 32;
 
-/// Maximum size of a batch in bytes.
-const MAX_BATCH_SIZE: usize = 4 * KB;
+/// Default size of a batch in bytes.
+const DEFAULT_BATCH_BYTES: usize = 4 * KB;
 
 /// Default amount of encrypted gates per batch.
 ///
-/// Batches are stack allocated, so we will limit the size to `MAX_BATCH_SIZE`.
-///
-/// Additionally, because the size of each batch is static, if a circuit is smaller than a batch
-/// we will be wasting some bandwidth sending empty bytes. This puts an upper limit on that
-/// waste.
-pub(crate) const DEFAULT_BATCH_SIZE: usize = MAX_BATCH_SIZE / BYTES_PER_GATE;
+/// Larger batches reduce the number of messages sent on high-latency links, while smaller
+/// batches reduce the memory held by the generator and evaluator. The batch size can be set at
+/// runtime with [`EncryptedGateBatchIter::set_batch_size`].
+pub const DEFAULT_BATCH_SIZE: usize = DEFAULT_BATCH_BYTES / BYTES_PER_GATE;
 
 #[cfg(test)]
 mod tests {
@@ -85,10 +91,16 @@ mod tests {
         cipher::{BlockEncrypt, KeyInit},
         Aes128,
     };
-    use mpz_circuits::{circuits::AES128, types::Value, CircuitBuilder};
-    use mpz_core::aes::FIXED_KEY_AES;
+    use mpz_circuits::{
+        circuits::AES128,
+        random::{RandomCircuit, RandomCircuitConfig},
+        types::Value,
+        CircuitBuilder,
+    };
+    use mpz_core::{aes::FIXED_KEY_AES, Block};
     use rand::SeedableRng;
     use rand_chacha::ChaCha12Rng;
+    use rstest::rstest;
 
     use super::*;
 
@@ -115,6 +127,45 @@ mod tests {
         assert_eq!(ev::and_gate(cipher, &x_1, &y_1, &encrypted_gate, gid), z_1);
     }
 
+    #[test]
+    fn test_and_gate_batch() {
+        use crate::{evaluator as ev, generator as gen};
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let cipher = &(*FIXED_KEY_AES);
+
+        let delta = Delta::random(&mut rng);
+        let gid: usize = 1;
+
+        // A partial batch exercises the unpipelined tail.
+        let inputs: Vec<(Label, Label)> = (0..gen::AND_BATCH_SIZE - 1)
+            .map(|_| (Label::random(&mut rng), Label::random(&mut rng)))
+            .collect();
+
+        let mut garbled = vec![(Label::default(), EncryptedGate::default()); inputs.len()];
+        gen::and_gate_batch(cipher, &inputs, &delta, gid, &mut garbled);
+
+        for (i, ((x_0, y_0), garbled)) in inputs.iter().zip(&garbled).enumerate() {
+            assert_eq!(
+                *garbled,
+                gen::and_gate(cipher, x_0, y_0, &delta, gid + 2 * i)
+            );
+        }
+
+        let active: Vec<_> = inputs
+            .iter()
+            .zip(&garbled)
+            .map(|((x_0, y_0), (_, encrypted_gate))| (*x_0 ^ delta, *y_0, *encrypted_gate))
+            .collect();
+
+        let mut outputs = vec![Label::default(); active.len()];
+        ev::and_gate_batch(cipher, &active, gid, &mut outputs);
+
+        for (output, (z_0, _)) in outputs.iter().zip(&garbled) {
+            assert_eq!(*output, *z_0);
+        }
+    }
+
     #[test]
     fn test_garble() {
         let encoder = ChaChaEncoder::new([0; 32]);
@@ -179,6 +230,119 @@ mod tests {
         assert_eq!(gen_hash, ev_hash);
     }
 
+    #[test]
+    fn test_garble_signed() {
+        use mpz_circuits::ops::WrappingAdd;
+
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i8>();
+        let b = builder.add_input::<i8>();
+        let sum = a.wrapping_add(b);
+        let lt = a.lt(b);
+        builder.add_output(sum);
+        builder.add_output(lt);
+        let circ = builder.build().unwrap();
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(-100i8).unwrap(),
+            full_inputs[1].clone().select(27i8).unwrap(),
+        ];
+
+        let mut gen = Generator::default();
+        let mut ev = Evaluator::default();
+
+        let mut gen_iter = gen
+            .generate_batched(&circ, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut ev_consumer = ev.evaluate_batched(&circ, active_inputs).unwrap();
+
+        for batch in gen_iter.by_ref() {
+            ev_consumer.next(batch);
+        }
+
+        let full_outputs = gen_iter.finish().unwrap().outputs;
+        let active_outputs = ev_consumer.finish().unwrap().outputs;
+
+        let outputs: Vec<Value> = active_outputs
+            .iter()
+            .zip(full_outputs)
+            .map(|(active_output, full_output)| {
+                full_output.commit().verify(active_output).unwrap();
+                active_output.decode(&full_output.decoding()).unwrap()
+            })
+            .collect();
+
+        assert_eq!(outputs, vec![Value::I8(-73), Value::Bit(true)]);
+    }
+
+    // Tests garbling with batch sizes smaller than, not dividing, and larger than the circuit.
+    #[rstest]
+    #[case::single(1)]
+    #[case::uneven(1000)]
+    #[case::oversized(1 << 20)]
+    fn test_garble_batch_size(#[case] batch_size: usize) {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(key).unwrap(),
+            full_inputs[1].clone().select(msg).unwrap(),
+        ];
+
+        let mut gen = Generator::default();
+        let mut ev = Evaluator::default();
+
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut ev_consumer = ev.evaluate_batched(&AES128, active_inputs).unwrap();
+
+        gen_iter.set_batch_size(batch_size);
+        gen_iter.enable_hasher();
+        ev_consumer.enable_hasher();
+        ev_consumer.enable_strict_padding();
+
+        let mut batches = Vec::new();
+        while let Some(batch) = gen_iter.next() {
+            batches.push((batch, gen_iter.digest().unwrap()));
+        }
+        assert_eq!(batches.len(), AES128.and_count().div_ceil(batch_size));
+        assert!(batches.iter().all(|(batch, _)| batch.len() <= batch_size));
+        assert_eq!(
+            batches.iter().map(|(batch, _)| batch.len()).sum::<usize>(),
+            AES128.and_count()
+        );
+
+        // The digests of the generator and evaluator agree at the end of each batch.
+        let mut count = 0;
+        for (batch, digest) in batches {
+            count += batch.len();
+            ev_consumer.next(batch);
+            assert_eq!(ev_consumer.digest(), Some((count, digest)));
+        }
+
+        let GeneratorOutput { hash: gen_hash, .. } = gen_iter.finish().unwrap();
+        let EvaluatorOutput { hash: ev_hash, .. } = ev_consumer.finish().unwrap();
+
+        assert_eq!(gen_hash, ev_hash);
+    }
+
     // Tests garbling a circuit with no AND gates
     #[test]
     fn test_garble_no_and() {
@@ -244,4 +408,406 @@ mod tests {
         assert_eq!(actual, a ^ b);
         assert_eq!(gen_hash, ev_hash);
     }
+
+    #[rstest]
+    #[case::canonical(false)]
+    #[case::non_canonical(true)]
+    fn test_garble_strict_padding(#[case] tamper: bool) {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = a & b;
+        builder.add_output(c);
+        let circ = builder.build().unwrap();
+        assert!(circ.and_count() < DEFAULT_BATCH_SIZE);
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(1u8).unwrap(),
+            full_inputs[1].clone().select(3u8).unwrap(),
+        ];
+
+        let mut gen = Generator::default();
+        let mut ev = Evaluator::default();
+
+        let mut gen_iter = gen
+            .generate_batched(&circ, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut ev_consumer = ev.evaluate_batched(&circ, active_inputs).unwrap();
+
+        ev_consumer.enable_strict_padding();
+
+        let mut batch = gen_iter.next().unwrap().into_vec();
+        assert_eq!(batch.len(), circ.and_count());
+        if tamper {
+            // Smuggle data into padding after the last gate.
+            batch.push(EncryptedGate::default());
+            batch.push(EncryptedGate::new([Block::ONES; 2]));
+        }
+        ev_consumer.next(EncryptedGateBatch::new(batch));
+
+        assert!(gen_iter.next().is_none());
+
+        let result = ev_consumer.finish();
+        if tamper {
+            assert!(matches!(result, Err(EvaluatorError::InvalidPadding)));
+        } else {
+            assert!(result.is_ok());
+        }
+    }
+
+    // Tests garbling a circuit with constant outputs
+    #[rstest]
+    #[case::standard(false)]
+    #[case::privacy_free(true)]
+    fn test_garble_constants(#[case] privacy_free: bool) {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output((a & b) & builder.get_constant(0x0fu8));
+        builder.add_output(builder.get_constant(0xa5u8));
+        let circ = builder.build().unwrap();
+
+        let inputs: Vec<Value> = vec![0x3cu8.into(), 0xffu8.into()];
+        let expected = circ.evaluate(&inputs).unwrap();
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = full_inputs
+            .iter()
+            .zip(inputs.clone())
+            .map(|(full_input, input)| full_input.clone().select(input).unwrap())
+            .collect();
+
+        let mut gen = Generator::default();
+        let mut ev = Evaluator::default();
+
+        let (mut gen_iter, mut ev_consumer) = if privacy_free {
+            (
+                gen.generate_privacy_free(&circ, encoder.delta(), full_inputs)
+                    .unwrap(),
+                ev.evaluate_privacy_free(&circ, active_inputs, inputs)
+                    .unwrap(),
+            )
+        } else {
+            (
+                gen.generate(&circ, encoder.delta(), full_inputs).unwrap(),
+                ev.evaluate(&circ, active_inputs).unwrap(),
+            )
+        };
+
+        for gate in gen_iter.by_ref() {
+            ev_consumer.next(gate);
+        }
+
+        let GeneratorOutput {
+            outputs: full_outputs,
+            ..
+        } = gen_iter.finish().unwrap();
+        let EvaluatorOutput {
+            outputs: active_outputs,
+            ..
+        } = ev_consumer.finish().unwrap();
+
+        let outputs: Vec<Value> = active_outputs
+            .iter()
+            .zip(full_outputs)
+            .map(|(active_output, full_output)| {
+                full_output.commit().verify(active_output).unwrap();
+                active_output.decode(&full_output.decoding()).unwrap()
+            })
+            .collect();
+
+        assert_eq!(outputs, expected);
+    }
+
+    // Tests interrupting an evaluation and resuming it from a persisted checkpoint.
+    #[rstest]
+    #[case::standard(false)]
+    #[case::privacy_free(true)]
+    fn test_evaluator_checkpoint(#[case] privacy_free: bool) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let config = RandomCircuitConfig {
+            gate_count: 2000,
+            depth: 50,
+            and_ratio: 0.5,
+            inv_ratio: 0.1,
+            ..Default::default()
+        };
+        let (circ, inputs, expected) = RandomCircuit::new(&mut rng, &config).into_parts();
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = full_inputs
+            .iter()
+            .zip(inputs.clone())
+            .map(|(full_input, input)| full_input.clone().select(input).unwrap())
+            .collect();
+
+        let mut gen = Generator::default();
+        let mut ev = Evaluator::default();
+
+        let (mut gen_iter, mut ev_consumer) = if privacy_free {
+            (
+                gen.generate_privacy_free(&circ, encoder.delta(), full_inputs)
+                    .unwrap(),
+                ev.evaluate_privacy_free(&circ, active_inputs, inputs)
+                    .unwrap(),
+            )
+        } else {
+            (
+                gen.generate(&circ, encoder.delta(), full_inputs).unwrap(),
+                ev.evaluate(&circ, active_inputs).unwrap(),
+            )
+        };
+
+        for gate in gen_iter.by_ref().take(circ.and_count() / 4) {
+            ev_consumer.next(gate);
+        }
+
+        let checkpoint = ev_consumer.checkpoint().unwrap();
+        assert!(checkpoint.label_count() < circ.feed_count());
+        drop(ev_consumer);
+
+        let bytes = bincode::serialize(&checkpoint).unwrap();
+        let checkpoint: EvaluatorCheckpoint = bincode::deserialize(&bytes).unwrap();
+
+        // Resume in a fresh evaluator.
+        let mut ev = Evaluator::default();
+        let mut ev_consumer = ev.resume(&circ, checkpoint).unwrap();
+
+        for gate in gen_iter.by_ref() {
+            ev_consumer.next(gate);
+        }
+
+        let GeneratorOutput {
+            outputs: full_outputs,
+            ..
+        } = gen_iter.finish().unwrap();
+        let EvaluatorOutput {
+            outputs: active_outputs,
+            ..
+        } = ev_consumer.finish().unwrap();
+
+        let outputs: Vec<Value> = active_outputs
+            .iter()
+            .zip(full_outputs)
+            .map(|(active_output, full_output)| {
+                full_output.commit().verify(active_output).unwrap();
+                active_output.decode(&full_output.decoding()).unwrap()
+            })
+            .collect();
+
+        assert_eq!(outputs, expected);
+    }
+
+    #[test]
+    fn test_evaluator_checkpoint_errors() {
+        let mut ev = Evaluator::default();
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = AES128
+            .inputs()
+            .iter()
+            .map(|input| {
+                ChaChaEncoder::new([0; 32])
+                    .encode_by_type(0, &input.value_type())
+                    .select(Value::from([0u8; 16]))
+                    .unwrap()
+            })
+            .collect();
+
+        let mut ev_consumer = ev.evaluate(&AES128, active_inputs).unwrap();
+        let checkpoint = ev_consumer.checkpoint().unwrap();
+
+        ev_consumer.enable_hasher();
+        assert!(matches!(
+            ev_consumer.checkpoint(),
+            Err(EvaluatorError::CheckpointHasher)
+        ));
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        builder.add_output(a);
+        let circ = builder.build().unwrap();
+
+        let mut ev = Evaluator::default();
+        assert!(matches!(
+            ev.resume(&circ, checkpoint),
+            Err(EvaluatorError::CheckpointMismatch)
+        ));
+    }
+
+    // Fuzzes the generator and evaluator with random circuits of various shapes,
+    // checking that they agree with the plaintext evaluation.
+    #[test]
+    fn test_garble_random_circuits() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        for (gate_count, depth, and_ratio) in [
+            (1, 1, 1.0),
+            (64, 1, 0.5),
+            (64, 64, 0.5),
+            (1000, 10, 0.0),
+            (1000, 10, 1.0),
+            (5000, 100, 0.3),
+        ] {
+            let config = RandomCircuitConfig {
+                gate_count,
+                depth,
+                and_ratio,
+                inv_ratio: (1.0 - and_ratio) / 4.0,
+                ..Default::default()
+            };
+            let (circ, inputs, expected) = RandomCircuit::new(&mut rng, &config).into_parts();
+
+            let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+                .inputs()
+                .iter()
+                .enumerate()
+                .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+                .collect();
+
+            let active_inputs: Vec<EncodedValue<encoding_state::Active>> = full_inputs
+                .iter()
+                .zip(inputs)
+                .map(|(full_input, input)| full_input.clone().select(input).unwrap())
+                .collect();
+
+            let mut gen = Generator::default();
+            let mut ev = Evaluator::default();
+
+            let mut gen_iter = gen
+                .generate_batched(&circ, encoder.delta(), full_inputs)
+                .unwrap();
+            let mut ev_consumer = ev.evaluate_batched(&circ, active_inputs).unwrap();
+
+            gen_iter.enable_hasher();
+            ev_consumer.enable_hasher();
+
+            for batch in gen_iter.by_ref() {
+                ev_consumer.next(batch);
+            }
+
+            let GeneratorOutput {
+                outputs: full_outputs,
+                hash: gen_hash,
+            } = gen_iter.finish().unwrap();
+            let EvaluatorOutput {
+                outputs: active_outputs,
+                hash: ev_hash,
+            } = ev_consumer.finish().unwrap();
+
+            let outputs: Vec<Value> = active_outputs
+                .iter()
+                .zip(full_outputs)
+                .map(|(active_output, full_output)| {
+                    full_output.commit().verify(active_output).unwrap();
+                    active_output.decode(&full_output.decoding()).unwrap()
+                })
+                .collect();
+
+            assert_eq!(outputs, expected);
+            assert_eq!(gen_hash, ev_hash);
+        }
+    }
+
+    #[test]
+    fn test_garble_privacy_free_random_circuits() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        for (gate_count, depth, and_ratio) in [
+            (1, 1, 1.0),
+            (63, 1, 1.0),
+            (64, 64, 0.5),
+            (1000, 10, 0.0),
+            (5000, 100, 0.3),
+        ] {
+            let config = RandomCircuitConfig {
+                gate_count,
+                depth,
+                and_ratio,
+                inv_ratio: (1.0 - and_ratio) / 4.0,
+                ..Default::default()
+            };
+            let (circ, inputs, expected) = RandomCircuit::new(&mut rng, &config).into_parts();
+
+            let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+                .inputs()
+                .iter()
+                .enumerate()
+                .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+                .collect();
+
+            let active_inputs: Vec<EncodedValue<encoding_state::Active>> = full_inputs
+                .iter()
+                .zip(inputs.clone())
+                .map(|(full_input, input)| full_input.clone().select(input).unwrap())
+                .collect();
+
+            let mut gen = Generator::default();
+            let mut ev = Evaluator::default();
+
+            let mut gen_iter = gen
+                .generate_privacy_free(&circ, encoder.delta(), full_inputs)
+                .unwrap();
+            let mut ev_consumer = ev
+                .evaluate_privacy_free(&circ, active_inputs, inputs)
+                .unwrap();
+
+            gen_iter.enable_hasher();
+            ev_consumer.enable_hasher();
+
+            let mut encrypted_gates = 0;
+            for gate in gen_iter.by_ref() {
+                ev_consumer.next(gate);
+                encrypted_gates += 1;
+            }
+
+            // Two AND gates are packed into each encrypted gate.
+            assert_eq!(encrypted_gates, (circ.and_count() + 1) / 2);
+
+            let GeneratorOutput {
+                outputs: full_outputs,
+                hash: gen_hash,
+            } = gen_iter.finish().unwrap();
+            let EvaluatorOutput {
+                outputs: active_outputs,
+                hash: ev_hash,
+            } = ev_consumer.finish().unwrap();
+
+            let outputs: Vec<Value> = active_outputs
+                .iter()
+                .zip(full_outputs)
+                .map(|(active_output, full_output)| {
+                    full_output.commit().verify(active_output).unwrap();
+                    active_output.decode(&full_output.decoding()).unwrap()
+                })
+                .collect();
+
+            assert_eq!(outputs, expected);
+            assert_eq!(gen_hash, ev_hash);
+        }
+    }
 }
@@ -0,0 +1,631 @@
+//! Persistence format for garbled circuits.
+//!
+//! Garbled circuits can be written to and read from any [`Write`] or [`Read`] in a stable binary
+//! format, which allows a generator to garble a circuit once, cache it on disk and stream it to an
+//! evaluator later.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! | Field              | Size           | Description                                         |
+//! |--------------------|----------------|-----------------------------------------------------|
+//! | magic              | 8 bytes        | [`MAGIC`]                                           |
+//! | version            | 2 bytes        | [`FORMAT_VERSION`]                                  |
+//! | flags              | 1 byte         | `1` if the gates are sealed, else `0`               |
+//! | gate count         | 8 bytes        | Number of encrypted gates                           |
+//! | gates              | 32 bytes each  | Encrypted gates, in the order they are evaluated    |
+//! | commitments flag   | 1 byte         | `1` if encoding commitments are present, else `0`   |
+//! | commitments length | 8 bytes        | Only if present                                     |
+//! | commitments        | variable       | Only if present, bincode encoded                    |
+//! | hash               | 32 bytes       | BLAKE3 hash of all preceding bytes                  |
+//!
+//! Batch padding is not persisted, so a circuit can be read back with any batch size.
+//!
+//! # Sealing
+//!
+//! A writer created with [`GarbledCircuitWriter::sealed`] seals the gates with
+//! [`mpz_common::seal`], so they do not hit the disk in plaintext. Sealed gates are written in
+//! chunks, one per written batch or gate, each of which is prefixed with its length as 8 bytes. The
+//! label of a chunk binds it to the gate count of the circuit and the index of its first gate, so
+//! chunks can not be reordered or dropped. Sealed circuits are read with
+//! [`GarbledCircuitReader::sealed`].
+//!
+//! # Integrity
+//!
+//! The integrity hash only protects against accidental corruption of the file, it does not
+//! authenticate who garbled the circuit. Because the hash is at the end of the file, gates which
+//! are streamed from a [`GarbledCircuitReader`] are not verified until
+//! [`GarbledCircuitReader::finish`] is called, so any output computed from them must not be
+//! used before that.
+
+use core::fmt;
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+};
+
+use blake3::Hasher;
+use mpz_common::seal::{self, SealError, SealingKey};
+
+use crate::{EncodingCommitment, EncryptedGate, EncryptedGateBatch, GarbledCircuit};
+
+/// Magic bytes at the start of a persisted garbled circuit.
+pub const MAGIC: [u8; 8] = *b"MPZGARBL";
+/// Current version of the persistence format.
+pub const FORMAT_VERSION: u16 = 2;
+
+const GATE_BYTES: usize = 32;
+const FLAG_SEALED: u8 = 1;
+
+fn chunk_label(gate_count: usize, offset: usize) -> String {
+    format!("mpz-garble-core/gates/{gate_count}/{offset}")
+}
+
+/// Errors that can occur while persisting a garbled circuit.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum PersistError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("invalid magic bytes, not a garbled circuit")]
+    InvalidMagic,
+    #[error("unsupported format version: {0}")]
+    UnsupportedVersion(u16),
+    #[error("invalid flags: {0}")]
+    InvalidFlags(u8),
+    #[error("the circuit is sealed, a sealing key is required to read it")]
+    Sealed,
+    #[error("the circuit is not sealed")]
+    NotSealed,
+    #[error("invalid sealed chunk: {0}")]
+    Seal(#[from] SealError),
+    #[error("gate count is too large: {0}")]
+    GateCountOverflow(u64),
+    #[error("incorrect number of encrypted gates, expected: {expected}, actual: {actual}")]
+    IncorrectGateCount { expected: usize, actual: usize },
+    #[error("invalid commitments flag: {0}")]
+    InvalidCommitmentsFlag(u8),
+    #[error("invalid commitments: {0}")]
+    InvalidCommitments(#[from] bincode::Error),
+    #[error("integrity check failed")]
+    IntegrityCheck,
+}
+
+/// Writes a garbled circuit in the persistence format.
+///
+/// Gates can be written one at a time or in batches, so a circuit can be persisted while it is
+/// being garbled.
+pub struct GarbledCircuitWriter<W> {
+    writer: W,
+    key: Option<SealingKey>,
+    hasher: Hasher,
+    gate_count: usize,
+    written: usize,
+}
+
+impl<W> fmt::Debug for GarbledCircuitWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GarbledCircuitWriter {{ .. }}")
+    }
+}
+
+impl<W: Write> GarbledCircuitWriter<W> {
+    /// Creates a new writer and writes the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to persist the circuit to.
+    /// * `gate_count` - The number of encrypted gates of the circuit.
+    pub fn new(writer: W, gate_count: usize) -> Result<Self, PersistError> {
+        Self::init(writer, gate_count, None)
+    }
+
+    /// Creates a new writer which seals the gates, and writes the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to persist the circuit to.
+    /// * `gate_count` - The number of encrypted gates of the circuit.
+    /// * `key` - The sealing key.
+    pub fn sealed(writer: W, gate_count: usize, key: SealingKey) -> Result<Self, PersistError> {
+        Self::init(writer, gate_count, Some(key))
+    }
+
+    fn init(writer: W, gate_count: usize, key: Option<SealingKey>) -> Result<Self, PersistError> {
+        let flags = if key.is_some() { FLAG_SEALED } else { 0 };
+
+        let mut this = Self {
+            writer,
+            key,
+            hasher: Hasher::new(),
+            gate_count,
+            written: 0,
+        };
+
+        this.write_bytes(&MAGIC)?;
+        this.write_bytes(&FORMAT_VERSION.to_le_bytes())?;
+        this.write_bytes(&[flags])?;
+        this.write_bytes(&(gate_count as u64).to_le_bytes())?;
+
+        Ok(this)
+    }
+
+    /// Returns the number of gates which still need to be written.
+    pub fn remaining(&self) -> usize {
+        self.gate_count - self.written
+    }
+
+    /// Writes an encrypted gate.
+    pub fn write_gate(&mut self, gate: EncryptedGate) -> Result<(), PersistError> {
+        if self.remaining() == 0 {
+            return Err(PersistError::IncorrectGateCount {
+                expected: self.gate_count,
+                actual: self.written + 1,
+            });
+        }
+
+        if self.key.is_some() {
+            return self.write_chunk(vec![gate]);
+        }
+
+        self.write_bytes(&gate.to_bytes())?;
+        self.written += 1;
+
+        Ok(())
+    }
+
+    /// Writes a batch of encrypted gates.
+    ///
+    /// Gates beyond the gate count of the circuit are treated as padding and are not written.
+    pub fn write_batch(&mut self, batch: EncryptedGateBatch) -> Result<(), PersistError> {
+        let remaining = self.remaining();
+        let gates = batch.into_vec().into_iter().take(remaining);
+
+        if self.key.is_some() {
+            let gates: Vec<_> = gates.collect();
+            if gates.is_empty() {
+                return Ok(());
+            }
+
+            return self.write_chunk(gates);
+        }
+
+        for gate in gates {
+            self.write_gate(gate)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the encoding commitments and the integrity hash, returning the inner writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `commitments` - The encoding commitments of the circuit outputs, if any.
+    pub fn finish(mut self, commitments: Option<&[EncodingCommitment]>) -> Result<W, PersistError> {
+        if self.remaining() != 0 {
+            return Err(PersistError::IncorrectGateCount {
+                expected: self.gate_count,
+                actual: self.written,
+            });
+        }
+
+        match commitments {
+            Some(commitments) => {
+                let bytes = bincode::serialize(commitments)?;
+                self.write_bytes(&[1])?;
+                self.write_bytes(&(bytes.len() as u64).to_le_bytes())?;
+                self.write_bytes(&bytes)?;
+            }
+            None => self.write_bytes(&[0])?,
+        }
+
+        let hash = self.hasher.finalize();
+        self.writer.write_all(hash.as_bytes())?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    fn write_chunk(&mut self, gates: Vec<EncryptedGate>) -> Result<(), PersistError> {
+        let key = self.key.as_ref().expect("writer should be sealed");
+        let chunk = seal::seal_value(
+            key,
+            &chunk_label(self.gate_count, self.written),
+            &gates,
+            &mut rand::thread_rng(),
+        )?;
+
+        self.write_bytes(&(chunk.len() as u64).to_le_bytes())?;
+        self.write_bytes(&chunk)?;
+        self.written += gates.len();
+
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), PersistError> {
+        self.hasher.update(bytes);
+        self.writer.write_all(bytes)?;
+
+        Ok(())
+    }
+}
+
+/// Reads a garbled circuit in the persistence format.
+///
+/// # ⚠️ Warning ⚠️
+///
+/// The gates returned by this reader are not verified until [`GarbledCircuitReader::finish`]
+/// returns successfully.
+pub struct GarbledCircuitReader<R> {
+    reader: R,
+    key: Option<SealingKey>,
+    hasher: Hasher,
+    gate_count: usize,
+    read: usize,
+    buffer: VecDeque<EncryptedGate>,
+}
+
+impl<R> fmt::Debug for GarbledCircuitReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GarbledCircuitReader {{ .. }}")
+    }
+}
+
+impl<R: Read> GarbledCircuitReader<R> {
+    /// Creates a new reader and reads the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to read the circuit from.
+    pub fn new(reader: R) -> Result<Self, PersistError> {
+        Self::init(reader, None)
+    }
+
+    /// Creates a new reader for a sealed circuit and reads the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to read the circuit from.
+    /// * `key` - The sealing key.
+    pub fn sealed(reader: R, key: SealingKey) -> Result<Self, PersistError> {
+        Self::init(reader, Some(key))
+    }
+
+    fn init(reader: R, key: Option<SealingKey>) -> Result<Self, PersistError> {
+        let mut this = Self {
+            reader,
+            key,
+            hasher: Hasher::new(),
+            gate_count: 0,
+            read: 0,
+            buffer: VecDeque::new(),
+        };
+
+        if this.read_array::<8>()? != MAGIC {
+            return Err(PersistError::InvalidMagic);
+        }
+
+        let version = u16::from_le_bytes(this.read_array()?);
+        if version != FORMAT_VERSION {
+            return Err(PersistError::UnsupportedVersion(version));
+        }
+
+        match (this.read_array::<1>()?, this.key.is_some()) {
+            ([0], false) | ([FLAG_SEALED], true) => {}
+            ([0], true) => return Err(PersistError::NotSealed),
+            ([FLAG_SEALED], false) => return Err(PersistError::Sealed),
+            ([flags], _) => return Err(PersistError::InvalidFlags(flags)),
+        }
+
+        let gate_count = u64::from_le_bytes(this.read_array()?);
+        this.gate_count =
+            usize::try_from(gate_count).map_err(|_| PersistError::GateCountOverflow(gate_count))?;
+
+        Ok(this)
+    }
+
+    /// Returns the number of encrypted gates of the circuit.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+
+    /// Returns the number of gates which have not been read yet.
+    pub fn remaining(&self) -> usize {
+        self.gate_count - self.read
+    }
+
+    /// Reads the next encrypted gate, returning `None` if all gates have been read.
+    pub fn read_gate(&mut self) -> Result<Option<EncryptedGate>, PersistError> {
+        if self.remaining() == 0 {
+            return Ok(None);
+        }
+
+        let gate = if self.key.is_some() {
+            if self.buffer.is_empty() {
+                self.read_chunk()?;
+            }
+
+            self.buffer
+                .pop_front()
+                .expect("chunk should contain at least one gate")
+        } else {
+            EncryptedGate::from_bytes(self.read_array::<GATE_BYTES>()?)
+        };
+        self.read += 1;
+
+        Ok(Some(gate))
+    }
+
+    /// Reads the next batch of at most `batch_size` encrypted gates, returning `None` if all
+    /// gates have been read.
+    pub fn read_batch(
+        &mut self,
+        batch_size: usize,
+    ) -> Result<Option<EncryptedGateBatch>, PersistError> {
+        if self.remaining() == 0 {
+            return Ok(None);
+        }
+
+        let count = self.remaining().min(batch_size);
+        let mut batch = Vec::with_capacity(count);
+        for _ in 0..count {
+            match self.read_gate()? {
+                Some(gate) => batch.push(gate),
+                None => break,
+            }
+        }
+
+        Ok(Some(EncryptedGateBatch::new(batch)))
+    }
+
+    /// Reads the encoding commitments and verifies the integrity hash.
+    ///
+    /// # Returns
+    ///
+    /// * The encoding commitments of the circuit outputs, if any.
+    pub fn finish(mut self) -> Result<Option<Vec<EncodingCommitment>>, PersistError> {
+        if self.remaining() != 0 {
+            return Err(PersistError::IncorrectGateCount {
+                expected: self.gate_count,
+                actual: self.read,
+            });
+        }
+
+        let commitments = match self.read_array::<1>()? {
+            [0] => None,
+            [1] => Some(bincode::deserialize(&self.read_vec()?)?),
+            [flag] => return Err(PersistError::InvalidCommitmentsFlag(flag)),
+        };
+
+        let mut expected = [0u8; 32];
+        self.reader.read_exact(&mut expected)?;
+
+        if *self.hasher.finalize().as_bytes() != expected {
+            return Err(PersistError::IntegrityCheck);
+        }
+
+        Ok(commitments)
+    }
+
+    fn read_chunk(&mut self) -> Result<(), PersistError> {
+        let chunk = self.read_vec()?;
+        let key = self.key.as_ref().expect("reader should be sealed");
+        let gates: Vec<EncryptedGate> =
+            seal::open_value(key, &chunk_label(self.gate_count, self.read), &chunk)?;
+
+        if gates.is_empty() || gates.len() > self.remaining() {
+            return Err(PersistError::IncorrectGateCount {
+                expected: self.gate_count,
+                actual: self.read + gates.len(),
+            });
+        }
+
+        self.buffer.extend(gates);
+
+        Ok(())
+    }
+
+    fn read_vec(&mut self) -> Result<Vec<u8>, PersistError> {
+        let len = u64::from_le_bytes(self.read_array()?);
+        let mut bytes = Vec::new();
+        self.reader.by_ref().take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+        }
+        self.hasher.update(&bytes);
+
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], PersistError> {
+        let mut bytes = [0u8; N];
+        self.reader.read_exact(&mut bytes)?;
+        self.hasher.update(&bytes);
+
+        Ok(bytes)
+    }
+}
+
+impl GarbledCircuit {
+    /// Writes the garbled circuit in the persistence format.
+    ///
+    /// See [`crate::persist`] for details.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), PersistError> {
+        let mut writer = GarbledCircuitWriter::new(writer, self.gates.len())?;
+        for gate in &self.gates {
+            writer.write_gate(*gate)?;
+        }
+        writer.finish(self.commitments.as_deref())?;
+
+        Ok(())
+    }
+
+    /// Reads a garbled circuit in the persistence format.
+    ///
+    /// See [`crate::persist`] for details.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, PersistError> {
+        let mut reader = GarbledCircuitReader::new(reader)?;
+
+        // The gate count is not verified yet, so we do not trust it for allocation.
+        let mut gates = Vec::with_capacity(reader.gate_count().min(1 << 16));
+        while let Some(gate) = reader.read_gate()? {
+            gates.push(gate);
+        }
+        let commitments = reader.finish()?;
+
+        Ok(Self { gates, commitments })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::circuits::AES128;
+
+    use super::*;
+    use crate::{ChaChaEncoder, Encoder, Evaluator, Generator, GeneratorOutput};
+
+    fn garble() -> Vec<u8> {
+        garble_with(|writer| GarbledCircuitWriter::new(writer, AES128.and_count()).unwrap())
+    }
+
+    fn garble_with(new_writer: impl FnOnce(Vec<u8>) -> GarbledCircuitWriter<Vec<u8>>) -> Vec<u8> {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+        let key = encoder.encode::<[u8; 16]>(0);
+        let msg = encoder.encode::<[u8; 16]>(1);
+
+        let mut gen = Generator::default();
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), vec![key, msg])
+            .unwrap();
+
+        let mut writer = new_writer(Vec::new());
+        for batch in gen_iter.by_ref() {
+            writer.write_batch(batch).unwrap();
+        }
+
+        let GeneratorOutput { outputs, .. } = gen_iter.finish().unwrap();
+        let commitments: Vec<_> = outputs.iter().map(|output| output.commit()).collect();
+
+        writer.finish(Some(&commitments)).unwrap()
+    }
+
+    fn evaluate(mut reader: GarbledCircuitReader<&[u8]>) {
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let active_key = encoder.encode::<[u8; 16]>(0).select(key).unwrap();
+        let active_msg = encoder.encode::<[u8; 16]>(1).select(msg).unwrap();
+
+        let mut ev = Evaluator::default();
+        let mut ev_consumer = ev
+            .evaluate_batched(&AES128, vec![active_key, active_msg])
+            .unwrap();
+
+        assert_eq!(reader.gate_count(), AES128.and_count());
+
+        // Batches do not need to match the size they were written with.
+        while let Some(batch) = reader.read_batch(100).unwrap() {
+            ev_consumer.next(batch);
+        }
+
+        let commitments = reader.finish().unwrap().unwrap();
+        let outputs = ev_consumer.finish().unwrap().outputs;
+
+        commitments[0].verify(&outputs[0]).unwrap();
+    }
+
+    #[test]
+    fn test_persist_stream() {
+        let bytes = garble();
+
+        evaluate(GarbledCircuitReader::new(bytes.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_persist_sealed() {
+        let key = SealingKey::new([1u8; 32]);
+        let bytes = garble_with(|writer| {
+            GarbledCircuitWriter::sealed(writer, AES128.and_count(), key.clone()).unwrap()
+        });
+
+        // The gates are not stored in plaintext.
+        let plain = garble();
+        assert!(!bytes
+            .windows(GATE_BYTES)
+            .any(|window| window == &plain[19..51]));
+
+        evaluate(GarbledCircuitReader::sealed(bytes.as_slice(), key).unwrap());
+
+        let err = GarbledCircuitReader::new(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, PersistError::Sealed));
+
+        let err =
+            GarbledCircuitReader::sealed(plain.as_slice(), SealingKey::new([1u8; 32])).unwrap_err();
+        assert!(matches!(err, PersistError::NotSealed));
+
+        let mut reader =
+            GarbledCircuitReader::sealed(bytes.as_slice(), SealingKey::new([2u8; 32])).unwrap();
+        let err = reader.read_gate().unwrap_err();
+        assert!(matches!(err, PersistError::Seal(SealError::Authentication)));
+    }
+
+    #[test]
+    fn test_persist_sealed_reordered() {
+        let key = SealingKey::new([1u8; 32]);
+
+        let mut writer = GarbledCircuitWriter::sealed(Vec::new(), 2, key.clone()).unwrap();
+        writer.write_gate(EncryptedGate::default()).unwrap();
+        writer.write_gate(EncryptedGate::default()).unwrap();
+        let mut bytes = writer.finish(None).unwrap();
+
+        // Swap the two chunks, which have the same length.
+        let end = bytes.len() - 33;
+        let chunks = bytes[19..end].to_vec();
+        let (first, second) = chunks.split_at(chunks.len() / 2);
+        bytes[19..end].copy_from_slice(&[second, first].concat());
+
+        let mut reader = GarbledCircuitReader::sealed(bytes.as_slice(), key).unwrap();
+        let err = reader.read_gate().unwrap_err();
+        assert!(matches!(err, PersistError::Seal(SealError::LabelMismatch { .. })));
+    }
+
+    #[test]
+    fn test_persist_garbled_circuit() {
+        let bytes = garble();
+
+        let circ = GarbledCircuit::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(circ.gates.len(), AES128.and_count());
+
+        let mut rewritten = Vec::new();
+        circ.write_to(&mut rewritten).unwrap();
+
+        assert_eq!(rewritten, bytes);
+    }
+
+    #[test]
+    fn test_persist_detects_corruption() {
+        let mut bytes = garble();
+
+        // Flip a bit in the first gate.
+        bytes[19] ^= 1;
+
+        let err = GarbledCircuit::read_from(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, PersistError::IntegrityCheck));
+    }
+
+    #[test]
+    fn test_persist_invalid_header() {
+        let mut bytes = garble();
+
+        bytes[8..10].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = GarbledCircuitReader::new(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, PersistError::UnsupportedVersion(_)));
+
+        bytes[0] ^= 1;
+        let err = GarbledCircuitReader::new(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, PersistError::InvalidMagic));
+    }
+}
@@ -1,92 +1,141 @@
+use std::sync::Arc;
+
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use mpz_circuits::circuits::AES128;
+use mpz_circuits::{
+    circuits::AES128,
+    ops::WrappingAdd,
+    types::{Value, ValueType},
+    Circuit, CircuitBuilder,
+};
 use mpz_common::executor::test_mt_executor;
-use mpz_garble::{config::Role, protocol::deap::DEAPThread, Decode, Execute, Memory};
+use mpz_garble::{
+    config::{Role, Visibility},
+    protocol::deap::DEAPThread,
+    Decode, Execute, Memory,
+};
 use mpz_ot::ideal::ot::ideal_ot;
 
-fn criterion_benchmark(c: &mut Criterion) {
-    let mut group = c.benchmark_group("deap");
-
-    let rt = tokio::runtime::Runtime::new().unwrap();
-    group.bench_function("aes", |b| {
-        b.to_async(&rt).iter(|| async {
-            let (mut leader_exec, mut follower_exec) = test_mt_executor(8);
-
-            let (leader_ot_send, follower_ot_recv) = ideal_ot();
-            let (follower_ot_send, leader_ot_recv) = ideal_ot();
+fn adder_circ() -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
 
-            let key = [0u8; 16];
-            let msg = [0u8; 16];
+    let a = builder.add_input::<u8>();
+    let b = builder.add_input::<u8>();
 
-            let leader_fut = {
-                async move {
-                    let leader_ctx = leader_exec.new_thread().await.unwrap();
+    let c = a.wrapping_add(b);
 
-                    let mut leader_vm = DEAPThread::new(
-                        Role::Leader,
-                        [42u8; 32],
-                        leader_ctx,
-                        leader_ot_send,
-                        leader_ot_recv,
-                    );
+    builder.add_output(c);
 
-                    let key_ref = leader_vm.new_private_input::<[u8; 16]>("key").unwrap();
-                    let msg_ref = leader_vm.new_private_input::<[u8; 16]>("msg").unwrap();
-                    let ciphertext_ref = leader_vm.new_output::<[u8; 16]>("ciphertext").unwrap();
-
-                    leader_vm.assign(&key_ref, key).unwrap();
-                    leader_vm.assign(&msg_ref, msg).unwrap();
+    Arc::new(builder.build().unwrap())
+}
 
-                    leader_vm
-                        .execute(
-                            AES128.clone(),
-                            &[key_ref.clone(), msg_ref],
-                            &[ciphertext_ref.clone()],
+/// Executes and decodes the circuit, with every input private to the leader.
+async fn execute(circ: Arc<Circuit>) {
+    let (mut leader_exec, mut follower_exec) = test_mt_executor(8);
+
+    let (leader_ot_send, follower_ot_recv) = ideal_ot();
+    let (follower_ot_send, leader_ot_recv) = ideal_ot();
+
+    let input_typs: Vec<ValueType> = circ
+        .inputs()
+        .iter()
+        .map(|input| input.value_type())
+        .collect();
+    let output_typ = circ.outputs()[0].value_type();
+
+    let leader_fut = {
+        let circ = circ.clone();
+        let input_typs = input_typs.clone();
+        let output_typ = output_typ.clone();
+        async move {
+            let leader_ctx = leader_exec.new_thread().await.unwrap();
+
+            let mut leader_vm = DEAPThread::new(
+                Role::Leader,
+                [42u8; 32],
+                leader_ctx,
+                leader_ot_send,
+                leader_ot_recv,
+            );
+
+            let input_refs = input_typs
+                .iter()
+                .enumerate()
+                .map(|(idx, typ)| {
+                    let input_ref = leader_vm
+                        .new_input_with_type(
+                            &format!("input/{idx}"),
+                            typ.clone(),
+                            Visibility::Private,
                         )
-                        .await
                         .unwrap();
-
-                    leader_vm.decode(&[ciphertext_ref]).await.unwrap();
-
-                    leader_vm.finalize().await.unwrap();
-                }
-            };
-
-            let follower_fut = {
-                async move {
-                    let follower_ctx = follower_exec.new_thread().await.unwrap();
-
-                    let mut follower_vm = DEAPThread::new(
-                        Role::Follower,
-                        [69u8; 32],
-                        follower_ctx,
-                        follower_ot_send,
-                        follower_ot_recv,
-                    );
-
-                    let key_ref = follower_vm.new_blind_input::<[u8; 16]>("key").unwrap();
-                    let msg_ref = follower_vm.new_blind_input::<[u8; 16]>("msg").unwrap();
-                    let ciphertext_ref = follower_vm.new_output::<[u8; 16]>("ciphertext").unwrap();
-
-                    follower_vm
-                        .execute(
-                            AES128.clone(),
-                            &[key_ref.clone(), msg_ref],
-                            &[ciphertext_ref.clone()],
-                        )
-                        .await
+                    leader_vm
+                        .assign(&input_ref, Value::random(&mut rand::thread_rng(), typ))
                         .unwrap();
+                    input_ref
+                })
+                .collect::<Vec<_>>();
+            let output_ref = leader_vm
+                .new_output_with_type("output", output_typ)
+                .unwrap();
+
+            leader_vm
+                .execute(circ, &input_refs, &[output_ref.clone()])
+                .await
+                .unwrap();
+
+            leader_vm.decode(&[output_ref]).await.unwrap();
+
+            leader_vm.finalize().await.unwrap();
+        }
+    };
+
+    let follower_fut = async move {
+        let follower_ctx = follower_exec.new_thread().await.unwrap();
+
+        let mut follower_vm = DEAPThread::new(
+            Role::Follower,
+            [69u8; 32],
+            follower_ctx,
+            follower_ot_send,
+            follower_ot_recv,
+        );
+
+        let input_refs = input_typs
+            .iter()
+            .enumerate()
+            .map(|(idx, typ)| {
+                follower_vm
+                    .new_input_with_type(&format!("input/{idx}"), typ.clone(), Visibility::Blind)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let output_ref = follower_vm
+            .new_output_with_type("output", output_typ)
+            .unwrap();
+
+        follower_vm
+            .execute(circ, &input_refs, &[output_ref.clone()])
+            .await
+            .unwrap();
+
+        follower_vm.decode(&[output_ref]).await.unwrap();
+
+        follower_vm.finalize().await.unwrap();
+    };
+
+    futures::join!(leader_fut, follower_fut);
+}
 
-                    follower_vm.decode(&[ciphertext_ref]).await.unwrap();
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deap");
 
-                    follower_vm.finalize().await.unwrap();
-                }
-            };
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    group.bench_function("aes", |b| b.to_async(&rt).iter(|| execute(AES128.clone())));
 
-            futures::join!(leader_fut, follower_fut);
-        })
-    });
+    // Small enough to take the inline garbling fast path.
+    let adder = adder_circ();
+    group.bench_function("adder", |b| b.to_async(&rt).iter(|| execute(adder.clone())));
 }
 
 criterion_group!(benches, criterion_benchmark);
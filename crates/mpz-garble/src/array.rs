@@ -0,0 +1,190 @@
+//! Reading and writing arrays at secret indices.
+//!
+//! These helpers build the circuits in [`mpz_circuits::circuits::array`] for the type of the
+//! provided array and execute them, so a lookup into a secret-indexed table is a single call.
+//! Both parties must call them with the same arguments.
+//!
+//! Indices are `u32` values. Reading an out of range index returns zero, and writing to one
+//! leaves the array unchanged.
+
+use std::sync::Arc;
+
+use mpz_circuits::{
+    circuits::array::{build_array_read, build_array_write},
+    types::ValueType,
+};
+
+use crate::{value::ValueRef, AssignmentError, Execute, Memory, MemoryError, VmError};
+
+/// Returns the element type and length of an array, checking that the index is a `u32`.
+fn array_type<T: Memory>(
+    thread: &T,
+    array: &ValueRef,
+    index: &ValueRef,
+) -> Result<(ValueType, usize), MemoryError> {
+    let ValueType::Array(elem_typ, len) = thread.get_value_type(array) else {
+        return Err(MemoryError::InvalidArray(format!(
+            "can not index into a value which is not an array: {array:?}"
+        )));
+    };
+
+    let index_typ = thread.get_value_type(index);
+    if index_typ != ValueType::U32 {
+        Err(AssignmentError::Type {
+            value: index.clone(),
+            expected: ValueType::U32,
+            actual: index_typ,
+        })?
+    }
+
+    Ok((*elem_typ, len))
+}
+
+/// Reads an element of an array at a secret index.
+///
+/// Returns the output value holding the element.
+///
+/// # Arguments
+///
+/// * `thread` - The thread to execute the circuit with.
+/// * `id` - The id of the output value.
+/// * `array` - The array to read from.
+/// * `index` - The `u32` index to read.
+pub async fn read<T>(
+    thread: &mut T,
+    id: &str,
+    array: &ValueRef,
+    index: &ValueRef,
+) -> Result<ValueRef, VmError>
+where
+    T: Memory + Execute + Send,
+{
+    let (elem_typ, len) = array_type(thread, array, index)?;
+
+    let output = thread.new_output_with_type(id, elem_typ.clone())?;
+    let circ = Arc::new(build_array_read(elem_typ, len));
+
+    thread
+        .execute(circ, &[array.clone(), index.clone()], &[output.clone()])
+        .await?;
+
+    Ok(output)
+}
+
+/// Writes an element of an array at a secret index.
+///
+/// Returns the output value holding the updated array.
+///
+/// # Arguments
+///
+/// * `thread` - The thread to execute the circuit with.
+/// * `id` - The id of the output value.
+/// * `array` - The array to write to.
+/// * `index` - The `u32` index to write.
+/// * `value` - The value to write, which must have the type of the array elements.
+pub async fn write<T>(
+    thread: &mut T,
+    id: &str,
+    array: &ValueRef,
+    index: &ValueRef,
+    value: &ValueRef,
+) -> Result<ValueRef, VmError>
+where
+    T: Memory + Execute + Send,
+{
+    let (elem_typ, len) = array_type(thread, array, index)?;
+
+    let value_typ = thread.get_value_type(value);
+    if value_typ != elem_typ {
+        Err(MemoryError::from(AssignmentError::Type {
+            value: value.clone(),
+            expected: elem_typ.clone(),
+            actual: value_typ,
+        }))?
+    }
+
+    let output = thread.new_output_with_type(id, thread.get_value_type(array))?;
+    let circ = Arc::new(build_array_write(elem_typ, len));
+
+    thread
+        .execute(
+            circ,
+            &[array.clone(), index.clone(), value.clone()],
+            &[output.clone()],
+        )
+        .await?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::types::Value;
+
+    use crate::{ideal::ideal_vm, Decode};
+
+    use super::*;
+
+    const TABLE: [u8; 6] = [2, 3, 5, 7, 11, 13];
+
+    #[tokio::test]
+    async fn test_array_read_write() {
+        let (mut leader, mut follower) = ideal_vm();
+
+        let leader_fut = async {
+            let table = leader.new_private_array_input::<u8>("table", 6).unwrap();
+            let index = leader.new_blind_input::<u32>("index").unwrap();
+            let value = leader.new_private_input::<u8>("value").unwrap();
+
+            leader.assign(&table, TABLE).unwrap();
+            leader.assign(&value, 17u8).unwrap();
+
+            let elem = read(&mut leader, "elem", &table, &index).await.unwrap();
+            let table = write(&mut leader, "table/1", &table, &index, &value)
+                .await
+                .unwrap();
+
+            leader.decode(&[elem, table]).await.unwrap()
+        };
+
+        let follower_fut = async {
+            let table = follower.new_blind_array_input::<u8>("table", 6).unwrap();
+            let index = follower.new_private_input::<u32>("index").unwrap();
+            let value = follower.new_blind_input::<u8>("value").unwrap();
+
+            follower.assign(&index, 4u32).unwrap();
+
+            let elem = read(&mut follower, "elem", &table, &index).await.unwrap();
+            let table = write(&mut follower, "table/1", &table, &index, &value)
+                .await
+                .unwrap();
+
+            follower.decode(&[elem, table]).await.unwrap()
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        let mut expected = TABLE;
+        expected[4] = 17;
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(
+            leader_output,
+            vec![Value::from(11u8), Value::from(expected)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_array_read_invalid_index() {
+        let (mut leader, _) = ideal_vm();
+
+        let table = leader.new_private_array_input::<u8>("table", 6).unwrap();
+        let index = leader.new_private_input::<u8>("index").unwrap();
+
+        let err = read(&mut leader, "elem", &table, &index).await.unwrap_err();
+        assert!(matches!(
+            err,
+            VmError::MemoryError(MemoryError::Assignment(AssignmentError::Type { .. }))
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+//! Garbled circuit artifacts.
+//!
+//! An artifact is a garbled circuit which the generator garbles ahead of time, without a
+//! connection to the evaluator. It can be transferred out-of-band, e.g. as a file or from a CDN,
+//! and registered with the evaluator later in the session instead of streaming the circuit with
+//! [`Load`](crate::Load).
+//!
+//! # Integrity
+//!
+//! An artifact is not trusted on its own. When the generator releases an artifact it sends a
+//! digest of it over the session channel, which binds the header, the encrypted gates, the output
+//! encoding commitments, and the references of the circuit inputs and outputs. The evaluator only
+//! accepts the artifact if it matches this digest and the circuit it is registered for.
+
+use std::io::{Read, Write};
+
+use mpz_circuits::Circuit;
+use mpz_core::hash::{Hash, SecureHash};
+use mpz_garble_core::{GarbledCircuit, PersistError};
+use serde::{Deserialize, Serialize};
+
+use crate::value::{CircuitRefs, ValueRef};
+
+/// Errors that can occur when reading or writing a garbled circuit artifact.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ArtifactError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    PersistError(#[from] PersistError),
+    #[error("header value is too large: {0}")]
+    HeaderOverflow(u64),
+}
+
+/// Metadata of a garbled circuit artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactHeader {
+    /// The number of circuit inputs.
+    pub input_count: usize,
+    /// The number of circuit outputs.
+    pub output_count: usize,
+    /// The number of AND gates of the circuit.
+    pub and_count: usize,
+}
+
+impl ArtifactHeader {
+    /// Creates the header for a circuit.
+    pub fn new(circ: &Circuit) -> Self {
+        Self {
+            input_count: circ.inputs().len(),
+            output_count: circ.outputs().len(),
+            and_count: circ.and_count(),
+        }
+    }
+}
+
+/// A garbled circuit sealed for out-of-band transfer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GarbledArtifact {
+    header: ArtifactHeader,
+    circuit: GarbledCircuit,
+}
+
+impl GarbledArtifact {
+    pub(crate) fn new(header: ArtifactHeader, circuit: GarbledCircuit) -> Self {
+        Self { header, circuit }
+    }
+
+    /// Returns the header of the artifact.
+    pub fn header(&self) -> &ArtifactHeader {
+        &self.header
+    }
+
+    /// Returns the garbled circuit.
+    pub fn circuit(&self) -> &GarbledCircuit {
+        &self.circuit
+    }
+
+    /// Writes the artifact.
+    ///
+    /// The header is written as little-endian integers, followed by the garbled circuit in the
+    /// [persistence format](mpz_garble_core::persist).
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), ArtifactError> {
+        for value in [
+            self.header.input_count,
+            self.header.output_count,
+            self.header.and_count,
+        ] {
+            writer.write_all(&(value as u64).to_le_bytes())?;
+        }
+
+        self.circuit.write_to(writer)?;
+
+        Ok(())
+    }
+
+    /// Reads an artifact written with [`GarbledArtifact::write_to`].
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, ArtifactError> {
+        let mut read_usize = || -> Result<usize, ArtifactError> {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            let value = u64::from_le_bytes(bytes);
+            usize::try_from(value).map_err(|_| ArtifactError::HeaderOverflow(value))
+        };
+
+        let header = ArtifactHeader {
+            input_count: read_usize()?,
+            output_count: read_usize()?,
+            and_count: read_usize()?,
+        };
+
+        let circuit = GarbledCircuit::read_from(reader)?;
+
+        Ok(Self { header, circuit })
+    }
+
+    /// Computes the digest of the artifact for the provided circuit references.
+    pub(crate) fn digest(&self, refs: &CircuitRefs) -> Hash {
+        #[derive(Serialize)]
+        struct Digest<'a> {
+            inputs: Vec<Vec<&'a str>>,
+            outputs: Vec<Vec<&'a str>>,
+            header: &'a ArtifactHeader,
+            circuit: &'a GarbledCircuit,
+        }
+
+        fn ids(values: &[ValueRef]) -> Vec<Vec<&str>> {
+            values
+                .iter()
+                .map(|value| value.iter().map(|id| id.as_ref()).collect())
+                .collect()
+        }
+
+        Digest {
+            inputs: ids(&refs.inputs),
+            outputs: ids(&refs.outputs),
+            header: &self.header,
+            circuit: &self.circuit,
+        }
+        .hash()
+    }
+
+    pub(crate) fn into_circuit(self) -> GarbledCircuit {
+        self.circuit
+    }
+}
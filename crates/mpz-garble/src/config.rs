@@ -28,4 +28,22 @@ pub enum Visibility {
     Private,
     /// A private value not known to this party.
     Blind,
+    /// A private value which is XOR-shared between the parties, each assigning its own share.
+    Shared,
+}
+
+/// Policy for transferring the encoding of a private or blind input value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferPolicy {
+    /// The evaluator receives the encoding obliviously, without revealing the value.
+    #[default]
+    Oblivious,
+    /// The evaluator sends the plaintext value to the generator, which replies with
+    /// the active encoding directly.
+    ///
+    /// # Warning
+    ///
+    /// This reveals the value to the generator, and should only be used for values which
+    /// the generator is trusted to learn. Both parties must agree on the policy of a value.
+    Direct,
 }
@@ -1,5 +1,18 @@
+use std::time::Duration;
+
 use derive_builder::Builder;
 
+/// Default maximum number of AND gates re-garbled in a single chunk during verification.
+const DEFAULT_VERIFY_CHUNK_SIZE: usize = 1 << 20;
+/// Default maximum number of array elements decoded or verified in a single chunk.
+const DEFAULT_DECODE_CHUNK_SIZE: usize = 1 << 12;
+/// Default maximum number of chunks decoded or verified concurrently.
+const DEFAULT_MAX_IN_FLIGHT_CHUNKS: usize = 4;
+/// Default maximum duration to wait for the next frame of a garbled circuit stream.
+pub(crate) const DEFAULT_GARBLE_STREAM_TIMEOUT: Duration = Duration::from_secs(60);
+/// Default maximum duration to wait for decoding information from the generator.
+pub(crate) const DEFAULT_DECODE_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Evaluator configuration.
 #[derive(Debug, Clone, Builder)]
 pub struct EvaluatorConfig {
@@ -12,6 +25,37 @@ pub struct EvaluatorConfig {
     /// Whether to log decodings.
     #[builder(default = "false", setter(custom))]
     pub(crate) log_decodings: bool,
+    /// Whether to verify that the generator's batch padding is canonical.
+    #[builder(default = "false", setter(custom))]
+    pub(crate) strict_padding: bool,
+    /// The maximum number of AND gates to re-garble in a single chunk during verification.
+    ///
+    /// Verification yields to the executor in between chunks.
+    #[builder(default = "DEFAULT_VERIFY_CHUNK_SIZE")]
+    pub(crate) verify_chunk_size: usize,
+    /// The maximum number of array elements to decode, or verify against an encoding commitment,
+    /// in a single chunk.
+    ///
+    /// Arrays larger than this are split into chunks which are processed in parallel on the CPU
+    /// backend.
+    #[builder(default = "DEFAULT_DECODE_CHUNK_SIZE")]
+    pub(crate) decode_chunk_size: usize,
+    /// The maximum number of chunks to decode, or verify against encoding commitments,
+    /// concurrently.
+    ///
+    /// Chunks are processed in order, so this bounds the memory held by chunks which are in
+    /// flight.
+    #[builder(default = "DEFAULT_MAX_IN_FLIGHT_CHUNKS")]
+    pub(crate) max_in_flight_chunks: usize,
+    /// The maximum duration to wait for the next frame of a garbled circuit stream.
+    ///
+    /// The stream can not be resynchronized after a timeout, so the evaluator should not be
+    /// used to receive further circuits from the same peer.
+    #[builder(default = "DEFAULT_GARBLE_STREAM_TIMEOUT")]
+    pub(crate) garble_stream_timeout: Duration,
+    /// The maximum duration to wait for decoding information from the generator.
+    #[builder(default = "DEFAULT_DECODE_TIMEOUT")]
+    pub(crate) decode_timeout: Duration,
 }
 
 impl EvaluatorConfig {
@@ -39,4 +83,13 @@ impl EvaluatorConfigBuilder {
         self.log_decodings = Some(true);
         self
     }
+
+    /// Enable verification of batch padding.
+    ///
+    /// Any encrypted gates received after the last gate of the circuit must be zero. With this
+    /// enabled, any other padding is rejected so that it can not be used as a covert channel.
+    pub fn strict_padding(&mut self) -> &mut Self {
+        self.strict_padding = Some(true);
+        self
+    }
 }
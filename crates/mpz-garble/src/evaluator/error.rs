@@ -10,6 +10,10 @@ pub enum EvaluatorError {
     IOError(#[from] std::io::Error),
     #[error("context error: {0}")]
     ContextError(#[from] mpz_common::ContextError),
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
     // TODO: Fix the size of this error
     #[error(transparent)]
     OTError(Box<mpz_ot::OTError>),
@@ -25,10 +29,28 @@ pub enum EvaluatorError {
     MissingEncoding(ValueRef),
     #[error("duplicate garbled circuit")]
     DuplicateCircuit,
+    #[error("generator aborted the garbled circuit")]
+    Aborted,
+    #[error("garbled circuit contains more encrypted gates than expected")]
+    UnexpectedGates,
+    #[error("unexpected frame in garbled circuit stream")]
+    UnexpectedFrame,
+    #[error("invalid garbled artifact: {0}")]
+    InvalidArtifact(String),
     #[error("duplicate decoding for value: {0:?}")]
     DuplicateDecoding(ValueId),
+    #[error("duplicate decoding digest for value: {0:?}")]
+    DuplicateDecodingDigest(ValueRef),
+    #[error("missing decoding digest for value: {0:?}")]
+    MissingDecodingDigest(ValueRef),
+    #[error("decoding does not match digest for value: {0:?}")]
+    InvalidDecodingDigest(ValueRef),
+    #[error("values have incompatible types: {0:?} and {1:?}")]
+    IncompatibleValues(ValueRef, ValueRef),
     #[error(transparent)]
     VerificationError(#[from] VerificationError),
+    #[error(transparent)]
+    Poisoned(#[from] mpz_common::poison::PoisonedError),
 }
 
 #[derive(Debug, thiserror::Error)]
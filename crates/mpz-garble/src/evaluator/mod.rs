@@ -4,34 +4,51 @@ mod config;
 mod error;
 
 use std::{
-    collections::{HashMap, HashSet},
-    mem,
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    iter, mem,
     ops::DerefMut,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use mpz_circuits::{
     types::{TypeError, Value, ValueType},
     Circuit,
 };
-use mpz_common::{cpu::CpuBackend, executor::DummyExecutor, scoped, Context};
-use mpz_core::hash::Hash;
+use mpz_common::{
+    cancel::CancellationToken,
+    cpu::CpuBackend,
+    poison::PoisonFlag,
+    scoped,
+    timeout::{timeout, Phase},
+    Context, Poison,
+};
+use mpz_core::{commit::Decommitment, hash::Hash};
 use mpz_garble_core::{
-    encoding_state, Decoding, EncodedValue, EncodingCommitment, EncryptedGateBatch,
-    Evaluator as EvaluatorCore, EvaluatorOutput, GarbledCircuit,
+    encoding_state, is_canonical_padding, Decoding, Delta, EncodedValue, EncodingCommitment,
+    Evaluator as EvaluatorCore, EvaluatorError as CoreEvaluatorError, EvaluatorOutput,
+    GarbledCircuit, Generator as GeneratorCore, GeneratorError as GeneratorCoreError,
+    GeneratorOutput, Label,
 };
 use mpz_ot::TransferId;
-use serio::stream::IoStreamExt;
+use serio::{stream::IoStreamExt, IoStream, SinkExt};
 use utils::iter::FilterDrain;
 
 use crate::{
-    memory::EncodingMemory,
+    artifact::{ArtifactHeader, GarbledArtifact},
+    frame::{is_small_circuit, next_frame, resync, CircuitFrame},
+    generator::GeneratorError,
+    memory::{EncodingMemory, EncodingMemoryError},
     ot::{EncodingReceiverOutput, OTReceiveEncoding, OTVerifyEncoding},
+    rerandomize::{translate, TranslationRow},
     value::{CircuitRefs, ValueId, ValueRef},
     AssignedValues, Generator, GeneratorConfigBuilder,
 };
 
 pub use config::{EvaluatorConfig, EvaluatorConfigBuilder};
+pub(crate) use config::{DEFAULT_DECODE_TIMEOUT, DEFAULT_GARBLE_STREAM_TIMEOUT};
 pub use error::EvaluatorError;
 
 use error::VerificationError;
@@ -41,6 +58,8 @@ use error::VerificationError;
 pub struct Evaluator {
     config: EvaluatorConfig,
     state: Mutex<State>,
+    /// Poisoned if an operation is dropped before it completes.
+    poison: PoisonFlag,
 }
 
 impl Default for Evaluator {
@@ -48,6 +67,7 @@ impl Default for Evaluator {
         Self {
             config: EvaluatorConfigBuilder::default().build().unwrap(),
             state: Mutex::new(State::default()),
+            poison: PoisonFlag::new(),
         }
     }
 }
@@ -57,7 +77,9 @@ struct State {
     /// Encodings of values
     memory: EncodingMemory<encoding_state::Active>,
     /// Encoded values which were received either directly or via OT
-    received_values: HashMap<ValueId, ValueType>,
+    ///
+    /// Ordered so that verification is deterministic.
+    received_values: BTreeMap<ValueId, ValueType>,
     /// Values which have been decoded
     decoded_values: HashSet<ValueId>,
     /// Pre-transferred garbled circuits
@@ -65,11 +87,17 @@ struct State {
     /// A map used to look up a garbled circuit by its unique (inputs, outputs) reference.
     garbled_circuits: HashMap<CircuitRefs, GarbledCircuit>,
     /// OT logs
-    ot_log: HashMap<TransferId, Vec<ValueId>>,
+    ///
+    /// Ordered by transfer id, so the OTs are verified in the order they were performed.
+    ot_log: BTreeMap<TransferId, Vec<ValueId>>,
     /// Garbled circuit logs
     circuit_logs: Vec<EvaluatorLog>,
     /// Decodings of values received from the generator
-    decoding_logs: HashMap<ValueRef, Decoding>,
+    decoding_logs: BTreeMap<ValueRef, Decoding>,
+    /// Digests of decoding information committed to by the generator
+    decoding_digests: HashMap<ValueRef, Hash>,
+    /// Values which were linked to the encoding of another value, in the order they were linked
+    links: Vec<(ValueRef, ValueRef)>,
 }
 
 impl Evaluator {
@@ -86,6 +114,21 @@ impl Evaluator {
         self.state.lock().unwrap()
     }
 
+    /// Runs an operation, poisoning the evaluator if it is dropped before it completes.
+    ///
+    /// A dropped operation may have consumed part of a garbled circuit or a decoding from the
+    /// connection, so the evaluator can not be used again.
+    async fn guarded<T>(
+        &self,
+        fut: impl Future<Output = Result<T, EvaluatorError>>,
+    ) -> Result<T, EvaluatorError> {
+        let guard = self.poison.guard()?;
+        let output = fut.await;
+        guard.disarm();
+
+        output
+    }
+
     /// Sets a value as decoded.
     ///
     /// # Errors
@@ -146,9 +189,19 @@ impl Evaluator {
         ctx: &mut Ctx,
         values: &AssignedValues,
         ot: &mut OT,
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._setup_assigned_values(ctx, values, ot))
+            .await
+    }
+
+    async fn _setup_assigned_values<Ctx: Context, OT: OTReceiveEncoding<Ctx> + Send>(
+        &self,
+        ctx: &mut Ctx,
+        values: &AssignedValues,
+        ot: &mut OT,
     ) -> Result<(), EvaluatorError> {
         // Filter out any values that are already active.
-        let (mut ot_recv_values, mut direct_recv_values) = {
+        let (mut ot_recv_values, mut direct_recv_values, mut requested_values) = {
             let state = self.state();
             let ot_recv_values = values
                 .private
@@ -161,18 +214,28 @@ impl Evaluator {
                 .iter()
                 .map(|(id, value)| (id.clone(), value.value_type()))
                 .chain(values.blind.clone())
+                .chain(values.blind_direct.clone())
                 .filter(|(id, _)| !state.memory.contains(id))
                 .collect::<Vec<_>>();
+            let requested_values = values
+                .private_direct
+                .iter()
+                .filter(|(id, _)| !state.memory.contains(id))
+                .cloned()
+                .collect::<Vec<_>>();
 
-            (ot_recv_values, direct_recv_values)
+            (ot_recv_values, direct_recv_values, requested_values)
         };
 
         ot_recv_values.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
         direct_recv_values.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+        requested_values.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
 
         ctx.try_join(
             scoped!(|ctx| async move {
                 self.direct_receive_active_encodings(ctx, &direct_recv_values)
+                    .await?;
+                self.direct_request_active_encodings(ctx, &requested_values)
                     .await
             }),
             scoped!(|ctx| async move {
@@ -197,6 +260,16 @@ impl Evaluator {
         ctx: &mut Ctx,
         values: &[(ValueId, Value)],
         ot: &mut OT,
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._ot_receive_active_encodings(ctx, values, ot))
+            .await
+    }
+
+    async fn _ot_receive_active_encodings<Ctx: Context, OT: OTReceiveEncoding<Ctx>>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[(ValueId, Value)],
+        ot: &mut OT,
     ) -> Result<(), EvaluatorError> {
         if values.is_empty() {
             return Ok(());
@@ -252,6 +325,15 @@ impl Evaluator {
         &self,
         ctx: &mut Ctx,
         values: &[(ValueId, ValueType)],
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._direct_receive_active_encodings(ctx, values))
+            .await
+    }
+
+    async fn _direct_receive_active_encodings<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[(ValueId, ValueType)],
     ) -> Result<(), EvaluatorError> {
         if values.is_empty() {
             return Ok(());
@@ -287,6 +369,35 @@ impl Evaluator {
         Ok(())
     }
 
+    /// Sends the provided private values to the generator in plaintext, and directly receives
+    /// their active encodings.
+    ///
+    /// This is used for values with a [`TransferPolicy::Direct`](crate::config::TransferPolicy)
+    /// policy.
+    ///
+    /// # Arguments
+    /// - `values` - The values to request, sorted by ID
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub(crate) async fn direct_request_active_encodings<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[(ValueId, Value)],
+    ) -> Result<(), EvaluatorError> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let (ids, plaintexts): (Vec<ValueId>, Vec<Value>) = values.iter().cloned().unzip();
+        let expected = ids
+            .into_iter()
+            .zip(plaintexts.iter().map(|value| value.value_type()))
+            .collect::<Vec<_>>();
+
+        ctx.io_mut().send(plaintexts).await?;
+
+        self.direct_receive_active_encodings(ctx, &expected).await
+    }
+
     /// Receives a garbled circuit from the generator, storing it for later evaluation.
     ///
     /// # Arguments
@@ -302,6 +413,17 @@ impl Evaluator {
         circ: Arc<Circuit>,
         inputs: &[ValueRef],
         outputs: &[ValueRef],
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._receive_garbled_circuit(ctx, circ, inputs, outputs))
+            .await
+    }
+
+    async fn _receive_garbled_circuit<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
     ) -> Result<(), EvaluatorError> {
         let refs = CircuitRefs {
             inputs: inputs.to_vec(),
@@ -309,23 +431,52 @@ impl Evaluator {
         };
 
         if self.state().garbled_circuits.contains_key(&refs) {
+            resync(ctx.io_mut()).await?;
             return Err(EvaluatorError::DuplicateCircuit);
         }
 
         let gate_count = circ.and_count();
         let mut gates = Vec::with_capacity(gate_count);
 
-        while gates.len() < gate_count {
-            let batch: EncryptedGateBatch = ctx.io_mut().expect_next().await?;
-            gates.extend_from_slice(&batch.into_array());
+        let stream_timeout = self.config.garble_stream_timeout;
+        let cancel = ctx.cancel_token().clone();
+        let commitments = loop {
+            let end = is_small_circuit(&circ) || gates.len() >= gate_count;
+            match stream_frame(ctx.io_mut(), end, stream_timeout, &cancel).await? {
+                CircuitFrame::Batch(_) if gates.len() >= gate_count => {
+                    resync(ctx.io_mut()).await?;
+                    return Err(EvaluatorError::UnexpectedGates);
+                }
+                CircuitFrame::Batch(batch) => gates.extend(batch.into_vec()),
+                CircuitFrame::End(commitments) => break commitments,
+                CircuitFrame::Complete(batch, commitments) => {
+                    gates.extend(batch.into_vec());
+                    break commitments;
+                }
+                CircuitFrame::Abort => return Err(EvaluatorError::Aborted),
+                CircuitFrame::Decodings(_) => {
+                    resync(ctx.io_mut()).await?;
+                    return Err(EvaluatorError::UnexpectedFrame);
+                }
+            }
+        };
+
+        if gates.len() < gate_count {
+            return Err(CoreEvaluatorError::IncorrectGateCount {
+                expected: gate_count,
+                actual: gates.len(),
+            })?;
         }
 
-        // Trim off any batch padding.
+        // Trim off any padding after the last gate.
+        if self.config.strict_padding && !is_canonical_padding(&gates[gate_count..]) {
+            return Err(CoreEvaluatorError::InvalidPadding)?;
+        }
         gates.truncate(gate_count);
 
         // If configured, expect the output encoding commitments
         let encoding_commitments = if self.config.encoding_commitments {
-            let commitments: Vec<EncodingCommitment> = ctx.io_mut().expect_next().await?;
+            let commitments = commitments.unwrap_or_default();
 
             // Make sure the generator sent the expected number of commitments.
             if commitments.len() != circ.outputs().len() {
@@ -351,6 +502,95 @@ impl Evaluator {
         Ok(())
     }
 
+    /// Registers a garbled artifact which was transferred out-of-band, storing it for later
+    /// evaluation.
+    ///
+    /// The artifact is only accepted if it matches the digest released by the generator with
+    /// [`Generator::release_artifact`] and the provided circuit. See [`crate::artifact`] for
+    /// details.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit of the artifact
+    /// * `inputs` - The inputs to the circuit
+    /// * `outputs` - The outputs from the circuit
+    /// * `artifact` - The garbled artifact
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub async fn register_artifact<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        artifact: GarbledArtifact,
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._register_artifact(ctx, circ, inputs, outputs, artifact))
+            .await
+    }
+
+    async fn _register_artifact<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        artifact: GarbledArtifact,
+    ) -> Result<(), EvaluatorError> {
+        let refs = CircuitRefs {
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+        };
+
+        // Always receive the digest to keep the stream in sync.
+        let digest: Hash = ctx.io_mut().expect_next().await?;
+
+        if self.state().garbled_circuits.contains_key(&refs) {
+            return Err(EvaluatorError::DuplicateCircuit);
+        }
+
+        if artifact.header() != &ArtifactHeader::new(&circ) {
+            return Err(EvaluatorError::InvalidArtifact(
+                "header does not match the circuit".to_string(),
+            ));
+        }
+
+        if artifact.digest(&refs) != digest {
+            return Err(EvaluatorError::InvalidArtifact(
+                "digest does not match the generator's".to_string(),
+            ));
+        }
+
+        let GarbledCircuit { gates, commitments } = artifact.into_circuit();
+
+        if gates.len() != circ.and_count() {
+            return Err(CoreEvaluatorError::IncorrectGateCount {
+                expected: circ.and_count(),
+                actual: gates.len(),
+            })?;
+        }
+
+        let commitments = if self.config.encoding_commitments {
+            let commitments = commitments.unwrap_or_default();
+
+            if commitments.len() != circ.outputs().len() {
+                return Err(EvaluatorError::IncorrectValueCount {
+                    expected: circ.outputs().len(),
+                    actual: commitments.len(),
+                });
+            }
+
+            Some(commitments)
+        } else {
+            None
+        };
+
+        self.state()
+            .garbled_circuits
+            .insert(refs, GarbledCircuit { gates, commitments });
+
+        Ok(())
+    }
+
     /// Evaluate a circuit.
     ///
     /// Returns the encoded outputs of the evaluated circuit.
@@ -368,6 +608,17 @@ impl Evaluator {
         circ: Arc<Circuit>,
         inputs: &[ValueRef],
         outputs: &[ValueRef],
+    ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
+        self.guarded(self._evaluate(ctx, circ, inputs, outputs))
+            .await
+    }
+
+    async fn _evaluate<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
     ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
         let refs = CircuitRefs {
             inputs: inputs.to_vec(),
@@ -384,20 +635,34 @@ impl Evaluator {
                         .get_encoding(value_ref)
                         .ok_or_else(|| EvaluatorError::MissingEncoding(value_ref.clone()))
                 })
-                .collect::<Result<Vec<_>, _>>()?
+                .collect::<Result<Vec<_>, _>>()
         };
 
         let existing_garbled_circuit = self.state().garbled_circuits.remove(&refs);
 
+        // If the garbled circuit is being streamed, discard it to keep the stream in sync.
+        let encoded_inputs = match encoded_inputs {
+            Ok(encoded_inputs) => encoded_inputs,
+            Err(err) => {
+                if existing_garbled_circuit.is_none() {
+                    resync(ctx.io_mut()).await?;
+                }
+                return Err(err);
+            }
+        };
+
         // If we've already received the garbled circuit, we evaluate it, otherwise we stream the encrypted gates
         // from the generator.
-        let EvaluatorOutput {
-            outputs: encoded_outputs,
-            hash,
-        } = if let Some(GarbledCircuit { gates, commitments }) = existing_garbled_circuit {
+        let (
+            EvaluatorOutput {
+                outputs: encoded_outputs,
+                hash,
+            },
+            digests,
+        ) = if let Some(GarbledCircuit { gates, commitments }) = existing_garbled_circuit {
             let circ = circ.clone();
             let hash = self.config.log_circuits;
-            let output = CpuBackend::blocking(move || {
+            let mut output = CpuBackend::blocking(move || {
                 let mut ev = EvaluatorCore::default();
                 let mut ev_consumer = ev.evaluate(&circ, encoded_inputs)?;
 
@@ -414,57 +679,205 @@ impl Evaluator {
             .await?;
 
             if self.config.encoding_commitments {
-                for (output, commitment) in output
-                    .outputs
-                    .iter()
-                    .zip(commitments.expect("commitments were checked to be present"))
-                {
-                    commitment.verify(output)?;
-                }
+                output.outputs = verify_commitments(
+                    mem::take(&mut output.outputs),
+                    commitments.expect("commitments were checked to be present"),
+                    self.config.decode_chunk_size,
+                    self.config.max_in_flight_chunks,
+                )
+                .await?;
             }
 
-            output
+            (output, Vec::new())
         } else {
-            let circ = circ.clone();
-            let hash = self.config.log_circuits;
-            let output = ctx
-                .blocking(scoped!(move |ctx| async move {
-                    let mut ev = EvaluatorCore::default();
-                    let mut ev_consumer = ev.evaluate_batched(&circ, encoded_inputs)?;
-                    let io = ctx.io_mut();
-
-                    if hash {
-                        ev_consumer.enable_hasher();
-                    }
+            self.evaluate_streamed(ctx, circ.clone(), encoded_inputs, None)
+                .await?
+        };
 
-                    while ev_consumer.wants_gates() {
-                        let batch: EncryptedGateBatch = io.expect_next().await?;
-                        ev_consumer.next(batch);
-                    }
+        self.store_outputs(inputs, outputs, circ, &encoded_outputs, hash, digests, None)?;
 
-                    ev_consumer.finish().map_err(EvaluatorError::from)
-                }))
-                .await??;
+        Ok(encoded_outputs)
+    }
 
-            if self.config.encoding_commitments {
-                let commitments: Vec<EncodingCommitment> = ctx.io_mut().expect_next().await?;
-
-                // Make sure the generator sent the expected number of commitments.
-                if commitments.len() != output.outputs.len() {
-                    return Err(EvaluatorError::IncorrectValueCount {
-                        expected: output.outputs.len(),
-                        actual: commitments.len(),
-                    });
-                }
+    /// Evaluate a privacy-free garbled circuit.
+    ///
+    /// The generator must call [`Generator::generate_privacy_free`], which reveals the values of
+    /// the inputs to the evaluator.
+    ///
+    /// Returns the encoded outputs of the evaluated circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs from the circuit.
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all, err)]
+    pub async fn evaluate_privacy_free<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
+        self.guarded(self._evaluate_privacy_free(ctx, circ, inputs, outputs))
+            .await
+    }
 
-                for (output, commitment) in output.outputs.iter().zip(commitments) {
-                    commitment.verify(output)?;
-                }
+    async fn _evaluate_privacy_free<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
+        let encoded_inputs = match self.get_encodings(inputs) {
+            Ok(encoded_inputs) => encoded_inputs,
+            Err(err) => {
+                resync(ctx.io_mut()).await?;
+                return Err(err);
+            }
+        };
+
+        let stream_timeout = self.config.garble_stream_timeout;
+        let cancel = ctx.cancel_token().clone();
+        let decodings = match stream_frame(ctx.io_mut(), false, stream_timeout, &cancel).await? {
+            CircuitFrame::Decodings(decodings) => decodings,
+            CircuitFrame::Abort => return Err(EvaluatorError::Aborted),
+            CircuitFrame::End(_) | CircuitFrame::Complete(..) => {
+                return Err(EvaluatorError::UnexpectedFrame)
+            }
+            CircuitFrame::Batch(_) => {
+                resync(ctx.io_mut()).await?;
+                return Err(EvaluatorError::UnexpectedFrame);
             }
+        };
+
+        // Decode the inputs, which the evaluator needs to know in privacy-free mode.
+        let values = if decodings.len() != encoded_inputs.len() {
+            Err(EvaluatorError::IncorrectValueCount {
+                expected: encoded_inputs.len(),
+                actual: decodings.len(),
+            })
+        } else {
+            encoded_inputs
+                .iter()
+                .zip(&decodings)
+                .map(|(encoding, decoding)| encoding.decode(decoding).map_err(EvaluatorError::from))
+                .collect::<Result<Vec<_>, _>>()
+        };
 
-            output
+        let values = match values {
+            Ok(values) => values,
+            Err(err) => {
+                resync(ctx.io_mut()).await?;
+                return Err(err);
+            }
+        };
+
+        let (
+            EvaluatorOutput {
+                outputs: encoded_outputs,
+                hash,
+            },
+            digests,
+        ) = self
+            .evaluate_streamed(ctx, circ.clone(), encoded_inputs, Some(values))
+            .await?;
+
+        self.store_outputs(
+            inputs,
+            outputs,
+            circ,
+            &encoded_outputs,
+            hash,
+            digests,
+            Some(decodings),
+        )?;
+
+        Ok(encoded_outputs)
+    }
+
+    /// Evaluates a garbled circuit which is streamed from the generator.
+    ///
+    /// If `values` are provided, the circuit is evaluated in privacy-free mode. Returns the
+    /// output of the evaluator and the digests of the frames, see [`evaluate_frames`].
+    async fn evaluate_streamed<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        encoded_inputs: Vec<EncodedValue<encoding_state::Active>>,
+        values: Option<Vec<Value>>,
+    ) -> Result<(EvaluatorOutput, Vec<(usize, Hash)>), EvaluatorError> {
+        let hash = self.config.log_circuits;
+        let strict_padding = self.config.strict_padding;
+        let stream_timeout = self.config.garble_stream_timeout;
+        let cancel = ctx.cancel_token().clone();
+        let (mut output, commitments, digests) = if is_small_circuit(&circ) {
+            // Small circuits are evaluated inline, handing them to the worker thread would cost
+            // more than evaluating them.
+            evaluate_frames(
+                ctx.io_mut(),
+                &circ,
+                encoded_inputs,
+                values,
+                hash,
+                strict_padding,
+                stream_timeout,
+                &cancel,
+            )
+            .await?
+        } else {
+            ctx.blocking(scoped!(move |ctx| async move {
+                evaluate_frames(
+                    ctx.io_mut(),
+                    &circ,
+                    encoded_inputs,
+                    values,
+                    hash,
+                    strict_padding,
+                    stream_timeout,
+                    &cancel,
+                )
+                .await
+            }))
+            .await??
         };
 
+        if self.config.encoding_commitments {
+            let commitments = commitments.unwrap_or_default();
+
+            // Make sure the generator sent the expected number of commitments.
+            if commitments.len() != output.outputs.len() {
+                return Err(EvaluatorError::IncorrectValueCount {
+                    expected: output.outputs.len(),
+                    actual: commitments.len(),
+                });
+            }
+
+            output.outputs = verify_commitments(
+                mem::take(&mut output.outputs),
+                commitments,
+                self.config.decode_chunk_size,
+                self.config.max_in_flight_chunks,
+            )
+            .await?;
+        }
+
+        Ok((output, digests))
+    }
+
+    /// Adds the output encodings to the memory, and logs the circuit evaluation if configured.
+    #[allow(clippy::too_many_arguments)]
+    fn store_outputs(
+        &self,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        circ: Arc<Circuit>,
+        encoded_outputs: &[EncodedValue<encoding_state::Active>],
+        hash: Option<Hash>,
+        digests: Vec<(usize, Hash)>,
+        input_decodings: Option<Vec<Decoding>>,
+    ) -> Result<(), EvaluatorError> {
         // Add the output encodings to the memory.
         let mut state = self.state();
         for (output, encoding) in outputs.iter().zip(encoded_outputs.iter()) {
@@ -479,10 +892,12 @@ impl Evaluator {
                 outputs.to_vec(),
                 circ,
                 hash,
+                digests,
+                input_decodings,
             ));
         }
 
-        Ok(encoded_outputs)
+        Ok(())
     }
 
     /// Receive decoding information for a set of values from the generator
@@ -497,8 +912,346 @@ impl Evaluator {
         ctx: &mut Ctx,
         values: &[ValueRef],
     ) -> Result<Vec<Value>, EvaluatorError> {
-        let decodings: Vec<Decoding> = ctx.io_mut().expect_next().await?;
+        self.guarded(self._decode(ctx, values)).await
+    }
+
+    async fn _decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, EvaluatorError> {
+        let decodings: Vec<Decoding> = timeout(
+            Phase::Decode,
+            self.config.decode_timeout,
+            ctx.io_mut().expect_next(),
+        )
+        .await??;
+
+        self.decode_with(values, decodings).await
+    }
+
+    /// Receives decoding information for an array from the generator, returning a stream of
+    /// its decoded elements.
+    ///
+    /// Unlike [`Evaluator::decode`], the elements are yielded in chunks of at most
+    /// `decode_chunk_size` elements as soon as they are decoded, so the decoded array is never
+    /// held in memory at once. The generator sends the decodings with [`Generator::decode`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The array to decode.
+    pub async fn decode_array<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        value: &ValueRef,
+    ) -> Result<
+        impl Stream<Item = Result<Vec<Value>, EvaluatorError>> + Send + 'static,
+        EvaluatorError,
+    > {
+        self.guarded(self._decode_array(ctx, value)).await
+    }
+
+    async fn _decode_array<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        value: &ValueRef,
+    ) -> Result<
+        impl Stream<Item = Result<Vec<Value>, EvaluatorError>> + Send + 'static,
+        EvaluatorError,
+    > {
+        let decodings: Vec<Decoding> = timeout(
+            Phase::Decode,
+            self.config.decode_timeout,
+            ctx.io_mut().expect_next(),
+        )
+        .await??;
+
+        let values = std::slice::from_ref(value);
+        let encoding = self
+            .accept_decodings(values, &decodings)?
+            .pop()
+            .expect("one encoding per value");
+        let decoding = decodings
+            .into_iter()
+            .next()
+            .expect("one decoding per value");
+
+        match (encoding, decoding) {
+            (EncodedValue::Array(encoding), Decoding::Array(decoding))
+                if encoding.len() == decoding.len() =>
+            {
+                Ok(decode_chunks(
+                    encoding,
+                    decoding,
+                    self.config.decode_chunk_size,
+                    self.config.max_in_flight_chunks,
+                ))
+            }
+            (encoding, decoding) => Err(TypeError::UnexpectedType {
+                expected: encoding.value_type(),
+                actual: decoding.value_type(),
+            })?,
+        }
+    }
+
+    /// Receives digests of the decoding information for a set of values from the generator.
+    ///
+    /// The digests are used to verify the decodings later received via [`Evaluator::decode_committed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values whose decodings are committed
+    pub async fn receive_decoding_digests<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._receive_decoding_digests(ctx, values))
+            .await
+    }
+
+    async fn _receive_decoding_digests<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), EvaluatorError> {
+        let digests: Vec<Hash> = timeout(
+            Phase::Decode,
+            self.config.decode_timeout,
+            ctx.io_mut().expect_next(),
+        )
+        .await??;
+
+        // Make sure the generator sent the expected number of digests.
+        if digests.len() != values.len() {
+            return Err(EvaluatorError::IncorrectValueCount {
+                expected: values.len(),
+                actual: digests.len(),
+            });
+        }
+
+        let mut state = self.state();
+
+        // Check every value before inserting any digest, so an error leaves the state unchanged.
+        let mut seen = HashSet::with_capacity(values.len());
+        if let Some(value) = values
+            .iter()
+            .find(|value| state.decoding_digests.contains_key(*value) || !seen.insert(*value))
+        {
+            return Err(EvaluatorError::DuplicateDecodingDigest(value.clone()));
+        }
+
+        state
+            .decoding_digests
+            .extend(values.iter().cloned().zip(digests));
+
+        Ok(())
+    }
+
+    /// Receives decoding information for a set of values from the generator, verifies
+    /// it against the previously received digests and decodes the values.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to decode
+    pub async fn decode_committed<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, EvaluatorError> {
+        self.guarded(self._decode_committed(ctx, values)).await
+    }
+
+    async fn _decode_committed<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, EvaluatorError> {
+        let decommitments: Vec<Decommitment<Decoding>> = timeout(
+            Phase::Decode,
+            self.config.decode_timeout,
+            ctx.io_mut().expect_next(),
+        )
+        .await??;
+
+        // Make sure the generator sent the expected number of decodings.
+        if decommitments.len() != values.len() {
+            return Err(EvaluatorError::IncorrectValueCount {
+                expected: values.len(),
+                actual: decommitments.len(),
+            });
+        }
+
+        let decodings = {
+            let mut state = self.state();
+            let decodings = values
+                .iter()
+                .zip(decommitments)
+                .map(|(value, decommitment)| {
+                    let digest = state
+                        .decoding_digests
+                        .get(value)
+                        .ok_or_else(|| EvaluatorError::MissingDecodingDigest(value.clone()))?;
+
+                    decommitment
+                        .verify(digest)
+                        .map_err(|_| EvaluatorError::InvalidDecodingDigest(value.clone()))?;
+
+                    Ok(decommitment.into_inner())
+                })
+                .collect::<Result<Vec<_>, EvaluatorError>>()?;
+
+            // The digests are only consumed once all decodings are verified.
+            for value in values {
+                state.decoding_digests.remove(value);
+            }
+
+            decodings
+        };
+
+        self.decode_with(values, decodings).await
+    }
+
+    /// Re-randomizes the encoding of a value, assigning fresh labels for the same plaintext
+    /// to a new value.
+    ///
+    /// The generator must call [`Generator::rerandomize`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to re-randomize.
+    /// * `new_value` - The value to assign the fresh encoding to. It must have the same type as
+    ///   `value` and must not already be encoded.
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub async fn rerandomize<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        value: &ValueRef,
+        new_value: &ValueRef,
+    ) -> Result<(), EvaluatorError> {
+        self.guarded(self._rerandomize(ctx, value, new_value)).await
+    }
+
+    async fn _rerandomize<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        value: &ValueRef,
+        new_value: &ValueRef,
+    ) -> Result<(), EvaluatorError> {
+        if value.is_array() != new_value.is_array() || value.len() != new_value.len() {
+            return Err(EvaluatorError::IncompatibleValues(
+                value.clone(),
+                new_value.clone(),
+            ));
+        }
+
+        let table: Vec<TranslationRow> = ctx.io_mut().expect_next().await?;
+
+        let mut state = self.state();
+
+        let encoding = state
+            .memory
+            .get_encoding(value)
+            .ok_or_else(|| EvaluatorError::MissingEncoding(value.clone()))?;
+
+        let len = encoding.iter().count();
+        if table.len() != len {
+            return Err(TypeError::InvalidLength {
+                expected: len,
+                actual: table.len(),
+            })?;
+        }
+
+        let labels = translate(new_value, encoding.iter().map(AsRef::as_ref), &table)
+            .into_iter()
+            .map(Label::new)
+            .collect::<Vec<_>>();
+
+        let new_encoding =
+            EncodedValue::<encoding_state::Active>::from_labels(encoding.value_type(), &labels)?;
+        state.memory.set_encoding(new_value, new_encoding)?;
+
+        Ok(())
+    }
+
+    /// Links a value to the encoding of another value, so that it can be used as an input
+    /// to a circuit without being transferred.
+    ///
+    /// The generator must call [`Generator::link`].
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The value to link from. It must already be encoded.
+    /// * `to` - The value to link to. It must have the same type as `from` and must not
+    ///   already be encoded.
+    pub fn link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), EvaluatorError> {
+        self.check_link(from, to)?;
+
+        let mut state = self.state();
+
+        let encoding = state
+            .memory
+            .get_encoding(from)
+            .ok_or_else(|| EvaluatorError::MissingEncoding(from.clone()))?;
+
+        state.memory.set_encoding(to, encoding)?;
+
+        if self.config.log_circuits {
+            state.links.push((from.clone(), to.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `to` can be linked to `from` without modifying the evaluator.
+    pub fn check_link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), EvaluatorError> {
+        if from.is_array() != to.is_array() || from.len() != to.len() {
+            return Err(EvaluatorError::IncompatibleValues(from.clone(), to.clone()));
+        }
+
+        let state = self.state();
+
+        if !from.iter().all(|id| state.memory.contains_encoding(id)) {
+            return Err(EvaluatorError::MissingEncoding(from.clone()));
+        }
+
+        if let Some(id) = to.iter().find(|id| state.memory.contains_encoding(id)) {
+            return Err(EncodingMemoryError::DuplicateId(id.clone()).into());
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the provided values using the provided decodings.
+    async fn decode_with(
+        &self,
+        values: &[ValueRef],
+        decodings: Vec<Decoding>,
+    ) -> Result<Vec<Value>, EvaluatorError> {
+        let active_encodings = self.accept_decodings(values, &decodings)?;
+
+        let mut decoded_values = Vec::with_capacity(values.len());
+        for (encoding, decoding) in active_encodings.into_iter().zip(decodings) {
+            decoded_values.push(
+                decode_chunked(
+                    encoding,
+                    decoding,
+                    self.config.decode_chunk_size,
+                    self.config.max_in_flight_chunks,
+                )
+                .await?,
+            );
+        }
+
+        Ok(decoded_values)
+    }
 
+    /// Marks the provided values as decoded and returns their active encodings.
+    fn accept_decodings(
+        &self,
+        values: &[ValueRef],
+        decodings: &[Decoding],
+    ) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
         // Make sure the generator sent the expected number of decodings.
         if decodings.len() != values.len() {
             return Err(EvaluatorError::IncorrectValueCount {
@@ -514,21 +1267,13 @@ impl Evaluator {
             }
         }
 
-        let active_encodings = values
+        values
             .iter()
             .map(|value| {
                 self.get_encoding(value)
                     .ok_or_else(|| EvaluatorError::MissingEncoding(value.clone()))
             })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let decoded_values = decodings
-            .iter()
-            .zip(active_encodings.iter())
-            .map(|(decoding, encoding)| encoding.decode(decoding))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(decoded_values)
+            .collect()
     }
 
     /// Verifies all the evaluator state using the generator's encoder seed and the OT verifier.
@@ -537,7 +1282,21 @@ impl Evaluator {
     ///
     /// * `encoder_seed` - The seed used by the generator to generate encodings for input values.
     /// * `ot` - The OT verifier.
-    pub async fn verify<Ctx: Context, T: OTVerifyEncoding<Ctx>>(
+    pub async fn verify<Ctx: Context, T: OTVerifyEncoding<Ctx> + Send>(
+        &mut self,
+        ctx: &mut Ctx,
+        encoder_seed: [u8; 32],
+        ot: &mut T,
+    ) -> Result<(), EvaluatorError> {
+        // The guard does not borrow the evaluator, which is borrowed mutably by the verification.
+        let guard = self.poison.guard()?;
+        let output = self._verify(ctx, encoder_seed, ot).await;
+        guard.disarm();
+
+        output
+    }
+
+    async fn _verify<Ctx: Context, T: OTVerifyEncoding<Ctx> + Send>(
         &mut self,
         ctx: &mut Ctx,
         encoder_seed: [u8; 32],
@@ -554,32 +1313,46 @@ impl Evaluator {
 
         // Generate encodings for all received values
         let received_values: Vec<(ValueId, ValueType)> =
-            self.state().received_values.drain().collect();
+            mem::take(&mut self.state().received_values)
+                .into_iter()
+                .collect();
         gen.generate_input_encodings_by_id(&received_values);
 
-        let (ot_log, mut circuit_logs) = {
+        let (ot_log, mut circuit_logs, mut links) = {
             let mut state = self.state();
             (
                 mem::take(&mut state.ot_log),
                 mem::take(&mut state.circuit_logs),
+                mem::take(&mut state.links),
             )
         };
 
-        // Verify all OTs in the log
-        for (ot_id, value_ids) in ot_log {
-            let encoded_values = gen
-                .get_encodings_by_id(&value_ids)
-                .expect("encodings should be present");
-            ot.verify(ctx, ot_id, encoded_values).await?
-        }
-
-        // Verify all garbled circuits in the log
-        let mut dummy_ctx = DummyExecutor::default();
+        // Verify all OTs in the log in a single batch
+        let ot_batch = ot_log
+            .into_iter()
+            .map(|(ot_id, value_ids)| {
+                let encoded_values = gen
+                    .get_encodings_by_id(&value_ids)
+                    .expect("encodings should be present");
+                (ot_id, encoded_values)
+            })
+            .collect();
+        ot.verify_batch(ctx, ot_batch).await?;
+
+        // Verify all garbled circuits in the log.
+        //
+        // Circuits are re-garbled on the CPU backend in chunks bounded by the configured
+        // number of AND gates, instead of replaying the whole log in one blocking task.
+        let delta = gen.delta();
         while !circuit_logs.is_empty() {
+            // Apply the links whose source encodings are known at this point, usually the
+            // outputs of a previous batch.
+            apply_links(&gen, &mut links).map_err(VerificationError::from)?;
+
             // drain_filter is not stabilized.. such is life.
             // here we drain out log batches for which we have all the input encodings
             // computed at this point.
-            let log_batch = circuit_logs
+            let mut log_batch = circuit_logs
                 .filter_drain(|log| {
                     log.inputs
                         .iter()
@@ -587,27 +1360,76 @@ impl Evaluator {
                 })
                 .collect::<Vec<_>>();
 
-            for log in log_batch {
-                // Compute the garbled circuit digest
-                let (_, digest) = gen
-                    .generate(
-                        &mut dummy_ctx,
-                        log.circ.clone(),
-                        &log.inputs,
-                        &log.outputs,
-                        true,
-                    )
-                    .await
+            // If none of the remaining circuits can be garbled, their inputs were
+            // never received.
+            if log_batch.is_empty() {
+                return Err(VerificationError::InvalidGarbledCircuit.into());
+            }
+
+            while !log_batch.is_empty() {
+                // Take circuits until the gate budget is exhausted, taking at least one.
+                let mut gate_count = 0;
+                let chunk_len = log_batch
+                    .iter()
+                    .position(|log| {
+                        gate_count += log.circ.and_count();
+                        gate_count > self.config.verify_chunk_size
+                    })
+                    .unwrap_or(log_batch.len())
+                    .max(1);
+                let chunk = log_batch.drain(..chunk_len).collect::<Vec<_>>();
+
+                let inputs = chunk
+                    .iter()
+                    .map(|log| gen.get_encodings(&log.inputs))
+                    .collect::<Result<Vec<_>, _>>()
                     .map_err(VerificationError::from)?;
 
-                if digest.unwrap() != log.hash {
-                    return Err(VerificationError::InvalidGarbledCircuit.into());
+                // Verify the input decodings of privacy-free circuits.
+                for (log, inputs) in chunk.iter().zip(&inputs) {
+                    if let Some(decodings) = &log.input_decodings {
+                        if !inputs
+                            .iter()
+                            .map(|input| input.decoding())
+                            .eq(decodings.iter().cloned())
+                        {
+                            return Err(VerificationError::InvalidDecoding)?;
+                        }
+                    }
+                }
+
+                // Each circuit is checked frame by frame, stopping at the first frame which
+                // does not match.
+                let (chunk, outputs) = CpuBackend::blocking(move || {
+                    let mut gen = GeneratorCore::default();
+                    let outputs = chunk
+                        .iter()
+                        .zip(inputs)
+                        .map(|(log, inputs)| log.regarble(&mut gen, delta, inputs))
+                        .collect::<Result<Option<Vec<_>>, _>>();
+
+                    (chunk, outputs)
+                })
+                .await;
+
+                let outputs = outputs
+                    .map_err(GeneratorError::from)
+                    .map_err(VerificationError::from)?
+                    .ok_or(VerificationError::InvalidGarbledCircuit)?;
+
+                for (log, GeneratorOutput { outputs, .. }) in chunk.iter().zip(outputs) {
+                    gen.set_encodings(&log.outputs, &outputs)
+                        .map_err(VerificationError::from)?;
                 }
             }
         }
 
+        // Linked values may only be decoded, without being used by any circuit.
+        apply_links(&gen, &mut links).map_err(VerificationError::from)?;
+
         // Verify all decodings in the log
-        for (value, decoding) in self.state().decoding_logs.drain() {
+        let decoding_logs = mem::take(&mut self.state().decoding_logs);
+        for (value, decoding) in decoding_logs {
             let encoding = gen.get_encoding(&value).expect("encoding should exist");
 
             if encoding.decoding() != decoding {
@@ -619,12 +1441,23 @@ impl Evaluator {
     }
 }
 
+impl Poison for Evaluator {
+    fn is_poisoned(&self) -> bool {
+        self.poison.is_poisoned()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct EvaluatorLog {
     inputs: Vec<ValueRef>,
     outputs: Vec<ValueRef>,
     circ: Arc<Circuit>,
     hash: Hash,
+    /// The number of encrypted gates received up to the end of each frame and their digest, if
+    /// the circuit was streamed.
+    digests: Vec<(usize, Hash)>,
+    /// Decodings of the inputs, if the circuit was garbled in privacy-free mode.
+    input_decodings: Option<Vec<Decoding>>,
 }
 
 impl EvaluatorLog {
@@ -633,12 +1466,278 @@ impl EvaluatorLog {
         outputs: Vec<ValueRef>,
         circ: Arc<Circuit>,
         digest: Hash,
+        digests: Vec<(usize, Hash)>,
+        input_decodings: Option<Vec<Decoding>>,
     ) -> Self {
         Self {
             inputs,
             outputs,
             circ,
             hash: digest,
+            digests,
+            input_decodings,
+        }
+    }
+
+    /// Re-garbles the logged circuit, checking the digest of each frame as the encrypted gates
+    /// are generated.
+    ///
+    /// Returns `None` as soon as a frame does not match the encrypted gates sent by the generator.
+    fn regarble(
+        &self,
+        gen: &mut GeneratorCore,
+        delta: Delta,
+        inputs: Vec<EncodedValue<encoding_state::Full>>,
+    ) -> Result<Option<GeneratorOutput>, GeneratorCoreError> {
+        let mut iter = if self.input_decodings.is_some() {
+            gen.generate_privacy_free(&self.circ, delta, inputs)?
+        } else {
+            gen.generate(&self.circ, delta, inputs)?
+        };
+        iter.enable_hasher();
+
+        let mut count = 0;
+        for (end, digest) in &self.digests {
+            count += iter.by_ref().take(end - count).count();
+            if count != *end || iter.digest().as_ref() != Some(digest) {
+                return Ok(None);
+            }
+        }
+
+        for _ in iter.by_ref() {}
+        let output = iter.finish()?;
+
+        Ok((output.hash.as_ref() == Some(&self.hash)).then_some(output))
+    }
+}
+
+/// Receives the next frame of a garbled circuit stream.
+///
+/// Returns an error if no frame arrives within `stream_timeout` or if `cancel` is cancelled while
+/// waiting. `end` is set if the frame is expected to end the circuit, see [`next_frame`].
+async fn stream_frame<Io: IoStream + Unpin>(
+    io: &mut Io,
+    end: bool,
+    stream_timeout: Duration,
+    cancel: &CancellationToken,
+) -> Result<CircuitFrame, EvaluatorError> {
+    let frame = cancel
+        .run(timeout(
+            Phase::GarbleStream,
+            stream_timeout,
+            next_frame(io, end),
+        ))
+        .await???;
+
+    Ok(frame)
+}
+
+/// Evaluates a garbled circuit from the frames sent by the generator.
+///
+/// If `values` are provided, the circuit is evaluated in privacy-free mode. Returns the output of
+/// the evaluator, the output encoding commitments if they were sent and, if `hash` is set, the
+/// digest of the encrypted gates received up to the end of each frame.
+#[allow(clippy::too_many_arguments)]
+async fn evaluate_frames<Io: IoStream + Unpin>(
+    io: &mut Io,
+    circ: &Circuit,
+    encoded_inputs: Vec<EncodedValue<encoding_state::Active>>,
+    values: Option<Vec<Value>>,
+    hash: bool,
+    strict_padding: bool,
+    stream_timeout: Duration,
+    cancel: &CancellationToken,
+) -> Result<
+    (
+        EvaluatorOutput,
+        Option<Vec<EncodingCommitment>>,
+        Vec<(usize, Hash)>,
+    ),
+    EvaluatorError,
+> {
+    let mut ev = EvaluatorCore::default();
+
+    let ev_consumer = match values {
+        Some(values) => ev.evaluate_privacy_free_batched(circ, encoded_inputs, values),
+        None => ev.evaluate_batched(circ, encoded_inputs),
+    };
+
+    let mut ev_consumer = match ev_consumer {
+        Ok(ev_consumer) => ev_consumer,
+        Err(err) => {
+            resync(io).await?;
+            return Err(EvaluatorError::from(err));
+        }
+    };
+
+    if hash {
+        ev_consumer.enable_hasher();
+    }
+
+    if strict_padding {
+        ev_consumer.enable_strict_padding();
+    }
+
+    // The digest of each frame is recorded as it arrives, so that the frames can be verified one
+    // by one once the generator reveals its encoder seed.
+    let mut digests = Vec::new();
+    let commitments = loop {
+        let end = is_small_circuit(circ) || !ev_consumer.wants_gates();
+        match stream_frame(io, end, stream_timeout, cancel).await? {
+            CircuitFrame::Batch(_) if !ev_consumer.wants_gates() => {
+                resync(io).await?;
+                return Err(EvaluatorError::UnexpectedGates);
+            }
+            CircuitFrame::Batch(batch) => {
+                ev_consumer.next(batch);
+                digests.extend(ev_consumer.digest());
+            }
+            CircuitFrame::End(commitments) => break commitments,
+            CircuitFrame::Complete(batch, _) if !ev_consumer.wants_gates() && !batch.is_empty() => {
+                return Err(EvaluatorError::UnexpectedGates);
+            }
+            CircuitFrame::Complete(batch, commitments) => {
+                ev_consumer.next(batch);
+                digests.extend(ev_consumer.digest());
+                break commitments;
+            }
+            CircuitFrame::Abort => return Err(EvaluatorError::Aborted),
+            CircuitFrame::Decodings(_) => {
+                resync(io).await?;
+                return Err(EvaluatorError::UnexpectedFrame);
+            }
         }
+    };
+
+    Ok((ev_consumer.finish()?, commitments, digests))
+}
+
+/// Decodes a value, splitting large arrays into chunks which are decoded in parallel
+/// on the CPU backend.
+async fn decode_chunked(
+    encoding: EncodedValue<encoding_state::Active>,
+    decoding: Decoding,
+    chunk_size: usize,
+    max_in_flight_chunks: usize,
+) -> Result<Value, EvaluatorError> {
+    match (encoding, decoding) {
+        (EncodedValue::Array(encoding), Decoding::Array(decoding))
+            if encoding.len() > chunk_size && encoding.len() == decoding.len() =>
+        {
+            let values = Vec::with_capacity(encoding.len());
+            let values = decode_chunks(encoding, decoding, chunk_size, max_in_flight_chunks)
+                .try_fold(values, |mut values, chunk| async move {
+                    values.extend(chunk);
+                    Ok(values)
+                })
+                .await?;
+
+            Ok(Value::Array(values))
+        }
+        (encoding, decoding) => Ok(encoding.decode(&decoding)?),
     }
 }
+
+/// Returns a stream of the decoded elements of an array, in chunks of at most `chunk_size`
+/// elements.
+///
+/// The chunks are decoded on the CPU backend, at most `max_in_flight_chunks` at a time, and
+/// are yielded in order.
+fn decode_chunks(
+    encoding: Vec<EncodedValue<encoding_state::Active>>,
+    decoding: Vec<Decoding>,
+    chunk_size: usize,
+    max_in_flight_chunks: usize,
+) -> impl Stream<Item = Result<Vec<Value>, EvaluatorError>> + Send + 'static {
+    stream::iter(chunks(encoding, chunk_size).zip(chunks(decoding, chunk_size)))
+        .map(|(encoding, decoding)| {
+            CpuBackend::blocking(move || {
+                encoding
+                    .iter()
+                    .zip(decoding.iter())
+                    .map(|(encoding, decoding)| Ok(encoding.decode(decoding)?))
+                    .collect::<Result<Vec<_>, EvaluatorError>>()
+            })
+        })
+        .buffered(max_in_flight_chunks.max(1))
+}
+
+/// Verifies active encodings against the generator's commitments, splitting large arrays
+/// into chunks which are verified in parallel on the CPU backend, at most
+/// `max_in_flight_chunks` at a time.
+///
+/// Returns the verified encodings.
+async fn verify_commitments(
+    encodings: Vec<EncodedValue<encoding_state::Active>>,
+    commitments: Vec<EncodingCommitment>,
+    chunk_size: usize,
+    max_in_flight_chunks: usize,
+) -> Result<Vec<EncodedValue<encoding_state::Active>>, EvaluatorError> {
+    let mut verified = Vec::with_capacity(encodings.len());
+    for (encoding, commitment) in encodings.into_iter().zip(commitments) {
+        let encoding = match (encoding, commitment) {
+            (EncodedValue::Array(encoding), EncodingCommitment::Array(commitment))
+                if encoding.len() > chunk_size && encoding.len() == commitment.len() =>
+            {
+                let elements = Vec::with_capacity(encoding.len());
+                let elements =
+                    stream::iter(chunks(encoding, chunk_size).zip(chunks(commitment, chunk_size)))
+                        .map(|(encoding, commitment)| {
+                            CpuBackend::blocking(move || {
+                                for (encoding, commitment) in encoding.iter().zip(commitment.iter())
+                                {
+                                    commitment.verify(encoding)?;
+                                }
+
+                                Ok::<_, EvaluatorError>(encoding)
+                            })
+                        })
+                        .buffered(max_in_flight_chunks.max(1))
+                        .try_fold(elements, |mut elements, chunk| async move {
+                            elements.extend(chunk);
+                            Ok(elements)
+                        })
+                        .await?;
+
+                EncodedValue::Array(elements)
+            }
+            (encoding, commitment) => {
+                commitment.verify(&encoding)?;
+                encoding
+            }
+        };
+
+        verified.push(encoding);
+    }
+
+    Ok(verified)
+}
+
+/// Returns an iterator which moves the items of a vector out in chunks of at most
+/// `chunk_size` items, one chunk at a time.
+fn chunks<T>(items: Vec<T>, chunk_size: usize) -> impl Iterator<Item = Vec<T>> {
+    let chunk_size = chunk_size.max(1);
+    let mut items = items.into_iter();
+    iter::from_fn(move || {
+        let chunk: Vec<T> = items.by_ref().take(chunk_size).collect();
+        (!chunk.is_empty()).then_some(chunk)
+    })
+}
+
+/// Links the values whose source encodings are known to the generator, removing them from
+/// `links`.
+fn apply_links(
+    gen: &Generator,
+    links: &mut Vec<(ValueRef, ValueRef)>,
+) -> Result<(), GeneratorError> {
+    let mut result = Ok(());
+    links.retain(|(from, to)| {
+        if result.is_err() || gen.get_encoding(from).is_none() {
+            return true;
+        }
+        result = gen.link(from, to);
+        false
+    });
+
+    result
+}
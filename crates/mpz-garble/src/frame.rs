@@ -0,0 +1,185 @@
+//! Framing of garbled circuit streams.
+//!
+//! Every garbled circuit sent by the generator is terminated by either [`CircuitFrame::End`] or
+//! [`CircuitFrame::Abort`]. If either party fails in the middle of a circuit, the evaluator
+//! discards the remaining frames of the circuit so that the session can continue with the next
+//! circuit.
+//!
+//! Circuits with at most [`SMALL_CIRCUIT_MAX_AND_GATES`] AND gates are instead sent in a single
+//! [`CircuitFrame::Complete`] frame, which also terminates the circuit.
+
+use mpz_circuits::Circuit;
+use mpz_garble_core::{Decoding, EncodingCommitment, EncryptedGateBatch};
+use serde::{Deserialize, Serialize};
+use serio::{stream::IoStreamExt, IoStream};
+
+/// A frame of a garbled circuit stream.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum CircuitFrame {
+    /// The decodings of the circuit inputs, sent first in privacy-free mode.
+    Decodings(Vec<Decoding>),
+    /// A batch of encrypted gates.
+    Batch(EncryptedGateBatch),
+    /// The end of the garbled circuit, with the output encoding commitments if enabled.
+    End(Option<Vec<EncodingCommitment>>),
+    /// The generator aborted the garbled circuit.
+    Abort,
+    /// All encrypted gates of a small circuit followed by the end of the circuit, with the
+    /// output encoding commitments if enabled.
+    Complete(EncryptedGateBatch, Option<Vec<EncodingCommitment>>),
+}
+
+/// Maximum number of AND gates of a circuit which is garbled and evaluated inline on the
+/// async task, and sent as a single frame.
+///
+/// For circuits this small, handing the work to the CPU backend and streaming batches costs more
+/// than garbling itself. Both parties select the fast path from the circuit alone, so this must
+/// not differ between them.
+pub(crate) const SMALL_CIRCUIT_MAX_AND_GATES: usize = 64;
+
+/// Returns `true` if the circuit is garbled and evaluated using the small circuit fast path.
+pub(crate) fn is_small_circuit(circ: &Circuit) -> bool {
+    circ.and_count() <= SMALL_CIRCUIT_MAX_AND_GATES
+}
+
+/// Receives the next frame of a garbled circuit stream.
+///
+/// If the frame is malformed the rest of the garbled circuit is discarded before returning the
+/// error, unless the frame was expected to end the circuit.
+///
+/// # Arguments
+///
+/// * `io` - The stream to receive the frame from.
+/// * `end` - Whether the frame is expected to end the circuit, in which case a malformed frame
+///   was the end of the circuit and nothing is discarded.
+pub(crate) async fn next_frame<Io: IoStream + Unpin>(
+    io: &mut Io,
+    end: bool,
+) -> Result<CircuitFrame, std::io::Error> {
+    match io.expect_next().await {
+        Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+            if !end {
+                resync(io).await?;
+            }
+            Err(err)
+        }
+        frame => frame,
+    }
+}
+
+/// Discards the remaining frames of the current garbled circuit.
+pub(crate) async fn resync<Io: IoStream + Unpin>(io: &mut Io) -> Result<(), std::io::Error> {
+    loop {
+        match io.expect_next::<CircuitFrame>().await {
+            Ok(CircuitFrame::End(_) | CircuitFrame::Abort | CircuitFrame::Complete(..)) => {
+                return Ok(())
+            }
+            Ok(CircuitFrame::Decodings(_) | CircuitFrame::Batch(_)) => {}
+            // The malformed frame has already been consumed from the stream.
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::circuits::AES128;
+    use mpz_core::transcript::Transcript;
+    use mpz_garble_core::{ChaChaEncoder, Encoder, Evaluator, Generator};
+    use serio::{channel::duplex, SinkExt};
+
+    #[test]
+    fn test_circuit_frame_transcript() {
+        let mut transcript = Transcript::open(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/transcripts"),
+            "circuit_frame",
+            env!("CARGO_PKG_VERSION"),
+        );
+
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let full_inputs: Vec<_> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+        let active_inputs = vec![
+            full_inputs[0].clone().select([69u8; 16]).unwrap(),
+            full_inputs[1].clone().select([42u8; 16]).unwrap(),
+        ];
+
+        // Sent ahead of the gates in privacy-free mode.
+        let decodings = full_inputs.iter().map(|input| input.decoding()).collect();
+        transcript.exchange("decodings", CircuitFrame::Decodings(decodings));
+
+        let mut gen = Generator::default();
+        let mut ev = Evaluator::default();
+
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut ev_consumer = ev.evaluate_batched(&AES128, active_inputs).unwrap();
+
+        for batch in gen_iter.by_ref() {
+            let CircuitFrame::Batch(batch) =
+                transcript.exchange("batch", CircuitFrame::Batch(batch))
+            else {
+                panic!("expected a batch frame");
+            };
+            ev_consumer.next(batch);
+        }
+
+        let commitments = gen_iter
+            .finish()
+            .unwrap()
+            .outputs
+            .iter()
+            .map(|output| output.commit())
+            .collect();
+        let CircuitFrame::End(Some(commitments)) =
+            transcript.exchange("end", CircuitFrame::End(Some(commitments)))
+        else {
+            panic!("expected an end frame");
+        };
+
+        let outputs = ev_consumer.finish().unwrap().outputs;
+        for (commitment, output) in commitments.iter().zip(&outputs) {
+            commitment.verify(output).unwrap();
+        }
+
+        transcript.exchange("abort", CircuitFrame::Abort);
+        transcript.finish();
+    }
+
+    #[tokio::test]
+    async fn test_next_frame_resync() {
+        let (mut gen_io, mut ev_io) = duplex(8);
+
+        // A malformed frame in the middle of a circuit discards the rest of the circuit.
+        gen_io.send(()).await.unwrap();
+        gen_io
+            .send(CircuitFrame::Decodings(Vec::new()))
+            .await
+            .unwrap();
+        gen_io.send(CircuitFrame::Abort).await.unwrap();
+        gen_io.send(CircuitFrame::End(None)).await.unwrap();
+
+        assert!(next_frame(&mut ev_io, false).await.is_err());
+        assert!(matches!(
+            next_frame(&mut ev_io, false).await.unwrap(),
+            CircuitFrame::End(None)
+        ));
+
+        // A malformed end of a circuit leaves the next circuit in the stream.
+        gen_io.send(()).await.unwrap();
+        gen_io.send(CircuitFrame::Abort).await.unwrap();
+
+        assert!(next_frame(&mut ev_io, true).await.is_err());
+        assert!(matches!(
+            next_frame(&mut ev_io, false).await.unwrap(),
+            CircuitFrame::Abort
+        ));
+    }
+}
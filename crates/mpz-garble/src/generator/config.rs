@@ -1,11 +1,30 @@
 use derive_builder::Builder;
+use mpz_garble_core::DEFAULT_BATCH_SIZE;
+
+/// Default maximum number of batches buffered in the sink before the generator waits for them to
+/// be written.
+pub(crate) const DEFAULT_MAX_IN_FLIGHT_BATCHES: usize = 256;
 
 /// Generator configuration.
 #[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct GeneratorConfig {
     /// Whether to send commitments to output encodings.
     #[builder(default = "false", setter(custom))]
     pub(crate) encoding_commitments: bool,
+    /// The number of encrypted gates sent to the evaluator in each batch.
+    ///
+    /// Larger batches reduce the number of messages on high-latency links, smaller batches
+    /// reduce the memory used by both parties.
+    #[builder(default = "DEFAULT_BATCH_SIZE")]
+    pub(crate) batch_size: usize,
+    /// The maximum number of batches buffered in the sink before the generator waits for them to
+    /// be written to the underlying connection.
+    ///
+    /// This bounds the memory used by the generator when it garbles faster than the connection
+    /// can send, at the cost of a flush for every `max_in_flight_batches` batches.
+    #[builder(default = "DEFAULT_MAX_IN_FLIGHT_BATCHES")]
+    pub(crate) max_in_flight_batches: usize,
 }
 
 impl GeneratorConfig {
@@ -21,6 +40,18 @@ impl GeneratorConfigBuilder {
         self.encoding_commitments = Some(true);
         self
     }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.batch_size == Some(0) {
+            return Err("batch size must be non-zero".to_string());
+        }
+
+        if self.max_in_flight_batches == Some(0) {
+            return Err("max in-flight batches must be non-zero".to_string());
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for GeneratorConfig {
@@ -28,3 +59,33 @@ impl Default for GeneratorConfig {
         GeneratorConfigBuilder::default().build().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = GeneratorConfig::default();
+
+        assert_eq!(config.batch_size, DEFAULT_BATCH_SIZE);
+        assert_eq!(config.max_in_flight_batches, DEFAULT_MAX_IN_FLIGHT_BATCHES);
+    }
+
+    #[test]
+    fn test_config_rejects_zero_batch_size() {
+        assert!(GeneratorConfig::builder().batch_size(0).build().is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_zero_max_in_flight_batches() {
+        assert!(GeneratorConfig::builder()
+            .max_in_flight_batches(0)
+            .build()
+            .is_err());
+        assert!(GeneratorConfig::builder()
+            .max_in_flight_batches(1)
+            .build()
+            .is_ok());
+    }
+}
@@ -16,13 +16,31 @@ pub enum GeneratorError {
     #[error("context error: {0}")]
     ContextError(#[from] mpz_common::ContextError),
     #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
+    #[error(transparent)]
     ValueError(#[from] ValueError),
+    #[error(transparent)]
+    TypeError(#[from] mpz_circuits::types::TypeError),
+    #[error("incorrect number of values: expected {expected}, got {actual}")]
+    IncorrectValueCount { expected: usize, actual: usize },
     #[error("duplicate encoding for value: {0:?}")]
     DuplicateEncoding(ValueRef),
     #[error("missing encoding for value: {0:?}")]
     MissingEncoding(ValueRef),
+    #[error("duplicate decoding commitment for value: {0:?}")]
+    DuplicateDecodingCommitment(ValueRef),
+    #[error("missing decoding commitment for value: {0:?}")]
+    MissingDecodingCommitment(ValueRef),
+    #[error("values have incompatible types: {0:?} and {1:?}")]
+    IncompatibleValues(ValueRef, ValueRef),
+    #[error("circuit has already been garbled")]
+    DuplicateCircuit,
+    #[error("missing garbled artifact")]
+    MissingArtifact,
     #[error(transparent)]
     EncodingRegistryError(#[from] crate::memory::EncodingMemoryError),
+    #[error(transparent)]
+    Poisoned(#[from] mpz_common::poison::PoisonedError),
 }
 
 impl From<mpz_ot::OTError> for GeneratorError {
@@ -5,30 +5,38 @@ mod error;
 
 use std::{
     collections::{HashMap, HashSet},
+    future::Future,
     ops::DerefMut,
     sync::{Arc, Mutex},
 };
 
 use mpz_circuits::{
-    types::{Value, ValueType},
+    types::{TypeError, Value, ValueType},
     Circuit,
 };
-use mpz_common::{scoped, Context};
-use mpz_core::hash::Hash;
+use mpz_common::{cancel::CancellationToken, poison::PoisonFlag, scoped, Context, Poison};
+use mpz_core::{
+    commit::{Decommitment, HashCommit},
+    hash::Hash,
+};
 use mpz_garble_core::{
-    encoding_state, ChaChaEncoder, EncodedValue, Encoder, EncodingCommitment,
-    Generator as GeneratorCore, GeneratorOutput,
+    encoding_state, ChaChaEncoder, Decoding, Delta, EncodedValue, Encoder, EncodingCommitment,
+    EncryptedGateBatch, GarbledCircuit, Generator as GeneratorCore, GeneratorOutput,
 };
-use serio::SinkExt;
+use serio::{stream::IoStreamExt, IoSink, SinkExt};
 use tracing::{span, Level};
 
 use crate::{
+    artifact::{ArtifactHeader, GarbledArtifact},
+    frame::{is_small_circuit, CircuitFrame},
     memory::EncodingMemory,
     ot::OTSendEncoding,
+    rerandomize::garble_translation,
     value::{CircuitRefs, ValueId, ValueRef},
     AssignedValues,
 };
 
+pub(crate) use config::DEFAULT_MAX_IN_FLIGHT_BATCHES;
 pub use config::{GeneratorConfig, GeneratorConfigBuilder};
 pub use error::GeneratorError;
 
@@ -37,6 +45,8 @@ pub use error::GeneratorError;
 pub struct Generator {
     config: GeneratorConfig,
     state: Mutex<State>,
+    /// Poisoned if an operation is dropped before it completes.
+    poison: PoisonFlag,
 }
 
 #[derive(Debug, Default)]
@@ -56,6 +66,10 @@ struct State {
     /// This is used to guarantee that the same encoding is never used
     /// with different active values.
     active: HashSet<ValueId>,
+    /// Decommitments to decoding information which has been committed to the evaluator.
+    decoding_decommitments: HashMap<ValueRef, Decommitment<Decoding>>,
+    /// Digests of garbled artifacts which have not been released to the evaluator yet.
+    artifacts: HashMap<CircuitRefs, Hash>,
 }
 
 impl Generator {
@@ -64,9 +78,25 @@ impl Generator {
         Self {
             config,
             state: Mutex::new(State::new(ChaChaEncoder::new(encoder_seed))),
+            poison: PoisonFlag::new(),
         }
     }
 
+    /// Runs an operation, poisoning the generator if it is dropped before it completes.
+    ///
+    /// A dropped operation may have activated encodings it never sent, or left a partially
+    /// streamed circuit in the connection, so the generator can not be used again.
+    async fn guarded<T>(
+        &self,
+        fut: impl Future<Output = Result<T, GeneratorError>>,
+    ) -> Result<T, GeneratorError> {
+        let guard = self.poison.guard()?;
+        let output = fut.await;
+        guard.disarm();
+
+        output
+    }
+
     /// Convenience method for grabbing a lock to the state.
     fn state(&self) -> impl DerefMut<Target = State> + '_ {
         self.state.lock().unwrap()
@@ -77,6 +107,11 @@ impl Generator {
         self.state().encoder.seed()
     }
 
+    /// Returns the global offset used for encodings.
+    pub(crate) fn delta(&self) -> Delta {
+        self.state().encoder.delta()
+    }
+
     /// Returns the encoding for a value.
     pub fn get_encoding(&self, value: &ValueRef) -> Option<EncodedValue<encoding_state::Full>> {
         self.state().memory.get_encoding(value)
@@ -110,6 +145,20 @@ impl Generator {
             .collect::<Option<Vec<_>>>()
     }
 
+    /// Sets the encodings for the provided values.
+    pub(crate) fn set_encodings(
+        &self,
+        values: &[ValueRef],
+        encodings: &[EncodedValue<encoding_state::Full>],
+    ) -> Result<(), GeneratorError> {
+        let mut state = self.state();
+        for (value, encoding) in values.iter().zip(encodings) {
+            state.memory.set_encoding(value, encoding.clone())?;
+        }
+
+        Ok(())
+    }
+
     /// Generates encoding for the provided input value.
     ///
     /// If an encoding for a value have already been generated, it is ignored.
@@ -121,6 +170,20 @@ impl Generator {
         self.state().encode(value, typ);
     }
 
+    /// Generates encodings for the provided input values.
+    ///
+    /// If an encoding for a value have already been generated, it is ignored.
+    ///
+    /// # Panics
+    ///
+    /// If a provided value type does not match its value reference.
+    pub fn generate_input_encodings(&self, values: &[(ValueRef, ValueType)]) {
+        let mut state = self.state();
+        for (value, typ) in values {
+            state.encode(value, typ);
+        }
+    }
+
     /// Generates encodings for the provided input values.
     ///
     /// If encodings for a value have already been generated, it is ignored.
@@ -148,14 +211,28 @@ impl Generator {
         ctx: &mut Ctx,
         values: &AssignedValues,
         ot: &mut OT,
+    ) -> Result<(), GeneratorError> {
+        self.guarded(self._setup_assigned_values(ctx, values, ot))
+            .await
+    }
+
+    async fn _setup_assigned_values<Ctx: Context, OT: OTSendEncoding<Ctx> + Send>(
+        &self,
+        ctx: &mut Ctx,
+        values: &AssignedValues,
+        ot: &mut OT,
     ) -> Result<(), GeneratorError> {
         let ot_send_values = values.blind.clone();
         let mut direct_send_values = values.public.clone();
         direct_send_values.extend(values.private.iter().cloned());
+        direct_send_values.extend(values.private_direct.iter().cloned());
+        let requested_values = values.blind_direct.clone();
 
         ctx.try_join(
             scoped!(|ctx| async move {
                 self.direct_send_active_encodings(ctx, &direct_send_values)
+                    .await?;
+                self.direct_send_requested_encodings(ctx, &requested_values)
                     .await
             }),
             scoped!(|ctx| async move {
@@ -245,6 +322,66 @@ impl Generator {
         Ok(())
     }
 
+    /// Receives the plaintext values of the provided blind values from the evaluator, and
+    /// directly sends back their active encodings.
+    ///
+    /// This is used for values with a [`TransferPolicy::Direct`](crate::config::TransferPolicy)
+    /// policy.
+    ///
+    /// # Arguments
+    ///
+    /// - `values` - The values requested by the evaluator
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub(crate) async fn direct_send_requested_encodings<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[(ValueId, ValueType)],
+    ) -> Result<(), GeneratorError> {
+        // Filter out any values that are already active
+        let mut values = {
+            let state = self.state();
+            values
+                .iter()
+                .filter(|(id, _)| !state.active.contains(id))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        values.sort_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b));
+
+        let plaintexts: Vec<Value> = ctx.io_mut().expect_next().await?;
+
+        // Make sure the evaluator sent the expected number of values.
+        if plaintexts.len() != values.len() {
+            return Err(GeneratorError::IncorrectValueCount {
+                expected: values.len(),
+                actual: plaintexts.len(),
+            });
+        }
+
+        let values = values
+            .into_iter()
+            .zip(plaintexts)
+            .map(|((id, expected_ty), value)| {
+                // Make sure the evaluator sent the expected type.
+                if value.value_type() != expected_ty {
+                    return Err(TypeError::UnexpectedType {
+                        expected: expected_ty,
+                        actual: value.value_type(),
+                    })?;
+                }
+
+                Ok((id, value))
+            })
+            .collect::<Result<Vec<_>, GeneratorError>>()?;
+
+        self.direct_send_active_encodings(ctx, &values).await
+    }
+
     /// Generate a garbled circuit, streaming the encrypted gates to the evaluator in batches.
     ///
     /// Returns the encodings of the outputs, and optionally a hash of the circuit.
@@ -264,6 +401,49 @@ impl Generator {
         inputs: &[ValueRef],
         outputs: &[ValueRef],
         hash: bool,
+    ) -> Result<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>), GeneratorError> {
+        self.guarded(self.generate_inner(ctx, circ, inputs, outputs, hash, false))
+            .await
+    }
+
+    /// Generate a privacy-free garbled circuit, streaming the encrypted gates to the evaluator in
+    /// batches.
+    ///
+    /// Privacy-free garbled circuits only provide authenticity, which is sufficient for proving
+    /// statements in zero-knowledge, and require half the bandwidth. The decodings of the inputs
+    /// are sent to the evaluator, so **all** values of the circuit are revealed to the evaluator.
+    ///
+    /// The evaluator must call [`Evaluator::evaluate_privacy_free`](crate::Evaluator::evaluate_privacy_free).
+    ///
+    /// Returns the encodings of the outputs, and optionally a hash of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble
+    /// * `inputs` - The inputs of the circuit
+    /// * `outputs` - The outputs of the circuit
+    /// * `hash` - Whether to hash the circuit
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub async fn generate_privacy_free<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        hash: bool,
+    ) -> Result<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>), GeneratorError> {
+        self.guarded(self.generate_inner(ctx, circ, inputs, outputs, hash, true))
+            .await
+    }
+
+    async fn generate_inner<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        hash: bool,
+        privacy_free: bool,
     ) -> Result<(Vec<EncodedValue<encoding_state::Full>>, Option<Hash>), GeneratorError> {
         let refs = CircuitRefs {
             inputs: inputs.to_vec(),
@@ -298,12 +478,51 @@ impl Generator {
                         .get_encoding(value)
                         .ok_or(GeneratorError::MissingEncoding(value.clone()))
                 })
-                .collect::<Result<Vec<_>, _>>()?;
+                .collect::<Result<Vec<_>, _>>();
 
             (delta, inputs)
         };
 
+        // The evaluator is expecting the garbled circuit, so abort it to keep the stream in sync.
+        let inputs = match inputs {
+            Ok(inputs) => inputs,
+            Err(err) => {
+                ctx.io_mut().send(CircuitFrame::Abort).await?;
+                return Err(err);
+            }
+        };
+
+        // In privacy-free mode the evaluator needs to know the values of the inputs.
+        if privacy_free {
+            let decodings = inputs.iter().map(|input| input.decoding()).collect();
+            ctx.io_mut()
+                .send(CircuitFrame::Decodings(decodings))
+                .await?;
+        }
+
+        // Small circuits are garbled inline and sent in a single frame.
+        if is_small_circuit(&circ) {
+            let (output, gates) = match garble_small(&circ, delta, inputs, hash, privacy_free) {
+                Ok(garbled) => garbled,
+                Err(err) => {
+                    ctx.io_mut().send(CircuitFrame::Abort).await?;
+                    return Err(err);
+                }
+            };
+
+            let commitments = self.commitments(&output.outputs);
+            ctx.io_mut()
+                .send(CircuitFrame::Complete(gates, commitments))
+                .await?;
+
+            self.store_outputs(&refs, outputs, &output.outputs, output.hash)?;
+
+            return Ok((output.outputs, output.hash));
+        }
+
         // Garble the circuit in batches, streaming the encrypted gates from the worker thread.
+        let batch_size = self.config.batch_size;
+        let max_in_flight_batches = self.config.max_in_flight_batches;
         let span = span!(Level::TRACE, "worker");
         let GeneratorOutput {
             outputs: encoded_outputs,
@@ -312,43 +531,205 @@ impl Generator {
             .blocking(scoped!(move |ctx| async move {
                 let _enter = span.enter();
                 let mut gen = GeneratorCore::default();
-                let mut gen_iter = gen.generate_batched(&circ, delta, inputs)?;
+                let cancel = ctx.cancel_token().clone();
                 let io = ctx.io_mut();
 
+                let gen_iter = if privacy_free {
+                    gen.generate_privacy_free_batched(&circ, delta, inputs)
+                } else {
+                    gen.generate_batched(&circ, delta, inputs)
+                };
+
+                let mut gen_iter = match gen_iter {
+                    Ok(gen_iter) => gen_iter,
+                    Err(err) => {
+                        io.send(CircuitFrame::Abort).await?;
+                        return Err(GeneratorError::from(err));
+                    }
+                };
+
+                gen_iter.set_batch_size(batch_size);
                 if hash {
                     gen_iter.enable_hasher();
                 }
 
-                while let Some(batch) = gen_iter.by_ref().next() {
-                    io.feed(batch).await?;
-                }
+                feed_batches(io, gen_iter.by_ref(), max_in_flight_batches, &cancel).await?;
 
-                gen_iter.finish().map_err(GeneratorError::from)
+                match gen_iter.finish() {
+                    Ok(output) => Ok(output),
+                    Err(err) => {
+                        io.send(CircuitFrame::Abort).await?;
+                        Err(GeneratorError::from(err))
+                    }
+                }
             }))
             .await??;
 
-        if self.config.encoding_commitments {
-            let commitments: Vec<EncodingCommitment> = encoded_outputs
+        let commitments = self.commitments(&encoded_outputs);
+        ctx.io_mut().send(CircuitFrame::End(commitments)).await?;
+
+        self.store_outputs(&refs, outputs, &encoded_outputs, hash)?;
+
+        Ok((encoded_outputs, hash))
+    }
+
+    /// Returns the commitments to the output encodings, if enabled.
+    fn commitments(
+        &self,
+        encoded_outputs: &[EncodedValue<encoding_state::Full>],
+    ) -> Option<Vec<EncodingCommitment>> {
+        self.config.encoding_commitments.then(|| {
+            encoded_outputs
                 .iter()
                 .map(|output| output.commit())
-                .collect();
-            ctx.io_mut().feed(commitments).await?;
+                .collect()
+        })
+    }
+
+    /// Adds the output encodings of a garbled circuit to the memory and sets them as active.
+    fn store_outputs(
+        &self,
+        refs: &CircuitRefs,
+        outputs: &[ValueRef],
+        encoded_outputs: &[EncodedValue<encoding_state::Full>],
+        hash: Option<Hash>,
+    ) -> Result<(), GeneratorError> {
+        let mut state = self.state();
+        for (output, encoding) in outputs.iter().zip(encoded_outputs.iter()) {
+            state.memory.set_encoding(output, encoding.clone())?;
+            output.iter().for_each(|id| {
+                state.active.insert(id.clone());
+            });
+        }
+
+        state.garbled.insert(refs.clone(), hash);
+
+        Ok(())
+    }
+
+    /// Garbles a circuit ahead of time, producing an artifact which can be transferred to the
+    /// evaluator out-of-band.
+    ///
+    /// The artifact must be released with [`Generator::release_artifact`] and registered with
+    /// [`Evaluator::register_artifact`](crate::Evaluator::register_artifact) before the circuit
+    /// is executed. See [`crate::artifact`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble
+    /// * `inputs` - The inputs of the circuit
+    /// * `outputs` - The outputs of the circuit
+    /// * `hash` - Whether to hash the circuit
+    pub fn garble_artifact(
+        &self,
+        circ: &Circuit,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        hash: bool,
+    ) -> Result<GarbledArtifact, GeneratorError> {
+        let refs = CircuitRefs {
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+        };
+
+        let (delta, encoded_inputs) = {
+            let state = self.state();
+
+            if state.garbled.contains_key(&refs) {
+                return Err(GeneratorError::DuplicateCircuit);
+            }
+
+            let encoded_inputs = inputs
+                .iter()
+                .map(|value| {
+                    state
+                        .memory
+                        .get_encoding(value)
+                        .ok_or(GeneratorError::MissingEncoding(value.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            (state.encoder.delta(), encoded_inputs)
+        };
+
+        let mut gen = GeneratorCore::default();
+        let mut gen_iter = gen.generate(circ, delta, encoded_inputs)?;
+
+        if hash {
+            gen_iter.enable_hasher();
         }
 
-        ctx.io_mut().flush().await?;
+        let gates = gen_iter.by_ref().collect();
+        let GeneratorOutput {
+            outputs: encoded_outputs,
+            hash,
+        } = gen_iter.finish()?;
+
+        let commitments = self.config.encoding_commitments.then(|| {
+            encoded_outputs
+                .iter()
+                .map(|output| output.commit())
+                .collect::<Vec<EncodingCommitment>>()
+        });
+
+        let artifact = GarbledArtifact::new(
+            ArtifactHeader::new(circ),
+            GarbledCircuit { gates, commitments },
+        );
+        let digest = artifact.digest(&refs);
 
         // Add the outputs to the memory and set as active.
         let mut state = self.state();
-        for (output, encoding) in outputs.iter().zip(encoded_outputs.iter()) {
-            state.memory.set_encoding(output, encoding.clone())?;
+        for (output, encoding) in outputs.iter().zip(encoded_outputs) {
+            state.memory.set_encoding(output, encoding)?;
             output.iter().for_each(|id| {
                 state.active.insert(id.clone());
             });
         }
 
-        state.garbled.insert(refs, hash);
+        state.garbled.insert(refs.clone(), hash);
+        state.artifacts.insert(refs, digest);
 
-        Ok((encoded_outputs, hash))
+        Ok(artifact)
+    }
+
+    /// Releases a garbled artifact to the evaluator by sending its digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs of the circuit
+    /// * `outputs` - The outputs of the circuit
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub async fn release_artifact<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        self.guarded(self._release_artifact(ctx, inputs, outputs))
+            .await
+    }
+
+    async fn _release_artifact<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        let refs = CircuitRefs {
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+        };
+
+        let digest = self
+            .state()
+            .artifacts
+            .remove(&refs)
+            .ok_or(GeneratorError::MissingArtifact)?;
+
+        ctx.io_mut().send(digest).await?;
+
+        Ok(())
     }
 
     /// Send value decoding information to the evaluator.
@@ -361,6 +742,14 @@ impl Generator {
         &self,
         ctx: &mut Ctx,
         values: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        self.guarded(self._decode(ctx, values)).await
+    }
+
+    async fn _decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
     ) -> Result<(), GeneratorError> {
         let decodings = {
             let state = self.state();
@@ -380,6 +769,217 @@ impl Generator {
 
         Ok(())
     }
+
+    /// Sends digests of the decoding information for the provided values to the evaluator,
+    /// without revealing the decodings.
+    ///
+    /// The decodings can later be revealed using [`Generator::decode_committed`], at which
+    /// point the evaluator will verify them against the digests.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to commit the decodings of
+    pub async fn commit_decodings<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        self.guarded(self._commit_decodings(ctx, values)).await
+    }
+
+    async fn _commit_decodings<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        let digests = {
+            let mut state = self.state();
+            values
+                .iter()
+                .map(|value| {
+                    if state.decoding_decommitments.contains_key(value) {
+                        return Err(GeneratorError::DuplicateDecodingCommitment(value.clone()));
+                    }
+
+                    let decoding = state
+                        .memory
+                        .get_encoding(value)
+                        .ok_or(GeneratorError::MissingEncoding(value.clone()))?
+                        .decoding();
+
+                    let (decommitment, digest) = decoding.hash_commit();
+                    state
+                        .decoding_decommitments
+                        .insert(value.clone(), decommitment);
+
+                    Ok(digest)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        ctx.io_mut().send(digests).await?;
+
+        Ok(())
+    }
+
+    /// Sends the decoding information for values which were previously committed
+    /// using [`Generator::commit_decodings`] to the evaluator.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to decode
+    pub async fn decode_committed<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        self.guarded(self._decode_committed(ctx, values)).await
+    }
+
+    async fn _decode_committed<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), GeneratorError> {
+        let decommitments = {
+            let mut state = self.state();
+            values
+                .iter()
+                .map(|value| {
+                    state
+                        .decoding_decommitments
+                        .remove(value)
+                        .ok_or(GeneratorError::MissingDecodingCommitment(value.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        ctx.io_mut().send(decommitments).await?;
+
+        Ok(())
+    }
+
+    /// Re-randomizes the encoding of a value, assigning fresh labels for the same plaintext
+    /// to a new value.
+    ///
+    /// The new labels are sampled independently of the old ones. The generator sends a
+    /// translation table for every bit, which encrypts the new labels under the old ones, so the
+    /// evaluator learns its new active labels without the table or the new encoding revealing
+    /// the old encoding. The new value can then be handed to another thread without its logs
+    /// revealing the old encoding.
+    ///
+    /// The evaluator must call [`Evaluator::rerandomize`](crate::Evaluator::rerandomize).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to re-randomize.
+    /// * `new_value` - The value to assign the fresh encoding to. It must have the same type as
+    ///   `value` and must not already be encoded.
+    #[tracing::instrument(fields(thread = %ctx.id()), skip_all)]
+    pub async fn rerandomize<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        value: &ValueRef,
+        new_value: &ValueRef,
+    ) -> Result<(), GeneratorError> {
+        self.guarded(self._rerandomize(ctx, value, new_value)).await
+    }
+
+    async fn _rerandomize<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        value: &ValueRef,
+        new_value: &ValueRef,
+    ) -> Result<(), GeneratorError> {
+        if value.is_array() != new_value.is_array() || value.len() != new_value.len() {
+            return Err(GeneratorError::IncompatibleValues(
+                value.clone(),
+                new_value.clone(),
+            ));
+        }
+
+        let table = {
+            let mut state = self.state();
+
+            let encoding = state
+                .memory
+                .get_encoding(value)
+                .ok_or_else(|| GeneratorError::MissingEncoding(value.clone()))?;
+
+            if new_value.iter().any(|id| state.memory.contains(id)) {
+                return Err(GeneratorError::DuplicateEncoding(new_value.clone()));
+            }
+
+            let delta = state.encoder.delta();
+            let new_encoding = state.encode(new_value, &encoding.value_type());
+
+            // The evaluator holds the active encoding of the new value once it decrypts the
+            // translation table, so it must never be sent again.
+            state.active.extend(new_value.iter().cloned());
+
+            garble_translation(
+                new_value,
+                delta,
+                encoding.iter().map(AsRef::as_ref),
+                new_encoding.iter().map(AsRef::as_ref),
+            )
+        };
+
+        ctx.io_mut().send(table).await?;
+
+        Ok(())
+    }
+
+    /// Links a value to the encoding of another value, so that it can be used as an input
+    /// to a circuit without being transferred to the evaluator.
+    ///
+    /// Unlike [`Generator::rerandomize`] this requires no communication, as both values share
+    /// the same labels. The evaluator must call [`Evaluator::link`](crate::Evaluator::link).
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The value to link from. It must already be encoded.
+    /// * `to` - The value to link to. It must have the same type as `from` and must not be
+    ///   active.
+    pub fn link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), GeneratorError> {
+        self.check_link(from, to)?;
+
+        let mut state = self.state();
+
+        if !state.memory.link(from, to) {
+            return Err(GeneratorError::MissingEncoding(from.clone()));
+        }
+
+        // The evaluator already holds the active labels of `from`.
+        state.active.extend(to.iter().cloned());
+
+        Ok(())
+    }
+
+    /// Checks that `to` can be linked to `from` without modifying the generator.
+    pub fn check_link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), GeneratorError> {
+        if from.is_array() != to.is_array() || from.len() != to.len() {
+            return Err(GeneratorError::IncompatibleValues(from.clone(), to.clone()));
+        }
+
+        let state = self.state();
+
+        if to.iter().any(|id| state.active.contains(id)) {
+            return Err(GeneratorError::DuplicateEncoding(to.clone()));
+        }
+
+        if !from.iter().all(|id| state.memory.contains_encoding(id)) {
+            return Err(GeneratorError::MissingEncoding(from.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Poison for Generator {
+    fn is_poisoned(&self) -> bool {
+        self.poison.is_poisoned()
+    }
 }
 
 impl State {
@@ -443,3 +1043,144 @@ impl State {
         Ok(encoding)
     }
 }
+
+/// Feeds the batches to the sink as circuit frames.
+///
+/// After every `max_in_flight_batches` batches, waits for the buffered batches to be written
+/// before garbling any more, so that the generator can not outrun the connection.
+async fn feed_batches<S, I>(
+    io: &mut S,
+    batches: I,
+    max_in_flight_batches: usize,
+    cancel: &CancellationToken,
+) -> Result<(), GeneratorError>
+where
+    S: IoSink + Unpin,
+    I: Iterator<Item = EncryptedGateBatch>,
+{
+    let mut in_flight = 0;
+    for batch in batches {
+        cancel.check()?;
+        io.feed(CircuitFrame::Batch(batch)).await?;
+        in_flight += 1;
+
+        if in_flight == max_in_flight_batches {
+            cancel.run(io.flush()).await??;
+            in_flight = 0;
+        }
+    }
+
+    Ok(())
+}
+
+/// Garbles a small circuit inline, returning all of its encrypted gates in a single batch.
+fn garble_small(
+    circ: &Circuit,
+    delta: Delta,
+    inputs: Vec<EncodedValue<encoding_state::Full>>,
+    hash: bool,
+    privacy_free: bool,
+) -> Result<(GeneratorOutput, EncryptedGateBatch), GeneratorError> {
+    let mut gen = GeneratorCore::default();
+    let mut gen_iter = if privacy_free {
+        gen.generate_privacy_free_batched(circ, delta, inputs)?
+    } else {
+        gen.generate_batched(circ, delta, inputs)?
+    };
+
+    gen_iter.set_batch_size(circ.and_count().max(1));
+    if hash {
+        gen_iter.enable_hasher();
+    }
+
+    let gates = gen_iter
+        .next()
+        .unwrap_or_else(|| EncryptedGateBatch::new(Vec::new()));
+
+    Ok((gen_iter.finish()?, gates))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context as TaskContext, Poll},
+    };
+
+    use super::*;
+
+    /// A sink which records the number of frames fed before each flush.
+    #[derive(Default)]
+    struct FlushRecorder {
+        fed: usize,
+        flushes: Vec<usize>,
+    }
+
+    impl serio::Sink for FlushRecorder {
+        type Error = io::Error;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send<Item: serio::Serialize>(
+            mut self: Pin<&mut Self>,
+            _item: Item,
+        ) -> io::Result<()> {
+            self.fed += 1;
+            Ok(())
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+            let fed = self.fed;
+            self.flushes.push(fed);
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn batches(count: usize) -> impl Iterator<Item = EncryptedGateBatch> {
+        (0..count).map(|_| EncryptedGateBatch::new(Vec::new()))
+    }
+
+    #[tokio::test]
+    async fn test_feed_batches_flush_cadence() {
+        let mut io = FlushRecorder::default();
+
+        feed_batches(&mut io, batches(10), 4, &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(io.fed, 10);
+        assert_eq!(io.flushes, vec![4, 8]);
+    }
+
+    #[tokio::test]
+    async fn test_feed_batches_flush_every_batch() {
+        let mut io = FlushRecorder::default();
+
+        feed_batches(&mut io, batches(3), 1, &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(io.flushes, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_feed_batches_cancelled() {
+        let mut io = FlushRecorder::default();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let err = feed_batches(&mut io, batches(3), 1, &cancel)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, GeneratorError::Cancelled(_)));
+        assert_eq!(io.fed, 0);
+    }
+}
@@ -0,0 +1,465 @@
+//! Ideal garbling functionality.
+//!
+//! The ideal VM computes circuits in the clear, exposing the same [`Memory`], [`Execute`] and
+//! [`Decode`] interface as the garbled circuit VMs. It is intended for unit testing protocols
+//! built on top of a VM without paying for garbling.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit, CircuitError,
+};
+use mpz_common::{
+    executor::DummyExecutor,
+    ideal::{ideal_f2p, Alice, Bob},
+};
+
+use crate::{
+    config::{TransferPolicy, Visibility},
+    internal_circuits::build_share_circuit,
+    memory::ValueMemory,
+    value::{ValueId, ValueRef},
+    Decode, DecodeError, Execute, ExecutionError, Load, LoadError, Memory, MemoryError, Thread,
+};
+
+/// Errors that can occur when using the ideal VM.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum IdealVmError {
+    #[error("value has not been assigned by either party: {0:?}")]
+    Unassigned(ValueId),
+    #[error("share of shared value has not been assigned: {0:?}")]
+    UnassignedShare(ValueRef),
+    #[error(transparent)]
+    Circuit(#[from] CircuitError),
+}
+
+impl From<IdealVmError> for ExecutionError {
+    fn from(err: IdealVmError) -> Self {
+        ExecutionError::ProtocolError(Box::new(err))
+    }
+}
+
+impl From<IdealVmError> for DecodeError {
+    fn from(err: IdealVmError) -> Self {
+        DecodeError::ProtocolError(Box::new(err))
+    }
+}
+
+/// Values assigned by a party which are sent to the functionality.
+#[derive(Debug, Default)]
+struct Assigned {
+    values: Vec<(ValueId, Value)>,
+    shares: Vec<(ValueRef, Value)>,
+}
+
+fn exchange(_: &mut (), alice: Assigned, bob: Assigned) -> (Assigned, Assigned) {
+    (bob, alice)
+}
+
+#[derive(Debug)]
+enum Party {
+    Alice(Alice<()>),
+    Bob(Bob<()>),
+}
+
+/// Returns a connected pair of ideal VMs, the leader followed by the follower.
+pub fn ideal_vm() -> (IdealVm, IdealVm) {
+    let (alice, bob) = ideal_f2p(());
+
+    (
+        IdealVm::new(Party::Alice(alice)),
+        IdealVm::new(Party::Bob(bob)),
+    )
+}
+
+/// An ideal VM which computes circuits in the clear.
+///
+/// Values assigned by either party are revealed to the other party when they are committed or
+/// used as inputs to a circuit.
+#[derive(Debug)]
+pub struct IdealVm {
+    ctx: DummyExecutor,
+    party: Party,
+    memory: Mutex<ValueMemory>,
+    /// Plaintext of every value known to the functionality.
+    values: HashMap<ValueId, Value>,
+}
+
+impl IdealVm {
+    fn new(party: Party) -> Self {
+        Self {
+            ctx: DummyExecutor::default(),
+            party,
+            memory: Default::default(),
+            values: HashMap::new(),
+        }
+    }
+
+    fn memory(&self) -> MutexGuard<'_, ValueMemory> {
+        self.memory.lock().unwrap()
+    }
+
+    /// Sends the values assigned to the provided inputs to the functionality, receiving the
+    /// values assigned by the other party.
+    async fn transfer(&mut self, inputs: &[ValueRef]) -> Result<(), IdealVmError> {
+        let mut assigned = Assigned::default();
+        {
+            let mut memory = self.memory();
+            let drained = memory.drain_assigned(inputs);
+            assigned.values.extend(drained.public);
+            assigned.values.extend(drained.private);
+            assigned.values.extend(drained.private_direct);
+
+            for value in inputs {
+                if memory.is_shared(value) {
+                    if let Some(share) = memory.drain_share(value) {
+                        assigned.shares.push((value.clone(), share));
+                    }
+                }
+            }
+        }
+
+        let own_values = assigned.values.clone();
+        let own_shares = assigned.shares.clone();
+        let peer = match &mut self.party {
+            Party::Alice(alice) => alice.call(&mut self.ctx, assigned, exchange).await,
+            Party::Bob(bob) => bob.call(&mut self.ctx, assigned, exchange).await,
+        };
+
+        self.values.extend(own_values);
+        self.values.extend(peer.values);
+
+        for (value_ref, peer_share) in peer.shares {
+            let share = own_shares
+                .iter()
+                .find(|(own_ref, _)| own_ref == &value_ref)
+                .map(|(_, share)| share.clone())
+                .ok_or_else(|| IdealVmError::UnassignedShare(value_ref.clone()))?;
+
+            let circ = build_share_circuit(&[share.value_type()]);
+            let value = circ
+                .evaluate(&[share, peer_share])?
+                .pop()
+                .expect("share circuit has one output");
+
+            self.set(&value_ref, value);
+        }
+
+        Ok(())
+    }
+
+    fn get(&self, value_ref: &ValueRef) -> Result<Value, IdealVmError> {
+        let get = |id: &ValueId| {
+            self.values
+                .get(id)
+                .cloned()
+                .ok_or_else(|| IdealVmError::Unassigned(id.clone()))
+        };
+
+        match value_ref {
+            ValueRef::Value { id } => get(id),
+            ValueRef::Array(array) => array
+                .ids()
+                .iter()
+                .map(get)
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+        }
+    }
+
+    fn set(&mut self, value_ref: &ValueRef, value: Value) {
+        match (value_ref, value) {
+            (ValueRef::Value { id }, value) => {
+                self.values.insert(id.clone(), value);
+            }
+            (ValueRef::Array(array), Value::Array(elems)) => {
+                for (id, elem) in array.ids().iter().zip(elems) {
+                    self.values.insert(id.clone(), elem);
+                }
+            }
+            (ValueRef::Array(_), _) => unreachable!("array values are arrays"),
+        }
+    }
+}
+
+impl Thread for IdealVm {}
+
+impl Memory for IdealVm {
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        self.memory().new_input(id, typ, visibility)
+    }
+
+    fn new_inputs_batch(
+        &self,
+        inputs: &[(&str, ValueType, Visibility)],
+    ) -> Result<Vec<ValueRef>, MemoryError> {
+        let mut memory = self.memory();
+        inputs
+            .iter()
+            .map(|(id, typ, visibility)| memory.new_input(id, typ.clone(), *visibility))
+            .collect()
+    }
+
+    fn new_constant(&self, value: impl Into<Value>) -> Result<ValueRef, MemoryError> {
+        self.memory()
+            .get_or_insert_constant(value.into())
+            .map(|(value_ref, _)| value_ref)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.memory().new_output(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.memory().assign(value_ref, value.into())
+    }
+
+    fn assign_many(
+        &self,
+        values: impl IntoIterator<Item = (ValueRef, Value)>,
+    ) -> Result<(), MemoryError> {
+        let mut memory = self.memory();
+        values
+            .into_iter()
+            .try_for_each(|(value_ref, value)| memory.assign(&value_ref, value))
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        let mut memory = self.memory();
+        let value_ref = memory
+            .get_ref_by_id(id)
+            .ok_or_else(|| MemoryError::Undefined(id.to_string()))?
+            .clone();
+        memory.assign(&value_ref, value.into())
+    }
+
+    fn set_transfer_policy(
+        &self,
+        value_ref: &ValueRef,
+        policy: TransferPolicy,
+    ) -> Result<(), MemoryError> {
+        self.memory().set_transfer_policy(value_ref, policy)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.memory().get_ref_by_id(id).cloned()
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.memory().get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        let memory = self.memory();
+        let value_ref = memory.get_ref_by_id(id)?;
+        Some(memory.get_value_type(value_ref))
+    }
+}
+
+#[async_trait]
+impl Load for IdealVm {
+    async fn load(
+        &mut self,
+        _circ: Arc<Circuit>,
+        _inputs: &[ValueRef],
+        _outputs: &[ValueRef],
+    ) -> Result<(), LoadError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Execute for IdealVm {
+    async fn commit(&mut self, inputs: &[ValueRef]) -> Result<(), ExecutionError> {
+        self.transfer(inputs).await.map_err(ExecutionError::from)
+    }
+
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.transfer(inputs).await?;
+
+        let input_values = inputs
+            .iter()
+            .map(|value| self.get(value))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let output_values = circ.evaluate(&input_values).map_err(IdealVmError::from)?;
+
+        for (value_ref, value) in outputs.iter().zip(output_values) {
+            self.set(value_ref, value);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Decode for IdealVm {
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        values
+            .iter()
+            .map(|value| self.get(value).map_err(DecodeError::from))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
+
+    use super::*;
+
+    fn adder_circ() -> Arc<Circuit> {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a.wrapping_add(b);
+
+        builder.add_output(c);
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_ideal_vm_private_inputs() {
+        let (mut leader, mut follower) = ideal_vm();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = async {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            leader
+                .execute(
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    &[ciphertext_ref.clone()],
+                )
+                .await
+                .unwrap();
+
+            leader.decode(&[ciphertext_ref]).await.unwrap()
+        };
+
+        let follower_fut = async {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            follower
+                .execute(
+                    AES128.clone(),
+                    &[key_ref, msg_ref],
+                    &[ciphertext_ref.clone()],
+                )
+                .await
+                .unwrap();
+
+            follower.decode(&[ciphertext_ref]).await.unwrap()
+        };
+
+        let (leader_output, follower_output) = futures::join!(leader_fut, follower_fut);
+
+        let expected = AES128
+            .evaluate(&[key.into(), msg.into()])
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        assert_eq!(leader_output, vec![expected.clone()]);
+        assert_eq!(follower_output, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn test_ideal_vm_shared_inputs() {
+        let (mut leader, mut follower) = ideal_vm();
+        let circ = adder_circ();
+
+        let (leader_output, follower_output) = futures::join!(
+            async {
+                let a = leader.new_shared_input::<u8>("a").unwrap();
+                let b = leader.new_public_input::<u8>("b").unwrap();
+                let c = leader.new_output::<u8>("c").unwrap();
+
+                leader.assign(&a, 0b1010_1010u8).unwrap();
+                leader.assign(&b, 3u8).unwrap();
+
+                leader
+                    .execute(circ.clone(), &[a, b], &[c.clone()])
+                    .await
+                    .unwrap();
+                leader.decode(&[c]).await.unwrap()
+            },
+            async {
+                let a = follower.new_shared_input::<u8>("a").unwrap();
+                let b = follower.new_public_input::<u8>("b").unwrap();
+                let c = follower.new_output::<u8>("c").unwrap();
+
+                follower.assign(&a, 0b1010_0000u8).unwrap();
+                follower.assign(&b, 3u8).unwrap();
+
+                follower
+                    .execute(circ.clone(), &[a, b], &[c.clone()])
+                    .await
+                    .unwrap();
+                follower.decode(&[c]).await.unwrap()
+            }
+        );
+
+        assert_eq!(leader_output, vec![Value::U8(13)]);
+        assert_eq!(follower_output, vec![Value::U8(13)]);
+    }
+
+    #[tokio::test]
+    async fn test_ideal_vm_unassigned_input() {
+        let (mut leader, mut follower) = ideal_vm();
+        let circ = adder_circ();
+
+        let (leader_result, follower_result) = futures::join!(
+            async {
+                let a = leader.new_private_input::<u8>("a").unwrap();
+                let b = leader.new_blind_input::<u8>("b").unwrap();
+                let c = leader.new_output::<u8>("c").unwrap();
+
+                leader.assign(&a, 1u8).unwrap();
+
+                leader.execute(circ.clone(), &[a, b], &[c]).await
+            },
+            async {
+                let a = follower.new_blind_input::<u8>("a").unwrap();
+                let b = follower.new_private_input::<u8>("b").unwrap();
+                let c = follower.new_output::<u8>("c").unwrap();
+
+                follower.execute(circ.clone(), &[a, b], &[c]).await
+            }
+        );
+
+        assert!(leader_result.is_err());
+        assert!(follower_result.is_err());
+    }
+}
@@ -21,6 +21,25 @@ pub(crate) fn build_otp_circuit(inputs: &[ValueType]) -> Arc<Circuit> {
     Arc::new(circ)
 }
 
+/// Builds a circuit for reconstructing the provided values from the XOR of two shares.
+pub(crate) fn build_share_circuit(inputs: &[ValueType]) -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
+
+    for input_ty in inputs {
+        let share_0 = builder.add_input_by_type(input_ty.clone());
+        let share_1 = builder.add_input_by_type(input_ty.clone());
+
+        let share_0 = Tracer::new(builder.state(), share_0);
+        let share_1 = Tracer::new(builder.state(), share_1);
+        let value = share_0 ^ share_1;
+        builder.add_output(value);
+    }
+
+    let circ = builder.build().expect("circuit should be valid");
+
+    Arc::new(circ)
+}
+
 /// Builds a circuit for applying one-time pads to secret share the provided values.
 pub(crate) fn build_otp_shared_circuit(inputs: &[ValueType]) -> Arc<Circuit> {
     let builder = CircuitBuilder::new();
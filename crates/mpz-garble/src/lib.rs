@@ -8,19 +8,26 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use config::Visibility;
+use config::{TransferPolicy, Visibility};
 use mpz_circuits::{
-    types::{PrimitiveType, StaticValueType, Value, ValueType},
+    types::{Endianness, PrimitiveType, StaticValueType, TypeError, Value, ValueType},
     Circuit,
 };
 
+pub mod array;
+pub mod artifact;
 pub mod config;
 pub(crate) mod evaluator;
+pub(crate) mod frame;
 pub(crate) mod generator;
+#[cfg(any(test, feature = "ideal"))]
+pub mod ideal;
 pub(crate) mod internal_circuits;
 pub(crate) mod memory;
 pub mod ot;
 pub mod protocol;
+pub(crate) mod rerandomize;
+pub mod scheduler;
 pub mod value;
 
 pub use evaluator::{Evaluator, EvaluatorConfig, EvaluatorConfigBuilder, EvaluatorError};
@@ -69,6 +76,10 @@ pub enum MemoryError {
     Undefined(String),
     #[error("attempted to create an invalid array: {0}")]
     InvalidArray(String),
+    #[error("transfer policy can only be set for private or blind inputs: {0:?}")]
+    InvalidTransferPolicy(ValueId),
+    #[error("visibility is not supported by this protocol: {0:?}")]
+    UnsupportedVisibility(Visibility),
     #[error(transparent)]
     Assignment(#[from] AssignmentError),
 }
@@ -85,6 +96,14 @@ pub enum AssignmentError {
     /// Can not assign to an output value.
     #[error("can not assign to an output value: {0:?}")]
     Output(ValueId),
+    /// Attempted to assign bytes which do not represent a value of the expected type.
+    #[error("invalid bytes for {value:?}: {error}")]
+    Bytes {
+        /// The value reference.
+        value: ValueRef,
+        /// The conversion error.
+        error: TypeError,
+    },
     /// Attempted to assign a value with an invalid type.
     #[error("invalid value type {actual:?} for {value:?}, expected {expected:?}")]
     Type {
@@ -221,6 +240,49 @@ pub trait Memory {
         self.new_input_with_type(id, ValueType::new_array::<T>(len), Visibility::Blind)
     }
 
+    /// Adds a new shared input value, returning a reference to it.
+    fn new_shared_input<T: StaticValueType>(&self, id: &str) -> Result<ValueRef, MemoryError> {
+        self.new_input::<T>(id, Visibility::Shared)
+    }
+
+    /// Adds a new shared array input value, returning a reference to it.
+    fn new_shared_array_input<T: PrimitiveType>(
+        &self,
+        id: &str,
+        len: usize,
+    ) -> Result<ValueRef, MemoryError> {
+        self.new_input_with_type(id, ValueType::new_array::<T>(len), Visibility::Shared)
+    }
+
+    /// Adds a batch of new input values, returning references to them in the same order.
+    ///
+    /// This is equivalent to calling [`Memory::new_input_with_type`] for each input, but
+    /// implementations may add all of them at once, which is much faster when setting up
+    /// circuits with many inputs.
+    ///
+    /// If an input can not be added, the inputs before it remain in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The ID, type and visibility of each input.
+    fn new_inputs_batch(
+        &self,
+        inputs: &[(&str, ValueType, Visibility)],
+    ) -> Result<Vec<ValueRef>, MemoryError> {
+        inputs
+            .iter()
+            .map(|(id, typ, visibility)| self.new_input_with_type(id, typ.clone(), *visibility))
+            .collect()
+    }
+
+    /// Returns a reference to a public constant, adding it to the constant pool if it is not
+    /// present yet.
+    ///
+    /// Constants are identified by their value, so all parties derive the same reference without
+    /// any communication. A constant is only encoded and transferred the first time a circuit
+    /// uses it, later circuits referencing it reuse the encoding.
+    fn new_constant(&self, value: impl Into<Value>) -> Result<ValueRef, MemoryError>;
+
     /// Adds a new output value, returning a reference to it.
     fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError>;
 
@@ -244,6 +306,57 @@ pub trait Memory {
     /// Assigns a value.
     fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError>;
 
+    /// Assigns a batch of values.
+    ///
+    /// This is equivalent to calling [`Memory::assign`] for each value, but implementations may
+    /// assign all of them at once.
+    ///
+    /// If a value can not be assigned, the values before it remain assigned.
+    fn assign_many(
+        &self,
+        values: impl IntoIterator<Item = (ValueRef, Value)>,
+    ) -> Result<(), MemoryError> {
+        values
+            .into_iter()
+            .try_for_each(|(value_ref, value)| self.assign(&value_ref, value))
+    }
+
+    /// Assigns a value from its byte representation.
+    ///
+    /// Both parties must use the same convention, otherwise the value is wired differently by
+    /// each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_ref` - The value to assign.
+    /// * `bytes` - The bytes of the value.
+    /// * `endianness` - The convention the bytes are in.
+    fn assign_bytes(
+        &self,
+        value_ref: &ValueRef,
+        bytes: &[u8],
+        endianness: Endianness,
+    ) -> Result<(), MemoryError> {
+        let value = Value::from_bytes(&self.get_value_type(value_ref), bytes, endianness).map_err(
+            |error| AssignmentError::Bytes {
+                value: value_ref.clone(),
+                error,
+            },
+        )?;
+
+        self.assign(value_ref, value)
+    }
+
+    /// Sets the transfer policy of a private or blind input value.
+    ///
+    /// Both parties must set the same policy for a value before it is transferred.
+    /// See [`TransferPolicy`] for details.
+    fn set_transfer_policy(
+        &self,
+        value_ref: &ValueRef,
+        policy: TransferPolicy,
+    ) -> Result<(), MemoryError>;
+
     /// Returns a value if it exists.
     fn get_value(&self, id: &str) -> Option<ValueRef>;
 
@@ -255,7 +368,8 @@ pub trait Memory {
 
     /// Creates an array from the provided values.
     ///
-    /// All values must be of the same primitive type.
+    /// All values must be of the same primitive type, eg. elements of arrays returned by
+    /// [`ValueRef::elements`].
     fn array_from_values(&self, values: &[ValueRef]) -> Result<ValueRef, MemoryError> {
         if values.is_empty() {
             return Err(MemoryError::InvalidArray(
@@ -264,7 +378,6 @@ pub trait Memory {
         }
 
         let mut ids = Vec::with_capacity(values.len());
-        let elem_typ = self.get_value_type(&values[0]);
         for value in values {
             let ValueRef::Value { id } = value else {
                 return Err(MemoryError::InvalidArray(
@@ -272,6 +385,15 @@ pub trait Memory {
                 ));
             };
 
+            if self.get_value(id.as_ref()).as_ref() != Some(value) {
+                return Err(MemoryError::Undefined(id.as_ref().to_string()));
+            }
+
+            ids.push(id.clone());
+        }
+
+        let elem_typ = self.get_value_type(&values[0]);
+        for value in values {
             let value_typ = self.get_value_type(value);
 
             if value_typ != elem_typ {
@@ -280,8 +402,6 @@ pub trait Memory {
                     elem_typ, value_typ
                 )));
             };
-
-            ids.push(id.clone());
         }
 
         Ok(ValueRef::Array(ArrayRef::new(ids)))
@@ -363,6 +483,9 @@ pub trait Verify {
 #[async_trait]
 pub trait Decode {
     /// Decodes the provided values, returning the plaintext values to all parties.
+    ///
+    /// Only the provided values are revealed. Use [`ValueRef::slice`] or [`ValueRef::select`]
+    /// to decode part of an array.
     async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError>;
 }
 
@@ -4,7 +4,7 @@ use mpz_circuits::types::{Value, ValueType};
 use mpz_garble_core::{encoding_state::LabelState, EncodedValue};
 
 use crate::{
-    config::Visibility,
+    config::{TransferPolicy, Visibility},
     value::{ArrayRef, ValueId, ValueRef},
     AssignmentError, MemoryError,
 };
@@ -18,12 +18,17 @@ pub struct AssignedValues {
     pub private: Vec<(ValueId, Value)>,
     /// Blind values.
     pub blind: Vec<(ValueId, ValueType)>,
+    /// Private values which are revealed to the generator.
+    pub private_direct: Vec<(ValueId, Value)>,
+    /// Blind values which are revealed by the evaluator.
+    pub blind_direct: Vec<(ValueId, ValueType)>,
 }
 
 enum AssignedValue {
     Public(Value),
     Private(Value),
     Blind(ValueType),
+    Shared(Value),
 }
 
 enum ValueDetails {
@@ -58,6 +63,8 @@ pub struct ValueMemory {
     assigned: HashSet<ValueId>,
     /// Buffer containing assigned values
     assigned_buffer: HashMap<ValueId, AssignedValue>,
+    /// Transfer policies for private and blind inputs, if not oblivious
+    policies: HashMap<ValueId, TransferPolicy>,
 }
 
 opaque_debug::implement!(ValueMemory);
@@ -128,8 +135,7 @@ impl ValueMemory {
             ValueRef::Value { id: value_id }
         };
 
-        self.id_to_ref.insert(id.to_string(), value_ref.clone());
-        self.ref_to_id.insert(value_ref.clone(), id.to_string());
+        self.register(id, &value_ref);
 
         Ok(value_ref)
     }
@@ -170,12 +176,51 @@ impl ValueMemory {
             ValueRef::Value { id: value_id }
         };
 
-        self.id_to_ref.insert(id.to_string(), value_ref.clone());
-        self.ref_to_id.insert(value_ref.clone(), id.to_string());
+        self.register(id, &value_ref);
 
         Ok(value_ref)
     }
 
+    /// Returns a reference to a public constant, adding and assigning it if it is not present
+    /// yet.
+    ///
+    /// Also returns whether the constant was added, in which case it still needs to be encoded.
+    pub(crate) fn get_or_insert_constant(
+        &mut self,
+        value: Value,
+    ) -> Result<(ValueRef, bool), MemoryError> {
+        let id = ValueId::constant(&value);
+
+        if let Some(value_ref) = self.get_ref_by_id(id.as_ref()) {
+            return Ok((value_ref.clone(), false));
+        }
+
+        let value_ref = self.new_input(id.as_ref(), value.value_type(), Visibility::Public)?;
+        self.assign(&value_ref, value)?;
+
+        Ok((value_ref, true))
+    }
+
+    /// Registers a value reference under its ID.
+    ///
+    /// The elements of an array are registered as well, so they can be looked up on their own.
+    fn register(&mut self, id: &str, value_ref: &ValueRef) {
+        if let Some(elems) = value_ref.elements() {
+            for elem in elems {
+                let ValueRef::Value { id } = &elem else {
+                    unreachable!("elements are single values");
+                };
+                let id = id.as_ref().to_string();
+
+                self.id_to_ref.insert(id.clone(), elem.clone());
+                self.ref_to_id.insert(elem, id);
+            }
+        }
+
+        self.id_to_ref.insert(id.to_string(), value_ref.clone());
+        self.ref_to_id.insert(value_ref.clone(), id.to_string());
+    }
+
     /// Assigns a value to a value reference.
     ///
     /// # Arguments
@@ -231,6 +276,7 @@ impl ValueMemory {
                     Visibility::Public => AssignedValue::Public(value),
                     Visibility::Private => AssignedValue::Private(value),
                     Visibility::Blind => Err(AssignmentError::BlindInput(id.clone()))?,
+                    Visibility::Shared => AssignedValue::Shared(value),
                 };
 
                 if self.assigned.contains(id) {
@@ -245,6 +291,92 @@ impl ValueMemory {
         Ok(())
     }
 
+    /// Sets the transfer policy of a private or blind input value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_ref` - The value reference.
+    /// * `policy` - The transfer policy.
+    pub fn set_transfer_policy(
+        &mut self,
+        value_ref: &ValueRef,
+        policy: TransferPolicy,
+    ) -> Result<(), MemoryError> {
+        for id in value_ref.iter() {
+            match self.details.get(id) {
+                Some(ValueDetails::Input {
+                    visibility: Visibility::Private | Visibility::Blind,
+                    ..
+                }) => {}
+                Some(_) => return Err(MemoryError::InvalidTransferPolicy(id.clone())),
+                None => return Err(MemoryError::Undefined(id.as_ref().to_string())),
+            }
+        }
+
+        for id in value_ref.iter() {
+            match policy {
+                TransferPolicy::Oblivious => self.policies.remove(id),
+                TransferPolicy::Direct => self.policies.insert(id.clone(), policy),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Links an input value to another value, so that it takes on the encoding of `from`
+    /// instead of being transferred.
+    ///
+    /// The input is considered assigned afterwards and can not be assigned again.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The value to link from.
+    /// * `to` - The input value to link to. It must have the same type as `from` and must not
+    ///   have been assigned.
+    pub fn link(&mut self, from: &ValueRef, to: &ValueRef) -> Result<(), MemoryError> {
+        self.check_link(from, to)?;
+
+        for id in to.iter() {
+            self.assigned_buffer.remove(id);
+            self.assigned.insert(id.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `to` can be linked to `from` without modifying the memory.
+    pub fn check_link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), MemoryError> {
+        let expected = self.get_value_type(to);
+        let actual = self.get_value_type(from);
+        if expected != actual {
+            Err(AssignmentError::Type {
+                value: to.clone(),
+                expected,
+                actual,
+            })?
+        }
+
+        for id in to.iter() {
+            let details = self
+                .details
+                .get(id)
+                .expect("value is defined if reference exists");
+
+            if !matches!(details, ValueDetails::Input { .. }) {
+                Err(AssignmentError::Output(id.clone()))?
+            }
+
+            // Blind inputs are assigned when they are created, but they can still be linked as
+            // long as they have not been transferred.
+            let is_blind = matches!(self.assigned_buffer.get(id), Some(AssignedValue::Blind(_)));
+            if self.assigned.contains(id) && !is_blind {
+                Err(AssignmentError::Duplicate(id.clone()))?
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns a value reference by ID if it exists.
     pub fn get_ref_by_id(&self, id: &str) -> Option<&ValueRef> {
         self.id_to_ref.get(id)
@@ -275,19 +407,85 @@ impl ValueMemory {
         }
     }
 
+    /// Returns the assigned private value of a value reference, if it has not been drained yet.
+    pub(crate) fn get_assigned_private(&self, value_ref: &ValueRef) -> Option<Value> {
+        let get = |id: &ValueId| match self.assigned_buffer.get(id) {
+            Some(AssignedValue::Private(value)) => Some(value.clone()),
+            _ => None,
+        };
+
+        match value_ref {
+            ValueRef::Array(array) => array
+                .ids()
+                .iter()
+                .map(get)
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            ValueRef::Value { id } => get(id),
+        }
+    }
+
+    /// Returns whether a value is a shared input.
+    pub(crate) fn is_shared(&self, value_ref: &ValueRef) -> bool {
+        value_ref.iter().all(|id| {
+            matches!(
+                self.details.get(id),
+                Some(ValueDetails::Input {
+                    visibility: Visibility::Shared,
+                    ..
+                })
+            )
+        })
+    }
+
+    /// Returns whether the share of a shared input value has been assigned.
+    pub(crate) fn is_share_assigned(&self, value_ref: &ValueRef) -> bool {
+        value_ref
+            .iter()
+            .all(|id| matches!(self.assigned_buffer.get(id), Some(AssignedValue::Shared(_))))
+    }
+
+    /// Drains the assigned share of a shared input value from the buffer if it is present.
+    pub(crate) fn drain_share(&mut self, value_ref: &ValueRef) -> Option<Value> {
+        if !self.is_share_assigned(value_ref) {
+            return None;
+        }
+
+        let mut take = |id: &ValueId| match self.assigned_buffer.remove(id) {
+            Some(AssignedValue::Shared(value)) => value,
+            _ => unreachable!("share is checked to be assigned"),
+        };
+
+        match value_ref {
+            ValueRef::Array(array) => Some(Value::Array(array.ids().iter().map(take).collect())),
+            ValueRef::Value { id } => Some(take(id)),
+        }
+    }
+
     /// Drains assigned values from buffer if they are present.
     ///
-    /// Returns a tuple of public, private, and blind values.
+    /// Private and blind values are split according to their transfer policy. Shares of shared
+    /// values are left in the buffer.
     pub fn drain_assigned(&mut self, values: &[ValueRef]) -> AssignedValues {
         let mut public = Vec::new();
         let mut private = Vec::new();
         let mut blind = Vec::new();
+        let mut private_direct = Vec::new();
+        let mut blind_direct = Vec::new();
         for id in values.iter().flat_map(|value| value.iter()) {
+            if matches!(self.assigned_buffer.get(id), Some(AssignedValue::Shared(_))) {
+                continue;
+            }
+
             if let Some(value) = self.assigned_buffer.remove(id) {
+                let direct = matches!(self.policies.get(id), Some(TransferPolicy::Direct));
                 match value {
                     AssignedValue::Public(v) => public.push((id.clone(), v)),
+                    AssignedValue::Private(v) if direct => private_direct.push((id.clone(), v)),
                     AssignedValue::Private(v) => private.push((id.clone(), v)),
+                    AssignedValue::Blind(v) if direct => blind_direct.push((id.clone(), v)),
                     AssignedValue::Blind(v) => blind.push((id.clone(), v)),
+                    AssignedValue::Shared(_) => unreachable!("shares are not drained"),
                 }
             }
         }
@@ -296,6 +494,8 @@ impl ValueMemory {
             public,
             private,
             blind,
+            private_direct,
+            blind_direct,
         }
     }
 }
@@ -407,6 +607,43 @@ where
         Ok(())
     }
 
+    /// Sets the encoding of `to` to the encoding of `from`, replacing any existing encoding of
+    /// `to`.
+    ///
+    /// Returns `false` if `from` has no encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values do not have the same shape.
+    pub(crate) fn link(&mut self, from: &ValueRef, to: &ValueRef) -> bool {
+        let Some(encoding) = self.get_encoding(from) else {
+            return false;
+        };
+
+        match (to, encoding) {
+            (ValueRef::Value { id }, encoding) => {
+                self.encodings
+                    .insert(EncodingId::new(id.to_u64()), encoding);
+            }
+            (ValueRef::Array(array), EncodedValue::Array(encodings))
+                if array.len() == encodings.len() =>
+            {
+                for (id, encoding) in array.ids().iter().zip(encodings) {
+                    self.encodings
+                        .insert(EncodingId::new(id.to_u64()), encoding);
+                }
+            }
+            _ => panic!("can not link values of different shapes: {from:?} {to:?}"),
+        }
+
+        true
+    }
+
+    /// Returns whether an encoding is set for a value id.
+    pub(crate) fn contains_encoding(&self, id: &ValueId) -> bool {
+        self.encodings.contains_key(&id.to_u64().into())
+    }
+
     /// Get the encoding for a value id if it exists.
     pub(crate) fn get_encoding_by_id(&self, id: &ValueId) -> Option<EncodedValue<T>> {
         self.encodings.get(&id.to_u64().into()).cloned()
@@ -509,6 +746,140 @@ mod tests {
         assert!(matches!(err, MemoryError::DuplicateValueId(_)));
     }
 
+    #[test]
+    fn test_value_memory_transfer_policy() {
+        let mut memory = ValueMemory::default();
+
+        let public = memory
+            .new_input("public", u8::value_type(), Visibility::Public)
+            .unwrap();
+        let private = memory
+            .new_input("private", <[u8; 4]>::value_type(), Visibility::Private)
+            .unwrap();
+        let blind = memory
+            .new_input("blind", u8::value_type(), Visibility::Blind)
+            .unwrap();
+        let output = memory.new_output("output", u8::value_type()).unwrap();
+
+        let err = memory
+            .set_transfer_policy(&public, TransferPolicy::Direct)
+            .unwrap_err();
+        assert!(matches!(err, MemoryError::InvalidTransferPolicy(_)));
+
+        let err = memory
+            .set_transfer_policy(&output, TransferPolicy::Direct)
+            .unwrap_err();
+        assert!(matches!(err, MemoryError::InvalidTransferPolicy(_)));
+
+        memory
+            .set_transfer_policy(&private, TransferPolicy::Direct)
+            .unwrap();
+        memory
+            .set_transfer_policy(&blind, TransferPolicy::Direct)
+            .unwrap();
+
+        memory.assign(&public, 1u8.into()).unwrap();
+        memory.assign(&private, [2u8; 4].into()).unwrap();
+
+        let assigned = memory.drain_assigned(&[public, private, blind]);
+
+        assert_eq!(assigned.public.len(), 1);
+        assert!(assigned.private.is_empty());
+        assert!(assigned.blind.is_empty());
+        assert_eq!(assigned.private_direct.len(), 4);
+        assert_eq!(assigned.blind_direct.len(), 1);
+    }
+
+    #[test]
+    fn test_value_memory_shared() {
+        let mut memory = ValueMemory::default();
+
+        let shared = memory
+            .new_input("shared", <[u8; 4]>::value_type(), Visibility::Shared)
+            .unwrap();
+        let private = memory
+            .new_input("private", u8::value_type(), Visibility::Private)
+            .unwrap();
+
+        assert!(memory.is_shared(&shared));
+        assert!(!memory.is_shared(&private));
+        assert!(memory.drain_share(&shared).is_none());
+
+        memory.assign(&shared, [1u8; 4].into()).unwrap();
+        memory.assign(&private, 2u8.into()).unwrap();
+
+        // Shares are not drained with the other assigned values.
+        let assigned = memory.drain_assigned(&[shared.clone(), private]);
+        assert_eq!(assigned.private.len(), 1);
+
+        assert_eq!(memory.drain_share(&shared), Some([1u8; 4].into()));
+        assert!(memory.drain_share(&shared).is_none());
+    }
+
+    #[test]
+    fn test_value_memory_link() {
+        let mut memory = ValueMemory::default();
+
+        let output = memory
+            .new_output("output", <[u8; 4]>::value_type())
+            .unwrap();
+        let private = memory
+            .new_input("private", <[u8; 4]>::value_type(), Visibility::Private)
+            .unwrap();
+        let blind = memory
+            .new_input("blind", <[u8; 4]>::value_type(), Visibility::Blind)
+            .unwrap();
+        let assigned = memory
+            .new_input("assigned", <[u8; 4]>::value_type(), Visibility::Private)
+            .unwrap();
+        let other = memory
+            .new_input("other", u8::value_type(), Visibility::Private)
+            .unwrap();
+
+        memory.assign(&assigned, [1u8; 4].into()).unwrap();
+
+        memory.link(&output, &private).unwrap();
+        memory.link(&output, &blind).unwrap();
+
+        // Linked inputs are not transferred and can not be assigned.
+        let drained = memory.drain_assigned(&[private.clone(), blind]);
+        assert!(drained.private.is_empty());
+        assert!(drained.blind.is_empty());
+        assert!(memory.assign(&private, [0u8; 4].into()).is_err());
+
+        assert!(matches!(
+            memory.link(&output, &assigned),
+            Err(MemoryError::Assignment(AssignmentError::Duplicate(_)))
+        ));
+        assert!(matches!(
+            memory.link(&private, &output),
+            Err(MemoryError::Assignment(AssignmentError::Output(_)))
+        ));
+        assert!(matches!(
+            memory.link(&output, &other),
+            Err(MemoryError::Assignment(AssignmentError::Type { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_value_memory_elements() {
+        let mut memory = ValueMemory::default();
+
+        let value_ref = memory
+            .new_input("value", <[u8; 4]>::value_type(), Visibility::Private)
+            .unwrap();
+        let elem_ref = value_ref.get(2).unwrap();
+
+        // Elements are registered under their own ID.
+        let elem_id = memory.get_id_by_ref(&elem_ref).unwrap().to_string();
+        assert_eq!(memory.get_ref_by_id(&elem_id), Some(&elem_ref));
+        assert_eq!(memory.get_value_type(&elem_ref), ValueType::U8);
+
+        // Elements can be assigned on their own.
+        memory.assign(&elem_ref, Value::U8(42)).unwrap();
+        assert!(memory.assign(&value_ref, [0u8; 4].into()).is_err());
+    }
+
     #[rstest]
     #[case::bit(PhantomData::<bool>)]
     #[case::u8(PhantomData::<u8>)]
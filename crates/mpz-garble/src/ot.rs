@@ -117,6 +117,24 @@ pub trait OTVerifyEncoding<Ctx> {
         id: TransferId,
         input: Vec<EncodedValue<encoding_state::Full>>,
     ) -> Result<(), mpz_ot::OTError>;
+
+    /// Verifies the encodings of many transfers at once.
+    ///
+    /// The default implementation verifies each transfer in order.
+    async fn verify_batch(
+        &mut self,
+        ctx: &mut Ctx,
+        batch: Vec<(TransferId, Vec<EncodedValue<encoding_state::Full>>)>,
+    ) -> Result<(), mpz_ot::OTError>
+    where
+        Ctx: Send,
+    {
+        for (id, input) in batch {
+            self.verify(ctx, id, input).await?;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -137,6 +155,26 @@ where
 
         mpz_ot::VerifiableOTReceiver::verify(self, ctx, id, &blocks).await
     }
+
+    async fn verify_batch(
+        &mut self,
+        ctx: &mut Ctx,
+        batch: Vec<(TransferId, Vec<EncodedValue<encoding_state::Full>>)>,
+    ) -> Result<(), mpz_ot::OTError> {
+        let batch = batch
+            .into_iter()
+            .map(|(id, input)| {
+                let blocks: Vec<[Block; 2]> = input
+                    .into_iter()
+                    .flat_map(|v| v.iter_blocks().collect::<Vec<_>>())
+                    .collect();
+
+                (id, blocks)
+            })
+            .collect();
+
+        mpz_ot::VerifiableOTReceiver::verify_batch(self, ctx, batch).await
+    }
 }
 
 /// A trait for verifiable oblivious transfer of encodings.
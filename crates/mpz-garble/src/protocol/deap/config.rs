@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use derive_builder::Builder;
+use mpz_circuits::types::Endianness;
+use mpz_garble_core::DEFAULT_BATCH_SIZE;
+
+use crate::{
+    evaluator::{DEFAULT_DECODE_TIMEOUT, DEFAULT_GARBLE_STREAM_TIMEOUT},
+    generator::DEFAULT_MAX_IN_FLIGHT_BATCHES,
+};
+
+/// Default maximum duration to wait for each message from the other party during finalization.
+const DEFAULT_FINALIZE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// DEAP configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct DEAPConfig {
+    /// Whether to use privacy-free garbled circuits for proofs.
+    #[builder(default = "false", setter(custom))]
+    pub(crate) privacy_free_proofs: bool,
+    /// The maximum duration to wait for the next frame of a garbled circuit stream.
+    #[builder(default = "DEFAULT_GARBLE_STREAM_TIMEOUT")]
+    pub(crate) garble_stream_timeout: Duration,
+    /// The maximum duration to wait for decoding information from the other party.
+    #[builder(default = "DEFAULT_DECODE_TIMEOUT")]
+    pub(crate) decode_timeout: Duration,
+    /// The maximum duration to wait for each message from the other party during finalization.
+    ///
+    /// The follower waits for the leader to verify all of the follower's garbled circuits, so
+    /// this must be large enough to cover the leader's verification of the whole session.
+    #[builder(default = "DEFAULT_FINALIZE_TIMEOUT")]
+    pub(crate) finalize_timeout: Duration,
+    /// The number of encrypted gates sent to the other party in each batch.
+    ///
+    /// The parties may use different batch sizes.
+    #[builder(default = "DEFAULT_BATCH_SIZE")]
+    pub(crate) batch_size: usize,
+    /// The maximum number of batches buffered before waiting for them to be sent to the other
+    /// party.
+    ///
+    /// The parties may use different limits.
+    #[builder(default = "DEFAULT_MAX_IN_FLIGHT_BATCHES")]
+    pub(crate) max_in_flight_batches: usize,
+    /// The convention used to convert between bytes and values.
+    ///
+    /// Both parties must use the same convention, which can be checked with
+    /// [`DEAP::check_endianness`](super::DEAP::check_endianness).
+    #[builder(default)]
+    pub(crate) endianness: Endianness,
+}
+
+impl DEAPConfig {
+    /// Creates a new builder for the DEAP configuration.
+    pub fn builder() -> DEAPConfigBuilder {
+        DEAPConfigBuilder::default()
+    }
+}
+
+impl DEAPConfigBuilder {
+    /// Enable privacy-free garbled circuits in [`DEAP::execute_prove`](super::DEAP::execute_prove)
+    /// and [`DEAP::execute_verify`](super::DEAP::execute_verify).
+    ///
+    /// Privacy-free garbled circuits require half the bandwidth, but reveal all inputs of the
+    /// circuit to the prover, including private inputs of the verifier. Both parties must use the
+    /// same setting.
+    pub fn privacy_free_proofs(&mut self) -> &mut Self {
+        self.privacy_free_proofs = Some(true);
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.batch_size == Some(0) {
+            return Err("batch size must be non-zero".to_string());
+        }
+
+        if self.max_in_flight_batches == Some(0) {
+            return Err("max in-flight batches must be non-zero".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for DEAPConfig {
+    fn default() -> Self {
+        DEAPConfigBuilder::default().build().unwrap()
+    }
+}
@@ -1,3 +1,4 @@
+use mpz_circuits::types::Endianness;
 use mpz_garble_core::ValueError;
 
 use crate::{value::ValueRef, DecodeError, ExecutionError, LoadError, ProveError, VerifyError};
@@ -13,15 +14,30 @@ pub enum DEAPError {
     #[error("context error: {0}")]
     ContextError(#[from] mpz_common::ContextError),
     #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
     GeneratorError(#[from] crate::generator::GeneratorError),
     #[error(transparent)]
     EvaluatorError(#[from] crate::evaluator::EvaluatorError),
     #[error(transparent)]
     ValueError(#[from] ValueError),
+    #[error(transparent)]
+    MemoryError(#[from] crate::MemoryError),
     #[error("value does not exist: {0:?}")]
     ValueDoesNotExist(ValueRef),
     #[error("missing encoding for value: {0:?}")]
     MissingEncoding(ValueRef),
+    #[error("value is not an assigned private input: {0:?}")]
+    UncommittableInput(ValueRef),
+    #[error("share of shared input has not been assigned: {0:?}")]
+    UnassignedShare(ValueRef),
+    #[error("endianness mismatch: expected {expected}, peer uses {actual}")]
+    EndiannessMismatch {
+        expected: Endianness,
+        actual: Endianness,
+    },
     #[error(transparent)]
     FinalizationError(#[from] FinalizationError),
 }
@@ -40,6 +56,8 @@ pub enum FinalizationError {
     InvalidEqualityCheck,
     #[error("invalid proof")]
     InvalidProof,
+    #[error("invalid input commitment")]
+    InvalidInputCommitment,
 }
 
 /// Errors that can occur when accessing peer's encodings.
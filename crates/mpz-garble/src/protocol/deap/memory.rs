@@ -1,6 +1,10 @@
 use mpz_circuits::types::{Value, ValueType};
 
-use crate::{config::Visibility, value::ValueRef, Memory, MemoryError};
+use crate::{
+    config::{TransferPolicy, Visibility},
+    value::ValueRef,
+    Memory, MemoryError,
+};
 
 use super::DEAP;
 
@@ -12,7 +16,47 @@ impl Memory for DEAP {
         visibility: Visibility,
     ) -> Result<ValueRef, MemoryError> {
         let value_ref = self.state().memory.new_input(id, typ.clone(), visibility)?;
-        self.gen.generate_input_encoding(&value_ref, &typ);
+        // Shared values are encoded when they are reconstructed from their shares.
+        if !matches!(visibility, Visibility::Shared) {
+            self.gen.generate_input_encoding(&value_ref, &typ);
+        }
+        Ok(value_ref)
+    }
+
+    fn new_inputs_batch(
+        &self,
+        inputs: &[(&str, ValueType, Visibility)],
+    ) -> Result<Vec<ValueRef>, MemoryError> {
+        let mut value_refs = Vec::with_capacity(inputs.len());
+        let mut encode = Vec::with_capacity(inputs.len());
+
+        let mut state = self.state();
+        let result = inputs.iter().try_for_each(|(id, typ, visibility)| {
+            let value_ref = state.memory.new_input(id, typ.clone(), *visibility)?;
+            // Shared values are encoded when they are reconstructed from their shares.
+            if !matches!(visibility, Visibility::Shared) {
+                encode.push((value_ref.clone(), typ.clone()));
+            }
+            value_refs.push(value_ref);
+            Ok::<_, MemoryError>(())
+        });
+        drop(state);
+
+        // The inputs added before an error must be encoded as well, as they remain in memory.
+        self.gen.generate_input_encodings(&encode);
+
+        result.map(|_| value_refs)
+    }
+
+    fn new_constant(&self, value: impl Into<Value>) -> Result<ValueRef, MemoryError> {
+        let value = value.into();
+        let typ = value.value_type();
+
+        let (value_ref, added) = self.state().memory.get_or_insert_constant(value)?;
+        if added {
+            self.gen.generate_input_encoding(&value_ref, &typ);
+        }
+
         Ok(value_ref)
     }
 
@@ -24,6 +68,16 @@ impl Memory for DEAP {
         self.state().memory.assign(value_ref, value.into())
     }
 
+    fn assign_many(
+        &self,
+        values: impl IntoIterator<Item = (ValueRef, Value)>,
+    ) -> Result<(), MemoryError> {
+        let mut state = self.state();
+        values
+            .into_iter()
+            .try_for_each(|(value_ref, value)| state.memory.assign(&value_ref, value))
+    }
+
     fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
         let mut state = self.state();
         let value_ref = state
@@ -34,6 +88,14 @@ impl Memory for DEAP {
         state.memory.assign(&value_ref, value.into())
     }
 
+    fn set_transfer_policy(
+        &self,
+        value_ref: &ValueRef,
+        policy: TransferPolicy,
+    ) -> Result<(), MemoryError> {
+        self.state().memory.set_transfer_policy(value_ref, policy)
+    }
+
     fn get_value(&self, id: &str) -> Option<ValueRef> {
         self.state().memory.get_ref_by_id(id).cloned()
     }
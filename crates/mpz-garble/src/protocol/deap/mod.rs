@@ -2,42 +2,49 @@
 //!
 //! For more information, see the [DEAP specification](https://docs.tlsnotary.org/mpc/deap.html).
 
+mod config;
 mod error;
 mod memory;
 pub mod mock;
 mod vm;
 
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     mem,
     ops::DerefMut,
     sync::{Arc, Mutex},
 };
 
 use futures::TryFutureExt;
+use itybity::IntoBits;
 use mpz_circuits::{
-    types::{Value, ValueType},
+    types::{Endianness, Value, ValueType},
     Circuit,
 };
-use mpz_common::{try_join, Context, Counter, ThreadId};
+use mpz_common::{
+    timeout::{timeout, Phase},
+    try_join, Context, Counter, Poison, ThreadId,
+};
 use mpz_core::{
     commit::{Decommitment, HashCommit},
     hash::{Hash, SecureHash},
 };
-use mpz_garble_core::EqualityCheck;
+use mpz_garble_core::{encoding_state, EncodedValue, EqualityCheck};
 use rand::thread_rng;
 use serio::{stream::IoStreamExt, SinkExt};
 
 use crate::{
+    artifact::GarbledArtifact,
     config::{Role, Visibility},
     evaluator::{Evaluator, EvaluatorConfigBuilder},
     generator::{Generator, GeneratorConfigBuilder},
-    internal_circuits::{build_otp_circuit, build_otp_shared_circuit},
+    internal_circuits::{build_otp_circuit, build_otp_shared_circuit, build_share_circuit},
     memory::ValueMemory,
     ot::{OTReceiveEncoding, OTSendEncoding, OTVerifyEncoding},
     value::ValueRef,
 };
 
+pub use config::{DEAPConfig, DEAPConfigBuilder};
 pub use error::{DEAPError, PeerEncodingsError};
 pub use vm::{DEAPThread, PeerEncodings};
 
@@ -47,6 +54,7 @@ use self::error::FinalizationError;
 #[derive(Debug)]
 pub struct DEAP {
     role: Role,
+    config: DEAPConfig,
     gen: Generator,
     ev: Evaluator,
     state: Mutex<State>,
@@ -56,7 +64,8 @@ pub struct DEAP {
 #[derive(Debug, Default)]
 struct State {
     memory: ValueMemory,
-    logs: HashMap<ThreadId, ThreadLog>,
+    /// Logs of each thread, ordered by thread id so that finalization is deterministic.
+    logs: BTreeMap<ThreadId, ThreadLog>,
 }
 
 #[derive(Debug, Default)]
@@ -70,15 +79,27 @@ struct ThreadLog {
     ///
     /// (Expected eq. check value, hash commitment from leader)
     eq_commitments: Vec<(EqualityCheck, Hash)>,
-    /// Proof decommitments withheld by the leader
+    /// Proof decommitments withheld by the prover
     /// prior to finalization
     ///
     /// GC output hash decommitment
     proof_decommitments: Vec<Decommitment<Hash>>,
-    /// Proof commitments from the leader
+    /// Proof commitments from the prover
     ///
-    /// (Expected GC output hash, hash commitment from leader)
+    /// (Expected GC output hash, hash commitment from prover)
     proof_commitments: Vec<(Hash, Hash)>,
+    /// Input commitment decommitments withheld by the follower
+    /// prior to finalization
+    input_decommitments: Vec<Decommitment<Vec<Vec<bool>>>>,
+    /// Input commitment decommitments withheld by the leader
+    /// prior to finalization
+    ///
+    /// The leader commits to the active labels of its inputs in its own garbled circuits.
+    label_decommitments: Vec<Decommitment<Vec<EncodedValue<encoding_state::Active>>>>,
+    /// Input commitments from the other party
+    ///
+    /// (Committed values, hash commitment from the other party)
+    input_commitments: Vec<(Vec<ValueRef>, Hash)>,
 }
 
 #[derive(Default)]
@@ -90,23 +111,50 @@ struct FinalizedState {
     ///
     /// (Expected eq. check value, hash commitment from leader)
     eq_commitments: Vec<(EqualityCheck, Hash)>,
-    /// Proof decommitments withheld by the leader
+    /// Proof decommitments withheld by the prover
     /// prior to finalization
     ///
     /// GC output hash decommitment
     proof_decommitments: Vec<Decommitment<Hash>>,
-    /// Proof commitments from the leader
+    /// Proof commitments from the prover
     ///
-    /// (Expected GC output hash, hash commitment from leader)
+    /// (Expected GC output hash, hash commitment from prover)
     proof_commitments: Vec<(Hash, Hash)>,
+    /// Input commitment decommitments withheld by the follower
+    /// prior to finalization
+    input_decommitments: Vec<Decommitment<Vec<Vec<bool>>>>,
+    /// Input commitment decommitments withheld by the leader
+    /// prior to finalization
+    ///
+    /// The leader commits to the active labels of its inputs in its own garbled circuits.
+    label_decommitments: Vec<Decommitment<Vec<EncodedValue<encoding_state::Active>>>>,
+    /// Input commitments from the other party
+    ///
+    /// (Committed values, hash commitment from the other party)
+    input_commitments: Vec<(Vec<ValueRef>, Hash)>,
 }
 
 impl DEAP {
     /// Creates a new DEAP protocol instance.
     pub fn new(role: Role, encoder_seed: [u8; 32]) -> Self {
+        Self::new_with_config(role, encoder_seed, DEAPConfig::default())
+    }
+
+    /// Creates a new DEAP protocol instance with the provided configuration.
+    pub fn new_with_config(role: Role, encoder_seed: [u8; 32], config: DEAPConfig) -> Self {
         let mut gen_config_builder = GeneratorConfigBuilder::default();
         let mut ev_config_builder = EvaluatorConfigBuilder::default();
 
+        // Reject non-canonical batch padding from the other party.
+        ev_config_builder.strict_padding();
+
+        ev_config_builder
+            .garble_stream_timeout(config.garble_stream_timeout)
+            .decode_timeout(config.decode_timeout);
+        gen_config_builder
+            .batch_size(config.batch_size)
+            .max_in_flight_batches(config.max_in_flight_batches);
+
         match role {
             Role::Leader => {
                 // Sends commitments to output encodings.
@@ -128,6 +176,7 @@ impl DEAP {
 
         Self {
             role,
+            config,
             gen,
             ev,
             state: Mutex::new(State::default()),
@@ -139,6 +188,55 @@ impl DEAP {
         self.state.lock().unwrap()
     }
 
+    /// Returns the convention used to convert between bytes and values.
+    pub fn endianness(&self) -> Endianness {
+        self.config.endianness
+    }
+
+    /// Checks that the other party uses the same convention to convert between bytes and
+    /// values.
+    ///
+    /// Inputs assigned with different conventions are wired differently by each party, which
+    /// otherwise only surfaces as incorrect outputs.
+    pub async fn check_endianness<Ctx: Context>(&self, ctx: &mut Ctx) -> Result<(), DEAPError> {
+        let expected = self.config.endianness;
+
+        ctx.io_mut().send(expected).await?;
+        let actual: Endianness = ctx.io_mut().expect_next().await?;
+
+        if actual != expected {
+            return Err(DEAPError::EndiannessMismatch { expected, actual });
+        }
+
+        Ok(())
+    }
+
+    /// Links an input value to the encoding of another value, usually the output of a
+    /// previously executed circuit.
+    ///
+    /// The linked input is not transferred when it is used in a later circuit, so intermediate
+    /// values of multi-round protocols never have to be decoded or sent again. Both parties
+    /// must link the same values.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The value to link from. It must already be encoded.
+    /// * `to` - The input value to link to. It must have the same type as `from` and must not
+    ///   have been assigned.
+    pub fn link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), DEAPError> {
+        // Check everything before applying, so that a rejected link leaves the memory, generator
+        // and evaluator consistent with each other.
+        self.state().memory.check_link(from, to)?;
+        self.gen.check_link(from, to)?;
+        self.ev.check_link(from, to)?;
+
+        self.state().memory.link(from, to)?;
+        self.gen.link(from, to)?;
+        self.ev.link(from, to)?;
+
+        Ok(())
+    }
+
     /// Commits the provided input values.
     ///
     /// Values which are already committed are ignored.
@@ -183,6 +281,97 @@ impl DEAP {
         Ok(())
     }
 
+    /// Exchanges hash commitments to the parties' private inputs.
+    ///
+    /// This must be called before the inputs are committed with OT, ie. before
+    /// [`commit`](Self::commit) or [`execute`](Self::execute), so that neither party can choose
+    /// its inputs adaptively based on later protocol messages.
+    ///
+    /// # Notes
+    ///
+    /// Both commitments are opened during [`finalize`](Self::finalize). The leader verifies the
+    /// follower's commitment against the inputs the follower actually used. The leader's inputs
+    /// stay private, so it commits to the active labels of its inputs in its own garbled
+    /// circuits, which the follower verifies against the labels it received as the evaluator.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - This party's private inputs to commit to, which must be assigned.
+    /// * `peer_values` - The other party's private inputs it commits to, in the same order.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn commit_inputs<Ctx>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+        peer_values: &[ValueRef],
+    ) -> Result<(), DEAPError>
+    where
+        Ctx: Context,
+    {
+        let assigned = {
+            let state = self.state();
+            values
+                .iter()
+                .map(|value| {
+                    state
+                        .memory
+                        .get_assigned_private(value)
+                        .map(|assigned| (value, assigned, state.memory.get_value_type(value)))
+                        .ok_or_else(|| DEAPError::UncommittableInput(value.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        match self.role {
+            Role::Leader => {
+                // The leader's inputs stay private, so it commits to the active labels of its
+                // inputs in its own garbled circuits instead, which the follower receives as the
+                // evaluator.
+                let labels = assigned
+                    .into_iter()
+                    .map(|(value, assigned, typ)| {
+                        self.gen.generate_input_encoding(value, &typ);
+                        self.gen
+                            .get_encoding(value)
+                            .expect("encoding was generated")
+                            .select(assigned)
+                            .map_err(|_| DEAPError::UncommittableInput(value.clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let (decommitment, commitment) = labels.hash_commit();
+
+                ctx.io_mut().send(commitment).await?;
+                let peer_commitment: Hash = ctx.io_mut().expect_next().await?;
+
+                let mut state = self.state();
+                let log = state.log(ctx.id());
+                log.label_decommitments.push(decommitment);
+                log.input_commitments
+                    .push((peer_values.to_vec(), peer_commitment));
+            }
+            Role::Follower => {
+                let bits = assigned
+                    .into_iter()
+                    .map(|(_, assigned, _)| assigned.into_lsb0_vec())
+                    .collect::<Vec<_>>();
+
+                let (decommitment, commitment) = bits.hash_commit();
+
+                let peer_commitment: Hash = ctx.io_mut().expect_next().await?;
+                ctx.io_mut().send(commitment).await?;
+
+                let mut state = self.state();
+                let log = state.log(ctx.id());
+                log.input_decommitments.push(decommitment);
+                log.input_commitments
+                    .push((peer_values.to_vec(), peer_commitment));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Commits the provided values for proving.
     ///
     /// Values which are already committed are ignored.
@@ -272,8 +461,78 @@ impl DEAP {
         Ok(())
     }
 
+    /// Garbles a circuit ahead of time, producing an artifact which can be transferred to the
+    /// peer out-of-band.
+    ///
+    /// Both parties garble their own artifact and exchange them, after which they must call
+    /// [`DEAP::register_artifact`] before executing the circuit. See [`crate::artifact`] for
+    /// details.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    pub fn garble_artifact(
+        &self,
+        circ: &Circuit,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<GarbledArtifact, DEAPError> {
+        self.gen
+            .garble_artifact(circ, inputs, outputs, false)
+            .map_err(DEAPError::from)
+    }
+
+    /// Releases this party's artifact and registers the artifact received from the peer.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit of the artifacts.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `artifact` - The artifact garbled by the peer.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn register_artifact<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        artifact: GarbledArtifact,
+    ) -> Result<(), DEAPError> {
+        match self.role {
+            Role::Leader => {
+                try_join!(
+                    ctx,
+                    self.gen
+                        .release_artifact(ctx, inputs, outputs)
+                        .map_err(DEAPError::from),
+                    self.ev
+                        .register_artifact(ctx, circ.clone(), inputs, outputs, artifact)
+                        .map_err(DEAPError::from)
+                )??;
+            }
+            Role::Follower => {
+                try_join!(
+                    ctx,
+                    self.ev
+                        .register_artifact(ctx, circ.clone(), inputs, outputs, artifact)
+                        .map_err(DEAPError::from),
+                    self.gen
+                        .release_artifact(ctx, inputs, outputs)
+                        .map_err(DEAPError::from)
+                )??;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Executes a circuit.
     ///
+    /// Shared inputs are first reconstructed from the shares assigned by each party.
+    ///
     /// # Arguments
     ///
     /// * `id` - The ID of the circuit.
@@ -295,6 +554,121 @@ impl DEAP {
         ot_send: &mut OTS,
         ot_recv: &mut OTR,
     ) -> Result<(), DEAPError>
+    where
+        Ctx: Context,
+        OTS: OTSendEncoding<Ctx> + Send,
+        OTR: OTReceiveEncoding<Ctx> + Send,
+    {
+        self.reconstruct_shared(ctx, inputs, ot_send, ot_recv)
+            .await?;
+
+        self.execute_inner(ctx, circ, inputs, outputs, ot_send, ot_recv)
+            .await
+    }
+
+    /// Reconstructs the shared inputs which have not been encoded yet by executing a circuit
+    /// which XORs the shares of both parties.
+    async fn reconstruct_shared<Ctx, OTS, OTR>(
+        &self,
+        ctx: &mut Ctx,
+        inputs: &[ValueRef],
+        ot_send: &mut OTS,
+        ot_recv: &mut OTR,
+    ) -> Result<(), DEAPError>
+    where
+        Ctx: Context,
+        OTS: OTSendEncoding<Ctx> + Send,
+        OTR: OTReceiveEncoding<Ctx> + Send,
+    {
+        let shared = {
+            let state = self.state();
+            let mut shared: Vec<ValueRef> = Vec::new();
+            for value in inputs {
+                if state.memory.is_shared(value)
+                    && self.gen.get_encoding(value).is_none()
+                    && !shared.contains(value)
+                {
+                    shared.push(value.clone());
+                }
+            }
+            shared
+        };
+
+        if shared.is_empty() {
+            return Ok(());
+        }
+
+        let id = self.state().log(ctx.id()).operation_counter.next();
+        let mut share_refs = Vec::with_capacity(2 * shared.len());
+        let mut share_typs = Vec::with_capacity(shared.len());
+        {
+            let mut state = self.state();
+
+            // Check every share before draining any, so that a missing share leaves memory
+            // untouched.
+            if let Some(value) = shared
+                .iter()
+                .find(|value| !state.memory.is_share_assigned(value))
+            {
+                return Err(DEAPError::UnassignedShare(value.clone()));
+            }
+
+            for (idx, value) in shared.iter().enumerate() {
+                let share = state
+                    .memory
+                    .drain_share(value)
+                    .expect("share is checked to be assigned");
+                let typ = state.memory.get_value_type(value);
+
+                let [leader_visibility, follower_visibility] = match self.role {
+                    Role::Leader => [Visibility::Private, Visibility::Blind],
+                    Role::Follower => [Visibility::Blind, Visibility::Private],
+                };
+
+                for (name, visibility) in [
+                    ("share_0", leader_visibility),
+                    ("share_1", follower_visibility),
+                ] {
+                    let share_ref = state
+                        .memory
+                        .new_input(
+                            &format!("{}/{id}/{idx}/{name}", ctx.id()),
+                            typ.clone(),
+                            visibility,
+                        )
+                        .expect("share id is unique");
+
+                    if let Visibility::Private = visibility {
+                        state
+                            .memory
+                            .assign(&share_ref, share.clone())
+                            .expect("share should assign");
+                    }
+
+                    self.gen.generate_input_encoding(&share_ref, &typ);
+                    share_refs.push(share_ref);
+                }
+
+                share_typs.push(typ);
+            }
+        }
+
+        let circ = build_share_circuit(&share_typs);
+
+        self.execute_inner(ctx, circ, &share_refs, &shared, ot_send, ot_recv)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_inner<Ctx, OTS, OTR>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        ot_send: &mut OTS,
+        ot_recv: &mut OTR,
+    ) -> Result<(), DEAPError>
     where
         Ctx: Context,
         OTS: OTSendEncoding<Ctx> + Send,
@@ -362,7 +736,13 @@ impl DEAP {
     ///
     /// # Notes
     ///
-    /// This function can only be called by the leader.
+    /// Either party can act as the prover, in which case the other party must call
+    /// [`execute_verify`](Self::execute_verify).
+    ///
+    /// When the follower is the prover, the leader's garbled circuit is authenticated
+    /// using the output encoding commitments but it is never revealed to the follower.
+    /// As with the dual-execution equality check, a malicious leader can learn at most
+    /// whether the proof succeeded.
     ///
     /// This function does _not_ prove the output right away,
     /// instead the proof is committed to and decommitted later during
@@ -391,12 +771,6 @@ impl DEAP {
         Ctx: Context,
         OTR: OTReceiveEncoding<Ctx> + Send,
     {
-        if matches!(self.role, Role::Follower) {
-            return Err(DEAPError::RoleError(
-                "DEAP follower can not act as the prover".to_string(),
-            ))?;
-        }
-
         let assigned_values = self.state().memory.drain_assigned(inputs);
 
         // The prover only acts as the evaluator for ZKPs instead of
@@ -406,19 +780,28 @@ impl DEAP {
             .map_err(DEAPError::from)
             .await?;
 
-        self.ev
-            .evaluate(ctx, circ, inputs, outputs)
-            .map_err(DEAPError::from)
-            .await?;
+        if self.config.privacy_free_proofs {
+            self.ev
+                .evaluate_privacy_free(ctx, circ, inputs, outputs)
+                .map_err(DEAPError::from)
+                .await?;
+        } else {
+            self.ev
+                .evaluate(ctx, circ, inputs, outputs)
+                .map_err(DEAPError::from)
+                .await?;
+        }
 
         Ok(())
     }
 
-    /// Executes the circuit where only the follower is the generator.
+    /// Executes the circuit where only this party is the generator, verifying the proof
+    /// of the other party.
     ///
     /// # Notes
     ///
-    /// This function can only be called by the follower.
+    /// Either party can act as the verifier, in which case the other party must call
+    /// [`execute_prove`](Self::execute_prove).
     ///
     /// # Arguments
     ///
@@ -442,12 +825,6 @@ impl DEAP {
         Ctx: Context,
         OTS: OTSendEncoding<Ctx> + Send,
     {
-        if matches!(self.role, Role::Leader) {
-            return Err(DEAPError::RoleError(
-                "DEAP leader can not act as the verifier".to_string(),
-            ))?;
-        }
-
         let assigned_values = self.state().memory.drain_assigned(inputs);
 
         // The verifier only acts as the generator for ZKPs instead of
@@ -457,15 +834,22 @@ impl DEAP {
             .map_err(DEAPError::from)
             .await?;
 
-        self.gen
-            .generate(ctx, circ.clone(), inputs, outputs, false)
-            .map_err(DEAPError::from)
-            .await?;
+        if self.config.privacy_free_proofs {
+            self.gen
+                .generate_privacy_free(ctx, circ.clone(), inputs, outputs, false)
+                .map_err(DEAPError::from)
+                .await?;
+        } else {
+            self.gen
+                .generate(ctx, circ.clone(), inputs, outputs, false)
+                .map_err(DEAPError::from)
+                .await?;
+        }
 
         Ok(())
     }
 
-    /// Sends a commitment to the provided values, proving them to the follower upon finalization.
+    /// Sends a commitment to the provided values, proving them to the other party upon finalization.
     #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
     pub async fn defer_prove<Ctx>(
         &self,
@@ -857,6 +1241,9 @@ impl DEAP {
     /// and ZK proofs from the session. The follower then verifies the decommitments
     /// and that all the equality checks and proofs were performed as expected.
     ///
+    /// Finally, the follower decommits to any ZK proofs it made during the session,
+    /// which the leader verifies.
+    ///
     /// # Arguments
     ///
     /// - `channel` - The channel to communicate with the other party
@@ -869,7 +1256,7 @@ impl DEAP {
     ) -> Result<Option<[u8; 32]>, DEAPError>
     where
         Ctx: Context,
-        OT: OTVerifyEncoding<Ctx>,
+        OT: OTVerifyEncoding<Ctx> + Send,
     {
         if self.finalized {
             return Err(FinalizationError::AlreadyFinalized)?;
@@ -882,21 +1269,56 @@ impl DEAP {
             eq_decommitments,
             proof_commitments,
             proof_decommitments,
+            input_commitments,
+            input_decommitments,
+            label_decommitments,
         } = self.state().finalize_state();
 
+        let finalize_timeout = self.config.finalize_timeout;
+
         match self.role {
             Role::Leader => {
-                // Receive the encoder seed from the follower.
-                let encoder_seed: [u8; 32] = ctx.io_mut().expect_next().await?;
+                // Receive the encoder seed and the input decommitments from the follower.
+                let encoder_seed: [u8; 32] = timeout(
+                    Phase::Finalize,
+                    finalize_timeout,
+                    ctx.io_mut().expect_next(),
+                )
+                .await??;
+                let follower_input_decommitments: Vec<Decommitment<Vec<Vec<bool>>>> = timeout(
+                    Phase::Finalize,
+                    finalize_timeout,
+                    ctx.io_mut().expect_next(),
+                )
+                .await??;
 
                 // Verify all oblivious transfers, garbled circuits and decodings
                 // sent by the follower.
                 self.ev.verify(ctx, encoder_seed, ot).await?;
 
-                // Reveal the equality checks and proofs to the follower.
+                // Verify the follower used the inputs it committed to.
+                self.verify_input_commitments(
+                    encoder_seed,
+                    &follower_input_decommitments,
+                    &input_commitments,
+                )?;
+
+                // Reveal the equality checks, input commitments and proofs to the follower.
                 ctx.io_mut().feed(eq_decommitments).await?;
+                ctx.io_mut().feed(label_decommitments).await?;
                 ctx.io_mut().send(proof_decommitments).await?;
 
+                // Receive the proofs from the follower.
+                let follower_proof_decommitments: Vec<Decommitment<Hash>> = timeout(
+                    Phase::Finalize,
+                    finalize_timeout,
+                    ctx.io_mut().expect_next(),
+                )
+                .await??;
+
+                // Verify all proofs.
+                verify_proofs(&follower_proof_decommitments, &proof_commitments)?;
+
                 Ok(Some(encoder_seed))
             }
             Role::Follower => {
@@ -906,13 +1328,30 @@ impl DEAP {
                     .try_into()
                     .expect("encoder seed is 32 bytes");
 
-                ctx.io_mut().send(encoder_seed).await?;
-
-                // Receive the equality checks and proofs from the leader.
-                let eq_decommitments: Vec<Decommitment<EqualityCheck>> =
-                    ctx.io_mut().expect_next().await?;
-                let proof_decommitments: Vec<Decommitment<Hash>> =
-                    ctx.io_mut().expect_next().await?;
+                ctx.io_mut().feed(encoder_seed).await?;
+                ctx.io_mut().send(input_decommitments).await?;
+
+                // Receive the equality checks, input commitments and proofs from the leader.
+                let eq_decommitments: Vec<Decommitment<EqualityCheck>> = timeout(
+                    Phase::Finalize,
+                    finalize_timeout,
+                    ctx.io_mut().expect_next(),
+                )
+                .await??;
+                let leader_label_decommitments: Vec<
+                    Decommitment<Vec<EncodedValue<encoding_state::Active>>>,
+                > = timeout(
+                    Phase::Finalize,
+                    finalize_timeout,
+                    ctx.io_mut().expect_next(),
+                )
+                .await??;
+                let leader_proof_decommitments: Vec<Decommitment<Hash>> = timeout(
+                    Phase::Finalize,
+                    finalize_timeout,
+                    ctx.io_mut().expect_next(),
+                )
+                .await??;
 
                 // Verify all equality checks.
                 for (decommitment, (expected_check, commitment)) in
@@ -927,18 +1366,14 @@ impl DEAP {
                     }
                 }
 
+                // Verify the leader used the inputs it committed to.
+                self.verify_label_commitments(&leader_label_decommitments, &input_commitments)?;
+
                 // Verify all proofs.
-                for (decommitment, (expected_digest, commitment)) in
-                    proof_decommitments.iter().zip(proof_commitments.iter())
-                {
-                    decommitment
-                        .verify(commitment)
-                        .map_err(FinalizationError::from)?;
+                verify_proofs(&leader_proof_decommitments, &proof_commitments)?;
 
-                    if decommitment.data() != expected_digest {
-                        return Err(FinalizationError::InvalidProof)?;
-                    }
-                }
+                // Reveal the proofs to the leader.
+                ctx.io_mut().send(proof_decommitments).await?;
 
                 Ok(None)
             }
@@ -949,6 +1384,101 @@ impl DEAP {
     pub(crate) fn ev(&self) -> &Evaluator {
         &self.ev
     }
+
+    /// Verifies the follower's input decommitments against the inputs it used, which
+    /// are decoded using the follower's encoder seed.
+    fn verify_input_commitments(
+        &self,
+        encoder_seed: [u8; 32],
+        decommitments: &[Decommitment<Vec<Vec<bool>>>],
+        commitments: &[(Vec<ValueRef>, Hash)],
+    ) -> Result<(), FinalizationError> {
+        if decommitments.len() != commitments.len() {
+            return Err(FinalizationError::InvalidInputCommitment);
+        }
+
+        let gen = Generator::new(
+            GeneratorConfigBuilder::default().build().unwrap(),
+            encoder_seed,
+        );
+
+        for (decommitment, (values, commitment)) in decommitments.iter().zip(commitments) {
+            decommitment.verify(commitment)?;
+
+            if decommitment.data().len() != values.len() {
+                return Err(FinalizationError::InvalidInputCommitment);
+            }
+
+            for (bits, value) in decommitment.data().iter().zip(values) {
+                let typ = self.state().memory.get_value_type(value);
+                gen.generate_input_encoding(value, &typ);
+
+                let (Some(full), Some(active)) =
+                    (gen.get_encoding(value), self.ev.get_encoding(value))
+                else {
+                    return Err(FinalizationError::InvalidInputCommitment);
+                };
+
+                let used = full
+                    .decode(&active)
+                    .map_err(|_| FinalizationError::InvalidInputCommitment)?;
+
+                if &used.into_lsb0_vec() != bits {
+                    return Err(FinalizationError::InvalidInputCommitment);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the leader's input decommitments against the active labels of its inputs
+    /// received from the leader.
+    fn verify_label_commitments(
+        &self,
+        decommitments: &[Decommitment<Vec<EncodedValue<encoding_state::Active>>>],
+        commitments: &[(Vec<ValueRef>, Hash)],
+    ) -> Result<(), FinalizationError> {
+        if decommitments.len() != commitments.len() {
+            return Err(FinalizationError::InvalidInputCommitment);
+        }
+
+        for (decommitment, (values, commitment)) in decommitments.iter().zip(commitments) {
+            decommitment.verify(commitment)?;
+
+            if decommitment.data().len() != values.len() {
+                return Err(FinalizationError::InvalidInputCommitment);
+            }
+
+            for (labels, value) in decommitment.data().iter().zip(values) {
+                if self.ev.get_encoding(value).as_ref() != Some(labels) {
+                    return Err(FinalizationError::InvalidInputCommitment);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies the proof decommitments from the other party against the expected digests.
+fn verify_proofs(
+    decommitments: &[Decommitment<Hash>],
+    commitments: &[(Hash, Hash)],
+) -> Result<(), FinalizationError> {
+    if decommitments.len() != commitments.len() {
+        return Err(FinalizationError::InvalidProof);
+    }
+
+    for (decommitment, (expected_digest, commitment)) in decommitments.iter().zip(commitments) {
+        decommitment.verify(commitment)?;
+
+        if decommitment.data() != expected_digest {
+            return Err(FinalizationError::InvalidProof);
+        }
+    }
+
+    Ok(())
 }
 
 impl State {
@@ -994,16 +1524,17 @@ impl State {
 
     /// Drain the states to be finalized.
     fn finalize_state(&mut self) -> FinalizedState {
-        let mut logs = mem::take(&mut self.logs).into_iter().collect::<Vec<_>>();
-        logs.sort_by_cached_key(|(id, _)| id.clone());
-
-        logs.into_iter()
-            .fold(FinalizedState::default(), |mut state, (_, log)| {
+        mem::take(&mut self.logs)
+            .into_values()
+            .fold(FinalizedState::default(), |mut state, log| {
                 let ThreadLog {
                     eq_commitments,
                     eq_decommitments,
                     proof_commitments,
                     proof_decommitments,
+                    input_decommitments,
+                    label_decommitments,
+                    input_commitments,
                     ..
                 } = log;
 
@@ -1011,20 +1542,39 @@ impl State {
                 state.eq_decommitments.extend(eq_decommitments);
                 state.proof_commitments.extend(proof_commitments);
                 state.proof_decommitments.extend(proof_decommitments);
+                state.input_decommitments.extend(input_decommitments);
+                state.label_decommitments.extend(label_decommitments);
+                state.input_commitments.extend(input_commitments);
 
                 state
             })
     }
 }
 
+impl Poison for DEAP {
+    fn is_poisoned(&self) -> bool {
+        self.gen.is_poisoned() || self.ev.is_poisoned()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
+    use mpz_circuits::{
+        circuits::AES128,
+        ops::WrappingAdd,
+        types::{BitOrder, ByteOrder},
+        CircuitBuilder,
+    };
     use mpz_common::executor::test_st_executor;
     use mpz_core::Block;
+    use mpz_garble_core::DEFAULT_BATCH_SIZE;
     use mpz_ot::ideal::ot::ideal_ot;
+    use rstest::rstest;
 
-    use crate::Memory;
+    use crate::{
+        config::TransferPolicy, generator::DEFAULT_MAX_IN_FLIGHT_BATCHES, AssignmentError, Memory,
+        MemoryError,
+    };
 
     use super::*;
 
@@ -1041,14 +1591,34 @@ mod tests {
         Arc::new(builder.build().unwrap())
     }
 
+    #[rstest]
+    #[case::default_batch_size(
+        DEFAULT_BATCH_SIZE,
+        DEFAULT_BATCH_SIZE,
+        DEFAULT_MAX_IN_FLIGHT_BATCHES
+    )]
+    #[case::mixed_batch_size(1000, 17, DEFAULT_MAX_IN_FLIGHT_BATCHES)]
+    #[case::single_in_flight_batch(17, 17, 1)]
     #[tokio::test]
-    async fn test_deap() {
+    async fn test_deap(
+        #[case] leader_batch_size: usize,
+        #[case] follower_batch_size: usize,
+        #[case] max_in_flight_batches: usize,
+    ) {
         let (mut ctx_a, mut ctx_b) = test_st_executor(8);
         let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
         let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
 
-        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
-        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+        let config = |batch_size| {
+            DEAPConfig::builder()
+                .batch_size(batch_size)
+                .max_in_flight_batches(max_in_flight_batches)
+                .build()
+                .unwrap()
+        };
+        let mut leader = DEAP::new_with_config(Role::Leader, [42u8; 32], config(leader_batch_size));
+        let mut follower =
+            DEAP::new_with_config(Role::Follower, [69u8; 32], config(follower_batch_size));
 
         let key = [42u8; 16];
         let msg = [69u8; 16];
@@ -1124,7 +1694,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_deap_commit() {
+    async fn test_deap_constant_pool() {
         let (mut ctx_a, mut ctx_b) = test_st_executor(8);
         let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
         let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
@@ -1132,27 +1702,962 @@ mod tests {
         let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
         let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
 
-        let key = [42u8; 16];
-        let msg = [69u8; 16];
-
-        let leader_fut = {
-            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+        let circ = adder_circ();
 
-            leader.assign(&key_ref, key).unwrap();
+        let one = leader.new_constant(1u8).unwrap();
+        assert_eq!(leader.new_constant(1u8).unwrap(), one);
+        assert_ne!(leader.new_constant(1u16).unwrap(), one);
+        assert_ne!(leader.new_constant(2u8).unwrap(), one);
 
-            async move {
+        let leader_fut = {
+            let circ = circ.clone();
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+            let d_ref = leader.new_output::<u8>("d").unwrap();
+
+            leader.assign(&a_ref, 1u8).unwrap();
+
+            async move {
+                // Both circuits reference the same pooled constant.
+                for (input, output) in [(&a_ref, &c_ref), (&b_ref, &d_ref)] {
+                    let one = leader.new_constant(1u8).unwrap();
+                    leader
+                        .execute(
+                            &mut ctx_a,
+                            circ.clone(),
+                            &[input.clone(), one],
+                            &[output.clone()],
+                            &mut leader_ot_send,
+                            &mut leader_ot_recv,
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                let outputs = leader.decode(&mut ctx_a, &[c_ref, d_ref]).await.unwrap();
+
+                leader
+                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let circ = circ.clone();
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+            let d_ref = follower.new_output::<u8>("d").unwrap();
+
+            follower.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                for (input, output) in [(&a_ref, &c_ref), (&b_ref, &d_ref)] {
+                    let one = follower.new_constant(1u8).unwrap();
+                    follower
+                        .execute(
+                            &mut ctx_b,
+                            circ.clone(),
+                            &[input.clone(), one],
+                            &[output.clone()],
+                            &mut follower_ot_send,
+                            &mut follower_ot_recv,
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                let outputs = follower.decode(&mut ctx_b, &[c_ref, d_ref]).await.unwrap();
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output, vec![Value::U8(2), Value::U8(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_deap_decode_slice() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let prefix_ref = ciphertext_ref.slice(..4).unwrap();
+                let last_ref = ciphertext_ref.select(&[15]).unwrap();
+                let elem_ref = ciphertext_ref.get(4).unwrap();
+                let elems = ciphertext_ref.elements().unwrap();
+                let regrouped_ref = leader
+                    .array_from_values(&[elems[6].clone(), elems[5].clone()])
+                    .unwrap();
+
+                let outputs = leader
+                    .decode(&mut ctx_a, &[prefix_ref, last_ref, elem_ref, regrouped_ref])
+                    .await
+                    .unwrap();
+
+                leader
+                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let prefix_ref = ciphertext_ref.slice(..4).unwrap();
+                let last_ref = ciphertext_ref.select(&[15]).unwrap();
+                let elem_ref = ciphertext_ref.get(4).unwrap();
+                let elems = ciphertext_ref.elements().unwrap();
+                let regrouped_ref = follower
+                    .array_from_values(&[elems[6].clone(), elems[5].clone()])
+                    .unwrap();
+
+                let outputs = follower
+                    .decode(&mut ctx_b, &[prefix_ref, last_ref, elem_ref, regrouped_ref])
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+
+        let expected: [u8; 16] = AES128
+            .evaluate(&[key.into(), msg.into()])
+            .unwrap()
+            .pop()
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let prefix: Vec<u8> = leader_output[0].clone().try_into().unwrap();
+        let last: Vec<u8> = leader_output[1].clone().try_into().unwrap();
+
+        let regrouped: Vec<u8> = leader_output[3].clone().try_into().unwrap();
+
+        assert_eq!(prefix, expected[..4]);
+        assert_eq!(last, expected[15..]);
+        assert_eq!(leader_output[2], Value::U8(expected[4]));
+        assert_eq!(regrouped, [expected[6], expected[5]]);
+    }
+
+    /// Records the order in which the OTs are verified.
+    ///
+    /// The recorder wraps the OT receiver rather than implementing [`OTVerifyEncoding`]
+    /// directly, which would overlap with its blanket implementation.
+    struct RecordingVerifier<'a, T> {
+        inner: &'a mut T,
+        ids: Vec<mpz_ot::TransferId>,
+    }
+
+    #[async_trait::async_trait]
+    impl<'a, Ctx, T> mpz_ot::OTReceiver<Ctx, bool, Block> for RecordingVerifier<'a, T>
+    where
+        Ctx: Context,
+        T: mpz_ot::OTReceiver<Ctx, bool, Block> + Send,
+    {
+        async fn receive(
+            &mut self,
+            ctx: &mut Ctx,
+            choices: &[bool],
+        ) -> Result<mpz_ot::OTReceiverOutput<Block>, mpz_ot::OTError> {
+            self.inner.receive(ctx, choices).await
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<'a, Ctx, T> mpz_ot::VerifiableOTReceiver<Ctx, bool, Block, [Block; 2]>
+        for RecordingVerifier<'a, T>
+    where
+        Ctx: Context,
+        T: mpz_ot::VerifiableOTReceiver<Ctx, bool, Block, [Block; 2]> + Send,
+    {
+        async fn accept_reveal(&mut self, ctx: &mut Ctx) -> Result<(), mpz_ot::OTError> {
+            self.inner.accept_reveal(ctx).await
+        }
+
+        async fn verify(
+            &mut self,
+            ctx: &mut Ctx,
+            id: mpz_ot::TransferId,
+            msgs: &[[Block; 2]],
+        ) -> Result<(), mpz_ot::OTError> {
+            self.ids.push(id);
+            self.inner.verify(ctx, id, msgs).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deap_verify_ot_order() {
+        const COUNT: usize = 8;
+
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let circ = adder_circ();
+
+        let leader_fut = {
+            let circ = circ.clone();
+            let refs = (0..COUNT)
+                .map(|i| {
+                    let a = leader.new_private_input::<u8>(&format!("a/{i}")).unwrap();
+                    let b = leader.new_blind_input::<u8>(&format!("b/{i}")).unwrap();
+                    let c = leader.new_output::<u8>(&format!("c/{i}")).unwrap();
+                    leader.assign(&a, i as u8).unwrap();
+                    (a, b, c)
+                })
+                .collect::<Vec<_>>();
+
+            async move {
+                for (a, b, c) in &refs {
+                    leader
+                        .execute(
+                            &mut ctx_a,
+                            circ.clone(),
+                            &[a.clone(), b.clone()],
+                            &[c.clone()],
+                            &mut leader_ot_send,
+                            &mut leader_ot_recv,
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                let mut verifier = RecordingVerifier {
+                    inner: &mut leader_ot_recv,
+                    ids: Vec::new(),
+                };
+
+                leader.finalize(&mut ctx_a, &mut verifier).await.unwrap();
+
+                verifier.ids
+            }
+        };
+
+        let follower_fut = {
+            let refs = (0..COUNT)
+                .map(|i| {
+                    let a = follower.new_blind_input::<u8>(&format!("a/{i}")).unwrap();
+                    let b = follower.new_private_input::<u8>(&format!("b/{i}")).unwrap();
+                    let c = follower.new_output::<u8>(&format!("c/{i}")).unwrap();
+                    follower.assign(&b, 1u8).unwrap();
+                    (a, b, c)
+                })
+                .collect::<Vec<_>>();
+
+            async move {
+                for (a, b, c) in &refs {
+                    follower
+                        .execute(
+                            &mut ctx_b,
+                            circ.clone(),
+                            &[a.clone(), b.clone()],
+                            &[c.clone()],
+                            &mut follower_ot_send,
+                            &mut follower_ot_recv,
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+            }
+        };
+
+        let (ids, _) = tokio::join!(leader_fut, follower_fut);
+
+        // The OTs are verified in the order they were performed, regardless of how they are
+        // stored internally.
+        assert_eq!(ids.len(), COUNT);
+        assert!(ids.windows(2).all(|ids| ids[0] < ids[1]));
+    }
+
+    #[tokio::test]
+    async fn test_deap_direct_transfer() {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader
+                .set_transfer_policy(&msg_ref, TransferPolicy::Direct)
+                .unwrap();
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader.decode(&mut ctx_a, &[ciphertext_ref]).await.unwrap();
+
+                leader
+                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower
+                .set_transfer_policy(&msg_ref, TransferPolicy::Direct)
+                .unwrap();
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower
+                    .decode(&mut ctx_b, &[ciphertext_ref])
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        let mut expected = msg.into();
+        Aes128::new_from_slice(&key)
+            .unwrap()
+            .encrypt_block(&mut expected);
+        let expected: [u8; 16] = expected.into();
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output[0], Value::from(expected));
+    }
+
+    #[tokio::test]
+    async fn test_deap_link() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let circ = adder_circ();
+
+        let leader_fut = {
+            let circ = circ.clone();
+            let a = leader.new_private_input::<u8>("a").unwrap();
+            let b = leader.new_blind_input::<u8>("b").unwrap();
+            let c = leader.new_output::<u8>("c").unwrap();
+            let d = leader.new_private_input::<u8>("d").unwrap();
+            let e = leader.new_output::<u8>("e").unwrap();
+            let f = leader.new_private_input::<u8>("f").unwrap();
+            let g = leader.new_output::<u8>("g").unwrap();
+
+            leader.assign(&a, 1u8).unwrap();
+            leader.assign(&d, 10u8).unwrap();
+
+            async move {
+                // The output of the first circuit is used directly as an input to the second.
+                for (inputs, output) in [([a, b], c.clone()), ([c.clone(), d], e.clone())] {
+                    leader
+                        .execute(
+                            &mut ctx_a,
+                            circ.clone(),
+                            &inputs,
+                            &[output],
+                            &mut leader_ot_send,
+                            &mut leader_ot_recv,
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                leader.link(&c, &f).unwrap();
+                assert!(leader.assign(&f, 0u8).is_err());
+
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        circ,
+                        &[f, e.clone()],
+                        &[g.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader.decode(&mut ctx_a, &[e, g]).await.unwrap();
+
+                leader
+                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let a = follower.new_blind_input::<u8>("a").unwrap();
+            let b = follower.new_private_input::<u8>("b").unwrap();
+            let c = follower.new_output::<u8>("c").unwrap();
+            let d = follower.new_blind_input::<u8>("d").unwrap();
+            let e = follower.new_output::<u8>("e").unwrap();
+            let f = follower.new_blind_input::<u8>("f").unwrap();
+            let g = follower.new_output::<u8>("g").unwrap();
+
+            follower.assign(&b, 2u8).unwrap();
+
+            async move {
+                for (inputs, output) in [([a, b], c.clone()), ([c.clone(), d], e.clone())] {
+                    follower
+                        .execute(
+                            &mut ctx_b,
+                            circ.clone(),
+                            &inputs,
+                            &[output],
+                            &mut follower_ot_send,
+                            &mut follower_ot_recv,
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                follower.link(&c, &f).unwrap();
+
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        circ,
+                        &[f, e.clone()],
+                        &[g.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower.decode(&mut ctx_b, &[e, g]).await.unwrap();
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output, vec![Value::from(13u8), Value::from(16u8)]);
+    }
+
+    #[test]
+    fn test_deap_link_rejected() {
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+
+        // The output has not been computed yet, so it has no encoding to link from.
+        let c = leader.new_output::<u8>("c").unwrap();
+        let f = leader.new_private_input::<u8>("f").unwrap();
+
+        assert!(leader.link(&c, &f).is_err());
+
+        // A rejected link must not leave the input marked as assigned.
+        leader.assign(&f, 1u8).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_deap_commit() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader
+                    .commit(
+                        &mut ctx_a,
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader.decode(&mut ctx_a, &[ciphertext_ref]).await.unwrap();
+
+                leader
+                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .commit(
+                        &mut ctx_b,
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower
+                    .decode(&mut ctx_b, &[ciphertext_ref])
+                    .await
+                    .unwrap();
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+    }
+
+    /// Runs DEAP with input commitments, returning the finalization results of both parties.
+    ///
+    /// If `cheater` is set, that party commits to a different value than it uses.
+    async fn run_commit_inputs(
+        cheater: Option<Role>,
+    ) -> (
+        Result<Option<[u8; 32]>, DEAPError>,
+        Result<Option<[u8; 32]>, DEAPError>,
+    ) {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let decoy_ref = leader.new_private_input::<[u8; 16]>("decoy").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+            leader.assign(&decoy_ref, [0u8; 16]).unwrap();
+
+            let committed_ref = if cheater == Some(Role::Leader) {
+                decoy_ref
+            } else {
+                key_ref.clone()
+            };
+
+            async move {
+                leader
+                    .commit_inputs(&mut ctx_a, &[committed_ref], &[msg_ref.clone()])
+                    .await?;
+
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await?;
+
+                leader.decode(&mut ctx_a, &[ciphertext_ref]).await?;
+
+                leader.finalize(&mut ctx_a, &mut leader_ot_recv).await
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let decoy_ref = follower.new_private_input::<[u8; 16]>("decoy").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+            follower.assign(&decoy_ref, [0u8; 16]).unwrap();
+
+            let committed_ref = if cheater == Some(Role::Follower) {
+                decoy_ref
+            } else {
+                msg_ref.clone()
+            };
+
+            async move {
+                follower
+                    .commit_inputs(&mut ctx_b, &[committed_ref], &[key_ref.clone()])
+                    .await?;
+
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await?;
+
+                follower.decode(&mut ctx_b, &[ciphertext_ref]).await?;
+
+                follower.finalize(&mut ctx_b, &mut follower_ot_recv).await
+            }
+        };
+
+        tokio::join!(leader_fut, follower_fut)
+    }
+
+    #[rstest]
+    #[case::same(Endianness::default(), true)]
+    #[case::different(Endianness::new(BitOrder::Msb0, ByteOrder::Big), false)]
+    #[tokio::test]
+    async fn test_deap_check_endianness(#[case] follower_endianness: Endianness, #[case] ok: bool) {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let follower = DEAP::new_with_config(
+            Role::Follower,
+            [69u8; 32],
+            DEAPConfig::builder()
+                .endianness(follower_endianness)
+                .build()
+                .unwrap(),
+        );
+
+        let (leader_result, follower_result) = tokio::join!(
+            leader.check_endianness(&mut ctx_a),
+            follower.check_endianness(&mut ctx_b)
+        );
+
+        if ok {
+            leader_result.unwrap();
+            follower_result.unwrap();
+        } else {
+            assert!(matches!(
+                leader_result,
+                Err(DEAPError::EndiannessMismatch { .. })
+            ));
+            assert!(matches!(
+                follower_result,
+                Err(DEAPError::EndiannessMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_deap_assign_bytes() {
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let endianness = Endianness::new(BitOrder::Lsb0, ByteOrder::Big);
+
+        let a_ref = leader.new_private_input::<u32>("a").unwrap();
+        leader
+            .assign_bytes(&a_ref, &[0x01, 0x02, 0x03, 0x04], endianness)
+            .unwrap();
+
+        assert_eq!(
+            leader.state().memory.get_assigned_private(&a_ref),
+            Some(Value::U32(0x01020304))
+        );
+
+        let b_ref = leader.new_private_input::<u32>("b").unwrap();
+        let err = leader
+            .assign_bytes(&b_ref, &[0x01, 0x02], endianness)
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            MemoryError::Assignment(AssignmentError::Bytes { .. })
+        ));
+    }
+
+    #[test]
+    fn test_deap_batch_inputs() {
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+
+        let refs = leader
+            .new_inputs_batch(&[
+                ("a", ValueType::U8, Visibility::Private),
+                ("b", ValueType::new_array::<u32>(4), Visibility::Public),
+                ("c", ValueType::U16, Visibility::Blind),
+            ])
+            .unwrap();
+
+        assert_eq!(refs.len(), 3);
+        assert_eq!(leader.get_value("b"), Some(refs[1].clone()));
+        assert!(refs
+            .iter()
+            .all(|value| leader.gen.get_encoding(value).is_some()));
+
+        leader
+            .assign_many([
+                (refs[0].clone(), Value::U8(1)),
+                (refs[1].clone(), Value::from([1u32, 2, 3, 4])),
+            ])
+            .unwrap();
+        assert_eq!(
+            leader.state().memory.get_assigned_private(&refs[0]),
+            Some(Value::U8(1))
+        );
+
+        // The inputs before a duplicate remain in memory, and are encoded.
+        let err = leader
+            .new_inputs_batch(&[
+                ("d", ValueType::U8, Visibility::Private),
+                ("a", ValueType::U8, Visibility::Private),
+            ])
+            .unwrap_err();
+        assert!(matches!(err, MemoryError::DuplicateValueId(_)));
+
+        let d_ref = leader.get_value("d").unwrap();
+        assert!(leader.gen.get_encoding(&d_ref).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deap_commit_inputs() {
+        let (leader_result, follower_result) = run_commit_inputs(None).await;
+
+        leader_result.unwrap();
+        follower_result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_deap_commit_inputs_mismatch() {
+        let (leader_result, _) = run_commit_inputs(Some(Role::Follower)).await;
+
+        assert!(matches!(
+            leader_result.unwrap_err(),
+            DEAPError::FinalizationError(FinalizationError::InvalidInputCommitment)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deap_commit_inputs_leader_mismatch() {
+        let (_, follower_result) = run_commit_inputs(Some(Role::Leader)).await;
+
+        assert!(matches!(
+            follower_result.unwrap_err(),
+            DEAPError::FinalizationError(FinalizationError::InvalidInputCommitment)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deap_commit_inputs_unassigned() {
+        let (mut ctx, _) = test_st_executor(8);
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+
+        let err = leader
+            .commit_inputs(&mut ctx, &[key_ref], &[])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DEAPError::UncommittableInput(_)));
+    }
+
+    #[tokio::test]
+    async fn test_deap_load() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            async move {
                 leader
-                    .commit(
+                    .load(
                         &mut ctx_a,
+                        AES128.clone(),
                         &[key_ref.clone(), msg_ref.clone()],
-                        &mut leader_ot_send,
-                        &mut leader_ot_recv,
+                        &[ciphertext_ref.clone()],
                     )
                     .await
                     .unwrap();
 
+                leader.assign(&key_ref, key).unwrap();
+
                 leader
                     .execute(
                         &mut ctx_a,
@@ -1181,19 +2686,19 @@ mod tests {
             let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
             let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
 
-            follower.assign(&msg_ref, msg).unwrap();
-
             async move {
                 follower
-                    .commit(
+                    .load(
                         &mut ctx_b,
+                        AES128.clone(),
                         &[key_ref.clone(), msg_ref.clone()],
-                        &mut follower_ot_send,
-                        &mut follower_ot_recv,
+                        &[ciphertext_ref.clone()],
                     )
                     .await
                     .unwrap();
 
+                follower.assign(&msg_ref, msg).unwrap();
+
                 follower
                     .execute(
                         &mut ctx_b,
@@ -1226,7 +2731,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_deap_load() {
+    async fn test_deap_artifact() {
         let (mut ctx_a, mut ctx_b) = test_st_executor(8);
         let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
         let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
@@ -1237,18 +2742,43 @@ mod tests {
         let key = [42u8; 16];
         let msg = [69u8; 16];
 
+        let leader_key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+        let leader_msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+        let leader_ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+        let follower_key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+        let follower_msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+        let follower_ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+        // Both parties garble ahead of time and exchange the artifacts out-of-band.
+        let leader_artifact = leader
+            .garble_artifact(
+                &AES128,
+                &[leader_key_ref.clone(), leader_msg_ref.clone()],
+                &[leader_ciphertext_ref.clone()],
+            )
+            .unwrap();
+        let follower_artifact = follower
+            .garble_artifact(
+                &AES128,
+                &[follower_key_ref.clone(), follower_msg_ref.clone()],
+                &[follower_ciphertext_ref.clone()],
+            )
+            .unwrap();
+
         let leader_fut = {
-            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+            let key_ref = leader_key_ref;
+            let msg_ref = leader_msg_ref;
+            let ciphertext_ref = leader_ciphertext_ref;
 
             async move {
                 leader
-                    .load(
+                    .register_artifact(
                         &mut ctx_a,
                         AES128.clone(),
                         &[key_ref.clone(), msg_ref.clone()],
                         &[ciphertext_ref.clone()],
+                        follower_artifact,
                     )
                     .await
                     .unwrap();
@@ -1279,17 +2809,18 @@ mod tests {
         };
 
         let follower_fut = {
-            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+            let key_ref = follower_key_ref;
+            let msg_ref = follower_msg_ref;
+            let ciphertext_ref = follower_ciphertext_ref;
 
             async move {
                 follower
-                    .load(
+                    .register_artifact(
                         &mut ctx_b,
                         AES128.clone(),
                         &[key_ref.clone(), msg_ref.clone()],
                         &[ciphertext_ref.clone()],
+                        leader_artifact,
                     )
                     .await
                     .unwrap();
@@ -1524,14 +3055,187 @@ mod tests {
         assert_eq!((leader_share ^ follower_share), c);
     }
 
+    #[tokio::test]
+    async fn test_deap_shared_input() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
+        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+
+        let circ = adder_circ();
+
+        let a = 1u8;
+        let b = 2u8;
+        let c = a + b;
+
+        let leader_fut = {
+            let circ = circ.clone();
+            let a_ref = leader.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader.new_output::<u8>("c").unwrap();
+            let c_shared_ref = leader.new_shared_input::<u8>("c_shared").unwrap();
+            let d_ref = leader.new_output::<u8>("d").unwrap();
+
+            leader.assign(&a_ref, a).unwrap();
+
+            async move {
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        circ.clone(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let share = leader
+                    .decode_shared(
+                        &mut ctx_a,
+                        &[c_ref],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap()
+                    .pop()
+                    .unwrap();
+
+                // Feed the shared output back into a circuit.
+                leader.assign(&c_shared_ref, share).unwrap();
+
+                leader
+                    .execute(
+                        &mut ctx_a,
+                        circ,
+                        &[c_shared_ref.clone(), c_shared_ref],
+                        &[d_ref.clone()],
+                        &mut leader_ot_send,
+                        &mut leader_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = leader.decode(&mut ctx_a, &[d_ref]).await.unwrap();
+
+                leader
+                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let follower_fut = {
+            let a_ref = follower.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower.new_output::<u8>("c").unwrap();
+            let c_shared_ref = follower.new_shared_input::<u8>("c_shared").unwrap();
+            let d_ref = follower.new_output::<u8>("d").unwrap();
+
+            follower.assign(&b_ref, b).unwrap();
+
+            async move {
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        circ.clone(),
+                        &[a_ref, b_ref],
+                        &[c_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let share = follower
+                    .decode_shared(
+                        &mut ctx_b,
+                        &[c_ref],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap()
+                    .pop()
+                    .unwrap();
+
+                follower.assign(&c_shared_ref, share).unwrap();
+
+                follower
+                    .execute(
+                        &mut ctx_b,
+                        circ,
+                        &[c_shared_ref.clone(), c_shared_ref],
+                        &[d_ref.clone()],
+                        &mut follower_ot_send,
+                        &mut follower_ot_recv,
+                    )
+                    .await
+                    .unwrap();
+
+                let outputs = follower.decode(&mut ctx_b, &[d_ref]).await.unwrap();
+
+                follower
+                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                    .await
+                    .unwrap();
+
+                outputs
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output, vec![Value::from(c + c)]);
+    }
+
+    #[tokio::test]
+    async fn test_deap_shared_input_unassigned() {
+        let (mut ctx_a, _) = test_st_executor(8);
+        let (mut ot_send, mut ot_recv) = ideal_ot();
+
+        let leader = DEAP::new(Role::Leader, [42u8; 32]);
+
+        let a_ref = leader.new_shared_input::<u8>("a").unwrap();
+        let b_ref = leader.new_shared_input::<u8>("b").unwrap();
+        let c_ref = leader.new_output::<u8>("c").unwrap();
+
+        leader.assign(&a_ref, 1u8).unwrap();
+
+        let err = leader
+            .execute(
+                &mut ctx_a,
+                adder_circ(),
+                &[a_ref.clone(), b_ref.clone()],
+                &[c_ref],
+                &mut ot_send,
+                &mut ot_recv,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DEAPError::UnassignedShare(value) if value == b_ref));
+        // The assigned share must not be consumed by a failed reconstruction.
+        assert!(leader.state().memory.is_share_assigned(&a_ref));
+    }
+
     #[tokio::test]
     async fn test_deap_zk_pass() {
         run_zk(
+            Role::Leader,
             [42u8; 16],
             [69u8; 16],
             [
                 235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
             ],
+            false,
         )
         .await;
     }
@@ -1540,86 +3244,172 @@ mod tests {
     #[should_panic]
     async fn test_deap_zk_fail() {
         run_zk(
+            Role::Leader,
             [42u8; 16],
             [69u8; 16],
             // wrong ciphertext
             [
                 235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 76,
             ],
+            false,
         )
         .await;
     }
 
-    async fn run_zk(key: [u8; 16], msg: [u8; 16], expected_ciphertext: [u8; 16]) {
-        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
-        let (_, mut follower_ot_recv) = ideal_ot::<[Block; 2], _>();
-        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+    #[tokio::test]
+    async fn test_deap_zk_follower_prover_pass() {
+        run_zk(
+            Role::Follower,
+            [42u8; 16],
+            [69u8; 16],
+            [
+                235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
+            ],
+            false,
+        )
+        .await;
+    }
 
-        let mut leader = DEAP::new(Role::Leader, [42u8; 32]);
-        let mut follower = DEAP::new(Role::Follower, [69u8; 32]);
+    #[tokio::test]
+    #[should_panic]
+    async fn test_deap_zk_follower_prover_fail() {
+        run_zk(
+            Role::Follower,
+            [42u8; 16],
+            [69u8; 16],
+            // wrong ciphertext
+            [
+                235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 76,
+            ],
+            false,
+        )
+        .await;
+    }
 
-        let leader_fut = {
-            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+    #[rstest]
+    #[case::leader_prover(Role::Leader)]
+    #[case::follower_prover(Role::Follower)]
+    #[tokio::test]
+    async fn test_deap_zk_privacy_free_pass(#[case] prover_role: Role) {
+        run_zk(
+            prover_role,
+            [42u8; 16],
+            [69u8; 16],
+            [
+                235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 75,
+            ],
+            true,
+        )
+        .await;
+    }
 
-            leader.assign(&key_ref, key).unwrap();
+    #[tokio::test]
+    #[should_panic]
+    async fn test_deap_zk_privacy_free_fail() {
+        run_zk(
+            Role::Leader,
+            [42u8; 16],
+            [69u8; 16],
+            // wrong ciphertext
+            [
+                235u8, 22, 253, 138, 102, 20, 139, 100, 252, 153, 244, 111, 84, 116, 199, 76,
+            ],
+            true,
+        )
+        .await;
+    }
+
+    async fn run_zk(
+        prover_role: Role,
+        key: [u8; 16],
+        msg: [u8; 16],
+        expected_ciphertext: [u8; 16],
+        privacy_free: bool,
+    ) {
+        let (mut prover_ctx, mut verifier_ctx) = test_st_executor(8);
+        let (_, mut verifier_ot_recv) = ideal_ot::<[Block; 2], _>();
+        let (mut verifier_ot_send, mut prover_ot_recv) = ideal_ot();
+
+        let verifier_role = match prover_role {
+            Role::Leader => Role::Follower,
+            Role::Follower => Role::Leader,
+        };
+
+        let mut config = DEAPConfig::builder();
+        if privacy_free {
+            config.privacy_free_proofs();
+        }
+        let config = config.build().unwrap();
+
+        let mut prover = DEAP::new_with_config(prover_role, [42u8; 32], config.clone());
+        let mut verifier = DEAP::new_with_config(verifier_role, [69u8; 32], config);
+
+        let prover_fut = {
+            let key_ref = prover.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = prover.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = prover.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            prover.assign(&key_ref, key).unwrap();
 
             async move {
-                leader
+                prover
                     .execute_prove(
-                        &mut ctx_a,
+                        &mut prover_ctx,
                         AES128.clone(),
                         &[key_ref, msg_ref],
                         &[ciphertext_ref.clone()],
-                        &mut leader_ot_recv,
+                        &mut prover_ot_recv,
                     )
                     .await
                     .unwrap();
 
-                leader
-                    .defer_prove(&mut ctx_a, &[ciphertext_ref])
+                prover
+                    .defer_prove(&mut prover_ctx, &[ciphertext_ref])
                     .await
                     .unwrap();
 
-                leader
-                    .finalize(&mut ctx_a, &mut leader_ot_recv)
+                prover
+                    .finalize(&mut prover_ctx, &mut prover_ot_recv)
                     .await
                     .unwrap();
             }
         };
 
-        let follower_fut = {
-            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
-            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
-            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+        let verifier_fut = {
+            let key_ref = verifier.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = verifier.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = verifier.new_output::<[u8; 16]>("ciphertext").unwrap();
 
-            follower.assign(&msg_ref, msg).unwrap();
+            verifier.assign(&msg_ref, msg).unwrap();
 
             async move {
-                follower
+                verifier
                     .execute_verify(
-                        &mut ctx_b,
+                        &mut verifier_ctx,
                         AES128.clone(),
                         &[key_ref, msg_ref],
                         &[ciphertext_ref.clone()],
-                        &mut follower_ot_send,
+                        &mut verifier_ot_send,
                     )
                     .await
                     .unwrap();
 
-                follower
-                    .defer_verify(&mut ctx_b, &[ciphertext_ref], &[expected_ciphertext.into()])
+                verifier
+                    .defer_verify(
+                        &mut verifier_ctx,
+                        &[ciphertext_ref],
+                        &[expected_ciphertext.into()],
+                    )
                     .await
                     .unwrap();
 
-                follower
-                    .finalize(&mut ctx_b, &mut follower_ot_recv)
+                verifier
+                    .finalize(&mut verifier_ctx, &mut verifier_ot_recv)
                     .await
                     .unwrap();
             }
         };
 
-        futures::join!(leader_fut, follower_fut);
+        futures::join!(prover_fut, verifier_fut);
     }
 }
@@ -7,23 +7,26 @@ use async_trait::async_trait;
 use futures::TryFutureExt;
 
 use mpz_circuits::{
-    types::{Value, ValueType},
+    types::{Endianness, Value, ValueType},
     Circuit,
 };
-use mpz_common::Context;
+use mpz_common::{Context, Poison};
+use mpz_core::Block;
 use mpz_garble_core::{encoding_state::Active, EncodedValue};
+use mpz_ot::{CommittedOTSender, VerifiableOTReceiver};
 
 use crate::{
-    config::{Role, Visibility},
+    config::{Role, TransferPolicy, Visibility},
     ot::{VerifiableOTReceiveEncoding, VerifiableOTSendEncoding},
+    scheduler::Scheduler,
     value::ValueRef,
     Decode, DecodeError, DecodePrivate, Execute, ExecutionError, Load, LoadError, Memory,
-    MemoryError, Prove, ProveError, Thread, Verify, VerifyError,
+    MemoryError, Prove, ProveError, Thread, Verify, VerifyError, VmError,
 };
 
 use super::{
     error::{FinalizationError, PeerEncodingsError},
-    DEAPError, DEAP,
+    DEAPConfig, DEAPError, DEAP,
 };
 
 #[derive(Debug)]
@@ -62,11 +65,30 @@ pub struct DEAPThread<Ctx, OTS, OTR> {
 impl<Ctx, OTS, OTR> DEAPThread<Ctx, OTS, OTR> {
     /// Creates a new DEAP instance.
     pub fn new(role: Role, encoder_seed: [u8; 32], ctx: Ctx, ot_send: OTS, ot_recv: OTR) -> Self {
+        Self::new_with_config(
+            role,
+            encoder_seed,
+            DEAPConfig::default(),
+            ctx,
+            ot_send,
+            ot_recv,
+        )
+    }
+
+    /// Creates a new DEAP instance with the provided configuration.
+    pub fn new_with_config(
+        role: Role,
+        encoder_seed: [u8; 32],
+        config: DEAPConfig,
+        ctx: Ctx,
+        ot_send: OTS,
+        ot_recv: OTR,
+    ) -> Self {
         Self {
             ctx,
             ot_send,
             ot_recv,
-            state: State::Main(Arc::new(DEAP::new(role, encoder_seed))),
+            state: State::Main(Arc::new(DEAP::new_with_config(role, encoder_seed, config))),
         }
     }
 
@@ -88,12 +110,56 @@ impl<Ctx, OTS, OTR> DEAPThread<Ctx, OTS, OTR> {
             State::Finalized => Err(FinalizationError::AlreadyFinalized.into()),
         }
     }
+
+    /// Creates a scheduler which runs units of work on new threads of this DEAP instance.
+    ///
+    /// This thread is not part of the scheduler, so it can finalize the instance once the
+    /// scheduler has run to completion.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - The context and OT instances of each thread.
+    pub fn new_scheduler<'a, R>(
+        &self,
+        threads: impl IntoIterator<Item = (Ctx, OTS, OTR)>,
+    ) -> Result<Scheduler<'a, Self, R>, VmError> {
+        let threads = threads
+            .into_iter()
+            .map(|(ctx, ot_send, ot_recv)| self.new_thread(ctx, ot_send, ot_recv))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| VmError::ProtocolError(Box::new(err)))?;
+
+        Scheduler::new(threads)
+    }
 }
 
 impl<Ctx, OTS, OTR> DEAPThread<Ctx, OTS, OTR>
 where
     Ctx: Context,
-    OTR: VerifiableOTReceiveEncoding<Ctx>,
+{
+    /// Returns the convention used to convert between bytes and values.
+    pub fn endianness(&self) -> Endianness {
+        self.state.get().endianness()
+    }
+
+    /// Checks that the other party uses the same convention to convert between bytes and
+    /// values.
+    pub async fn check_endianness(&mut self) -> Result<(), DEAPError> {
+        self.state.get().check_endianness(&mut self.ctx).await
+    }
+
+    /// Links an input value to the encoding of another value.
+    ///
+    /// See [`DEAP::link`] for details.
+    pub fn link(&self, from: &ValueRef, to: &ValueRef) -> Result<(), DEAPError> {
+        self.state.get().link(from, to)
+    }
+}
+
+impl<Ctx, OTS, OTR> DEAPThread<Ctx, OTS, OTR>
+where
+    Ctx: Context,
+    OTR: VerifiableOTReceiveEncoding<Ctx> + Send,
 {
     /// Finalizes the DEAP instance.
     ///
@@ -112,8 +178,43 @@ where
     }
 }
 
+impl<Ctx, OTS, OTR> DEAPThread<Ctx, OTS, OTR>
+where
+    Ctx: Context,
+    OTS: CommittedOTSender<Ctx, [Block; 2]> + Send,
+    OTR: VerifiableOTReceiver<Ctx, bool, Block, [Block; 2]>
+        + VerifiableOTReceiveEncoding<Ctx>
+        + Send,
+{
+    /// Finalizes the DEAP instance when the OTs are committed.
+    ///
+    /// The leader can only verify committed OTs, e.g. KOS with sender commitments, after the
+    /// follower's OT sender has revealed its secrets. Both parties must call this method instead
+    /// of [`finalize`](Self::finalize).
+    pub async fn finalize_committed(&mut self) -> Result<Option<[u8; 32]>, DEAPError> {
+        let State::Main(deap) = &self.state else {
+            return self.finalize().await;
+        };
+
+        match deap.role {
+            Role::Leader => self.ot_recv.accept_reveal(&mut self.ctx).await?,
+            Role::Follower => self.ot_send.reveal(&mut self.ctx).await?,
+        }
+
+        self.finalize().await
+    }
+}
+
 impl<Ctx, OTS, OTR> Thread for DEAPThread<Ctx, OTS, OTR> {}
 
+impl<Ctx, OTS, OTR> Poison for DEAPThread<Ctx, OTS, OTR> {
+    fn is_poisoned(&self) -> bool {
+        // The generator and evaluator are shared by all threads, so a cancelled operation on
+        // any thread poisons the instance.
+        !self.state.is_finalized() && self.state.get().is_poisoned()
+    }
+}
+
 impl<Ctx, OTS, OTR> Memory for DEAPThread<Ctx, OTS, OTR> {
     fn new_input_with_type(
         &self,
@@ -124,6 +225,17 @@ impl<Ctx, OTS, OTR> Memory for DEAPThread<Ctx, OTS, OTR> {
         self.state.get().new_input_with_type(id, typ, visibility)
     }
 
+    fn new_inputs_batch(
+        &self,
+        inputs: &[(&str, ValueType, Visibility)],
+    ) -> Result<Vec<ValueRef>, MemoryError> {
+        self.state.get().new_inputs_batch(inputs)
+    }
+
+    fn new_constant(&self, value: impl Into<Value>) -> Result<ValueRef, MemoryError> {
+        self.state.get().new_constant(value)
+    }
+
     fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
         self.state.get().new_output_with_type(id, typ)
     }
@@ -136,6 +248,21 @@ impl<Ctx, OTS, OTR> Memory for DEAPThread<Ctx, OTS, OTR> {
         self.state.get().assign_by_id(id, value)
     }
 
+    fn assign_many(
+        &self,
+        values: impl IntoIterator<Item = (ValueRef, Value)>,
+    ) -> Result<(), MemoryError> {
+        self.state.get().assign_many(values)
+    }
+
+    fn set_transfer_policy(
+        &self,
+        value_ref: &ValueRef,
+        policy: TransferPolicy,
+    ) -> Result<(), MemoryError> {
+        self.state.get().set_transfer_policy(value_ref, policy)
+    }
+
     fn get_value(&self, id: &str) -> Option<ValueRef> {
         self.state.get().get_value(id)
     }
@@ -1,3 +1,4 @@
 //! Implementations of garbled circuit protocols
 
 pub mod deap;
+pub mod symmetric;
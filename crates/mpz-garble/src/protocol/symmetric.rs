@@ -0,0 +1,512 @@
+//! Symmetric garbling, where both parties garble circuits for each other within one session.
+//!
+//! Unlike [`DEAP`](crate::protocol::deap::DEAP), which evaluates every circuit in both directions,
+//! each circuit is garbled by only one of the parties. This allows applications to alternate
+//! the garbler and evaluator roles, eg. round-robin, while sharing a single [`ValueMemory`].
+//!
+//! # Security
+//!
+//! This protocol is only secure against a semi-honest adversary. The evaluator of a circuit has no
+//! way to detect a garbler which garbled a different circuit.
+//!
+//! # Directions
+//!
+//! Each party holds a [`Generator`] and an [`Evaluator`], one for each direction. Encodings are not
+//! shared between directions, so the output of a circuit garbled by one party can not be used as
+//! an input to a circuit garbled by the other. Such values must be decoded and assigned to a new
+//! input instead.
+
+use std::{
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use futures::TryFutureExt;
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_common::{try_join, Context};
+
+use crate::{
+    config::{Role, TransferPolicy, Visibility},
+    evaluator::{Evaluator, EvaluatorConfig, EvaluatorError},
+    generator::{Generator, GeneratorConfig, GeneratorError},
+    memory::ValueMemory,
+    ot::{OTReceiveEncoding, OTSendEncoding},
+    value::ValueRef,
+    Memory, MemoryError,
+};
+
+/// Errors that can occur during symmetric garbling.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SymmetricError {
+    #[error(transparent)]
+    ContextError(#[from] mpz_common::ContextError),
+    #[error(transparent)]
+    GeneratorError(#[from] GeneratorError),
+    #[error(transparent)]
+    EvaluatorError(#[from] EvaluatorError),
+}
+
+/// A garbling session in which both parties garble circuits for each other.
+#[derive(Debug)]
+pub struct Symmetric {
+    role: Role,
+    gen: Generator,
+    ev: Evaluator,
+    memory: Mutex<ValueMemory>,
+}
+
+impl Symmetric {
+    /// Creates a new symmetric garbling session.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party, used to order concurrent operations.
+    /// * `encoder_seed` - The seed used to generate encodings for circuits garbled by this party.
+    pub fn new(role: Role, encoder_seed: [u8; 32]) -> Self {
+        let gen_config = GeneratorConfig::builder()
+            .build()
+            .expect("config should be valid");
+        let ev_config = EvaluatorConfig::builder()
+            .build()
+            .expect("config should be valid");
+
+        Self::new_with_config(role, gen_config, ev_config, encoder_seed)
+    }
+
+    /// Creates a new symmetric garbling session with the provided configurations.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party, used to order concurrent operations.
+    /// * `gen_config` - The configuration of the generator.
+    /// * `ev_config` - The configuration of the evaluator.
+    /// * `encoder_seed` - The seed used to generate encodings for circuits garbled by this party.
+    pub fn new_with_config(
+        role: Role,
+        gen_config: GeneratorConfig,
+        ev_config: EvaluatorConfig,
+        encoder_seed: [u8; 32],
+    ) -> Self {
+        Self {
+            role,
+            gen: Generator::new(gen_config, encoder_seed),
+            ev: Evaluator::new(ev_config),
+            memory: Mutex::new(ValueMemory::default()),
+        }
+    }
+
+    fn memory(&self) -> impl DerefMut<Target = ValueMemory> + '_ {
+        self.memory.lock().unwrap()
+    }
+
+    /// Returns the generator used for circuits garbled by this party.
+    pub fn generator(&self) -> &Generator {
+        &self.gen
+    }
+
+    /// Returns the evaluator used for circuits garbled by the other party.
+    pub fn evaluator(&self) -> &Evaluator {
+        &self.ev
+    }
+
+    /// Commits the provided input values in both directions.
+    ///
+    /// Assigned values are consumed upon first use, so values which are used as inputs to
+    /// circuits garbled by both parties must be committed using this method beforehand.
+    ///
+    /// Values which are already committed are ignored.
+    pub async fn commit<Ctx, OTS, OTR>(
+        &self,
+        ctx: &mut Ctx,
+        inputs: &[ValueRef],
+        ot_send: &mut OTS,
+        ot_recv: &mut OTR,
+    ) -> Result<(), SymmetricError>
+    where
+        Ctx: Context,
+        OTS: OTSendEncoding<Ctx> + Send,
+        OTR: OTReceiveEncoding<Ctx> + Send,
+    {
+        let assigned = self.memory().drain_assigned(inputs);
+        match self.role {
+            Role::Leader => {
+                try_join!(
+                    ctx,
+                    self.gen
+                        .setup_assigned_values(ctx, &assigned, ot_send)
+                        .map_err(SymmetricError::from),
+                    self.ev
+                        .setup_assigned_values(ctx, &assigned, ot_recv)
+                        .map_err(SymmetricError::from)
+                )??;
+            }
+            Role::Follower => {
+                try_join!(
+                    ctx,
+                    self.ev
+                        .setup_assigned_values(ctx, &assigned, ot_recv)
+                        .map_err(SymmetricError::from),
+                    self.gen
+                        .setup_assigned_values(ctx, &assigned, ot_send)
+                        .map_err(SymmetricError::from)
+                )??;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Garbles a circuit, sending it to the other party for evaluation.
+    ///
+    /// The other party must call [`evaluate`](Self::evaluate).
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `ot_send` - The OT sender.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn generate<Ctx, OTS>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        ot_send: &mut OTS,
+    ) -> Result<(), SymmetricError>
+    where
+        Ctx: Context,
+        OTS: OTSendEncoding<Ctx> + Send,
+    {
+        let assigned_values = self.memory().drain_assigned(inputs);
+
+        self.gen
+            .setup_assigned_values(ctx, &assigned_values, ot_send)
+            .await?;
+
+        self.gen.generate(ctx, circ, inputs, outputs, false).await?;
+
+        Ok(())
+    }
+
+    /// Evaluates a circuit garbled by the other party.
+    ///
+    /// The other party must call [`generate`](Self::generate).
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `ot_recv` - The OT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn evaluate<Ctx, OTR>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        ot_recv: &mut OTR,
+    ) -> Result<(), SymmetricError>
+    where
+        Ctx: Context,
+        OTR: OTReceiveEncoding<Ctx> + Send,
+    {
+        let assigned_values = self.memory().drain_assigned(inputs);
+
+        self.ev
+            .setup_assigned_values(ctx, &assigned_values, ot_recv)
+            .await?;
+
+        self.ev.evaluate(ctx, circ, inputs, outputs).await?;
+
+        Ok(())
+    }
+
+    /// Sends the decoding of the provided values to the other party.
+    ///
+    /// The values must be outputs of circuits garbled by this party, and the other party
+    /// must call [`decode`](Self::decode).
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn send_decoding<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), SymmetricError> {
+        self.gen.decode(ctx, values).await?;
+
+        Ok(())
+    }
+
+    /// Receives the decoding of the provided values from the other party and decodes them.
+    ///
+    /// The values must be outputs of circuits garbled by the other party, and the other party
+    /// must call [`send_decoding`](Self::send_decoding).
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, SymmetricError> {
+        Ok(self.ev.decode(ctx, values).await?)
+    }
+}
+
+impl Memory for Symmetric {
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        if let Visibility::Shared = visibility {
+            return Err(MemoryError::UnsupportedVisibility(visibility));
+        }
+
+        let value_ref = self.memory().new_input(id, typ.clone(), visibility)?;
+        self.gen.generate_input_encoding(&value_ref, &typ);
+        Ok(value_ref)
+    }
+
+    fn new_inputs_batch(
+        &self,
+        inputs: &[(&str, ValueType, Visibility)],
+    ) -> Result<Vec<ValueRef>, MemoryError> {
+        if let Some((_, _, visibility)) = inputs
+            .iter()
+            .find(|(_, _, visibility)| matches!(visibility, Visibility::Shared))
+        {
+            return Err(MemoryError::UnsupportedVisibility(*visibility));
+        }
+
+        let mut memory = self.memory();
+        let mut value_refs = Vec::with_capacity(inputs.len());
+        let mut encode = Vec::with_capacity(inputs.len());
+        let result = inputs.iter().try_for_each(|(id, typ, visibility)| {
+            let value_ref = memory.new_input(id, typ.clone(), *visibility)?;
+            encode.push((value_ref.clone(), typ.clone()));
+            value_refs.push(value_ref);
+            Ok::<_, MemoryError>(())
+        });
+        drop(memory);
+
+        // The inputs added before an error must be encoded as well, as they remain in memory.
+        self.gen.generate_input_encodings(&encode);
+
+        result.map(|_| value_refs)
+    }
+
+    fn new_constant(&self, value: impl Into<Value>) -> Result<ValueRef, MemoryError> {
+        let value = value.into();
+        let typ = value.value_type();
+
+        let (value_ref, added) = self.memory().get_or_insert_constant(value)?;
+        if added {
+            self.gen.generate_input_encoding(&value_ref, &typ);
+        }
+
+        Ok(value_ref)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.memory().new_output(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.memory().assign(value_ref, value.into())
+    }
+
+    fn assign_many(
+        &self,
+        values: impl IntoIterator<Item = (ValueRef, Value)>,
+    ) -> Result<(), MemoryError> {
+        let mut memory = self.memory();
+        values
+            .into_iter()
+            .try_for_each(|(value_ref, value)| memory.assign(&value_ref, value))
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        let mut memory = self.memory();
+        let value_ref = memory
+            .get_ref_by_id(id)
+            .ok_or_else(|| MemoryError::Undefined(id.to_string()))?
+            .clone();
+        memory.assign(&value_ref, value.into())
+    }
+
+    fn set_transfer_policy(
+        &self,
+        value_ref: &ValueRef,
+        policy: TransferPolicy,
+    ) -> Result<(), MemoryError> {
+        self.memory().set_transfer_policy(value_ref, policy)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.memory().get_ref_by_id(id).cloned()
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.memory().get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        let memory = self.memory();
+        let value_ref = memory.get_ref_by_id(id)?;
+        Some(memory.get_value_type(value_ref))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::circuits::AES128;
+    use mpz_common::executor::test_st_executor;
+    use mpz_ot::ideal::ot::ideal_ot;
+
+    use super::*;
+
+    fn aes128(key: [u8; 16], msg: [u8; 16]) -> [u8; 16] {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    }
+
+    #[tokio::test]
+    async fn test_symmetric_round_robin() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_ot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_ot();
+
+        let leader = Symmetric::new(Role::Leader, [42u8; 32]);
+        let follower = Symmetric::new(Role::Follower, [69u8; 32]);
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = async {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_0 = leader.new_output::<[u8; 16]>("ciphertext_0").unwrap();
+            let ciphertext_1 = leader.new_output::<[u8; 16]>("ciphertext_1").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            let inputs = [key_ref, msg_ref];
+
+            leader
+                .commit(
+                    &mut ctx_a,
+                    &inputs,
+                    &mut leader_ot_send,
+                    &mut leader_ot_recv,
+                )
+                .await
+                .unwrap();
+
+            // The leader garbles the first circuit.
+            leader
+                .generate(
+                    &mut ctx_a,
+                    AES128.clone(),
+                    &inputs,
+                    &[ciphertext_0.clone()],
+                    &mut leader_ot_send,
+                )
+                .await
+                .unwrap();
+
+            leader
+                .send_decoding(&mut ctx_a, &[ciphertext_0])
+                .await
+                .unwrap();
+
+            // The follower garbles the second circuit.
+            leader
+                .evaluate(
+                    &mut ctx_a,
+                    AES128.clone(),
+                    &inputs,
+                    &[ciphertext_1.clone()],
+                    &mut leader_ot_recv,
+                )
+                .await
+                .unwrap();
+
+            leader.decode(&mut ctx_a, &[ciphertext_1]).await.unwrap()
+        };
+
+        let follower_fut = async {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_0 = follower.new_output::<[u8; 16]>("ciphertext_0").unwrap();
+            let ciphertext_1 = follower.new_output::<[u8; 16]>("ciphertext_1").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            let inputs = [key_ref, msg_ref];
+
+            follower
+                .commit(
+                    &mut ctx_b,
+                    &inputs,
+                    &mut follower_ot_send,
+                    &mut follower_ot_recv,
+                )
+                .await
+                .unwrap();
+
+            follower
+                .evaluate(
+                    &mut ctx_b,
+                    AES128.clone(),
+                    &inputs,
+                    &[ciphertext_0.clone()],
+                    &mut follower_ot_recv,
+                )
+                .await
+                .unwrap();
+
+            let outputs = follower.decode(&mut ctx_b, &[ciphertext_0]).await.unwrap();
+
+            follower
+                .generate(
+                    &mut ctx_b,
+                    AES128.clone(),
+                    &inputs,
+                    &[ciphertext_1.clone()],
+                    &mut follower_ot_send,
+                )
+                .await
+                .unwrap();
+
+            follower
+                .send_decoding(&mut ctx_b, &[ciphertext_1])
+                .await
+                .unwrap();
+
+            outputs
+        };
+
+        let (mut leader_output, mut follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        let expected = aes128(key, msg);
+        let leader_ciphertext: [u8; 16] = leader_output.pop().unwrap().try_into().unwrap();
+        let follower_ciphertext: [u8; 16] = follower_output.pop().unwrap().try_into().unwrap();
+
+        assert_eq!(leader_ciphertext, expected);
+        assert_eq!(follower_ciphertext, expected);
+    }
+}
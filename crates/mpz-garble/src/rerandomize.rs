@@ -0,0 +1,115 @@
+//! Re-randomization of encodings.
+//!
+//! The generator assigns fresh labels to the new value, which are independent of the old ones.
+//! For every bit it sends a translation table with the new labels encrypted under the old labels,
+//! ordered by the pointer bits of the old labels. The evaluator can only decrypt the row of its
+//! active label, and the table reveals only hashes of the old labels, so the new encoding and
+//! the table do not reveal the old encoding.
+
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+use mpz_garble_core::Delta;
+
+use crate::value::ValueRef;
+
+/// The translation table of a single bit.
+pub(crate) type TranslationRow = [Block; 2];
+
+/// Returns the tweak of the translation table for the bit at `index` of `new_value`.
+///
+/// The new value must not already be encoded, so its ID is never the target of two re-randomizations.
+fn tweak(new_value: &ValueRef, index: usize) -> Block {
+    let id = new_value
+        .iter()
+        .next()
+        .expect("value references are not empty")
+        .to_u64();
+
+    Block::new((((id as u128) << 64) | index as u128).to_le_bytes())
+}
+
+/// Returns the translation tables from the 0-bit labels of the old encoding to the 0-bit
+/// labels of the new encoding.
+///
+/// # Arguments
+///
+/// * `new_value` - The value the new encoding is assigned to.
+/// * `delta` - The global offset of both encodings.
+/// * `old` - The 0-bit labels of the old encoding.
+/// * `new` - The 0-bit labels of the new encoding.
+pub(crate) fn garble_translation<'a>(
+    new_value: &ValueRef,
+    delta: Delta,
+    old: impl Iterator<Item = &'a Block>,
+    new: impl Iterator<Item = &'a Block>,
+) -> Vec<TranslationRow> {
+    old.zip(new)
+        .enumerate()
+        .map(|(index, (old_0, new_0))| {
+            let tweak = tweak(new_value, index);
+            let old_1 = *old_0 ^ *delta;
+            let new_1 = *new_0 ^ *delta;
+
+            let mut row = [Block::ZERO; 2];
+            row[old_0.lsb()] = FIXED_KEY_AES.tccr(tweak, *old_0) ^ *new_0;
+            row[old_1.lsb()] = FIXED_KEY_AES.tccr(tweak, old_1) ^ new_1;
+            row
+        })
+        .collect()
+}
+
+/// Returns the active labels of the new encoding, decrypted from the translation tables with
+/// the active labels of the old encoding.
+///
+/// # Arguments
+///
+/// * `new_value` - The value the new encoding is assigned to.
+/// * `old` - The active labels of the old encoding.
+/// * `table` - The translation tables, one for each label.
+pub(crate) fn translate<'a>(
+    new_value: &ValueRef,
+    old: impl Iterator<Item = &'a Block>,
+    table: &[TranslationRow],
+) -> Vec<Block> {
+    old.zip(table)
+        .enumerate()
+        .map(|(index, (old, row))| {
+            row[old.lsb()] ^ FIXED_KEY_AES.tccr(tweak(new_value, index), *old)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_core::prg::Prg;
+    use rand::SeedableRng;
+
+    use crate::value::ValueId;
+
+    #[test]
+    fn test_translation() {
+        let mut prg = Prg::from_seed(Block::ZERO);
+        let delta = Delta::random(&mut prg);
+        let new_value = ValueRef::Value {
+            id: ValueId::new("new"),
+        };
+
+        let old: Vec<Block> = (0..8).map(|_| prg.random_block()).collect();
+        let new: Vec<Block> = (0..8).map(|_| prg.random_block()).collect();
+        let table = garble_translation(&new_value, delta, old.iter(), new.iter());
+
+        let choices: Vec<bool> = (0..8).map(|i| i % 3 == 0).collect();
+        let select = |labels: &[Block]| -> Vec<Block> {
+            labels
+                .iter()
+                .zip(&choices)
+                .map(|(label, choice)| if *choice { *label ^ *delta } else { *label })
+                .collect()
+        };
+
+        let active_new = translate(&new_value, select(&old).iter(), &table);
+
+        assert_eq!(active_new, select(&new));
+    }
+}
@@ -0,0 +1,917 @@
+//! A scheduler for units of work which share the threads of a VM.
+//!
+//! Interactive applications often share a session with background precomputation, for example
+//! garbling circuits ahead of time while also serving online requests. The [`Scheduler`] accepts
+//! requests with a [`Priority`] and an optional deadline, and runs their units of work on its
+//! threads in order of urgency. Since each unit runs on a thread, the garbling, evaluation and
+//! OT transfers it performs are ordered accordingly.
+//!
+//! # Running
+//!
+//! [`Scheduler::run`] runs every thread independently: as soon as a thread finishes a unit, it
+//! starts the most urgent unit assigned to it. Requests can be pushed while the scheduler is
+//! running through a [`Handle`], and `run` returns once all requests have completed and all
+//! handles have been dropped.
+//!
+//! [`Scheduler::step`] instead runs at most one unit on every thread and waits for all of them,
+//! which allows requests to be pushed at well-defined points.
+//!
+//! # Determinism
+//!
+//! The parties of an MPC must perform the same operations on each thread in the same order.
+//! The units of a request are assigned to the threads in turn, in the order the requests are
+//! received, so each thread is assigned the same units on both parties. Both parties must push
+//! the same requests, with the same priorities and deadlines, in the same order, and a request
+//! must reach both schedulers before the same unit boundary of every thread, eg. by pushing it
+//! before running or between steps.
+//!
+//! # Preemption
+//!
+//! A request which is split into many units, such as bulk preprocessing, is preempted on each
+//! thread as soon as the thread finishes its current unit and a more urgent request has been
+//! pushed. A unit which is already running is never interrupted.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    mem,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::{
+    channel::{mpsc, oneshot},
+    Future, FutureExt, StreamExt,
+};
+
+use crate::VmError;
+
+/// A closure which takes a mutable reference to a thread and returns a boxed future.
+type Unit<'a, T, R> =
+    Box<dyn for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = R> + Send + 'b>> + Send + 'a>;
+
+/// The key requests are ordered by, most urgent first.
+type Key = (Reverse<Priority>, Duration, RequestId);
+
+/// The priority of a request.
+///
+/// Requests with a higher priority are always scheduled before requests with a lower priority.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Background work, such as preprocessing.
+    Background,
+    /// Regular work.
+    #[default]
+    Normal,
+    /// Latency-critical work, such as online requests.
+    Critical,
+}
+
+/// The id of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RequestId(u64);
+
+/// A completed request.
+#[derive(Debug)]
+pub struct Completed<R> {
+    /// The id of the request.
+    pub id: RequestId,
+    /// The results of the units of the request, in the order they were pushed.
+    pub results: Vec<R>,
+    /// Whether the request completed after its deadline.
+    pub missed_deadline: bool,
+}
+
+struct Request<'a, T, R> {
+    id: RequestId,
+    priority: Priority,
+    deadline: Option<Duration>,
+    units: VecDeque<Unit<'a, T, R>>,
+    results: Vec<R>,
+    /// Notified when the request completes, if it was pushed through a [`Handle`].
+    notify: Option<oneshot::Sender<Completed<R>>>,
+}
+
+impl<'a, T, R> Request<'a, T, R> {
+    fn new<F>(
+        id: RequestId,
+        priority: Priority,
+        deadline: Option<Duration>,
+        units: impl IntoIterator<Item = F>,
+        notify: Option<oneshot::Sender<Completed<R>>>,
+    ) -> Self
+    where
+        F: for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = R> + Send + 'b>> + Send + 'a,
+    {
+        let units: VecDeque<Unit<'a, T, R>> = units
+            .into_iter()
+            .map(|unit| Box::new(unit) as Unit<'a, T, R>)
+            .collect();
+
+        Self {
+            id,
+            priority,
+            deadline,
+            results: Vec::with_capacity(units.len()),
+            units,
+            notify,
+        }
+    }
+
+    /// Returns the key requests are ordered by, most urgent first.
+    ///
+    /// Requests with the same priority are ordered by deadline, and requests without a deadline
+    /// come last. Ties are broken by the order the requests were pushed in.
+    fn key(&self) -> Key {
+        (
+            Reverse(self.priority),
+            self.deadline.unwrap_or(Duration::MAX),
+            self.id,
+        )
+    }
+}
+
+/// Completes a request, returning it unless it was pushed through a [`Handle`].
+fn complete<R>(
+    id: RequestId,
+    deadline: Option<Duration>,
+    results: Vec<R>,
+    notify: Option<oneshot::Sender<Completed<R>>>,
+    elapsed: Duration,
+) -> Option<Completed<R>> {
+    let completed = Completed {
+        id,
+        results,
+        missed_deadline: deadline.is_some_and(|deadline| elapsed > deadline),
+    };
+
+    match notify {
+        Some(notify) => {
+            // The handle may have stopped waiting for the request, which is not an error.
+            _ = notify.send(completed);
+            None
+        }
+        None => Some(completed),
+    }
+}
+
+/// A scheduler for units of work which share the threads of a VM.
+///
+/// See the [module level documentation](crate::scheduler) for details.
+pub struct Scheduler<'a, T, R> {
+    threads: Vec<T>,
+    requests: Vec<Request<'a, T, R>>,
+    next_id: Arc<AtomicU64>,
+    /// The thread the next unit is assigned to when running.
+    next_thread: usize,
+    sender: mpsc::UnboundedSender<Request<'a, T, R>>,
+    receiver: mpsc::UnboundedReceiver<Request<'a, T, R>>,
+    start: Instant,
+}
+
+impl<T, R> std::fmt::Debug for Scheduler<'_, T, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("threads", &self.threads.len())
+            .field("requests", &self.requests.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, R> Scheduler<'a, T, R> {
+    /// Creates a new scheduler which runs units of work on the provided threads.
+    ///
+    /// Deadlines are measured from the creation of the scheduler.
+    pub fn new(threads: Vec<T>) -> Result<Self, VmError> {
+        if threads.is_empty() {
+            return Err(VmError::ThreadPoolEmpty);
+        }
+
+        let (sender, receiver) = mpsc::unbounded();
+
+        Ok(Self {
+            threads,
+            requests: Vec::new(),
+            next_id: Arc::new(AtomicU64::new(0)),
+            next_thread: 0,
+            sender,
+            receiver,
+            start: Instant::now(),
+        })
+    }
+
+    /// Returns the threads of the scheduler.
+    pub fn into_threads(self) -> Vec<T> {
+        self.threads
+    }
+
+    /// Returns `true` if there are no pending requests.
+    ///
+    /// Requests pushed through a [`Handle`] are pending once they have been received by a step.
+    pub fn is_idle(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Returns a handle for pushing requests, including while the scheduler is running.
+    pub fn handle(&self) -> Handle<'a, T, R> {
+        Handle {
+            sender: self.sender.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+
+    /// Pushes a request consisting of a single unit of work.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority of the request.
+    /// * `deadline` - The time after the creation of the scheduler by which the request should
+    ///   be completed.
+    /// * `unit` - The unit of work.
+    ///
+    /// # Boxed Future
+    ///
+    /// The closures provided must return a boxed future. This is due to higher-ranked trait bound
+    /// limitation with closures. See [this issue](https://github.com/rust-lang/rust/issues/70263) for more details.
+    pub fn push<F>(&mut self, priority: Priority, deadline: Option<Duration>, unit: F) -> RequestId
+    where
+        F: for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = R> + Send + 'b>> + Send + 'a,
+    {
+        self.push_units(priority, deadline, [unit])
+    }
+
+    /// Pushes a request consisting of many units of work, such as bulk preprocessing.
+    ///
+    /// The units of a request may run concurrently on different threads, and the request can be
+    /// preempted between its units.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority of the request.
+    /// * `deadline` - The time after the creation of the scheduler by which the request should
+    ///   be completed.
+    /// * `units` - The units of work.
+    pub fn push_units<F>(
+        &mut self,
+        priority: Priority,
+        deadline: Option<Duration>,
+        units: impl IntoIterator<Item = F>,
+    ) -> RequestId
+    where
+        F: for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = R> + Send + 'b>> + Send + 'a,
+    {
+        let id = RequestId(self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        self.requests
+            .push(Request::new(id, priority, deadline, units, None));
+
+        id
+    }
+
+    /// Runs the next step, returning the requests which completed.
+    ///
+    /// Each thread runs the next unit of the most urgent request which has units remaining, and
+    /// the step completes once all of them have finished. Requests pushed through a [`Handle`]
+    /// are received at the start of the step, and are not returned once they complete.
+    pub async fn step(&mut self) -> Vec<Completed<R>> {
+        while let Some(Some(request)) = self.receiver.next().now_or_never() {
+            self.requests.push(request);
+        }
+
+        self.requests.sort_by_key(|request| request.key());
+
+        // Assign units to threads, most urgent first.
+        let mut assigned = Vec::with_capacity(self.threads.len());
+        for (idx, request) in self.requests.iter_mut().enumerate() {
+            while assigned.len() < self.threads.len() {
+                let Some(unit) = request.units.pop_front() else {
+                    break;
+                };
+                assigned.push((idx, unit));
+            }
+        }
+
+        let (idxs, units): (Vec<_>, Vec<_>) = assigned.into_iter().unzip();
+
+        let results = futures::future::join_all(
+            units
+                .into_iter()
+                .zip(self.threads.iter_mut())
+                .map(|(unit, thread)| unit(thread)),
+        )
+        .await;
+
+        // Units of a request are assigned to threads in order, so the results stay in order.
+        for (idx, result) in idxs.into_iter().zip(results) {
+            self.requests[idx].results.push(result);
+        }
+
+        let elapsed = self.start.elapsed();
+        let (completed, pending) = mem::take(&mut self.requests)
+            .into_iter()
+            .partition::<Vec<_>, _>(|request| request.units.is_empty());
+        self.requests = pending;
+
+        completed
+            .into_iter()
+            .filter_map(|request| {
+                complete(
+                    request.id,
+                    request.deadline,
+                    request.results,
+                    request.notify,
+                    elapsed,
+                )
+            })
+            .collect()
+    }
+
+    /// Runs until all requests have completed and all handles have been dropped, returning the
+    /// requests which were not pushed through a [`Handle`] in the order they completed.
+    ///
+    /// Each thread runs independently, starting the most urgent unit assigned to it as soon as
+    /// it finishes the previous one.
+    pub async fn run(&mut self) -> Vec<Completed<R>> {
+        // Replace the channel, so that it closes once the handles pushing into it are dropped.
+        let (sender, receiver) = mpsc::unbounded();
+        drop(mem::replace(&mut self.sender, sender));
+        let mut receiver = mem::replace(&mut self.receiver, receiver);
+
+        let shared = Mutex::new(Shared {
+            queues: (0..self.threads.len()).map(|_| Vec::new()).collect(),
+            in_flight: HashMap::new(),
+            completed: Vec::new(),
+            closed: false,
+            next_thread: self.next_thread,
+            start: self.start,
+        });
+
+        {
+            let mut shared = shared.lock().unwrap();
+            for request in mem::take(&mut self.requests) {
+                shared.dispatch(request);
+            }
+        }
+
+        let (bells, doorbells): (Vec<_>, Vec<_>) =
+            (0..self.threads.len()).map(|_| mpsc::unbounded()).unzip();
+
+        let dispatch = async {
+            while let Some(request) = receiver.next().await {
+                shared.lock().unwrap().dispatch(request);
+                for bell in &bells {
+                    _ = bell.unbounded_send(());
+                }
+            }
+
+            shared.lock().unwrap().closed = true;
+            drop(bells);
+        };
+
+        let workers = futures::future::join_all(
+            self.threads
+                .iter_mut()
+                .zip(doorbells)
+                .enumerate()
+                .map(|(idx, (thread, doorbell))| work(thread, idx, &shared, doorbell)),
+        );
+
+        futures::join!(dispatch, workers);
+
+        let shared = shared.into_inner().unwrap();
+        self.next_thread = shared.next_thread;
+
+        shared.completed
+    }
+}
+
+/// A unit of work assigned to a thread.
+struct Queued<'a, T, R> {
+    key: Key,
+    /// The index of the unit in its request.
+    index: usize,
+    unit: Unit<'a, T, R>,
+}
+
+/// A request whose units are running.
+struct InFlight<R> {
+    deadline: Option<Duration>,
+    results: Vec<Option<R>>,
+    remaining: usize,
+    notify: Option<oneshot::Sender<Completed<R>>>,
+}
+
+/// The state shared by the threads of a running scheduler.
+struct Shared<'a, T, R> {
+    queues: Vec<Vec<Queued<'a, T, R>>>,
+    in_flight: HashMap<RequestId, InFlight<R>>,
+    completed: Vec<Completed<R>>,
+    /// Whether no more requests can be pushed.
+    closed: bool,
+    next_thread: usize,
+    start: Instant,
+}
+
+impl<'a, T, R> Shared<'a, T, R> {
+    /// Assigns the remaining units of a request to the threads in turn.
+    fn dispatch(&mut self, request: Request<'a, T, R>) {
+        let key = request.key();
+        let Request {
+            id,
+            deadline,
+            units,
+            results,
+            notify,
+            ..
+        } = request;
+
+        if units.is_empty() {
+            let elapsed = self.start.elapsed();
+            self.completed
+                .extend(complete(id, deadline, results, notify, elapsed));
+            return;
+        }
+
+        // Units which already ran in a step keep their results.
+        let offset = results.len();
+        let remaining = units.len();
+        for (index, unit) in units.into_iter().enumerate() {
+            let thread = self.next_thread % self.queues.len();
+            self.next_thread += 1;
+
+            self.queues[thread].push(Queued {
+                key,
+                index: offset + index,
+                unit,
+            });
+        }
+
+        self.in_flight.insert(
+            id,
+            InFlight {
+                deadline,
+                results: results
+                    .into_iter()
+                    .map(Some)
+                    .chain((0..remaining).map(|_| None))
+                    .collect(),
+                remaining,
+                notify,
+            },
+        );
+    }
+
+    /// Removes the most urgent unit assigned to a thread.
+    fn pop(&mut self, thread: usize) -> Option<(RequestId, usize, Unit<'a, T, R>)> {
+        let queue = &mut self.queues[thread];
+        let pos = queue
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, queued)| (queued.key, queued.index))
+            .map(|(pos, _)| pos)?;
+
+        let Queued { key, index, unit } = queue.remove(pos);
+
+        Some((key.2, index, unit))
+    }
+
+    /// Records the result of a unit, completing its request if it was the last one.
+    fn finish(&mut self, id: RequestId, index: usize, result: R) {
+        let request = self
+            .in_flight
+            .get_mut(&id)
+            .expect("request of a running unit is in flight");
+
+        request.results[index] = Some(result);
+        request.remaining -= 1;
+
+        if request.remaining == 0 {
+            let InFlight {
+                deadline,
+                results,
+                notify,
+                ..
+            } = self.in_flight.remove(&id).expect("request is in flight");
+
+            let results = results
+                .into_iter()
+                .map(|result| result.expect("all units have completed"))
+                .collect();
+
+            let elapsed = self.start.elapsed();
+            self.completed
+                .extend(complete(id, deadline, results, notify, elapsed));
+        }
+    }
+}
+
+/// Runs the units assigned to a thread until no more requests can be pushed.
+async fn work<'a, T, R>(
+    thread: &mut T,
+    idx: usize,
+    shared: &Mutex<Shared<'a, T, R>>,
+    mut doorbell: mpsc::UnboundedReceiver<()>,
+) {
+    loop {
+        let next = {
+            let mut shared = shared.lock().unwrap();
+            let next = shared.pop(idx);
+            if next.is_none() && shared.closed {
+                return;
+            }
+            next
+        };
+
+        match next {
+            Some((id, index, unit)) => {
+                let result = unit(thread).await;
+                shared.lock().unwrap().finish(id, index, result);
+            }
+            // Wait for more units to be assigned, or for the scheduler to close.
+            None => _ = doorbell.next().await,
+        }
+    }
+}
+
+/// A handle for pushing requests to a [`Scheduler`], including while it is running.
+pub struct Handle<'a, T, R> {
+    sender: mpsc::UnboundedSender<Request<'a, T, R>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl<T, R> Clone for Handle<'_, T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+}
+
+impl<T, R> std::fmt::Debug for Handle<'_, T, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handle").finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, R> Handle<'a, T, R> {
+    /// Pushes a request consisting of a single unit of work.
+    ///
+    /// See [`Scheduler::push`] for details.
+    pub fn push<F>(&self, priority: Priority, deadline: Option<Duration>, unit: F) -> Pending<R>
+    where
+        F: for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = R> + Send + 'b>> + Send + 'a,
+    {
+        self.push_units(priority, deadline, [unit])
+    }
+
+    /// Pushes a request consisting of many units of work.
+    ///
+    /// See [`Scheduler::push_units`] for details.
+    pub fn push_units<F>(
+        &self,
+        priority: Priority,
+        deadline: Option<Duration>,
+        units: impl IntoIterator<Item = F>,
+    ) -> Pending<R>
+    where
+        F: for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = R> + Send + 'b>> + Send + 'a,
+    {
+        let id = RequestId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (notify, receiver) = oneshot::channel();
+
+        // If the scheduler was dropped, the request is dropped too and the pending request
+        // resolves to `None`.
+        _ = self
+            .sender
+            .unbounded_send(Request::new(id, priority, deadline, units, Some(notify)));
+
+        Pending { id, receiver }
+    }
+}
+
+/// A request pushed through a [`Handle`].
+///
+/// Resolves to the completed request, or `None` if the scheduler was dropped before the request
+/// completed.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Pending<R> {
+    id: RequestId,
+    receiver: oneshot::Receiver<Completed<R>>,
+}
+
+impl<R> Pending<R> {
+    /// Returns the id of the request.
+    pub fn id(&self) -> RequestId {
+        self.id
+    }
+}
+
+impl<R> Future for Pending<R> {
+    type Output = Option<Completed<R>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll(cx)
+            .map(|completed| completed.ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::circuits::AES128;
+
+    use mpz_common::executor::test_mt_executor;
+    use mpz_core::Block;
+    use mpz_ot::ideal::ot::ideal_ot;
+
+    use crate::{
+        config::Role,
+        protocol::deap::{mock::create_mock_deap_vm, DEAPThread},
+        Decode, Execute, Thread,
+    };
+
+    /// A mock thread which logs the units it runs.
+    type Log = Vec<&'static str>;
+
+    fn unit(
+        name: &'static str,
+    ) -> impl for<'b> FnOnce(&'b mut Log) -> Pin<Box<dyn Future<Output = &'static str> + Send + 'b>> + Send
+    {
+        move |log: &mut Log| {
+            Box::pin(async move {
+                log.push(name);
+                name
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_priority() {
+        let mut scheduler = Scheduler::new(vec![Log::new()]).unwrap();
+
+        let background = scheduler.push(Priority::Background, None, unit("background"));
+        let normal = scheduler.push(Priority::Normal, None, unit("normal"));
+        let late = scheduler.push(Priority::Critical, None, unit("late"));
+        let early = scheduler.push(
+            Priority::Critical,
+            Some(Duration::from_secs(3600)),
+            unit("early"),
+        );
+
+        let completed = scheduler.run().await;
+
+        let ids: Vec<_> = completed.iter().map(|completed| completed.id).collect();
+        assert_eq!(ids, vec![early, late, normal, background]);
+        assert!(completed.iter().all(|completed| !completed.missed_deadline));
+
+        let log = scheduler.into_threads().pop().unwrap();
+        assert_eq!(log, vec!["early", "late", "normal", "background"]);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_preemption() {
+        let mut scheduler = Scheduler::new(vec![Log::new(), Log::new()]).unwrap();
+
+        let bulk = scheduler.push_units(
+            Priority::Background,
+            None,
+            ["bulk/0", "bulk/1", "bulk/2", "bulk/3", "bulk/4", "bulk/5"].map(unit),
+        );
+
+        // Both threads work on the bulk request.
+        assert!(scheduler.step().await.is_empty());
+
+        // An online request preempts the bulk request at the next step.
+        let online = scheduler.push(Priority::Critical, None, unit("online"));
+
+        let completed = scheduler.step().await;
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].id, online);
+        assert_eq!(completed[0].results, vec!["online"]);
+
+        let completed = scheduler.run().await;
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].id, bulk);
+        assert_eq!(
+            completed[0].results,
+            vec!["bulk/0", "bulk/1", "bulk/2", "bulk/3", "bulk/4", "bulk/5"]
+        );
+
+        let logs = scheduler.into_threads();
+        assert_eq!(logs[0], vec!["bulk/0", "online", "bulk/3", "bulk/5"]);
+        assert_eq!(logs[1], vec!["bulk/1", "bulk/2", "bulk/4"]);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_missed_deadline() {
+        let mut scheduler = Scheduler::new(vec![Log::new()]).unwrap();
+
+        scheduler.push(
+            Priority::Normal,
+            Some(Duration::ZERO),
+            move |log: &mut Log| {
+                Box::pin(async move {
+                    std::thread::sleep(Duration::from_millis(1));
+                    log.push("slow");
+                })
+            },
+        );
+
+        let completed = scheduler.run().await;
+
+        assert!(completed[0].missed_deadline);
+    }
+
+    #[test]
+    fn test_scheduler_empty() {
+        assert!(matches!(
+            Scheduler::<Log, ()>::new(Vec::new()),
+            Err(VmError::ThreadPoolEmpty)
+        ));
+    }
+
+    async fn encrypt<T: Thread + Execute + Decode + Send>(
+        thread: &mut T,
+        name: String,
+        key: Option<[u8; 16]>,
+    ) -> [u8; 16] {
+        let key_ref = match key {
+            Some(_) => thread.new_private_input::<[u8; 16]>(&format!("{name}/key")),
+            None => thread.new_blind_input::<[u8; 16]>(&format!("{name}/key")),
+        }
+        .unwrap();
+        let msg_ref = thread
+            .new_public_input::<[u8; 16]>(&format!("{name}/msg"))
+            .unwrap();
+        let ciphertext_ref = thread
+            .new_output::<[u8; 16]>(&format!("{name}/ciphertext"))
+            .unwrap();
+
+        if let Some(key) = key {
+            thread.assign(&key_ref, key).unwrap();
+        }
+        thread.assign(&msg_ref, [0u8; 16]).unwrap();
+
+        thread
+            .execute(
+                AES128.clone(),
+                &[key_ref, msg_ref],
+                &[ciphertext_ref.clone()],
+            )
+            .await
+            .unwrap();
+
+        thread
+            .decode(&[ciphertext_ref])
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn encrypt_unit<T: Thread + Execute + Decode + Send>(
+        name: String,
+        key: Option<[u8; 16]>,
+    ) -> impl for<'b> FnOnce(&'b mut T) -> Pin<Box<dyn Future<Output = [u8; 16]> + Send + 'b>> + Send
+    {
+        move |thread: &mut T| Box::pin(encrypt(thread, name, key))
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_deap() {
+        let (leader, follower) = create_mock_deap_vm();
+
+        let mut leader_scheduler = Scheduler::new(vec![leader]).unwrap();
+        let mut follower_scheduler = Scheduler::new(vec![follower]).unwrap();
+
+        // Both parties push the same requests in the same order.
+        for (scheduler, key) in [
+            (&mut leader_scheduler, Some([42u8; 16])),
+            (&mut follower_scheduler, None),
+        ] {
+            scheduler.push_units(
+                Priority::Background,
+                None,
+                (0..4).map(|i| encrypt_unit(format!("bulk/{i}"), key)),
+            );
+            scheduler.push(
+                Priority::Critical,
+                None,
+                encrypt_unit("online".to_string(), key),
+            );
+        }
+
+        let (leader_completed, follower_completed) =
+            futures::join!(leader_scheduler.run(), follower_scheduler.run());
+
+        let leader_results: Vec<_> = leader_completed
+            .into_iter()
+            .map(|completed| completed.results)
+            .collect();
+        let follower_results: Vec<_> = follower_completed
+            .into_iter()
+            .map(|completed| completed.results)
+            .collect();
+
+        // The online request completes first.
+        assert_eq!(leader_results[0].len(), 1);
+        assert_eq!(leader_results, follower_results);
+    }
+
+    /// Pushes bulk work before running, and an online request through a handle while running.
+    async fn serve<T: Thread + Execute + Decode + Send + 'static>(
+        mut scheduler: Scheduler<'static, T, [u8; 16]>,
+        key: Option<[u8; 16]>,
+    ) -> (Vec<Completed<[u8; 16]>>, Completed<[u8; 16]>) {
+        scheduler.push_units(
+            Priority::Background,
+            None,
+            (0..4).map(|i| encrypt_unit(format!("bulk/{i}"), key)),
+        );
+
+        let handle = scheduler.handle();
+        let online = async move {
+            handle
+                .push(
+                    Priority::Critical,
+                    None,
+                    encrypt_unit("online".to_string(), key),
+                )
+                .await
+                .unwrap()
+        };
+
+        futures::join!(scheduler.run(), online)
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_deap_threads() {
+        let (mut leader_exec, mut follower_exec) = test_mt_executor(8);
+        let (leader_ot_send, follower_ot_recv) = ideal_ot::<[Block; 2], Block>();
+        let (follower_ot_send, leader_ot_recv) = ideal_ot::<[Block; 2], Block>();
+
+        let mut leader_ctxs = Vec::new();
+        let mut follower_ctxs = Vec::new();
+        for _ in 0..3 {
+            let (leader_ctx, follower_ctx) =
+                futures::try_join!(leader_exec.new_thread(), follower_exec.new_thread()).unwrap();
+            leader_ctxs.push(leader_ctx);
+            follower_ctxs.push(follower_ctx);
+        }
+
+        let mut leader = DEAPThread::new(
+            Role::Leader,
+            [42u8; 32],
+            leader_ctxs.remove(0),
+            leader_ot_send.clone(),
+            leader_ot_recv.clone(),
+        );
+        let mut follower = DEAPThread::new(
+            Role::Follower,
+            [69u8; 32],
+            follower_ctxs.remove(0),
+            follower_ot_send.clone(),
+            follower_ot_recv.clone(),
+        );
+
+        let leader_scheduler = leader
+            .new_scheduler(
+                leader_ctxs
+                    .into_iter()
+                    .map(|ctx| (ctx, leader_ot_send.clone(), leader_ot_recv.clone())),
+            )
+            .unwrap();
+        let follower_scheduler = follower
+            .new_scheduler(
+                follower_ctxs
+                    .into_iter()
+                    .map(|ctx| (ctx, follower_ot_send.clone(), follower_ot_recv.clone())),
+            )
+            .unwrap();
+
+        let ((leader_bulk, leader_online), (follower_bulk, follower_online)) = futures::join!(
+            serve(leader_scheduler, Some([42u8; 16])),
+            serve(follower_scheduler, None)
+        );
+
+        assert_eq!(leader_bulk.len(), 1);
+        assert_eq!(leader_bulk[0].results, follower_bulk[0].results);
+        assert_eq!(leader_online.results, follower_online.results);
+
+        // The main threads finalize once the scheduler has run to completion.
+        let (leader_seed, follower_seed) = futures::join!(leader.finalize(), follower.finalize());
+        assert_eq!(leader_seed.unwrap(), Some([69u8; 32]));
+        assert_eq!(follower_seed.unwrap(), None);
+    }
+}
@@ -1,7 +1,9 @@
 //! Types associated with values in MPC.
 
-use std::sync::Arc;
+use std::{ops::RangeBounds, slice::SliceIndex, sync::Arc};
 
+use itybity::{FromBitIterator, IntoBits};
+use mpz_circuits::types::Value;
 use mpz_core::utils::blake3;
 
 /// A unique ID for a value.
@@ -24,6 +26,20 @@ impl ValueId {
         Self::new(&format!("{}/{}", self.0, counter))
     }
 
+    /// Returns the ID of a constant in the constant pool.
+    ///
+    /// The ID is derived from the type and the bits of the value, so every party computes the
+    /// same ID for the same constant.
+    pub(crate) fn constant(value: &Value) -> Self {
+        let bytes = Vec::<u8>::from_lsb0_iter(value.clone().into_iter_lsb0());
+        let digest: String = blake3(&bytes)[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        Self::new(&format!("__constant/{}/{}", value.value_type(), digest))
+    }
+
     /// Returns the u64 representation of the value ID.
     ///
     /// # Warning
@@ -44,7 +60,7 @@ impl AsRef<str> for ValueId {
 }
 
 /// A reference to an array value.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ArrayRef {
     ids: Vec<ValueId>,
 }
@@ -80,7 +96,7 @@ impl ArrayRef {
 ///
 /// Every single value is assigned a unique ID. Whereas, arrays are
 /// collections of values, and do not have their own ID.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(missing_docs)]
 pub enum ValueRef {
     /// A single value.
@@ -117,6 +133,80 @@ impl ValueRef {
         }
     }
 
+    /// Returns a reference to the element of an array value at the provided index.
+    ///
+    /// Returns `None` if the value is not an array, or if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<ValueRef> {
+        let ValueRef::Array(values) = self else {
+            return None;
+        };
+
+        values
+            .ids
+            .get(index)
+            .map(|id| ValueRef::Value { id: id.clone() })
+    }
+
+    /// Returns references to each element of an array value.
+    ///
+    /// The elements can be grouped into an array again with
+    /// [`Memory::array_from_values`](crate::Memory::array_from_values).
+    ///
+    /// Returns `None` if the value is not an array.
+    pub fn elements(&self) -> Option<Vec<ValueRef>> {
+        let ValueRef::Array(values) = self else {
+            return None;
+        };
+
+        Some(
+            values
+                .ids
+                .iter()
+                .map(|id| ValueRef::Value { id: id.clone() })
+                .collect(),
+        )
+    }
+
+    /// Returns a reference to a range of elements of an array value.
+    ///
+    /// Decoding the returned reference only reveals the selected elements, which is useful when
+    /// only part of an output is needed.
+    ///
+    /// Returns `None` if the value is not an array, or if the range is empty or out of bounds.
+    pub fn slice<R>(&self, range: R) -> Option<ValueRef>
+    where
+        R: RangeBounds<usize> + SliceIndex<[ValueId], Output = [ValueId]>,
+    {
+        let ValueRef::Array(values) = self else {
+            return None;
+        };
+
+        match values.ids.get(range) {
+            Some(ids) if !ids.is_empty() => Some(ValueRef::Array(ArrayRef::new(ids.to_vec()))),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the selected elements of an array value, in the provided order.
+    ///
+    /// Returns `None` if the value is not an array, or if no indices are provided or any index is
+    /// out of bounds.
+    pub fn select(&self, indices: &[usize]) -> Option<ValueRef> {
+        let ValueRef::Array(values) = self else {
+            return None;
+        };
+
+        if indices.is_empty() {
+            return None;
+        }
+
+        indices
+            .iter()
+            .map(|idx| values.ids.get(*idx).cloned())
+            .collect::<Option<Vec<_>>>()
+            .map(|ids| ValueRef::Array(ArrayRef::new(ids)))
+    }
+
     /// Returns `true` if the value is an array.
     pub fn is_array(&self) -> bool {
         matches!(self, ValueRef::Array(_))
@@ -157,3 +247,68 @@ pub(crate) struct CircuitRefs {
     pub(crate) inputs: Vec<ValueRef>,
     pub(crate) outputs: Vec<ValueRef>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn array(len: usize) -> ValueRef {
+        let id = ValueId::new("array");
+        ValueRef::Array(ArrayRef::new(
+            (0..len).map(|i| id.append_counter(i)).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_value_ref_slice() {
+        let value = array(8);
+
+        let slice = value.slice(..4).unwrap();
+        assert_eq!(slice.len(), 4);
+        assert!(slice.iter().eq(value.iter().take(4)));
+
+        let slice = value.slice(6..).unwrap();
+        assert!(slice.iter().eq(value.iter().skip(6)));
+
+        assert!(value.slice(4..4).is_none());
+        assert!(value.slice(..9).is_none());
+
+        let single = ValueRef::Value {
+            id: ValueId::new("single"),
+        };
+        assert!(single.slice(..).is_none());
+    }
+
+    #[test]
+    fn test_value_ref_select() {
+        let value = array(8);
+
+        let selected = value.select(&[7, 0]).unwrap();
+        let ids: Vec<_> = value.iter().collect();
+        assert!(selected.iter().eq([ids[7], ids[0]]));
+
+        assert!(value.select(&[]).is_none());
+        assert!(value.select(&[8]).is_none());
+    }
+
+    #[test]
+    fn test_value_ref_elements() {
+        let value = array(8);
+        let ids: Vec<_> = value.iter().collect();
+
+        let elem = value.get(3).unwrap();
+        assert!(!elem.is_array());
+        assert!(elem.iter().eq([ids[3]]));
+        assert!(value.get(8).is_none());
+
+        let elems = value.elements().unwrap();
+        assert_eq!(elems.len(), 8);
+        assert!(elems.iter().flat_map(|elem| elem.iter()).eq(value.iter()));
+
+        let single = ValueRef::Value {
+            id: ValueId::new("single"),
+        };
+        assert!(single.get(0).is_none());
+        assert!(single.elements().is_none());
+    }
+}
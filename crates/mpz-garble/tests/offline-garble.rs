@@ -2,7 +2,11 @@ use mpz_circuits::{circuits::AES128, types::StaticValueType};
 use mpz_common::executor::test_st_executor;
 use mpz_ot::ideal::ot::ideal_ot;
 
-use mpz_garble::{config::Visibility, Evaluator, Generator, GeneratorConfigBuilder, ValueMemory};
+use mpz_garble::{
+    artifact::{ArtifactHeader, GarbledArtifact},
+    config::Visibility,
+    Evaluator, EvaluatorError, Generator, GeneratorConfigBuilder, ValueMemory,
+};
 
 #[tokio::test]
 async fn test_offline_garble() {
@@ -131,3 +135,183 @@ async fn test_offline_garble() {
 
     assert_eq!(ciphertext, expected)
 }
+
+#[tokio::test]
+async fn test_offline_garble_artifact() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::default();
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let typ = <[u8; 16]>::value_type();
+
+    let mut gen_memory = ValueMemory::default();
+    let key_ref = gen_memory
+        .new_input("key", typ.clone(), Visibility::Private)
+        .unwrap();
+    let msg_ref = gen_memory
+        .new_input("msg", typ.clone(), Visibility::Blind)
+        .unwrap();
+    let ciphertext_ref = gen_memory.new_output("ciphertext", typ.clone()).unwrap();
+
+    gen.generate_input_encoding(&key_ref, &typ);
+    gen.generate_input_encoding(&msg_ref, &typ);
+
+    // Garble the circuit ahead of time and transfer it out-of-band.
+    let mut bytes = Vec::new();
+    gen.garble_artifact(
+        &AES128,
+        &[key_ref.clone(), msg_ref.clone()],
+        &[ciphertext_ref.clone()],
+        false,
+    )
+    .unwrap()
+    .write_to(&mut bytes)
+    .unwrap();
+
+    let artifact = GarbledArtifact::read_from(bytes.as_slice()).unwrap();
+    assert_eq!(artifact.header(), &ArtifactHeader::new(&AES128));
+
+    let gen_fut = async {
+        gen.release_artifact(
+            &mut ctx_a,
+            &[key_ref.clone(), msg_ref.clone()],
+            &[ciphertext_ref.clone()],
+        )
+        .await
+        .unwrap();
+
+        gen_memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &gen_memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.get_encoding(&ciphertext_ref).unwrap()
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", typ.clone(), Visibility::Private)
+            .unwrap();
+        let ciphertext_ref = memory.new_output("ciphertext", typ.clone()).unwrap();
+
+        ev.register_artifact(
+            &mut ctx_b,
+            AES128.clone(),
+            &[key_ref.clone(), msg_ref.clone()],
+            &[ciphertext_ref.clone()],
+            artifact,
+        )
+        .await
+        .unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                &mut ctx_b,
+                AES128.clone(),
+                &[key_ref.clone(), msg_ref.clone()],
+                &[ciphertext_ref.clone()],
+            )
+            .await
+            .unwrap();
+
+        ev.get_encoding(&ciphertext_ref).unwrap()
+    };
+
+    let (ciphertext_full_encoding, ciphertext_active_encoding) = tokio::join!(gen_fut, ev_fut);
+
+    let decoding = ciphertext_full_encoding.decoding();
+    let ciphertext: [u8; 16] = ciphertext_active_encoding
+        .decode(&decoding)
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
+
+#[tokio::test]
+async fn test_offline_garble_artifact_wrong_session() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+
+    let typ = <[u8; 16]>::value_type();
+
+    let mut memory = ValueMemory::default();
+    let key_ref = memory
+        .new_input("key", typ.clone(), Visibility::Private)
+        .unwrap();
+    let msg_ref = memory
+        .new_input("msg", typ.clone(), Visibility::Blind)
+        .unwrap();
+    let ciphertext_ref = memory.new_output("ciphertext", typ.clone()).unwrap();
+
+    let inputs = [key_ref.clone(), msg_ref.clone()];
+    let outputs = [ciphertext_ref.clone()];
+
+    // Garble the same circuit for the same values in two different sessions.
+    let [(gen, _), (_, artifact)] = [[0u8; 32], [1u8; 32]].map(|seed| {
+        let gen = Generator::new(GeneratorConfigBuilder::default().build().unwrap(), seed);
+        gen.generate_input_encoding(&key_ref, &typ);
+        gen.generate_input_encoding(&msg_ref, &typ);
+
+        let artifact = gen
+            .garble_artifact(&AES128, &inputs, &outputs, false)
+            .unwrap();
+
+        (gen, artifact)
+    });
+
+    let ev = Evaluator::default();
+
+    let (gen_result, ev_result) = tokio::join!(
+        gen.release_artifact(&mut ctx_a, &inputs, &outputs),
+        ev.register_artifact(&mut ctx_b, AES128.clone(), &inputs, &outputs, artifact),
+    );
+
+    gen_result.unwrap();
+    assert!(matches!(
+        ev_result.unwrap_err(),
+        EvaluatorError::InvalidArtifact(_)
+    ));
+}
@@ -1,8 +1,19 @@
-use mpz_circuits::{circuits::AES128, types::StaticValueType};
-use mpz_common::executor::test_st_executor;
+use std::sync::Arc;
+
+use futures::{FutureExt, TryStreamExt};
+use mpz_circuits::{
+    circuits::AES128,
+    ops::WrappingAdd,
+    types::{StaticValueType, Value},
+    CircuitBuilder,
+};
+use mpz_common::{executor::test_st_executor, Context, Poison};
 use mpz_ot::ideal::ot::ideal_ot;
 
-use mpz_garble::{config::Visibility, Evaluator, Generator, GeneratorConfigBuilder, ValueMemory};
+use mpz_garble::{
+    config::Visibility, Evaluator, EvaluatorConfig, EvaluatorError, Generator,
+    GeneratorConfigBuilder, ValueMemory,
+};
 
 #[tokio::test]
 async fn test_semi_honest() {
@@ -122,3 +133,823 @@ async fn test_semi_honest() {
 
     assert_eq!(ciphertext, expected)
 }
+
+#[tokio::test]
+async fn test_semi_honest_committed_decoding() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::default();
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let key_typ = <[u8; 16]>::value_type();
+    let msg_typ = <[u8; 16]>::value_type();
+    let ciphertext_typ = <[u8; 16]>::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Private)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.generate_input_encoding(&key_ref, &key_typ);
+        gen.generate_input_encoding(&msg_ref, &msg_typ);
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.generate(
+            &mut ctx_a,
+            AES128.clone(),
+            &[key_ref.clone(), msg_ref.clone()],
+            &[ciphertext_ref.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        gen.commit_decodings(&mut ctx_a, &[ciphertext_ref.clone()])
+            .await
+            .unwrap();
+
+        gen.decode_committed(&mut ctx_a, &[ciphertext_ref.clone()])
+            .await
+            .unwrap();
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Private)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                &mut ctx_b,
+                AES128.clone(),
+                &[key_ref.clone(), msg_ref.clone()],
+                &[ciphertext_ref.clone()],
+            )
+            .await
+            .unwrap();
+
+        ev.receive_decoding_digests(&mut ctx_b, &[ciphertext_ref.clone()])
+            .await
+            .unwrap();
+
+        ev.decode_committed(&mut ctx_b, &[ciphertext_ref.clone()])
+            .await
+            .unwrap()
+    };
+
+    let (_, mut values) = tokio::join!(gen_fut, ev_fut);
+
+    let ciphertext: [u8; 16] = values.pop().unwrap().try_into().unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
+
+#[tokio::test]
+async fn test_semi_honest_rerandomize() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::default();
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let key_typ = <[u8; 16]>::value_type();
+    let msg_typ = <[u8; 16]>::value_type();
+    let ciphertext_typ = <[u8; 16]>::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Private)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+        let rerandomized_ref = memory
+            .new_output("rerandomized", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.generate_input_encoding(&key_ref, &key_typ);
+        gen.generate_input_encoding(&msg_ref, &msg_typ);
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.generate(
+            &mut ctx_a,
+            AES128.clone(),
+            &[key_ref.clone(), msg_ref.clone()],
+            &[ciphertext_ref.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        gen.rerandomize(&mut ctx_a, &ciphertext_ref, &rerandomized_ref)
+            .await
+            .unwrap();
+
+        gen.decode(&mut ctx_a, &[rerandomized_ref]).await.unwrap();
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Private)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+        let rerandomized_ref = memory
+            .new_output("rerandomized", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                &mut ctx_b,
+                AES128.clone(),
+                &[key_ref.clone(), msg_ref.clone()],
+                &[ciphertext_ref.clone()],
+            )
+            .await
+            .unwrap();
+
+        ev.rerandomize(&mut ctx_b, &ciphertext_ref, &rerandomized_ref)
+            .await
+            .unwrap();
+
+        // The labels must be fresh.
+        let old_encoding = ev.get_encoding(&ciphertext_ref).unwrap();
+        let new_encoding = ev.get_encoding(&rerandomized_ref).unwrap();
+        assert!(old_encoding
+            .iter()
+            .zip(new_encoding.iter())
+            .all(|(old, new)| old != new));
+
+        ev.decode(&mut ctx_b, &[rerandomized_ref]).await.unwrap()
+    };
+
+    let (_, mut values) = tokio::join!(gen_fut, ev_fut);
+
+    let ciphertext: [u8; 16] = values.pop().unwrap().try_into().unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
+
+#[tokio::test]
+async fn test_semi_honest_chunked_decoding() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default()
+            .encoding_commitments()
+            .build()
+            .unwrap(),
+        [0u8; 32],
+    );
+    // Use a small chunk size so that the ciphertext is decoded and verified in several chunks.
+    let ev = Evaluator::new(
+        EvaluatorConfig::builder()
+            .encoding_commitments()
+            .decode_chunk_size(3)
+            .build()
+            .unwrap(),
+    );
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let key_typ = <[u8; 16]>::value_type();
+    let msg_typ = <[u8; 16]>::value_type();
+    let ciphertext_typ = <[u8; 16]>::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Private)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.generate_input_encoding(&key_ref, &key_typ);
+        gen.generate_input_encoding(&msg_ref, &msg_typ);
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.generate(
+            &mut ctx_a,
+            AES128.clone(),
+            &[key_ref.clone(), msg_ref.clone()],
+            &[ciphertext_ref.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        gen.decode(&mut ctx_a, &[ciphertext_ref.clone()])
+            .await
+            .unwrap();
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Private)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                &mut ctx_b,
+                AES128.clone(),
+                &[key_ref.clone(), msg_ref.clone()],
+                &[ciphertext_ref.clone()],
+            )
+            .await
+            .unwrap();
+
+        ev.decode(&mut ctx_b, &[ciphertext_ref.clone()])
+            .await
+            .unwrap()
+    };
+
+    let (_, mut values) = tokio::join!(gen_fut, ev_fut);
+
+    let ciphertext: [u8; 16] = values.pop().unwrap().try_into().unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
+
+#[tokio::test]
+async fn test_semi_honest_streamed_decoding() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::new(
+        EvaluatorConfig::builder()
+            .decode_chunk_size(3)
+            .max_in_flight_chunks(2)
+            .build()
+            .unwrap(),
+    );
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let key_typ = <[u8; 16]>::value_type();
+    let msg_typ = <[u8; 16]>::value_type();
+    let ciphertext_typ = <[u8; 16]>::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Private)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.generate_input_encoding(&key_ref, &key_typ);
+        gen.generate_input_encoding(&msg_ref, &msg_typ);
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.generate(
+            &mut ctx_a,
+            AES128.clone(),
+            &[key_ref.clone(), msg_ref.clone()],
+            &[ciphertext_ref.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        gen.decode(&mut ctx_a, &[ciphertext_ref.clone()])
+            .await
+            .unwrap();
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", key_typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", msg_typ.clone(), Visibility::Private)
+            .unwrap();
+        let ciphertext_ref = memory
+            .new_output("ciphertext", ciphertext_typ.clone())
+            .unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(
+                &mut ctx_b,
+                AES128.clone(),
+                &[key_ref.clone(), msg_ref.clone()],
+                &[ciphertext_ref.clone()],
+            )
+            .await
+            .unwrap();
+
+        ev.decode_array(&mut ctx_b, &ciphertext_ref)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+    };
+
+    let (_, chunks) = tokio::join!(gen_fut, ev_fut);
+
+    // The elements are yielded in order, in chunks of at most 3.
+    assert_eq!(
+        chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+        [3, 3, 3, 3, 3, 1]
+    );
+
+    let ciphertext: [u8; 16] = Value::Array(chunks.into_iter().flatten().collect())
+        .try_into()
+        .unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
+
+#[tokio::test]
+async fn test_semi_honest_soft_restart() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default().build().unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::default();
+
+    let key = [69u8; 16];
+    let msg = [42u8; 16];
+
+    let typ = <[u8; 16]>::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", typ.clone(), Visibility::Private)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let first_ref = memory.new_output("first", typ.clone()).unwrap();
+        let second_ref = memory.new_output("second", typ.clone()).unwrap();
+
+        memory.assign(&key_ref, key.into()).unwrap();
+
+        gen.generate_input_encoding(&key_ref, &typ);
+        gen.generate_input_encoding(&msg_ref, &typ);
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        for output_ref in [&first_ref, &second_ref] {
+            gen.generate(
+                &mut ctx_a,
+                AES128.clone(),
+                &[key_ref.clone(), msg_ref.clone()],
+                &[output_ref.clone()],
+                false,
+            )
+            .await
+            .unwrap();
+        }
+
+        gen.get_encoding(&second_ref).unwrap()
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let key_ref = memory
+            .new_input("key", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let msg_ref = memory
+            .new_input("msg", typ.clone(), Visibility::Private)
+            .unwrap();
+        let unknown_ref = memory
+            .new_input("unknown", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let first_ref = memory.new_output("first", typ.clone()).unwrap();
+        let second_ref = memory.new_output("second", typ.clone()).unwrap();
+
+        memory.assign(&msg_ref, msg.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[key_ref.clone(), msg_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        // The evaluator has no encoding for this input, so evaluation fails before the
+        // garbled circuit is consumed.
+        let err = ev
+            .evaluate(
+                &mut ctx_b,
+                AES128.clone(),
+                &[key_ref.clone(), unknown_ref],
+                &[first_ref],
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, EvaluatorError::MissingEncoding(_)));
+
+        ev.evaluate(
+            &mut ctx_b,
+            AES128.clone(),
+            &[key_ref, msg_ref],
+            &[second_ref.clone()],
+        )
+        .await
+        .unwrap();
+
+        ev.get_encoding(&second_ref).unwrap()
+    };
+
+    let (full_encoding, active_encoding) = tokio::join!(gen_fut, ev_fut);
+
+    let ciphertext: [u8; 16] = active_encoding
+        .decode(&full_encoding.decoding())
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let expected: [u8; 16] = {
+        use aes::{
+            cipher::{BlockEncrypt, KeyInit},
+            Aes128,
+        };
+
+        let mut msg = msg.into();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        cipher.encrypt_block(&mut msg);
+
+        msg.into()
+    };
+
+    assert_eq!(ciphertext, expected)
+}
+
+// Circuits this small are garbled inline and sent in a single frame.
+#[tokio::test]
+async fn test_semi_honest_small_circuit() {
+    let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+    let (mut ot_send, mut ot_recv) = ideal_ot();
+
+    let gen = Generator::new(
+        GeneratorConfigBuilder::default()
+            .encoding_commitments()
+            .build()
+            .unwrap(),
+        [0u8; 32],
+    );
+    let ev = Evaluator::new(
+        EvaluatorConfig::builder()
+            .encoding_commitments()
+            .build()
+            .unwrap(),
+    );
+
+    let circ = {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = a.wrapping_add(b);
+        builder.add_output(c);
+        Arc::new(builder.build().unwrap())
+    };
+
+    let typ = u8::value_type();
+
+    let gen_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let a_ref = memory
+            .new_input("a", typ.clone(), Visibility::Private)
+            .unwrap();
+        let b_ref = memory
+            .new_input("b", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let c_ref = memory.new_output("c", typ.clone()).unwrap();
+
+        memory.assign(&a_ref, 200u8.into()).unwrap();
+
+        gen.generate_input_encoding(&a_ref, &typ);
+        gen.generate_input_encoding(&b_ref, &typ);
+
+        gen.setup_assigned_values(
+            &mut ctx_a,
+            &memory.drain_assigned(&[a_ref.clone(), b_ref.clone()]),
+            &mut ot_send,
+        )
+        .await
+        .unwrap();
+
+        gen.generate(
+            &mut ctx_a,
+            circ.clone(),
+            &[a_ref, b_ref],
+            &[c_ref.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        gen.get_encoding(&c_ref).unwrap()
+    };
+
+    let ev_fut = async {
+        let mut memory = ValueMemory::default();
+
+        let a_ref = memory
+            .new_input("a", typ.clone(), Visibility::Blind)
+            .unwrap();
+        let b_ref = memory
+            .new_input("b", typ.clone(), Visibility::Private)
+            .unwrap();
+        let c_ref = memory.new_output("c", typ.clone()).unwrap();
+
+        memory.assign(&b_ref, 100u8.into()).unwrap();
+
+        ev.setup_assigned_values(
+            &mut ctx_b,
+            &memory.drain_assigned(&[a_ref.clone(), b_ref.clone()]),
+            &mut ot_recv,
+        )
+        .await
+        .unwrap();
+
+        _ = ev
+            .evaluate(&mut ctx_b, circ.clone(), &[a_ref, b_ref], &[c_ref.clone()])
+            .await
+            .unwrap();
+
+        ev.get_encoding(&c_ref).unwrap()
+    };
+
+    let (c_full, c_active) = tokio::join!(gen_fut, ev_fut);
+
+    let c: u8 = c_active
+        .decode(&c_full.decoding())
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    assert_eq!(c, 200u8.wrapping_add(100));
+}
+
+#[tokio::test]
+async fn test_semi_honest_cancelled_decode() {
+    let (_ctx_a, mut ctx_b) = test_st_executor(8);
+
+    let ev = Evaluator::default();
+
+    let mut memory = ValueMemory::default();
+    let value_ref = memory
+        .new_output("value", <[u8; 16]>::value_type())
+        .unwrap();
+
+    // The generator never sends the decodings, so the decode is dropped part way through.
+    assert!(ev
+        .decode(&mut ctx_b, &[value_ref.clone()])
+        .now_or_never()
+        .is_none());
+
+    assert!(ev.is_poisoned());
+
+    let err = ev.decode(&mut ctx_b, &[value_ref]).await.unwrap_err();
+    assert!(matches!(err, EvaluatorError::Poisoned(_)));
+}
+
+#[tokio::test]
+async fn test_semi_honest_cancelled_stream() {
+    let (_ctx_a, mut ctx_b) = test_st_executor(8);
+
+    let ev = Evaluator::default();
+
+    let mut memory = ValueMemory::default();
+    let key_ref = memory
+        .new_input("key", <[u8; 16]>::value_type(), Visibility::Blind)
+        .unwrap();
+    let msg_ref = memory
+        .new_input("msg", <[u8; 16]>::value_type(), Visibility::Blind)
+        .unwrap();
+    let ciphertext_ref = memory
+        .new_output("ciphertext", <[u8; 16]>::value_type())
+        .unwrap();
+
+    // The generator never sends the garbled circuit, so the evaluator waits until it is cancelled.
+    let cancel = ctx_b.cancel_token().clone();
+    let inputs = [key_ref, msg_ref];
+    let outputs = [ciphertext_ref];
+    let (result, _) = tokio::join!(
+        ev.receive_garbled_circuit(&mut ctx_b, AES128.clone(), &inputs, &outputs),
+        async {
+            tokio::task::yield_now().await;
+            cancel.cancel();
+        }
+    );
+
+    assert!(matches!(result, Err(EvaluatorError::Cancelled(_))));
+}
@@ -8,6 +8,7 @@
 //!                                                                                       
 //! Note that this is an OLE with errors implementation. A malicious sender is allowed to set its own
 //! output and can introduce additive errors into the receiver's output.
+//! See [`crate::malicious`] for a maliciously secure variant.
 
 mod receiver;
 mod sender;
@@ -34,8 +35,8 @@ mod tests {
     use crate::core::{ReceiverShare, SenderShare};
     use crate::tests::create_rot;
     use mpz_core::{prg::Prg, Block};
-    use mpz_fields::{p256::P256, UniformRand};
-    use rand::SeedableRng;
+    use mpz_fields::{curve25519::Curve25519Scalar, p256::P256, p384::P384, Field, UniformRand};
+    use rand::{distributions::Standard, prelude::Distribution, SeedableRng};
 
     #[test]
     fn test_ole_core() {
@@ -56,12 +57,21 @@ mod tests {
 
     #[test]
     fn test_ole_core_vec() {
+        ole_core_vec::<P256>();
+        ole_core_vec::<P384>();
+        ole_core_vec::<Curve25519Scalar>();
+    }
+
+    fn ole_core_vec<F: Field>()
+    where
+        Standard: Distribution<F>,
+    {
         let count = 12;
         let from_seed = Prg::from_seed(Block::ZERO);
         let mut rng = from_seed;
 
-        let sender_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
-        let receiver_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let sender_input: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
+        let receiver_input: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
 
         let (ot_messages, ot_message_choices) = create_rot(receiver_input.clone());
 
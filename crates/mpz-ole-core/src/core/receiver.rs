@@ -93,11 +93,26 @@ impl<F: Field> ReceiverShare<F> {
         Ok(shares)
     }
 
+    /// Creates a receiver share from its input and output.
+    pub(crate) fn from_parts(input: F, output: F) -> Self {
+        Self { input, output }
+    }
+
     /// Returns the receiver's output share.
     pub fn inner(self) -> F {
         self.output
     }
 
+    /// Returns the receiver's input.
+    pub(crate) fn input(&self) -> F {
+        self.input
+    }
+
+    /// Returns the receiver's output.
+    pub(crate) fn output(&self) -> F {
+        self.output
+    }
+
     /// Adjusts a preprocessed share.
     ///
     /// This is an implementation of <https://crypto.stackexchange.com/questions/100634/converting-a-random-ole-oblivious-linear-function-evaluation-to-an-ole>.
@@ -119,6 +134,16 @@ impl<F: Field> ReceiverShare<F> {
             ShareAdjust(self.input + target),
         )
     }
+
+    /// Corrects the output share of the receiver.
+    ///
+    /// Used for chosen-input OLE, where the sender chooses its own output.
+    pub(crate) fn correct(self, correction: F) -> Self {
+        Self {
+            input: self.input,
+            output: self.output + correction,
+        }
+    }
 }
 
 /// Intermediate type for share adjustment of the receiver.
@@ -88,11 +88,26 @@ impl<F: Field> SenderShare<F> {
         Ok((shares, masked))
     }
 
+    /// Creates a sender share from its input and output.
+    pub(crate) fn from_parts(input: F, output: F) -> Self {
+        Self { input, output }
+    }
+
     /// Returns the sender's output share.
     pub fn inner(self) -> F {
         self.output
     }
 
+    /// Returns the sender's input.
+    pub(crate) fn input(&self) -> F {
+        self.input
+    }
+
+    /// Returns the sender's output.
+    pub(crate) fn output(&self) -> F {
+        self.output
+    }
+
     /// Adjusts a preprocessed share.
     ///
     /// This is an implementation of <https://crypto.stackexchange.com/questions/100634/converting-a-random-ole-oblivious-linear-function-evaluation-to-an-ole>.
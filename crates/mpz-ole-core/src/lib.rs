@@ -9,13 +9,17 @@
 pub mod ideal;
 
 pub mod core;
+pub mod malicious;
 pub mod msg;
 mod receiver;
 mod sender;
+mod share;
 
+pub use malicious::{MaliciousOLEReceiver, MaliciousOLESender};
 pub use receiver::{BatchReceiverAdjust, OLEReceiver};
 pub use sender::{BatchSenderAdjust, OLESender};
 use serde::{Deserialize, Serialize};
+pub use share::{OLEParty, OLEShare};
 
 /// An OLE transfer identifier.
 ///
@@ -33,7 +37,7 @@ impl std::fmt::Display for TransferId {
 
 impl TransferId {
     /// Returns the current transfer ID, incrementing `self` in-place.
-    pub(crate) fn next(&mut self) -> Self {
+    pub fn next(&mut self) -> Self {
         let id = *self;
         self.0 += 1;
         id
@@ -54,16 +58,30 @@ pub enum OLEError {
     MultipleOf(usize, usize),
     #[error("Wrong transfer id. Got {0}, expected {1}")]
     WrongId(TransferId, TransferId),
+    #[error("Number of outputs does not match the number of inputs. Got {0}, expected {1}")]
+    UnequalOutputs(usize, usize),
+    #[error("Wrong number of output corrections. Got {0}, expected {1}")]
+    UnequalCorrections(usize, usize),
+    #[error("Share is not from the counterpart, got a share of the {0:?}")]
+    NotCounterpart(OLEParty),
+    #[error("Share length does not match the counterpart. Got {0}, expected {1}")]
+    UnequalShares(usize, usize),
+    #[error("Wrong number of check inputs. Got {0}, expected {1}")]
+    UnequalCheckInputs(usize, usize),
+    #[error("Wrong number of opened check values. Got {0}, expected {1}")]
+    UnequalOpening(usize, usize),
+    #[error("OLE consistency check failed")]
+    ConsistencyCheck,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{OLEReceiver, OLESender};
+    use crate::{OLEError, OLEReceiver, OLESender};
     use itybity::ToBits;
     use mpz_core::{prg::Prg, Block};
-    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_fields::{p256::P256, Field, UniformRand};
     use mpz_ot_core::ideal::rot::IdealROT;
-    use rand::SeedableRng;
+    use rand::{distributions::Standard, prelude::Distribution, SeedableRng};
 
     #[test]
     fn test_ole_sender_receiver_preprocess() {
@@ -133,13 +151,89 @@ mod tests {
             .for_each(|(((&a, b), x), y)| assert_eq!(y.inner(), a * b + x.inner()));
     }
 
-    pub(crate) fn create_rot(receiver_choices: Vec<P256>) -> (Vec<[P256; 2]>, Vec<P256>) {
+    #[test]
+    fn test_ole_sender_receiver_chosen() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (mut sender, mut receiver) =
+            (OLESender::<P256>::default(), OLEReceiver::<P256>::default());
+
+        let sender_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let receiver_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (ot_messages, ot_message_choices) = create_rot(receiver_input.clone());
+
+        let masked = sender
+            .preprocess(sender_input.clone(), ot_messages)
+            .unwrap();
+        receiver
+            .preprocess(receiver_input.clone(), ot_message_choices, masked)
+            .unwrap();
+
+        let sender_targets: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let sender_outputs: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let receiver_targets: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (sender_adjust, s_to_r_adjust) = sender.adjust(sender_targets.clone()).unwrap();
+        let (receiver_adjust, r_to_s_adjust) = receiver.adjust(receiver_targets.clone()).unwrap();
+
+        let correction = sender_adjust
+            .finish_chosen(r_to_s_adjust, sender_outputs.clone())
+            .unwrap();
+        let receiver_shares = receiver_adjust
+            .finish_chosen(s_to_r_adjust, correction)
+            .unwrap();
+
+        sender_targets
+            .iter()
+            .zip(receiver_targets)
+            .zip(sender_outputs)
+            .zip(receiver_shares)
+            .for_each(|(((&a, b), x), y)| assert_eq!(y.inner(), a * b + x));
+    }
+
+    #[test]
+    fn test_ole_sender_chosen_unequal_outputs() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (mut sender, mut receiver) =
+            (OLESender::<P256>::default(), OLEReceiver::<P256>::default());
+
+        let sender_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let receiver_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (ot_messages, ot_message_choices) = create_rot(receiver_input.clone());
+
+        let masked = sender.preprocess(sender_input, ot_messages).unwrap();
+        receiver
+            .preprocess(receiver_input, ot_message_choices, masked)
+            .unwrap();
+
+        let targets: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let outputs: Vec<P256> = (0..count - 1).map(|_| P256::rand(&mut rng)).collect();
+
+        let (sender_adjust, _) = sender.adjust(targets.clone()).unwrap();
+        let (_, r_to_s_adjust) = receiver.adjust(targets).unwrap();
+
+        let err = sender_adjust
+            .finish_chosen(r_to_s_adjust, outputs)
+            .unwrap_err();
+
+        assert!(matches!(err, OLEError::UnequalOutputs(11, 12)));
+    }
+
+    pub(crate) fn create_rot<F: Field>(receiver_choices: Vec<F>) -> (Vec<[F; 2]>, Vec<F>)
+    where
+        Standard: Distribution<F>,
+    {
         let mut rot = IdealROT::default();
         let receiver_choices: Vec<bool> = receiver_choices.iter_lsb0().collect();
-        let (rot_sender, rot_receiver) = rot.random_with_choices::<P256>(receiver_choices);
+        let (rot_sender, rot_receiver) = rot.random_with_choices::<F>(receiver_choices);
 
-        let ot_messages: Vec<[P256; 2]> = rot_sender.msgs;
-        let ot_message_choices: Vec<P256> = rot_receiver.msgs;
+        let ot_messages: Vec<[F; 2]> = rot_sender.msgs;
+        let ot_message_choices: Vec<F> = rot_receiver.msgs;
 
         (ot_messages, ot_message_choices)
     }
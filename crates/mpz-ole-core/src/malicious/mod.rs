@@ -0,0 +1,253 @@
+//! Maliciously secure OLE.
+//!
+//! The OLE in [`crate::core`] is an OLE with errors: a malicious sender can use a different input
+//! for every bit of the receiver's input, which introduces an error into the receiver's output
+//! that depends on the receiver's input.
+//!
+//! This module follows the full protocol of <https://eprint.iacr.org/2016/505>.
+//!
+//! # Input encoding
+//!
+//! The receiver does not use the bits of its input as OT choices. Instead its random input is
+//! encoded as `b_k = Σ_i g_i * c_{k,i}` from [`ot_count`] uniformly random choice bits
+//! `c_{k,i}`, where `g` is a public random gadget vector. The encoding has [`SSP`] more bits than
+//! a field element, so `b_k` stays statistically close to uniform even if a malicious sender
+//! learns some of the choice bits of an OLE.
+//!
+//! # Consistency check
+//!
+//! Every random OT is expanded into a second, independent correlation with the same choice bit,
+//! which the sender uses for a uniformly random check input `r_k`. This yields a second OLE
+//! `y'_k = r_k * b_k + x'_k` with the same receiver input `b_k`. After the sender has committed to
+//! its correlations, both parties sample weights `χ_k` with a coin-toss, and the sender opens
+//!
+//! - `α_k = χ_k * a_k + r_k`
+//! - `ρ_k = χ_k * x_k + x'_k`
+//!
+//! which the receiver checks against `χ_k * y_k + y'_k = α_k * b_k + ρ_k`. The check OLEs are
+//! sacrificed, so the opening does not reveal anything about the sender's inputs and outputs.
+//!
+//! A sender which cheats on some OTs learns the corresponding choice bits from whether the check
+//! fails. Because of the input encoding this does not leak the receiver's input, as long as the
+//! session is aborted after the first failed check. Adjusting an OLE to a chosen input only
+//! reveals the difference to the random input, which remains hidden.
+
+mod receiver;
+mod sender;
+
+pub use receiver::MaliciousOLEReceiver;
+pub use sender::MaliciousOLESender;
+
+use mpz_core::{prg::Prg, utils::blake3, Block};
+use mpz_fields::Field;
+use rand::SeedableRng;
+
+/// Statistical security parameter of the receiver's input encoding.
+pub const SSP: usize = 64;
+
+/// Returns the number of random OTs which are needed for one OLE.
+pub fn ot_count<F: Field>() -> usize {
+    F::BIT_SIZE + SSP
+}
+
+/// Returns the public gadget vector which encodes the receiver's input.
+fn gadget<F: Field>() -> Vec<F> {
+    let seed: [u8; 16] = blake3(b"mpz-ole/malicious/gadget")[..16]
+        .try_into()
+        .expect("slice should have length 16");
+
+    let mut rng = Prg::from_seed(Block::from(seed));
+    (0..ot_count::<F>()).map(|_| F::rand(&mut rng)).collect()
+}
+
+/// Expands a random OT message into the message of the check correlation.
+fn expand<F: Field>(message: F) -> F {
+    let seed: [u8; 16] = blake3(&message.to_le_bytes())[..16]
+        .try_into()
+        .expect("slice should have length 16");
+
+    F::rand(&mut Prg::from_seed(Block::from(seed)))
+}
+
+/// Returns the weights of the consistency check.
+fn chis<F: Field>(chi_seed: Block, count: usize) -> Vec<F> {
+    let mut rng = Prg::from_seed(chi_seed);
+    (0..count).map(|_| F::rand(&mut rng)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        msg::{CheckOpening, MaliciousCorrelations},
+        OLEError,
+    };
+    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_ot_core::ideal::rot::IdealROT;
+    use rand::Rng;
+
+    struct Setup {
+        sender: MaliciousOLESender<P256>,
+        receiver: MaliciousOLEReceiver<P256>,
+        sender_input: Vec<P256>,
+        choices: Vec<bool>,
+        masked: MaliciousCorrelations<P256>,
+        received: Vec<P256>,
+    }
+
+    fn setup(count: usize) -> Setup {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let mut sender = MaliciousOLESender::<P256>::default();
+
+        let sender_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let check_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let choices: Vec<bool> = (0..count * ot_count::<P256>()).map(|_| rng.gen()).collect();
+
+        let (rot_sender, rot_receiver) =
+            IdealROT::default().random_with_choices::<P256>(choices.clone());
+
+        let masked = sender
+            .preprocess(sender_input.clone(), check_input, rot_sender.msgs)
+            .unwrap();
+
+        Setup {
+            sender,
+            receiver: MaliciousOLEReceiver::default(),
+            sender_input,
+            choices,
+            masked,
+            received: rot_receiver.msgs,
+        }
+    }
+
+    #[test]
+    fn test_malicious_ole() {
+        let count = 12;
+        let Setup {
+            mut sender,
+            mut receiver,
+            sender_input,
+            choices,
+            masked,
+            received,
+        } = setup(count);
+
+        receiver
+            .preprocess(choices.clone(), received, masked)
+            .unwrap();
+
+        assert_eq!(sender.cache_size(), 0);
+        assert_eq!(receiver.cache_size(), 0);
+
+        let chi_seed = Block::ONES;
+        let opening = sender.check(chi_seed);
+        receiver.check(chi_seed, opening).unwrap();
+
+        let sender_shares = sender.consume(count).unwrap();
+        let receiver_shares = receiver.consume(count).unwrap();
+
+        let gadget = gadget::<P256>();
+        sender_input
+            .iter()
+            .zip(choices.chunks_exact(ot_count::<P256>()))
+            .zip(sender_shares)
+            .zip(receiver_shares)
+            .for_each(|(((&a, choices), x), y)| {
+                // The receiver's input is encoded from its choice bits.
+                let b = choices
+                    .iter()
+                    .zip(&gadget)
+                    .filter(|(&choice, _)| choice)
+                    .fold(P256::zero(), |acc, (_, &g)| acc + g);
+
+                assert_eq!(y.input(), b);
+                assert_eq!(y.inner(), a * b + x.inner());
+            });
+    }
+
+    #[test]
+    fn test_malicious_ole_detects_error() {
+        let count = 12;
+        let Setup {
+            mut sender,
+            mut receiver,
+            choices,
+            masked,
+            received,
+            ..
+        } = setup(count);
+
+        receiver.preprocess(choices, received, masked).unwrap();
+
+        let chi_seed = Block::ONES;
+        let CheckOpening {
+            mut inputs,
+            outputs,
+        } = sender.check(chi_seed);
+
+        // Tamper with the opening.
+        inputs[3] = inputs[3] + P256::one();
+
+        let err = receiver
+            .check(chi_seed, CheckOpening { inputs, outputs })
+            .unwrap_err();
+
+        assert!(matches!(err, OLEError::ConsistencyCheck));
+        assert_eq!(receiver.cache_size(), 0);
+    }
+
+    #[test]
+    fn test_malicious_ole_detects_inconsistent_input() {
+        let count = 12;
+        let Setup {
+            mut sender,
+            mut receiver,
+            choices,
+            mut masked,
+            received,
+            ..
+        } = setup(count);
+
+        // The sender uses another input for an OT whose choice bit is set, which introduces an
+        // error into the receiver's output.
+        let i = choices.iter().position(|&choice| choice).unwrap();
+        masked.masks[i] = masked.masks[i] + P256::one();
+
+        receiver.preprocess(choices, received, masked).unwrap();
+
+        let chi_seed = Block::ONES;
+        let err = receiver
+            .check(chi_seed, sender.check(chi_seed))
+            .unwrap_err();
+
+        assert!(matches!(err, OLEError::ConsistencyCheck));
+    }
+
+    #[test]
+    fn test_malicious_ole_wrong_ot_count() {
+        let mut sender = MaliciousOLESender::<P256>::default();
+        let mut receiver = MaliciousOLEReceiver::<P256>::default();
+
+        let err = sender
+            .preprocess(
+                vec![P256::one()],
+                vec![P256::one()],
+                vec![[P256::zero(); 2]; P256::BIT_SIZE],
+            )
+            .unwrap_err();
+        assert!(matches!(err, OLEError::ExpectedMultipleOf(..)));
+
+        let err = receiver
+            .preprocess(
+                vec![false; P256::BIT_SIZE],
+                vec![P256::zero(); P256::BIT_SIZE],
+                MaliciousCorrelations {
+                    masks: vec![P256::zero(); P256::BIT_SIZE],
+                    check_masks: vec![P256::zero(); P256::BIT_SIZE],
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OLEError::MultipleOf(..)));
+    }
+}
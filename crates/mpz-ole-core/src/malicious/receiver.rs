@@ -0,0 +1,166 @@
+//! Receiver implementation of maliciously secure OLE.
+
+use crate::{
+    core::ReceiverShare,
+    malicious::{chis, expand, gadget, ot_count},
+    msg::{BatchAdjust, CheckOpening, MaliciousCorrelations},
+    BatchReceiverAdjust, OLEError, OLEReceiver,
+};
+use mpz_core::Block;
+use mpz_fields::Field;
+
+/// A receiver for batched, maliciously secure OLE.
+#[derive(Debug)]
+pub struct MaliciousOLEReceiver<F> {
+    ole: OLEReceiver<F>,
+    /// OLEs which have not been checked yet, together with the output of their check OLE.
+    unchecked: Vec<(ReceiverShare<F>, F)>,
+}
+
+impl<F: Field> Default for MaliciousOLEReceiver<F> {
+    fn default() -> Self {
+        MaliciousOLEReceiver {
+            ole: OLEReceiver::default(),
+            unchecked: Vec::default(),
+        }
+    }
+}
+
+impl<F: Field> MaliciousOLEReceiver<F> {
+    /// Generates new OLEs which need to be checked with [`MaliciousOLEReceiver::check`].
+    ///
+    /// The inputs of the OLEs are encoded from the choice bits, see [`crate::malicious`].
+    ///
+    /// # Arguments
+    ///
+    /// * `choices` - The uniformly random choice bits of [`ot_count`] random OTs per OLE.
+    /// * `random` - The received messages of the random OTs.
+    /// * `masked` - The correlations from the sender.
+    pub fn preprocess(
+        &mut self,
+        choices: Vec<bool>,
+        random: Vec<F>,
+        masked: MaliciousCorrelations<F>,
+    ) -> Result<(), OLEError> {
+        let MaliciousCorrelations { masks, check_masks } = masked;
+
+        let ot_count = ot_count::<F>();
+        if choices.len() % ot_count != 0 {
+            return Err(OLEError::MultipleOf(choices.len(), ot_count));
+        }
+
+        if random.len() != choices.len() {
+            return Err(OLEError::ExpectedMultipleOf(choices.len(), random.len()));
+        }
+
+        if masks.len() != choices.len() || check_masks.len() != choices.len() {
+            return Err(OLEError::WrongNumberOfMasks(
+                masks.len().min(check_masks.len()),
+                choices.len(),
+            ));
+        }
+
+        let gadget = gadget::<F>();
+        let ots = choices
+            .chunks_exact(ot_count)
+            .zip(random.chunks_exact(ot_count))
+            .zip(masks.chunks_exact(ot_count))
+            .zip(check_masks.chunks_exact(ot_count));
+
+        for (((choices, random), masks), check_masks) in ots {
+            let (mut b, mut y, mut check_y) = (F::zero(), F::zero(), F::zero());
+            for (i, &g) in gadget.iter().enumerate() {
+                let t = random[i];
+                let check_t = expand(t);
+
+                if choices[i] {
+                    b = b + g;
+                    y = y + g * (masks[i] + t);
+                    check_y = check_y + g * (check_masks[i] + check_t);
+                } else {
+                    y = y + g * t;
+                    check_y = check_y + g * check_t;
+                }
+            }
+
+            self.unchecked
+                .push((ReceiverShare::from_parts(b, y), check_y));
+        }
+
+        Ok(())
+    }
+
+    /// Performs the consistency check for all unchecked OLEs.
+    ///
+    /// If the check succeeds the OLEs are available for consumption. Otherwise they are
+    /// discarded and the sender must be considered malicious.
+    ///
+    /// # ⚠️ Warning ⚠️
+    ///
+    /// The provided seed must be unbiased! It should be generated using a secure coin-toss
+    /// protocol **after** the sender has sent its correlations to the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `chi_seed` - The seed used to generate the consistency check weights.
+    /// * `opening` - The sender's opening of the consistency check.
+    pub fn check(&mut self, chi_seed: Block, opening: CheckOpening<F>) -> Result<(), OLEError> {
+        let unchecked = std::mem::take(&mut self.unchecked);
+        let CheckOpening { inputs, outputs } = opening;
+
+        if inputs.len() != unchecked.len() || outputs.len() != unchecked.len() {
+            return Err(OLEError::UnequalOpening(
+                inputs.len().max(outputs.len()),
+                unchecked.len(),
+            ));
+        }
+
+        let chis = chis::<F>(chi_seed, unchecked.len());
+
+        let valid = unchecked
+            .iter()
+            .zip(chis)
+            .zip(inputs.into_iter().zip(outputs))
+            .all(|(((share, y), chi), (alpha, rho))| {
+                chi * share.output() + *y == alpha * share.input() + rho
+            });
+
+        if !valid {
+            return Err(OLEError::ConsistencyCheck);
+        }
+
+        self.ole
+            .insert(unchecked.into_iter().map(|(share, _)| share));
+
+        Ok(())
+    }
+
+    /// Returns checked OLEs from the internal cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of shares to return.
+    ///
+    /// # Returns
+    ///
+    /// * A vector of [`ReceiverShare`]s containing the OLE outputs for the receiver.
+    pub fn consume(&mut self, count: usize) -> Option<Vec<ReceiverShare<F>>> {
+        self.ole.consume(count)
+    }
+
+    /// Adjusts checked OLEs in the internal cache.
+    ///
+    /// See [`OLEReceiver::adjust`].
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - The new OLE receiver inputs.
+    pub fn adjust(&mut self, targets: Vec<F>) -> Option<(BatchReceiverAdjust<F>, BatchAdjust<F>)> {
+        self.ole.adjust(targets)
+    }
+
+    /// Returns the number of checked OLEs that are available.
+    pub fn cache_size(&self) -> usize {
+        self.ole.cache_size()
+    }
+}
@@ -0,0 +1,148 @@
+//! Sender implementation of maliciously secure OLE.
+
+use crate::{
+    core::SenderShare,
+    malicious::{chis, expand, gadget, ot_count},
+    msg::{BatchAdjust, CheckOpening, MaliciousCorrelations},
+    BatchSenderAdjust, OLEError, OLESender,
+};
+use mpz_core::Block;
+use mpz_fields::Field;
+
+/// A sender for batched, maliciously secure OLE.
+#[derive(Debug)]
+pub struct MaliciousOLESender<F> {
+    ole: OLESender<F>,
+    /// OLEs which have not been checked yet, together with the input and output of their check
+    /// OLE.
+    unchecked: Vec<(SenderShare<F>, F, F)>,
+}
+
+impl<F: Field> Default for MaliciousOLESender<F> {
+    fn default() -> Self {
+        MaliciousOLESender {
+            ole: OLESender::default(),
+            unchecked: Vec::default(),
+        }
+    }
+}
+
+impl<F: Field> MaliciousOLESender<F> {
+    /// Generates new OLEs which need to be checked with [`MaliciousOLESender::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The sender's OLE input shares.
+    /// * `check_input` - Uniformly random field elements used as inputs of the check OLEs.
+    /// * `random` - The messages of [`ot_count`] random OTs per OLE.
+    ///
+    /// # Returns
+    ///
+    /// * [`MaliciousCorrelations`], which are to be sent to the receiver.
+    pub fn preprocess(
+        &mut self,
+        input: Vec<F>,
+        check_input: Vec<F>,
+        random: Vec<[F; 2]>,
+    ) -> Result<MaliciousCorrelations<F>, OLEError> {
+        if input.len() != check_input.len() {
+            return Err(OLEError::UnequalCheckInputs(check_input.len(), input.len()));
+        }
+
+        let ot_count = ot_count::<F>();
+        if input.len() * ot_count != random.len() {
+            return Err(OLEError::ExpectedMultipleOf(
+                input.len() * ot_count,
+                random.len(),
+            ));
+        }
+
+        let gadget = gadget::<F>();
+        let mut masks = Vec::with_capacity(random.len());
+        let mut check_masks = Vec::with_capacity(random.len());
+
+        for ((&a, &r), random) in input
+            .iter()
+            .zip(&check_input)
+            .zip(random.chunks_exact(ot_count))
+        {
+            let (x, check_x) = random.iter().zip(&gadget).fold(
+                (F::zero(), F::zero()),
+                |(x, check_x), (&[zero, one], &g)| {
+                    let (check_zero, check_one) = (expand(zero), expand(one));
+
+                    masks.push(zero + -one + a);
+                    check_masks.push(check_zero + -check_one + r);
+
+                    (x + g * zero, check_x + g * check_zero)
+                },
+            );
+
+            self.unchecked
+                .push((SenderShare::from_parts(a, x), r, check_x));
+        }
+
+        Ok(MaliciousCorrelations { masks, check_masks })
+    }
+
+    /// Performs the consistency check for all unchecked OLEs.
+    ///
+    /// Afterwards the OLEs are available for consumption.
+    ///
+    /// # ⚠️ Warning ⚠️
+    ///
+    /// The provided seed must be unbiased! It should be generated using a secure coin-toss
+    /// protocol **after** the sender has sent its correlations to the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `chi_seed` - The seed used to generate the consistency check weights.
+    ///
+    /// # Returns
+    ///
+    /// * [`CheckOpening`], which needs to be sent to the receiver.
+    pub fn check(&mut self, chi_seed: Block) -> CheckOpening<F> {
+        let unchecked = std::mem::take(&mut self.unchecked);
+        let chis = chis::<F>(chi_seed, unchecked.len());
+
+        let (inputs, outputs) = unchecked
+            .iter()
+            .zip(chis)
+            .map(|((share, r, x), chi)| (chi * share.input() + *r, chi * share.output() + *x))
+            .unzip();
+
+        self.ole
+            .insert(unchecked.into_iter().map(|(share, ..)| share));
+
+        CheckOpening { inputs, outputs }
+    }
+
+    /// Returns checked OLEs from the internal cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of shares to return.
+    ///
+    /// # Returns
+    ///
+    /// * A vector of [`SenderShare`]s containing the OLE output for the sender.
+    pub fn consume(&mut self, count: usize) -> Option<Vec<SenderShare<F>>> {
+        self.ole.consume(count)
+    }
+
+    /// Adjusts checked OLEs in the internal cache.
+    ///
+    /// See [`OLESender::adjust`].
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - The new OLE sender inputs.
+    pub fn adjust(&mut self, targets: Vec<F>) -> Option<(BatchSenderAdjust<F>, BatchAdjust<F>)> {
+        self.ole.adjust(targets)
+    }
+
+    /// Returns the number of checked OLEs that are available.
+    pub fn cache_size(&self) -> usize {
+        self.ole.cache_size()
+    }
+}
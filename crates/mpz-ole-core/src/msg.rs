@@ -43,3 +43,29 @@ pub struct BatchAdjust<F> {
     pub id: TransferId,
     pub adjustments: Vec<F>,
 }
+
+/// Message type for sending the output corrections of chosen-input OLE to the receiver.
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchCorrection<F> {
+    pub id: TransferId,
+    pub corrections: Vec<F>,
+}
+
+/// Message type for sending the masked correlations of malicious OLE to the receiver.
+///
+/// Contains one mask per random OT, see [`crate::malicious::ot_count`].
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaliciousCorrelations<F> {
+    pub masks: Vec<F>,
+    pub check_masks: Vec<F>,
+}
+
+/// Message type for opening the consistency check of malicious OLE to the receiver.
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckOpening<F> {
+    pub inputs: Vec<F>,
+    pub outputs: Vec<F>,
+}
@@ -2,7 +2,7 @@
 
 use crate::{
     core::{ReceiverAdjust, ReceiverShare, ShareAdjust},
-    msg::{BatchAdjust, MaskedCorrelations},
+    msg::{BatchAdjust, BatchCorrection, MaskedCorrelations},
     OLEError, TransferId,
 };
 use mpz_fields::Field;
@@ -101,6 +101,11 @@ impl<F: Field> OLEReceiver<F> {
     pub fn cache_size(&self) -> usize {
         self.cache.len()
     }
+
+    /// Stores OLEs in the internal cache.
+    pub(crate) fn insert(&mut self, shares: impl IntoIterator<Item = ReceiverShare<F>>) {
+        self.cache.extend(shares);
+    }
 }
 
 /// Receiver adjustments waiting for [`BatchAdjust`] from the sender.
@@ -110,6 +115,11 @@ pub struct BatchReceiverAdjust<F> {
 }
 
 impl<F: Field> BatchReceiverAdjust<F> {
+    /// Returns the transfer id of the adjustment.
+    pub fn id(&self) -> TransferId {
+        self.id
+    }
+
     /// Completes the adjustment and returns the new shares.
     ///
     /// # Arguments
@@ -145,4 +155,42 @@ impl<F: Field> BatchReceiverAdjust<F> {
 
         Ok(shares)
     }
+
+    /// Completes the adjustment of chosen-input OLE and returns the new shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_adjust` - The sender's adjustments.
+    /// * `batch_correction` - The sender's output corrections.
+    ///
+    /// # Returns
+    ///
+    /// * A vector of [`ReceiverShare`]s containing the new OLE outputs for the receiver.
+    pub fn finish_chosen(
+        self,
+        batch_adjust: BatchAdjust<F>,
+        batch_correction: BatchCorrection<F>,
+    ) -> Result<Vec<ReceiverShare<F>>, OLEError> {
+        if self.id != batch_correction.id {
+            return Err(OLEError::WrongId(batch_correction.id, self.id));
+        }
+
+        let shares = self.finish_adjust(batch_adjust)?;
+        let corrections = batch_correction.corrections;
+
+        if shares.len() != corrections.len() {
+            return Err(OLEError::UnequalCorrections(
+                corrections.len(),
+                shares.len(),
+            ));
+        }
+
+        let shares = shares
+            .into_iter()
+            .zip(corrections)
+            .map(|(s, c)| s.correct(c))
+            .collect();
+
+        Ok(shares)
+    }
 }
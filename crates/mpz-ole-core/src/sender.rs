@@ -2,7 +2,7 @@
 
 use crate::{
     core::{SenderAdjust, SenderShare, ShareAdjust},
-    msg::{BatchAdjust, MaskedCorrelations},
+    msg::{BatchAdjust, BatchCorrection, MaskedCorrelations},
     OLEError, TransferId,
 };
 use mpz_fields::Field;
@@ -102,6 +102,11 @@ impl<F: Field> OLESender<F> {
     pub fn cache_size(&self) -> usize {
         self.cache.len()
     }
+
+    /// Stores OLEs in the internal cache.
+    pub(crate) fn insert(&mut self, shares: impl IntoIterator<Item = SenderShare<F>>) {
+        self.cache.extend(shares);
+    }
 }
 
 /// Sender adjustments waiting for [`BatchAdjust`] from the receiver.
@@ -111,6 +116,11 @@ pub struct BatchSenderAdjust<F> {
 }
 
 impl<F: Field> BatchSenderAdjust<F> {
+    /// Returns the transfer id of the adjustment.
+    pub fn id(&self) -> TransferId {
+        self.id
+    }
+
     /// Completes the adjustment and returns the new shares.
     ///
     /// # Arguments
@@ -146,4 +156,38 @@ impl<F: Field> BatchSenderAdjust<F> {
 
         Ok(shares)
     }
+
+    /// Completes the adjustment with chosen outputs.
+    ///
+    /// Instead of keeping the random outputs of [`BatchSenderAdjust::finish_adjust`], the sender
+    /// chooses its outputs and corrects the receiver's outputs accordingly.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_adjust` - The receiver's adjustments.
+    /// * `outputs` - The sender's chosen outputs.
+    ///
+    /// # Returns
+    ///
+    /// * [`BatchCorrection`] which needs to be sent to the receiver.
+    pub fn finish_chosen(
+        self,
+        batch_adjust: BatchAdjust<F>,
+        outputs: Vec<F>,
+    ) -> Result<BatchCorrection<F>, OLEError> {
+        if self.adjust.len() != outputs.len() {
+            return Err(OLEError::UnequalOutputs(outputs.len(), self.adjust.len()));
+        }
+
+        let id = self.id;
+        let shares = self.finish_adjust(batch_adjust)?;
+
+        let corrections = shares
+            .into_iter()
+            .zip(outputs)
+            .map(|(s, x)| x + -s.inner())
+            .collect();
+
+        Ok(BatchCorrection { id, corrections })
+    }
 }
@@ -0,0 +1,138 @@
+//! OLE output shares bound to their transfer.
+
+use crate::{OLEError, TransferId};
+use serde::{Deserialize, Serialize};
+
+/// A party of an OLE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OLEParty {
+    /// The OLE sender.
+    Sender,
+    /// The OLE receiver.
+    Receiver,
+}
+
+impl OLEParty {
+    /// Returns the counterpart of this party.
+    pub fn counterpart(&self) -> Self {
+        match self {
+            OLEParty::Sender => OLEParty::Receiver,
+            OLEParty::Receiver => OLEParty::Sender,
+        }
+    }
+}
+
+/// The outputs of a batched OLE transfer.
+///
+/// The outputs are bound to the [`TransferId`] of the transfer and the party which holds them, so
+/// that outputs of different transfers can not be combined by accident.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OLEShare<F> {
+    id: TransferId,
+    party: OLEParty,
+    values: Vec<F>,
+}
+
+impl<F> OLEShare<F> {
+    /// Creates a new share.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the transfer which produced the outputs.
+    /// * `party` - The party which holds the outputs.
+    /// * `values` - The OLE outputs.
+    pub fn new(id: TransferId, party: OLEParty, values: Vec<F>) -> Self {
+        Self { id, party, values }
+    }
+
+    /// Returns the id of the transfer which produced the share.
+    pub fn id(&self) -> TransferId {
+        self.id
+    }
+
+    /// Returns the party which holds the share.
+    pub fn party(&self) -> OLEParty {
+        self.party
+    }
+
+    /// Returns the number of outputs.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the share contains no outputs.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the OLE outputs.
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    /// Returns the OLE outputs, consuming the share.
+    pub fn into_inner(self) -> Vec<F> {
+        self.values
+    }
+
+    /// Verifies that a share of the other party was produced by the same transfer as this share.
+    ///
+    /// This should be called before combining this share with data derived from the counterpart's
+    /// share.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The transfer id of the counterpart's share.
+    /// * `party` - The party which holds the counterpart's share.
+    /// * `len` - The number of outputs of the counterpart's share.
+    pub fn verify_counterpart(
+        &self,
+        id: TransferId,
+        party: OLEParty,
+        len: usize,
+    ) -> Result<(), OLEError> {
+        if party != self.party.counterpart() {
+            return Err(OLEError::NotCounterpart(party));
+        }
+
+        if id != self.id {
+            return Err(OLEError::WrongId(id, self.id));
+        }
+
+        if len != self.values.len() {
+            return Err(OLEError::UnequalShares(len, self.values.len()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ole_share_verify_counterpart() {
+        let mut id = TransferId::default();
+        let (first, second) = (id.next(), id.next());
+
+        let share = OLEShare::new(first, OLEParty::Receiver, vec![0u8; 4]);
+
+        share
+            .verify_counterpart(first, OLEParty::Sender, 4)
+            .unwrap();
+
+        assert!(matches!(
+            share.verify_counterpart(second, OLEParty::Sender, 4),
+            Err(OLEError::WrongId(..))
+        ));
+        assert!(matches!(
+            share.verify_counterpart(first, OLEParty::Receiver, 4),
+            Err(OLEError::NotCounterpart(OLEParty::Receiver))
+        ));
+        assert!(matches!(
+            share.verify_counterpart(first, OLEParty::Sender, 3),
+            Err(OLEError::UnequalShares(3, 4))
+        ));
+    }
+}
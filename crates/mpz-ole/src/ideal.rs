@@ -1,6 +1,9 @@
 //! Ideal OLE implementation.
 
-use crate::{OLEError, OLEReceiver, OLESender};
+use crate::{
+    ChosenOLEReceiver, ChosenOLESender, OLEError, OLEParty, OLEReceiver, OLESender, OLEShare,
+    TransferId,
+};
 use async_trait::async_trait;
 use mpz_common::{
     ideal::{ideal_f2p, Alice, Bob},
@@ -10,19 +13,24 @@ use mpz_fields::Field;
 use rand::thread_rng;
 
 /// Ideal OLESender.
-pub struct IdealOLESender(Alice<()>);
+pub struct IdealOLESender(Alice<TransferId>);
 
 /// Ideal OLEReceiver.
-pub struct IdealOLEReceiver(Bob<()>);
+pub struct IdealOLEReceiver(Bob<TransferId>);
 
 /// Returns an OLE sender and receiver pair.
 pub fn ideal_ole() -> (IdealOLESender, IdealOLEReceiver) {
-    let (alice, bob) = ideal_f2p(());
+    let (alice, bob) = ideal_f2p(TransferId::default());
 
     (IdealOLESender(alice), IdealOLEReceiver(bob))
 }
 
-fn ole<F: Field>(_: &mut (), alice_input: Vec<F>, bob_input: Vec<F>) -> (Vec<F>, Vec<F>) {
+fn ole<F: Field>(
+    id: &mut TransferId,
+    alice_input: Vec<F>,
+    bob_input: Vec<F>,
+) -> (OLEShare<F>, OLEShare<F>) {
+    let id = id.next();
     let mut rng = thread_rng();
     let alice_output: Vec<F> = (0..alice_input.len()).map(|_| F::rand(&mut rng)).collect();
 
@@ -33,7 +41,28 @@ fn ole<F: Field>(_: &mut (), alice_input: Vec<F>, bob_input: Vec<F>) -> (Vec<F>,
         .map(|((&a, &b), x)| a * b + x)
         .collect();
 
-    (alice_output, bob_output)
+    (
+        OLEShare::new(id, OLEParty::Sender, alice_output),
+        OLEShare::new(id, OLEParty::Receiver, bob_output),
+    )
+}
+
+fn chosen_ole<F: Field>(
+    id: &mut TransferId,
+    alice_input: (Vec<F>, Vec<F>),
+    bob_input: Vec<F>,
+) -> ((), OLEShare<F>) {
+    let id = id.next();
+    let (a_k, x_k) = alice_input;
+
+    let bob_output: Vec<F> = a_k
+        .iter()
+        .zip(bob_input.iter())
+        .zip(x_k)
+        .map(|((&a, &b), x)| a * b + x)
+        .collect();
+
+    ((), OLEShare::new(id, OLEParty::Receiver, bob_output))
 }
 
 impl Allocate for IdealOLESender {
@@ -64,21 +93,45 @@ impl<Ctx: Context> Preprocess<Ctx> for IdealOLEReceiver {
 
 #[async_trait]
 impl<F: Field, Ctx: Context> OLESender<Ctx, F> for IdealOLESender {
-    async fn send(&mut self, ctx: &mut Ctx, a_k: Vec<F>) -> Result<Vec<F>, OLEError> {
+    async fn send(&mut self, ctx: &mut Ctx, a_k: Vec<F>) -> Result<OLEShare<F>, OLEError> {
         Ok(self.0.call(ctx, a_k, ole).await)
     }
 }
 
 #[async_trait]
 impl<F: Field, Ctx: Context> OLEReceiver<Ctx, F> for IdealOLEReceiver {
-    async fn receive(&mut self, ctx: &mut Ctx, b_k: Vec<F>) -> Result<Vec<F>, OLEError> {
+    async fn receive(&mut self, ctx: &mut Ctx, b_k: Vec<F>) -> Result<OLEShare<F>, OLEError> {
         Ok(self.0.call(ctx, b_k, ole).await)
     }
 }
 
+#[async_trait]
+impl<F: Field, Ctx: Context> ChosenOLESender<Ctx, F> for IdealOLESender {
+    async fn send_chosen(
+        &mut self,
+        ctx: &mut Ctx,
+        a_k: Vec<F>,
+        x_k: Vec<F>,
+    ) -> Result<(), OLEError> {
+        self.0.call(ctx, (a_k, x_k), chosen_ole).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<F: Field, Ctx: Context> ChosenOLEReceiver<Ctx, F> for IdealOLEReceiver {
+    async fn receive_chosen(
+        &mut self,
+        ctx: &mut Ctx,
+        b_k: Vec<F>,
+    ) -> Result<OLEShare<F>, OLEError> {
+        Ok(self.0.call(ctx, b_k, chosen_ole).await)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{ideal::ideal_ole, OLEReceiver, OLESender};
+    use crate::{ideal::ideal_ole, ChosenOLEReceiver, ChosenOLESender, OLEReceiver, OLESender};
     use mpz_common::executor::test_st_executor;
     use mpz_core::{prg::Prg, Block};
     use mpz_fields::{p256::P256, UniformRand};
@@ -102,6 +155,10 @@ mod tests {
         )
         .unwrap();
 
+        y_k.verify_counterpart(x_k.id(), x_k.party(), x_k.len())
+            .unwrap();
+        let (x_k, y_k) = (x_k.into_inner(), y_k.into_inner());
+
         assert_eq!(x_k.len(), count);
         assert_eq!(y_k.len(), count);
         a_k.iter()
@@ -110,4 +167,32 @@ mod tests {
             .zip(y_k)
             .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
     }
+
+    #[tokio::test]
+    async fn test_ideal_chosen_ole() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let x_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let (mut sender, mut receiver) = ideal_ole();
+
+        let (_, y_k) = tokio::try_join!(
+            sender.send_chosen(&mut ctx_sender, a_k.clone(), x_k.clone()),
+            receiver.receive_chosen(&mut ctx_receiver, b_k.clone())
+        )
+        .unwrap();
+        let y_k = y_k.into_inner();
+
+        assert_eq!(y_k.len(), count);
+        a_k.iter()
+            .zip(b_k)
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
+    }
 }
@@ -17,7 +17,11 @@ use std::{
 
 #[cfg(feature = "ideal")]
 pub mod ideal;
+pub mod malicious;
 pub mod rot;
+pub mod stream;
+
+pub use mpz_ole_core::{OLEParty, OLEShare, TransferId};
 
 /// Batch OLE Sender.
 ///
@@ -36,7 +40,7 @@ pub trait OLESender<Ctx: Context, F: Field> {
     /// # Returns
     ///
     /// * The sender's OLE outputs `x_k`.
-    async fn send(&mut self, ctx: &mut Ctx, inputs: Vec<F>) -> Result<Vec<F>, OLEError>;
+    async fn send(&mut self, ctx: &mut Ctx, inputs: Vec<F>) -> Result<OLEShare<F>, OLEError>;
 }
 
 /// Batch OLE Receiver.
@@ -56,7 +60,115 @@ pub trait OLEReceiver<Ctx: Context, F: Field> {
     /// # Returns
     ///
     /// * The receiver's OLE outputs `y_k`.
-    async fn receive(&mut self, ctx: &mut Ctx, inputs: Vec<F>) -> Result<Vec<F>, OLEError>;
+    async fn receive(&mut self, ctx: &mut Ctx, inputs: Vec<F>) -> Result<OLEShare<F>, OLEError>;
+}
+
+/// Batch chosen-input OLE Sender.
+///
+/// The sender inputs field elements `a_k` and chooses its outputs `x_k`, such that
+/// `y_k = a_k * b_k + x_k` holds, where `b_k` and `y_k` are the [`ChosenOLEReceiver`]'s inputs and
+/// outputs respectively.
+#[async_trait]
+pub trait ChosenOLESender<Ctx: Context, F: Field> {
+    /// Sends his masked inputs and outputs to the [`ChosenOLEReceiver`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `inputs` - The sender's OLE inputs `a_k`.
+    /// * `outputs` - The sender's OLE outputs `x_k`.
+    async fn send_chosen(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: Vec<F>,
+        outputs: Vec<F>,
+    ) -> Result<(), OLEError>;
+}
+
+/// Batch chosen-input OLE Receiver.
+///
+/// The receiver inputs field elements `b_k` and gets outputs `y_k`, such that
+/// `y_k = a_k * b_k + x_k` holds, where `a_k` and `x_k` are the [`ChosenOLESender`]'s inputs and
+/// outputs respectively.
+#[async_trait]
+pub trait ChosenOLEReceiver<Ctx: Context, F: Field> {
+    /// Receives the masked inputs and outputs of the [`ChosenOLESender`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `inputs` - The receiver's OLE inputs `b_k`.
+    ///
+    /// # Returns
+    ///
+    /// * The receiver's OLE outputs `y_k`.
+    async fn receive_chosen(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: Vec<F>,
+    ) -> Result<OLEShare<F>, OLEError>;
+}
+
+/// Streamed OLE Sender.
+///
+/// Like [`OLESender`], but takes the inputs `a_k` as an iterator and outputs `x_k` chunk by chunk,
+/// so that the batch does not need to fit in memory. See [`stream`] for details.
+#[async_trait]
+pub trait StreamOLESender<Ctx: Context, F: Field> {
+    /// Sends the masked inputs to the [`StreamOLEReceiver`], preprocessing the OLEs as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `config` - The stream configuration, which must match the receiver's.
+    /// * `inputs` - The sender's OLE inputs, which must have the same length as the receiver's.
+    /// * `output` - Called with the sender's OLE outputs `x_k` of each chunk, in order.
+    ///
+    /// # Returns
+    ///
+    /// * The number of OLEs which were performed.
+    async fn send_stream<I, O>(
+        &mut self,
+        ctx: &mut Ctx,
+        config: &stream::StreamConfig,
+        inputs: I,
+        output: O,
+    ) -> Result<usize, OLEError>
+    where
+        I: IntoIterator<Item = F> + Send,
+        I::IntoIter: Send,
+        O: FnMut(OLEShare<F>) -> Result<(), OLEError> + Send;
+}
+
+/// Streamed OLE Receiver.
+///
+/// Like [`OLEReceiver`], but takes the inputs `b_k` as an iterator and outputs `y_k` chunk by
+/// chunk, so that the batch does not need to fit in memory. See [`stream`] for details.
+#[async_trait]
+pub trait StreamOLEReceiver<Ctx: Context, F: Field> {
+    /// Receives the masked inputs of the [`StreamOLESender`], preprocessing the OLEs as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `config` - The stream configuration, which must match the sender's.
+    /// * `inputs` - The receiver's OLE inputs, which must have the same length as the sender's.
+    /// * `output` - Called with the receiver's OLE outputs `y_k` of each chunk, in order.
+    ///
+    /// # Returns
+    ///
+    /// * The number of OLEs which were performed.
+    async fn receive_stream<I, O>(
+        &mut self,
+        ctx: &mut Ctx,
+        config: &stream::StreamConfig,
+        inputs: I,
+        output: O,
+    ) -> Result<usize, OLEError>
+    where
+        I: IntoIterator<Item = F> + Send,
+        I::IntoIter: Send,
+        O: FnMut(OLEShare<F>) -> Result<(), OLEError> + Send;
 }
 
 /// An OLE error.
@@ -83,11 +195,14 @@ impl Display for OLEError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.kind {
             OLEErrorKind::Context => write!(f, "Context Error"),
+            OLEErrorKind::Cointoss => write!(f, "Cointoss Error"),
             OLEErrorKind::OT => write!(f, "OT Error"),
             OLEErrorKind::IO => write!(f, "IO Error"),
             OLEErrorKind::Core => write!(f, "OLE Core Error"),
             OLEErrorKind::Field => write!(f, "FieldError"),
             OLEErrorKind::InsufficientOLEs => write!(f, "Insufficient OLEs"),
+            OLEErrorKind::Tag => write!(f, "Tag Error"),
+            OLEErrorKind::Beacon => write!(f, "Beacon Error"),
         }?;
 
         if let Some(source) = self.source.as_ref() {
@@ -101,11 +216,14 @@ impl Display for OLEError {
 #[derive(Debug)]
 pub(crate) enum OLEErrorKind {
     Context,
+    Cointoss,
     OT,
     IO,
     Core,
     Field,
     InsufficientOLEs,
+    Tag,
+    Beacon,
 }
 
 impl From<mpz_common::ContextError> for OLEError {
@@ -120,6 +238,30 @@ impl From<mpz_common::sync::MutexError> for OLEError {
     }
 }
 
+impl From<mpz_common::store::InsufficientCorrelations> for OLEError {
+    fn from(value: mpz_common::store::InsufficientCorrelations) -> Self {
+        Self::new(OLEErrorKind::InsufficientOLEs, value)
+    }
+}
+
+impl From<mpz_common::beacon::BeaconError> for OLEError {
+    fn from(value: mpz_common::beacon::BeaconError) -> Self {
+        Self::new(OLEErrorKind::Beacon, value)
+    }
+}
+
+impl From<mpz_cointoss::CointossError> for OLEError {
+    fn from(value: mpz_cointoss::CointossError) -> Self {
+        Self::new(OLEErrorKind::Cointoss, value)
+    }
+}
+
+impl From<mpz_common::tag::TagError> for OLEError {
+    fn from(value: mpz_common::tag::TagError) -> Self {
+        Self::new(OLEErrorKind::Tag, value)
+    }
+}
+
 impl From<OTError> for OLEError {
     fn from(value: OTError) -> Self {
         Self::new(OLEErrorKind::OT, value)
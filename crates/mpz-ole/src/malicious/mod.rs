@@ -0,0 +1,98 @@
+//! Implementation of maliciously secure OLE based on random OT.
+//!
+//! See [`mpz_ole_core::malicious`] for details on the consistency check.
+
+mod receiver;
+mod sender;
+
+pub use receiver::MaliciousOLEReceiver;
+pub use sender::MaliciousOLESender;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        malicious::{MaliciousOLEReceiver, MaliciousOLESender},
+        OLEReceiver as _, OLESender as _,
+    };
+    use mpz_common::{
+        beacon::test_utils::TestBeacon, executor::test_st_executor, tag::Tag, Allocate, Preprocess,
+    };
+    use mpz_core::{prg::Prg, Block};
+    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_ot::ideal::rot::ideal_rot;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_malicious_ole() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let mut ole_sender = MaliciousOLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = MaliciousOLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        ole_sender.alloc(count);
+        ole_receiver.alloc(count);
+
+        tokio::try_join!(
+            ole_sender.preprocess(&mut ctx_sender),
+            ole_receiver.preprocess(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let (x_k, y_k) = tokio::try_join!(
+            ole_sender.send(&mut ctx_sender, a_k.clone()),
+            ole_receiver.receive(&mut ctx_receiver, b_k.clone())
+        )
+        .unwrap();
+
+        y_k.verify_counterpart(x_k.id(), x_k.party(), x_k.len())
+            .unwrap();
+        let (x_k, y_k) = (x_k.into_inner(), y_k.into_inner());
+
+        assert_eq!(x_k.len(), count);
+        assert_eq!(y_k.len(), count);
+        a_k.iter()
+            .zip(b_k)
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
+    }
+
+    #[tokio::test]
+    async fn test_malicious_ole_beacon() {
+        let count = 12;
+
+        for (receiver_beacon, ok) in [(0, true), (1, false)] {
+            let (rot_sender, rot_receiver) = ideal_rot();
+
+            let mut ole_sender = MaliciousOLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+            let mut ole_receiver =
+                MaliciousOLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+            ole_sender.set_beacon(Arc::new(TestBeacon(0)));
+            ole_receiver.set_beacon(Arc::new(TestBeacon(receiver_beacon)));
+
+            let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+            ole_sender.alloc(count);
+            ole_receiver.alloc(count);
+
+            let (sender_result, receiver_result) = tokio::join!(
+                ole_sender.preprocess(&mut ctx_sender),
+                ole_receiver.preprocess(&mut ctx_receiver)
+            );
+
+            // Only the receiver checks the sender's opening.
+            sender_result.unwrap();
+            assert_eq!(receiver_result.is_ok(), ok);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{OLEError, OLEParty, OLEReceiver as OLEReceive, OLEShare};
+use async_trait::async_trait;
+use mpz_cointoss as cointoss;
+use mpz_common::{
+    beacon::{Beacon, ChallengeDeriver},
+    scoped_futures::ScopedFutureExt as _,
+    store::CorrelatedStore,
+    tag::{Side, Tag, TagBinding},
+    Allocate, Context, Preprocess, ThreadId,
+};
+use mpz_core::Block;
+use mpz_fields::Field;
+use mpz_ole_core::{
+    malicious::ot_count,
+    msg::{BatchAdjust, CheckOpening, MaliciousCorrelations},
+    BatchReceiverAdjust, MaliciousOLEReceiver as MaliciousOLECoreReceiver,
+};
+use mpz_ot::{OTError, RandomOTReceiver};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
+use std::sync::Arc;
+
+/// Maliciously secure OLE receiver.
+#[derive(Debug)]
+pub struct MaliciousOLEReceiver<T, F> {
+    tag: TagBinding,
+    rot_receiver: T,
+    store: CorrelatedStore<MaliciousOLECoreReceiver<F>>,
+    /// Derives the consistency check challenges.
+    challenges: ChallengeDeriver,
+}
+
+impl<T, F> MaliciousOLEReceiver<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    /// Creates a new receiver for the purpose given by `tag`.
+    pub fn new(tag: Tag, rot_receiver: T) -> Self {
+        Self {
+            tag: TagBinding::new(tag, Side::Receiver),
+            rot_receiver,
+            store: CorrelatedStore::default(),
+            challenges: ChallengeDeriver::default(),
+        }
+    }
+
+    /// Sets a beacon whose randomness is mixed into the consistency check challenges.
+    ///
+    /// The other party must use the same beacon, see [`mpz_common::beacon`] for details.
+    pub fn set_beacon(&mut self, beacon: Arc<dyn Beacon>) {
+        self.challenges.set_beacon(beacon);
+    }
+
+    pub(crate) fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchReceiverAdjust<F>, BatchAdjust<F>), OLEError> {
+        let adjust = self
+            .store
+            .consume(id, inputs.len(), |core| core.adjust(inputs))?;
+
+        Ok(adjust.expect("store tracks the size of the cache"))
+    }
+}
+
+impl<T, F> Allocate for MaliciousOLEReceiver<T, F>
+where
+    T: Allocate,
+    F: Field,
+{
+    fn alloc(&mut self, count: usize) {
+        self.rot_receiver.alloc(count * ot_count::<F>());
+        self.store.alloc(count);
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, F> Preprocess<Ctx> for MaliciousOLEReceiver<T, F>
+where
+    Ctx: Context,
+    T: Preprocess<Ctx, Error = OTError> + RandomOTReceiver<Ctx, bool, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    type Error = OLEError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OLEError> {
+        self.tag.bind(ctx).await?;
+
+        let rot_receiver = &mut self.rot_receiver;
+        let challenges = &mut self.challenges;
+        let tag = self.tag.tag();
+        self.store
+            .refill(ctx, |ctx, core, count| {
+                async move {
+                    rot_receiver.preprocess(ctx).await?;
+
+                    let random_ot = rot_receiver
+                        .receive_random(ctx, count * ot_count::<F>())
+                        .await?;
+
+                    let masks = ctx
+                        .io_mut()
+                        .expect_next::<MaliciousCorrelations<F>>()
+                        .await?;
+                    core.preprocess(random_ot.choices, random_ot.msgs, masks)?;
+
+                    // Sample the check weights after the sender has committed to its correlations.
+                    let seed: Block = thread_rng().gen();
+                    let chi_seed = cointoss::cointoss_receiver(ctx, vec![seed]).await?[0];
+                    let chi_seed = tag.bind_seed("ole/malicious/check", chi_seed);
+                    let chi_seed = challenges
+                        .derive(tag, "ole/malicious/check", chi_seed)
+                        .await?;
+
+                    let opening = ctx.io_mut().expect_next::<CheckOpening<F>>().await?;
+                    core.check(chi_seed, opening)?;
+
+                    Ok::<_, OLEError>(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Send, F, Ctx: Context> OLEReceive<Ctx, F> for MaliciousOLEReceiver<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    async fn receive(&mut self, ctx: &mut Ctx, b_k: Vec<F>) -> Result<OLEShare<F>, OLEError> {
+        let (receiver_adjust, adjust) = self.adjust(ctx.id(), b_k)?;
+        let id = receiver_adjust.id();
+
+        let channel = ctx.io_mut();
+        channel.send(adjust).await?;
+        let adjust = channel.expect_next::<BatchAdjust<F>>().await?;
+
+        let shares = receiver_adjust.finish_adjust(adjust)?;
+        let y_k = shares.into_iter().map(|s| s.inner()).collect();
+
+        Ok(OLEShare::new(id, OLEParty::Receiver, y_k))
+    }
+}
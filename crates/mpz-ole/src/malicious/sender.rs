@@ -0,0 +1,152 @@
+use crate::{OLEError, OLEParty, OLESender as OLESend, OLEShare};
+use async_trait::async_trait;
+use mpz_cointoss as cointoss;
+use mpz_common::{
+    beacon::{Beacon, ChallengeDeriver},
+    scoped_futures::ScopedFutureExt as _,
+    store::CorrelatedStore,
+    tag::{Side, Tag, TagBinding},
+    Allocate, Context, Preprocess, ThreadId,
+};
+use mpz_core::Block;
+use mpz_fields::Field;
+use mpz_ole_core::{
+    malicious::ot_count, msg::BatchAdjust, BatchSenderAdjust,
+    MaliciousOLESender as MaliciousOLECoreSender,
+};
+use mpz_ot::{OTError, RandomOTSender};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
+use std::sync::Arc;
+
+/// Maliciously secure OLE sender.
+#[derive(Debug)]
+pub struct MaliciousOLESender<T, F> {
+    tag: TagBinding,
+    rot_sender: T,
+    store: CorrelatedStore<MaliciousOLECoreSender<F>>,
+    /// Derives the consistency check challenges.
+    challenges: ChallengeDeriver,
+}
+
+impl<T, F> MaliciousOLESender<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    /// Creates a new sender for the purpose given by `tag`.
+    pub fn new(tag: Tag, rot_sender: T) -> Self {
+        Self {
+            tag: TagBinding::new(tag, Side::Sender),
+            rot_sender,
+            store: CorrelatedStore::default(),
+            challenges: ChallengeDeriver::default(),
+        }
+    }
+
+    /// Sets a beacon whose randomness is mixed into the consistency check challenges.
+    ///
+    /// The other party must use the same beacon, see [`mpz_common::beacon`] for details.
+    pub fn set_beacon(&mut self, beacon: Arc<dyn Beacon>) {
+        self.challenges.set_beacon(beacon);
+    }
+
+    pub(crate) fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchSenderAdjust<F>, BatchAdjust<F>), OLEError> {
+        let adjust = self
+            .store
+            .consume(id, inputs.len(), |core| core.adjust(inputs))?;
+
+        Ok(adjust.expect("store tracks the size of the cache"))
+    }
+}
+
+impl<T, F> Allocate for MaliciousOLESender<T, F>
+where
+    T: Allocate,
+    F: Field,
+{
+    fn alloc(&mut self, count: usize) {
+        self.rot_sender.alloc(count * ot_count::<F>());
+        self.store.alloc(count);
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, F> Preprocess<Ctx> for MaliciousOLESender<T, F>
+where
+    Ctx: Context,
+    T: Allocate + Preprocess<Ctx, Error = OTError> + RandomOTSender<Ctx, [F; 2]> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    type Error = OLEError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OLEError> {
+        self.tag.bind(ctx).await?;
+
+        let rot_sender = &mut self.rot_sender;
+        let challenges = &mut self.challenges;
+        let tag = self.tag.tag();
+        self.store
+            .refill(ctx, |ctx, core, count| {
+                async move {
+                    rot_sender.preprocess(ctx).await?;
+
+                    let (random, check_random) = {
+                        let mut rng = thread_rng();
+                        (
+                            (0..count).map(|_| F::rand(&mut rng)).collect(),
+                            (0..count).map(|_| F::rand(&mut rng)).collect(),
+                        )
+                    };
+
+                    let random_ot: Vec<[F; 2]> = rot_sender
+                        .send_random(ctx, count * ot_count::<F>())
+                        .await?
+                        .msgs;
+
+                    let masks = core.preprocess(random, check_random, random_ot)?;
+                    ctx.io_mut().send(masks).await?;
+
+                    // Sample the check weights after committing to the correlations.
+                    let seed: Block = thread_rng().gen();
+                    let chi_seed = cointoss::cointoss_sender(ctx, vec![seed]).await?[0];
+                    let chi_seed = tag.bind_seed("ole/malicious/check", chi_seed);
+                    let chi_seed = challenges
+                        .derive(tag, "ole/malicious/check", chi_seed)
+                        .await?;
+
+                    let opening = core.check(chi_seed);
+                    ctx.io_mut().send(opening).await?;
+
+                    Ok::<_, OLEError>(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Send, F, Ctx: Context> OLESend<Ctx, F> for MaliciousOLESender<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    async fn send(&mut self, ctx: &mut Ctx, a_k: Vec<F>) -> Result<OLEShare<F>, OLEError> {
+        let (sender_adjust, adjust) = self.adjust(ctx.id(), a_k)?;
+        let id = sender_adjust.id();
+
+        let channel = ctx.io_mut();
+        channel.send(adjust).await?;
+        let adjust = channel.expect_next::<BatchAdjust<F>>().await?;
+
+        let shares = sender_adjust.finish_adjust(adjust)?;
+        let x_k = shares.into_iter().map(|s| s.inner()).collect();
+
+        Ok(OLEShare::new(id, OLEParty::Sender, x_k))
+    }
+}
@@ -2,17 +2,19 @@
 
 mod receiver;
 mod sender;
+mod shared;
 
 pub use receiver::OLEReceiver;
 pub use sender::OLESender;
+pub use shared::{SharedROTReceiver, SharedROTSender};
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        rot::{OLEReceiver, OLESender},
-        OLEReceiver as _, OLESender as _,
+        rot::{OLEReceiver, OLESender, SharedROTReceiver, SharedROTSender},
+        ChosenOLEReceiver as _, ChosenOLESender as _, OLEReceiver as _, OLESender as _,
     };
-    use mpz_common::{executor::test_st_executor, Allocate, Preprocess};
+    use mpz_common::{executor::test_st_executor, tag::Tag, Allocate, Preprocess};
     use mpz_core::{prg::Prg, Block};
     use mpz_fields::{p256::P256, UniformRand};
     use mpz_ot::ideal::rot::ideal_rot;
@@ -25,8 +27,8 @@ mod tests {
 
         let (rot_sender, rot_receiver) = ideal_rot();
 
-        let mut ole_sender = OLESender::<_, P256>::new(rot_sender);
-        let mut ole_receiver = OLEReceiver::<_, P256>::new(rot_receiver);
+        let mut ole_sender = OLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = OLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
 
         let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
         let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
@@ -48,6 +50,10 @@ mod tests {
         )
         .unwrap();
 
+        y_k.verify_counterpart(x_k.id(), x_k.party(), x_k.len())
+            .unwrap();
+        let (x_k, y_k) = (x_k.into_inner(), y_k.into_inner());
+
         assert_eq!(x_k.len(), count);
         assert_eq!(y_k.len(), count);
         a_k.iter()
@@ -56,4 +62,143 @@ mod tests {
             .zip(y_k)
             .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
     }
+
+    #[tokio::test]
+    async fn test_chosen_ole() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let mut ole_sender = OLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = OLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let x_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        ole_sender.alloc(count);
+        ole_receiver.alloc(count);
+
+        tokio::try_join!(
+            ole_sender.preprocess(&mut ctx_sender),
+            ole_receiver.preprocess(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let (_, y_k) = tokio::try_join!(
+            ole_sender.send_chosen(&mut ctx_sender, a_k.clone(), x_k.clone()),
+            ole_receiver.receive_chosen(&mut ctx_receiver, b_k.clone())
+        )
+        .unwrap();
+        let y_k = y_k.into_inner();
+
+        assert_eq!(y_k.len(), count);
+        a_k.iter()
+            .zip(b_k)
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
+    }
+
+    #[tokio::test]
+    async fn test_chosen_ole_unequal_outputs() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, _) = ideal_rot();
+        let mut ole_sender = OLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let x_k: Vec<P256> = (0..count - 1).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, _) = test_st_executor(10);
+
+        let err = ole_sender.send_chosen(&mut ctx_sender, a_k, x_k).await;
+
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ole_insufficient() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let mut ole_sender = OLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = OLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        ole_sender.alloc(count);
+        ole_receiver.alloc(count);
+
+        tokio::try_join!(
+            ole_sender.preprocess(&mut ctx_sender),
+            ole_receiver.preprocess(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let a_k: Vec<P256> = (0..count + 1).map(|_| P256::rand(&mut rng)).collect();
+
+        let err = ole_sender.send(&mut ctx_sender, a_k).await.unwrap_err();
+
+        assert!(err.to_string().starts_with("Insufficient OLEs"));
+    }
+
+    #[tokio::test]
+    async fn test_ole_shared_rot() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+        let rot_sender = SharedROTSender::new(rot_sender);
+        let rot_receiver = SharedROTReceiver::new(rot_receiver);
+
+        let mut ole_senders = [
+            OLESender::<_, P256>::new(Tag::new("test/0"), rot_sender.clone()),
+            OLESender::<_, P256>::new(Tag::new("test/1"), rot_sender),
+        ];
+        let mut ole_receivers = [
+            OLEReceiver::<_, P256>::new(Tag::new("test/0"), rot_receiver.clone()),
+            OLEReceiver::<_, P256>::new(Tag::new("test/1"), rot_receiver),
+        ];
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        for (ole_sender, ole_receiver) in ole_senders.iter_mut().zip(ole_receivers.iter_mut()) {
+            let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+            let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+            ole_sender.alloc(count);
+            ole_receiver.alloc(count);
+
+            tokio::try_join!(
+                ole_sender.preprocess(&mut ctx_sender),
+                ole_receiver.preprocess(&mut ctx_receiver)
+            )
+            .unwrap();
+
+            let (x_k, y_k) = tokio::try_join!(
+                ole_sender.send(&mut ctx_sender, a_k.clone()),
+                ole_receiver.receive(&mut ctx_receiver, b_k.clone())
+            )
+            .unwrap();
+
+            y_k.verify_counterpart(x_k.id(), x_k.party(), x_k.len())
+                .unwrap();
+            let (x_k, y_k) = (x_k.into_inner(), y_k.into_inner());
+
+            assert_eq!(x_k.len(), count);
+            assert_eq!(y_k.len(), count);
+            a_k.iter()
+                .zip(b_k)
+                .zip(x_k)
+                .zip(y_k)
+                .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
+        }
+    }
 }
@@ -1,12 +1,15 @@
-use std::mem;
-
-use crate::{OLEError, OLEErrorKind, OLEReceiver as OLEReceive};
+use crate::{ChosenOLEReceiver, OLEError, OLEParty, OLEReceiver as OLEReceive, OLEShare};
 use async_trait::async_trait;
 use itybity::ToBits;
-use mpz_common::{Allocate, Context, Preprocess};
+use mpz_common::{
+    scoped_futures::ScopedFutureExt as _,
+    store::CorrelatedStore,
+    tag::{Side, Tag, TagBinding},
+    Allocate, Context, Preprocess, ThreadId,
+};
 use mpz_fields::Field;
 use mpz_ole_core::{
-    msg::{BatchAdjust, MaskedCorrelations},
+    msg::{BatchAdjust, BatchCorrection, MaskedCorrelations},
     BatchReceiverAdjust, OLEReceiver as OLECoreReceiver,
 };
 use mpz_ot::{OTError, RandomOTReceiver};
@@ -15,35 +18,34 @@ use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
 /// OLE receiver.
 #[derive(Debug)]
 pub struct OLEReceiver<T, F> {
+    tag: TagBinding,
     rot_receiver: T,
-    core: OLECoreReceiver<F>,
-    alloc: usize,
+    store: CorrelatedStore<OLECoreReceiver<F>>,
 }
 
 impl<T, F> OLEReceiver<T, F>
 where
     F: Field + Serialize + Deserialize,
 {
-    /// Creates a new receiver.
-    pub fn new(rot_receiver: T) -> Self {
+    /// Creates a new receiver for the purpose given by `tag`.
+    pub fn new(tag: Tag, rot_receiver: T) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Receiver),
             rot_receiver,
-            core: OLECoreReceiver::default(),
-            alloc: 0,
+            store: CorrelatedStore::default(),
         }
     }
 
     pub(crate) fn adjust(
         &mut self,
+        id: &ThreadId,
         inputs: Vec<F>,
     ) -> Result<(BatchReceiverAdjust<F>, BatchAdjust<F>), OLEError> {
-        let len = inputs.len();
-        self.core.adjust(inputs).ok_or_else(|| {
-            OLEError::new(
-                OLEErrorKind::InsufficientOLEs,
-                format!("{} < {}", self.core.cache_size(), len),
-            )
-        })
+        let adjust = self
+            .store
+            .consume(id, inputs.len(), |core| core.adjust(inputs))?;
+
+        Ok(adjust.expect("store tracks the size of the cache"))
     }
 }
 
@@ -54,7 +56,7 @@ where
 {
     fn alloc(&mut self, count: usize) {
         self.rot_receiver.alloc(count * F::BIT_SIZE);
-        self.alloc += count;
+        self.store.alloc(count);
     }
 }
 
@@ -68,30 +70,36 @@ where
     type Error = OLEError;
 
     async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OLEError> {
-        let count = mem::take(&mut self.alloc);
-        if count == 0 {
-            return Ok(());
-        }
+        self.tag.bind(ctx).await?;
 
-        self.rot_receiver.preprocess(ctx).await?;
+        let rot_receiver = &mut self.rot_receiver;
+        self.store
+            .refill(ctx, |ctx, core, count| {
+                async move {
+                    rot_receiver.preprocess(ctx).await?;
 
-        let random_ot = self
-            .rot_receiver
-            .receive_random(ctx, count * F::BIT_SIZE)
-            .await?;
+                    let random_ot = rot_receiver
+                        .receive_random(ctx, count * F::BIT_SIZE)
+                        .await?;
 
-        let rot_msg: Vec<F> = random_ot.msgs;
+                    let rot_msg: Vec<F> = random_ot.msgs;
 
-        let rot_choices: Vec<F> = random_ot
-            .choices
-            .chunks(F::BIT_SIZE)
-            .map(|choice| F::from_lsb0_iter(choice.iter_lsb0()))
-            .collect();
+                    let rot_choices: Vec<F> = random_ot
+                        .choices
+                        .chunks(F::BIT_SIZE)
+                        .map(|choice| F::from_lsb0_iter(choice.iter_lsb0()))
+                        .collect();
 
-        let channel = ctx.io_mut();
-        let masks = channel.expect_next::<MaskedCorrelations<F>>().await?;
+                    let masks = ctx.io_mut().expect_next::<MaskedCorrelations<F>>().await?;
+
+                    core.preprocess(rot_choices, rot_msg, masks)?;
+
+                    Ok::<_, OLEError>(())
+                }
+                .scope_boxed()
+            })
+            .await?;
 
-        self.core.preprocess(rot_choices, rot_msg, masks)?;
         Ok(())
     }
 }
@@ -101,8 +109,9 @@ impl<T: Send, F, Ctx: Context> OLEReceive<Ctx, F> for OLEReceiver<T, F>
 where
     F: Field + Serialize + Deserialize,
 {
-    async fn receive(&mut self, ctx: &mut Ctx, b_k: Vec<F>) -> Result<Vec<F>, OLEError> {
-        let (receiver_adjust, adjust) = self.adjust(b_k)?;
+    async fn receive(&mut self, ctx: &mut Ctx, b_k: Vec<F>) -> Result<OLEShare<F>, OLEError> {
+        let (receiver_adjust, adjust) = self.adjust(ctx.id(), b_k)?;
+        let id = receiver_adjust.id();
 
         let channel = ctx.io_mut();
         channel.send(adjust).await?;
@@ -111,6 +120,31 @@ where
         let shares = receiver_adjust.finish_adjust(adjust)?;
         let y_k = shares.into_iter().map(|s| s.inner()).collect();
 
-        Ok(y_k)
+        Ok(OLEShare::new(id, OLEParty::Receiver, y_k))
+    }
+}
+
+#[async_trait]
+impl<T: Send, F, Ctx: Context> ChosenOLEReceiver<Ctx, F> for OLEReceiver<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    async fn receive_chosen(
+        &mut self,
+        ctx: &mut Ctx,
+        b_k: Vec<F>,
+    ) -> Result<OLEShare<F>, OLEError> {
+        let (receiver_adjust, adjust) = self.adjust(ctx.id(), b_k)?;
+        let id = receiver_adjust.id();
+
+        let channel = ctx.io_mut();
+        channel.send(adjust).await?;
+        let adjust = channel.expect_next::<BatchAdjust<F>>().await?;
+        let correction = channel.expect_next::<BatchCorrection<F>>().await?;
+
+        let shares = receiver_adjust.finish_chosen(adjust, correction)?;
+        let y_k = shares.into_iter().map(|s| s.inner()).collect();
+
+        Ok(OLEShare::new(id, OLEParty::Receiver, y_k))
     }
 }
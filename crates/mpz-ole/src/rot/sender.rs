@@ -1,10 +1,15 @@
-use std::mem;
-
-use crate::{OLEError, OLEErrorKind, OLESender as OLESend};
+use crate::{ChosenOLESender, OLEError, OLEParty, OLESender as OLESend, OLEShare};
 use async_trait::async_trait;
-use mpz_common::{Allocate, Context, Preprocess};
+use mpz_common::{
+    scoped_futures::ScopedFutureExt as _,
+    store::CorrelatedStore,
+    tag::{Side, Tag, TagBinding},
+    Allocate, Context, Preprocess, ThreadId,
+};
 use mpz_fields::Field;
-use mpz_ole_core::{msg::BatchAdjust, BatchSenderAdjust, OLESender as OLECoreSender};
+use mpz_ole_core::{
+    msg::BatchAdjust, BatchSenderAdjust, OLEError as OLECoreError, OLESender as OLECoreSender,
+};
 use mpz_ot::{OTError, RandomOTSender};
 use rand::thread_rng;
 use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
@@ -12,35 +17,34 @@ use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
 /// OLE sender.
 #[derive(Debug)]
 pub struct OLESender<T, F> {
+    tag: TagBinding,
     rot_sender: T,
-    core: OLECoreSender<F>,
-    alloc: usize,
+    store: CorrelatedStore<OLECoreSender<F>>,
 }
 
 impl<T, F> OLESender<T, F>
 where
     F: Field + Serialize + Deserialize,
 {
-    /// Creates a new sender.
-    pub fn new(rot_sender: T) -> Self {
+    /// Creates a new sender for the purpose given by `tag`.
+    pub fn new(tag: Tag, rot_sender: T) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Sender),
             rot_sender,
-            core: OLECoreSender::default(),
-            alloc: 0,
+            store: CorrelatedStore::default(),
         }
     }
 
     pub(crate) fn adjust(
         &mut self,
+        id: &ThreadId,
         inputs: Vec<F>,
     ) -> Result<(BatchSenderAdjust<F>, BatchAdjust<F>), OLEError> {
-        let len = inputs.len();
-        self.core.adjust(inputs).ok_or_else(|| {
-            OLEError::new(
-                OLEErrorKind::InsufficientOLEs,
-                format!("{} < {}", self.core.cache_size(), len),
-            )
-        })
+        let adjust = self
+            .store
+            .consume(id, inputs.len(), |core| core.adjust(inputs))?;
+
+        Ok(adjust.expect("store tracks the size of the cache"))
     }
 }
 
@@ -51,7 +55,7 @@ where
 {
     fn alloc(&mut self, count: usize) {
         self.rot_sender.alloc(count * F::BIT_SIZE);
-        self.alloc += count;
+        self.store.alloc(count);
     }
 }
 
@@ -65,28 +69,30 @@ where
     type Error = OLEError;
 
     async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OLEError> {
-        let count = mem::take(&mut self.alloc);
-        if count == 0 {
-            return Ok(());
-        }
+        self.tag.bind(ctx).await?;
 
-        self.rot_sender.preprocess(ctx).await?;
+        let rot_sender = &mut self.rot_sender;
+        self.store
+            .refill(ctx, |ctx, core, count| {
+                async move {
+                    rot_sender.preprocess(ctx).await?;
 
-        let random = {
-            let mut rng = thread_rng();
-            (0..count).map(|_| F::rand(&mut rng)).collect()
-        };
+                    let random = {
+                        let mut rng = thread_rng();
+                        (0..count).map(|_| F::rand(&mut rng)).collect()
+                    };
 
-        let random_ot: Vec<[F; 2]> = self
-            .rot_sender
-            .send_random(ctx, count * F::BIT_SIZE)
-            .await?
-            .msgs;
+                    let random_ot: Vec<[F; 2]> =
+                        rot_sender.send_random(ctx, count * F::BIT_SIZE).await?.msgs;
 
-        let channel = ctx.io_mut();
+                    let masks = core.preprocess(random, random_ot)?;
+                    ctx.io_mut().send(masks).await?;
 
-        let masks = self.core.preprocess(random, random_ot)?;
-        channel.send(masks).await?;
+                    Ok::<_, OLEError>(())
+                }
+                .scope_boxed()
+            })
+            .await?;
 
         Ok(())
     }
@@ -97,8 +103,9 @@ impl<T: Send, F, Ctx: Context> OLESend<Ctx, F> for OLESender<T, F>
 where
     F: Field + Serialize + Deserialize,
 {
-    async fn send(&mut self, ctx: &mut Ctx, a_k: Vec<F>) -> Result<Vec<F>, OLEError> {
-        let (sender_adjust, adjust) = self.adjust(a_k)?;
+    async fn send(&mut self, ctx: &mut Ctx, a_k: Vec<F>) -> Result<OLEShare<F>, OLEError> {
+        let (sender_adjust, adjust) = self.adjust(ctx.id(), a_k)?;
+        let id = sender_adjust.id();
 
         let channel = ctx.io_mut();
         channel.send(adjust).await?;
@@ -107,6 +114,34 @@ where
         let shares = sender_adjust.finish_adjust(adjust)?;
         let x_k = shares.into_iter().map(|s| s.inner()).collect();
 
-        Ok(x_k)
+        Ok(OLEShare::new(id, OLEParty::Sender, x_k))
+    }
+}
+
+#[async_trait]
+impl<T: Send, F, Ctx: Context> ChosenOLESender<Ctx, F> for OLESender<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    async fn send_chosen(
+        &mut self,
+        ctx: &mut Ctx,
+        a_k: Vec<F>,
+        x_k: Vec<F>,
+    ) -> Result<(), OLEError> {
+        if a_k.len() != x_k.len() {
+            return Err(OLECoreError::UnequalOutputs(x_k.len(), a_k.len()).into());
+        }
+
+        let (sender_adjust, adjust) = self.adjust(ctx.id(), a_k)?;
+
+        let channel = ctx.io_mut();
+        channel.send(adjust).await?;
+        let adjust = channel.expect_next::<BatchAdjust<F>>().await?;
+
+        let correction = sender_adjust.finish_chosen(adjust, x_k)?;
+        channel.send(correction).await?;
+
+        Ok(())
     }
 }
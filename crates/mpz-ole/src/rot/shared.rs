@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mpz_common::{sync::AsyncMutex, Allocate, Context, Preprocess, Ready};
+use mpz_ot::{OTError, ROTReceiverOutput, ROTSenderOutput, RandomOTReceiver, RandomOTSender};
+
+/// A shared ROT sender.
+///
+/// Wraps a ROT sender so that a single instance can back multiple [`OLESender`](super::OLESender)s,
+/// possibly across threads, without repeating the ROT setup for each of them.
+///
+/// Access to the ROT sender is synchronized with the [`SharedROTReceiver`] of the other party.
+#[derive(Debug)]
+pub struct SharedROTSender<T> {
+    inner: Arc<AsyncMutex<T>>,
+}
+
+impl<T> Clone for SharedROTSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> SharedROTSender<T> {
+    /// Creates a new shared sender.
+    pub fn new(sender: T) -> Self {
+        Self {
+            // The sender always follows the order of the receiver.
+            inner: Arc::new(AsyncMutex::new_follower(sender)),
+        }
+    }
+}
+
+impl<T: Ready> Ready for SharedROTSender<T> {
+    fn is_ready(&self) -> bool {
+        self.inner.blocking_lock_unsync().is_ready()
+    }
+}
+
+impl<T: Allocate> Allocate for SharedROTSender<T> {
+    fn alloc(&mut self, count: usize) {
+        self.inner.blocking_lock_unsync().alloc(count);
+    }
+}
+
+#[async_trait]
+impl<Ctx, T> Preprocess<Ctx> for SharedROTSender<T>
+where
+    Ctx: Context,
+    T: Preprocess<Ctx, Error = OTError> + Send,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        self.inner.lock(ctx).await?.preprocess(ctx).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, U> RandomOTSender<Ctx, U> for SharedROTSender<T>
+where
+    Ctx: Context,
+    T: RandomOTSender<Ctx, U> + Send,
+    U: Send + 'static,
+{
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<ROTSenderOutput<U>, OTError> {
+        self.inner.lock(ctx).await?.send_random(ctx, count).await
+    }
+}
+
+/// A shared ROT receiver.
+///
+/// Wraps a ROT receiver so that a single instance can back multiple
+/// [`OLEReceiver`](super::OLEReceiver)s, possibly across threads, without repeating the ROT setup
+/// for each of them.
+///
+/// Access to the ROT receiver is synchronized with the [`SharedROTSender`] of the other party.
+#[derive(Debug)]
+pub struct SharedROTReceiver<T> {
+    inner: Arc<AsyncMutex<T>>,
+}
+
+impl<T> Clone for SharedROTReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> SharedROTReceiver<T> {
+    /// Creates a new shared receiver.
+    pub fn new(receiver: T) -> Self {
+        Self {
+            // The receiver always leads the order.
+            inner: Arc::new(AsyncMutex::new_leader(receiver)),
+        }
+    }
+}
+
+impl<T: Ready> Ready for SharedROTReceiver<T> {
+    fn is_ready(&self) -> bool {
+        self.inner.blocking_lock_unsync().is_ready()
+    }
+}
+
+impl<T: Allocate> Allocate for SharedROTReceiver<T> {
+    fn alloc(&mut self, count: usize) {
+        self.inner.blocking_lock_unsync().alloc(count);
+    }
+}
+
+#[async_trait]
+impl<Ctx, T> Preprocess<Ctx> for SharedROTReceiver<T>
+where
+    Ctx: Context,
+    T: Preprocess<Ctx, Error = OTError> + Send,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        self.inner.lock(ctx).await?.preprocess(ctx).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, U, V> RandomOTReceiver<Ctx, U, V> for SharedROTReceiver<T>
+where
+    Ctx: Context,
+    T: RandomOTReceiver<Ctx, U, V> + Send,
+    U: Send + 'static,
+    V: Send + 'static,
+{
+    async fn receive_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<ROTReceiverOutput<U, V>, OTError> {
+        self.inner.lock(ctx).await?.receive_random(ctx, count).await
+    }
+}
@@ -0,0 +1,500 @@
+//! Streamed OLE for large batches.
+//!
+//! [`StreamOLESender`](crate::StreamOLESender) and [`StreamOLEReceiver`](crate::StreamOLEReceiver)
+//! take their inputs as iterators and return their outputs chunk by chunk, so that batches which
+//! do not fit in memory can be processed with memory bounded by the [`StreamConfig`].
+//!
+//! The inputs are processed in windows of at most [`StreamConfig::in_flight`] chunks of
+//! [`StreamConfig::chunk_size`] elements each. For every window the parties:
+//!
+//! 1. Exchange the number of elements in the window, and stop once both have no inputs left.
+//! 2. Preprocess the OLEs for the window.
+//! 3. Send the adjustments for all the chunks of the window.
+//! 4. Receive the adjustments of the other party, and output each chunk in order.
+
+use async_trait::async_trait;
+use mpz_common::{Allocate, Context, Preprocess, ThreadId};
+use mpz_fields::Field;
+use mpz_ole_core::{
+    msg::BatchAdjust, BatchReceiverAdjust, BatchSenderAdjust, OLEError as OLECoreError,
+};
+use mpz_ot::{OTError, RandomOTReceiver, RandomOTSender};
+use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
+
+use crate::{
+    malicious::{MaliciousOLEReceiver, MaliciousOLESender},
+    rot::{OLEReceiver, OLESender},
+    OLEError, OLEParty, OLEShare, StreamOLEReceiver, StreamOLESender,
+};
+
+/// Default number of elements in a chunk.
+const DEFAULT_CHUNK_SIZE: usize = 1024;
+/// Default number of chunks in flight.
+const DEFAULT_IN_FLIGHT: usize = 4;
+
+/// Configuration of a streamed OLE.
+///
+/// Both parties must use the same configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamConfig {
+    chunk_size: usize,
+    in_flight: usize,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            in_flight: DEFAULT_IN_FLIGHT,
+        }
+    }
+}
+
+impl StreamConfig {
+    /// Creates a new configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk_size` - The number of elements in a chunk.
+    /// * `in_flight` - The maximum number of chunks which are preprocessed and adjusted at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` or `in_flight` is zero.
+    pub fn new(chunk_size: usize, in_flight: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        assert!(in_flight > 0, "number of chunks in flight must be non-zero");
+
+        Self {
+            chunk_size,
+            in_flight,
+        }
+    }
+
+    /// Returns the number of elements in a chunk.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the maximum number of chunks which are preprocessed and adjusted at once.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// Returns the maximum number of elements held in memory at once.
+    pub fn window_size(&self) -> usize {
+        self.chunk_size * self.in_flight
+    }
+
+    /// Takes the next window of chunks from the inputs.
+    fn next_window<F>(&self, inputs: &mut impl Iterator<Item = F>) -> Vec<Vec<F>> {
+        (0..self.in_flight)
+            .map_while(|_| {
+                let chunk: Vec<F> = inputs.by_ref().take(self.chunk_size).collect();
+                (!chunk.is_empty()).then_some(chunk)
+            })
+            .collect()
+    }
+}
+
+/// A sender which can consume preprocessed OLEs chunk by chunk.
+pub(crate) trait AdjustSender<F> {
+    fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchSenderAdjust<F>, BatchAdjust<F>), OLEError>;
+}
+
+/// A receiver which can consume preprocessed OLEs chunk by chunk.
+pub(crate) trait AdjustReceiver<F> {
+    fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchReceiverAdjust<F>, BatchAdjust<F>), OLEError>;
+}
+
+impl<T, F> AdjustSender<F> for OLESender<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchSenderAdjust<F>, BatchAdjust<F>), OLEError> {
+        OLESender::adjust(self, id, inputs)
+    }
+}
+
+impl<T, F> AdjustSender<F> for MaliciousOLESender<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchSenderAdjust<F>, BatchAdjust<F>), OLEError> {
+        MaliciousOLESender::adjust(self, id, inputs)
+    }
+}
+
+impl<T, F> AdjustReceiver<F> for OLEReceiver<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchReceiverAdjust<F>, BatchAdjust<F>), OLEError> {
+        OLEReceiver::adjust(self, id, inputs)
+    }
+}
+
+impl<T, F> AdjustReceiver<F> for MaliciousOLEReceiver<T, F>
+where
+    F: Field + Serialize + Deserialize,
+{
+    fn adjust(
+        &mut self,
+        id: &ThreadId,
+        inputs: Vec<F>,
+    ) -> Result<(BatchReceiverAdjust<F>, BatchAdjust<F>), OLEError> {
+        MaliciousOLEReceiver::adjust(self, id, inputs)
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, F> StreamOLESender<Ctx, F> for OLESender<T, F>
+where
+    Ctx: Context,
+    T: Allocate + Preprocess<Ctx, Error = OTError> + RandomOTSender<Ctx, [F; 2]> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn send_stream<I, O>(
+        &mut self,
+        ctx: &mut Ctx,
+        config: &StreamConfig,
+        inputs: I,
+        output: O,
+    ) -> Result<usize, OLEError>
+    where
+        I: IntoIterator<Item = F> + Send,
+        I::IntoIter: Send,
+        O: FnMut(OLEShare<F>) -> Result<(), OLEError> + Send,
+    {
+        send_stream(self, ctx, config, inputs, output).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, F> StreamOLESender<Ctx, F> for MaliciousOLESender<T, F>
+where
+    Ctx: Context,
+    T: Allocate + Preprocess<Ctx, Error = OTError> + RandomOTSender<Ctx, [F; 2]> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn send_stream<I, O>(
+        &mut self,
+        ctx: &mut Ctx,
+        config: &StreamConfig,
+        inputs: I,
+        output: O,
+    ) -> Result<usize, OLEError>
+    where
+        I: IntoIterator<Item = F> + Send,
+        I::IntoIter: Send,
+        O: FnMut(OLEShare<F>) -> Result<(), OLEError> + Send,
+    {
+        send_stream(self, ctx, config, inputs, output).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, F> StreamOLEReceiver<Ctx, F> for OLEReceiver<T, F>
+where
+    Ctx: Context,
+    T: Allocate + Preprocess<Ctx, Error = OTError> + RandomOTReceiver<Ctx, bool, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn receive_stream<I, O>(
+        &mut self,
+        ctx: &mut Ctx,
+        config: &StreamConfig,
+        inputs: I,
+        output: O,
+    ) -> Result<usize, OLEError>
+    where
+        I: IntoIterator<Item = F> + Send,
+        I::IntoIter: Send,
+        O: FnMut(OLEShare<F>) -> Result<(), OLEError> + Send,
+    {
+        receive_stream(self, ctx, config, inputs, output).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, F> StreamOLEReceiver<Ctx, F> for MaliciousOLEReceiver<T, F>
+where
+    Ctx: Context,
+    T: Allocate + Preprocess<Ctx, Error = OTError> + RandomOTReceiver<Ctx, bool, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn receive_stream<I, O>(
+        &mut self,
+        ctx: &mut Ctx,
+        config: &StreamConfig,
+        inputs: I,
+        output: O,
+    ) -> Result<usize, OLEError>
+    where
+        I: IntoIterator<Item = F> + Send,
+        I::IntoIter: Send,
+        O: FnMut(OLEShare<F>) -> Result<(), OLEError> + Send,
+    {
+        receive_stream(self, ctx, config, inputs, output).await
+    }
+}
+
+/// Exchanges the number of elements in the current window with the other party.
+async fn sync_window<Ctx: Context>(ctx: &mut Ctx, count: usize) -> Result<(), OLEError> {
+    let channel = ctx.io_mut();
+    channel.send(count as u64).await?;
+    let expected = channel.expect_next::<u64>().await? as usize;
+
+    if count != expected {
+        return Err(OLECoreError::UnequalShares(count, expected).into());
+    }
+
+    Ok(())
+}
+
+/// Streams the sender's inputs through the OLE.
+async fn send_stream<Ctx, F, S, I, O>(
+    sender: &mut S,
+    ctx: &mut Ctx,
+    config: &StreamConfig,
+    inputs: I,
+    mut output: O,
+) -> Result<usize, OLEError>
+where
+    Ctx: Context,
+    F: Field + Serialize + Deserialize,
+    S: Allocate + Preprocess<Ctx, Error = OLEError> + AdjustSender<F> + Send,
+    I: IntoIterator<Item = F>,
+    O: FnMut(OLEShare<F>) -> Result<(), OLEError>,
+{
+    let mut inputs = inputs.into_iter();
+    let mut total = 0;
+
+    loop {
+        let window = config.next_window(&mut inputs);
+        let count = window.iter().map(Vec::len).sum();
+
+        sync_window(ctx, count).await?;
+        if count == 0 {
+            return Ok(total);
+        }
+
+        sender.alloc(count);
+        sender.preprocess(ctx).await?;
+
+        let mut pending = Vec::with_capacity(window.len());
+        for chunk in window {
+            let (sender_adjust, adjust) = sender.adjust(ctx.id(), chunk)?;
+            ctx.io_mut().send(adjust).await?;
+            pending.push(sender_adjust);
+        }
+
+        for sender_adjust in pending {
+            let id = sender_adjust.id();
+            let adjust = ctx.io_mut().expect_next::<BatchAdjust<F>>().await?;
+
+            let shares = sender_adjust.finish_adjust(adjust)?;
+            let x_k = shares.into_iter().map(|s| s.inner()).collect();
+
+            output(OLEShare::new(id, OLEParty::Sender, x_k))?;
+        }
+
+        total += count;
+    }
+}
+
+/// Streams the receiver's inputs through the OLE.
+async fn receive_stream<Ctx, F, R, I, O>(
+    receiver: &mut R,
+    ctx: &mut Ctx,
+    config: &StreamConfig,
+    inputs: I,
+    mut output: O,
+) -> Result<usize, OLEError>
+where
+    Ctx: Context,
+    F: Field + Serialize + Deserialize,
+    R: Allocate + Preprocess<Ctx, Error = OLEError> + AdjustReceiver<F> + Send,
+    I: IntoIterator<Item = F>,
+    O: FnMut(OLEShare<F>) -> Result<(), OLEError>,
+{
+    let mut inputs = inputs.into_iter();
+    let mut total = 0;
+
+    loop {
+        let window = config.next_window(&mut inputs);
+        let count = window.iter().map(Vec::len).sum();
+
+        sync_window(ctx, count).await?;
+        if count == 0 {
+            return Ok(total);
+        }
+
+        receiver.alloc(count);
+        receiver.preprocess(ctx).await?;
+
+        let mut pending = Vec::with_capacity(window.len());
+        for chunk in window {
+            let (receiver_adjust, adjust) = receiver.adjust(ctx.id(), chunk)?;
+            ctx.io_mut().send(adjust).await?;
+            pending.push(receiver_adjust);
+        }
+
+        for receiver_adjust in pending {
+            let id = receiver_adjust.id();
+            let adjust = ctx.io_mut().expect_next::<BatchAdjust<F>>().await?;
+
+            let shares = receiver_adjust.finish_adjust(adjust)?;
+            let y_k = shares.into_iter().map(|s| s.inner()).collect();
+
+            output(OLEShare::new(id, OLEParty::Receiver, y_k))?;
+        }
+
+        total += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::{executor::test_st_executor, tag::Tag};
+    use mpz_core::{prg::Prg, Block};
+    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_ot::ideal::rot::ideal_rot;
+    use rand::SeedableRng;
+
+    #[tokio::test]
+    async fn test_ole_stream() {
+        // Not a multiple of the window size, so the last window is partial.
+        let count = 100;
+        let config = StreamConfig::new(8, 3);
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let mut ole_sender = OLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = OLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let mut x_k = Vec::new();
+        let mut y_k = Vec::new();
+        let (sent, received) = tokio::try_join!(
+            ole_sender.send_stream(&mut ctx_sender, &config, a_k.clone(), |share| {
+                assert!(share.len() <= config.chunk_size());
+                x_k.push(share);
+                Ok(())
+            }),
+            ole_receiver.receive_stream(&mut ctx_receiver, &config, b_k.clone(), |share| {
+                y_k.push(share);
+                Ok(())
+            })
+        )
+        .unwrap();
+
+        assert_eq!(sent, count);
+        assert_eq!(received, count);
+        assert_eq!(x_k.len(), count.div_ceil(config.chunk_size()));
+
+        let x_k: Vec<P256> = x_k
+            .into_iter()
+            .zip(&y_k)
+            .flat_map(|(x, y)| {
+                y.verify_counterpart(x.id(), x.party(), x.len()).unwrap();
+                x.into_inner()
+            })
+            .collect();
+        let y_k: Vec<P256> = y_k.into_iter().flat_map(|y| y.into_inner()).collect();
+
+        a_k.iter()
+            .zip(b_k)
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
+    }
+
+    #[tokio::test]
+    async fn test_malicious_ole_stream() {
+        let count = 20;
+        let config = StreamConfig::new(4, 2);
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let mut ole_sender = MaliciousOLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = MaliciousOLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let mut x_k = Vec::new();
+        let mut y_k = Vec::new();
+        tokio::try_join!(
+            ole_sender.send_stream(&mut ctx_sender, &config, a_k.clone(), |share| {
+                x_k.extend(share.into_inner());
+                Ok(())
+            }),
+            ole_receiver.receive_stream(&mut ctx_receiver, &config, b_k.clone(), |share| {
+                y_k.extend(share.into_inner());
+                Ok(())
+            })
+        )
+        .unwrap();
+
+        a_k.iter()
+            .zip(b_k)
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|(((&a, b), x), y)| assert_eq!(y, a * b + x));
+    }
+
+    #[tokio::test]
+    async fn test_ole_stream_unequal_length() {
+        let config = StreamConfig::new(4, 2);
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let mut ole_sender = OLESender::<_, P256>::new(Tag::new("test"), rot_sender);
+        let mut ole_receiver = OLEReceiver::<_, P256>::new(Tag::new("test"), rot_receiver);
+
+        let a_k: Vec<P256> = (0..8).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..7).map(|_| P256::rand(&mut rng)).collect();
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let (sent, received) = tokio::join!(
+            ole_sender.send_stream(&mut ctx_sender, &config, a_k, |_| Ok(())),
+            ole_receiver.receive_stream(&mut ctx_receiver, &config, b_k, |_| Ok(()))
+        );
+
+        assert!(sent.is_err());
+        assert!(received.is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Errors that can occur when using the KKRT protocol.
+
+/// Errors that can occur when using the KKRT sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("invalid number of base OTs: expected {expected}, got {actual}")]
+    InvalidBaseOTCount { expected: usize, actual: usize },
+    #[error("invalid number of OPRF instances: {0}")]
+    InvalidCount(usize),
+    #[error("invalid length of the OPRF columns: expected {expected}, got {actual}")]
+    InvalidColumns { expected: usize, actual: usize },
+    #[error("invalid number of programmed instances: expected {expected}, got {actual}")]
+    InvalidPointCount { expected: usize, actual: usize },
+    #[error("too many points to program: bound {bound}, got {actual}")]
+    TooManyPoints { bound: usize, actual: usize },
+    #[error("OPRF instance {0} is programmed on duplicate points")]
+    DuplicatePoint(usize),
+}
+
+/// Errors that can occur when using the KKRT receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("invalid number of base OTs: expected {expected}, got {actual}")]
+    InvalidBaseOTCount { expected: usize, actual: usize },
+    #[error("invalid number of hints: expected {expected}, got {actual}")]
+    InvalidHintCount { expected: usize, actual: usize },
+}
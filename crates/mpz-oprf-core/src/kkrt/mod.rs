@@ -0,0 +1,251 @@
+//! An implementation of the batched, related-key OPRF of [KKRT](https://eprint.iacr.org/2016/799),
+//! secure against semi-honest adversaries.
+//!
+//! Each extension creates a batch of independent OPRF instances. The receiver evaluates the `j`-th
+//! instance on its `j`-th input, while the sender obtains a key with which it can evaluate the
+//! `j`-th instance on any number of inputs.
+//!
+//! The OPRF is an OT extension where the choice bits of each row are replaced by the encoding of
+//! the receiver's input under a pseudorandom code of width [`CODE_WIDTH`]. It requires
+//! [`BASE_OT_COUNT`] base OTs in which the receiver acts as the OT sender.
+//!
+//! The instances are numbered across extensions, so the outputs of different extensions are
+//! independent.
+//!
+//! The instances can also be programmed to output chosen values on a set of points, see the
+//! [`program`] module.
+
+pub mod error;
+pub mod msgs;
+pub mod program;
+mod receiver;
+mod sender;
+
+use mpz_core::{aes::AesEncryptor, prg::Prg, Block};
+use rand::SeedableRng;
+
+pub use error::{ReceiverError, SenderError};
+pub use program::{Hint, ProgrammedKeys};
+pub use receiver::{state as receiver_state, Receiver, ReceiverOutput};
+pub use sender::{state as sender_state, Sender, SenderKeys};
+
+/// Width of the pseudorandom code in bits.
+pub const CODE_WIDTH: usize = 512;
+/// Number of base OTs required for the setup.
+pub const BASE_OT_COUNT: usize = CODE_WIDTH;
+
+/// Number of blocks in a codeword.
+pub(crate) const CODE_BLOCKS: usize = CODE_WIDTH / 128;
+/// Number of bytes in a codeword.
+pub(crate) const CODE_BYTES: usize = CODE_WIDTH / 8;
+
+/// A codeword of the pseudorandom code.
+pub(crate) type Codeword = [Block; CODE_BLOCKS];
+
+/// Pseudorandom code, derived from a seed chosen by the receiver.
+pub(crate) struct Code([AesEncryptor; CODE_BLOCKS]);
+
+impl Code {
+    pub(crate) fn new(seed: Block) -> Self {
+        let mut prg = Prg::from_seed(seed);
+        Self(std::array::from_fn(|_| {
+            AesEncryptor::new(prg.random_block())
+        }))
+    }
+
+    /// Encodes an input.
+    pub(crate) fn encode(&self, input: Block) -> Codeword {
+        std::array::from_fn(|i| self.0[i].encrypt_block(input))
+    }
+}
+
+/// Pads the number of OPRF instances to a multiple of [`CODE_WIDTH`], so that the OPRF matrix can
+/// be transposed in square chunks.
+///
+/// Returns `None` if the padded count overflows.
+pub fn pad_count(count: usize) -> Option<usize> {
+    count.checked_next_multiple_of(CODE_WIDTH)
+}
+
+/// Hashes a row of the OPRF matrix into the output of the OPRF instance with the given index.
+pub(crate) fn output(index: usize, row: &Codeword) -> Block {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&(index as u64).to_le_bytes());
+    for block in row {
+        hasher.update(&block.to_bytes());
+    }
+
+    let mut output = [0u8; 16];
+    output.copy_from_slice(&hasher.finalize().as_bytes()[..16]);
+    Block::new(output)
+}
+
+/// Returns the row of a matrix with rows of [`CODE_BYTES`] bytes as a codeword.
+pub(crate) fn row(matrix: &[u8], pos: usize) -> Codeword {
+    let row = &matrix[pos * CODE_BYTES..(pos + 1) * CODE_BYTES];
+    std::array::from_fn(|i| {
+        row[i * 16..(i + 1) * 16]
+            .try_into()
+            .expect("block is 16 bytes")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rstest::*;
+
+    fn setup(
+        rng: &mut impl Rng,
+    ) -> (
+        Sender<sender_state::Extension>,
+        Receiver<receiver_state::Extension>,
+    ) {
+        let keys: Vec<[Block; 2]> = (0..BASE_OT_COUNT).map(|_| rng.gen()).collect();
+        let choices: Vec<bool> = (0..BASE_OT_COUNT).map(|_| rng.gen()).collect();
+        let chosen = keys
+            .iter()
+            .zip(&choices)
+            .map(|(keys, choice)| keys[*choice as usize])
+            .collect();
+
+        (
+            Sender::new().setup(choices, chosen).unwrap(),
+            Receiver::new().setup(keys).unwrap(),
+        )
+    }
+
+    fn random_blocks(rng: &mut impl Rng, count: usize) -> Vec<Block> {
+        (0..count).map(|_| rng.gen()).collect()
+    }
+
+    #[rstest]
+    #[case::empty(&[0])]
+    #[case::single(&[1])]
+    #[case::exact(&[CODE_WIDTH])]
+    #[case::multiple(&[10, 1000, 0, 513])]
+    fn test_kkrt(#[case] counts: &[usize]) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut sender, mut receiver) = setup(&mut rng);
+
+        let mut outputs = Vec::new();
+        for &count in counts {
+            let inputs = random_blocks(&mut rng, count);
+
+            let (
+                ReceiverOutput {
+                    offset,
+                    outputs: receiver_outputs,
+                },
+                extend,
+            ) = receiver.receive(&inputs, &mut rng);
+            let keys = sender.send(extend).unwrap();
+
+            assert_eq!(keys.offset(), offset);
+            assert_eq!(keys.len(), count);
+
+            for (j, (input, output)) in inputs.iter().zip(&receiver_outputs).enumerate() {
+                assert_eq!(keys.eval(j, *input), *output);
+                // Evaluating on any other input gives an unrelated output.
+                assert_ne!(keys.eval(j, *input ^ Block::ONE), *output);
+            }
+
+            outputs.extend(receiver_outputs);
+        }
+
+        // Every instance is independent, so the outputs are distinct, even for equal inputs.
+        let (_, extend) = receiver.receive(&[Block::ZERO; 2], &mut rng);
+        let keys = sender.send(extend).unwrap();
+        assert_ne!(keys.eval(0, Block::ZERO), keys.eval(1, Block::ZERO));
+
+        let mut distinct: Vec<_> = outputs.iter().map(|output| output.to_bytes()).collect();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), outputs.len());
+    }
+
+    #[test]
+    fn test_kkrt_wrong_base_ots() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (_, mut receiver) = setup(&mut rng);
+        let (mut sender, _) = setup(&mut rng);
+
+        let inputs = random_blocks(&mut rng, 10);
+
+        let (ReceiverOutput { outputs, .. }, extend) = receiver.receive(&inputs, &mut rng);
+        let keys = sender.send(extend).unwrap();
+
+        assert!(inputs
+            .iter()
+            .zip(outputs)
+            .enumerate()
+            .all(|(j, (input, output))| keys.eval(j, *input) != output));
+    }
+
+    #[test]
+    fn test_kkrt_invalid_extend() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut sender, mut receiver) = setup(&mut rng);
+
+        let (_, mut extend) = receiver.receive(&random_blocks(&mut rng, 10), &mut rng);
+        extend.columns.pop();
+
+        assert!(matches!(
+            sender.send(extend),
+            Err(SenderError::InvalidColumns { .. })
+        ));
+
+        let (_, mut extend) = receiver.receive(&random_blocks(&mut rng, 10), &mut rng);
+        extend.count = usize::MAX;
+
+        assert!(matches!(
+            sender.send(extend),
+            Err(SenderError::InvalidCount(_))
+        ));
+    }
+
+    #[test]
+    fn test_kkrt_programmed() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut sender, mut receiver) = setup(&mut rng);
+
+        let inputs = random_blocks(&mut rng, 10);
+        let (output, extend) = receiver.receive(&inputs, &mut rng);
+        let keys = sender.send(extend).unwrap();
+
+        // Even instances are programmed on the receiver's input, odd instances on other points.
+        let points: Vec<Vec<(Block, Block)>> = inputs
+            .iter()
+            .enumerate()
+            .map(|(j, input)| {
+                let x = if j % 2 == 0 { *input } else { rng.gen() };
+                vec![(x, Block::new([j as u8; 16])), (rng.gen(), rng.gen())]
+            })
+            .collect();
+
+        let keys = keys.program(4, &points, &mut rng).unwrap();
+
+        let outputs = output.program(&inputs, keys.hints()).unwrap();
+
+        for (j, (input, output)) in inputs.iter().zip(&outputs).enumerate() {
+            assert_eq!(keys.eval(j, *input), *output);
+            for (x, y) in &points[j] {
+                assert_eq!(keys.eval(j, *x), *y);
+            }
+            assert_eq!(*output == Block::new([j as u8; 16]), j % 2 == 0);
+        }
+
+        let (_, extend) = receiver.receive(&inputs, &mut rng);
+        let keys = sender.send(extend).unwrap();
+        assert!(matches!(
+            keys.program(1, &points, &mut rng),
+            Err(SenderError::TooManyPoints {
+                bound: 1,
+                actual: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,24 @@
+//! Messages for the KKRT protocol.
+
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+use crate::kkrt::Hint;
+
+/// Extension message sent by the receiver.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Extend {
+    /// Seed of the pseudorandom code.
+    pub seed: Block,
+    /// The number of OPRF instances.
+    pub count: usize,
+    /// The columns of the OPRF matrix, masked with the base OT keys.
+    pub columns: Vec<u8>,
+}
+
+/// Hints which program the OPRF instances of an extension, sent by the sender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Program {
+    /// The hint of each OPRF instance.
+    pub hints: Vec<Hint>,
+}
@@ -0,0 +1,244 @@
+//! Programmable OPRF, using the polynomial-based construction of
+//! [KMPRT](https://eprint.iacr.org/2017/799).
+//!
+//! The sender programs an OPRF instance to output chosen values on a set of points by sending a
+//! [`Hint`], the polynomial `P` over GF(2^128) with `P(x) = y ⊕ F(x)` on each point `(x, y)`.
+//! The programmed output on any input `x` is `F(x) ⊕ P(x)`.
+//!
+//! The polynomial is interpolated through random padding points up to a public bound, so the hint
+//! reveals nothing about the number of points, and the programmed output on any input which is
+//! not a point remains pseudorandom.
+
+use mpz_core::Block;
+use mpz_fields::{gf2_128::Gf2_128, Field};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::kkrt::{
+    error::{ReceiverError, SenderError},
+    ReceiverOutput, SenderKeys,
+};
+
+/// A hint which programs the outputs of an OPRF instance.
+///
+/// The hint is the polynomial which corrects the outputs of the instance on the programmed points.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hint {
+    /// The coefficients of the polynomial, lowest degree first.
+    coeffs: Vec<Block>,
+}
+
+impl Hint {
+    /// Returns the programmed output of an OPRF instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input.
+    /// * `output` - The output of the OPRF instance on the input.
+    pub fn correct(&self, input: Block, output: Block) -> Block {
+        let x = Gf2_128::from(input);
+        let p = self
+            .coeffs
+            .iter()
+            .rev()
+            .fold(<Gf2_128 as Field>::zero(), |acc, coeff| {
+                acc * x + Gf2_128::from(*coeff)
+            });
+
+        output ^ Block::from(p)
+    }
+}
+
+/// The sender's keys of a batch of programmed OPRF instances.
+#[derive(Debug)]
+pub struct ProgrammedKeys {
+    keys: SenderKeys,
+    hints: Vec<Hint>,
+}
+
+impl ProgrammedKeys {
+    /// Returns the index of the first OPRF instance of the batch.
+    pub fn offset(&self) -> usize {
+        self.keys.offset()
+    }
+
+    /// Returns the number of OPRF instances in the batch.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the batch has no OPRF instances.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the hints of the OPRF instances, which are sent to the receiver.
+    pub fn hints(&self) -> &[Hint] {
+        &self.hints
+    }
+
+    /// Evaluates the programmed OPRF instance with the given index in the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the OPRF instance in the batch.
+    /// * `input` - The input.
+    pub fn eval(&self, index: usize, input: Block) -> Block {
+        self.hints[index].correct(input, self.keys.eval(index, input))
+    }
+}
+
+impl SenderKeys {
+    /// Programs the OPRF instances of the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `bound` - The maximum number of points of an instance, which is revealed to the receiver.
+    /// * `points` - The points `(x, y)` of each instance, on which it outputs `y`.
+    /// * `rng` - The random number generator used to sample the padding points.
+    pub fn program<R: Rng + ?Sized>(
+        self,
+        bound: usize,
+        points: &[Vec<(Block, Block)>],
+        rng: &mut R,
+    ) -> Result<ProgrammedKeys, SenderError> {
+        if points.len() != self.len() {
+            return Err(SenderError::InvalidPointCount {
+                expected: self.len(),
+                actual: points.len(),
+            });
+        }
+
+        let hints = points
+            .iter()
+            .enumerate()
+            .map(|(index, points)| {
+                if points.len() > bound {
+                    return Err(SenderError::TooManyPoints {
+                        bound,
+                        actual: points.len(),
+                    });
+                }
+
+                let (xs, ys): (Vec<_>, Vec<_>) = points
+                    .iter()
+                    .map(|(x, y)| (*x, *y ^ self.eval(index, *x)))
+                    .chain((points.len()..bound).map(|_| (rng.gen(), rng.gen())))
+                    .map(|(x, y)| (Gf2_128::from(x), Gf2_128::from(y)))
+                    .unzip();
+
+                let coeffs = interpolate(&xs, &ys).ok_or(SenderError::DuplicatePoint(index))?;
+
+                Ok(Hint {
+                    coeffs: coeffs.into_iter().map(Block::from).collect(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ProgrammedKeys { keys: self, hints })
+    }
+}
+
+impl ReceiverOutput {
+    /// Applies the sender's hints to the outputs, returning the programmed outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs of the OPRF instances.
+    /// * `hints` - The hints of the OPRF instances.
+    pub fn program(self, inputs: &[Block], hints: &[Hint]) -> Result<Vec<Block>, ReceiverError> {
+        if hints.len() != self.outputs.len() || inputs.len() != self.outputs.len() {
+            return Err(ReceiverError::InvalidHintCount {
+                expected: self.outputs.len(),
+                actual: hints.len(),
+            });
+        }
+
+        Ok(self
+            .outputs
+            .into_iter()
+            .zip(inputs)
+            .zip(hints)
+            .map(|((output, input), hint)| hint.correct(*input, output))
+            .collect())
+    }
+}
+
+/// Returns the coefficients of the polynomial of degree less than `xs.len()` which maps each `x`
+/// to the corresponding `y`, lowest degree first.
+///
+/// Returns `None` if the `xs` are not distinct.
+fn interpolate(xs: &[Gf2_128], ys: &[Gf2_128]) -> Option<Vec<Gf2_128>> {
+    let zero = <Gf2_128 as Field>::zero();
+    let n = xs.len();
+
+    // M(X) = ∏ (X - xⱼ). Subtraction is addition in a field of characteristic 2.
+    let mut m = vec![zero; n + 1];
+    m[0] = <Gf2_128 as Field>::one();
+    for (deg, x) in xs.iter().enumerate() {
+        for k in (1..=deg + 1).rev() {
+            m[k] = m[k - 1] + *x * m[k];
+        }
+        m[0] = *x * m[0];
+    }
+
+    // P(X) = Σ yᵢ Mᵢ(X) / Mᵢ(xᵢ), where Mᵢ(X) = M(X) / (X - xᵢ).
+    let mut coeffs = vec![zero; n];
+    let mut q = vec![zero; n];
+    for (x, y) in xs.iter().zip(ys) {
+        // Synthetic division of M by (X - xᵢ).
+        q[n - 1] = m[n];
+        for k in (1..n).rev() {
+            q[k - 1] = m[k] + *x * q[k];
+        }
+
+        let denominator = q.iter().rev().fold(zero, |acc, coeff| acc * *x + *coeff);
+        if denominator == zero {
+            return None;
+        }
+
+        let scale = *y * denominator.inverse();
+        coeffs
+            .iter_mut()
+            .zip(&q)
+            .for_each(|(coeff, q)| *coeff = *coeff + scale * *q);
+    }
+
+    Some(coeffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    #[test]
+    fn test_interpolate() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let xs: Vec<Gf2_128> = (0..10).map(|_| rng.gen()).collect();
+        let ys: Vec<Gf2_128> = (0..10).map(|_| rng.gen()).collect();
+
+        let hint = Hint {
+            coeffs: interpolate(&xs, &ys)
+                .unwrap()
+                .into_iter()
+                .map(Block::from)
+                .collect(),
+        };
+
+        for (x, y) in xs.iter().zip(&ys) {
+            assert_eq!(hint.correct((*x).into(), Block::ZERO), Block::from(*y));
+        }
+
+        let mut duplicate = xs.clone();
+        duplicate[1] = duplicate[0];
+        assert!(interpolate(&duplicate, &ys).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+use mpz_core::{prg::Prg, Block};
+use rand::{Rng, SeedableRng};
+
+use crate::kkrt::{
+    error::ReceiverError, msgs::Extend, output, pad_count, row, Code, BASE_OT_COUNT, CODE_BYTES,
+    CODE_WIDTH,
+};
+
+/// The receiver's outputs of an extension.
+#[derive(Debug)]
+pub struct ReceiverOutput {
+    /// The index of the first OPRF instance of the extension.
+    pub offset: usize,
+    /// The outputs of the OPRF instances, one for each input.
+    pub outputs: Vec<Block>,
+}
+
+/// KKRT receiver.
+#[derive(Debug, Default)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Receiver {
+    /// Creates a new receiver.
+    pub fn new() -> Self {
+        Receiver {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Completes the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys sent in the base OTs, see [`BASE_OT_COUNT`].
+    pub fn setup(self, keys: Vec<[Block; 2]>) -> Result<Receiver<state::Extension>, ReceiverError> {
+        if keys.len() != BASE_OT_COUNT {
+            return Err(ReceiverError::InvalidBaseOTCount {
+                expected: BASE_OT_COUNT,
+                actual: keys.len(),
+            });
+        }
+
+        let rngs = keys
+            .into_iter()
+            .map(|[k0, k1]| [Prg::from_seed(k0), Prg::from_seed(k1)])
+            .collect();
+
+        Ok(Receiver {
+            state: state::Extension { rngs, counter: 0 },
+        })
+    }
+}
+
+impl Receiver<state::Extension> {
+    /// Evaluates a batch of new OPRF instances, one on each input.
+    ///
+    /// Returns the outputs and the message for the sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs of the OPRF instances.
+    /// * `rng` - The random number generator used to sample the code and the padding.
+    pub fn receive<R: Rng>(&mut self, inputs: &[Block], rng: &mut R) -> (ReceiverOutput, Extend) {
+        let count = inputs.len();
+        let rows = pad_count(count).expect("padded count of a slice does not overflow");
+        let column_len = rows / 8;
+
+        let seed: Block = rng.gen();
+        let code = Code::new(seed);
+
+        // Encode the inputs. The padding gets random codewords.
+        let mut ds = vec![0u8; rows * CODE_BYTES];
+        for (pos, d) in ds.chunks_exact_mut(CODE_BYTES).enumerate() {
+            match inputs.get(pos) {
+                Some(input) => {
+                    for (d, block) in d.chunks_exact_mut(16).zip(code.encode(*input)) {
+                        d.copy_from_slice(&block.to_bytes());
+                    }
+                }
+                None => rng.fill(d),
+            }
+        }
+
+        // Transpose the codewords into columns. The matrix is transposed in square chunks, as the
+        // number of rows is not a power of two.
+        let chunk_len = CODE_WIDTH * CODE_BYTES;
+        ds.chunks_exact_mut(chunk_len).for_each(|chunk| {
+            matrix_transpose::transpose_bits(chunk, CODE_WIDTH).expect("matrix is square")
+        });
+
+        // Figure 2 of KKRT: t₀ⁱ = G(k₀ⁱ), uⁱ = t₀ⁱ ⊕ dⁱ ⊕ G(k₁ⁱ).
+        let mut ts = vec![0u8; BASE_OT_COUNT * column_len];
+        let mut columns = vec![0u8; BASE_OT_COUNT * column_len];
+        if count > 0 {
+            for (i, ((t, u), [rng0, rng1])) in ts
+                .chunks_exact_mut(column_len)
+                .zip(columns.chunks_exact_mut(column_len))
+                .zip(self.state.rngs.iter_mut())
+                .enumerate()
+            {
+                rng0.random_bytes(t);
+                rng1.random_bytes(u);
+
+                let d = ds
+                    .chunks_exact(chunk_len)
+                    .flat_map(|chunk| &chunk[i * CODE_BYTES..(i + 1) * CODE_BYTES]);
+                u.iter_mut()
+                    .zip(t.iter())
+                    .zip(d)
+                    .for_each(|((u, t), d)| *u ^= t ^ d);
+            }
+
+            matrix_transpose::transpose_bits(&mut ts, BASE_OT_COUNT)
+                .expect("matrix is rectangular");
+        }
+
+        let offset = self.state.counter;
+        self.state.counter += count;
+
+        let outputs = (0..count)
+            .map(|j| output(offset + j, &row(&ts, j)))
+            .collect();
+
+        (
+            ReceiverOutput { offset, outputs },
+            Extend {
+                seed,
+                count,
+                columns,
+            },
+        )
+    }
+}
+
+/// The receiver's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after the setup phase.
+    ///
+    /// In this state the receiver evaluates batches of OPRF instances.
+    pub struct Extension {
+        /// Receiver's rngs seeded from the keys sent in the base OTs.
+        pub(super) rngs: Vec<[Prg; 2]>,
+        /// Current OPRF instance counter.
+        pub(super) counter: usize,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
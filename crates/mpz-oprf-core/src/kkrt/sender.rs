@@ -0,0 +1,220 @@
+use mpz_core::{prg::Prg, Block};
+use rand::SeedableRng;
+
+use crate::kkrt::{
+    error::SenderError, msgs::Extend, output, pad_count, row, Code, Codeword, BASE_OT_COUNT,
+    CODE_BYTES,
+};
+
+/// KKRT sender.
+#[derive(Debug, Default)]
+pub struct Sender<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Sender {
+    /// Creates a new sender.
+    pub fn new() -> Self {
+        Sender {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Completes the setup phase of the protocol.
+    ///
+    /// The sender acts as the receiver of the base OTs. Its choice bits are the secret key of the
+    /// OPRF.
+    ///
+    /// # Arguments
+    ///
+    /// * `choices` - The random choice bits of the base OTs, see [`BASE_OT_COUNT`].
+    /// * `keys` - The keys received in the base OTs.
+    pub fn setup(
+        self,
+        choices: Vec<bool>,
+        keys: Vec<Block>,
+    ) -> Result<Sender<state::Extension>, SenderError> {
+        if choices.len() != BASE_OT_COUNT || keys.len() != BASE_OT_COUNT {
+            return Err(SenderError::InvalidBaseOTCount {
+                expected: BASE_OT_COUNT,
+                actual: choices.len().min(keys.len()),
+            });
+        }
+
+        let mut bytes = [0u8; CODE_BYTES];
+        for (i, choice) in choices.iter().enumerate() {
+            bytes[i / 8] |= (*choice as u8) << (i % 8);
+        }
+
+        Ok(Sender {
+            state: state::Extension {
+                choices,
+                s: row(&bytes, 0),
+                rngs: keys.into_iter().map(Prg::from_seed).collect(),
+                counter: 0,
+            },
+        })
+    }
+}
+
+impl Sender<state::Extension> {
+    /// Creates the keys of a batch of new OPRF instances from the receiver's message.
+    ///
+    /// # Arguments
+    ///
+    /// * `extend` - The receiver's extension message.
+    pub fn send(&mut self, extend: Extend) -> Result<SenderKeys, SenderError> {
+        let Extend {
+            seed,
+            count,
+            columns,
+        } = extend;
+
+        let rows = pad_count(count).ok_or(SenderError::InvalidCount(count))?;
+        let column_len = rows / 8;
+
+        let expected = BASE_OT_COUNT * column_len;
+        if columns.len() != expected {
+            return Err(SenderError::InvalidColumns {
+                expected,
+                actual: columns.len(),
+            });
+        }
+
+        // Expand the base OT keys into the columns of Q, where the i-th column is the i-th
+        // column of T₀, XORed with the i-th column of the receiver's codewords if the i-th choice
+        // bit is set.
+        let mut qs = columns;
+        if count > 0 {
+            for ((q, rng), choice) in qs
+                .chunks_exact_mut(column_len)
+                .zip(self.state.rngs.iter_mut())
+                .zip(&self.state.choices)
+            {
+                let mut t = vec![0u8; column_len];
+                rng.random_bytes(&mut t);
+
+                if *choice {
+                    q.iter_mut().zip(t).for_each(|(q, t)| *q ^= t);
+                } else {
+                    q.copy_from_slice(&t);
+                }
+            }
+
+            matrix_transpose::transpose_bits(&mut qs, BASE_OT_COUNT)
+                .expect("matrix is rectangular");
+        }
+
+        let offset = self.state.counter;
+        self.state.counter += count;
+
+        Ok(SenderKeys {
+            offset,
+            count,
+            code: Code::new(seed),
+            s: self.state.s,
+            qs,
+        })
+    }
+}
+
+/// The sender's keys of a batch of OPRF instances.
+pub struct SenderKeys {
+    /// The index of the first OPRF instance.
+    offset: usize,
+    /// The number of OPRF instances.
+    count: usize,
+    /// The pseudorandom code of the batch.
+    code: Code,
+    /// The choice bits of the base OTs packed into a codeword.
+    s: Codeword,
+    /// The rows of Q.
+    qs: Vec<u8>,
+}
+
+opaque_debug::implement!(SenderKeys);
+
+impl SenderKeys {
+    /// Returns the index of the first OPRF instance of the batch.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of OPRF instances in the batch.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the batch has no OPRF instances.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Evaluates the OPRF instance with the given index in the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the OPRF instance in the batch.
+    /// * `input` - The input.
+    pub fn eval(&self, index: usize, input: Block) -> Block {
+        assert!(
+            index < self.count,
+            "index {index} out of bounds for {} OPRF instances",
+            self.count
+        );
+
+        // Figure 2 of KKRT: F(x) = H(j, qⱼ ⊕ (C(x) ∧ s)).
+        let mut q = row(&self.qs, index);
+        q.iter_mut()
+            .zip(self.code.encode(input))
+            .zip(self.s)
+            .for_each(|((q, code), s)| *q ^= code & s);
+
+        output(self.offset + index, &q)
+    }
+}
+
+/// The sender's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The sender's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The sender's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The sender's state after the setup phase.
+    ///
+    /// In this state the sender creates the keys of batches of OPRF instances.
+    pub struct Extension {
+        /// The choice bits of the base OTs.
+        pub(super) choices: Vec<bool>,
+        /// The choice bits packed into a codeword.
+        pub(super) s: Codeword,
+        /// Sender's rngs seeded from the keys received in the base OTs.
+        pub(super) rngs: Vec<Prg>,
+        /// Current OPRF instance counter.
+        pub(super) counter: usize,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
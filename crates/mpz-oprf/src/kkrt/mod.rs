@@ -0,0 +1,145 @@
+//! The batched OPRF of [KKRT](https://eprint.iacr.org/2016/799), secure against semi-honest
+//! adversaries.
+//!
+//! The setup consumes [`BASE_OT_COUNT`](mpz_oprf_core::kkrt::BASE_OT_COUNT) chosen-message OTs,
+//! in which the OPRF receiver is the OT sender. Afterwards, any number of batches can be
+//! evaluated, and each batch can be programmed, see [`mpz_oprf_core::kkrt::program`].
+//!
+//! # Example
+//!
+//! ```
+//! use mpz_common::executor::test_st_executor;
+//! use mpz_core::Block;
+//! use mpz_oprf::{
+//!     kkrt::{Receiver, Sender},
+//!     OPRFKeys, OPRFReceiver, OPRFSender,
+//! };
+//! use mpz_ot::ideal::ot::ideal_ot;
+//! # futures::executor::block_on(async {
+//!
+//! let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+//! let (ot_sender, ot_receiver) = ideal_ot();
+//!
+//! let mut sender = Sender::new(ot_receiver);
+//! let mut receiver = Receiver::new(ot_sender);
+//!
+//! futures::try_join!(sender.setup(&mut ctx_sender), receiver.setup(&mut ctx_receiver)).unwrap();
+//!
+//! let inputs = vec![Block::new([1; 16]), Block::new([2; 16])];
+//!
+//! let (keys, outputs) = futures::try_join!(
+//!     sender.send(&mut ctx_sender, inputs.len()),
+//!     receiver.receive(&mut ctx_receiver, &inputs),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(keys.eval(1, inputs[1]), outputs[1]);
+//! # });
+//! ```
+
+mod receiver;
+mod sender;
+
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::executor::test_st_executor;
+    use mpz_core::{prg::Prg, Block};
+    use mpz_ot::ideal::ot::ideal_ot;
+    use rand::{Rng, SeedableRng};
+
+    use crate::{
+        OPRFError, OPRFReceiver, OPRFSender, ProgrammableOPRFReceiver, ProgrammableOPRFSender,
+    };
+
+    #[tokio::test]
+    async fn test_kkrt() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (ot_sender, ot_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(ot_receiver);
+        let mut receiver = Receiver::new(ot_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        // Evaluate multiple batches with the same base OTs.
+        for count in [100, 0, 1000] {
+            let inputs: Vec<Block> = (0..count).map(|_| rng.gen()).collect();
+
+            let (keys, outputs) = tokio::try_join!(
+                sender.send(&mut ctx_sender, count),
+                receiver.receive(&mut ctx_receiver, &inputs),
+            )
+            .unwrap();
+
+            assert_eq!(keys.len(), count);
+            assert!(inputs
+                .iter()
+                .zip(outputs)
+                .enumerate()
+                .all(|(j, (input, output))| keys.eval(j, *input) == output));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_kkrt_not_setup() {
+        let (mut ctx_sender, _) = test_st_executor(8);
+        let (_, ot_receiver) = ideal_ot::<[Block; 2], Block>();
+
+        let mut sender = Sender::new(ot_receiver);
+
+        assert!(matches!(
+            sender.send(&mut ctx_sender, 1).await,
+            Err(OPRFError::StateError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kkrt_programmed() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (ot_sender, ot_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(ot_receiver);
+        let mut receiver = Receiver::new(ot_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let inputs: Vec<Block> = (0..100).map(|_| rng.gen()).collect();
+
+        // Half of the instances are programmed on the receiver's input.
+        let points: Vec<Vec<(Block, Block)>> = inputs
+            .iter()
+            .enumerate()
+            .map(|(j, input)| {
+                let x = if j % 2 == 0 { *input } else { rng.gen() };
+                vec![(x, Block::ONES)]
+            })
+            .collect();
+
+        let (keys, outputs) = tokio::try_join!(
+            sender.send_programmed(&mut ctx_sender, 3, &points),
+            receiver.receive_programmed(&mut ctx_receiver, &inputs),
+        )
+        .unwrap();
+
+        for (j, (input, output)) in inputs.iter().zip(outputs).enumerate() {
+            assert_eq!(keys.eval(j, *input), output);
+            assert_eq!(output == Block::ONES, j % 2 == 0);
+        }
+    }
+}
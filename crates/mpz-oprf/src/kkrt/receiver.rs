@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_oprf_core::kkrt::{
+    msgs::Program, receiver_state as state, Receiver as ReceiverCore, ReceiverOutput, BASE_OT_COUNT,
+};
+use mpz_ot::OTSender;
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{OPRFError, OPRFReceiver, ProgrammableOPRFReceiver};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(ReceiverCore<state::Extension>),
+    Error,
+}
+
+impl From<StateError> for OPRFError {
+    fn from(err: StateError) -> Self {
+        OPRFError::StateError(err.to_string())
+    }
+}
+
+/// KKRT receiver.
+#[derive(Debug)]
+pub struct Receiver<BaseOT> {
+    state: State,
+    base: BaseOT,
+}
+
+impl<BaseOT> Receiver<BaseOT> {
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base OT sender.
+    pub fn new(base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(ReceiverCore::new()),
+            base,
+        }
+    }
+
+    /// Performs the base OTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    pub async fn setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), OPRFError>
+    where
+        BaseOT: OTSender<Ctx, [Block; 2]>,
+    {
+        let receiver = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        let keys: Vec<[Block; 2]> = {
+            let mut rng = thread_rng();
+            (0..BASE_OT_COUNT).map(|_| rng.gen()).collect()
+        };
+
+        self.base.send(ctx, &keys).await?;
+
+        self.state = State::Extension(receiver.setup(keys)?);
+
+        Ok(())
+    }
+}
+
+impl<BaseOT> Receiver<BaseOT> {
+    /// Evaluates a batch of new OPRF instances and sends the extension message to the sender.
+    async fn extend<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: &[Block],
+    ) -> Result<ReceiverOutput, OPRFError> {
+        let mut receiver = std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let inputs = inputs.to_vec();
+        let (receiver, output, extend) = Backend::spawn(move || {
+            let (output, extend) = receiver.receive(&inputs, &mut thread_rng());
+            (receiver, output, extend)
+        })
+        .await;
+
+        self.state = State::Extension(receiver);
+
+        ctx.io_mut().send(extend).await?;
+
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> OPRFReceiver<Ctx> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn receive(&mut self, ctx: &mut Ctx, inputs: &[Block]) -> Result<Vec<Block>, OPRFError> {
+        let ReceiverOutput { outputs, .. } = self.extend(ctx, inputs).await?;
+
+        Ok(outputs)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> ProgrammableOPRFReceiver<Ctx> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn receive_programmed(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: &[Block],
+    ) -> Result<Vec<Block>, OPRFError> {
+        let output = self.extend(ctx, inputs).await?;
+
+        let Program { hints } = ctx.io_mut().expect_next().await?;
+
+        Ok(output.program(inputs, &hints)?)
+    }
+}
@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_oprf_core::kkrt::{
+    msgs::{Extend, Program},
+    sender_state as state, ProgrammedKeys, Sender as SenderCore, SenderKeys, BASE_OT_COUNT,
+};
+use mpz_ot::OTReceiver;
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{OPRFError, OPRFKeys, OPRFSender, ProgrammableOPRFSender};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(SenderCore<state::Extension>),
+    Error,
+}
+
+impl From<StateError> for OPRFError {
+    fn from(err: StateError) -> Self {
+        OPRFError::StateError(err.to_string())
+    }
+}
+
+/// KKRT sender.
+#[derive(Debug)]
+pub struct Sender<BaseOT> {
+    state: State,
+    base: BaseOT,
+}
+
+impl<BaseOT> Sender<BaseOT> {
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base OT receiver.
+    pub fn new(base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(SenderCore::new()),
+            base,
+        }
+    }
+
+    /// Performs the base OTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    pub async fn setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), OPRFError>
+    where
+        BaseOT: OTReceiver<Ctx, bool, Block>,
+    {
+        let sender = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        let choices: Vec<bool> = {
+            let mut rng = thread_rng();
+            (0..BASE_OT_COUNT).map(|_| rng.gen()).collect()
+        };
+
+        let keys = self.base.receive(ctx, &choices).await?.msgs;
+
+        self.state = State::Extension(sender.setup(choices, keys)?);
+
+        Ok(())
+    }
+}
+
+impl OPRFKeys for SenderKeys {
+    fn len(&self) -> usize {
+        SenderKeys::len(self)
+    }
+
+    fn eval(&self, index: usize, input: Block) -> Block {
+        SenderKeys::eval(self, index, input)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> OPRFSender<Ctx> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    type Keys = SenderKeys;
+
+    async fn send(&mut self, ctx: &mut Ctx, count: usize) -> Result<SenderKeys, OPRFError> {
+        let mut sender = std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let extend: Extend = ctx.io_mut().expect_next().await?;
+        if extend.count != count {
+            return Err(OPRFError::CountMismatch {
+                expected: count,
+                actual: extend.count,
+            });
+        }
+
+        let (sender, keys) = Backend::spawn(move || {
+            let keys = sender.send(extend);
+            (sender, keys)
+        })
+        .await;
+
+        self.state = State::Extension(sender);
+
+        Ok(keys?)
+    }
+}
+
+impl OPRFKeys for ProgrammedKeys {
+    fn len(&self) -> usize {
+        ProgrammedKeys::len(self)
+    }
+
+    fn eval(&self, index: usize, input: Block) -> Block {
+        ProgrammedKeys::eval(self, index, input)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> ProgrammableOPRFSender<Ctx> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    type Keys = ProgrammedKeys;
+
+    async fn send_programmed(
+        &mut self,
+        ctx: &mut Ctx,
+        bound: usize,
+        points: &[Vec<(Block, Block)>],
+    ) -> Result<ProgrammedKeys, OPRFError> {
+        let keys = OPRFSender::send(self, ctx, points.len()).await?;
+
+        let points = points.to_vec();
+        let keys = Backend::spawn(move || keys.program(bound, &points, &mut thread_rng())).await?;
+
+        ctx.io_mut()
+            .send(Program {
+                hints: keys.hints().to_vec(),
+            })
+            .await?;
+
+        Ok(keys)
+    }
+}
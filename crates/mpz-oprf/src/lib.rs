@@ -0,0 +1,131 @@
+//! Implementations of oblivious pseudorandom function (OPRF) protocols.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+pub mod kkrt;
+
+use async_trait::async_trait;
+use mpz_core::Block;
+
+/// An OPRF error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum OPRFError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("context error: {0}")]
+    Context(#[from] mpz_common::ContextError),
+    #[error("OT error: {0}")]
+    OTError(#[from] mpz_ot::OTError),
+    #[error("sender error: {0}")]
+    SenderError(#[from] mpz_oprf_core::kkrt::SenderError),
+    #[error("receiver error: {0}")]
+    ReceiverError(#[from] mpz_oprf_core::kkrt::ReceiverError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("sender and receiver count mismatch: expected {expected}, got {actual}")]
+    CountMismatch { expected: usize, actual: usize },
+}
+
+/// The keys of a batch of OPRF instances, held by the sender.
+pub trait OPRFKeys {
+    /// Returns the number of OPRF instances.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no OPRF instances.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evaluates the OPRF instance with the given index on an input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    fn eval(&self, index: usize, input: Block) -> Block;
+}
+
+/// An OPRF sender.
+///
+/// The sender obtains the keys of a batch of OPRF instances, which it can evaluate on any number
+/// of inputs, and learns nothing about the receiver's inputs.
+#[async_trait]
+pub trait OPRFSender<Ctx> {
+    /// The keys of a batch of OPRF instances.
+    type Keys: OPRFKeys;
+
+    /// Creates the keys of a batch of OPRF instances.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of OPRF instances, which must match the number of the receiver's
+    ///   inputs.
+    async fn send(&mut self, ctx: &mut Ctx, count: usize) -> Result<Self::Keys, OPRFError>;
+}
+
+/// An OPRF receiver.
+///
+/// The receiver evaluates a batch of OPRF instances, one on each of its inputs, and learns
+/// nothing else about the sender's keys.
+#[async_trait]
+pub trait OPRFReceiver<Ctx> {
+    /// Evaluates a batch of OPRF instances, returning one output for each input.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `inputs` - The inputs.
+    async fn receive(&mut self, ctx: &mut Ctx, inputs: &[Block]) -> Result<Vec<Block>, OPRFError>;
+}
+
+/// A programmable OPRF sender.
+///
+/// The sender programs each OPRF instance of a batch to output chosen values on a set of points.
+/// The receiver learns nothing about the points, except for the output on its own input.
+#[async_trait]
+pub trait ProgrammableOPRFSender<Ctx> {
+    /// The keys of a batch of programmed OPRF instances.
+    type Keys: OPRFKeys;
+
+    /// Creates and programs the keys of a batch of OPRF instances.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `bound` - The maximum number of points of an instance, which is revealed to the
+    ///   receiver.
+    /// * `points` - The points `(x, y)` of each instance, on which it outputs `y`. There must be
+    ///   one entry for each of the receiver's inputs.
+    async fn send_programmed(
+        &mut self,
+        ctx: &mut Ctx,
+        bound: usize,
+        points: &[Vec<(Block, Block)>],
+    ) -> Result<Self::Keys, OPRFError>;
+}
+
+/// A programmable OPRF receiver.
+///
+/// The receiver evaluates a batch of programmed OPRF instances, one on each of its inputs.
+#[async_trait]
+pub trait ProgrammableOPRFReceiver<Ctx> {
+    /// Evaluates a batch of programmed OPRF instances, returning one output for each input.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `inputs` - The inputs.
+    async fn receive_programmed(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: &[Block],
+    ) -> Result<Vec<Block>, OPRFError>;
+}
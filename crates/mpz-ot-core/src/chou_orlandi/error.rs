@@ -10,6 +10,10 @@ pub enum SenderError {
     IdMismatch(TransferId, TransferId),
     #[error("count mismatch: sender expected {0} but receiver sent {1}")]
     CountMismatch(usize, usize),
+    #[error("insufficient precomputed OTs: needed {0}, available {1}")]
+    InsufficientPool(usize, usize),
+    #[error("precomputation is not supported with a committed receiver")]
+    CommittedPrecompute,
     #[error(transparent)]
     VerifyError(#[from] SenderVerifyError),
 }
@@ -24,6 +28,10 @@ pub enum ReceiverError {
     IdMismatch(TransferId, TransferId),
     #[error("count mismatch: receiver expected {0} but sender sent {1}")]
     CountMismatch(usize, usize),
+    #[error("insufficient precomputed OTs: needed {0}, available {1}")]
+    InsufficientPool(usize, usize),
+    #[error("precomputation is not supported with a committed receiver")]
+    CommittedPrecompute,
 }
 
 /// Errors that can occur during verification of the receiver's choices.
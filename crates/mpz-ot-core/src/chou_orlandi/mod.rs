@@ -20,10 +20,12 @@ use mpz_core::Block;
 
 /// Hashes a ristretto point to a symmetric key
 ///
-/// Prepending a tweak is suggested in Section 2, "Non-Malleability in Practice"
-pub(crate) fn hash_point(point: &RistrettoPoint, tweak: u128) -> Block {
-    // Compute H(tweak || point)
+/// Prepending a tweak is suggested in Section 2, "Non-Malleability in Practice". The domain
+/// binds the key to the protocol instance.
+pub(crate) fn hash_point(domain: &[u8; 32], point: &RistrettoPoint, tweak: u128) -> Block {
+    // Compute H(domain || tweak || point)
     let mut h = Hasher::new();
+    h.update(domain);
     h.update(&tweak.to_be_bytes());
     h.update(point.compress().as_bytes());
     let digest = h.finalize();
@@ -95,6 +97,27 @@ mod tests {
         assert_eq!(received_data, expected);
     }
 
+    #[rstest]
+    fn test_ot_domain_mismatch(choices: Vec<bool>, data: Vec<[Block; 2]>, expected: Vec<Block>) {
+        let sender =
+            Sender::new_with_seed(SenderConfig::default(), SENDER_SEED).with_domain([0; 32]);
+        let receiver =
+            Receiver::new_with_seed(ReceiverConfig::default(), RECEIVER_SEED).with_domain([1; 32]);
+
+        let (sender_setup, mut sender) = sender.setup();
+        let mut receiver = receiver.setup(sender_setup);
+
+        let receiver_payload = receiver.receive_random(&choices);
+        let sender_payload = sender.send(&data, receiver_payload).unwrap();
+
+        let received_data = receiver.receive(sender_payload).unwrap();
+
+        assert!(received_data
+            .iter()
+            .zip(&expected)
+            .all(|(received, expected)| received != expected));
+    }
+
     #[rstest]
     fn test_multiple_ot_pass(choices: Vec<bool>, data: Vec<[Block; 2]>, expected: Vec<Block>) {
         let (mut sender, mut receiver) = setup(SenderConfig::default(), ReceiverConfig::default());
@@ -114,6 +137,40 @@ mod tests {
         assert_eq!(received_data, expected);
     }
 
+    #[rstest]
+    fn test_precomputed_ot_pass(choices: Vec<bool>, data: Vec<[Block; 2]>, expected: Vec<Block>) {
+        let (mut sender, mut receiver) = setup(SenderConfig::default(), ReceiverConfig::default());
+
+        let receiver_payload = receiver.precompute(2 * choices.len()).unwrap();
+        sender.precompute(receiver_payload).unwrap();
+
+        assert_eq!(sender.pool_len(), 2 * choices.len());
+        assert_eq!(receiver.pool_len(), 2 * choices.len());
+
+        for _ in 0..2 {
+            let derandomize = receiver.derandomize(&choices).unwrap();
+            let sender_payload = sender.send_derandomized(&data, derandomize).unwrap();
+
+            let received_data = receiver.receive(sender_payload).unwrap();
+
+            assert_eq!(received_data, expected);
+        }
+
+        assert_eq!(sender.pool_len(), 0);
+        assert!(matches!(
+            receiver.derandomize(&choices),
+            Err(ReceiverError::InsufficientPool(128, 0))
+        ));
+
+        // The on-demand path still works once the pool is exhausted.
+        let receiver_payload = receiver.receive_random(&choices);
+        let sender_payload = sender.send(&data, receiver_payload).unwrap();
+
+        let received_data = receiver.receive(sender_payload).unwrap();
+
+        assert_eq!(received_data, expected);
+    }
+
     #[rstest]
     fn test_committed_ot_receiver_pass(
         choices: Vec<bool>,
@@ -4,7 +4,10 @@ use curve25519_dalek::RistrettoPoint;
 use mpz_core::Block;
 use serde::{Deserialize, Serialize};
 
-use crate::TransferId;
+use crate::{
+    msgs::{deserialize_bytes, deserialize_ots},
+    TransferId,
+};
 
 /// Sender setup message.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -19,6 +22,7 @@ pub struct SenderPayload {
     /// The transfer ID.
     pub id: TransferId,
     /// The sender's ciphertexts
+    #[serde(deserialize_with = "deserialize_ots")]
     pub payload: Vec<[Block; 2]>,
 }
 
@@ -28,6 +32,7 @@ pub struct ReceiverPayload {
     /// The transfer ID.
     pub id: TransferId,
     /// The receiver's blinded choices.
+    #[serde(deserialize_with = "deserialize_ots")]
     pub blinded_choices: Vec<RistrettoPoint>,
 }
 
@@ -35,5 +40,6 @@ pub struct ReceiverPayload {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReceiverReveal {
     /// The receiver's choices.
+    #[serde(deserialize_with = "deserialize_bytes")]
     pub choices: Vec<u8>,
 }
@@ -3,7 +3,7 @@ use crate::chou_orlandi::{
     msgs::{ReceiverPayload, ReceiverReveal, SenderPayload, SenderSetup},
     ReceiverConfig, ReceiverError,
 };
-use crate::TransferId;
+use crate::{msgs::Derandomize, TransferId};
 
 use itybity::{BitIterable, FromBitIterator, ToBits};
 use mpz_core::Block;
@@ -13,6 +13,7 @@ use curve25519_dalek::{
     ristretto::{RistrettoBasepointTable, RistrettoPoint},
     scalar::Scalar,
 };
+use rand::Rng;
 use rand_chacha::ChaCha20Rng;
 use rand_core::SeedableRng;
 
@@ -26,6 +27,8 @@ pub struct Receiver<T = state::Initialized> {
     config: ReceiverConfig,
     /// The current state of the protocol
     state: T,
+    /// Domain of the protocol instance, hashed into the keys
+    domain: [u8; 32],
 }
 
 impl Receiver {
@@ -47,6 +50,7 @@ impl Receiver {
         Self {
             config,
             state: state::Initialized::default(),
+            domain: [0u8; 32],
         }
     }
 
@@ -69,9 +73,18 @@ impl Receiver {
             state: state::Initialized {
                 rng: ChaCha20Rng::from_seed(seed),
             },
+            domain: [0u8; 32],
         }
     }
 
+    /// Sets the domain of the protocol instance, which is hashed into the keys.
+    ///
+    /// The sender must use the same domain.
+    pub fn with_domain(mut self, domain: [u8; 32]) -> Self {
+        self.domain = domain;
+        self
+    }
+
     /// Returns the receiver's configuration.
     pub fn config(&self) -> &ReceiverConfig {
         &self.config
@@ -94,7 +107,9 @@ impl Receiver {
                 counter: 0,
                 choice_log: Vec::default(),
                 decryption_keys: Vec::default(),
+                pool: Vec::default(),
             },
+            domain: self.domain,
         }
     }
 }
@@ -120,8 +135,13 @@ impl Receiver<state::Setup> {
             .map(|_| Scalar::random(rng))
             .collect::<Vec<_>>();
 
-        let (blinded_choices, decryption_keys) =
-            compute_decryption_keys(sender_base_table, &private_keys, choices, *counter);
+        let (blinded_choices, decryption_keys) = compute_decryption_keys(
+            &self.domain,
+            sender_base_table,
+            &private_keys,
+            choices,
+            *counter,
+        );
 
         *counter += blinded_choices.len();
         cached_decryption_keys.extend(decryption_keys);
@@ -182,6 +202,99 @@ impl Receiver<state::Setup> {
             .collect::<Vec<Block>>())
     }
 
+    /// Returns the number of precomputed OTs which have not been used yet.
+    pub fn pool_len(&self) -> usize {
+        self.state.pool.len()
+    }
+
+    /// Precomputes `count` random OTs with random choices, adding their keys to the pool.
+    ///
+    /// Returns the payload to be sent to the Sender, which passes it to
+    /// [`Sender::precompute`](crate::chou_orlandi::Sender::precompute).
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of OTs to precompute.
+    pub fn precompute(&mut self, count: usize) -> Result<ReceiverPayload, ReceiverError> {
+        // The random choices are not the choices the pooled OTs are used with, so they can not
+        // be revealed.
+        if self.config.receiver_commit() {
+            return Err(ReceiverError::CommittedPrecompute);
+        }
+
+        let state::Setup {
+            rng,
+            sender_base_table,
+            transfer_id,
+            counter,
+            pool,
+            ..
+        } = &mut self.state;
+
+        let choices = (0..count).map(|_| rng.gen()).collect::<Vec<bool>>();
+        let private_keys = choices
+            .iter()
+            .map(|_| Scalar::random(rng))
+            .collect::<Vec<_>>();
+
+        let (blinded_choices, keys) = compute_decryption_keys(
+            &self.domain,
+            sender_base_table,
+            &private_keys,
+            &choices,
+            *counter,
+        );
+
+        *counter += blinded_choices.len();
+        pool.extend(keys);
+
+        // The sender does not reply to a precomputation, so the transfer is complete.
+        Ok(ReceiverPayload {
+            id: transfer_id.next(),
+            blinded_choices,
+        })
+    }
+
+    /// Assigns precomputed OTs to the provided choices.
+    ///
+    /// Returns the correction bits to be sent to the Sender, which passes them to
+    /// [`Sender::send_derandomized`](crate::chou_orlandi::Sender::send_derandomized). The
+    /// Sender's payload is then decrypted with [`Receiver::receive`].
+    ///
+    /// # Arguments
+    ///
+    /// * `choices` - The receiver's choices
+    pub fn derandomize<T: BitIterable>(
+        &mut self,
+        choices: &[T],
+    ) -> Result<Derandomize, ReceiverError> {
+        let state::Setup {
+            transfer_id,
+            pool,
+            decryption_keys,
+            ..
+        } = &mut self.state;
+
+        let count = choices.iter_lsb0().count();
+        if count > pool.len() {
+            return Err(ReceiverError::InsufficientPool(count, pool.len()));
+        }
+
+        let mut flip = Vec::with_capacity(count);
+        for ((random_choice, key), choice) in pool.drain(..count).zip(choices.iter_lsb0()) {
+            flip.push(random_choice ^ choice);
+            // The sender swaps the keys if the choice is flipped, so the key decrypts the
+            // chosen message.
+            decryption_keys.push((choice, key));
+        }
+
+        Ok(Derandomize {
+            id: *transfer_id,
+            count: count as u32,
+            flip: Vec::<u8>::from_lsb0_iter(flip),
+        })
+    }
+
     /// Reveals the receiver's choices to the Sender
     pub fn reveal_choices(self) -> Result<ReceiverReveal, ReceiverError> {
         let state::Setup { choice_log, .. } = self.state;
@@ -196,12 +309,14 @@ impl Receiver<state::Setup> {
 ///
 /// # Arguments
 ///
+/// * `domain` - The domain of the protocol instance
 /// * `base_table` - A Ristretto basepoint table from the sender's public key
 /// * `receiver_private_keys` - The private keys of the OT receiver
 /// * `choices` - The choices of the OT receiver
 /// * `offset` - The number of decryption keys that have already been computed
 ///              (used for the key derivation tweak)
 fn compute_decryption_keys<T: BitIterable + Sync>(
+    domain: &[u8; 32],
     base_table: &RistrettoBasepointTable,
     receiver_private_keys: &[Scalar],
     choices: &[T],
@@ -235,7 +350,7 @@ fn compute_decryption_keys<T: BitIterable + Sync>(
             zero + b * RISTRETTO_BASEPOINT_TABLE
         };
 
-        let decryption_key = hash_point(&(b * base_table), (offset + i) as u128);
+        let decryption_key = hash_point(domain, &(b * base_table), (offset + i) as u128);
 
         (blinded_choice, (c, decryption_key))
     })
@@ -289,6 +404,8 @@ pub mod state {
 
         /// The decryption key for each OT, with the corresponding choice bit
         pub(super) decryption_keys: Vec<(bool, Block)>,
+        /// Precomputed random OTs, with their random choice bit
+        pub(super) pool: Vec<(bool, Block)>,
     }
 
     impl State for Setup {}
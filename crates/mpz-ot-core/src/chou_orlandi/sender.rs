@@ -4,6 +4,7 @@ use crate::{
         msgs::{ReceiverPayload, ReceiverReveal, SenderPayload, SenderSetup},
         Receiver, ReceiverConfig, SenderConfig, SenderError, SenderVerifyError,
     },
+    msgs::Derandomize,
     TransferId,
 };
 
@@ -34,6 +35,8 @@ pub struct Sender<T: state::State = state::Initialized> {
     state: T,
     /// Protocol tape
     tape: Option<Tape>,
+    /// Domain of the protocol instance, hashed into the keys
+    domain: [u8; 32],
 }
 
 impl Sender {
@@ -53,6 +56,7 @@ impl Sender {
             config,
             state: state::Initialized::default(),
             tape,
+            domain: [0u8; 32],
         }
     }
 
@@ -82,9 +86,18 @@ impl Sender {
             config,
             state,
             tape,
+            domain: [0u8; 32],
         }
     }
 
+    /// Sets the domain of the protocol instance, which is hashed into the keys.
+    ///
+    /// The receiver must use the same domain.
+    pub fn with_domain(mut self, domain: [u8; 32]) -> Self {
+        self.domain = domain;
+        self
+    }
+
     /// Returns the Sender's configuration
     pub fn config(&self) -> &SenderConfig {
         &self.config
@@ -106,8 +119,10 @@ impl Sender {
                     public_key,
                     transfer_id: TransferId::default(),
                     counter: 0,
+                    pool: Vec::new(),
                 },
                 tape: self.tape,
+                domain: self.domain,
             },
         )
     }
@@ -157,8 +172,13 @@ impl Sender<state::Setup> {
             tape.receiver_choices.extend_from_slice(&blinded_choices);
         }
 
-        let mut payload =
-            compute_encryption_keys(private_key, public_key, &blinded_choices, *counter);
+        let mut payload = compute_encryption_keys(
+            &self.domain,
+            private_key,
+            public_key,
+            &blinded_choices,
+            *counter,
+        );
 
         *counter += inputs.len();
 
@@ -171,6 +191,109 @@ impl Sender<state::Setup> {
         Ok(SenderPayload { id, payload })
     }
 
+    /// Returns the number of precomputed OTs which have not been used yet.
+    pub fn pool_len(&self) -> usize {
+        self.state.pool.len()
+    }
+
+    /// Precomputes random OTs for the receiver's random choices, adding their keys to the pool.
+    ///
+    /// The pooled OTs are later used with [`Sender::send_derandomized`].
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver_payload` - The receiver's payload from [`Receiver::precompute`].
+    pub fn precompute(&mut self, receiver_payload: ReceiverPayload) -> Result<(), SenderError> {
+        // The tape records the blinded choices of chosen-input transfers only, so the
+        // receiver's choices could not be verified.
+        if self.tape.is_some() {
+            return Err(SenderError::CommittedPrecompute);
+        }
+
+        let state::Setup {
+            private_key,
+            public_key,
+            transfer_id: current_id,
+            counter,
+            pool,
+        } = &mut self.state;
+
+        let ReceiverPayload {
+            id,
+            blinded_choices,
+        } = receiver_payload;
+
+        // Check that the transfer id matches
+        let expected_id = current_id.next();
+        if id != expected_id {
+            return Err(SenderError::IdMismatch(expected_id, id));
+        }
+
+        pool.extend(compute_encryption_keys(
+            &self.domain,
+            private_key,
+            public_key,
+            &blinded_choices,
+            *counter,
+        ));
+
+        *counter += blinded_choices.len();
+
+        Ok(())
+    }
+
+    /// Obliviously sends `inputs` to the receiver using precomputed OTs.
+    ///
+    /// The receiver's choices are corrected using Beaver derandomization.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs to be obliviously sent to the receiver.
+    /// * `derandomize` - The receiver's correction bits.
+    pub fn send_derandomized(
+        &mut self,
+        inputs: &[[Block; 2]],
+        derandomize: Derandomize,
+    ) -> Result<SenderPayload, SenderError> {
+        let state::Setup {
+            transfer_id: current_id,
+            pool,
+            ..
+        } = &mut self.state;
+
+        let Derandomize { id, count, flip } = derandomize;
+
+        // Check that the transfer id matches
+        let expected_id = current_id.next();
+        if id != expected_id {
+            return Err(SenderError::IdMismatch(expected_id, id));
+        }
+
+        // Check that the number of inputs matches the number of choices
+        if inputs.len() != count as usize {
+            return Err(SenderError::CountMismatch(inputs.len(), count as usize));
+        }
+
+        if inputs.len() > pool.len() {
+            return Err(SenderError::InsufficientPool(inputs.len(), pool.len()));
+        }
+
+        let payload = pool
+            .drain(..inputs.len())
+            .zip(inputs)
+            .zip(flip.into_iter_lsb0())
+            .map(|(([k0, k1], [m0, m1]), flip)| {
+                if flip {
+                    [k1 ^ *m0, k0 ^ *m1]
+                } else {
+                    [k0 ^ *m0, k1 ^ *m1]
+                }
+            })
+            .collect();
+
+        Ok(SenderPayload { id, payload })
+    }
+
     /// Returns the Receiver choices after verifying them against the tape.
     ///
     /// # ⚠️ Warning ⚠️
@@ -230,12 +353,14 @@ impl Sender<state::Setup> {
 ///
 /// # Arguments
 ///
+/// * `domain` - The domain of the protocol instance.
 /// * `private_key` - The sender's private key.
 /// * `public_key` - The sender's public key.
 /// * `blinded_choices` - The receiver's blinded choices.
 /// * `offset` - The number of OTs that have already been performed
 ///              (used for the key derivation tweak)
 fn compute_encryption_keys(
+    domain: &[u8; 32],
     private_key: &Scalar,
     public_key: &RistrettoPoint,
     blinded_choices: &[RistrettoPoint],
@@ -259,9 +384,9 @@ fn compute_encryption_keys(
     iter.map(|(i, blinded_choice)| {
         // yr is B^a in [ref1]
         let yr = private_key * blinded_choice;
-        let k0 = hash_point(&yr, (offset + i) as u128);
+        let k0 = hash_point(domain, &yr, (offset + i) as u128);
         // yr - ys == (B/A)^a in [ref1]
-        let k1 = hash_point(&(yr - ys), (offset + i) as u128);
+        let k1 = hash_point(domain, &(yr - ys), (offset + i) as u128);
 
         [k0, k1]
     })
@@ -316,6 +441,8 @@ pub mod state {
         pub(super) transfer_id: TransferId,
         /// Number of OTs sent so far
         pub(super) counter: usize,
+        /// Keys of precomputed random OTs
+        pub(super) pool: Vec<[Block; 2]>,
     }
 
     impl State for Setup {}
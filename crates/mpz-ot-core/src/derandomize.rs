@@ -0,0 +1,289 @@
+//! Chosen-message OT from random OT.
+//!
+//! Any random OT can be turned into a chosen-message OT with Beaver derandomization. For each OT
+//! `i`, where the sender holds random messages `(k_0, k_1)` and the receiver holds a random choice
+//! `r_i` and `k_{r_i}`:
+//!
+//! - The receiver sends the flip bit `f_i = r_i ⊕ c_i` for its actual choice `c_i`.
+//! - The sender sends `(k_{f_i} ⊕ m_0, k_{1 ⊕ f_i} ⊕ m_1)`.
+//! - The receiver outputs `m_{c_i}` by removing `k_{r_i}` from the `c_i`-th ciphertext.
+//!
+//! The flip bits are uniformly random from the sender's point of view, so they do not reveal the
+//! choices. This only requires the random OT to have endemic security, ie a corrupted party may
+//! choose its own outputs. Random COTs, such as the outputs of Ferret, can be used once their
+//! correlation is broken with [`rot_from_rcot_sender`] and [`rot_from_rcot_receiver`].
+//!
+//! Each random OT must be used at most once.
+
+use itybity::{FromBitIterator, ToBits};
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+
+use crate::{
+    msgs::{Derandomize, DerandomizePayload},
+    OTReceiverOutput, OTSenderOutput, RCOTReceiverOutput, RCOTSenderOutput, ROTReceiverOutput,
+    ROTSenderOutput, TransferId,
+};
+
+/// Errors that can occur when derandomizing random OTs.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum DerandomizeError {
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(TransferId, TransferId),
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+}
+
+/// Returns the tweak for the OT at index `i` of a transfer.
+fn tweak(id: TransferId, i: usize) -> Block {
+    Block::new((((id.0 as u128) << 64) | i as u128).to_be_bytes())
+}
+
+/// Returns the sender's random OTs derived from random COTs.
+///
+/// The messages are hashed with a tweakable circular correlation-robust hash, which breaks the
+/// correlation of the COTs.
+///
+/// # Arguments
+///
+/// * `delta` - The correlation of the COTs.
+/// * `output` - The random COTs, which must not be used again.
+pub fn rot_from_rcot_sender(
+    delta: Block,
+    output: RCOTSenderOutput<Block>,
+) -> ROTSenderOutput<[Block; 2]> {
+    let RCOTSenderOutput { id, msgs } = output;
+
+    let cipher = &(*FIXED_KEY_AES);
+    let msgs = msgs
+        .into_iter()
+        .enumerate()
+        .map(|(i, k)| {
+            let t = tweak(id, i);
+            [cipher.tccr(t, k), cipher.tccr(t, k ^ delta)]
+        })
+        .collect();
+
+    ROTSenderOutput { id, msgs }
+}
+
+/// Returns the receiver's random OTs derived from random COTs.
+///
+/// # Arguments
+///
+/// * `output` - The random COTs, which must not be used again.
+pub fn rot_from_rcot_receiver(
+    output: RCOTReceiverOutput<bool, Block>,
+) -> ROTReceiverOutput<bool, Block> {
+    let RCOTReceiverOutput { id, choices, msgs } = output;
+
+    let cipher = &(*FIXED_KEY_AES);
+    let msgs = msgs
+        .into_iter()
+        .enumerate()
+        .map(|(i, m)| cipher.tccr(tweak(id, i), m))
+        .collect();
+
+    ROTReceiverOutput { id, choices, msgs }
+}
+
+/// Returns the message the receiver sends to correct its random choices to `choices`.
+///
+/// # Arguments
+///
+/// * `output` - The receiver's random OTs.
+/// * `choices` - The receiver's choices.
+pub fn derandomize_choices(
+    output: &ROTReceiverOutput<bool, Block>,
+    choices: &[bool],
+) -> Result<Derandomize, DerandomizeError> {
+    if choices.len() != output.choices.len() {
+        return Err(DerandomizeError::CountMismatch(
+            output.choices.len(),
+            choices.len(),
+        ));
+    }
+
+    let flip = Vec::<u8>::from_lsb0_iter(
+        output
+            .choices
+            .iter()
+            .zip(choices)
+            .map(|(random, choice)| random ^ choice),
+    );
+
+    Ok(Derandomize {
+        id: output.id,
+        count: choices.len() as u32,
+        flip,
+    })
+}
+
+/// Encrypts the sender's messages using its random OTs and the receiver's correction.
+///
+/// # Arguments
+///
+/// * `output` - The sender's random OTs, which must not be used again.
+/// * `derandomize` - The receiver's correction.
+/// * `msgs` - The sender's messages.
+pub fn derandomize_sender(
+    output: ROTSenderOutput<[Block; 2]>,
+    derandomize: Derandomize,
+    msgs: &[[Block; 2]],
+) -> Result<(OTSenderOutput, DerandomizePayload), DerandomizeError> {
+    let ROTSenderOutput { id, msgs: keys } = output;
+
+    if derandomize.id != id {
+        return Err(DerandomizeError::IdMismatch(id, derandomize.id));
+    }
+
+    if derandomize.count as usize != keys.len() {
+        return Err(DerandomizeError::CountMismatch(
+            keys.len(),
+            derandomize.count as usize,
+        ));
+    }
+
+    if msgs.len() != keys.len() {
+        return Err(DerandomizeError::CountMismatch(keys.len(), msgs.len()));
+    }
+
+    let ciphertexts = keys
+        .into_iter()
+        .zip(msgs)
+        .zip(derandomize.flip.iter_lsb0())
+        .flat_map(|(([k0, k1], [m0, m1]), flip)| {
+            if flip {
+                [k1 ^ *m0, k0 ^ *m1]
+            } else {
+                [k0 ^ *m0, k1 ^ *m1]
+            }
+        })
+        .collect();
+
+    Ok((
+        OTSenderOutput { id },
+        DerandomizePayload { id, ciphertexts },
+    ))
+}
+
+/// Decrypts the messages chosen by the receiver.
+///
+/// # Arguments
+///
+/// * `output` - The receiver's random OTs, which must not be used again.
+/// * `choices` - The receiver's choices, which were used to derandomize the OTs.
+/// * `payload` - The sender's ciphertexts.
+pub fn derandomize_receiver(
+    output: ROTReceiverOutput<bool, Block>,
+    choices: &[bool],
+    payload: DerandomizePayload,
+) -> Result<OTReceiverOutput<Block>, DerandomizeError> {
+    let ROTReceiverOutput { id, msgs: keys, .. } = output;
+    let DerandomizePayload {
+        id: payload_id,
+        ciphertexts,
+    } = payload;
+
+    if payload_id != id {
+        return Err(DerandomizeError::IdMismatch(id, payload_id));
+    }
+
+    if choices.len() != keys.len() {
+        return Err(DerandomizeError::CountMismatch(keys.len(), choices.len()));
+    }
+
+    if ciphertexts.len() != 2 * keys.len() {
+        return Err(DerandomizeError::CountMismatch(
+            2 * keys.len(),
+            ciphertexts.len(),
+        ));
+    }
+
+    let msgs = keys
+        .into_iter()
+        .zip(choices)
+        .zip(ciphertexts.chunks_exact(2))
+        .map(|((key, &choice), ciphertexts)| key ^ ciphertexts[choice as usize])
+        .collect();
+
+    Ok(OTReceiverOutput { id, msgs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::{
+        ideal::{cot::IdealCOT, rot::IdealROT},
+        test::assert_rot,
+    };
+
+    fn derandomize(
+        sender_output: ROTSenderOutput<[Block; 2]>,
+        receiver_output: ROTReceiverOutput<bool, Block>,
+        rng: &mut ChaCha12Rng,
+    ) {
+        let count = sender_output.msgs.len();
+        let msgs: Vec<[Block; 2]> = (0..count).map(|_| rng.gen()).collect();
+        let choices: Vec<bool> = (0..count).map(|_| rng.gen()).collect();
+
+        let derandomize = derandomize_choices(&receiver_output, &choices).unwrap();
+        let (sender_output, payload) =
+            derandomize_sender(sender_output, derandomize, &msgs).unwrap();
+        let receiver_output = derandomize_receiver(receiver_output, &choices, payload).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_rot(&choices, &msgs, &receiver_output.msgs);
+    }
+
+    #[test]
+    fn test_derandomize() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let mut rot = IdealROT::default();
+
+        let (sender_output, receiver_output) = rot.random::<Block>(131);
+        derandomize(sender_output, receiver_output, &mut rng);
+    }
+
+    #[test]
+    fn test_derandomize_rcot() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let mut cot = IdealCOT::default();
+        let delta = cot.delta();
+
+        let (sender_output, receiver_output) = cot.random_correlated(131);
+        let sender_output = rot_from_rcot_sender(delta, sender_output);
+        let receiver_output = rot_from_rcot_receiver(receiver_output);
+
+        assert_rot(
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
+        assert!(sender_output.msgs.iter().all(|[k0, k1]| *k0 ^ *k1 != delta));
+
+        derandomize(sender_output, receiver_output, &mut rng);
+    }
+
+    #[test]
+    fn test_derandomize_mismatch() {
+        let mut rot = IdealROT::default();
+
+        let (sender_output, receiver_output) = rot.random::<Block>(8);
+        assert!(matches!(
+            derandomize_choices(&receiver_output, &[false; 7]),
+            Err(DerandomizeError::CountMismatch(8, 7))
+        ));
+
+        let (_, other_output) = rot.random::<Block>(8);
+        let derandomize = derandomize_choices(&other_output, &[false; 8]).unwrap();
+        assert!(matches!(
+            derandomize_sender(sender_output, derandomize, &[[Block::ZERO; 2]; 8]),
+            Err(DerandomizeError::IdMismatch(..))
+        ));
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use derive_builder::Builder;
+use mpz_core::lpn::LpnParameters;
+
+use crate::{
+    ferret::{LpnType, LPN_PARAMETERS_REGULAR},
+    kos::{DEFAULT_BASE_OT_TIMEOUT, DEFAULT_EXTENSION_TIMEOUT},
+};
+
+/// Ferret configuration.
+///
+/// Both parties must use the same configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct FerretConfig {
+    /// The LPN parameters.
+    #[builder(default = "LPN_PARAMETERS_REGULAR")]
+    lpn_parameters: LpnParameters,
+    /// The type of the LPN error distribution.
+    #[builder(default = "LpnType::Regular")]
+    lpn_type: LpnType,
+    /// Whether to perform the SPCOT consistency check, which is required for security against a
+    /// malicious sender.
+    ///
+    /// Without the check the protocol is only secure against semi-honest adversaries.
+    #[builder(default = "true")]
+    consistency_check: bool,
+    /// Timeout for the setup.
+    #[builder(default = "DEFAULT_BASE_OT_TIMEOUT")]
+    setup_timeout: Duration,
+    /// Timeout for a single extension, including the consistency check.
+    #[builder(default = "DEFAULT_EXTENSION_TIMEOUT")]
+    extension_timeout: Duration,
+}
+
+impl Default for FerretConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+impl FerretConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let lpn_type = self.lpn_type.unwrap_or(LpnType::Regular);
+        let Some(lpn_parameters) = self.lpn_parameters else {
+            return Ok(());
+        };
+
+        check_lpn_parameters(lpn_type, &lpn_parameters)
+    }
+}
+
+/// Checks that LPN parameters can be used for the Ferret extension.
+pub(crate) fn check_lpn_parameters(
+    lpn_type: LpnType,
+    lpn_parameters: &LpnParameters,
+) -> Result<(), String> {
+    let LpnParameters { n, k, t } = *lpn_parameters;
+
+    if t == 0 || t > n || k >= n {
+        return Err(format!("invalid LPN parameters: n={n}, k={k}, t={t}"));
+    }
+
+    if lpn_type == LpnType::Regular && n % t != 0 {
+        return Err(format!(
+            "regular LPN parameters require t to divide n: n={n}, t={t}"
+        ));
+    }
+
+    Ok(())
+}
+
+impl FerretConfig {
+    /// Creates a new builder for FerretConfig.
+    pub fn builder() -> FerretConfigBuilder {
+        FerretConfigBuilder::default()
+    }
+
+    /// Returns the LPN parameters.
+    pub fn lpn_parameters(&self) -> LpnParameters {
+        self.lpn_parameters
+    }
+
+    /// Returns the type of the LPN error distribution.
+    pub fn lpn_type(&self) -> LpnType {
+        self.lpn_type
+    }
+
+    /// Returns whether the SPCOT consistency check is performed.
+    pub fn consistency_check(&self) -> bool {
+        self.consistency_check
+    }
+
+    /// Returns the timeout for the setup.
+    pub fn setup_timeout(&self) -> Duration {
+        self.setup_timeout
+    }
+
+    /// Returns the timeout for a single extension, including the consistency check.
+    pub fn extension_timeout(&self) -> Duration {
+        self.extension_timeout
+    }
+
+    /// Returns the number of COTs produced by a single extension, including those set aside for
+    /// the next extension.
+    pub fn extension_output_size(&self) -> usize {
+        self.lpn_parameters.n - self.lpn_parameters.k
+    }
+}
@@ -30,6 +30,20 @@ pub struct Item {
     pub(crate) hash_index: usize,
 }
 
+impl Item {
+    /// Returns the value of the item.
+    ///
+    /// For tables built with [`CuckooHash::insert_blocks`] this is the index of the block.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Returns the index of the hash function which placed the item.
+    pub fn hash_index(&self) -> usize {
+        self.hash_index
+    }
+}
+
 /// Implementation of Cuckoo hash. See [here](https://eprint.iacr.org/2019/1084.pdf) for reference.
 pub struct CuckooHash {
     hashes: Arc<[AesEncryptor; CUCKOO_HASH_NUM]>,
@@ -56,14 +70,53 @@ impl CuckooHash {
         let mut table = vec![None; m];
         // Inserts each alpha.
         for &value in alphas {
-            self.hash(&mut table, value)?
+            self.hash(&mut table, value, |hash, range, value| {
+                hash_to_index(hash, range, value)
+            })?
         }
         Ok(table)
     }
 
+    /// Insert blocks into a Cuckoo hash table.
+    ///
+    /// The blocks are placed by their value, but the items in the table hold the index of the
+    /// block instead, see [`Item::value`].
+    ///
+    /// * Argument
+    ///
+    /// * `blocks` - The blocks being inserted, which must be distinct.
+    #[inline]
+    pub fn insert_blocks(&self, blocks: &[Block]) -> Result<Vec<Option<Item>>, CuckooHashError> {
+        let m = compute_table_length(blocks.len() as u32);
+
+        let mut table = vec![None; m];
+        for index in 0..blocks.len() as u32 {
+            self.hash(&mut table, index, |hash, range, index| {
+                hash_block_to_index(hash, range, blocks[index as usize])
+            })?
+        }
+        Ok(table)
+    }
+
+    /// Returns the position of a block in a table of length `range` under each hash function.
+    ///
+    /// * Argument
+    ///
+    /// * `block` - The block.
+    /// * `range` - The length of the table.
+    #[inline]
+    pub fn positions(&self, block: Block, range: usize) -> [usize; CUCKOO_HASH_NUM] {
+        std::array::from_fn(|i| hash_block_to_index(&self.hashes[i], range, block))
+    }
+
     // Hash an element to a position with the current hash function.
     #[inline]
-    fn hash(&self, table: &mut [Option<Item>], value: u32) -> Result<(), CuckooHashError> {
+    fn hash(
+        &self,
+        table: &mut [Option<Item>],
+        value: u32,
+        position: impl Fn(&AesEncryptor, usize, u32) -> usize,
+    ) -> Result<(), CuckooHashError> {
         // The item consists of the value and hash index, starting from 0.
         let mut item = Item {
             value,
@@ -72,7 +125,7 @@ impl CuckooHash {
 
         for _ in 0..CUCKOO_TRIAL_NUM {
             // Computes the position of the value.
-            let pos = hash_to_index(&self.hashes[item.hash_index], table.len(), item.value);
+            let pos = position(&self.hashes[item.hash_index], table.len(), item.value);
 
             // Inserts the value to position `pos`.
             let opt_item = table[pos].replace(item);
@@ -127,17 +180,24 @@ impl Bucket {
     }
 }
 
-// Always sets m = 1.5 * t. t is the length of `alphas`. See Section 7.1 Parameter Selection.
+/// Returns the length of the table for `t` elements.
+///
+/// Always sets m = 1.5 * t. See Section 7.1 Parameter Selection.
 #[inline(always)]
-pub(crate) fn compute_table_length(t: u32) -> usize {
+pub fn compute_table_length(t: u32) -> usize {
     (1.5 * (t as f32)).ceil() as usize
 }
 
 // Hash the value into index using AES.
 #[inline(always)]
 pub(crate) fn hash_to_index(hash: &AesEncryptor, range: usize, value: u32) -> usize {
-    let mut blk: Block = bytemuck::cast::<_, Block>(value as u128);
-    blk = hash.encrypt_block(blk);
+    hash_block_to_index(hash, range, bytemuck::cast::<_, Block>(value as u128))
+}
+
+// Hash the block into index using AES.
+#[inline(always)]
+fn hash_block_to_index(hash: &AesEncryptor, range: usize, blk: Block) -> usize {
+    let blk = hash.encrypt_block(blk);
     let res = u128::from_le_bytes(blk.to_bytes());
     (res as usize) % range
 }
@@ -155,7 +215,7 @@ mod tests {
     use std::sync::Arc;
 
     use super::{Bucket, CuckooHash};
-    use mpz_core::{aes::AesEncryptor, prg::Prg};
+    use mpz_core::{aes::AesEncryptor, prg::Prg, Block};
 
     #[test]
     fn cockoo_hash_bucket_test() {
@@ -192,4 +252,27 @@ mod tests {
             })
             .collect();
     }
+
+    #[test]
+    fn cuckoo_hash_blocks_test() {
+        let mut prg = Prg::new();
+        let hashes = Arc::new(std::array::from_fn(|_| {
+            AesEncryptor::new(prg.random_block())
+        }));
+        let cuckoo = CuckooHash::new(hashes);
+
+        let mut blocks = vec![Block::ZERO; 100];
+        prg.random_blocks(&mut blocks);
+
+        let table = cuckoo.insert_blocks(&blocks).unwrap();
+
+        // Every block is in the table, at the position of the hash function which placed it.
+        assert_eq!(table.iter().flatten().count(), blocks.len());
+        for (pos, item) in table.iter().enumerate() {
+            if let Some(item) = item {
+                let block = blocks[item.value() as usize];
+                assert_eq!(cuckoo.positions(block, table.len())[item.hash_index()], pos);
+            }
+        }
+    }
 }
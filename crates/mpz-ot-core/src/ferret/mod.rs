@@ -1,7 +1,9 @@
 //! An implementation of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) protocol.
 
 use mpz_core::lpn::LpnParameters;
+use serde::{Deserialize, Serialize};
 
+mod config;
 pub mod cuckoo;
 pub mod error;
 pub mod mpcot;
@@ -10,9 +12,17 @@ pub mod receiver;
 pub mod sender;
 pub mod spcot;
 
+pub use config::{FerretConfig, FerretConfigBuilder, FerretConfigBuilderError};
+
+use config::check_lpn_parameters;
+
 /// Computational security parameter
 pub const CSP: usize = 128;
 
+/// The format version of a sealed sender or receiver state, which is the first byte of the
+/// state inside the seal.
+const STATE_VERSION: u8 = 1;
+
 /// Number of hashes in Cuckoo hash.
 pub const CUCKOO_HASH_NUM: usize = 3;
 
@@ -36,7 +46,7 @@ pub const LPN_PARAMETERS_UNIFORM: LpnParameters = LpnParameters {
 };
 
 /// The type of Lpn parameters.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LpnType {
     /// Uniform error distribution.
     Uniform,
@@ -52,11 +62,14 @@ mod tests {
     use receiver::Receiver;
     use sender::Sender;
 
+    use spcot::{receiver::Receiver as SpcotReceiver, sender::Sender as SpcotSender};
+
     use crate::ideal::{cot::IdealCOT, mpcot::IdealMpcot};
     use crate::test::assert_cot;
     use crate::{MPCOTReceiverOutput, MPCOTSenderOutput, RCOTReceiverOutput, RCOTSenderOutput};
-    use mpz_core::{lpn::LpnParameters, prg::Prg};
-    use rand::SeedableRng;
+    use mpz_common::seal::{self, SealingKey};
+    use mpz_core::{lpn::LpnParameters, prg::Prg, transcript::Transcript, Block};
+    use rand::{rngs::StdRng, SeedableRng};
 
     const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
         n: 9600,
@@ -138,5 +151,138 @@ mod tests {
         let (choices, received) = receiver.extend(&r).unwrap();
 
         assert_cot(delta, &choices, &msgs, &received);
+
+        // seal and restore the state, then extend again
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = SealingKey::random(&mut rng);
+        let mut sender = Sender::open(&key, &sender.seal(&key, &mut rng)).unwrap();
+        let mut receiver = Receiver::open(&key, &receiver.seal(&key, &mut rng)).unwrap();
+
+        let _ = sender.get_mpcot_query();
+        let query = receiver.get_mpcot_query();
+
+        let (MPCOTSenderOutput { s, .. }, MPCOTReceiverOutput { r, .. }) =
+            ideal_mpcot.extend(&query.0, query.1);
+
+        let msgs = sender.extend(&s).unwrap();
+        let (choices, received) = receiver.extend(&r).unwrap();
+
+        assert_cot(delta, &choices, &msgs, &received);
+    }
+
+    #[test]
+    fn ferret_transcript() {
+        let mut transcript = Transcript::open(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/transcripts"),
+            "ferret",
+            env!("CARGO_PKG_VERSION"),
+        );
+
+        let mut prg = Prg::from_seed([1u8; 16].into());
+        let mut ideal_cot = IdealCOT::default();
+        let delta = ideal_cot.delta();
+
+        // Setup, in which the receiver sends the seed of the LPN matrix.
+        let (sender_cot, receiver_cot) = ideal_cot.random_correlated(LPN_PARAMETERS_TEST.k);
+
+        let (_, seed) = Receiver::new()
+            .setup(
+                LPN_PARAMETERS_TEST,
+                LpnType::Regular,
+                prg.random_block(),
+                &receiver_cot.choices,
+                &receiver_cot.msgs,
+            )
+            .unwrap();
+
+        let LpnMatrixSeed {
+            seed: lpn_matrix_seed,
+        } = transcript.exchange("lpn_matrix_seed", seed);
+
+        Sender::new()
+            .setup(
+                delta,
+                LPN_PARAMETERS_TEST,
+                LpnType::Regular,
+                lpn_matrix_seed,
+                &sender_cot.msgs,
+            )
+            .unwrap();
+
+        // The SPCOT messages of one extension, which the MPCOT of each Ferret extension is
+        // built from.
+        let mut spcot_sender = SpcotSender::new().setup(delta, prg.random_block());
+        let mut spcot_receiver = SpcotReceiver::new().setup();
+
+        let (h, alpha) = (8, 3);
+        let (sender_cot, receiver_cot) = ideal_cot.random_correlated(h);
+
+        let mask_bits = transcript.exchange(
+            "spcot/mask_bits",
+            spcot_receiver
+                .extend_mask_bits(h, alpha, &receiver_cot.choices)
+                .unwrap(),
+        );
+        let extend = transcript.exchange(
+            "spcot/extend",
+            spcot_sender.extend(h, &sender_cot.msgs, mask_bits).unwrap(),
+        );
+        spcot_receiver
+            .extend(h, alpha, &receiver_cot.msgs, extend)
+            .unwrap();
+
+        let (sender_cot, receiver_cot) = ideal_cot.random_correlated(CSP);
+
+        let check = transcript.exchange(
+            "spcot/check_from_receiver",
+            spcot_receiver.check_pre(&receiver_cot.choices).unwrap(),
+        );
+        let (_, check) = spcot_sender.check(&sender_cot.msgs, check).unwrap();
+        let check = transcript.exchange("spcot/check_from_sender", check);
+        spcot_receiver.check(&receiver_cot.msgs, check).unwrap();
+
+        transcript.finish();
+    }
+
+    #[test]
+    fn ferret_restore_invalid_state_fails() {
+        assert!(Sender::from_bytes(&[]).is_err());
+        assert!(Receiver::from_bytes(&[]).is_err());
+        assert!(Sender::from_bytes(&[0u8; 4]).is_err());
+        assert!(Receiver::from_bytes(&[0u8; 4]).is_err());
+
+        let sender = Sender::new()
+            .setup(
+                Block::ONES,
+                LPN_PARAMETERS_TEST,
+                LpnType::Regular,
+                Block::ZERO,
+                &vec![Block::ZERO; LPN_PARAMETERS_TEST.k],
+            )
+            .unwrap();
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let key = SealingKey::random(&mut rng);
+        let sealed = sender.seal(&key, &mut rng);
+        assert!(Sender::open(&key, &sealed).is_ok());
+
+        // A sealed state can not be opened with another key, or as a receiver state.
+        assert!(Sender::open(&SealingKey::random(&mut rng), &sealed).is_err());
+        assert!(Receiver::open(&key, &sealed).is_err());
+
+        let bytes = seal::open(&key, "mpz-ot/ferret/sender", &sealed).unwrap();
+        assert!(Sender::from_bytes(&bytes).is_ok());
+
+        // A state of another format version.
+        let mut other_version = bytes.clone();
+        other_version[0] = STATE_VERSION + 1;
+        assert!(Sender::from_bytes(&other_version).is_err());
+
+        // A state with k > n, which is laid out as the version, delta, counter, LPN type and
+        // then n.
+        let mut invalid_lpn = bytes;
+        invalid_lpn[29..37].copy_from_slice(&1u64.to_le_bytes());
+        let err = Sender::from_bytes(&invalid_lpn).unwrap_err();
+        assert!(err.0.contains("invalid LPN parameters"));
     }
 }
@@ -1,10 +1,15 @@
 //! Ferret receiver
+use std::borrow::Cow;
+
+use mpz_common::seal::{self, SealingKey};
 use mpz_core::{
     lpn::{LpnEncoder, LpnParameters},
     Block,
 };
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
 
-use crate::ferret::{error::ReceiverError, LpnType};
+use crate::ferret::{check_lpn_parameters, error::ReceiverError, LpnType, STATE_VERSION};
 
 use super::msgs::LpnMatrixSeed;
 
@@ -133,6 +138,103 @@ impl Receiver<state::Extension> {
 
         Ok((x_, z_))
     }
+
+    /// Seals the receiver's extension state, so that it can be restored later with
+    /// [`Receiver::open`] without repeating the setup phase.
+    ///
+    /// The state contains the receiver's secret choices, so it is only ever persisted sealed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sealing key.
+    /// * `rng` - The random number generator used for sealing.
+    pub fn seal<R: RngCore + CryptoRng>(&self, key: &SealingKey, rng: &mut R) -> Vec<u8> {
+        let state = SavedState {
+            counter: self.state.counter,
+            lpn_type: self.state.lpn_type,
+            lpn_parameters: self.state.lpn_parameters,
+            lpn_seed: self.state.lpn_encoder.seed(),
+            u: Cow::Borrowed(self.state.u.as_slice()),
+            w: Cow::Borrowed(self.state.w.as_slice()),
+            e: Cow::Borrowed(self.state.e.as_slice()),
+        };
+
+        seal::seal_value(key, STATE_LABEL, &(STATE_VERSION, state), rng)
+            .expect("receiver state should seal")
+    }
+
+    /// Opens a receiver's extension state which was sealed with [`Receiver::seal`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sealing key.
+    /// * `sealed` - The sealed state.
+    pub fn open(key: &SealingKey, sealed: &[u8]) -> Result<Self, ReceiverError> {
+        let bytes = seal::open(key, STATE_LABEL, sealed)
+            .map_err(|e| ReceiverError(format!("a valid sealed receiver state: {e}")))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Restores a receiver's extension state from its serialized form.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, ReceiverError> {
+        let Some((&version, bytes)) = bytes.split_first() else {
+            return Err(ReceiverError(
+                "a non-empty serialized receiver state".to_string(),
+            ));
+        };
+
+        if version != STATE_VERSION {
+            return Err(ReceiverError(format!(
+                "serialized receiver state version {STATE_VERSION}, got {version}"
+            )));
+        }
+
+        let state: SavedState = bincode::deserialize(bytes)
+            .map_err(|e| ReceiverError(format!("a valid serialized receiver state: {e}")))?;
+
+        check_lpn_parameters(state.lpn_type, &state.lpn_parameters).map_err(ReceiverError)?;
+
+        if state.u.len() != state.lpn_parameters.k || state.w.len() != state.lpn_parameters.k {
+            return Err(ReceiverError(
+                "the length of u and w in the serialized state to be equal to k".to_string(),
+            ));
+        }
+
+        // The error vector is only sampled once the first extension starts.
+        if !state.e.is_empty() && state.e.len() != state.lpn_parameters.n {
+            return Err(ReceiverError(
+                "the length of e in the serialized state to be equal to n".to_string(),
+            ));
+        }
+
+        Ok(Receiver {
+            state: state::Extension {
+                counter: state.counter,
+                lpn_type: state.lpn_type,
+                lpn_parameters: state.lpn_parameters,
+                lpn_encoder: LpnEncoder::<10>::new(state.lpn_seed, state.lpn_parameters.k as u32),
+                u: state.u.into_owned(),
+                w: state.w.into_owned(),
+                e: state.e.into_owned(),
+            },
+        })
+    }
+}
+
+/// The label of a sealed receiver state.
+const STATE_LABEL: &str = "mpz-ot/ferret/receiver";
+
+/// The serialized form of the receiver's extension state.
+#[derive(Serialize, Deserialize)]
+struct SavedState<'a> {
+    counter: usize,
+    lpn_type: LpnType,
+    lpn_parameters: LpnParameters,
+    lpn_seed: Block,
+    u: Cow<'a, [bool]>,
+    w: Cow<'a, [Block]>,
+    e: Cow<'a, [Block]>,
 }
 
 /// The receiver's state.
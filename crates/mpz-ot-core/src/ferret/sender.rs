@@ -1,10 +1,15 @@
 //! Ferret sender.
+use std::borrow::Cow;
+
+use mpz_common::seal::{self, SealingKey};
 use mpz_core::{
     lpn::{LpnEncoder, LpnParameters},
     Block,
 };
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
 
-use crate::ferret::{error::SenderError, LpnType};
+use crate::ferret::{check_lpn_parameters, error::SenderError, LpnType, STATE_VERSION};
 
 /// Ferret sender.
 #[derive(Debug, Default)]
@@ -97,6 +102,93 @@ impl Sender<state::Extension> {
 
         Ok(y_)
     }
+
+    /// Seals the sender's extension state, so that it can be restored later with
+    /// [`Sender::open`] without repeating the setup phase.
+    ///
+    /// The state contains the sender's global secret, so it is only ever persisted sealed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sealing key.
+    /// * `rng` - The random number generator used for sealing.
+    pub fn seal<R: RngCore + CryptoRng>(&self, key: &SealingKey, rng: &mut R) -> Vec<u8> {
+        let state = SavedState {
+            delta: self.state.delta,
+            counter: self.state.counter,
+            lpn_type: self.state.lpn_type,
+            lpn_parameters: self.state.lpn_parameters,
+            lpn_seed: self.state.lpn_encoder.seed(),
+            v: Cow::Borrowed(self.state.v.as_slice()),
+        };
+
+        seal::seal_value(key, STATE_LABEL, &(STATE_VERSION, state), rng)
+            .expect("sender state should seal")
+    }
+
+    /// Opens a sender's extension state which was sealed with [`Sender::seal`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sealing key.
+    /// * `sealed` - The sealed state.
+    pub fn open(key: &SealingKey, sealed: &[u8]) -> Result<Self, SenderError> {
+        let bytes = seal::open(key, STATE_LABEL, sealed)
+            .map_err(|e| SenderError(format!("a valid sealed sender state: {e}")))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Restores a sender's extension state from its serialized form.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, SenderError> {
+        let Some((&version, bytes)) = bytes.split_first() else {
+            return Err(SenderError(
+                "a non-empty serialized sender state".to_string(),
+            ));
+        };
+
+        if version != STATE_VERSION {
+            return Err(SenderError(format!(
+                "serialized sender state version {STATE_VERSION}, got {version}"
+            )));
+        }
+
+        let state: SavedState = bincode::deserialize(bytes)
+            .map_err(|e| SenderError(format!("a valid serialized sender state: {e}")))?;
+
+        check_lpn_parameters(state.lpn_type, &state.lpn_parameters).map_err(SenderError)?;
+
+        if state.v.len() != state.lpn_parameters.k {
+            return Err(SenderError(
+                "the length of v in the serialized state to be equal to k".to_string(),
+            ));
+        }
+
+        Ok(Sender {
+            state: state::Extension {
+                delta: state.delta,
+                counter: state.counter,
+                lpn_type: state.lpn_type,
+                lpn_parameters: state.lpn_parameters,
+                lpn_encoder: LpnEncoder::<10>::new(state.lpn_seed, state.lpn_parameters.k as u32),
+                v: state.v.into_owned(),
+            },
+        })
+    }
+}
+
+/// The label of a sealed sender state.
+const STATE_LABEL: &str = "mpz-ot/ferret/sender";
+
+/// The serialized form of the sender's extension state.
+#[derive(Serialize, Deserialize)]
+struct SavedState<'a> {
+    delta: Block,
+    counter: usize,
+    lpn_type: LpnType,
+    lpn_parameters: LpnParameters,
+    lpn_seed: Block,
+    v: Cow<'a, [Block]>,
 }
 
 /// The sender's state.
@@ -126,13 +218,11 @@ pub mod state {
     /// In this state the sender performs Ferret extension (potentially multiple times).
     pub struct Extension {
         /// Sender's global secret.
-        #[allow(dead_code)]
         pub(super) delta: Block,
         /// Current Ferret counter.
         pub(super) counter: usize,
 
         /// Lpn type.
-        #[allow(dead_code)]
         pub(super) lpn_type: LpnType,
         /// Lpn parameters.
         pub(super) lpn_parameters: LpnParameters,
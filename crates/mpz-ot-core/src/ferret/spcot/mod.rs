@@ -87,4 +87,81 @@ mod tests {
                 vs == ws
             }));
     }
+
+    #[test]
+    fn spcot_finish_test() {
+        let mut ideal_cot = IdealCOT::default();
+        let delta = ideal_cot.delta();
+
+        let mut sender = SpcotSender::new().setup(delta, Prg::new().random_block());
+        let mut receiver = SpcotReceiver::new().setup();
+
+        let (h, alpha) = (8, 3);
+        let (msg_for_sender, msg_for_receiver) = ideal_cot.random_correlated(h);
+
+        let maskbits = receiver
+            .extend_mask_bits(h, alpha, &msg_for_receiver.choices)
+            .unwrap();
+        let extend = sender.extend(h, &msg_for_sender.msgs, maskbits).unwrap();
+        receiver
+            .extend(h, alpha, &msg_for_receiver.msgs, extend)
+            .unwrap();
+
+        let mut output_sender = sender.finish().unwrap();
+        let output_receiver = receiver.finish().unwrap();
+
+        assert!(output_sender
+            .iter_mut()
+            .zip(output_receiver.iter())
+            .all(|(vs, (ws, alpha))| {
+                vs[*alpha as usize] ^= delta;
+                vs == ws
+            }));
+
+        // The outputs can only be taken once.
+        assert!(sender.finish().is_err());
+        assert!(receiver.finish().is_err());
+    }
+
+    #[test]
+    fn spcot_batched_masks_test() {
+        let mut ideal_cot = IdealCOT::default();
+        let delta = ideal_cot.delta();
+
+        let mut sender = SpcotSender::new().setup(delta, Prg::new().random_block());
+        let mut receiver = SpcotReceiver::new().setup();
+
+        // The mask bits of all trees are computed before any tree is extended.
+        let trees = [(8, 3), (4, 2)];
+        let cots: Vec<_> = trees
+            .iter()
+            .map(|&(h, _)| ideal_cot.random_correlated(h))
+            .collect();
+        let masks: Vec<_> = trees
+            .iter()
+            .zip(&cots)
+            .map(|(&(h, alpha), (_, msg_for_receiver))| {
+                receiver
+                    .extend_mask_bits(h, alpha, &msg_for_receiver.choices)
+                    .unwrap()
+            })
+            .collect();
+
+        for ((&(h, alpha), (msg_for_sender, msg_for_receiver)), maskbits) in
+            trees.iter().zip(&cots).zip(masks)
+        {
+            let extend = sender.extend(h, &msg_for_sender.msgs, maskbits).unwrap();
+            receiver
+                .extend(h, alpha, &msg_for_receiver.msgs, extend)
+                .unwrap();
+        }
+
+        let (msg_for_sender, msg_for_receiver) = ideal_cot.random_correlated(CSP);
+        let check_from_receiver = receiver.check_pre(&msg_for_receiver.choices).unwrap();
+        let (_, check) = sender
+            .check(&msg_for_sender.msgs, check_from_receiver)
+            .unwrap();
+
+        receiver.check(&msg_for_receiver.msgs, check).unwrap();
+    }
 }
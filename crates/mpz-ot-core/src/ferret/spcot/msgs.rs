@@ -3,6 +3,8 @@
 use mpz_core::{hash::Hash, Block};
 use serde::{Deserialize, Serialize};
 
+use crate::msgs::deserialize_ots;
+
 /// An SPCOT message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -18,6 +20,7 @@ pub enum Message<CotMsg> {
 /// The mask bits sent by the receiver.
 pub struct MaskBits {
     /// The mask bits sent by the receiver.
+    #[serde(deserialize_with = "deserialize_ots")]
     pub bs: Vec<bool>,
 }
 
@@ -25,6 +28,7 @@ pub struct MaskBits {
 /// The extend messages sent by the sender.
 pub struct ExtendFromSender {
     /// The mask `m0` and `m1`.
+    #[serde(deserialize_with = "deserialize_ots")]
     pub ms: Vec<[Block; 2]>,
     /// The sum of the ggm tree leaves and delta.
     pub sum: Block,
@@ -34,6 +38,7 @@ pub struct ExtendFromSender {
 /// The consistency check message sent from the receiver.
 pub struct CheckFromReceiver {
     /// The `x'` from the receiver.
+    #[serde(deserialize_with = "deserialize_ots")]
     pub x_prime: Vec<bool>,
 }
 
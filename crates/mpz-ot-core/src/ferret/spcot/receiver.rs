@@ -1,4 +1,6 @@
 //! SPCOT receiver
+use std::collections::VecDeque;
+
 use crate::ferret::{spcot::error::ReceiverError, CSP};
 use itybity::ToBits;
 use mpz_core::{
@@ -33,6 +35,7 @@ impl Receiver {
                 unchecked_ws: Vec::default(),
                 chis: Vec::default(),
                 alphas_and_length: Vec::default(),
+                pending_bs: VecDeque::default(),
                 cot_counter: 0,
                 exec_counter: 0,
                 extended: false,
@@ -86,8 +89,9 @@ impl Receiver<state::Extension> {
             .map(|(alpha, &r)| alpha == r)
             .collect();
 
-        // Updates hasher.
-        self.state.hasher.update(&bs.to_bytes());
+        // The mask bits are hashed together with the sender's response, in the same order as the
+        // sender, so that the masks of several trees can be sent before any of them are extended.
+        self.state.pending_bs.push_back(bs.clone());
 
         Ok(MaskBits { bs })
     }
@@ -134,7 +138,14 @@ impl Receiver<state::Extension> {
             ));
         }
 
+        let Some(bs) = self.state.pending_bs.pop_front() else {
+            return Err(ReceiverError::InvalidState(
+                "mask bits must be computed before extension".to_string(),
+            ));
+        };
+
         // Updates hasher
+        self.state.hasher.update(&bs.to_bytes());
         self.state.hasher.update(&ms.to_bytes());
         self.state.hasher.update(&sum.to_bytes());
 
@@ -247,16 +258,37 @@ impl Receiver<state::Extension> {
             return Err(ReceiverError::ConsistencyCheckFailed);
         }
 
+        Ok(self.take_outputs())
+    }
+
+    /// Outputs the resulting COTs without performing the consistency check.
+    ///
+    /// This is only secure against a semi-honest sender, see [`Receiver::check`] otherwise.
+    pub fn finish(&mut self) -> Result<Vec<(Vec<Block>, u32)>, ReceiverError> {
+        if self.state.extended {
+            return Err(ReceiverError::InvalidState(
+                "extension is not allowed".to_string(),
+            ));
+        }
+
+        Ok(self.take_outputs())
+    }
+
+    fn take_outputs(&mut self) -> Vec<(Vec<Block>, u32)> {
         self.state.cot_counter += self.state.unchecked_ws.len();
         self.state.extended = true;
 
+        // Splits off each tree in turn, as draining from the front would be quadratic.
+        let ws = std::mem::take(&mut self.state.unchecked_ws);
+        let mut rest = ws.as_slice();
         let mut res = Vec::new();
         for (alpha, n) in &self.state.alphas_and_length {
-            let tmp: Vec<Block> = self.state.unchecked_ws.drain(..*n as usize).collect();
-            res.push((tmp, *alpha));
+            let (tree, tail) = rest.split_at(*n as usize);
+            res.push((tree.to_vec(), *alpha));
+            rest = tail;
         }
 
-        Ok(res)
+        res
     }
 }
 
@@ -292,6 +324,8 @@ pub mod state {
         pub(super) chis: Vec<Block>,
         /// Stores the alpha and the length in each extend phase.
         pub(super) alphas_and_length: Vec<(u32, u32)>,
+        /// Mask bits which are not yet hashed, in the order they were computed.
+        pub(super) pending_bs: VecDeque<Vec<bool>>,
 
         /// Current COT counter
         pub(super) cot_counter: usize,
@@ -185,17 +185,38 @@ impl Sender<state::Extension> {
         // Computes H'(V)
         let hashed_v = Hash::from(blake3(&v.to_bytes()));
 
+        Ok((self.take_outputs(), CheckFromSender { hashed_v }))
+    }
+
+    /// Outputs the resulting COTs without performing the consistency check.
+    ///
+    /// This is only secure against a semi-honest receiver, see [`Sender::check`] otherwise.
+    pub fn finish(&mut self) -> Result<Vec<Vec<Block>>, SenderError> {
+        if self.state.extended {
+            return Err(SenderError::InvalidState(
+                "extension is not allowed".to_string(),
+            ));
+        }
+
+        Ok(self.take_outputs())
+    }
+
+    fn take_outputs(&mut self) -> Vec<Vec<Block>> {
         self.state.cot_counter += self.state.unchecked_vs.len();
 
+        // Splits off each tree in turn, as draining from the front would be quadratic.
+        let vs = std::mem::take(&mut self.state.unchecked_vs);
+        let mut rest = vs.as_slice();
         let mut res = Vec::new();
         for n in &self.state.vs_length {
-            let tmp: Vec<Block> = self.state.unchecked_vs.drain(..*n as usize).collect();
-            res.push(tmp);
+            let (tree, tail) = rest.split_at(*n as usize);
+            res.push(tree.to_vec());
+            rest = tail;
         }
 
         self.state.extended = true;
 
-        Ok((res, CheckFromSender { hashed_v }))
+        res
     }
 }
 
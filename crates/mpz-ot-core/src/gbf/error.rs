@@ -0,0 +1,13 @@
+/// Errors that can occur when using a garbled Bloom filter.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum GbfError {
+    #[error("invalid hash count, must be between 1 and {max}: {actual}")]
+    InvalidHashCount { max: usize, actual: usize },
+    #[error("filter is too large: max {max}, got {actual}")]
+    TooLarge { max: usize, actual: usize },
+    #[error("failed to insert element, all of its slots are taken")]
+    InsertionFailed,
+    #[error("incorrect number of slots: expected {expected}, got {actual}")]
+    IncorrectSlotCount { expected: usize, actual: usize },
+}
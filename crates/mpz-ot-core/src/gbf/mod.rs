@@ -0,0 +1,345 @@
+//! An implementation of the [`DCW13`](https://eprint.iacr.org/2013/515) garbled Bloom filter.
+//!
+//! A garbled Bloom filter encodes a set of blocks into a vector of slots, such that XORing the
+//! slots an element hashes to yields the element itself if, and with overwhelming probability
+//! only if, the element is in the set. All other slots are random.
+//!
+//! A receiver can privately query the set of a sender by obliviously receiving the slots which
+//! are set in a plain [`BloomFilter`] of its queries, and random blocks for all other slots. The
+//! XOR of the received slots recovers a query only if it is in the sender's set.
+
+mod error;
+pub mod msgs;
+
+pub use error::GbfError;
+
+use mpz_core::{aes::AesEncryptor, Block};
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::msgs::MAX_OT_COUNT;
+
+/// Default number of hash functions, which bounds the probability that inserting an element
+/// fails by `2^-40`.
+pub const DEFAULT_HASH_COUNT: usize = 40;
+/// Maximum number of hash functions.
+pub const MAX_HASH_COUNT: usize = 128;
+
+/// Garbled Bloom filter parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedGbfParams")]
+pub struct GbfParams {
+    seed: Block,
+    hash_count: usize,
+    size: usize,
+}
+
+impl GbfParams {
+    /// Creates new parameters for a filter which holds up to `set_size` elements.
+    ///
+    /// The filter is sized for a false positive rate of `2^-hash_count` in a plain Bloom filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the hash functions.
+    /// * `hash_count` - The number of hash functions.
+    /// * `set_size` - The maximum number of elements in the filter.
+    pub fn new(seed: Block, hash_count: usize, set_size: usize) -> Result<Self, GbfError> {
+        // The optimal size of a Bloom filter is `n * k * log2(e)`.
+        let size =
+            (set_size.max(1) as f64 * hash_count as f64 * std::f64::consts::LOG2_E).ceil() as usize;
+
+        Self::with_size(seed, hash_count, size)
+    }
+
+    fn with_size(seed: Block, hash_count: usize, size: usize) -> Result<Self, GbfError> {
+        if hash_count == 0 || hash_count > MAX_HASH_COUNT {
+            return Err(GbfError::InvalidHashCount {
+                max: MAX_HASH_COUNT,
+                actual: hash_count,
+            });
+        }
+
+        // Every slot is obliviously transferred, so the size is bounded accordingly.
+        if size > MAX_OT_COUNT {
+            return Err(GbfError::TooLarge {
+                max: MAX_OT_COUNT,
+                actual: size,
+            });
+        }
+
+        Ok(Self {
+            seed,
+            hash_count,
+            size: size.max(hash_count),
+        })
+    }
+
+    /// Returns the seed of the hash functions.
+    pub fn seed(&self) -> Block {
+        self.seed
+    }
+
+    /// Returns the number of hash functions.
+    pub fn hash_count(&self) -> usize {
+        self.hash_count
+    }
+
+    /// Returns the number of slots in the filter.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn hasher(&self) -> Hasher {
+        Hasher {
+            aes: AesEncryptor::new(self.seed),
+            hash_count: self.hash_count,
+            size: self.size,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UncheckedGbfParams {
+    seed: Block,
+    hash_count: usize,
+    size: usize,
+}
+
+impl TryFrom<UncheckedGbfParams> for GbfParams {
+    type Error = GbfError;
+
+    fn try_from(value: UncheckedGbfParams) -> Result<Self, Self::Error> {
+        GbfParams::with_size(value.seed, value.hash_count, value.size)
+    }
+}
+
+/// Maps elements to the slots of a filter.
+struct Hasher {
+    aes: AesEncryptor,
+    hash_count: usize,
+    size: usize,
+}
+
+impl Hasher {
+    /// Returns the distinct slots which `element` hashes to.
+    fn positions(&self, element: Block) -> Vec<usize> {
+        let digest = self.aes.encrypt_block(element) ^ element;
+
+        let mut positions = Vec::with_capacity(self.hash_count);
+        for i in 0..self.hash_count {
+            let tweak = Block::from((i as u128).to_le_bytes());
+            let hash = self.aes.encrypt_block(digest ^ tweak).to_bytes();
+            let position =
+                (u64::from_le_bytes(hash[..8].try_into().unwrap()) % self.size as u64) as usize;
+
+            // Repeated positions would cancel out when XORing the slots.
+            if !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+
+        positions
+    }
+}
+
+/// A garbled Bloom filter.
+#[derive(Debug, Clone)]
+pub struct GarbledBloomFilter {
+    params: GbfParams,
+    slots: Vec<Block>,
+}
+
+impl GarbledBloomFilter {
+    /// Creates a new garbled Bloom filter containing `elements`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - The random number generator used to fill the slots.
+    /// * `params` - The filter parameters.
+    /// * `elements` - The elements of the set.
+    pub fn new<R: Rng + CryptoRng + ?Sized>(
+        rng: &mut R,
+        params: GbfParams,
+        elements: &[Block],
+    ) -> Result<Self, GbfError> {
+        let hasher = params.hasher();
+        let mut slots: Vec<Option<Block>> = vec![None; params.size];
+
+        for element in elements {
+            let mut empty_slot = None;
+            let mut share = *element;
+            for position in hasher.positions(*element) {
+                let current = slots[position];
+                let slot = match current {
+                    Some(slot) => slot,
+                    // The first empty slot holds the final share, the rest are random.
+                    None if empty_slot.is_none() => {
+                        empty_slot = Some(position);
+                        continue;
+                    }
+                    None => *slots[position].insert(Block::random(rng)),
+                };
+
+                share ^= slot;
+            }
+
+            let empty_slot = empty_slot.ok_or(GbfError::InsertionFailed)?;
+            slots[empty_slot] = Some(share);
+        }
+
+        let slots = slots
+            .into_iter()
+            .map(|slot| slot.unwrap_or_else(|| Block::random(rng)))
+            .collect();
+
+        Ok(Self { params, slots })
+    }
+
+    /// Returns the filter parameters.
+    pub fn params(&self) -> &GbfParams {
+        &self.params
+    }
+
+    /// Returns the slots of the filter.
+    pub fn slots(&self) -> &[Block] {
+        &self.slots
+    }
+
+    /// Returns whether `element` is in the filter.
+    pub fn contains(&self, element: Block) -> bool {
+        recover(&self.params.hasher(), &self.slots, element) == element
+    }
+}
+
+/// A plain Bloom filter.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    params: GbfParams,
+    bits: Vec<bool>,
+}
+
+impl BloomFilter {
+    /// Creates a new Bloom filter containing `elements`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The filter parameters.
+    /// * `elements` - The elements of the set.
+    pub fn new(params: GbfParams, elements: &[Block]) -> Self {
+        let hasher = params.hasher();
+        let mut bits = vec![false; params.size];
+
+        for element in elements {
+            for position in hasher.positions(*element) {
+                bits[position] = true;
+            }
+        }
+
+        Self { params, bits }
+    }
+
+    /// Returns the filter parameters.
+    pub fn params(&self) -> &GbfParams {
+        &self.params
+    }
+
+    /// Returns the bits of the filter.
+    pub fn bits(&self) -> &[bool] {
+        &self.bits
+    }
+}
+
+/// Returns whether each query is in the set encoded by the slots of a garbled Bloom filter.
+///
+/// The slots must be those of a garbled Bloom filter wherever the [`BloomFilter`] of the queries
+/// is set. All other slots are ignored.
+///
+/// # Arguments
+///
+/// * `params` - The filter parameters.
+/// * `slots` - The received slots.
+/// * `queries` - The queried elements.
+pub fn query(
+    params: &GbfParams,
+    slots: &[Block],
+    queries: &[Block],
+) -> Result<Vec<bool>, GbfError> {
+    if slots.len() != params.size {
+        return Err(GbfError::IncorrectSlotCount {
+            expected: params.size,
+            actual: slots.len(),
+        });
+    }
+
+    let hasher = params.hasher();
+
+    Ok(queries
+        .iter()
+        .map(|query| recover(&hasher, slots, *query) == *query)
+        .collect())
+}
+
+/// XORs the slots which `element` hashes to.
+fn recover(hasher: &Hasher, slots: &[Block], element: Block) -> Block {
+    hasher
+        .positions(element)
+        .into_iter()
+        .fold(Block::ZERO, |acc, position| acc ^ slots[position])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    #[test]
+    fn test_gbf() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let set = Block::random_vec(&mut rng, 100);
+        let params = GbfParams::new(rng.gen(), DEFAULT_HASH_COUNT, set.len()).unwrap();
+
+        let gbf = GarbledBloomFilter::new(&mut rng, params, &set).unwrap();
+
+        assert_eq!(gbf.slots().len(), params.size());
+        assert!(set.iter().all(|element| gbf.contains(*element)));
+        assert!(!gbf.contains(rng.gen()));
+    }
+
+    #[test]
+    fn test_gbf_query() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let set = Block::random_vec(&mut rng, 100);
+        let queries = vec![set[0], rng.gen(), set[42], rng.gen()];
+        let params = GbfParams::new(rng.gen(), DEFAULT_HASH_COUNT, set.len()).unwrap();
+
+        let gbf = GarbledBloomFilter::new(&mut rng, params, &set).unwrap();
+        let bf = BloomFilter::new(params, &queries);
+
+        // Only the slots set in the Bloom filter are received.
+        let slots: Vec<Block> = gbf
+            .slots()
+            .iter()
+            .zip(bf.bits())
+            .map(|(slot, bit)| if *bit { *slot } else { rng.gen() })
+            .collect();
+
+        let members = query(&params, &slots, &queries).unwrap();
+
+        assert_eq!(members, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn test_gbf_params_invalid() {
+        assert!(matches!(
+            GbfParams::new(Block::ZERO, 0, 10),
+            Err(GbfError::InvalidHashCount { .. })
+        ));
+        assert!(matches!(
+            GbfParams::new(Block::ZERO, DEFAULT_HASH_COUNT, MAX_OT_COUNT),
+            Err(GbfError::TooLarge { .. })
+        ));
+    }
+}
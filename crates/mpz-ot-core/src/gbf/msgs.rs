@@ -0,0 +1,10 @@
+//! Messages for the garbled Bloom filter membership protocol.
+
+use serde::{Deserialize, Serialize};
+
+/// Message sent by the receiver to announce the number of queries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartQuery {
+    /// The number of queries.
+    pub count: usize,
+}
@@ -1,11 +1,33 @@
+use std::time::Duration;
+
 use derive_builder::Builder;
 
+/// Default timeout for the base OT setup.
+pub const DEFAULT_BASE_OT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default timeout for a single OT extension or consistency check.
+pub const DEFAULT_EXTENSION_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// KOS15 sender configuration.
-#[derive(Debug, Default, Clone, Builder)]
+#[derive(Debug, Clone, Builder)]
 pub struct SenderConfig {
     /// Enables committed sender functionality.
     #[builder(setter(custom), default = "false")]
     sender_commit: bool,
+    /// Enables committed receiver functionality.
+    #[builder(setter(custom), default = "false")]
+    receiver_commit: bool,
+    /// Timeout for the base OT setup.
+    #[builder(default = "DEFAULT_BASE_OT_TIMEOUT")]
+    base_ot_timeout: Duration,
+    /// Timeout for a single OT extension or consistency check.
+    #[builder(default = "DEFAULT_EXTENSION_TIMEOUT")]
+    extension_timeout: Duration,
+}
+
+impl Default for SenderConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
 }
 
 impl SenderConfigBuilder {
@@ -14,6 +36,12 @@ impl SenderConfigBuilder {
         self.sender_commit = Some(true);
         self
     }
+
+    /// Enables committed receiver functionality.
+    pub fn receiver_commit(&mut self) -> &mut Self {
+        self.receiver_commit = Some(true);
+        self
+    }
 }
 
 impl SenderConfig {
@@ -26,14 +54,44 @@ impl SenderConfig {
     pub fn sender_commit(&self) -> bool {
         self.sender_commit
     }
+
+    /// Enables committed receiver functionality.
+    pub fn receiver_commit(&self) -> bool {
+        self.receiver_commit
+    }
+
+    /// Returns the timeout for the base OT setup.
+    pub fn base_ot_timeout(&self) -> Duration {
+        self.base_ot_timeout
+    }
+
+    /// Returns the timeout for a single OT extension or consistency check.
+    pub fn extension_timeout(&self) -> Duration {
+        self.extension_timeout
+    }
 }
 
 /// KOS15 receiver configuration.
-#[derive(Debug, Default, Clone, Builder)]
+#[derive(Debug, Clone, Builder)]
 pub struct ReceiverConfig {
     /// Enables committed sender functionality.
     #[builder(setter(custom), default = "false")]
     sender_commit: bool,
+    /// Enables committed receiver functionality.
+    #[builder(setter(custom), default = "false")]
+    receiver_commit: bool,
+    /// Timeout for the base OT setup.
+    #[builder(default = "DEFAULT_BASE_OT_TIMEOUT")]
+    base_ot_timeout: Duration,
+    /// Timeout for a single OT extension or consistency check.
+    #[builder(default = "DEFAULT_EXTENSION_TIMEOUT")]
+    extension_timeout: Duration,
+}
+
+impl Default for ReceiverConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
 }
 
 impl ReceiverConfigBuilder {
@@ -42,6 +100,12 @@ impl ReceiverConfigBuilder {
         self.sender_commit = Some(true);
         self
     }
+
+    /// Enables committed receiver functionality.
+    pub fn receiver_commit(&mut self) -> &mut Self {
+        self.receiver_commit = Some(true);
+        self
+    }
 }
 
 impl ReceiverConfig {
@@ -54,4 +118,19 @@ impl ReceiverConfig {
     pub fn sender_commit(&self) -> bool {
         self.sender_commit
     }
+
+    /// Enables committed receiver functionality.
+    pub fn receiver_commit(&self) -> bool {
+        self.receiver_commit
+    }
+
+    /// Returns the timeout for the base OT setup.
+    pub fn base_ot_timeout(&self) -> Duration {
+        self.base_ot_timeout
+    }
+
+    /// Returns the timeout for a single OT extension or consistency check.
+    pub fn extension_timeout(&self) -> Duration {
+        self.extension_timeout
+    }
 }
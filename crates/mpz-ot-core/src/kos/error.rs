@@ -18,6 +18,20 @@ pub enum SenderError {
     ConsistencyCheckFailed,
     #[error("not enough OTs are setup: expected {0}, actual {1}")]
     InsufficientSetup(usize, usize),
+    #[error(transparent)]
+    SenderVerifyError(#[from] SenderVerifyError),
+}
+
+/// Errors that can occur during verification of the receiver's choices.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderVerifyError {
+    #[error("tape was not recorded")]
+    TapeNotRecorded,
+    #[error("revealed seeds do not match the base OT")]
+    InconsistentSeeds,
+    #[error("revealed seeds are inconsistent with the extension")]
+    InconsistentChoices,
 }
 
 /// Errors that can occur when using the KOS15 receiver.
@@ -36,6 +50,8 @@ pub enum ReceiverError {
     InsufficientSetup(usize, usize),
     #[error("invalid payload")]
     InvalidPayload(String),
+    #[error("receiver is not committed to its choices")]
+    NotCommitted,
     #[error(transparent)]
     ReceiverVerifyError(#[from] ReceiverVerifyError),
 }
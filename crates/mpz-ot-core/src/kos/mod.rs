@@ -8,9 +8,11 @@ mod sender;
 
 pub use config::{
     ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, SenderConfig,
-    SenderConfigBuilder, SenderConfigBuilderError,
+    SenderConfigBuilder, SenderConfigBuilderError, DEFAULT_BASE_OT_TIMEOUT,
+    DEFAULT_EXTENSION_TIMEOUT,
 };
-pub use error::{ReceiverError, ReceiverVerifyError, SenderError};
+pub use error::{ReceiverError, ReceiverVerifyError, SenderError, SenderVerifyError};
+use mpz_core::Block;
 use rand_chacha::ChaCha20Rng;
 use rand_core::SeedableRng;
 pub use receiver::{state as receiver_state, PayloadRecord, Receiver, ReceiverKeys};
@@ -33,10 +35,27 @@ pub(crate) type Aes128Ctr = ctr::Ctr64LE<aes::Aes128>;
 pub fn pad_ot_count(mut count: usize) -> usize {
     // Add OTs for the KOS extension check.
     count += CSP + SSP;
-    // Round up the OTs to extend to the nearest multiple of 64 (matrix transpose optimization).
+    round_ot_count(count)
+}
+
+/// Rounds up the number of OTs to the nearest multiple of 64 (matrix transpose optimization).
+///
+/// Unlike [`pad_ot_count`] this does not add the OTs sacrificed by the KOS extension check, which
+/// is useful when multiple extensions share a single deferred check.
+pub fn round_ot_count(count: usize) -> usize {
     (count + 63) & !63
 }
 
+/// Stretches a Block-sized seed to a 32-byte rng seed.
+pub(crate) fn stretch_seed(seed: Block) -> RngSeed {
+    let mut seed_ = RngSeed::default();
+    seed_
+        .iter_mut()
+        .zip(seed.to_bytes().into_iter().cycle())
+        .for_each(|(s, c)| *s = c);
+    seed_
+}
+
 /// Returns the size in bytes of the extension matrix for a given number of OTs.
 pub fn extension_matrix_size(count: usize) -> usize {
     count * CSP / 8
@@ -48,7 +67,8 @@ mod tests {
     use itybity::ToBits;
     use rstest::*;
 
-    use mpz_core::Block;
+    use mpz_core::{transcript::Transcript, Block};
+    use msgs::{Extend, StartExtend};
 
     use rand::Rng;
     use rand_chacha::ChaCha12Rng;
@@ -180,6 +200,86 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[derive(Debug, Clone, Copy)]
+    enum Tamper {
+        None,
+        HeldSeed,
+        UnheldSeed,
+    }
+
+    #[rstest]
+    #[case::honest(Tamper::None)]
+    #[case::held_seed(Tamper::HeldSeed)]
+    #[case::unheld_seed(Tamper::UnheldSeed)]
+    fn test_kos_committed_receiver(
+        #[case] tamper: Tamper,
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+        choices: Vec<bool>,
+        data: Vec<[Block; 2]>,
+    ) {
+        let sender = Sender::new(SenderConfig::builder().receiver_commit().build().unwrap());
+        let receiver = Receiver::new(ReceiverConfig::builder().receiver_commit().build().unwrap());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        // Extend in two steps, so the choices span both extensions.
+        let receiver_setup = receiver.extend(choices.len() + 64).unwrap();
+        sender.extend(choices.len() + 64, receiver_setup).unwrap();
+        let receiver_setup = receiver.extend(256).unwrap();
+        sender.extend(256, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        // A derandomized transfer.
+        let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+        let mut sender_keys = sender.keys(data.len()).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+
+        // A random transfer.
+        let (random_choices, _) = receiver.keys(64).unwrap().take_choices_and_keys();
+        sender.keys(64).unwrap();
+
+        let mut reveal = receiver.reveal_choices().unwrap();
+        assert!(matches!(
+            receiver.reveal_choices(),
+            Err(ReceiverError::NotCommitted)
+        ));
+
+        let held = delta.iter_lsb0().next().unwrap() as usize;
+        match tamper {
+            Tamper::None => {}
+            Tamper::HeldSeed => reveal.seeds[0][held] = Block::ZERO,
+            Tamper::UnheldSeed => reveal.seeds[0][1 - held] = Block::ZERO,
+        }
+
+        let result = sender.verify_choices(reveal);
+
+        match tamper {
+            Tamper::None => {
+                let expected: Vec<bool> = choices.into_iter().chain(random_choices).collect();
+                assert_eq!(result.unwrap(), expected);
+            }
+            Tamper::HeldSeed => assert!(matches!(
+                result,
+                Err(SenderError::SenderVerifyError(
+                    SenderVerifyError::InconsistentSeeds
+                ))
+            )),
+            Tamper::UnheldSeed => assert!(matches!(
+                result,
+                Err(SenderError::SenderVerifyError(
+                    SenderVerifyError::InconsistentChoices
+                ))
+            )),
+        }
+    }
+
     #[rstest]
     fn test_kos_extension_stream_extends(
         delta: Block,
@@ -382,4 +482,122 @@ mod tests {
             ReceiverError::ReceiverVerifyError(ReceiverVerifyError::InconsistentPayload)
         ));
     }
+
+    #[rstest]
+    fn test_kos_transcript(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+        choices: Vec<bool>,
+        data: Vec<[Block; 2]>,
+        expected: Vec<Block>,
+    ) {
+        let mut transcript = Transcript::open(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/transcripts"),
+            "kos",
+            env!("CARGO_PKG_VERSION"),
+        );
+
+        let sender = Sender::new(SenderConfig::default());
+        let receiver = Receiver::new(ReceiverConfig::default());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let count = choices.len() + 256;
+        let StartExtend { count } = transcript.exchange("start_extend", StartExtend { count });
+
+        // The receiver streams the extension in chunks, as it does on the wire.
+        let us = receiver
+            .extend(count)
+            .unwrap()
+            .into_chunks(1024)
+            .flat_map(|chunk| transcript.exchange("extend", chunk).us)
+            .collect();
+        sender.extend(count, Extend { us }).unwrap();
+
+        let receiver_check = transcript.exchange("check", receiver.check(chi_seed).unwrap());
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+        let derandomize =
+            transcript.exchange("derandomize", receiver_keys.derandomize(&choices).unwrap());
+
+        let mut sender_keys = sender.keys(data.len()).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+        let payload = transcript.exchange("payload", sender_keys.encrypt_blocks(&data).unwrap());
+
+        let received = receiver_keys.decrypt_blocks(payload).unwrap();
+
+        assert_eq!(received, expected);
+
+        transcript.finish();
+    }
+
+    #[rstest]
+    fn test_kos_extension_verify_batch(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+        choices: Vec<bool>,
+        data: Vec<[Block; 2]>,
+    ) {
+        let sender = Sender::new(SenderConfig::default());
+        let receiver = Receiver::new(ReceiverConfig::builder().sender_commit().build().unwrap());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let receiver_setup = receiver.extend(4 * choices.len() + 256).unwrap();
+        sender.extend(4 * data.len() + 256, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+            let derandomize = receiver_keys.derandomize(&choices).unwrap();
+
+            let mut sender_keys = sender.keys(data.len()).unwrap();
+            sender_keys.derandomize(derandomize).unwrap();
+            let payload = sender_keys.encrypt_blocks(&data).unwrap();
+
+            ids.push(payload.id);
+            receiver_keys.decrypt_blocks(payload).unwrap();
+        }
+
+        let receiver = receiver.start_verification(delta).unwrap();
+
+        let err = receiver.remove_records(&[ids[0], ids[0]]).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiverError::ReceiverVerifyError(ReceiverVerifyError::InvalidTransferId(_))
+        ));
+
+        let records = receiver.remove_records(&ids[..2]).unwrap();
+        let batch = records
+            .into_iter()
+            .map(|record| (record, data.clone()))
+            .collect();
+
+        PayloadRecord::verify_batch(batch).unwrap();
+
+        assert!(receiver.remove_record(ids[0]).is_err());
+
+        // A single inconsistent transfer fails the whole batch.
+        let mut tampered = data.clone();
+        tampered[0][1] ^= Block::ONE;
+
+        let records = receiver.remove_records(&ids[2..]).unwrap();
+        let batch = records.into_iter().zip([data.clone(), tampered]).collect();
+
+        let err = PayloadRecord::verify_batch(batch).unwrap_err();
+        assert!(matches!(
+            err,
+            ReceiverError::ReceiverVerifyError(ReceiverVerifyError::InconsistentPayload)
+        ));
+    }
 }
@@ -3,7 +3,10 @@
 use mpz_core::Block;
 use serde::{Deserialize, Serialize};
 
-use crate::TransferId;
+use crate::{
+    msgs::{deserialize_blocks, deserialize_bytes},
+    TransferId,
+};
 
 /// Extension message sent by the receiver to agree upon the number of OTs to set up.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -16,6 +19,7 @@ pub struct StartExtend {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Extend {
     /// The receiver's extension vectors.
+    #[serde(deserialize_with = "deserialize_bytes")]
     pub us: Vec<u8>,
 }
 
@@ -58,6 +62,13 @@ pub struct Check {
     pub t1: Block,
 }
 
+/// The receiver's base OT seeds, revealed to the sender to open its choices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiverReveal {
+    /// The receiver's base OT seeds.
+    pub seeds: Vec<[Block; 2]>,
+}
+
 /// Sender payload message.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SenderPayload {
@@ -73,13 +84,16 @@ pub enum Ciphertexts {
     /// Messages encrypted with XOR
     Blocks {
         /// Sender's ciphertexts
+        #[serde(deserialize_with = "deserialize_blocks")]
         ciphertexts: Vec<Block>,
     },
     /// Messages encrypted with stream cipher
     Bytes {
         /// Sender's ciphertexts
+        #[serde(deserialize_with = "deserialize_bytes")]
         ciphertexts: Vec<u8>,
         /// The IV used for encryption.
+        #[serde(deserialize_with = "deserialize_bytes")]
         iv: Vec<u8>,
         /// The length of each message in bytes.
         length: u32,
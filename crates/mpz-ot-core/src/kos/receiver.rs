@@ -1,13 +1,13 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
 use crate::{
     kos::{
         error::ReceiverVerifyError,
-        msgs::{Check, Ciphertexts, Extend, SenderPayload},
-        Aes128Ctr, ReceiverConfig, ReceiverError, Rng, RngSeed, CSP, SSP,
+        msgs::{Check, Ciphertexts, Extend, ReceiverReveal, SenderPayload},
+        stretch_seed, Aes128Ctr, ReceiverConfig, ReceiverError, Rng, RngSeed, CSP, SSP,
     },
     msgs::Derandomize,
     TransferId,
@@ -74,23 +74,16 @@ impl Receiver {
     pub fn setup(self, seeds: [[Block; 2]; CSP]) -> Receiver<state::Extension> {
         let rngs = seeds
             .iter()
-            .map(|seeds| {
-                seeds.map(|seed| {
-                    // Stretch the Block-sized seed to a 32-byte seed.
-                    let mut seed_ = RngSeed::default();
-                    seed_
-                        .iter_mut()
-                        .zip(seed.to_bytes().into_iter().cycle())
-                        .for_each(|(s, c)| *s = c);
-                    Rng::from_seed(seed_)
-                })
-            })
+            .map(|seeds| seeds.map(|seed| Rng::from_seed(stretch_seed(seed))))
             .collect();
 
+        let seeds = self.config.receiver_commit().then_some(seeds);
+
         Receiver {
             config: self.config,
             state: state::Extension {
                 rngs,
+                seeds,
                 ts: Vec::default(),
                 keys: Vec::default(),
                 choices: Vec::default(),
@@ -347,6 +340,29 @@ impl Receiver<state::Extension> {
         })
     }
 
+    /// Reveals the receiver's choices to the sender by opening its base OT seeds.
+    ///
+    /// Revealing the seeds opens the choices of every extended OT, so any remaining OTs are
+    /// discarded and no further OTs can be extended.
+    ///
+    /// See [`Sender::verify_choices`](crate::kos::Sender::verify_choices).
+    pub fn reveal_choices(&mut self) -> Result<ReceiverReveal, ReceiverError> {
+        let Some(seeds) = self.state.seeds.take() else {
+            return Err(ReceiverError::NotCommitted);
+        };
+
+        self.state.keys.clear();
+        self.state.choices.clear();
+        self.state.ts.clear();
+        self.state.unchecked_ts.clear();
+        self.state.unchecked_choices.clear();
+        self.state.extended = true;
+
+        Ok(ReceiverReveal {
+            seeds: seeds.to_vec(),
+        })
+    }
+
     /// Enters the verification state for verifiable OT.
     ///
     /// # ⚠️ Warning ⚠️
@@ -408,6 +424,51 @@ impl Receiver<state::Verify> {
             ciphertext_digest,
         })
     }
+
+    /// Removes the [`PayloadRecord`]s for the given transfer ids.
+    ///
+    /// The records are returned in the order of the ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the records does not exist, or if an id is repeated. In that
+    /// case no records are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The transfer ids
+    pub fn remove_records(&self, ids: &[TransferId]) -> Result<Vec<PayloadRecord>, ReceiverError> {
+        let mut tape = self.state.tape.lock().unwrap();
+
+        let mut seen = HashSet::with_capacity(ids.len());
+        for id in ids {
+            if !seen.insert(*id) || !tape.records.contains_key(id) {
+                return Err(ReceiverVerifyError::InvalidTransferId(*id))?;
+            }
+        }
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                let PayloadRecordNoDelta {
+                    index,
+                    choices,
+                    ts,
+                    keys,
+                    ciphertext_digest,
+                } = tape.records.remove(id).expect("record should be present");
+
+                PayloadRecord {
+                    index,
+                    choices,
+                    ts,
+                    keys,
+                    delta: self.state.delta,
+                    ciphertext_digest,
+                }
+            })
+            .collect())
+    }
 }
 
 /// KOS receiver's keys for a single transfer.
@@ -626,47 +687,80 @@ impl PayloadRecord {
     ///
     /// * `purported_msgs` - The purported messages sent by the sender.
     pub fn verify(self, purported_msgs: &[[Block; 2]]) -> Result<(), ReceiverError> {
-        let PayloadRecord {
-            index: counter,
-            choices,
-            ts,
-            keys,
-            delta,
-            ciphertext_digest,
-        } = self;
+        if self.ciphertext_digest != self.digest(purported_msgs) {
+            return Err(ReceiverVerifyError::InconsistentPayload)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the purported messages of many transfers against their records.
+    ///
+    /// The ciphertext digests of all transfers are recomputed in a single pass and checked
+    /// against the recorded digests at once, so the check does not reveal which transfer is
+    /// inconsistent.
+    ///
+    /// With the `rayon` feature enabled, the digests are computed in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The records and the purported messages sent by the sender.
+    pub fn verify_batch(batch: Vec<(PayloadRecord, Vec<[Block; 2]>)>) -> Result<(), ReceiverError> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = batch.into_par_iter();
+            } else {
+                let iter = batch.into_iter();
+            }
+        }
+
+        let digests: Vec<([u8; 32], [u8; 32])> = iter
+            .map(|(record, msgs)| (record.ciphertext_digest, record.digest(&msgs)))
+            .collect();
+
+        let mut expected = Hasher::default();
+        let mut actual = Hasher::default();
+        for (expected_digest, actual_digest) in digests {
+            expected.update(&expected_digest);
+            actual.update(&actual_digest);
+        }
+
+        if expected.finalize() != actual.finalize() {
+            return Err(ReceiverVerifyError::InconsistentPayload)?;
+        }
 
+        Ok(())
+    }
+
+    /// Computes the ciphertext digest of the purported messages.
+    fn digest(&self, purported_msgs: &[[Block; 2]]) -> [u8; 32] {
         // Here we compute the complementary key to the one used earlier in the protocol.
         //
-        // From this, we encrypt the purported messages and check that the ciphertext digests match.
+        // From this, we encrypt the purported messages and compute the ciphertext digest.
         let cipher = &(*FIXED_KEY_AES);
         let mut hasher = Hasher::default();
-        for (j, (((c, t), key), msgs)) in choices
+        for (j, (((c, t), key), msgs)) in self
+            .choices
             .iter_lsb0()
-            .zip(ts)
-            .zip(keys)
+            .zip(&self.ts)
+            .zip(&self.keys)
             .zip(purported_msgs)
             .enumerate()
         {
-            let j = Block::new(((counter + j) as u128).to_be_bytes());
-            let key_ = cipher.tccr(j, t ^ delta);
+            let j = Block::new(((self.index + j) as u128).to_be_bytes());
+            let key_ = cipher.tccr(j, *t ^ self.delta);
 
             let (ct0, ct1) = if c {
-                (msgs[0] ^ key_, msgs[1] ^ key)
+                (msgs[0] ^ key_, msgs[1] ^ *key)
             } else {
-                (msgs[0] ^ key, msgs[1] ^ key_)
+                (msgs[0] ^ *key, msgs[1] ^ key_)
             };
 
             hasher.update(&ct0.to_bytes());
             hasher.update(&ct1.to_bytes());
         }
 
-        let digest: [u8; 32] = hasher.finalize().into();
-
-        if ciphertext_digest != digest {
-            return Err(ReceiverVerifyError::InconsistentPayload)?;
-        }
-
-        Ok(())
+        hasher.finalize().into()
     }
 }
 
@@ -703,6 +797,8 @@ pub mod state {
     pub struct Extension {
         /// Receiver's rngs
         pub(super) rngs: Vec<[ChaCha20Rng; 2]>,
+        /// Receiver's base OT seeds, if the receiver is committed to its choices
+        pub(super) seeds: Option<[[Block; 2]; CSP]>,
         /// Receiver's ts
         pub(super) ts: Vec<Block>,
         /// Receiver's keys
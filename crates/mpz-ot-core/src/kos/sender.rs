@@ -1,8 +1,14 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
 use crate::{
     kos::{
         extension_matrix_size,
-        msgs::{Check, Ciphertexts, Extend, SenderPayload},
-        Aes128Ctr, Rng, RngSeed, SenderConfig, SenderError, CSP, SSP,
+        msgs::{Check, Ciphertexts, Extend, ReceiverReveal, SenderPayload},
+        stretch_seed, Aes128Ctr, Rng, RngSeed, SenderConfig, SenderError, SenderVerifyError, CSP,
+        SSP,
     },
     msgs::Derandomize,
     TransferId,
@@ -25,6 +31,27 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Record of the receiver's messages, used to verify its choices.
+#[derive(Debug)]
+struct Tape {
+    /// The seeds received during base OT.
+    seeds: [Block; CSP],
+    /// The receiver's extension vectors, one for each extension.
+    us: Vec<Vec<u8>>,
+    /// The transfers which have been performed.
+    transfers: BTreeMap<TransferId, TransferRecord>,
+}
+
+#[derive(Debug)]
+struct TransferRecord {
+    /// The starting index of the OTs of the transfer.
+    index: usize,
+    /// The number of OTs in the transfer.
+    count: usize,
+    /// The receiver's correction bits, if the transfer was derandomized.
+    flip: Option<Vec<u8>>,
+}
+
 /// KOS15 sender.
 #[derive(Debug, Default)]
 pub struct Sender<T: state::State = state::Initialized> {
@@ -64,17 +91,17 @@ impl Sender {
     pub fn setup(self, delta: Block, seeds: [Block; CSP]) -> Sender<state::Extension> {
         let rngs = seeds
             .iter()
-            .map(|seed| {
-                // Stretch the Block-sized seed to a 32-byte seed.
-                let mut seed_ = RngSeed::default();
-                seed_
-                    .iter_mut()
-                    .zip(seed.to_bytes().into_iter().cycle())
-                    .for_each(|(s, c)| *s = c);
-                Rng::from_seed(seed_)
-            })
+            .map(|seed| Rng::from_seed(stretch_seed(*seed)))
             .collect();
 
+        let tape = self.config.receiver_commit().then(|| {
+            Arc::new(Mutex::new(Tape {
+                seeds,
+                us: Vec::default(),
+                transfers: BTreeMap::default(),
+            }))
+        });
+
         Sender {
             config: self.config,
             state: state::Extension {
@@ -85,6 +112,7 @@ impl Sender {
                 counter: 0,
                 extended: false,
                 unchecked_qs: Vec::default(),
+                tape,
             },
         }
     }
@@ -137,6 +165,10 @@ impl Sender<state::Extension> {
             return Err(SenderError::InvalidExtend);
         }
 
+        if let Some(tape) = &self.state.tape {
+            tape.lock().unwrap().us.push(us.clone());
+        }
+
         let mut qs = vec![0u8; NROWS * row_width];
         cfg_if::cfg_if! {
             if #[cfg(feature = "rayon")] {
@@ -296,12 +328,102 @@ impl Sender<state::Extension> {
 
         let id = self.state.transfer_id.next();
 
+        if let Some(tape) = &self.state.tape {
+            tape.lock().unwrap().transfers.insert(
+                id,
+                TransferRecord {
+                    index: self.state.counter - self.state.keys.len(),
+                    count,
+                    flip: None,
+                },
+            );
+        }
+
         Ok(SenderKeys {
             id,
             keys: self.state.keys.drain(..count).collect(),
             derandomize: None,
+            tape: self.state.tape.clone(),
         })
     }
+
+    /// Verifies the choices revealed by the receiver, returning the choices of every transfer in
+    /// order.
+    ///
+    /// The receiver opens its choices by revealing both seeds of every base OT. The seeds chosen
+    /// by the sender during base OT must match, and the remaining seeds must yield the same
+    /// choice vector for every row of the extension matrix. As the receiver does not know which
+    /// seeds the sender holds, it can not open different choices without being detected.
+    ///
+    /// # Arguments
+    ///
+    /// * `reveal` - The receiver's revealed seeds.
+    pub fn verify_choices(self, reveal: ReceiverReveal) -> Result<Vec<bool>, SenderError> {
+        let Some(tape) = self.state.tape else {
+            return Err(SenderVerifyError::TapeNotRecorded)?;
+        };
+
+        let mut tape = tape.lock().unwrap();
+        let seeds = tape.seeds;
+        let us = std::mem::take(&mut tape.us);
+        let transfers = std::mem::take(&mut tape.transfers);
+
+        let revealed = reveal.seeds;
+        if revealed.len() != CSP
+            || revealed
+                .iter()
+                .zip(self.state.delta.iter_lsb0())
+                .zip(seeds)
+                .any(|((revealed, b), seed)| revealed[b as usize] != seed)
+        {
+            return Err(SenderVerifyError::InconsistentSeeds)?;
+        }
+
+        let mut rngs: Vec<[Rng; 2]> = revealed
+            .iter()
+            .map(|seeds| seeds.map(|seed| Rng::from_seed(stretch_seed(seed))))
+            .collect();
+
+        // Recompute the choice vector of each extension from every row of the matrix.
+        let mut choices = Vec::new();
+        for us in us {
+            let row_width = us.len() / CSP;
+            let mut x = None;
+            for (rngs, u) in rngs.iter_mut().zip(us.chunks_exact(row_width)) {
+                let mut row = u.to_vec();
+                let mut t = vec![0u8; row_width];
+                for rng in rngs.iter_mut() {
+                    rng.fill_bytes(&mut t);
+                    row.iter_mut().zip(&t).for_each(|(x, t)| *x ^= t);
+                }
+
+                match &x {
+                    None => x = Some(row),
+                    Some(x) if *x != row => {
+                        return Err(SenderVerifyError::InconsistentChoices)?;
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            choices.extend(x.unwrap_or_default().iter_lsb0());
+        }
+
+        // The rows sacrificed for the consistency check are stripped off.
+        choices.truncate(self.state.counter);
+
+        Ok(transfers
+            .into_values()
+            .flat_map(|TransferRecord { index, count, flip }| {
+                let flip = flip.unwrap_or_else(|| vec![0; count.div_ceil(8)]);
+                choices[index..index + count]
+                    .iter()
+                    .zip(flip.iter_lsb0())
+                    .map(|(c, flip)| c ^ flip)
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
 }
 
 /// KOS sender's keys for a single transfer.
@@ -316,6 +438,8 @@ pub struct SenderKeys {
     keys: Vec<[Block; 2]>,
     /// Derandomization
     derandomize: Option<Derandomize>,
+    /// Sender tape
+    tape: Option<Arc<Mutex<Tape>>>,
 }
 
 impl SenderKeys {
@@ -337,6 +461,12 @@ impl SenderKeys {
             ));
         }
 
+        if let Some(tape) = &self.tape {
+            if let Some(record) = tape.lock().unwrap().transfers.get_mut(&self.id) {
+                record.flip = Some(derandomize.flip.clone());
+            }
+        }
+
         self.derandomize = Some(derandomize);
 
         Ok(())
@@ -497,6 +627,9 @@ pub mod state {
 
         /// Sender's unchecked qs
         pub(super) unchecked_qs: Vec<Block>,
+
+        /// Protocol tape
+        pub(super) tape: Option<Arc<Mutex<Tape>>>,
     }
 
     impl State for Extension {}
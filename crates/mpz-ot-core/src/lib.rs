@@ -22,10 +22,15 @@
 use serde::{Deserialize, Serialize};
 
 pub mod chou_orlandi;
+pub mod derandomize;
 pub mod ferret;
+pub mod gbf;
 pub mod ideal;
 pub mod kos;
 pub mod msgs;
+pub mod packed;
+pub mod rerandomize;
+pub mod softspoken;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test;
 
@@ -45,7 +50,8 @@ impl std::fmt::Display for TransferId {
 
 impl TransferId {
     /// Returns the current transfer ID, incrementing `self` in-place.
-    pub(crate) fn next(&mut self) -> Self {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Self {
         let id = *self;
         self.0 += 1;
         id
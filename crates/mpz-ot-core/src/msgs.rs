@@ -1,9 +1,118 @@
 //! General OT message types
+//!
+//! Messages received from a peer are validated during deserialization. Vectors are bounded
+//! before they are allocated, so that a malicious peer can not trigger large allocations by
+//! sending forged length fields.
 
-use serde::{Deserialize, Serialize};
+use std::{fmt, marker::PhantomData};
+
+use mpz_core::Block;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 
 use crate::TransferId;
 
+/// Maximum number of OTs which can be transferred with a single message.
+pub const MAX_OT_COUNT: usize = 1 << 22;
+/// Maximum length in bytes of a vector in a single message.
+pub const MAX_MESSAGE_BYTES: usize = 1 << 28;
+
+/// Maximum number of elements which are allocated up front when deserializing a vector.
+const MAX_PREALLOC: usize = 1 << 16;
+
+/// Errors that can occur when validating a message.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum MessageError {
+    #[error("message contains too many elements: max {max}, got {actual}")]
+    TooLarge { max: usize, actual: usize },
+    #[error("message length does not match count: expected {expected}, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+    #[error("packed bits are set beyond the length")]
+    TrailingBits,
+}
+
+/// Deserializes a vector with at most `max` elements.
+///
+/// The length provided by the deserializer is checked before anything is allocated, and
+/// at most [`MAX_PREALLOC`] elements are allocated up front.
+pub(crate) fn deserialize_bounded<'de, D, T>(
+    deserializer: D,
+    max: usize,
+) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct BoundedVisitor<T> {
+        max: usize,
+        _pd: PhantomData<T>,
+    }
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for BoundedVisitor<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a sequence of at most {} elements", self.max)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let len = seq.size_hint().unwrap_or(0);
+            if len > self.max {
+                return Err(de::Error::custom(MessageError::TooLarge {
+                    max: self.max,
+                    actual: len,
+                }));
+            }
+
+            let mut vec = Vec::with_capacity(len.min(MAX_PREALLOC));
+            while let Some(elem) = seq.next_element()? {
+                if vec.len() == self.max {
+                    return Err(de::Error::custom(MessageError::TooLarge {
+                        max: self.max,
+                        actual: vec.len() + 1,
+                    }));
+                }
+                vec.push(elem);
+            }
+
+            Ok(vec)
+        }
+    }
+
+    deserializer.deserialize_seq(BoundedVisitor {
+        max,
+        _pd: PhantomData,
+    })
+}
+
+/// Deserializes a vector with at most one element per OT.
+pub(crate) fn deserialize_ots<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserialize_bounded(deserializer, MAX_OT_COUNT)
+}
+
+/// Deserializes a vector of blocks with at most [`MAX_MESSAGE_BYTES`] bytes.
+pub(crate) fn deserialize_blocks<'de, D>(deserializer: D) -> Result<Vec<Block>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_bounded(deserializer, MAX_MESSAGE_BYTES / Block::LEN)
+}
+
+/// Deserializes a byte vector with at most [`MAX_MESSAGE_BYTES`] bytes.
+pub(crate) fn deserialize_bytes<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_bounded(deserializer, MAX_MESSAGE_BYTES)
+}
+
 /// A message sent by the receiver which a sender can use to perform
 /// Beaver derandomization.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -21,21 +130,29 @@ pub struct Derandomize {
 struct UncheckedDerandomize {
     id: TransferId,
     count: u32,
+    #[serde(deserialize_with = "deserialize_bytes")]
     flip: Vec<u8>,
 }
 
 impl TryFrom<UncheckedDerandomize> for Derandomize {
-    type Error = std::io::Error;
+    type Error = MessageError;
 
     fn try_from(value: UncheckedDerandomize) -> Result<Self, Self::Error> {
+        if value.count as usize > MAX_OT_COUNT {
+            return Err(MessageError::TooLarge {
+                max: MAX_OT_COUNT,
+                actual: value.count as usize,
+            });
+        }
+
         // Divide by 8, rounding up
         let expected_len = (value.count as usize + 7) / 8;
 
         if value.flip.len() != expected_len {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "flip length does not match count",
-            ));
+            return Err(MessageError::LengthMismatch {
+                expected: expected_len,
+                actual: value.flip.len(),
+            });
         }
 
         Ok(Derandomize {
@@ -46,6 +163,30 @@ impl TryFrom<UncheckedDerandomize> for Derandomize {
     }
 }
 
+/// A message sent by the sender containing its messages encrypted with derandomized random OTs.
+///
+/// See [`derandomize`](crate::derandomize) for details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerandomizePayload {
+    /// Transfer ID of the random OTs.
+    pub id: TransferId,
+    /// Pair of ciphertexts for each OT.
+    #[serde(deserialize_with = "deserialize_blocks")]
+    pub ciphertexts: Vec<Block>,
+}
+
+/// A message sent by the sender to re-randomize a batch of random COTs under a new delta.
+///
+/// See [`rerandomize`](crate::rerandomize) for details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rerandomize {
+    /// Transfer ID of the re-randomized COTs.
+    pub id: TransferId,
+    /// Correction for each COT.
+    #[serde(deserialize_with = "deserialize_blocks")]
+    pub corrections: Vec<Block>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,5 +206,33 @@ mod tests {
             flip: vec![0],
         })
         .is_err());
+
+        assert!(Derandomize::try_from(UncheckedDerandomize {
+            id: TransferId::default(),
+            count: MAX_OT_COUNT as u32 + 1,
+            flip: vec![],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_bounded_vec() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Bounded {
+            #[serde(deserialize_with = "deserialize_bytes")]
+            bytes: Vec<u8>,
+        }
+
+        let msg = bincode::serialize(&Bounded { bytes: vec![42; 8] }).unwrap();
+
+        let Bounded { bytes } = bincode::deserialize(&msg).unwrap();
+        assert_eq!(bytes, vec![42; 8]);
+
+        // Forge the length prefix.
+        let mut forged = msg.clone();
+        forged[..8].copy_from_slice(&(MAX_MESSAGE_BYTES as u64 + 1).to_le_bytes());
+
+        let err = bincode::deserialize::<Bounded>(&forged).unwrap_err();
+        assert!(err.to_string().contains("too many elements"));
     }
 }
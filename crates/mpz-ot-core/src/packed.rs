@@ -0,0 +1,374 @@
+//! Compact representations of OT outputs.
+//!
+//! The memory of large batches of random OTs is dominated by their outputs. The types in this
+//! module store choice bits packed into words, and the messages of many transfers in a single
+//! contiguous allocation.
+
+use std::{collections::BTreeMap, ops::Range};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    msgs::{deserialize_bounded, MessageError, MAX_MESSAGE_BYTES},
+    RCOTReceiverOutput, ROTReceiverOutput, TransferId,
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Bit-packed choice bits.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedPackedChoices")]
+pub struct PackedChoices {
+    len: usize,
+    words: Vec<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UncheckedPackedChoices {
+    len: usize,
+    #[serde(deserialize_with = "deserialize_words")]
+    words: Vec<u64>,
+}
+
+fn deserialize_words<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_bounded(deserializer, MAX_MESSAGE_BYTES / std::mem::size_of::<u64>())
+}
+
+impl TryFrom<UncheckedPackedChoices> for PackedChoices {
+    type Error = MessageError;
+
+    fn try_from(value: UncheckedPackedChoices) -> Result<Self, Self::Error> {
+        let expected_len = value.len.div_ceil(WORD_BITS);
+
+        if value.words.len() != expected_len {
+            return Err(MessageError::LengthMismatch {
+                expected: expected_len,
+                actual: value.words.len(),
+            });
+        }
+
+        // The unused bits of the last word must be zero, otherwise equal choices would compare
+        // unequal.
+        let used = value.len % WORD_BITS;
+        if used != 0 && value.words[expected_len - 1] >> used != 0 {
+            return Err(MessageError::TrailingBits);
+        }
+
+        Ok(PackedChoices {
+            len: value.len,
+            words: value.words,
+        })
+    }
+}
+
+impl PackedChoices {
+    /// Creates an empty set of choice bits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty set of choice bits with capacity for `len` bits.
+    pub fn with_capacity(len: usize) -> Self {
+        Self {
+            len: 0,
+            words: Vec::with_capacity(len.div_ceil(WORD_BITS)),
+        }
+    }
+
+    /// Returns the number of choice bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no choice bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the choice bit at `idx`, or `None` if it is out of bounds.
+    pub fn get(&self, idx: usize) -> Option<bool> {
+        (idx < self.len).then(|| (self.words[idx / WORD_BITS] >> (idx % WORD_BITS)) & 1 == 1)
+    }
+
+    /// Appends a choice bit.
+    pub fn push(&mut self, bit: bool) {
+        if self.len % WORD_BITS == 0 {
+            self.words.push(0);
+        }
+
+        if bit {
+            self.words[self.len / WORD_BITS] |= 1 << (self.len % WORD_BITS);
+        }
+
+        self.len += 1;
+    }
+
+    /// Returns an iterator over the choice bits.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = bool> + '_ {
+        (0..self.len).map(|idx| (self.words[idx / WORD_BITS] >> (idx % WORD_BITS)) & 1 == 1)
+    }
+
+    /// Returns the choice bits as a vector of booleans.
+    ///
+    /// This allocates a byte per choice bit, so it should be avoided for large batches in favor
+    /// of [`PackedChoices::iter`] or [`PackedChoices::get`].
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+}
+
+impl FromIterator<bool> for PackedChoices {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut choices = Self::with_capacity(iter.size_hint().0);
+        iter.for_each(|bit| choices.push(bit));
+        choices
+    }
+}
+
+impl From<&[bool]> for PackedChoices {
+    fn from(bits: &[bool]) -> Self {
+        bits.iter().copied().collect()
+    }
+}
+
+macro_rules! define_packed_output {
+    ($(#[$doc:meta])* $name:ident, $output:ident) => {
+        $(#[$doc])*
+        #[derive(Debug)]
+        pub struct $name<T> {
+            /// The transfer id.
+            pub id: TransferId,
+            /// The choice bits.
+            pub choices: PackedChoices,
+            /// The chosen messages.
+            pub msgs: Vec<T>,
+        }
+
+        impl<T> $name<T> {
+            /// Returns an iterator over the choice bits and chosen messages.
+            pub fn iter(&self) -> impl Iterator<Item = (bool, &T)> + '_ {
+                self.choices.iter().zip(self.msgs.iter())
+            }
+
+            /// Returns the transfer id, the packed choice bits and the chosen messages.
+            ///
+            /// The choice bits are not unpacked, so consumers can read them without allocating a
+            /// byte per bit.
+            pub fn into_parts(self) -> (TransferId, PackedChoices, Vec<T>) {
+                (self.id, self.choices, self.msgs)
+            }
+        }
+
+        impl<T> From<$output<bool, T>> for $name<T> {
+            fn from(output: $output<bool, T>) -> Self {
+                let $output { id, choices, msgs } = output;
+                // The unpacked choices are dropped as soon as they are packed.
+                let choices = PackedChoices::from(choices.as_slice());
+
+                Self { id, choices, msgs }
+            }
+        }
+    };
+}
+
+define_packed_output!(
+    /// A [`RCOTReceiverOutput`] with bit-packed choices.
+    PackedRCOTReceiverOutput,
+    RCOTReceiverOutput
+);
+define_packed_output!(
+    /// A [`ROTReceiverOutput`] with bit-packed choices.
+    PackedROTReceiverOutput,
+    ROTReceiverOutput
+);
+
+/// Contiguous storage for the messages of many transfers.
+///
+/// Storing the outputs of many transfers in one arena avoids an allocation per transfer, and
+/// lets consumers iterate over all messages in order.
+#[derive(Debug, Clone)]
+pub struct MessageArena<T> {
+    msgs: Vec<T>,
+    transfers: BTreeMap<TransferId, Range<usize>>,
+}
+
+impl<T> Default for MessageArena<T> {
+    fn default() -> Self {
+        Self {
+            msgs: Vec::new(),
+            transfers: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> MessageArena<T> {
+    /// Creates an empty arena.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty arena with capacity for `count` messages.
+    pub fn with_capacity(count: usize) -> Self {
+        Self {
+            msgs: Vec::with_capacity(count),
+            transfers: BTreeMap::new(),
+        }
+    }
+
+    /// Appends the messages of a transfer.
+    ///
+    /// Returns `false` if messages of a transfer with the same id are already stored, in which
+    /// case the arena is left unchanged.
+    pub fn push(&mut self, id: TransferId, msgs: impl IntoIterator<Item = T>) -> bool {
+        if self.transfers.contains_key(&id) {
+            return false;
+        }
+
+        let start = self.msgs.len();
+        self.msgs.extend(msgs);
+        self.transfers.insert(id, start..self.msgs.len());
+
+        true
+    }
+
+    /// Returns the messages of a transfer.
+    pub fn get(&self, id: TransferId) -> Option<&[T]> {
+        self.transfers
+            .get(&id)
+            .map(|range| &self.msgs[range.clone()])
+    }
+
+    /// Returns an iterator over the transfers and their messages, ordered by transfer id.
+    pub fn iter(&self) -> impl Iterator<Item = (TransferId, &[T])> + '_ {
+        self.transfers
+            .iter()
+            .map(|(id, range)| (*id, &self.msgs[range.clone()]))
+    }
+
+    /// Returns all stored messages in the order they were pushed.
+    pub fn msgs(&self) -> &[T] {
+        &self.msgs
+    }
+
+    /// Returns the number of stored messages.
+    pub fn len(&self) -> usize {
+        self.msgs.len()
+    }
+
+    /// Returns `true` if no messages are stored.
+    pub fn is_empty(&self) -> bool {
+        self.msgs.is_empty()
+    }
+
+    /// Shrinks the capacity of the arena as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.msgs.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_core::{prg::Prg, Block};
+    use rand::{Rng, SeedableRng};
+    use rstest::*;
+
+    #[rstest]
+    #[case::empty(0)]
+    #[case::partial_word(13)]
+    #[case::full_word(64)]
+    #[case::multiple_words(1000)]
+    fn test_packed_choices(#[case] len: usize) {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let bits: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+
+        let choices = PackedChoices::from(bits.as_slice());
+
+        assert_eq!(choices.len(), len);
+        assert_eq!(choices.to_vec(), bits);
+        assert!(bits
+            .iter()
+            .enumerate()
+            .all(|(idx, bit)| choices.get(idx) == Some(*bit)));
+        assert_eq!(choices.get(len), None);
+    }
+
+    #[test]
+    fn test_packed_output_roundtrip() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let choices: Vec<bool> = (0..100).map(|_| rng.gen()).collect();
+        let msgs = Block::random_vec(&mut rng, 100);
+
+        let output = RCOTReceiverOutput {
+            id: TransferId::default(),
+            choices: choices.clone(),
+            msgs: msgs.clone(),
+        };
+
+        let packed = PackedRCOTReceiverOutput::from(output);
+
+        assert!(packed
+            .iter()
+            .zip(choices.iter().zip(&msgs))
+            .all(|((a, b), (c, d))| a == *c && b == d));
+
+        let (_, packed_choices, packed_msgs) = packed.into_parts();
+
+        assert_eq!(packed_choices.to_vec(), choices);
+        assert_eq!(packed_msgs, msgs);
+    }
+
+    #[test]
+    fn test_packed_choices_serde() {
+        let choices: PackedChoices = [true, false, true].as_slice().into();
+
+        let bytes = bincode::serialize(&choices).unwrap();
+        assert_eq!(
+            bincode::deserialize::<PackedChoices>(&bytes).unwrap(),
+            choices
+        );
+
+        let forged = |len: usize, words: Vec<u64>| {
+            #[derive(Serialize)]
+            struct Forged {
+                len: usize,
+                words: Vec<u64>,
+            }
+
+            bincode::serialize(&Forged { len, words }).unwrap()
+        };
+
+        // Too few words for the length.
+        assert!(bincode::deserialize::<PackedChoices>(&forged(65, vec![0])).is_err());
+        // Too many words for the length.
+        assert!(bincode::deserialize::<PackedChoices>(&forged(3, vec![0, 0])).is_err());
+        // Bits set beyond the length.
+        assert!(bincode::deserialize::<PackedChoices>(&forged(3, vec![0b1101])).is_err());
+    }
+
+    #[test]
+    fn test_message_arena() {
+        let mut arena = MessageArena::new();
+
+        let mut id = TransferId::default();
+        let id_0 = id.next();
+        let id_1 = id.next();
+
+        assert!(arena.push(id_1, [3u8, 4, 5]));
+        assert!(arena.push(id_0, [1u8, 2]));
+        assert!(!arena.push(id_0, [6u8]));
+
+        assert_eq!(arena.get(id_0), Some([1u8, 2].as_slice()));
+        assert_eq!(arena.get(id_1), Some([3u8, 4, 5].as_slice()));
+        assert_eq!(arena.msgs(), &[3, 4, 5, 1, 2]);
+        assert_eq!(arena.len(), 5);
+
+        let ids: Vec<_> = arena.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![id_0, id_1]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Re-randomization of random COTs under a new global correlation.
+//!
+//! Given a batch of random COTs with correlation `delta`, the sender and receiver can derive a
+//! fresh batch of the same size with correlation `new_delta`, while the receiver keeps its choice
+//! bits. The keys are hashed locally with a tweakable circular correlation-robust hash, which
+//! breaks the old correlation, and the sender sends one correction block per COT to establish
+//! the new one.
+//!
+//! For each COT `i` with tweak `t_i`, where the sender holds `k_i` and the receiver holds
+//! `m_i = k_i ⊕ c_i · delta`:
+//!
+//! - The sender outputs `k'_i = H(t_i, k_i)` and sends `u_i = k'_i ⊕ H(t_i, k_i ⊕ delta) ⊕ new_delta`.
+//! - The receiver outputs `m'_i = H(t_i, m_i) ⊕ c_i · u_i = k'_i ⊕ c_i · new_delta`.
+//!
+//! The correction `u_i` is masked by `H(t_i, k_i ⊕ (1 ⊕ c_i) · delta)`, which the receiver can not
+//! compute without knowing `delta`.
+//!
+//! # Security
+//!
+//! - The derivation is not purely local. Two batches with independent deltas can not be derived
+//!   from one another without interaction, so the corrections must be sent to the receiver.
+//! - Each COT must be re-randomized at most once, and the original COTs must be discarded
+//!   afterwards. The tweaks are derived from the transfer ID and the index of each COT, so
+//!   re-randomizing the same COTs again, or using them elsewhere, reuses hash inputs.
+//! - `new_delta` must be sampled uniformly at random by the sender and kept secret. It is not
+//!   derived from `delta`.
+
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+
+use crate::{msgs::Rerandomize, RCOTReceiverOutput, RCOTSenderOutput, TransferId};
+
+/// Errors that can occur when re-randomizing COTs.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum RerandomizeError {
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(TransferId, TransferId),
+    #[error("count mismatch: receiver expected {0} but sender sent {1}")]
+    CountMismatch(usize, usize),
+}
+
+/// Returns the tweak for the COT at index `i` of a transfer.
+fn tweak(id: TransferId, i: usize) -> Block {
+    Block::new((((id.0 as u128) << 64) | i as u128).to_be_bytes())
+}
+
+/// Re-randomizes the sender's random COTs under `new_delta`.
+///
+/// Returns the new COTs, which keep the transfer ID of the originals, and the message to send to
+/// the receiver.
+///
+/// # Arguments
+///
+/// * `delta` - The correlation of the original COTs.
+/// * `new_delta` - The correlation of the new COTs.
+/// * `output` - The original COTs, which must not be used again.
+pub fn rerandomize_sender(
+    delta: Block,
+    new_delta: Block,
+    output: RCOTSenderOutput<Block>,
+) -> (RCOTSenderOutput<Block>, Rerandomize) {
+    let RCOTSenderOutput { id, msgs } = output;
+
+    let cipher = &(*FIXED_KEY_AES);
+    let (msgs, corrections) = msgs
+        .into_iter()
+        .enumerate()
+        .map(|(i, k)| {
+            let t = tweak(id, i);
+
+            let k0 = cipher.tccr(t, k);
+            let k1 = cipher.tccr(t, k ^ delta);
+
+            (k0, k0 ^ k1 ^ new_delta)
+        })
+        .unzip();
+
+    (
+        RCOTSenderOutput { id, msgs },
+        Rerandomize { id, corrections },
+    )
+}
+
+/// Re-randomizes the receiver's random COTs using the sender's corrections.
+///
+/// Returns the new COTs, which keep the choice bits and transfer ID of the originals.
+///
+/// # Arguments
+///
+/// * `output` - The original COTs, which must not be used again.
+/// * `msg` - The sender's corrections.
+pub fn rerandomize_receiver(
+    output: RCOTReceiverOutput<bool, Block>,
+    msg: Rerandomize,
+) -> Result<RCOTReceiverOutput<bool, Block>, RerandomizeError> {
+    let RCOTReceiverOutput { id, choices, msgs } = output;
+    let Rerandomize {
+        id: msg_id,
+        corrections,
+    } = msg;
+
+    if msg_id != id {
+        return Err(RerandomizeError::IdMismatch(id, msg_id));
+    }
+
+    if corrections.len() != msgs.len() {
+        return Err(RerandomizeError::CountMismatch(
+            msgs.len(),
+            corrections.len(),
+        ));
+    }
+
+    let cipher = &(*FIXED_KEY_AES);
+    let msgs = msgs
+        .into_iter()
+        .zip(&choices)
+        .zip(corrections)
+        .enumerate()
+        .map(|(i, ((m, &c), u))| {
+            let m = cipher.tccr(tweak(id, i), m);
+            if c {
+                m ^ u
+            } else {
+                m
+            }
+        })
+        .collect();
+
+    Ok(RCOTReceiverOutput { id, choices, msgs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::{ideal::cot::IdealCOT, test::assert_cot};
+
+    #[test]
+    fn test_rerandomize() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let mut cot = IdealCOT::default();
+        let delta = cot.delta();
+        let new_delta: Block = rng.gen();
+
+        let (sender_output, receiver_output) = cot.random_correlated(128);
+        let old_msgs = sender_output.msgs.clone();
+
+        let (sender_output, msg) = rerandomize_sender(delta, new_delta, sender_output);
+        let receiver_output = rerandomize_receiver(receiver_output, msg).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_cot(
+            new_delta,
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
+        assert!(old_msgs
+            .iter()
+            .zip(&sender_output.msgs)
+            .all(|(old, new)| old != new));
+    }
+
+    #[test]
+    fn test_rerandomize_mismatch() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let mut cot = IdealCOT::default();
+        let delta = cot.delta();
+        let new_delta: Block = rng.gen();
+
+        let (sender_output, receiver_output) = cot.random_correlated(8);
+        let (_, mut msg) = rerandomize_sender(delta, new_delta, sender_output);
+
+        msg.corrections.pop();
+        assert!(matches!(
+            rerandomize_receiver(receiver_output, msg.clone()),
+            Err(RerandomizeError::CountMismatch(8, 7))
+        ));
+
+        let (_, receiver_output) = cot.random_correlated(7);
+        assert!(matches!(
+            rerandomize_receiver(receiver_output, msg),
+            Err(RerandomizeError::IdMismatch(..))
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use derive_builder::Builder;
+
+use crate::{
+    kos::{DEFAULT_BASE_OT_TIMEOUT, DEFAULT_EXTENSION_TIMEOUT},
+    softspoken::{CSP, DEFAULT_K},
+};
+
+/// Validates the `k` parameter of a configuration builder.
+fn validate_k(k: Option<usize>) -> Result<(), String> {
+    match k {
+        Some(k) if !(2..=8).contains(&k) || CSP % k != 0 => {
+            Err(format!("k must be 2, 4 or 8, got {k}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// SoftSpokenOT sender configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct SenderConfig {
+    /// The number of bits per small-field VOLE.
+    ///
+    /// Larger values reduce communication at the cost of computation.
+    #[builder(default = "DEFAULT_K")]
+    k: usize,
+    /// Timeout for the base OT setup.
+    #[builder(default = "DEFAULT_BASE_OT_TIMEOUT")]
+    base_ot_timeout: Duration,
+    /// Timeout for a single OT extension or consistency check.
+    #[builder(default = "DEFAULT_EXTENSION_TIMEOUT")]
+    extension_timeout: Duration,
+}
+
+impl Default for SenderConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+impl SenderConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_k(self.k)
+    }
+}
+
+impl SenderConfig {
+    /// Creates a new builder for SenderConfig.
+    pub fn builder() -> SenderConfigBuilder {
+        SenderConfigBuilder::default()
+    }
+
+    /// Returns the number of bits per small-field VOLE.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the timeout for the base OT setup.
+    pub fn base_ot_timeout(&self) -> Duration {
+        self.base_ot_timeout
+    }
+
+    /// Returns the timeout for a single OT extension or consistency check.
+    pub fn extension_timeout(&self) -> Duration {
+        self.extension_timeout
+    }
+}
+
+/// SoftSpokenOT receiver configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct ReceiverConfig {
+    /// The number of bits per small-field VOLE.
+    ///
+    /// Larger values reduce communication at the cost of computation.
+    #[builder(default = "DEFAULT_K")]
+    k: usize,
+    /// Timeout for the base OT setup.
+    #[builder(default = "DEFAULT_BASE_OT_TIMEOUT")]
+    base_ot_timeout: Duration,
+    /// Timeout for a single OT extension or consistency check.
+    #[builder(default = "DEFAULT_EXTENSION_TIMEOUT")]
+    extension_timeout: Duration,
+}
+
+impl Default for ReceiverConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+impl ReceiverConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_k(self.k)
+    }
+}
+
+impl ReceiverConfig {
+    /// Creates a new builder for ReceiverConfig.
+    pub fn builder() -> ReceiverConfigBuilder {
+        ReceiverConfigBuilder::default()
+    }
+
+    /// Returns the number of bits per small-field VOLE.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the timeout for the base OT setup.
+    pub fn base_ot_timeout(&self) -> Duration {
+        self.base_ot_timeout
+    }
+
+    /// Returns the timeout for a single OT extension or consistency check.
+    pub fn extension_timeout(&self) -> Duration {
+        self.extension_timeout
+    }
+}
@@ -0,0 +1,27 @@
+/// Errors that can occur when using the SoftSpokenOT sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("invalid state: expected {0}")]
+    InvalidState(String),
+    #[error("invalid count, must be a multiple of 64: {0}")]
+    InvalidCount(usize),
+    #[error("invalid extend")]
+    InvalidExtend,
+    #[error("consistency check failed")]
+    ConsistencyCheckFailed,
+    #[error("not enough OTs are setup: expected {0}, actual {1}")]
+    InsufficientSetup(usize, usize),
+}
+
+/// Errors that can occur when using the SoftSpokenOT receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("invalid state: expected {0}")]
+    InvalidState(String),
+    #[error("invalid count, must be a multiple of 64: {0}")]
+    InvalidCount(usize),
+    #[error("not enough OTs are setup: expected {0}, actual {1}")]
+    InsufficientSetup(usize, usize),
+}
@@ -0,0 +1,160 @@
+//! An implementation of the [`SoftSpokenOT`](https://eprint.iacr.org/2022/192) oblivious transfer
+//! extension protocol.
+//!
+//! SoftSpokenOT replaces the base OTs of IKNP with small-field VOLEs, each built from a punctured
+//! GGM tree with `2^k` leaves. The receiver only sends `128 / k - 1` bits per OT instead of the
+//! 128 bits of KOS15, at the cost of `2^k / k` PRG evaluations per bit. The extended correlation is
+//! identical to IKNP, so the KOS15 consistency check is used for malicious security.
+//!
+//! The protocol produces random correlated OTs, i.e. the receiver obtains `t = q ^ x * delta`
+//! where the sender holds `q` and `delta`.
+
+mod config;
+mod error;
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use config::{
+    ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, SenderConfig,
+    SenderConfigBuilder, SenderConfigBuilderError,
+};
+pub use error::{ReceiverError, SenderError};
+pub use receiver::{state as receiver_state, Receiver};
+pub use sender::{state as sender_state, Sender};
+
+pub use crate::kos::{pad_ot_count, round_ot_count, CSP, SSP};
+
+use mpz_core::Block;
+use rand_core::SeedableRng;
+
+use crate::kos::{Rng, RngSeed};
+
+/// Default number of bits per small-field VOLE.
+pub const DEFAULT_K: usize = 4;
+
+/// Returns the size in bytes of the extension message for a given number of OTs.
+///
+/// # Arguments
+///
+/// * `k` - The number of bits per small-field VOLE.
+/// * `count` - The number of OTs.
+pub fn extension_message_size(k: usize, count: usize) -> usize {
+    (CSP / k - 1) * count / 8
+}
+
+/// Creates an rng from a GGM tree leaf.
+pub(crate) fn leaf_rng(seed: Block) -> Rng {
+    // Stretch the Block-sized seed to a 32-byte seed.
+    let mut seed_ = RngSeed::default();
+    seed_
+        .iter_mut()
+        .zip(seed.to_bytes().into_iter().cycle())
+        .for_each(|(s, c)| *s = c);
+    Rng::from_seed(seed_)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    #[fixture]
+    fn delta() -> Block {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        rng.gen()
+    }
+
+    #[fixture]
+    fn chi_seed() -> Block {
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        rng.gen()
+    }
+
+    fn setup(
+        k: usize,
+        delta: Block,
+    ) -> (
+        Sender<sender_state::Extension>,
+        Receiver<receiver_state::Extension>,
+    ) {
+        let sender = Sender::new(SenderConfig::builder().k(k).build().unwrap());
+        let receiver = Receiver::new(ReceiverConfig::builder().k(k).build().unwrap());
+
+        let choices = sender.base_choices(delta);
+
+        let mut rng = ChaCha12Rng::seed_from_u64(2);
+        let (receiver, base_msgs) = receiver.setup(rng.gen());
+
+        let seeds: Vec<Block> = base_msgs
+            .iter()
+            .zip(choices)
+            .map(|(msgs, choice)| msgs[choice as usize])
+            .collect();
+
+        let sender = sender.setup(delta, seeds.try_into().unwrap());
+
+        (sender, receiver)
+    }
+
+    #[rstest]
+    #[case::k2(2)]
+    #[case::k4(4)]
+    #[case::k8(8)]
+    fn test_softspoken_extension(#[case] k: usize, delta: Block, chi_seed: Block) {
+        let (mut sender, mut receiver) = setup(k, delta);
+
+        let count = 1000;
+        let padded = pad_ot_count(count);
+
+        let extend = receiver.extend(padded).unwrap();
+        assert_eq!(extend.us.len(), extension_message_size(k, padded));
+        sender.extend(padded, extend).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let sender_output = sender.random_correlated(count).unwrap();
+        let receiver_output = receiver.random_correlated(count).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_eq!(sender.delta(), delta);
+        assert!(sender_output
+            .msgs
+            .iter()
+            .zip(receiver_output.choices)
+            .zip(receiver_output.msgs)
+            .all(|((q, x), t)| if x { *q ^ delta == t } else { *q == t }));
+    }
+
+    #[rstest]
+    fn test_softspoken_bad_consistency_check(delta: Block, chi_seed: Block) {
+        let (mut sender, mut receiver) = setup(DEFAULT_K, delta);
+
+        let mut extend = receiver.extend(512).unwrap();
+
+        // Flip a bit in each of the receiver's corrections (breaking the choice vector).
+        extend
+            .us
+            .iter_mut()
+            .step_by(512 / 8)
+            .for_each(|byte| *byte ^= 1);
+
+        sender.extend(512, extend).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        let err = sender.check(chi_seed, receiver_check).unwrap_err();
+
+        assert!(matches!(err, SenderError::ConsistencyCheckFailed));
+    }
+
+    #[test]
+    fn test_softspoken_invalid_k() {
+        assert!(SenderConfig::builder().k(3).build().is_err());
+        assert!(ReceiverConfig::builder().k(16).build().is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Messages for the SoftSpokenOT protocol.
+
+use serde::{Deserialize, Serialize};
+
+use crate::msgs::deserialize_bytes;
+
+pub use crate::kos::msgs::{Check, StartExtend};
+
+/// Extension message sent by the receiver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Extend {
+    /// The receiver's corrections, which align the choice vectors of the small-field VOLEs.
+    #[serde(deserialize_with = "deserialize_bytes")]
+    pub us: Vec<u8>,
+}
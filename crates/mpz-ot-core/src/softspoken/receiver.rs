@@ -0,0 +1,350 @@
+use crate::{
+    kos::{Rng, RngSeed},
+    softspoken::{
+        leaf_rng,
+        msgs::{Check, Extend},
+        ReceiverConfig, ReceiverError, CSP, SSP,
+    },
+    RCOTReceiverOutput, TransferId,
+};
+
+use itybity::IntoBits;
+use mpz_core::{ggm_tree::GgmTree, prg::Prg, Block};
+
+use rand::{Rng as _, SeedableRng};
+use rand_core::RngCore;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// SoftSpokenOT receiver.
+#[derive(Debug, Default)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    config: ReceiverConfig,
+    state: T,
+}
+
+impl<T> Receiver<T>
+where
+    T: state::State,
+{
+    /// Returns the Receiver's configuration
+    pub fn config(&self) -> &ReceiverConfig {
+        &self.config
+    }
+}
+
+impl Receiver {
+    /// Creates a new Receiver
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Receiver's configuration
+    pub fn new(config: ReceiverConfig) -> Self {
+        Receiver {
+            config,
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Complete the setup phase of the protocol.
+    ///
+    /// Returns the receiver in the extension state, and the messages which it must send to the
+    /// sender using base OT.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed used to generate the GGM trees.
+    pub fn setup(self, seed: Block) -> (Receiver<state::Extension>, Vec<[Block; 2]>) {
+        let k = self.config.k();
+        let ggm = GgmTree::new(k);
+        let mut prg = Prg::from_seed(seed);
+
+        let mut tree = vec![Block::ZERO; 1 << k];
+        let mut k0 = vec![Block::ZERO; k];
+        let mut k1 = vec![Block::ZERO; k];
+
+        let mut base_msgs = Vec::with_capacity(CSP);
+        let rngs = (0..CSP / k)
+            .map(|_| {
+                ggm.gen(prg.gen(), &mut tree, &mut k0, &mut k1);
+                base_msgs.extend(k0.iter().zip(&k1).map(|(k0, k1)| [*k0, *k1]));
+                tree.iter().copied().map(leaf_rng).collect::<Vec<_>>()
+            })
+            .collect();
+
+        let receiver = Receiver {
+            config: self.config,
+            state: state::Extension {
+                rngs,
+                msgs: Vec::default(),
+                choices: Vec::default(),
+                transfer_id: TransferId::default(),
+                extended: false,
+                unchecked_ts: Vec::default(),
+                unchecked_choices: Vec::default(),
+            },
+        };
+
+        (receiver, base_msgs)
+    }
+}
+
+impl Receiver<state::Extension> {
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> usize {
+        self.state.msgs.len()
+    }
+
+    /// Performs the OT extension.
+    ///
+    /// The provided count _must_ be a multiple of 64, otherwise an error will be returned.
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing the consistency check sacrifices 256 OTs, so be sure to
+    /// extend enough OTs to compensate for this.
+    ///
+    /// # Streaming
+    ///
+    /// Extension can be performed in a streaming fashion by calling this method multiple times, sending
+    /// the `Extend` messages to the sender in-between calls.
+    ///
+    /// The freshly extended OTs are not available until after the consistency check has been
+    /// performed. See [`Receiver::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of OTs to extend (must be a multiple of 64).
+    pub fn extend(&mut self, count: usize) -> Result<Extend, ReceiverError> {
+        if self.state.extended {
+            return Err(ReceiverError::InvalidState(
+                "extending more than once is currently disabled".to_string(),
+            ));
+        }
+
+        if count % 64 != 0 {
+            return Err(ReceiverError::InvalidCount(count));
+        }
+
+        let k = self.config.k();
+        let row_width = count / 8;
+
+        let mut ts = vec![0u8; CSP * row_width];
+        let mut us = vec![0u8; CSP / k * row_width];
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = self.state.rngs
+                    .par_iter_mut()
+                    .zip(ts.par_chunks_exact_mut(k * row_width))
+                    .zip(us.par_chunks_exact_mut(row_width));
+            } else {
+                let iter = self.state.rngs
+                    .iter_mut()
+                    .zip(ts.chunks_exact_mut(k * row_width))
+                    .zip(us.chunks_exact_mut(row_width));
+            }
+        }
+
+        iter.for_each(|((rngs, t), u)| {
+            let mut r = vec![0u8; row_width];
+            for (x, rng) in rngs.iter_mut().enumerate() {
+                rng.fill_bytes(&mut r);
+
+                // u = sum r_x and v = sum x * r_x.
+                u.iter_mut().zip(&r).for_each(|(u, r)| *u ^= r);
+                for (b, t) in t.chunks_exact_mut(row_width).enumerate() {
+                    if (x >> b) & 1 == 1 {
+                        t.iter_mut().zip(&r).for_each(|(t, r)| *t ^= r);
+                    }
+                }
+            }
+        });
+
+        // The choice vector of the first VOLE is used as the choice bits, and the corrections
+        // align the choice vectors of the other VOLEs to it.
+        let (choice_vector, us) = us.split_at_mut(row_width);
+        for u in us.chunks_exact_mut(row_width) {
+            u.iter_mut()
+                .zip(choice_vector.iter())
+                .for_each(|(u, x)| *u ^= x);
+        }
+
+        let choices = choice_vector
+            .iter()
+            .flat_map(|&x| x.into_iter_lsb0())
+            .collect::<Vec<_>>();
+
+        matrix_transpose::transpose_bits(&mut ts, CSP).expect("matrix is rectangular");
+
+        self.state.unchecked_ts.extend(
+            ts.chunks_exact(CSP / 8)
+                .map(|t| Block::try_from(t).unwrap()),
+        );
+        self.state.unchecked_choices.extend(choices);
+
+        Ok(Extend { us: us.to_vec() })
+    }
+
+    /// Performs the correlation check for all outstanding OTS.
+    ///
+    /// This is the consistency check of KOS15, see section 3.1 of that paper for more details.
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing this check sacrifices 256 OTs for the consistency check, so be sure to
+    /// extend enough OTs to compensate for this.
+    ///
+    /// # ⚠️ Warning ⚠️
+    ///
+    /// The provided seed must be unbiased! It should be generated using a secure
+    /// coin-toss protocol **after** the receiver has sent their extension message, ie
+    /// after they have already committed to their choice vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `chi_seed` - The seed used to generate the consistency check weights.
+    pub fn check(&mut self, chi_seed: Block) -> Result<Check, ReceiverError> {
+        // Make sure we have enough sacrificial OTs to perform the consistency check.
+        if self.state.unchecked_ts.len() < CSP + SSP {
+            return Err(ReceiverError::InsufficientSetup(
+                CSP + SSP,
+                self.state.unchecked_ts.len(),
+            ));
+        }
+
+        let mut seed = RngSeed::default();
+        seed.iter_mut()
+            .zip(chi_seed.to_bytes().into_iter().cycle())
+            .for_each(|(s, c)| *s = c);
+
+        let mut rng = Rng::from_seed(seed);
+
+        let mut unchecked_ts = std::mem::take(&mut self.state.unchecked_ts);
+        let mut unchecked_choices = std::mem::take(&mut self.state.unchecked_choices);
+
+        // Sample random weights for the consistency check.
+        let chis = (0..unchecked_ts.len())
+            .map(|_| rng.gen::<Block>())
+            .collect::<Vec<_>>();
+
+        // Compute the random linear combinations.
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let (x, t0, t1) = unchecked_choices.par_iter()
+                    .zip(&unchecked_ts)
+                    .zip(chis)
+                    .map(|((c, t), chi)| {
+                        let x = if *c { chi } else { Block::ZERO };
+                        let (t0, t1) = t.clmul(chi);
+                        (x, t0, t1)
+                    })
+                    .reduce(
+                        || (Block::ZERO, Block::ZERO, Block::ZERO),
+                        |(_x, _t0, _t1), (x, t0, t1)| {
+                            (_x ^ x, _t0 ^ t0, _t1 ^ t1)
+                        },
+                    );
+            } else {
+                let (x, t0, t1) = unchecked_choices.iter()
+                    .zip(&unchecked_ts)
+                    .zip(chis)
+                    .map(|((c, t), chi)| {
+                        let x = if *c { chi } else { Block::ZERO };
+                        let (t0, t1) = t.clmul(chi);
+                        (x, t0, t1)
+                    })
+                    .reduce(|(_x, _t0, _t1), (x, t0, t1)| {
+                        (_x ^ x, _t0 ^ t0, _t1 ^ t1)
+                    }).unwrap();
+            }
+        }
+
+        // Strip off the rows sacrificed for the consistency check.
+        let nrows = unchecked_ts.len() - (CSP + SSP);
+        unchecked_ts.truncate(nrows);
+        unchecked_choices.truncate(nrows);
+
+        self.state.msgs.extend(unchecked_ts);
+        self.state.choices.extend(unchecked_choices);
+
+        // Disable any further extensions.
+        self.state.extended = true;
+
+        Ok(Check { x, t0, t1 })
+    }
+
+    /// Returns the choices and chosen messages of the given number of random correlated OTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of OTs.
+    pub fn random_correlated(
+        &mut self,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, ReceiverError> {
+        if count > self.state.msgs.len() {
+            return Err(ReceiverError::InsufficientSetup(
+                count,
+                self.state.msgs.len(),
+            ));
+        }
+
+        Ok(RCOTReceiverOutput {
+            id: self.state.transfer_id.next(),
+            choices: self.state.choices.drain(..count).collect(),
+            msgs: self.state.msgs.drain(..count).collect(),
+        })
+    }
+}
+
+/// The receiver's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after the setup phase.
+    ///
+    /// In this state the receiver performs OT extension and hands out the extended OTs.
+    pub struct Extension {
+        /// Rngs seeded from the leaves of the GGM trees
+        pub(super) rngs: Vec<Vec<Rng>>,
+        /// Receiver's checked messages
+        pub(super) msgs: Vec<Block>,
+        /// Receiver's random choices
+        pub(super) choices: Vec<bool>,
+        /// Current transfer id
+        pub(super) transfer_id: TransferId,
+
+        /// Whether extension has occurred yet
+        ///
+        /// This is to prevent the receiver from extending twice
+        pub(super) extended: bool,
+
+        /// Receiver's unchecked ts
+        pub(super) unchecked_ts: Vec<Block>,
+        /// Receiver's unchecked choices
+        pub(super) unchecked_choices: Vec<bool>,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
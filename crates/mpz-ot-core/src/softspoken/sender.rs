@@ -0,0 +1,372 @@
+use crate::{
+    kos::{Rng, RngSeed},
+    softspoken::{
+        extension_message_size, leaf_rng,
+        msgs::{Check, Extend},
+        SenderConfig, SenderError, CSP, SSP,
+    },
+    RCOTSenderOutput, TransferId,
+};
+
+use itybity::IntoBits;
+use mpz_core::{ggm_tree::GgmTree, Block};
+
+use rand::{Rng as _, SeedableRng};
+use rand_core::RngCore;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// SoftSpokenOT sender.
+#[derive(Debug, Default)]
+pub struct Sender<T: state::State = state::Initialized> {
+    config: SenderConfig,
+    state: T,
+}
+
+impl<T> Sender<T>
+where
+    T: state::State,
+{
+    /// Returns the Sender's configuration
+    pub fn config(&self) -> &SenderConfig {
+        &self.config
+    }
+}
+
+impl Sender {
+    /// Creates a new Sender
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Sender's configuration
+    pub fn new(config: SenderConfig) -> Self {
+        Sender {
+            config,
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Returns the sender's choice bits for the base OTs.
+    ///
+    /// Each small-field VOLE is punctured at the corresponding `k` bits of `delta`, so the
+    /// sender must choose the complement of those bits along the path of the GGM tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's correlation.
+    pub fn base_choices(&self, delta: Block) -> Vec<bool> {
+        delta
+            .into_lsb0_vec()
+            .chunks_exact(self.config.k())
+            .flat_map(|bits| bits.iter().rev().map(|bit| !bit).collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Complete the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's correlation.
+    /// * `seeds` - The seeds received during base OT, using [`Sender::base_choices`].
+    pub fn setup(self, delta: Block, seeds: [Block; CSP]) -> Sender<state::Extension> {
+        let k = self.config.k();
+        let ggm = GgmTree::new(k);
+        let choices = self.base_choices(delta);
+
+        let mut tree = vec![Block::ZERO; 1 << k];
+        let rngs = seeds
+            .chunks_exact(k)
+            .zip(choices.chunks_exact(k))
+            .map(|(seeds, choices)| {
+                // The leaf at the punctured position is unknown, and is set to zero.
+                ggm.reconstruct(&mut tree, seeds, choices);
+                tree.iter().copied().map(leaf_rng).collect::<Vec<_>>()
+            })
+            .collect();
+
+        let deltas = delta
+            .into_lsb0_vec()
+            .chunks_exact(k)
+            .map(|bits| {
+                bits.iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, bit)| acc | ((*bit as usize) << i))
+            })
+            .collect();
+
+        Sender {
+            config: self.config,
+            state: state::Extension {
+                delta,
+                deltas,
+                rngs,
+                msgs: Vec::default(),
+                transfer_id: TransferId::default(),
+                extended: false,
+                unchecked_qs: Vec::default(),
+            },
+        }
+    }
+}
+
+impl Sender<state::Extension> {
+    /// Returns the sender's correlation.
+    pub fn delta(&self) -> Block {
+        self.state.delta
+    }
+
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> usize {
+        self.state.msgs.len()
+    }
+
+    /// Performs the OT extension.
+    ///
+    /// The provided count _must_ be a multiple of 64, otherwise an error will be returned.
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing the consistency check sacrifices 256 OTs, so be sure to extend enough to
+    /// compensate for this.
+    ///
+    /// # Streaming
+    ///
+    /// Extension can be performed in a streaming fashion by processing an extension in batches via
+    /// multiple calls to this method.
+    ///
+    /// The freshly extended OTs are not available until after the consistency check has been
+    /// performed. See [`Sender::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of additional OTs to extend (must be a multiple of 64).
+    /// * `extend` - The receiver's extension message.
+    pub fn extend(&mut self, count: usize, extend: Extend) -> Result<(), SenderError> {
+        if self.state.extended {
+            return Err(SenderError::InvalidState(
+                "extending more than once is currently disabled".to_string(),
+            ));
+        }
+
+        if count % 64 != 0 {
+            return Err(SenderError::InvalidCount(count));
+        }
+
+        let k = self.config.k();
+        let row_width = count / 8;
+
+        let Extend { us } = extend;
+
+        if us.len() != extension_message_size(k, count) {
+            return Err(SenderError::InvalidExtend);
+        }
+
+        // The choice vector of the first VOLE is used for all of them, so it has no correction.
+        let zero = vec![0u8; row_width];
+        let corrections = std::iter::once(zero.as_slice())
+            .chain(us.chunks_exact(row_width))
+            .collect::<Vec<_>>();
+
+        let mut qs = vec![0u8; CSP * row_width];
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = self.state.rngs
+                    .par_iter_mut()
+                    .zip(self.state.deltas.par_iter())
+                    .zip(qs.par_chunks_exact_mut(k * row_width))
+                    .zip(corrections.par_iter());
+            } else {
+                let iter = self.state.rngs
+                    .iter_mut()
+                    .zip(self.state.deltas.iter())
+                    .zip(qs.chunks_exact_mut(k * row_width))
+                    .zip(corrections.iter());
+            }
+        }
+
+        iter.for_each(|(((rngs, delta), q), correction)| {
+            let mut r = vec![0u8; row_width];
+            for (x, rng) in rngs.iter_mut().enumerate() {
+                rng.fill_bytes(&mut r);
+
+                // w = sum (x + delta) * r_x, the punctured leaf is multiplied by zero.
+                let y = x ^ delta;
+                for (b, q) in q.chunks_exact_mut(row_width).enumerate() {
+                    let mask = 0u8.wrapping_sub(((y >> b) & 1) as u8);
+                    q.iter_mut().zip(&r).for_each(|(q, r)| *q ^= r & mask);
+                }
+            }
+
+            // Correct the choice vector to that of the first VOLE (constant time).
+            for (b, q) in q.chunks_exact_mut(row_width).enumerate() {
+                let mask = 0u8.wrapping_sub(((delta >> b) & 1) as u8);
+                q.iter_mut()
+                    .zip(*correction)
+                    .for_each(|(q, c)| *q ^= c & mask);
+            }
+        });
+
+        matrix_transpose::transpose_bits(&mut qs, CSP).expect("matrix is rectangular");
+
+        self.state
+            .unchecked_qs
+            .extend(qs.chunks_exact(CSP / 8).map(|q| {
+                let q: Block = q.try_into().unwrap();
+                q
+            }));
+
+        Ok(())
+    }
+
+    /// Performs the correlation check for all outstanding OTS.
+    ///
+    /// This is the consistency check of KOS15, see section 3.1 of that paper for more details.
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing this check sacrifices 256 OTs for the consistency check, so be sure to
+    /// extend enough OTs to compensate for this.
+    ///
+    /// # ⚠️ Warning ⚠️
+    ///
+    /// The provided seed must be unbiased! It should be generated using a secure
+    /// coin-toss protocol **after** the receiver has sent their extension message, ie
+    /// after they have already committed to their choice vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `chi_seed` - The seed used to generate the consistency check weights.
+    /// * `receiver_check` - The receiver's consistency check message.
+    pub fn check(&mut self, chi_seed: Block, receiver_check: Check) -> Result<(), SenderError> {
+        // Make sure we have enough sacrificial OTs to perform the consistency check.
+        if self.state.unchecked_qs.len() < CSP + SSP {
+            return Err(SenderError::InsufficientSetup(
+                CSP + SSP,
+                self.state.unchecked_qs.len(),
+            ));
+        }
+
+        let mut seed = RngSeed::default();
+        seed.iter_mut()
+            .zip(chi_seed.to_bytes().into_iter().cycle())
+            .for_each(|(s, c)| *s = c);
+
+        let mut rng = Rng::from_seed(seed);
+
+        let mut unchecked_qs = std::mem::take(&mut self.state.unchecked_qs);
+
+        // Sample random weights for the consistency check.
+        let chis = (0..unchecked_qs.len())
+            .map(|_| rng.gen())
+            .collect::<Vec<_>>();
+
+        // Compute the random linear combinations.
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let check = unchecked_qs.par_iter()
+                    .zip(chis)
+                    .map(|(q, chi)| q.clmul(chi))
+                    .reduce(
+                        || (Block::ZERO, Block::ZERO),
+                        |(_a, _b), (a, b)| (a ^ _a, b ^ _b),
+                    );
+            } else {
+                let check = unchecked_qs.iter()
+                    .zip(chis)
+                    .map(|(q, chi)| q.clmul(chi))
+                    .reduce(
+                        |(_a, _b), (a, b)| (a ^ _a, b ^ _b),
+                    ).unwrap();
+            }
+        }
+
+        let Check { x, t0, t1 } = receiver_check;
+        let tmp = x.clmul(self.state.delta);
+        let check = (check.0 ^ tmp.0, check.1 ^ tmp.1);
+
+        if check != (t0, t1) {
+            return Err(SenderError::ConsistencyCheckFailed);
+        }
+
+        // Strip off the rows sacrificed for the consistency check.
+        let nrows = unchecked_qs.len() - (CSP + SSP);
+        unchecked_qs.truncate(nrows);
+
+        self.state.msgs.extend(unchecked_qs);
+        self.state.extended = true;
+
+        Ok(())
+    }
+
+    /// Returns the `0`-bit messages of the given number of random correlated OTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of OTs.
+    pub fn random_correlated(
+        &mut self,
+        count: usize,
+    ) -> Result<RCOTSenderOutput<Block>, SenderError> {
+        if count > self.state.msgs.len() {
+            return Err(SenderError::InsufficientSetup(count, self.state.msgs.len()));
+        }
+
+        Ok(RCOTSenderOutput {
+            id: self.state.transfer_id.next(),
+            msgs: self.state.msgs.drain(..count).collect(),
+        })
+    }
+}
+
+/// The sender's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The sender's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The sender's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The sender's state after the setup phase.
+    ///
+    /// In this state the sender performs OT extension and hands out the extended OTs.
+    pub struct Extension {
+        /// Sender's correlation
+        pub(super) delta: Block,
+        /// The `k`-bit chunks of delta, one per small-field VOLE
+        pub(super) deltas: Vec<usize>,
+        /// Rngs seeded from the leaves of the punctured GGM trees
+        pub(super) rngs: Vec<Vec<Rng>>,
+        /// Sender's checked messages
+        pub(super) msgs: Vec<Block>,
+
+        /// Current transfer id
+        pub(super) transfer_id: TransferId,
+
+        /// Whether extension has occurred yet
+        ///
+        /// This is to prevent the receiver from extending twice
+        pub(super) extended: bool,
+
+        /// Sender's unchecked qs
+        pub(super) unchecked_qs: Vec<Block>,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
@@ -1,8 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use mpz_common::executor::test_st_executor;
+use mpz_common::{executor::test_st_executor, tag::Tag};
 use mpz_core::Block;
 use mpz_ot::{
-    chou_orlandi::{Receiver, Sender},
+    chou_orlandi::{Receiver, ReceiverConfig, Sender, SenderConfig},
     OTReceiver, OTSender, OTSetup,
 };
 
@@ -16,8 +16,8 @@ fn chou_orlandi(c: &mut Criterion) {
             b.to_async(&rt).iter(|| async {
                 let (mut sender_ctx, mut receiver_ctx) = test_st_executor(8);
 
-                let mut sender = Sender::default();
-                let mut receiver = Receiver::default();
+                let mut sender = Sender::new(Tag::new("bench"), SenderConfig::default());
+                let mut receiver = Receiver::new(Tag::new("bench"), ReceiverConfig::default());
 
                 futures::try_join!(
                     sender.setup(&mut sender_ctx),
@@ -3,7 +3,7 @@
 //! # Examples
 //!
 //! ```
-//! use mpz_common::executor::test_st_executor;
+//! use mpz_common::{executor::test_st_executor, tag::Tag};
 //! use mpz_ot::{
 //!     chou_orlandi::{Receiver, Sender, SenderConfig, ReceiverConfig},
 //!     OTReceiver, OTSender, OTSetup
@@ -13,8 +13,8 @@
 //! # futures::executor::block_on(async {
 //! let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
 //!
-//! let mut sender = Sender::default();
-//! let mut receiver = Receiver::default();
+//! let mut sender = Sender::new(Tag::new("example"), SenderConfig::default());
+//! let mut receiver = Receiver::new(Tag::new("example"), ReceiverConfig::default());
 //!
 //! // Perform the setup.
 //! futures::try_join!(
@@ -52,7 +52,7 @@ mod tests {
     use futures::TryFutureExt;
     use itybity::ToBits;
     use mpz_common::executor::test_st_executor;
-    use mpz_common::Context;
+    use mpz_common::{tag::Tag, Context, Ready};
     use mpz_core::Block;
     use rand::Rng;
     use rand_chacha::ChaCha12Rng;
@@ -91,11 +91,17 @@ mod tests {
         sender_ctx: &mut impl Context,
         receiver_ctx: &mut impl Context,
     ) -> (Sender, Receiver) {
-        let mut sender = Sender::new(sender_config);
-        let mut receiver = Receiver::new(receiver_config);
+        let mut sender = Sender::new(Tag::new("test"), sender_config);
+        let mut receiver = Receiver::new(Tag::new("test"), receiver_config);
+
+        assert!(!sender.is_ready());
+        assert!(!receiver.is_ready());
 
         tokio::try_join!(sender.setup(sender_ctx), receiver.setup(receiver_ctx)).unwrap();
 
+        assert!(sender.is_ready());
+        assert!(receiver.is_ready());
+
         (sender, receiver)
     }
 
@@ -125,6 +131,46 @@ mod tests {
         assert_eq!(output_receiver.msgs, expected);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_chou_orlandi_pool(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (mut sender_ctx, mut receiver_ctx) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut sender_ctx,
+            &mut receiver_ctx,
+        )
+        .await;
+
+        tokio::try_join!(
+            sender.setup_pool(&mut sender_ctx, data.len()),
+            receiver.setup_pool(&mut receiver_ctx, data.len())
+        )
+        .unwrap();
+
+        assert_eq!(sender.pool_len(), data.len());
+        assert_eq!(receiver.pool_len(), data.len());
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        // The first transfer is served from the pool, the second is performed on demand.
+        for _ in 0..2 {
+            let (output_sender, output_receiver) = tokio::try_join!(
+                sender.send(&mut sender_ctx, &data).map_err(OTError::from),
+                receiver
+                    .receive(&mut receiver_ctx, &choices)
+                    .map_err(OTError::from)
+            )
+            .unwrap();
+
+            assert_eq!(output_sender.id, output_receiver.id);
+            assert_eq!(output_receiver.msgs, expected);
+            assert_eq!(sender.pool_len(), 0);
+            assert_eq!(receiver.pool_len(), 0);
+        }
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_chou_orlandi_committed_receiver(data: Vec<[Block; 2]>, choices: Vec<bool>) {
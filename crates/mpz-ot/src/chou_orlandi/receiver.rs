@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 
-use itybity::BitIterable;
+use itybity::{BitIterable, ToBits};
 use mpz_cointoss as cointoss;
-use mpz_common::Context;
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    Context, Poison, Ready,
+};
 use mpz_core::Block;
 use mpz_ot_core::chou_orlandi::msgs::SenderPayload;
 use mpz_ot_core::chou_orlandi::{
@@ -33,30 +36,21 @@ pub(crate) enum State {
 /// Chou-Orlandi receiver.
 #[derive(Debug)]
 pub struct Receiver {
+    tag: TagBinding,
     state: State,
     cointoss_sender: Option<cointoss::Sender<cointoss::sender_state::Received>>,
 }
 
-impl Default for Receiver {
-    fn default() -> Self {
-        Self {
-            state: State::Initialized {
-                config: ReceiverConfig::default(),
-                seed: None,
-            },
-            cointoss_sender: None,
-        }
-    }
-}
-
 impl Receiver {
     /// Creates a new receiver.
     ///
     /// # Arguments
     ///
+    /// * `tag` - The purpose of the instance.
     /// * `config` - The receiver's configuration
-    pub fn new(config: ReceiverConfig) -> Self {
+    pub fn new(tag: Tag, config: ReceiverConfig) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Receiver),
             state: State::Initialized { config, seed: None },
             cointoss_sender: None,
         }
@@ -66,10 +60,12 @@ impl Receiver {
     ///
     /// # Arguments
     ///
+    /// * `tag` - The purpose of the instance.
     /// * `config` - The receiver's configuration
     /// * `seed` - The RNG seed used to generate the receiver's keys.
-    pub fn new_with_seed(config: ReceiverConfig, seed: [u8; 32]) -> Self {
+    pub fn new_with_seed(tag: Tag, config: ReceiverConfig, seed: [u8; 32]) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Receiver),
             state: State::Initialized {
                 config,
                 seed: Some(seed),
@@ -77,6 +73,48 @@ impl Receiver {
             cointoss_sender: None,
         }
     }
+
+    /// Precomputes a pool of `count` random OTs with the sender.
+    ///
+    /// Later transfers are served from the pool using Beaver derandomization if it holds
+    /// enough OTs. See [`Sender::setup_pool`](crate::chou_orlandi::Sender::setup_pool) for
+    /// details.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of OTs to precompute.
+    pub async fn setup_pool<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), OTError> {
+        let mut receiver = std::mem::replace(&mut self.state, State::Error)
+            .try_into_setup()
+            .map_err(ReceiverError::from)?;
+
+        let (receiver, receiver_payload) = Backend::spawn(move || {
+            receiver
+                .precompute(count)
+                .map(|payload| (receiver, payload))
+        })
+        .await
+        .map_err(ReceiverError::from)?;
+
+        ctx.io_mut().send(receiver_payload).await?;
+
+        self.state = State::Setup(receiver);
+
+        Ok(())
+    }
+
+    /// Returns the number of precomputed OTs which have not been used yet.
+    pub fn pool_len(&self) -> usize {
+        self.state
+            .try_as_setup()
+            .map(|receiver| receiver.pool_len())
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait]
@@ -86,6 +124,8 @@ impl<Ctx: Context> OTSetup<Ctx> for Receiver {
             return Ok(());
         }
 
+        self.tag.bind(ctx).await?;
+
         let (config, seed) = std::mem::replace(&mut self.state, State::Error)
             .try_into_initialized()
             .map_err(ReceiverError::from)?;
@@ -120,10 +160,14 @@ impl<Ctx: Context> OTSetup<Ctx> for Receiver {
             seed.unwrap_or_else(|| thread_rng().gen())
         };
 
+        let domain = self.tag.tag().domain();
         let sender_setup = ctx.io_mut().expect_next().await?;
-        let receiver =
-            Backend::spawn(move || ReceiverCore::new_with_seed(config, seed).setup(sender_setup))
-                .await;
+        let receiver = Backend::spawn(move || {
+            ReceiverCore::new_with_seed(config, seed)
+                .with_domain(domain)
+                .setup(sender_setup)
+        })
+        .await;
 
         self.state = State::Setup(Box::new(receiver));
 
@@ -146,14 +190,27 @@ where
             .try_into_setup()
             .map_err(ReceiverError::from)?;
 
-        let choices = choices.to_vec();
-        let (mut receiver, receiver_payload) = Backend::spawn(move || {
-            let payload = receiver.receive_random(&choices);
-            (receiver, payload)
-        })
-        .await;
+        // Serve the transfer from the pool if it holds enough OTs, otherwise fall back to
+        // performing the group operations now.
+        let count = choices.iter_lsb0().count();
+        let mut receiver = if count > 0 && count <= receiver.pool_len() {
+            let derandomize = receiver.derandomize(choices).map_err(ReceiverError::from)?;
 
-        ctx.io_mut().send(receiver_payload).await?;
+            ctx.io_mut().send(derandomize).await?;
+
+            receiver
+        } else {
+            let choices = choices.to_vec();
+            let (receiver, receiver_payload) = Backend::spawn(move || {
+                let payload = receiver.receive_random(&choices);
+                (receiver, payload)
+            })
+            .await;
+
+            ctx.io_mut().send(receiver_payload).await?;
+
+            receiver
+        };
 
         let sender_payload: SenderPayload = ctx.io_mut().expect_next().await?;
         let id = sender_payload.id;
@@ -199,3 +256,15 @@ impl<Ctx: Context> CommittedOTReceiver<Ctx, bool, Block> for Receiver {
         Ok(())
     }
 }
+
+impl Ready for Receiver {
+    fn is_ready(&self) -> bool {
+        self.state.is_setup()
+    }
+}
+
+impl Poison for Receiver {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
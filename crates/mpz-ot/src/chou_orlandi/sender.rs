@@ -4,9 +4,15 @@ use crate::{
 
 use async_trait::async_trait;
 use mpz_cointoss as cointoss;
-use mpz_common::Context;
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    Context, Poison, Ready,
+};
 use mpz_core::Block;
-use mpz_ot_core::chou_orlandi::{sender_state as state, Sender as SenderCore, SenderConfig};
+use mpz_ot_core::chou_orlandi::{
+    msgs::ReceiverPayload, sender_state as state, Sender as SenderCore, SenderConfig,
+    SenderError as SenderCoreError,
+};
 use rand::{thread_rng, Rng};
 use serio::{stream::IoStreamExt, SinkExt as _};
 use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
@@ -25,30 +31,24 @@ pub(crate) enum State {
 /// Chou-Orlandi sender.
 #[derive(Debug)]
 pub struct Sender {
+    tag: TagBinding,
     state: State,
     /// The coin toss receiver after revealing one's own seed but before receiving a decommitment
     /// from the coin toss sender.
     cointoss_receiver: Option<cointoss::Receiver<cointoss::receiver_state::Received>>,
 }
 
-impl Default for Sender {
-    fn default() -> Self {
-        Self {
-            state: State::Initialized(SenderCore::new(SenderConfig::default())),
-            cointoss_receiver: None,
-        }
-    }
-}
-
 impl Sender {
     /// Creates a new Sender
     ///
     /// # Arguments
     ///
+    /// * `tag` - The purpose of the instance.
     /// * `config` - The sender's configuration
-    pub fn new(config: SenderConfig) -> Self {
+    pub fn new(tag: Tag, config: SenderConfig) -> Self {
         Self {
-            state: State::Initialized(SenderCore::new(config)),
+            state: State::Initialized(SenderCore::new(config).with_domain(tag.domain())),
+            tag: TagBinding::new(tag, Side::Sender),
             cointoss_receiver: None,
         }
     }
@@ -57,14 +57,65 @@ impl Sender {
     ///
     /// # Arguments
     ///
+    /// * `tag` - The purpose of the instance.
     /// * `config` - The sender's configuration
     /// * `seed` - The RNG seed used to generate the sender's keys
-    pub fn new_with_seed(config: SenderConfig, seed: [u8; 32]) -> Self {
+    pub fn new_with_seed(tag: Tag, config: SenderConfig, seed: [u8; 32]) -> Self {
         Self {
-            state: State::Initialized(SenderCore::new_with_seed(config, seed)),
+            state: State::Initialized(
+                SenderCore::new_with_seed(config, seed).with_domain(tag.domain()),
+            ),
+            tag: TagBinding::new(tag, Side::Sender),
             cointoss_receiver: None,
         }
     }
+
+    /// Precomputes a pool of `count` random OTs with the receiver.
+    ///
+    /// Later transfers are served from the pool using Beaver derandomization if it holds
+    /// enough OTs, which only requires symmetric operations. This allows the expensive group
+    /// operations to be performed ahead of time, for example before KOS needs its base OTs.
+    ///
+    /// The receiver must call [`Receiver::setup_pool`](crate::chou_orlandi::Receiver::setup_pool)
+    /// with the same count. Precomputation is not supported if the receiver is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of OTs to precompute.
+    pub async fn setup_pool<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), OTError> {
+        let mut sender = std::mem::replace(&mut self.state, State::Error)
+            .try_into_setup()
+            .map_err(SenderError::from)?;
+
+        let receiver_payload: ReceiverPayload = ctx.io_mut().expect_next().await?;
+        if receiver_payload.blinded_choices.len() != count {
+            Err(SenderError::from(SenderCoreError::CountMismatch(
+                count,
+                receiver_payload.blinded_choices.len(),
+            )))?
+        }
+
+        let sender = Backend::spawn(move || sender.precompute(receiver_payload).map(|_| sender))
+            .await
+            .map_err(SenderError::from)?;
+
+        self.state = State::Setup(sender);
+
+        Ok(())
+    }
+
+    /// Returns the number of precomputed OTs which have not been used yet.
+    pub fn pool_len(&self) -> usize {
+        self.state
+            .try_as_setup()
+            .map(|sender| sender.pool_len())
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait]
@@ -74,6 +125,8 @@ impl<Ctx: Context> OTSetup<Ctx> for Sender {
             return Ok(());
         }
 
+        self.tag.bind(ctx).await?;
+
         let sender = std::mem::replace(&mut self.state, State::Error)
             .try_into_initialized()
             .map_err(SenderError::from)?;
@@ -110,15 +163,29 @@ impl<Ctx: Context> OTSender<Ctx, [Block; 2]> for Sender {
             .try_into_setup()
             .map_err(SenderError::from)?;
 
-        let receiver_payload = ctx.io_mut().expect_next().await?;
-
-        let input = input.to_vec();
-        let (sender, payload) = Backend::spawn(move || {
-            sender
-                .send(&input, receiver_payload)
-                .map(|payload| (sender, payload))
-        })
-        .await
+        // The receiver serves the transfer from its pool if it holds enough OTs, which it
+        // does exactly when ours does.
+        let (sender, payload) = if !input.is_empty() && input.len() <= sender.pool_len() {
+            let derandomize = ctx.io_mut().expect_next().await?;
+
+            let input = input.to_vec();
+            Backend::spawn(move || {
+                sender
+                    .send_derandomized(&input, derandomize)
+                    .map(|payload| (sender, payload))
+            })
+            .await
+        } else {
+            let receiver_payload = ctx.io_mut().expect_next().await?;
+
+            let input = input.to_vec();
+            Backend::spawn(move || {
+                sender
+                    .send(&input, receiver_payload)
+                    .map(|payload| (sender, payload))
+            })
+            .await
+        }
         .map_err(SenderError::from)?;
 
         let id = payload.id;
@@ -166,3 +233,15 @@ impl<Ctx: Context> VerifiableOTSender<Ctx, bool, [Block; 2]> for Sender {
         Ok(verified_choices)
     }
 }
+
+impl Ready for Sender {
+    fn is_ready(&self) -> bool {
+        self.state.is_setup()
+    }
+}
+
+impl Poison for Sender {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
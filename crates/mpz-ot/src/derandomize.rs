@@ -0,0 +1,297 @@
+//! Chosen-message OT from random OT.
+//!
+//! See [`mpz_ot_core::derandomize`] for the transformation. The adapters in this module wrap any
+//! random OT, so that for example the random COTs of Ferret can be used for chosen-message OT:
+//!
+//! ```ignore
+//! let sender = DerandomizedSender::new(ROTFromRCOTSender::new(delta, ferret_sender));
+//! let receiver = DerandomizedReceiver::new(ROTFromRCOTReceiver::new(ferret_receiver));
+//! ```
+
+use async_trait::async_trait;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot_core::{
+    derandomize::{
+        derandomize_choices, derandomize_receiver, derandomize_sender, rot_from_rcot_receiver,
+        rot_from_rcot_sender,
+    },
+    msgs::{Derandomize, DerandomizePayload},
+    OTReceiverOutput, OTSenderOutput, ROTReceiverOutput, ROTSenderOutput,
+};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::{
+    OTError, OTReceiver, OTSender, OTSetup, RandomCOTReceiver, RandomCOTSender, RandomOTReceiver,
+    RandomOTSender,
+};
+
+/// Chosen-message OT sender built from a random OT sender.
+#[derive(Debug)]
+pub struct DerandomizedSender<ROT> {
+    rot: ROT,
+}
+
+impl<ROT> DerandomizedSender<ROT> {
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `rot` - The random OT sender.
+    pub fn new(rot: ROT) -> Self {
+        Self { rot }
+    }
+
+    /// Returns the random OT sender.
+    pub fn into_inner(self) -> ROT {
+        self.rot
+    }
+}
+
+#[async_trait]
+impl<Ctx, ROT> OTSetup<Ctx> for DerandomizedSender<ROT>
+where
+    Ctx: Context,
+    ROT: OTSetup<Ctx> + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        self.rot.setup(ctx).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, ROT> OTSender<Ctx, [Block; 2]> for DerandomizedSender<ROT>
+where
+    Ctx: Context,
+    ROT: RandomOTSender<Ctx, [Block; 2]> + Send,
+{
+    async fn send(
+        &mut self,
+        ctx: &mut Ctx,
+        msgs: &[[Block; 2]],
+    ) -> Result<OTSenderOutput, OTError> {
+        let output = self.rot.send_random(ctx, msgs.len()).await?;
+
+        let derandomize: Derandomize = ctx.io_mut().expect_next().await?;
+        let (output, payload) = derandomize_sender(output, derandomize, msgs)
+            .map_err(|e| OTError::SenderError(Box::new(e)))?;
+
+        ctx.io_mut().send(payload).await?;
+
+        Ok(output)
+    }
+}
+
+/// Chosen-message OT receiver built from a random OT receiver.
+#[derive(Debug)]
+pub struct DerandomizedReceiver<ROT> {
+    rot: ROT,
+}
+
+impl<ROT> DerandomizedReceiver<ROT> {
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `rot` - The random OT receiver.
+    pub fn new(rot: ROT) -> Self {
+        Self { rot }
+    }
+
+    /// Returns the random OT receiver.
+    pub fn into_inner(self) -> ROT {
+        self.rot
+    }
+}
+
+#[async_trait]
+impl<Ctx, ROT> OTSetup<Ctx> for DerandomizedReceiver<ROT>
+where
+    Ctx: Context,
+    ROT: OTSetup<Ctx> + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        self.rot.setup(ctx).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, ROT> OTReceiver<Ctx, bool, Block> for DerandomizedReceiver<ROT>
+where
+    Ctx: Context,
+    ROT: RandomOTReceiver<Ctx, bool, Block> + Send,
+{
+    async fn receive(
+        &mut self,
+        ctx: &mut Ctx,
+        choices: &[bool],
+    ) -> Result<OTReceiverOutput<Block>, OTError> {
+        let output = self.rot.receive_random(ctx, choices.len()).await?;
+
+        let derandomize = derandomize_choices(&output, choices)
+            .map_err(|e| OTError::ReceiverError(Box::new(e)))?;
+        ctx.io_mut().send(derandomize).await?;
+
+        let payload: DerandomizePayload = ctx.io_mut().expect_next().await?;
+
+        derandomize_receiver(output, choices, payload)
+            .map_err(|e| OTError::ReceiverError(Box::new(e)))
+    }
+}
+
+/// Random OT sender built from a random COT sender.
+#[derive(Debug)]
+pub struct ROTFromRCOTSender<RCOT> {
+    delta: Block,
+    rcot: RCOT,
+}
+
+impl<RCOT> ROTFromRCOTSender<RCOT> {
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The correlation of the random COTs.
+    /// * `rcot` - The random COT sender.
+    pub fn new(delta: Block, rcot: RCOT) -> Self {
+        Self { delta, rcot }
+    }
+
+    /// Returns the random COT sender.
+    pub fn into_inner(self) -> RCOT {
+        self.rcot
+    }
+}
+
+#[async_trait]
+impl<Ctx, RCOT> OTSetup<Ctx> for ROTFromRCOTSender<RCOT>
+where
+    Ctx: Context,
+    RCOT: OTSetup<Ctx> + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        self.rcot.setup(ctx).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, RCOT> RandomOTSender<Ctx, [Block; 2]> for ROTFromRCOTSender<RCOT>
+where
+    Ctx: Context,
+    RCOT: RandomCOTSender<Ctx, Block> + Send,
+{
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<ROTSenderOutput<[Block; 2]>, OTError> {
+        let output = self.rcot.send_random_correlated(ctx, count).await?;
+
+        Ok(rot_from_rcot_sender(self.delta, output))
+    }
+}
+
+/// Random OT receiver built from a random COT receiver.
+#[derive(Debug)]
+pub struct ROTFromRCOTReceiver<RCOT> {
+    rcot: RCOT,
+}
+
+impl<RCOT> ROTFromRCOTReceiver<RCOT> {
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcot` - The random COT receiver.
+    pub fn new(rcot: RCOT) -> Self {
+        Self { rcot }
+    }
+
+    /// Returns the random COT receiver.
+    pub fn into_inner(self) -> RCOT {
+        self.rcot
+    }
+}
+
+#[async_trait]
+impl<Ctx, RCOT> OTSetup<Ctx> for ROTFromRCOTReceiver<RCOT>
+where
+    Ctx: Context,
+    RCOT: OTSetup<Ctx> + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        self.rcot.setup(ctx).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, RCOT> RandomOTReceiver<Ctx, bool, Block> for ROTFromRCOTReceiver<RCOT>
+where
+    Ctx: Context,
+    RCOT: RandomCOTReceiver<Ctx, bool, Block> + Send,
+{
+    async fn receive_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<ROTReceiverOutput<bool, Block>, OTError> {
+        let output = self.rcot.receive_random_correlated(ctx, count).await?;
+
+        Ok(rot_from_rcot_receiver(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_common::executor::{test_st_executor, TestSTExecutor};
+    use mpz_ot_core::test::assert_rot;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+    use crate::ideal::{cot::ideal_rcot, rot::ideal_rot};
+
+    async fn transfer<S, R>(mut sender: S, mut receiver: R)
+    where
+        S: OTSender<TestSTExecutor, [Block; 2]>,
+        R: OTReceiver<TestSTExecutor, bool, Block>,
+    {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+
+        let msgs: Vec<[Block; 2]> = (0..64).map(|_| rng.gen()).collect();
+        let choices: Vec<bool> = (0..64).map(|_| rng.gen()).collect();
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.send(&mut ctx_sender, &msgs),
+            receiver.receive(&mut ctx_receiver, &choices)
+        )
+        .unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_rot(&choices, &msgs, &receiver_output.msgs);
+    }
+
+    #[tokio::test]
+    async fn test_derandomized_ot() {
+        let (sender, receiver) = ideal_rot();
+
+        transfer(
+            DerandomizedSender::new(sender),
+            DerandomizedReceiver::new(receiver),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_derandomized_ot_from_rcot() {
+        let (mut sender, receiver) = ideal_rcot();
+        let delta = sender.delta();
+
+        transfer(
+            DerandomizedSender::new(ROTFromRCOTSender::new(delta, sender)),
+            DerandomizedReceiver::new(ROTFromRCOTReceiver::new(receiver)),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,104 @@
+use mpz_ot_core::ferret::{mpcot, spcot};
+
+use crate::OTError;
+
+/// A Ferret sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::ferret::error::SenderError),
+    #[error("MPCOT error: {0}")]
+    MpcotError(#[from] mpcot::error::SenderError),
+    #[error("SPCOT error: {0}")]
+    SpcotError(#[from] spcot::error::SenderError),
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
+    #[error(transparent)]
+    Tag(#[from] mpz_common::tag::TagError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("configuration error: {0}")]
+    ConfigError(String),
+}
+
+impl From<SenderError> for OTError {
+    fn from(err: SenderError) -> Self {
+        match err {
+            SenderError::IOError(e) => e.into(),
+            SenderError::Timeout(e) => e.into(),
+            SenderError::Cancelled(e) => e.into(),
+            SenderError::Tag(e) => e.into(),
+            e => OTError::SenderError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::ferret::SenderStateError> for SenderError {
+    fn from(err: crate::ferret::SenderStateError) -> Self {
+        SenderError::StateError(err.to_string())
+    }
+}
+
+/// A Ferret receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::ferret::error::ReceiverError),
+    #[error("MPCOT error: {0}")]
+    MpcotError(#[from] mpcot::error::ReceiverError),
+    #[error("SPCOT error: {0}")]
+    SpcotError(spcot::error::ReceiverError),
+    #[error("SPCOT consistency check failed, the sender may be malicious")]
+    ConsistencyCheckFailed,
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
+    #[error(transparent)]
+    Tag(#[from] mpz_common::tag::TagError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("configuration error: {0}")]
+    ConfigError(String),
+}
+
+impl From<spcot::error::ReceiverError> for ReceiverError {
+    fn from(err: spcot::error::ReceiverError) -> Self {
+        match err {
+            spcot::error::ReceiverError::ConsistencyCheckFailed => {
+                ReceiverError::ConsistencyCheckFailed
+            }
+            e => ReceiverError::SpcotError(e),
+        }
+    }
+}
+
+impl From<ReceiverError> for OTError {
+    fn from(err: ReceiverError) -> Self {
+        match err {
+            ReceiverError::IOError(e) => e.into(),
+            ReceiverError::Timeout(e) => e.into(),
+            ReceiverError::Cancelled(e) => e.into(),
+            ReceiverError::Tag(e) => e.into(),
+            e => OTError::ReceiverError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::ferret::ReceiverStateError> for ReceiverError {
+    fn from(err: crate::ferret::ReceiverStateError) -> Self {
+        ReceiverError::StateError(err.to_string())
+    }
+}
@@ -0,0 +1,217 @@
+//! An implementation of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) correlated OT
+//! extension protocol.
+//!
+//! Each extension runs MPCOT on the LPN error vector, which is built from one SPCOT per non-zero
+//! position. With [`FerretConfig::consistency_check`] enabled, the SPCOTs are verified with the
+//! consistency check of Figure 6 in the paper, which makes the protocol secure against a
+//! malicious sender. A failed check is reported as [`ReceiverError::ConsistencyCheckFailed`].
+
+mod error;
+mod mpcot;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_ot_core::ferret::{
+    FerretConfig, FerretConfigBuilder, FerretConfigBuilderError, LpnType, LPN_PARAMETERS_REGULAR,
+    LPN_PARAMETERS_UNIFORM,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use std::time::Duration;
+
+    use futures::TryFutureExt;
+    use mpz_common::{
+        executor::test_st_executor,
+        tag::Tag,
+        timeout::{Phase, TimeoutError},
+        Context, Poison, Ready,
+    };
+    use mpz_core::{lpn::LpnParameters, Block};
+    use mpz_ot_core::test::assert_cot;
+
+    use crate::{
+        ideal::cot::{ideal_rcot, IdealCOTReceiver, IdealCOTSender},
+        OTError, OTSetup, RandomCOTReceiver, RandomCOTSender,
+    };
+
+    const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
+        n: 9600,
+        k: 1220,
+        t: 600,
+    };
+
+    fn config(lpn_type: LpnType, consistency_check: bool) -> FerretConfig {
+        FerretConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .lpn_type(lpn_type)
+            .consistency_check(consistency_check)
+            .build()
+            .unwrap()
+    }
+
+    async fn setup<Ctx: Context>(
+        config: FerretConfig,
+        delta: Option<Block>,
+        ctx_sender: &mut Ctx,
+        ctx_receiver: &mut Ctx,
+    ) -> (Sender<IdealCOTSender>, Receiver<IdealCOTReceiver>) {
+        let (mut base_sender, base_receiver) = ideal_rcot();
+        let delta = delta.unwrap_or_else(|| base_sender.delta());
+
+        let mut sender = Sender::new(Tag::new("test"), config.clone(), delta, base_sender);
+        let mut receiver = Receiver::new(Tag::new("test"), config, base_receiver);
+
+        assert!(!sender.is_ready());
+        assert!(!receiver.is_ready());
+
+        tokio::try_join!(
+            sender.setup(ctx_sender).map_err(OTError::from),
+            receiver.setup(ctx_receiver).map_err(OTError::from)
+        )
+        .unwrap();
+
+        assert!(sender.is_ready());
+        assert!(receiver.is_ready());
+
+        (sender, receiver)
+    }
+
+    #[rstest]
+    #[case::regular(LpnType::Regular, true)]
+    #[case::regular_semi_honest(LpnType::Regular, false)]
+    #[case::uniform(LpnType::Uniform, true)]
+    #[tokio::test]
+    async fn test_ferret(#[case] lpn_type: LpnType, #[case] consistency_check: bool) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let config = config(lpn_type, consistency_check);
+        let (mut sender, mut receiver) =
+            setup(config.clone(), None, &mut ctx_sender, &mut ctx_receiver).await;
+
+        // Spans more than one extension.
+        let count = config.extension_output_size() + 10;
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.send_random_correlated(&mut ctx_sender, count),
+            receiver.receive_random_correlated(&mut ctx_receiver, count)
+        )
+        .unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_eq!(sender_output.msgs.len(), count);
+        assert_cot(
+            sender.delta(),
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
+        assert_eq!(sender.remaining(), receiver.remaining());
+    }
+
+    #[rstest]
+    #[case::regular(LpnType::Regular)]
+    #[case::uniform(LpnType::Uniform)]
+    #[tokio::test]
+    async fn test_ferret_bootstrap(#[case] lpn_type: LpnType) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut base_sender, base_receiver) = ideal_rcot();
+        let mut base = base_sender.clone();
+        let delta = base_sender.delta();
+
+        let config = config(lpn_type, true);
+        let mut sender = Sender::new(Tag::new("test"), config.clone(), delta, base_sender);
+        let mut receiver = Receiver::new(Tag::new("test"), config, base_receiver);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender).map_err(OTError::from),
+            receiver.setup(&mut ctx_receiver).map_err(OTError::from)
+        )
+        .unwrap();
+
+        let mut counts = Vec::new();
+        for _ in 0..3 {
+            tokio::try_join!(
+                sender.extend(&mut ctx_sender).map_err(OTError::from),
+                receiver.extend(&mut ctx_receiver).map_err(OTError::from)
+            )
+            .unwrap();
+            counts.push(base.count());
+        }
+
+        // Only the first extension uses base COTs, the later ones use COTs set aside from the
+        // previous extension.
+        assert_eq!(counts[0], counts[1]);
+        assert_eq!(counts[1], counts[2]);
+
+        let count = sender.remaining();
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.send_random_correlated(&mut ctx_sender, count),
+            receiver.receive_random_correlated(&mut ctx_receiver, count)
+        )
+        .unwrap();
+
+        assert_cot(
+            delta,
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ferret_extension_timeout() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let config = FerretConfig::builder()
+            .lpn_parameters(LPN_PARAMETERS_TEST)
+            .extension_timeout(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        let (mut sender, _receiver) = setup(config, None, &mut ctx_sender, &mut ctx_receiver).await;
+
+        // The receiver never starts the extension.
+        let err = sender.extend(&mut ctx_sender).await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            SenderError::Timeout(TimeoutError {
+                phase: Phase::Extension,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ferret_consistency_check_fails() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+
+        // The sender uses a delta which does not match its base COTs.
+        let (mut sender, mut receiver) = setup(
+            config(LpnType::Regular, true),
+            Some(Block::ONES),
+            &mut ctx_sender,
+            &mut ctx_receiver,
+        )
+        .await;
+
+        let (_, receiver_result) = tokio::join!(
+            sender.extend(&mut ctx_sender),
+            receiver.extend(&mut ctx_receiver)
+        );
+
+        assert!(matches!(
+            receiver_result,
+            Err(ReceiverError::ConsistencyCheckFailed)
+        ));
+        assert!(receiver.is_poisoned());
+    }
+}
@@ -0,0 +1,108 @@
+//! MPCOT with either a general or a regular error distribution.
+
+use mpz_core::Block;
+use mpz_ot_core::ferret::mpcot::{
+    error::{ReceiverError, SenderError},
+    receiver::{state as general_receiver_state, Receiver as GeneralReceiver},
+    receiver_regular::{state as regular_receiver_state, Receiver as RegularReceiver},
+    sender::{state as general_sender_state, Sender as GeneralSender},
+    sender_regular::{state as regular_sender_state, Sender as RegularSender},
+};
+
+/// An MPCOT sender which is ready to extend.
+#[derive(Debug)]
+pub(crate) enum MpcotSender {
+    General(GeneralSender<general_sender_state::PreExtension>),
+    Regular(RegularSender<regular_sender_state::PreExtension>),
+}
+
+/// An MPCOT sender which is waiting for the outputs of SPCOT.
+#[derive(Debug)]
+pub(crate) enum MpcotSenderExtension {
+    General(GeneralSender<general_sender_state::Extension>),
+    Regular(RegularSender<regular_sender_state::Extension>),
+}
+
+impl MpcotSender {
+    /// Returns the extension state and the depths of the SPCOT queries.
+    pub(crate) fn pre_extend(
+        self,
+        t: u32,
+        n: u32,
+    ) -> Result<(MpcotSenderExtension, Vec<usize>), SenderError> {
+        match self {
+            MpcotSender::General(sender) => sender
+                .pre_extend(t, n)
+                .map(|(sender, hs)| (MpcotSenderExtension::General(sender), hs)),
+            MpcotSender::Regular(sender) => sender
+                .pre_extend(t, n)
+                .map(|(sender, hs)| (MpcotSenderExtension::Regular(sender), hs)),
+        }
+    }
+}
+
+impl MpcotSenderExtension {
+    /// Combines the outputs of SPCOT into the outputs of MPCOT.
+    pub(crate) fn extend(
+        self,
+        st: &[Vec<Block>],
+    ) -> Result<(MpcotSender, Vec<Block>), SenderError> {
+        match self {
+            MpcotSenderExtension::General(sender) => sender
+                .extend(st)
+                .map(|(sender, s)| (MpcotSender::General(sender), s)),
+            MpcotSenderExtension::Regular(sender) => sender
+                .extend(st)
+                .map(|(sender, s)| (MpcotSender::Regular(sender), s)),
+        }
+    }
+}
+
+/// An MPCOT receiver which is ready to extend.
+#[derive(Debug)]
+pub(crate) enum MpcotReceiver {
+    General(GeneralReceiver<general_receiver_state::PreExtension>),
+    Regular(RegularReceiver<regular_receiver_state::PreExtension>),
+}
+
+/// An MPCOT receiver which is waiting for the outputs of SPCOT.
+#[derive(Debug)]
+pub(crate) enum MpcotReceiverExtension {
+    General(GeneralReceiver<general_receiver_state::Extension>),
+    Regular(RegularReceiver<regular_receiver_state::Extension>),
+}
+
+impl MpcotReceiver {
+    /// Returns the extension state and the depths and points of the SPCOT queries.
+    pub(crate) fn pre_extend(
+        self,
+        alphas: &[u32],
+        n: u32,
+    ) -> Result<(MpcotReceiverExtension, Vec<(usize, u32)>), ReceiverError> {
+        match self {
+            MpcotReceiver::General(receiver) => receiver
+                .pre_extend(alphas, n)
+                .map(|(receiver, queries)| (MpcotReceiverExtension::General(receiver), queries)),
+            MpcotReceiver::Regular(receiver) => receiver
+                .pre_extend(alphas, n)
+                .map(|(receiver, queries)| (MpcotReceiverExtension::Regular(receiver), queries)),
+        }
+    }
+}
+
+impl MpcotReceiverExtension {
+    /// Combines the outputs of SPCOT into the outputs of MPCOT.
+    pub(crate) fn extend(
+        self,
+        rt: &[Vec<Block>],
+    ) -> Result<(MpcotReceiver, Vec<Block>), ReceiverError> {
+        match self {
+            MpcotReceiverExtension::General(receiver) => receiver
+                .extend(rt)
+                .map(|(receiver, r)| (MpcotReceiver::General(receiver), r)),
+            MpcotReceiverExtension::Regular(receiver) => receiver
+                .extend(rt)
+                .map(|(receiver, r)| (MpcotReceiver::Regular(receiver), r)),
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::mem;
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    timeout::{timeout, Phase},
+    Allocate, Context, Poison, Preprocess, Ready,
+};
+use mpz_core::Block;
+use mpz_ot_core::{
+    ferret::{
+        mpcot::{
+            receiver::Receiver as GeneralReceiver, receiver_regular::Receiver as RegularReceiver,
+        },
+        receiver::{state, Receiver as ReceiverCore},
+        spcot::{
+            msgs::{CheckFromSender, ExtendFromSender, MaskBits},
+            receiver::Receiver as SpcotReceiver,
+        },
+        FerretConfig, LpnType, CSP,
+    },
+    RCOTReceiverOutput, TransferId,
+};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    ferret::{mpcot::MpcotReceiver, ReceiverError},
+    OTError, OTSetup, RandomCOTReceiver,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(Extension),
+    Error,
+}
+
+#[derive(Debug)]
+pub(crate) struct Extension {
+    core: ReceiverCore<state::Extension>,
+    mpcot: MpcotReceiver,
+}
+
+/// Ferret receiver.
+///
+/// Extends random correlated OTs from a smaller number of base random correlated OTs.
+#[derive(Debug)]
+pub struct Receiver<RandomCOT> {
+    tag: TagBinding,
+    config: FerretConfig,
+    state: State,
+    base: RandomCOT,
+    alloc: usize,
+    /// Extended choices and COTs which have not been consumed yet.
+    choices: Vec<bool>,
+    msgs: Vec<Block>,
+    /// Choices and COTs set aside from the previous extension for the SPCOTs and the consistency
+    /// check of the next one.
+    reserved_choices: Vec<bool>,
+    reserved_msgs: Vec<Block>,
+    id: TransferId,
+}
+
+impl<RandomCOT: Send> Receiver<RandomCOT> {
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The purpose of the instance.
+    /// * `config` - The Ferret configuration.
+    /// * `base` - The base random COT receiver.
+    pub fn new(tag: Tag, config: FerretConfig, base: RandomCOT) -> Self {
+        Self {
+            tag: TagBinding::new(tag, Side::Receiver),
+            config,
+            state: State::Initialized(ReceiverCore::new()),
+            base,
+            alloc: 0,
+            choices: Vec::new(),
+            msgs: Vec::new(),
+            reserved_choices: Vec::new(),
+            reserved_msgs: Vec::new(),
+            id: TransferId::default(),
+        }
+    }
+
+    /// The number of extended OTs which have not been consumed yet.
+    pub fn remaining(&self) -> usize {
+        self.msgs.len()
+    }
+
+    async fn _setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError>
+    where
+        RandomCOT: OTSetup<Ctx> + RandomCOTReceiver<Ctx, bool, Block>,
+    {
+        self.tag.bind(ctx).await?;
+
+        let core = mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+        let lpn_parameters = self.config.lpn_parameters();
+        let lpn_type = self.config.lpn_type();
+
+        self.base.setup(ctx).await?;
+        let RCOTReceiverOutput {
+            choices: u,
+            msgs: w,
+            ..
+        } = self
+            .base
+            .receive_random_correlated(ctx, lpn_parameters.k)
+            .await?;
+
+        let (core, seed) = core.setup(lpn_parameters, lpn_type, thread_rng().gen(), &u, &w)?;
+        ctx.io_mut().send(seed).await?;
+
+        let mpcot = match lpn_type {
+            LpnType::Uniform => {
+                let (receiver, hash_seed) = GeneralReceiver::new().setup(thread_rng().gen());
+                ctx.io_mut().send(hash_seed).await?;
+                MpcotReceiver::General(receiver)
+            }
+            LpnType::Regular => MpcotReceiver::Regular(RegularReceiver::new().setup()),
+        };
+
+        self.state = State::Extension(Extension { core, mpcot });
+
+        Ok(())
+    }
+
+    /// Performs a single Ferret extension, adding the resulting COTs to the buffer.
+    ///
+    /// If the consistency check is enabled and fails, [`ReceiverError::ConsistencyCheckFailed`]
+    /// is returned and the receiver can not be used anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError>
+    where
+        RandomCOT: RandomCOTReceiver<Ctx, bool, Block>,
+    {
+        timeout(
+            Phase::Extension,
+            self.config.extension_timeout(),
+            self._extend(ctx),
+        )
+        .await?
+    }
+
+    async fn _extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError>
+    where
+        RandomCOT: RandomCOTReceiver<Ctx, bool, Block>,
+    {
+        ctx.cancel_token().check()?;
+
+        let Extension { mut core, mpcot } =
+            mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let (alphas, n) = core.get_mpcot_query();
+        let (mpcot, queries) = mpcot.pre_extend(&alphas, n as u32)?;
+
+        // The COTs of the SPCOT trees, followed by those sacrificed by the consistency check.
+        let total: usize = queries.iter().map(|(h, _)| h).sum();
+        let check = self.config.consistency_check();
+        let count = if check { total + CSP } else { total };
+
+        // They are set aside from the output of the previous extension, so only the first
+        // extension uses base COTs.
+        if self.reserved_msgs.len() < count {
+            let RCOTReceiverOutput { choices, msgs, .. } = self
+                .base
+                .receive_random_correlated(ctx, count - self.reserved_msgs.len())
+                .await?;
+            self.reserved_choices.extend(choices);
+            self.reserved_msgs.extend(msgs);
+        }
+        let mut rs: Vec<bool> = self.reserved_choices.drain(..count).collect();
+        let mut ts: Vec<Block> = self.reserved_msgs.drain(..count).collect();
+        let x_star = rs.split_off(total);
+        let z_star = ts.split_off(total);
+
+        let mut spcot = SpcotReceiver::new().setup();
+
+        let mut offset = 0;
+        let mut masks: Vec<MaskBits> = Vec::with_capacity(queries.len());
+        for &(h, alpha) in &queries {
+            masks.push(spcot.extend_mask_bits(h, alpha, &rs[offset..offset + h])?);
+            offset += h;
+        }
+        ctx.io_mut().send(masks).await?;
+
+        let extends: Vec<ExtendFromSender> = ctx.io_mut().expect_next().await?;
+        if extends.len() != queries.len() {
+            return Err(ReceiverError::ConfigError(format!(
+                "expected {} SPCOT extensions, got {}",
+                queries.len(),
+                extends.len()
+            )));
+        }
+
+        let mut offset = 0;
+        for (&(h, alpha), extend) in queries.iter().zip(extends) {
+            spcot.extend(h, alpha, &ts[offset..offset + h], extend)?;
+            offset += h;
+        }
+
+        let rt = if check {
+            ctx.io_mut().send(spcot.check_pre(&x_star)?).await?;
+            let sender_check: CheckFromSender = ctx.io_mut().expect_next().await?;
+            spcot.check(&z_star, sender_check)?
+        } else {
+            spcot.finish()?
+        };
+        let rt: Vec<Vec<Block>> = rt.into_iter().map(|(ws, _)| ws).collect();
+
+        let (mpcot, r) = mpcot.extend(&rt)?;
+        let (core, (mut choices, mut msgs)) =
+            Backend::spawn(move || core.extend(&r).map(|output| (core, output))).await?;
+
+        // Set aside the COTs of the next extension, which needs as many as this one.
+        let split = msgs.len().saturating_sub(count);
+        self.reserved_choices.extend(choices.split_off(split));
+        self.reserved_msgs.extend(msgs.split_off(split));
+
+        self.choices.extend(choices);
+        self.msgs.extend(msgs);
+        self.state = State::Extension(Extension { core, mpcot });
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> OTSetup<Ctx> for Receiver<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx> + RandomCOTReceiver<Ctx, bool, Block> + Send + 'static,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        timeout(Phase::BaseOT, self.config.setup_timeout(), self._setup(ctx))
+            .await?
+            .map_err(OTError::from)
+    }
+}
+
+impl<RandomCOT> Allocate for Receiver<RandomCOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> Preprocess<Ctx> for Receiver<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx> + RandomCOTReceiver<Ctx, bool, Block> + Send + 'static,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        while self.msgs.len() < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> RandomCOTReceiver<Ctx, bool, Block> for Receiver<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: RandomCOTReceiver<Ctx, bool, Block> + Send,
+{
+    async fn receive_random_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+        // Extend on demand, the sender does the same for the same count.
+        while self.msgs.len() < count {
+            self.extend(ctx).await?;
+        }
+
+        let choices = self.choices.drain(..count).collect();
+        let msgs = self.msgs.drain(..count).collect();
+
+        Ok(RCOTReceiverOutput {
+            id: self.id.next(),
+            choices,
+            msgs,
+        })
+    }
+}
+
+impl<RandomCOT> Ready for Receiver<RandomCOT> {
+    fn is_ready(&self) -> bool {
+        self.state.is_extension()
+    }
+}
+
+impl<RandomCOT> Poison for Receiver<RandomCOT> {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
@@ -0,0 +1,305 @@
+use std::mem;
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    timeout::{timeout, Phase},
+    Allocate, Context, Poison, Preprocess, Ready,
+};
+use mpz_core::Block;
+use mpz_ot_core::{
+    ferret::{
+        mpcot::{
+            msgs::HashSeed, sender::Sender as GeneralSender,
+            sender_regular::Sender as RegularSender,
+        },
+        msgs::LpnMatrixSeed,
+        sender::{state, Sender as SenderCore},
+        spcot::{
+            msgs::{CheckFromReceiver, ExtendFromSender, MaskBits},
+            sender::Sender as SpcotSender,
+        },
+        FerretConfig, LpnType, CSP,
+    },
+    RCOTSenderOutput, TransferId,
+};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    ferret::{mpcot::MpcotSender, SenderError},
+    OTError, OTSetup, RandomCOTSender,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(Extension),
+    Error,
+}
+
+#[derive(Debug)]
+pub(crate) struct Extension {
+    core: SenderCore<state::Extension>,
+    mpcot: MpcotSender,
+}
+
+/// Ferret sender.
+///
+/// Extends random correlated OTs from a smaller number of base random correlated OTs, which must
+/// be correlated with the same `delta`.
+#[derive(Debug)]
+pub struct Sender<RandomCOT> {
+    tag: TagBinding,
+    config: FerretConfig,
+    delta: Block,
+    state: State,
+    base: RandomCOT,
+    alloc: usize,
+    /// Extended COTs which have not been consumed yet.
+    buffer: Vec<Block>,
+    /// COTs set aside from the previous extension for the SPCOTs and the consistency check of
+    /// the next one.
+    reserved: Vec<Block>,
+    id: TransferId,
+}
+
+impl<RandomCOT: Send> Sender<RandomCOT> {
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The purpose of the instance.
+    /// * `config` - The Ferret configuration.
+    /// * `delta` - The global correlation of `base`.
+    /// * `base` - The base random COT sender.
+    pub fn new(tag: Tag, config: FerretConfig, delta: Block, base: RandomCOT) -> Self {
+        Self {
+            tag: TagBinding::new(tag, Side::Sender),
+            config,
+            delta,
+            state: State::Initialized(SenderCore::new()),
+            base,
+            alloc: 0,
+            buffer: Vec::new(),
+            reserved: Vec::new(),
+            id: TransferId::default(),
+        }
+    }
+
+    /// Returns the sender's correlation.
+    pub fn delta(&self) -> Block {
+        self.delta
+    }
+
+    /// The number of extended OTs which have not been consumed yet.
+    pub fn remaining(&self) -> usize {
+        self.buffer.len()
+    }
+
+    async fn _setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError>
+    where
+        RandomCOT: OTSetup<Ctx> + RandomCOTSender<Ctx, Block>,
+    {
+        self.tag.bind(ctx).await?;
+
+        let core = mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+        let lpn_parameters = self.config.lpn_parameters();
+        let lpn_type = self.config.lpn_type();
+
+        self.base.setup(ctx).await?;
+        let RCOTSenderOutput { msgs: v, .. } = self
+            .base
+            .send_random_correlated(ctx, lpn_parameters.k)
+            .await?;
+
+        let LpnMatrixSeed { seed } = ctx.io_mut().expect_next().await?;
+        let core = core.setup(self.delta, lpn_parameters, lpn_type, seed, &v)?;
+
+        let mpcot = match lpn_type {
+            LpnType::Uniform => {
+                let hash_seed: HashSeed = ctx.io_mut().expect_next().await?;
+                MpcotSender::General(GeneralSender::new().setup(self.delta, hash_seed))
+            }
+            LpnType::Regular => MpcotSender::Regular(RegularSender::new().setup(self.delta)),
+        };
+
+        self.state = State::Extension(Extension { core, mpcot });
+
+        Ok(())
+    }
+
+    /// Performs a single Ferret extension, adding the resulting COTs to the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError>
+    where
+        RandomCOT: RandomCOTSender<Ctx, Block>,
+    {
+        timeout(
+            Phase::Extension,
+            self.config.extension_timeout(),
+            self._extend(ctx),
+        )
+        .await?
+    }
+
+    async fn _extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError>
+    where
+        RandomCOT: RandomCOTSender<Ctx, Block>,
+    {
+        ctx.cancel_token().check()?;
+
+        let Extension { mut core, mpcot } =
+            mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let (t, n) = core.get_mpcot_query();
+        let (mpcot, hs) = mpcot.pre_extend(t, n)?;
+
+        // The COTs of the SPCOT trees, followed by those sacrificed by the consistency check.
+        let total: usize = hs.iter().sum();
+        let check = self.config.consistency_check();
+        let count = if check { total + CSP } else { total };
+
+        // They are set aside from the output of the previous extension, so only the first
+        // extension uses base COTs.
+        if self.reserved.len() < count {
+            let RCOTSenderOutput { msgs, .. } = self
+                .base
+                .send_random_correlated(ctx, count - self.reserved.len())
+                .await?;
+            self.reserved.extend(msgs);
+        }
+        let mut qs: Vec<Block> = self.reserved.drain(..count).collect();
+        let y_star = qs.split_off(total);
+
+        let mut spcot = SpcotSender::new().setup(self.delta, thread_rng().gen());
+
+        let masks: Vec<MaskBits> = ctx.io_mut().expect_next().await?;
+        if masks.len() != hs.len() {
+            return Err(SenderError::ConfigError(format!(
+                "expected {} SPCOT queries, got {}",
+                hs.len(),
+                masks.len()
+            )));
+        }
+
+        let mut qs = qs.as_slice();
+        let mut extends: Vec<ExtendFromSender> = Vec::with_capacity(hs.len());
+        for (&h, mask) in hs.iter().zip(masks) {
+            let (q, rest) = qs.split_at(h);
+            extends.push(spcot.extend(h, q, mask)?);
+            qs = rest;
+        }
+        ctx.io_mut().send(extends).await?;
+
+        let st = if check {
+            let receiver_check: CheckFromReceiver = ctx.io_mut().expect_next().await?;
+            let (st, sender_check) = spcot.check(&y_star, receiver_check)?;
+            ctx.io_mut().send(sender_check).await?;
+            st
+        } else {
+            spcot.finish()?
+        };
+
+        let (mpcot, s) = mpcot.extend(&st)?;
+        let (core, mut output) =
+            Backend::spawn(move || core.extend(&s).map(|output| (core, output))).await?;
+
+        // Set aside the COTs of the next extension, which needs as many as this one.
+        let split = output.len().saturating_sub(count);
+        self.reserved.extend(output.split_off(split));
+
+        self.buffer.extend(output);
+        self.state = State::Extension(Extension { core, mpcot });
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> OTSetup<Ctx> for Sender<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx> + RandomCOTSender<Ctx, Block> + Send + 'static,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        timeout(Phase::BaseOT, self.config.setup_timeout(), self._setup(ctx))
+            .await?
+            .map_err(OTError::from)
+    }
+}
+
+impl<RandomCOT> Allocate for Sender<RandomCOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> Preprocess<Ctx> for Sender<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx> + RandomCOTSender<Ctx, Block> + Send + 'static,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        while self.buffer.len() < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> RandomCOTSender<Ctx, Block> for Sender<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: RandomCOTSender<Ctx, Block> + Send,
+{
+    async fn send_random_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        // Extend on demand, the receiver does the same for the same count.
+        while self.buffer.len() < count {
+            self.extend(ctx).await?;
+        }
+
+        let msgs = self.buffer.drain(..count).collect();
+
+        Ok(RCOTSenderOutput {
+            id: self.id.next(),
+            msgs,
+        })
+    }
+}
+
+impl<RandomCOT> Ready for Sender<RandomCOT> {
+    fn is_ready(&self) -> bool {
+        self.state.is_extension()
+    }
+}
+
+impl<RandomCOT> Poison for Sender<RandomCOT> {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
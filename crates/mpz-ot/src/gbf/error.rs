@@ -0,0 +1,11 @@
+/// A garbled Bloom filter membership protocol error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum GbfError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::gbf::GbfError),
+    #[error(transparent)]
+    OTError(#[from] crate::OTError),
+}
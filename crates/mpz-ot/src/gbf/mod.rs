@@ -0,0 +1,121 @@
+//! Private set membership using the [`DCW13`](https://eprint.iacr.org/2013/515) garbled Bloom
+//! filter.
+//!
+//! The sender encodes its set into a garbled Bloom filter, and the receiver obliviously receives
+//! the slots which are set in a Bloom filter of its queries. The receiver learns which of its
+//! queries are in the sender's set, and the sender learns only the number of queries.
+//!
+//! # Security
+//!
+//! The protocol is secure against semi-honest adversaries. A malicious receiver can choose to
+//! receive every slot of the filter, after which it can test arbitrary elements for membership.
+
+mod error;
+
+pub use error::GbfError;
+
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot_core::gbf::{
+    msgs::StartQuery, query, BloomFilter, GarbledBloomFilter, GbfParams, DEFAULT_HASH_COUNT,
+};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::{OTReceiver, OTSender};
+
+/// Executes the membership protocol as the sender, which holds the set.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `ot` - The OT sender.
+/// * `set` - The elements of the set.
+pub async fn gbf_sender<Ctx, OT>(ctx: &mut Ctx, ot: &mut OT, set: &[Block]) -> Result<(), GbfError>
+where
+    Ctx: Context,
+    OT: OTSender<Ctx, [Block; 2]>,
+{
+    let StartQuery { count } = ctx.io_mut().expect_next().await?;
+
+    // The filter must be sized for the larger set, otherwise the receiver could set most of
+    // the bits in its Bloom filter.
+    let params = GbfParams::new(thread_rng().gen(), DEFAULT_HASH_COUNT, set.len().max(count))?;
+
+    ctx.io_mut().send(params).await?;
+
+    let msgs = {
+        let mut rng = thread_rng();
+        let gbf = GarbledBloomFilter::new(&mut rng, params, set)?;
+
+        // The receiver gets the slot if the bit is set, otherwise a random block.
+        gbf.slots()
+            .iter()
+            .map(|slot| [rng.gen(), *slot])
+            .collect::<Vec<[Block; 2]>>()
+    };
+
+    ot.send(ctx, &msgs).await?;
+
+    Ok(())
+}
+
+/// Executes the membership protocol as the receiver, returning whether each query is in the
+/// sender's set.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `ot` - The OT receiver.
+/// * `queries` - The queried elements.
+pub async fn gbf_receiver<Ctx, OT>(
+    ctx: &mut Ctx,
+    ot: &mut OT,
+    queries: &[Block],
+) -> Result<Vec<bool>, GbfError>
+where
+    Ctx: Context,
+    OT: OTReceiver<Ctx, bool, Block>,
+{
+    ctx.io_mut()
+        .send(StartQuery {
+            count: queries.len(),
+        })
+        .await?;
+
+    let params: GbfParams = ctx.io_mut().expect_next().await?;
+
+    let bloom_filter = BloomFilter::new(params, queries);
+    let output = ot.receive(ctx, bloom_filter.bits()).await?;
+
+    Ok(query(&params, &output.msgs, queries)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::executor::test_st_executor;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::ideal::ot::ideal_ot;
+
+    #[tokio::test]
+    async fn test_gbf() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut ot_sender, mut ot_receiver) = ideal_ot();
+
+        let set = Block::random_vec(&mut rng, 100);
+        let queries = vec![set[0], rng.gen(), set[99], rng.gen(), set[50]];
+
+        let (_, members) = tokio::try_join!(
+            gbf_sender(&mut ctx_sender, &mut ot_sender, &set),
+            gbf_receiver(&mut ctx_receiver, &mut ot_receiver, &queries)
+        )
+        .unwrap();
+
+        assert_eq!(members, vec![true, false, true, false, true]);
+    }
+}
@@ -3,15 +3,18 @@
 use async_trait::async_trait;
 
 use mpz_common::{
-    ideal::{ideal_f2p, Alice, Bob},
-    Allocate, Context, Preprocess,
+    ideal::{ideal_f2p_with_latency, Alice, Bob},
+    Allocate, Context, Preprocess, Ready,
 };
 use mpz_core::Block;
 use mpz_ot_core::{
     ideal::cot::IdealCOT, COTReceiverOutput, COTSenderOutput, RCOTReceiverOutput, RCOTSenderOutput,
 };
 
-use crate::{COTReceiver, COTSender, OTError, OTSetup, RandomCOTReceiver, RandomCOTSender};
+use crate::{
+    rerandomize, COTReceiver, COTSender, OTError, OTSetup, RandomCOTReceiver, RandomCOTSender,
+    RerandomizeCOTReceiver, RerandomizeCOTSender,
+};
 
 fn cot(
     f: &mut IdealCOT,
@@ -33,22 +36,49 @@ fn rcot(
     f.random_correlated(sender_count)
 }
 
+/// The number of communication rounds consumed by each COT, in which the receiver sends its
+/// derandomization.
+const COT_ROUNDS: usize = 1;
+/// The number of communication rounds consumed by each random COT, which is non-interactive
+/// once set up.
+const RCOT_ROUNDS: usize = 0;
+
 /// Returns an ideal COT sender and receiver.
+///
+/// Chosen and random COTs are counted with their own number of communication rounds.
 pub fn ideal_cot() -> (IdealCOTSender, IdealCOTReceiver) {
-    let (alice, bob) = ideal_f2p(IdealCOT::default());
+    let (alice, bob) = ideal_f2p_with_latency(IdealCOT::default(), COT_ROUNDS);
     (IdealCOTSender(alice), IdealCOTReceiver(bob))
 }
 
 /// Returns an ideal random COT sender and receiver.
+///
+/// This is the same functionality as [`ideal_cot`].
 pub fn ideal_rcot() -> (IdealCOTSender, IdealCOTReceiver) {
-    let (alice, bob) = ideal_f2p(IdealCOT::default());
-    (IdealCOTSender(alice), IdealCOTReceiver(bob))
+    ideal_cot()
 }
 
 /// Ideal COT sender.
 #[derive(Debug, Clone)]
 pub struct IdealCOTSender(Alice<IdealCOT>);
 
+impl IdealCOTSender {
+    /// Returns the global correlation, delta.
+    pub fn delta(&mut self) -> Block {
+        self.0.get_mut().delta()
+    }
+
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+
+    /// Returns the number of COTs produced by the functionality.
+    pub fn count(&mut self) -> usize {
+        self.0.get_mut().count()
+    }
+}
+
 #[async_trait]
 impl<Ctx> OTSetup<Ctx> for IdealCOTSender
 where
@@ -59,6 +89,12 @@ where
     }
 }
 
+impl Ready for IdealCOTSender {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
 impl Allocate for IdealCOTSender {
     fn alloc(&mut self, _count: usize) {}
 }
@@ -82,7 +118,7 @@ impl<Ctx: Context> COTSender<Ctx, Block> for IdealCOTSender {
         ctx: &mut Ctx,
         count: usize,
     ) -> Result<COTSenderOutput<Block>, OTError> {
-        Ok(self.0.call(ctx, count, cot).await)
+        Ok(self.0.call_with_latency(ctx, count, COT_ROUNDS, cot).await)
     }
 }
 
@@ -93,7 +129,24 @@ impl<Ctx: Context> RandomCOTSender<Ctx, Block> for IdealCOTSender {
         ctx: &mut Ctx,
         count: usize,
     ) -> Result<RCOTSenderOutput<Block>, OTError> {
-        Ok(self.0.call(ctx, count, rcot).await)
+        Ok(self
+            .0
+            .call_with_latency(ctx, count, RCOT_ROUNDS, rcot)
+            .await)
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context> RerandomizeCOTSender<Ctx, Block> for IdealCOTSender {
+    async fn rerandomize_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        new_delta: Block,
+        output: RCOTSenderOutput<Block>,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        let delta = self.delta();
+
+        rerandomize::send(ctx, delta, new_delta, output).await
     }
 }
 
@@ -101,6 +154,13 @@ impl<Ctx: Context> RandomCOTSender<Ctx, Block> for IdealCOTSender {
 #[derive(Debug, Clone)]
 pub struct IdealCOTReceiver(Bob<IdealCOT>);
 
+impl IdealCOTReceiver {
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+}
+
 #[async_trait]
 impl<Ctx> OTSetup<Ctx> for IdealCOTReceiver
 where
@@ -111,6 +171,12 @@ where
     }
 }
 
+impl Ready for IdealCOTReceiver {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
 impl Allocate for IdealCOTReceiver {
     fn alloc(&mut self, _count: usize) {}
 }
@@ -134,7 +200,10 @@ impl<Ctx: Context> COTReceiver<Ctx, bool, Block> for IdealCOTReceiver {
         ctx: &mut Ctx,
         choices: &[bool],
     ) -> Result<COTReceiverOutput<Block>, OTError> {
-        Ok(self.0.call(ctx, choices.to_vec(), cot).await)
+        Ok(self
+            .0
+            .call_with_latency(ctx, choices.to_vec(), COT_ROUNDS, cot)
+            .await)
     }
 }
 
@@ -145,7 +214,21 @@ impl<Ctx: Context> RandomCOTReceiver<Ctx, bool, Block> for IdealCOTReceiver {
         ctx: &mut Ctx,
         count: usize,
     ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
-        Ok(self.0.call(ctx, count, rcot).await)
+        Ok(self
+            .0
+            .call_with_latency(ctx, count, RCOT_ROUNDS, rcot)
+            .await)
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context> RerandomizeCOTReceiver<Ctx, bool, Block> for IdealCOTReceiver {
+    async fn rerandomize_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        output: RCOTReceiverOutput<bool, Block>,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+        rerandomize::receive(ctx, output).await
     }
 }
 
@@ -187,6 +270,17 @@ mod tests {
         assert_eq!(count, sender_msgs.len());
         assert_eq!(count, receiver_msgs.len());
         assert_cot(delta, &choices, &sender_msgs, &receiver_msgs);
+        assert_eq!(alice.rounds(), 1);
+        assert_eq!(bob.rounds(), 1);
+
+        // Random COTs from the same functionality do not consume any rounds.
+        tokio::try_join!(
+            alice.send_random_correlated(&mut ctx_a, count),
+            bob.receive_random_correlated(&mut ctx_b, count)
+        )
+        .unwrap();
+
+        assert_eq!(alice.rounds(), 1);
     }
 
     #[tokio::test]
@@ -219,5 +313,37 @@ mod tests {
         assert_eq!(count, receiver_msgs.len());
         assert_eq!(count, choices.len());
         assert_cot(delta, &choices, &sender_msgs, &receiver_msgs);
+        assert_eq!(alice.rounds(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_ideal_rcot_rerandomize() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut alice, mut bob) = ideal_rcot();
+
+        let new_delta: Block = rng.gen();
+        let count = 10;
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            alice.send_random_correlated(&mut ctx_a, count),
+            bob.receive_random_correlated(&mut ctx_b, count)
+        )
+        .unwrap();
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            alice.rerandomize_correlated(&mut ctx_a, new_delta, sender_output),
+            bob.rerandomize_correlated(&mut ctx_b, receiver_output)
+        )
+        .unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_eq!(count, receiver_output.msgs.len());
+        assert_cot(
+            new_delta,
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
     }
 }
@@ -5,8 +5,8 @@ use std::marker::PhantomData;
 use async_trait::async_trait;
 
 use mpz_common::{
-    ideal::{ideal_f2p, Alice, Bob},
-    Allocate, Context, Preprocess,
+    ideal::{ideal_f2p_with_latency, Alice, Bob},
+    Allocate, Context, Preprocess, Ready,
 };
 use mpz_ot_core::{ideal::ot::IdealOT, TransferId};
 
@@ -29,9 +29,16 @@ fn verify(f: &mut IdealOT, _: (), _: ()) -> (Vec<bool>, ()) {
     (f.choices().to_vec(), ())
 }
 
+/// The number of communication rounds consumed by each OT, modeled after the online phase
+/// of OT extension: the receiver sends its derandomization and the sender replies with the
+/// encrypted messages.
+const ROUNDS: usize = 2;
+/// The number of communication rounds consumed by revealing the receiver's choices.
+const REVEAL_ROUNDS: usize = 1;
+
 /// Returns an ideal OT sender and receiver.
 pub fn ideal_ot<T: Send + 'static, U: Send + 'static>() -> (IdealOTSender<T>, IdealOTReceiver<U>) {
-    let (alice, bob) = ideal_f2p(IdealOT::default());
+    let (alice, bob) = ideal_f2p_with_latency(IdealOT::default(), ROUNDS);
     (
         IdealOTSender(alice, PhantomData),
         IdealOTReceiver(bob, PhantomData),
@@ -42,6 +49,13 @@ pub fn ideal_ot<T: Send + 'static, U: Send + 'static>() -> (IdealOTSender<T>, Id
 #[derive(Debug, Clone)]
 pub struct IdealOTSender<T>(Alice<IdealOT>, PhantomData<fn() -> T>);
 
+impl<T> IdealOTSender<T> {
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+}
+
 #[async_trait]
 impl<Ctx, T> OTSetup<Ctx> for IdealOTSender<T>
 where
@@ -52,6 +66,12 @@ where
     }
 }
 
+impl<T> Ready for IdealOTSender<T> {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
 impl<T> Allocate for IdealOTSender<T> {
     fn alloc(&mut self, _count: usize) {}
 }
@@ -91,7 +111,10 @@ impl<Ctx: Context, T: Copy + Send + Sync + 'static> VerifiableOTSender<Ctx, bool
     for IdealOTSender<[T; 2]>
 {
     async fn verify_choices(&mut self, ctx: &mut Ctx) -> Result<Vec<bool>, OTError> {
-        Ok(self.0.call(ctx, (), verify).await)
+        Ok(self
+            .0
+            .call_with_latency(ctx, (), REVEAL_ROUNDS, verify)
+            .await)
     }
 }
 
@@ -99,6 +122,13 @@ impl<Ctx: Context, T: Copy + Send + Sync + 'static> VerifiableOTSender<Ctx, bool
 #[derive(Debug, Clone)]
 pub struct IdealOTReceiver<T>(Bob<IdealOT>, PhantomData<fn() -> T>);
 
+impl<T> IdealOTReceiver<T> {
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+}
+
 #[async_trait]
 impl<Ctx, T> OTSetup<Ctx> for IdealOTReceiver<T>
 where
@@ -109,6 +139,12 @@ where
     }
 }
 
+impl<T> Ready for IdealOTReceiver<T> {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
 impl<T> Allocate for IdealOTReceiver<T> {
     fn alloc(&mut self, _count: usize) {}
 }
@@ -143,7 +179,9 @@ impl<Ctx: Context, T: Copy + Send + Sync + 'static> CommittedOTReceiver<Ctx, boo
     for IdealOTReceiver<T>
 {
     async fn reveal_choices(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
-        self.0.call(ctx, (), verify).await;
+        self.0
+            .call_with_latency(ctx, (), REVEAL_ROUNDS, verify)
+            .await;
         Ok(())
     }
 }
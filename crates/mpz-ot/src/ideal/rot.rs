@@ -3,8 +3,8 @@
 use async_trait::async_trait;
 
 use mpz_common::{
-    ideal::{ideal_f2p, Alice, Bob},
-    Allocate, Context, Preprocess,
+    ideal::{ideal_f2p_with_latency, Alice, Bob},
+    Allocate, Context, Preprocess, Ready,
 };
 use mpz_ot_core::{ideal::rot::IdealROT, ROTReceiverOutput, ROTSenderOutput};
 use rand::distributions::{Distribution, Standard};
@@ -24,9 +24,13 @@ where
     f.random(sender_count)
 }
 
+/// The number of communication rounds consumed by each call, as random OT is non-interactive
+/// once set up.
+const ROUNDS: usize = 0;
+
 /// Returns an ideal ROT sender and receiver.
 pub fn ideal_rot() -> (IdealROTSender, IdealROTReceiver) {
-    let (alice, bob) = ideal_f2p(IdealROT::default());
+    let (alice, bob) = ideal_f2p_with_latency(IdealROT::default(), ROUNDS);
     (IdealROTSender(alice), IdealROTReceiver(bob))
 }
 
@@ -34,6 +38,13 @@ pub fn ideal_rot() -> (IdealROTSender, IdealROTReceiver) {
 #[derive(Debug, Clone)]
 pub struct IdealROTSender(Alice<IdealROT>);
 
+impl IdealROTSender {
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+}
+
 #[async_trait]
 impl<Ctx> OTSetup<Ctx> for IdealROTSender
 where
@@ -44,6 +55,12 @@ where
     }
 }
 
+impl Ready for IdealROTSender {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
 impl Allocate for IdealROTSender {
     fn alloc(&mut self, _count: usize) {}
 }
@@ -78,6 +95,13 @@ where
 #[derive(Debug, Clone)]
 pub struct IdealROTReceiver(Bob<IdealROT>);
 
+impl IdealROTReceiver {
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+}
+
 #[async_trait]
 impl<Ctx> OTSetup<Ctx> for IdealROTReceiver
 where
@@ -88,6 +112,12 @@ where
     }
 }
 
+impl Ready for IdealROTReceiver {
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
 impl Allocate for IdealROTReceiver {
     fn alloc(&mut self, _count: usize) {}
 }
@@ -9,9 +9,17 @@ pub enum SenderError {
     #[error(transparent)]
     CoreError(#[from] mpz_ot_core::kos::SenderError),
     #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
+    #[error(transparent)]
+    Tag(#[from] mpz_common::tag::TagError),
+    #[error(transparent)]
     BaseOTError(#[from] crate::OTError),
     #[error("coin-toss error: {0}")]
     CointossError(#[from] mpz_cointoss::CointossError),
+    #[error(transparent)]
+    Beacon(#[from] mpz_common::beacon::BeaconError),
     #[error("{0}")]
     StateError(String),
     #[error("configuration error: {0}")]
@@ -24,6 +32,9 @@ impl From<SenderError> for OTError {
     fn from(err: SenderError) -> Self {
         match err {
             SenderError::IOError(e) => e.into(),
+            SenderError::Timeout(e) => e.into(),
+            SenderError::Cancelled(e) => e.into(),
+            SenderError::Tag(e) => e.into(),
             e => OTError::SenderError(Box::new(e)),
         }
     }
@@ -50,9 +61,15 @@ pub enum ReceiverError {
     #[error(transparent)]
     CoreError(#[from] mpz_ot_core::kos::ReceiverError),
     #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
+    #[error(transparent)]
     BaseOTError(#[from] crate::OTError),
     #[error("coin-toss error: {0}")]
     CointossError(#[from] mpz_cointoss::CointossError),
+    #[error(transparent)]
+    Beacon(#[from] mpz_common::beacon::BeaconError),
     #[error("{0}")]
     StateError(String),
     #[error("configuration error: {0}")]
@@ -67,6 +84,8 @@ impl From<ReceiverError> for OTError {
     fn from(err: ReceiverError) -> Self {
         match err {
             ReceiverError::IOError(e) => e.into(),
+            ReceiverError::Timeout(e) => e.into(),
+            ReceiverError::Cancelled(e) => e.into(),
             e => OTError::ReceiverError(Box::new(e)),
         }
     }
@@ -18,6 +18,7 @@ pub(crate) use sender::StateError as SenderStateError;
 pub use mpz_ot_core::kos::{
     msgs, PayloadRecord, ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError,
     ReceiverKeys, SenderConfig, SenderConfigBuilder, SenderConfigBuilderError, SenderKeys,
+    DEFAULT_BASE_OT_TIMEOUT, DEFAULT_EXTENSION_TIMEOUT,
 };
 
 // If we're testing we use a smaller chunk size to make sure the chunking code paths are tested.
@@ -35,9 +36,17 @@ mod tests {
     use super::*;
     use rstest::*;
 
-    use futures::TryFutureExt;
+    use std::{sync::Arc, time::Duration};
+
+    use futures::{FutureExt, TryFutureExt};
     use itybity::ToBits;
-    use mpz_common::{executor::test_st_executor, Context};
+    use mpz_common::{
+        beacon::test_utils::TestBeacon,
+        executor::test_st_executor,
+        tag::Tag,
+        timeout::{Phase, TimeoutError},
+        Context, Poison, Ready,
+    };
     use mpz_core::Block;
     use rand::Rng;
     use rand_chacha::ChaCha12Rng;
@@ -45,8 +54,8 @@ mod tests {
 
     use crate::{
         ideal::ot::{ideal_ot, IdealOTReceiver, IdealOTSender},
-        CommittedOTSender, OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver,
-        RandomOTSender, VerifiableOTReceiver,
+        CommittedOTReceiver, CommittedOTSender, OTError, OTReceiver, OTSender, OTSetup,
+        RandomOTReceiver, RandomOTSender, VerifiableOTReceiver, VerifiableOTSender,
     };
 
     #[fixture]
@@ -83,10 +92,16 @@ mod tests {
     ) {
         let (base_sender, base_receiver) = ideal_ot();
 
-        let mut sender = Sender::new(sender_config, base_receiver);
-        let mut receiver = Receiver::new(receiver_config, base_sender);
+        let mut sender = Sender::new(Tag::new("test"), sender_config, base_receiver);
+        let mut receiver = Receiver::new(Tag::new("test"), receiver_config, base_sender);
+
+        assert!(!sender.is_ready());
+        assert!(!receiver.is_ready());
 
         tokio::try_join!(sender.setup(ctx_sender), receiver.setup(ctx_receiver)).unwrap();
+
+        assert!(sender.is_ready());
+        assert!(receiver.is_ready());
         tokio::try_join!(
             sender.extend(ctx_sender, count).map_err(OTError::from),
             receiver.extend(ctx_receiver, count).map_err(OTError::from)
@@ -123,6 +138,221 @@ mod tests {
         assert_eq!(output_receiver.msgs, expected);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_deferred_check(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(Tag::new("test"), SenderConfig::default(), base_receiver);
+        let mut receiver = Receiver::new(Tag::new("test"), ReceiverConfig::default(), base_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        // Extend in two batches which share a single consistency check.
+        for count in [data.len() / 2, data.len() - data.len() / 2] {
+            tokio::try_join!(
+                sender
+                    .extend_deferred(&mut ctx_sender, count)
+                    .map_err(OTError::from),
+                receiver
+                    .extend_deferred(&mut ctx_receiver, count)
+                    .map_err(OTError::from)
+            )
+            .unwrap();
+        }
+
+        // OTs are not available until the check has been performed.
+        assert_eq!(sender.remaining().unwrap(), 0);
+        assert_eq!(receiver.remaining().unwrap(), 0);
+
+        tokio::try_join!(
+            sender.check(&mut ctx_sender).map_err(OTError::from),
+            receiver.check(&mut ctx_receiver).map_err(OTError::from)
+        )
+        .unwrap();
+
+        assert!(sender.remaining().unwrap() >= data.len());
+        assert_eq!(sender.remaining().unwrap(), receiver.remaining().unwrap());
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data)
+                .map_err(OTError::from),
+            OTReceiver::<_, bool, Block>::receive(&mut receiver, &mut ctx_receiver, &choices)
+                .map_err(OTError::from)
+        )
+        .unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_eq!(output_receiver.msgs, expected);
+    }
+
+    #[rstest]
+    #[case::same_beacon(0, true)]
+    #[case::different_beacon(1, false)]
+    #[tokio::test]
+    async fn test_kos_beacon(#[case] receiver_beacon: u8, #[case] ok: bool) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(Tag::new("test"), SenderConfig::default(), base_receiver);
+        let mut receiver = Receiver::new(Tag::new("test"), ReceiverConfig::default(), base_sender);
+
+        sender.set_beacon(Arc::new(TestBeacon(0)));
+        receiver.set_beacon(Arc::new(TestBeacon(receiver_beacon)));
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let (sender_result, receiver_result) = tokio::join!(
+            sender.extend(&mut ctx_sender, 128),
+            receiver.extend(&mut ctx_receiver, 128)
+        );
+
+        // The receiver can not tell that the challenges differ, only the sender's check fails.
+        receiver_result.unwrap();
+        assert_eq!(sender_result.is_ok(), ok);
+    }
+
+    #[tokio::test]
+    async fn test_kos_tag_mismatch() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(Tag::new("a"), SenderConfig::default(), base_receiver);
+        let mut receiver = Receiver::new(Tag::new("b"), ReceiverConfig::default(), base_sender);
+
+        let (sender_result, receiver_result) = tokio::join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        );
+
+        assert!(matches!(sender_result, Err(OTError::Tag(_))));
+        assert!(matches!(receiver_result, Err(OTError::Tag(_))));
+    }
+
+    #[tokio::test]
+    async fn test_kos_extension_timeout() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(
+            Tag::new("test"),
+            SenderConfig::builder()
+                .extension_timeout(Duration::from_millis(10))
+                .build()
+                .unwrap(),
+            base_receiver,
+        );
+        let mut receiver = Receiver::new(Tag::new("test"), ReceiverConfig::default(), base_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        // The receiver never starts the extension.
+        let err = sender.extend(&mut ctx_sender, 10).await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            SenderError::Timeout(TimeoutError {
+                phase: Phase::Extension,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kos_extension_cancelled() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(Tag::new("test"), SenderConfig::default(), base_receiver);
+        let mut receiver = Receiver::new(Tag::new("test"), ReceiverConfig::default(), base_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        // The receiver never starts the extension, so the sender waits until it is cancelled.
+        let cancel = ctx_sender.cancel_token().clone();
+        let (result, _) = tokio::join!(sender.extend(&mut ctx_sender, 10), async {
+            tokio::task::yield_now().await;
+            cancel.cancel();
+        });
+
+        assert!(matches!(result, Err(SenderError::Cancelled(_))));
+    }
+
+    #[tokio::test]
+    async fn test_kos_check_cancelled() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(Tag::new("test"), SenderConfig::default(), base_receiver);
+        let mut receiver = Receiver::new(Tag::new("test"), ReceiverConfig::default(), base_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        // The sender never takes part in the coin-toss of the consistency check, so the receiver
+        // waits until it is cancelled.
+        let cancel = ctx_receiver.cancel_token().clone();
+        let (result, _) = tokio::join!(receiver.extend(&mut ctx_receiver, 10), async {
+            tokio::task::yield_now().await;
+            cancel.cancel();
+        });
+
+        assert!(matches!(result, Err(ReceiverError::Cancelled(_))));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_cancelled_send(data: Vec<[Block; 2]>) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut ctx_sender,
+            &mut ctx_receiver,
+            data.len(),
+        )
+        .await;
+
+        // The receiver never sends its choices, so the sender is dropped part way through.
+        assert!(
+            OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data)
+                .now_or_never()
+                .is_none()
+        );
+
+        assert!(sender.is_poisoned());
+        assert!(!sender.is_ready());
+        assert!(!receiver.is_poisoned());
+
+        assert!(
+            OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data)
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_kos_random() {
         let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
@@ -226,6 +456,82 @@ mod tests {
             .unwrap();
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_committed_sender_verify_batch(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::builder().sender_commit().build().unwrap(),
+            ReceiverConfig::builder().sender_commit().build().unwrap(),
+            &mut ctx_sender,
+            &mut ctx_receiver,
+            2 * data.len(),
+        )
+        .await;
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let (_, output_receiver) = tokio::try_join!(
+                OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data)
+                    .map_err(OTError::from),
+                OTReceiver::<_, bool, Block>::receive(&mut receiver, &mut ctx_receiver, &choices)
+                    .map_err(OTError::from)
+            )
+            .unwrap();
+
+            ids.push(output_receiver.id);
+        }
+
+        tokio::try_join!(
+            CommittedOTSender::reveal(&mut sender, &mut ctx_sender),
+            receiver.accept_reveal(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let batch: Vec<_> = ids.iter().map(|id| (*id, data.clone())).collect();
+
+        receiver
+            .verify_batch(&mut ctx_receiver, batch.clone())
+            .await
+            .unwrap();
+
+        // The records are consumed by verification.
+        assert!(receiver
+            .verify_batch(&mut ctx_receiver, batch)
+            .await
+            .is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_kos_committed_receiver(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::builder().receiver_commit().build().unwrap(),
+            ReceiverConfig::builder().receiver_commit().build().unwrap(),
+            &mut ctx_sender,
+            &mut ctx_receiver,
+            data.len(),
+        )
+        .await;
+
+        tokio::try_join!(
+            OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data)
+                .map_err(OTError::from),
+            OTReceiver::<_, bool, Block>::receive(&mut receiver, &mut ctx_receiver, &choices)
+                .map_err(OTError::from)
+        )
+        .unwrap();
+
+        let (verified_choices, _) = tokio::try_join!(
+            sender.verify_choices(&mut ctx_sender),
+            receiver.reveal_choices(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        assert_eq!(verified_choices, choices);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_shared_kos(data: Vec<[Block; 2]>, choices: Vec<bool>) {
@@ -1,16 +1,21 @@
-use std::mem;
+use std::{mem, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use futures::TryFutureExt as _;
 use itybity::{FromBitIterator, IntoBitIterator};
 use mpz_cointoss as cointoss;
-use mpz_common::{try_join, Allocate, Context, Preprocess};
+use mpz_common::{
+    beacon::{Beacon, ChallengeDeriver},
+    tag::{Side, Tag, TagBinding},
+    timeout::{timeout, Phase},
+    try_join, Allocate, Context, Poison, Preprocess, Ready,
+};
 use mpz_core::{prg::Prg, Block};
 use mpz_ot_core::{
     kos::{
         msgs::{SenderPayload, StartExtend},
-        pad_ot_count, receiver_state as state, Receiver as ReceiverCore, ReceiverConfig,
-        ReceiverKeys, CSP,
+        pad_ot_count, receiver_state as state, round_ot_count, PayloadRecord,
+        Receiver as ReceiverCore, ReceiverConfig, ReceiverKeys, CSP,
     },
     OTReceiverOutput, ROTReceiverOutput, TransferId,
 };
@@ -26,8 +31,8 @@ use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
 
 use super::{ReceiverError, ReceiverVerifyError, EXTEND_CHUNK_SIZE};
 use crate::{
-    OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver, VerifiableOTReceiver,
-    VerifiableOTSender,
+    CommittedOTReceiver, OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver,
+    VerifiableOTReceiver, VerifiableOTSender,
 };
 
 #[derive(Debug, EnumTryAsInner)]
@@ -39,13 +44,33 @@ pub(crate) enum State {
     Error,
 }
 
+impl State {
+    /// Takes the extension state, leaving the receiver poisoned until it is restored.
+    fn take_extension(&mut self) -> Result<Box<ReceiverCore<state::Extension>>, ReceiverError> {
+        self.try_as_extension()?;
+
+        let State::Extension(receiver) = mem::replace(self, State::Error) else {
+            unreachable!("state is extension");
+        };
+
+        Ok(receiver)
+    }
+}
+
 /// KOS receiver.
 #[derive(Debug)]
 pub struct Receiver<BaseOT> {
+    tag: TagBinding,
     state: State,
     base: BaseOT,
     alloc: usize,
+    /// Whether there are extended OTs which have not been checked yet.
+    unchecked: bool,
     cointoss_receiver: Option<cointoss::Receiver<cointoss::receiver_state::Received>>,
+    /// Derives the consistency check challenges.
+    challenges: ChallengeDeriver,
+    base_ot_timeout: Duration,
+    extension_timeout: Duration,
 }
 
 impl<BaseOT> Receiver<BaseOT>
@@ -56,16 +81,29 @@ where
     ///
     /// # Arguments
     ///
+    /// * `tag` - The purpose of the instance.
     /// * `config` - The receiver's configuration
-    pub fn new(config: ReceiverConfig, base: BaseOT) -> Self {
+    pub fn new(tag: Tag, config: ReceiverConfig, base: BaseOT) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Receiver),
+            base_ot_timeout: config.base_ot_timeout(),
+            extension_timeout: config.extension_timeout(),
             state: State::Initialized(Box::new(ReceiverCore::new(config))),
             base,
             alloc: 0,
+            unchecked: false,
             cointoss_receiver: None,
+            challenges: ChallengeDeriver::default(),
         }
     }
 
+    /// Sets a beacon whose randomness is mixed into the consistency check challenges.
+    ///
+    /// The other party must use the same beacon, see [`mpz_common::beacon`] for details.
+    pub fn set_beacon(&mut self, beacon: Arc<dyn Beacon>) {
+        self.challenges.set_beacon(beacon);
+    }
+
     /// The number of remaining OTs which can be consumed.
     pub fn remaining(&self) -> Result<usize, ReceiverError> {
         Ok(self.state.try_as_extension()?.remaining())
@@ -94,30 +132,97 @@ where
         &mut self,
         ctx: &mut Ctx,
         count: usize,
+    ) -> Result<(), ReceiverError> {
+        self.extend_deferred(ctx, count).await?;
+        self.check(ctx).await
+    }
+
+    /// Performs OT extension, deferring the consistency check.
+    ///
+    /// Multiple deferred extensions can be performed before a single consistency check is run
+    /// with [`Receiver::check`], which amortizes the cost of the check. The extended OTs are not
+    /// available until the check has been performed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), ReceiverError> {
+        timeout(
+            Phase::Extension,
+            self.extension_timeout,
+            self._extend_deferred(ctx, count),
+        )
+        .await?
+    }
+
+    async fn _extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
     ) -> Result<(), ReceiverError> {
         let mut ext_receiver =
             std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
 
-        let count = pad_ot_count(count);
+        // The OTs sacrificed by the consistency check only need to be added once per check.
+        let count = if self.unchecked {
+            round_ot_count(count)
+        } else {
+            pad_ot_count(count)
+        };
 
         // Extend the OTs.
-        let (mut ext_receiver, extend) = Backend::spawn(move || {
+        let (ext_receiver, extend) = Backend::spawn(move || {
             ext_receiver
                 .extend(count)
                 .map(|extend| (ext_receiver, extend))
         })
         .await?;
 
-        // Send the extend message and cointoss commitment.
+        // Send the extend message.
         ctx.io_mut().feed(StartExtend { count }).await?;
         for extend in extend.into_chunks(EXTEND_CHUNK_SIZE) {
+            ctx.cancel_token().check()?;
             ctx.io_mut().feed(extend).await?;
         }
         ctx.io_mut().flush().await?;
 
+        self.state = State::Extension(ext_receiver);
+        self.unchecked = true;
+
+        Ok(())
+    }
+
+    /// Performs the consistency check for all deferred extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError> {
+        timeout(Phase::Extension, self.extension_timeout, self._check(ctx)).await?
+    }
+
+    async fn _check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError> {
+        let mut ext_receiver =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        // Give up waiting on the sender if the thread is cancelled.
+        let cancel = ctx.cancel_token().clone();
+
         // Sample chi_seed with coin-toss.
         let seed = thread_rng().gen();
-        let chi_seed = cointoss::cointoss_sender(ctx, vec![seed]).await?[0];
+        let chi_seed = cancel
+            .run(cointoss::cointoss_sender(ctx, vec![seed]))
+            .await??[0];
+        let chi_seed = self.tag.tag().bind_seed("kos/check", chi_seed);
+        let chi_seed = self
+            .challenges
+            .derive(self.tag.tag(), "kos/check", chi_seed)
+            .await?;
 
         // Compute consistency check.
         let (ext_receiver, check) = Backend::spawn(move || {
@@ -131,6 +236,7 @@ where
         ctx.io_mut().send(check).await?;
 
         self.state = State::Extension(ext_receiver);
+        self.unchecked = false;
 
         Ok(())
     }
@@ -160,6 +266,7 @@ where
             .finalize(ctx)
             .await
             .map_err(ReceiverError::from)?[0];
+        let expected_delta = self.tag.tag().bind_seed("kos/delta", expected_delta);
 
         // Receive delta by verifying the sender's base OT choices.
         let choices = self.base.verify_choices(ctx).await?;
@@ -187,30 +294,52 @@ where
             return Ok(());
         }
 
+        timeout(Phase::BaseOT, self.base_ot_timeout, self._setup(ctx)).await?
+    }
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: Send,
+{
+    async fn _setup<Ctx>(&mut self, ctx: &mut Ctx) -> Result<(), OTError>
+    where
+        Ctx: Context,
+        BaseOT: OTSetup<Ctx> + OTSender<Ctx, [Block; 2]>,
+    {
+        self.tag.bind(ctx).await?;
+
         let ext_receiver = std::mem::replace(&mut self.state, State::Error)
             .try_into_initialized()
             .map_err(ReceiverError::from)?;
 
+        // Give up waiting on the sender if the thread is cancelled.
+        let cancel = ctx.cancel_token().clone();
+
         // If the sender is committed, we run a coin toss
         if ext_receiver.config().sender_commit() {
             let cointoss_seed = thread_rng().gen();
-            let (cointoss_receiver, _) = try_join!(
-                ctx,
-                cointoss::Receiver::new(vec![cointoss_seed])
-                    .receive(ctx)
-                    .map_err(ReceiverError::from),
-                self.base.setup(ctx).map_err(ReceiverError::from)
-            )??;
+            let (cointoss_receiver, _) = cancel
+                .run(async {
+                    try_join!(
+                        ctx,
+                        cointoss::Receiver::new(vec![cointoss_seed])
+                            .receive(ctx)
+                            .map_err(ReceiverError::from),
+                        self.base.setup(ctx).map_err(ReceiverError::from)
+                    )
+                })
+                .await???;
 
             self.cointoss_receiver = Some(cointoss_receiver);
         } else {
-            self.base.setup(ctx).await?;
+            cancel.run(self.base.setup(ctx)).await??;
         }
 
         let seeds: [[Block; 2]; CSP] = std::array::from_fn(|_| thread_rng().gen());
 
         // Send seeds to sender
-        self.base.send(ctx, &seeds).await?;
+        cancel.run(self.base.send(ctx, &seeds)).await??;
 
         let ext_receiver = ext_receiver.setup(seeds);
 
@@ -259,33 +388,39 @@ where
         ctx: &mut Ctx,
         choices: &[bool],
     ) -> Result<OTReceiverOutput<Block>, OTError> {
-        let receiver = self
-            .state
-            .try_as_extension_mut()
-            .map_err(ReceiverError::from)?;
+        let mut receiver = self.state.take_extension()?;
 
-        let mut receiver_keys = receiver.keys(choices.len()).map_err(ReceiverError::from)?;
+        // The state is only restored once the round completes, so dropping this future
+        // part way through poisons the receiver.
+        let output: Result<_, OTError> = async {
+            let mut receiver_keys = receiver.keys(choices.len()).map_err(ReceiverError::from)?;
 
-        let choices = choices.into_lsb0_vec();
-        let derandomize = receiver_keys
-            .derandomize(&choices)
-            .map_err(ReceiverError::from)?;
+            let choices = choices.into_lsb0_vec();
+            let derandomize = receiver_keys
+                .derandomize(&choices)
+                .map_err(ReceiverError::from)?;
 
-        // Send derandomize message
-        ctx.io_mut().send(derandomize).await?;
+            // Send derandomize message
+            ctx.io_mut().send(derandomize).await?;
 
-        // Receive payload
-        let payload: SenderPayload = ctx.io_mut().expect_next().await?;
-        let id = payload.id;
+            // Receive payload
+            let payload: SenderPayload = ctx.io_mut().expect_next().await?;
+            let id = payload.id;
 
-        let received = Backend::spawn(move || {
-            receiver_keys
-                .decrypt_blocks(payload)
-                .map_err(ReceiverError::from)
-        })
-        .await?;
+            let received = Backend::spawn(move || {
+                receiver_keys
+                    .decrypt_blocks(payload)
+                    .map_err(ReceiverError::from)
+            })
+            .await?;
 
-        Ok(OTReceiverOutput { id, msgs: received })
+            Ok(OTReceiverOutput { id, msgs: received })
+        }
+        .await;
+
+        self.state = State::Extension(receiver);
+
+        output
     }
 }
 
@@ -327,33 +462,65 @@ where
         ctx: &mut Ctx,
         choices: &[bool],
     ) -> Result<OTReceiverOutput<[u8; N]>, OTError> {
-        let receiver = self
-            .state
-            .try_as_extension_mut()
-            .map_err(ReceiverError::from)?;
+        let mut receiver = self.state.take_extension()?;
 
-        let mut receiver_keys = receiver.keys(choices.len()).map_err(ReceiverError::from)?;
+        // The state is only restored once the round completes, so dropping this future
+        // part way through poisons the receiver.
+        let output: Result<_, OTError> = async {
+            let mut receiver_keys = receiver.keys(choices.len()).map_err(ReceiverError::from)?;
 
-        let choices = choices.into_lsb0_vec();
-        let derandomize = receiver_keys
-            .derandomize(&choices)
-            .map_err(ReceiverError::from)?;
+            let choices = choices.into_lsb0_vec();
+            let derandomize = receiver_keys
+                .derandomize(&choices)
+                .map_err(ReceiverError::from)?;
 
-        // Send derandomize message
-        ctx.io_mut().send(derandomize).await?;
+            // Send derandomize message
+            ctx.io_mut().send(derandomize).await?;
 
-        // Receive payload
-        let payload: SenderPayload = ctx.io_mut().expect_next().await?;
-        let id = payload.id;
+            // Receive payload
+            let payload: SenderPayload = ctx.io_mut().expect_next().await?;
+            let id = payload.id;
 
-        let received = Backend::spawn(move || {
-            receiver_keys
-                .decrypt_bytes(payload)
-                .map_err(ReceiverError::from)
-        })
-        .await?;
+            let received = Backend::spawn(move || {
+                receiver_keys
+                    .decrypt_bytes(payload)
+                    .map_err(ReceiverError::from)
+            })
+            .await?;
+
+            Ok(OTReceiverOutput { id, msgs: received })
+        }
+        .await;
+
+        self.state = State::Extension(receiver);
+
+        output
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> CommittedOTReceiver<Ctx, bool, Block> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn reveal_choices(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        let mut receiver = self.state.take_extension()?;
+
+        // The state is only restored once the reveal is sent, so dropping this future part
+        // way through poisons the receiver.
+        let output: Result<_, OTError> = async {
+            let reveal = receiver.reveal_choices().map_err(ReceiverError::from)?;
+
+            ctx.io_mut().send(reveal).await?;
+
+            Ok(())
+        }
+        .await;
 
-        Ok(OTReceiverOutput { id, msgs: received })
+        self.state = State::Extension(receiver);
+
+        output
     }
 }
 
@@ -384,4 +551,34 @@ where
 
         Ok(())
     }
+
+    async fn verify_batch(
+        &mut self,
+        _ctx: &mut Ctx,
+        batch: Vec<(TransferId, Vec<[Block; 2]>)>,
+    ) -> Result<(), OTError> {
+        let receiver = self.state.try_as_verify().map_err(ReceiverError::from)?;
+
+        let (ids, msgs): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        let records = receiver.remove_records(&ids).map_err(ReceiverError::from)?;
+
+        let batch = records.into_iter().zip(msgs).collect();
+        Backend::spawn(move || PayloadRecord::verify_batch(batch))
+            .await
+            .map_err(ReceiverError::from)?;
+
+        Ok(())
+    }
+}
+
+impl<BaseOT> Ready for Receiver<BaseOT> {
+    fn is_ready(&self) -> bool {
+        self.state.is_extension()
+    }
+}
+
+impl<BaseOT> Poison for Receiver<BaseOT> {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
 }
@@ -1,17 +1,23 @@
-use std::mem;
+use std::{mem, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use enum_try_as_inner::EnumTryAsInner;
 use futures::TryFutureExt;
 use itybity::IntoBits;
 use mpz_cointoss as cointoss;
-use mpz_common::{try_join, Allocate, Context, Preprocess};
+use mpz_common::{
+    beacon::{Beacon, ChallengeDeriver},
+    tag::{Side, Tag, TagBinding},
+    timeout::{timeout, Phase},
+    try_join, Allocate, Context, Poison, Preprocess, Ready,
+};
 use mpz_core::{prg::Prg, Block};
 use mpz_ot_core::{
     kos::{
         extension_matrix_size,
-        msgs::{Extend, StartExtend},
-        pad_ot_count, sender_state as state, Sender as SenderCore, SenderConfig, SenderKeys, CSP,
+        msgs::{Extend, ReceiverReveal, StartExtend},
+        pad_ot_count, round_ot_count, sender_state as state, Sender as SenderCore, SenderConfig,
+        SenderKeys, CSP,
     },
     OTSenderOutput, ROTSenderOutput,
 };
@@ -25,7 +31,7 @@ use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
 
 use crate::{
     kos::SenderError, CommittedOTReceiver, CommittedOTSender, OTError, OTReceiver, OTSender,
-    OTSetup, RandomOTSender,
+    OTSetup, RandomOTSender, VerifiableOTSender,
 };
 
 #[derive(Debug, EnumTryAsInner)]
@@ -37,13 +43,33 @@ pub(crate) enum State {
     Error,
 }
 
+impl State {
+    /// Takes the extension state, leaving the sender poisoned until it is restored.
+    fn take_extension(&mut self) -> Result<SenderCore<state::Extension>, SenderError> {
+        self.try_as_extension()?;
+
+        let State::Extension(sender) = mem::replace(self, State::Error) else {
+            unreachable!("state is extension");
+        };
+
+        Ok(sender)
+    }
+}
+
 /// KOS sender.
 #[derive(Debug)]
 pub struct Sender<BaseOT> {
+    tag: TagBinding,
     state: State,
     base: BaseOT,
     alloc: usize,
+    /// Whether there are extended OTs which have not been checked yet.
+    unchecked: bool,
     cointoss_sender: Option<cointoss::Sender<cointoss::sender_state::Received>>,
+    /// Derives the consistency check challenges.
+    challenges: ChallengeDeriver,
+    base_ot_timeout: Duration,
+    extension_timeout: Duration,
 }
 
 impl<BaseOT: Send> Sender<BaseOT> {
@@ -51,16 +77,29 @@ impl<BaseOT: Send> Sender<BaseOT> {
     ///
     /// # Arguments
     ///
+    /// * `tag` - The purpose of the instance.
     /// * `config` - The Sender's configuration
-    pub fn new(config: SenderConfig, base: BaseOT) -> Self {
+    pub fn new(tag: Tag, config: SenderConfig, base: BaseOT) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Sender),
+            base_ot_timeout: config.base_ot_timeout(),
+            extension_timeout: config.extension_timeout(),
             state: State::Initialized(SenderCore::new(config)),
             base,
             alloc: 0,
+            unchecked: false,
             cointoss_sender: None,
+            challenges: ChallengeDeriver::default(),
         }
     }
 
+    /// Sets a beacon whose randomness is mixed into the consistency check challenges.
+    ///
+    /// The other party must use the same beacon, see [`mpz_common::beacon`] for details.
+    pub fn set_beacon(&mut self, beacon: Arc<dyn Beacon>) {
+        self.challenges.set_beacon(beacon);
+    }
+
     /// The number of remaining OTs which can be consumed.
     pub fn remaining(&self) -> Result<usize, SenderError> {
         Ok(self.state.try_as_extension()?.remaining())
@@ -95,7 +134,12 @@ impl<BaseOT: Send> Sender<BaseOT> {
             ));
         }
 
-        self._setup_with_delta(ctx, delta).await
+        timeout(
+            Phase::BaseOT,
+            self.base_ot_timeout,
+            self._setup_with_delta(ctx, delta),
+        )
+        .await?
     }
 
     async fn _setup_with_delta<Ctx: Context>(
@@ -106,6 +150,8 @@ impl<BaseOT: Send> Sender<BaseOT> {
     where
         BaseOT: OTReceiver<Ctx, bool, Block>,
     {
+        self.tag.bind(ctx).await?;
+
         let ext_sender = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
 
         let choices = delta.into_lsb0_vec();
@@ -133,15 +179,55 @@ impl<BaseOT: Send> Sender<BaseOT> {
         &mut self,
         ctx: &mut Ctx,
         count: usize,
+    ) -> Result<(), SenderError> {
+        self.extend_deferred(ctx, count).await?;
+        self.check(ctx).await
+    }
+
+    /// Performs OT extension, deferring the consistency check.
+    ///
+    /// Multiple deferred extensions can be performed before a single consistency check is run
+    /// with [`Sender::check`], which amortizes the cost of the check. The extended OTs are not
+    /// available until the check has been performed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), SenderError> {
+        timeout(
+            Phase::Extension,
+            self.extension_timeout,
+            self._extend_deferred(ctx, count),
+        )
+        .await?
+    }
+
+    async fn _extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
     ) -> Result<(), SenderError> {
         let mut ext_sender =
             std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
 
-        let count = pad_ot_count(count);
+        // The OTs sacrificed by the consistency check only need to be added once per check.
+        let count = if self.unchecked {
+            round_ot_count(count)
+        } else {
+            pad_ot_count(count)
+        };
+
+        // Give up waiting on the receiver if the thread is cancelled.
+        let cancel = ctx.cancel_token().clone();
 
         let StartExtend {
             count: receiver_count,
-        } = ctx.io_mut().expect_next().await?;
+        } = cancel.run(ctx.io_mut().expect_next()).await??;
 
         if count != receiver_count {
             return Err(SenderError::ConfigError(
@@ -156,18 +242,42 @@ impl<BaseOT: Send> Sender<BaseOT> {
 
         // Receive extension matrix from the receiver.
         while extend.us.len() < expected_us {
-            let Extend { us: chunk } = ctx.io_mut().expect_next().await?;
+            let Extend { us: chunk } = cancel.run(ctx.io_mut().expect_next()).await??;
 
             extend.us.extend(chunk);
         }
 
         // Extend the OTs.
-        let mut ext_sender =
+        let ext_sender =
             Backend::spawn(move || ext_sender.extend(count, extend).map(|_| ext_sender)).await?;
 
+        self.state = State::Extension(ext_sender);
+        self.unchecked = true;
+
+        Ok(())
+    }
+
+    /// Performs the consistency check for all deferred extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError> {
+        timeout(Phase::Extension, self.extension_timeout, self._check(ctx)).await?
+    }
+
+    async fn _check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError> {
+        let mut ext_sender =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
         // Sample chi_seed with coin-toss.
         let seed: Block = thread_rng().gen();
         let chi_seed = cointoss::cointoss_receiver(ctx, vec![seed]).await?[0];
+        let chi_seed = self.tag.tag().bind_seed("kos/check", chi_seed);
+        let chi_seed = self
+            .challenges
+            .derive(self.tag.tag(), "kos/check", chi_seed)
+            .await?;
 
         // Receive the receiver's check.
         let receiver_check = ctx.io_mut().expect_next().await?;
@@ -181,6 +291,7 @@ impl<BaseOT: Send> Sender<BaseOT> {
         .await?;
 
         self.state = State::Extension(ext_sender);
+        self.unchecked = false;
 
         Ok(())
     }
@@ -223,6 +334,18 @@ where
             return Ok(());
         }
 
+        timeout(Phase::BaseOT, self.base_ot_timeout, self._setup(ctx)).await?
+    }
+}
+
+impl<BaseOT: Send> Sender<BaseOT> {
+    async fn _setup<Ctx>(&mut self, ctx: &mut Ctx) -> Result<(), OTError>
+    where
+        Ctx: Context,
+        BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block>,
+    {
+        self.tag.bind(ctx).await?;
+
         let sender = std::mem::replace(&mut self.state, State::Error)
             .try_into_initialized()
             .map_err(SenderError::from)?;
@@ -248,7 +371,7 @@ where
             // Store the sender to finalize the cointoss protocol later.
             self.cointoss_sender = Some(cointoss_sender);
 
-            seeds[0]
+            self.tag.tag().bind_seed("kos/delta", seeds[0])
         } else {
             self.base.setup(ctx).await?;
             Block::random(&mut thread_rng())
@@ -301,28 +424,34 @@ where
         ctx: &mut Ctx,
         msgs: &[[Block; 2]],
     ) -> Result<OTSenderOutput, OTError> {
-        let sender = self
-            .state
-            .try_as_extension_mut()
-            .map_err(SenderError::from)?;
-
-        let derandomize = ctx.io_mut().expect_next().await?;
-
-        let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
-        sender_keys
-            .derandomize(derandomize)
-            .map_err(SenderError::from)?;
-        let payload = sender_keys
-            .encrypt_blocks(msgs)
-            .map_err(SenderError::from)?;
-        let id = payload.id;
+        let mut sender = self.state.take_extension()?;
+
+        // The state is only restored once the round completes, so dropping this future
+        // part way through poisons the sender.
+        let output: Result<_, OTError> = async {
+            let derandomize = ctx.io_mut().expect_next().await?;
+
+            let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
+            sender_keys
+                .derandomize(derandomize)
+                .map_err(SenderError::from)?;
+            let payload = sender_keys
+                .encrypt_blocks(msgs)
+                .map_err(SenderError::from)?;
+            let id = payload.id;
+
+            ctx.io_mut()
+                .send(payload)
+                .await
+                .map_err(SenderError::from)?;
+
+            Ok(OTSenderOutput { id })
+        }
+        .await;
 
-        ctx.io_mut()
-            .send(payload)
-            .await
-            .map_err(SenderError::from)?;
+        self.state = State::Extension(sender);
 
-        Ok(OTSenderOutput { id })
+        output
     }
 }
 
@@ -337,26 +466,32 @@ where
         ctx: &mut Ctx,
         msgs: &[[[u8; N]; 2]],
     ) -> Result<OTSenderOutput, OTError> {
-        let sender = self
-            .state
-            .try_as_extension_mut()
-            .map_err(SenderError::from)?;
-
-        let derandomize = ctx.io_mut().expect_next().await?;
+        let mut sender = self.state.take_extension()?;
+
+        // The state is only restored once the round completes, so dropping this future
+        // part way through poisons the sender.
+        let output: Result<_, OTError> = async {
+            let derandomize = ctx.io_mut().expect_next().await?;
+
+            let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
+            sender_keys
+                .derandomize(derandomize)
+                .map_err(SenderError::from)?;
+            let payload = sender_keys.encrypt_bytes(msgs).map_err(SenderError::from)?;
+            let id = payload.id;
+
+            ctx.io_mut()
+                .send(payload)
+                .await
+                .map_err(SenderError::from)?;
+
+            Ok(OTSenderOutput { id })
+        }
+        .await;
 
-        let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
-        sender_keys
-            .derandomize(derandomize)
-            .map_err(SenderError::from)?;
-        let payload = sender_keys.encrypt_bytes(msgs).map_err(SenderError::from)?;
-        let id = payload.id;
+        self.state = State::Extension(sender);
 
-        ctx.io_mut()
-            .send(payload)
-            .await
-            .map_err(SenderError::from)?;
-
-        Ok(OTSenderOutput { id })
+        output
     }
 }
 
@@ -405,3 +540,37 @@ where
         self.reveal(ctx).await.map_err(OTError::from)
     }
 }
+
+#[async_trait]
+impl<Ctx, BaseOT> VerifiableOTSender<Ctx, bool, [Block; 2]> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn verify_choices(&mut self, ctx: &mut Ctx) -> Result<Vec<bool>, OTError> {
+        let sender = std::mem::replace(&mut self.state, State::Error)
+            .try_into_extension()
+            .map_err(SenderError::from)?;
+
+        let reveal: ReceiverReveal = ctx.io_mut().expect_next().await?;
+
+        let choices = Backend::spawn(move || sender.verify_choices(reveal)).await?;
+
+        // This sender is no longer usable, so mark it as complete.
+        self.state = State::Complete;
+
+        Ok(choices)
+    }
+}
+
+impl<BaseOT> Ready for Sender<BaseOT> {
+    fn is_ready(&self) -> bool {
+        self.state.is_extension()
+    }
+}
+
+impl<BaseOT> Poison for Sender<BaseOT> {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
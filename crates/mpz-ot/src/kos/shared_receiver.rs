@@ -2,9 +2,12 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use itybity::IntoBitIterator;
-use mpz_common::{sync::AsyncMutex, Allocate, Context, Preprocess};
+use mpz_common::{sync::AsyncMutex, Allocate, Context, Poison, Preprocess, Ready};
 use mpz_core::Block;
-use mpz_ot_core::{kos::msgs::SenderPayload, OTReceiverOutput, ROTReceiverOutput, TransferId};
+use mpz_ot_core::{
+    kos::{msgs::SenderPayload, PayloadRecord},
+    OTReceiverOutput, ROTReceiverOutput, TransferId,
+};
 use rand::distributions::{Distribution, Standard};
 use serio::{stream::IoStreamExt, SinkExt};
 use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
@@ -39,6 +42,18 @@ impl<BaseOT> SharedReceiver<BaseOT> {
     }
 }
 
+impl<BaseOT> Ready for SharedReceiver<BaseOT> {
+    fn is_ready(&self) -> bool {
+        self.inner.blocking_lock_unsync().is_ready()
+    }
+}
+
+impl<BaseOT> Poison for SharedReceiver<BaseOT> {
+    fn is_poisoned(&self) -> bool {
+        self.inner.blocking_lock_unsync().is_poisoned()
+    }
+}
+
 impl<BaseOT> Allocate for SharedReceiver<BaseOT> {
     fn alloc(&mut self, count: usize) {
         self.inner.blocking_lock_unsync().alloc(count);
@@ -136,4 +151,26 @@ where
 
         Ok(())
     }
+
+    async fn verify_batch(
+        &mut self,
+        _ctx: &mut Ctx,
+        batch: Vec<(TransferId, Vec<[Block; 2]>)>,
+    ) -> Result<(), OTError> {
+        let (ids, msgs): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        let records = {
+            let inner = self.inner.blocking_lock_unsync();
+
+            let receiver = inner.state().try_as_verify().map_err(ReceiverError::from)?;
+
+            receiver.remove_records(&ids).map_err(ReceiverError::from)?
+        };
+
+        let batch = records.into_iter().zip(msgs).collect();
+        Backend::spawn(move || PayloadRecord::verify_batch(batch))
+            .await
+            .map_err(ReceiverError::from)?;
+
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use mpz_common::{sync::AsyncMutex, Allocate, Context, Preprocess};
+use mpz_common::{sync::AsyncMutex, Allocate, Context, Poison, Preprocess, Ready};
 use mpz_core::Block;
 use rand::distributions::{Distribution, Standard};
 use serio::{stream::IoStreamExt as _, SinkExt as _};
@@ -37,6 +37,18 @@ impl<BaseOT> SharedSender<BaseOT> {
     }
 }
 
+impl<BaseOT> Ready for SharedSender<BaseOT> {
+    fn is_ready(&self) -> bool {
+        self.inner.blocking_lock_unsync().is_ready()
+    }
+}
+
+impl<BaseOT> Poison for SharedSender<BaseOT> {
+    fn is_poisoned(&self) -> bool {
+        self.inner.blocking_lock_unsync().is_poisoned()
+    }
+}
+
 impl<BaseOT> Allocate for SharedSender<BaseOT> {
     fn alloc(&mut self, count: usize) {
         self.inner.blocking_lock_unsync().alloc(count);
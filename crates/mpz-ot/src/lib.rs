@@ -10,13 +10,19 @@
 )]
 
 pub mod chou_orlandi;
+pub mod derandomize;
+pub mod ferret;
+pub mod gbf;
 #[cfg(any(test, feature = "ideal"))]
 pub mod ideal;
 pub mod kos;
+mod rerandomize;
+pub mod softspoken;
 
 use async_trait::async_trait;
 
 pub use mpz_ot_core::{
+    packed::{MessageArena, PackedChoices, PackedRCOTReceiverOutput, PackedROTReceiverOutput},
     COTReceiverOutput, COTSenderOutput, OTReceiverOutput, OTSenderOutput, RCOTReceiverOutput,
     RCOTSenderOutput, ROTReceiverOutput, ROTSenderOutput, TransferId,
 };
@@ -31,6 +37,12 @@ pub enum OTError {
     Context(#[from] mpz_common::ContextError),
     #[error("mutex error: {0}")]
     Mutex(#[from] mpz_common::sync::MutexError),
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Cancelled(#[from] mpz_common::cancel::CancelledError),
+    #[error(transparent)]
+    Tag(#[from] mpz_common::tag::TagError),
     #[error("sender error: {0}")]
     SenderError(Box<dyn std::error::Error + Send + Sync>),
     #[error("receiver error: {0}")]
@@ -178,6 +190,50 @@ pub trait RandomCOTReceiver<Ctx, T, U> {
     ) -> Result<RCOTReceiverOutput<T, U>, OTError>;
 }
 
+/// A random correlated oblivious transfer sender which can re-randomize its outputs under a new
+/// global correlation.
+///
+/// See [`mpz_ot_core::rerandomize`] for the construction and its security requirements.
+#[async_trait]
+pub trait RerandomizeCOTSender<Ctx, T>: RandomCOTSender<Ctx, T> {
+    /// Re-randomizes random COTs under `new_delta`, returning the new COTs.
+    ///
+    /// The original COTs must have been produced by this sender, and must not be used again.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `new_delta` - The correlation of the new COTs.
+    /// * `output` - The original COTs.
+    async fn rerandomize_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        new_delta: T,
+        output: RCOTSenderOutput<T>,
+    ) -> Result<RCOTSenderOutput<T>, OTError>;
+}
+
+/// A random correlated oblivious transfer receiver which can re-randomize its outputs under a
+/// new global correlation chosen by the sender.
+///
+/// See [`mpz_ot_core::rerandomize`] for the construction and its security requirements.
+#[async_trait]
+pub trait RerandomizeCOTReceiver<Ctx, T, U>: RandomCOTReceiver<Ctx, T, U> {
+    /// Re-randomizes random COTs, returning the new COTs with the same choices.
+    ///
+    /// The original COTs must have been produced by this receiver, and must not be used again.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `output` - The original COTs.
+    async fn rerandomize_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        output: RCOTReceiverOutput<T, U>,
+    ) -> Result<RCOTReceiverOutput<T, U>, OTError>;
+}
+
 /// An oblivious transfer sender that is committed to its messages and can reveal them
 /// to the receiver to verify them.
 #[async_trait]
@@ -239,4 +295,29 @@ pub trait VerifiableOTReceiver<Ctx, T, U, V>: OTReceiver<Ctx, T, U> {
     /// * `id` - The transfer id of the messages to verify.
     /// * `msgs` - The purported messages sent by the sender.
     async fn verify(&mut self, ctx: &mut Ctx, id: TransferId, msgs: &[V]) -> Result<(), OTError>;
+
+    /// Verifies purported messages of many transfers at once.
+    ///
+    /// The default implementation verifies each transfer in order. Implementations should
+    /// override this to verify the whole batch in a single pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `batch` - The transfer ids and the purported messages sent by the sender.
+    async fn verify_batch(
+        &mut self,
+        ctx: &mut Ctx,
+        batch: Vec<(TransferId, Vec<V>)>,
+    ) -> Result<(), OTError>
+    where
+        Ctx: Send,
+        V: Send + 'static,
+    {
+        for (id, msgs) in batch {
+            self.verify(ctx, id, &msgs).await?;
+        }
+
+        Ok(())
+    }
 }
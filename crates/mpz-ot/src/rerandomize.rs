@@ -0,0 +1,36 @@
+//! Shared implementation of COT re-randomization.
+
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot_core::{
+    msgs::Rerandomize,
+    rerandomize::{rerandomize_receiver, rerandomize_sender},
+    RCOTReceiverOutput, RCOTSenderOutput,
+};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::OTError;
+
+/// Re-randomizes the sender's COTs and sends the corrections to the receiver.
+pub(crate) async fn send<Ctx: Context>(
+    ctx: &mut Ctx,
+    delta: Block,
+    new_delta: Block,
+    output: RCOTSenderOutput<Block>,
+) -> Result<RCOTSenderOutput<Block>, OTError> {
+    let (output, msg) = rerandomize_sender(delta, new_delta, output);
+
+    ctx.io_mut().send(msg).await?;
+
+    Ok(output)
+}
+
+/// Receives the sender's corrections and re-randomizes the receiver's COTs.
+pub(crate) async fn receive<Ctx: Context>(
+    ctx: &mut Ctx,
+    output: RCOTReceiverOutput<bool, Block>,
+) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+    let msg: Rerandomize = ctx.io_mut().expect_next().await?;
+
+    rerandomize_receiver(output, msg).map_err(|e| OTError::ReceiverError(Box::new(e)))
+}
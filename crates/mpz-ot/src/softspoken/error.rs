@@ -0,0 +1,74 @@
+use crate::OTError;
+
+/// A SoftSpokenOT sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::softspoken::SenderError),
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    Tag(#[from] mpz_common::tag::TagError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("coin-toss error: {0}")]
+    CointossError(#[from] mpz_cointoss::CointossError),
+    #[error("{0}")]
+    StateError(String),
+    #[error("configuration error: {0}")]
+    ConfigError(String),
+}
+
+impl From<SenderError> for OTError {
+    fn from(err: SenderError) -> Self {
+        match err {
+            SenderError::IOError(e) => e.into(),
+            SenderError::Timeout(e) => e.into(),
+            SenderError::Tag(e) => e.into(),
+            e => OTError::SenderError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::softspoken::SenderStateError> for SenderError {
+    fn from(err: crate::softspoken::SenderStateError) -> Self {
+        SenderError::StateError(err.to_string())
+    }
+}
+
+/// A SoftSpokenOT receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_ot_core::softspoken::ReceiverError),
+    #[error(transparent)]
+    Timeout(#[from] mpz_common::timeout::TimeoutError),
+    #[error(transparent)]
+    BaseOTError(#[from] crate::OTError),
+    #[error("coin-toss error: {0}")]
+    CointossError(#[from] mpz_cointoss::CointossError),
+    #[error("{0}")]
+    StateError(String),
+}
+
+impl From<ReceiverError> for OTError {
+    fn from(err: ReceiverError) -> Self {
+        match err {
+            ReceiverError::IOError(e) => e.into(),
+            ReceiverError::Timeout(e) => e.into(),
+            e => OTError::ReceiverError(Box::new(e)),
+        }
+    }
+}
+
+impl From<crate::softspoken::ReceiverStateError> for ReceiverError {
+    fn from(err: crate::softspoken::ReceiverStateError) -> Self {
+        ReceiverError::StateError(err.to_string())
+    }
+}
@@ -0,0 +1,106 @@
+//! An implementation of the [`SoftSpokenOT`](https://eprint.iacr.org/2022/192) oblivious transfer
+//! extension protocol.
+//!
+//! SoftSpokenOT provides random correlated OTs with a tunable trade-off between communication and
+//! computation, controlled by the `k` parameter of the configuration. It only relies on the same
+//! assumptions as KOS15, unlike Ferret which additionally relies on LPN.
+
+mod error;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_ot_core::softspoken::{
+    msgs, ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, SenderConfig,
+    SenderConfigBuilder, SenderConfigBuilderError, DEFAULT_K,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use futures::TryFutureExt;
+    use mpz_common::{executor::test_st_executor, tag::Tag, Context, Ready};
+    use mpz_core::Block;
+
+    use crate::{
+        ideal::ot::{ideal_ot, IdealOTReceiver, IdealOTSender},
+        OTError, OTSetup, RandomCOTReceiver, RandomCOTSender,
+    };
+
+    async fn setup<Ctx: Context>(
+        k: usize,
+        ctx_sender: &mut Ctx,
+        ctx_receiver: &mut Ctx,
+        count: usize,
+    ) -> (
+        Sender<IdealOTReceiver<Block>>,
+        Receiver<IdealOTSender<[Block; 2]>>,
+    ) {
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(
+            Tag::new("test"),
+            SenderConfig::builder().k(k).build().unwrap(),
+            base_receiver,
+        );
+        let mut receiver = Receiver::new(
+            Tag::new("test"),
+            ReceiverConfig::builder().k(k).build().unwrap(),
+            base_sender,
+        );
+
+        assert!(!sender.is_ready());
+        assert!(!receiver.is_ready());
+
+        tokio::try_join!(sender.setup(ctx_sender), receiver.setup(ctx_receiver)).unwrap();
+
+        assert!(sender.is_ready());
+        assert!(receiver.is_ready());
+
+        tokio::try_join!(
+            sender.extend(ctx_sender, count).map_err(OTError::from),
+            receiver.extend(ctx_receiver, count).map_err(OTError::from)
+        )
+        .unwrap();
+
+        (sender, receiver)
+    }
+
+    #[rstest]
+    #[case::k2(2)]
+    #[case::k4(4)]
+    #[case::k8(8)]
+    #[tokio::test]
+    async fn test_softspoken_rcot(#[case] k: usize) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(k, &mut ctx_sender, &mut ctx_receiver, 1000).await;
+
+        let delta = sender.delta().unwrap();
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            RandomCOTSender::<_, Block>::send_random_correlated(&mut sender, &mut ctx_sender, 1000),
+            RandomCOTReceiver::<_, bool, Block>::receive_random_correlated(
+                &mut receiver,
+                &mut ctx_receiver,
+                1000
+            )
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert!(output_sender
+            .msgs
+            .into_iter()
+            .zip(output_receiver.choices)
+            .zip(output_receiver.msgs)
+            .all(|((q, x), t)| if x { q ^ delta == t } else { q == t }));
+    }
+}
@@ -0,0 +1,304 @@
+use std::{mem, time::Duration};
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_cointoss as cointoss;
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    timeout::{timeout, Phase},
+    Allocate, Context, Poison, Preprocess, Ready,
+};
+use mpz_core::Block;
+use mpz_ot_core::{
+    softspoken::{
+        msgs::{Extend, StartExtend},
+        pad_ot_count, receiver_state as state, round_ot_count, Receiver as ReceiverCore,
+        ReceiverConfig,
+    },
+    RCOTReceiverOutput,
+};
+use rand::{thread_rng, Rng};
+use serio::SinkExt as _;
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    kos::EXTEND_CHUNK_SIZE, rerandomize, softspoken::ReceiverError, OTError, OTSender, OTSetup,
+    RandomCOTReceiver, RerandomizeCOTReceiver,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(Box<ReceiverCore<state::Extension>>),
+    Error,
+}
+
+/// SoftSpokenOT receiver.
+#[derive(Debug)]
+pub struct Receiver<BaseOT> {
+    tag: TagBinding,
+    state: State,
+    base: BaseOT,
+    alloc: usize,
+    /// Whether there are extended OTs which have not been checked yet.
+    unchecked: bool,
+    base_ot_timeout: Duration,
+    extension_timeout: Duration,
+}
+
+impl<BaseOT> Receiver<BaseOT>
+where
+    BaseOT: Send,
+{
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The purpose of the instance.
+    /// * `config` - The receiver's configuration
+    /// * `base` - The base OT sender.
+    pub fn new(tag: Tag, config: ReceiverConfig, base: BaseOT) -> Self {
+        Self {
+            tag: TagBinding::new(tag, Side::Receiver),
+            base_ot_timeout: config.base_ot_timeout(),
+            extension_timeout: config.extension_timeout(),
+            state: State::Initialized(ReceiverCore::new(config)),
+            base,
+            alloc: 0,
+            unchecked: false,
+        }
+    }
+
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> Result<usize, ReceiverError> {
+        Ok(self.state.try_as_extension()?.remaining())
+    }
+
+    /// Performs OT extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), ReceiverError> {
+        self.extend_deferred(ctx, count).await?;
+        self.check(ctx).await
+    }
+
+    /// Performs OT extension, deferring the consistency check.
+    ///
+    /// See [`Receiver::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), ReceiverError> {
+        timeout(
+            Phase::Extension,
+            self.extension_timeout,
+            self._extend_deferred(ctx, count),
+        )
+        .await?
+    }
+
+    async fn _extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), ReceiverError> {
+        let mut ext_receiver =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        // The OTs sacrificed by the consistency check only need to be added once per check.
+        let count = if self.unchecked {
+            round_ot_count(count)
+        } else {
+            pad_ot_count(count)
+        };
+
+        // Extend the OTs.
+        let (ext_receiver, extend) = Backend::spawn(move || {
+            ext_receiver
+                .extend(count)
+                .map(|extend| (ext_receiver, extend))
+        })
+        .await?;
+
+        // Send the extend message.
+        ctx.io_mut().feed(StartExtend { count }).await?;
+        for chunk in extend.us.chunks(EXTEND_CHUNK_SIZE) {
+            ctx.io_mut().feed(Extend { us: chunk.to_vec() }).await?;
+        }
+        ctx.io_mut().flush().await?;
+
+        self.state = State::Extension(ext_receiver);
+        self.unchecked = true;
+
+        Ok(())
+    }
+
+    /// Performs the consistency check for all deferred extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError> {
+        timeout(Phase::Extension, self.extension_timeout, self._check(ctx)).await?
+    }
+
+    async fn _check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError> {
+        let mut ext_receiver =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        // Sample chi_seed with coin-toss.
+        let seed = thread_rng().gen();
+        let chi_seed = cointoss::cointoss_sender(ctx, vec![seed]).await?[0];
+        let chi_seed = self.tag.tag().bind_seed("softspoken/check", chi_seed);
+
+        // Compute consistency check.
+        let (ext_receiver, check) = Backend::spawn(move || {
+            ext_receiver
+                .check(chi_seed)
+                .map(|check| (ext_receiver, check))
+        })
+        .await?;
+
+        // Send correlation check value.
+        ctx.io_mut().send(check).await?;
+
+        self.state = State::Extension(ext_receiver);
+        self.unchecked = false;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> OTSetup<Ctx> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTSender<Ctx, [Block; 2]> + Send + 'static,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        timeout(Phase::BaseOT, self.base_ot_timeout, self._setup(ctx)).await?
+    }
+}
+
+impl<BaseOT: Send> Receiver<BaseOT> {
+    async fn _setup<Ctx>(&mut self, ctx: &mut Ctx) -> Result<(), OTError>
+    where
+        Ctx: Context,
+        BaseOT: OTSetup<Ctx> + OTSender<Ctx, [Block; 2]>,
+    {
+        self.tag.bind(ctx).await?;
+
+        let receiver = std::mem::replace(&mut self.state, State::Error)
+            .try_into_initialized()
+            .map_err(ReceiverError::from)?;
+
+        self.base.setup(ctx).await?;
+
+        // Generate the GGM trees and send the punctured keys to the sender.
+        let (ext_receiver, base_msgs) = receiver.setup(thread_rng().gen());
+
+        self.base.send(ctx, &base_msgs).await?;
+
+        self.state = State::Extension(Box::new(ext_receiver));
+
+        Ok(())
+    }
+}
+
+impl<BaseOT> Allocate for Receiver<BaseOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> Preprocess<Ctx> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTSender<Ctx, [Block; 2]> + Send + 'static,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        if count == 0 {
+            return Ok(());
+        }
+
+        self.extend(ctx, count).await.map_err(OTError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> RandomCOTReceiver<Ctx, bool, Block> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn receive_random_correlated(
+        &mut self,
+        _ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+        let receiver = self
+            .state
+            .try_as_extension_mut()
+            .map_err(ReceiverError::from)?;
+
+        receiver
+            .random_correlated(count)
+            .map_err(ReceiverError::from)
+            .map_err(OTError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> RerandomizeCOTReceiver<Ctx, bool, Block> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn rerandomize_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        output: RCOTReceiverOutput<bool, Block>,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+        self.state.try_as_extension().map_err(ReceiverError::from)?;
+
+        rerandomize::receive(ctx, output).await
+    }
+}
+
+impl<BaseOT> Ready for Receiver<BaseOT> {
+    fn is_ready(&self) -> bool {
+        self.state.is_extension()
+    }
+}
+
+impl<BaseOT> Poison for Receiver<BaseOT> {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
@@ -0,0 +1,352 @@
+use std::{mem, time::Duration};
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_cointoss as cointoss;
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    timeout::{timeout, Phase},
+    Allocate, Context, Poison, Preprocess, Ready,
+};
+use mpz_core::Block;
+use mpz_ot_core::{
+    softspoken::{
+        extension_message_size,
+        msgs::{Extend, StartExtend},
+        pad_ot_count, round_ot_count, sender_state as state, Sender as SenderCore, SenderConfig,
+        CSP,
+    },
+    RCOTSenderOutput,
+};
+use rand::{thread_rng, Rng};
+use serio::stream::IoStreamExt as _;
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    rerandomize, softspoken::SenderError, OTError, OTReceiver, OTSetup, RandomCOTSender,
+    RerandomizeCOTSender,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(SenderCore<state::Extension>),
+    Error,
+}
+
+/// SoftSpokenOT sender.
+#[derive(Debug)]
+pub struct Sender<BaseOT> {
+    tag: TagBinding,
+    state: State,
+    base: BaseOT,
+    alloc: usize,
+    /// Whether there are extended OTs which have not been checked yet.
+    unchecked: bool,
+    base_ot_timeout: Duration,
+    extension_timeout: Duration,
+}
+
+impl<BaseOT: Send> Sender<BaseOT> {
+    /// Creates a new Sender
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The purpose of the instance.
+    /// * `config` - The Sender's configuration
+    /// * `base` - The base OT receiver.
+    pub fn new(tag: Tag, config: SenderConfig, base: BaseOT) -> Self {
+        Self {
+            tag: TagBinding::new(tag, Side::Sender),
+            base_ot_timeout: config.base_ot_timeout(),
+            extension_timeout: config.extension_timeout(),
+            state: State::Initialized(SenderCore::new(config)),
+            base,
+            alloc: 0,
+            unchecked: false,
+        }
+    }
+
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> Result<usize, SenderError> {
+        Ok(self.state.try_as_extension()?.remaining())
+    }
+
+    /// Returns the sender's correlation.
+    pub fn delta(&self) -> Result<Block, SenderError> {
+        Ok(self.state.try_as_extension()?.delta())
+    }
+
+    /// Performs the base OT setup with the provided delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `delta` - The delta value to use for the base OT setup.
+    pub async fn setup_with_delta<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        delta: Block,
+    ) -> Result<(), SenderError>
+    where
+        BaseOT: OTReceiver<Ctx, bool, Block>,
+    {
+        timeout(
+            Phase::BaseOT,
+            self.base_ot_timeout,
+            self._setup_with_delta(ctx, delta),
+        )
+        .await?
+    }
+
+    async fn _setup_with_delta<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        delta: Block,
+    ) -> Result<(), SenderError>
+    where
+        BaseOT: OTReceiver<Ctx, bool, Block>,
+    {
+        self.tag.bind(ctx).await?;
+
+        let ext_sender = std::mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        let choices = ext_sender.base_choices(delta);
+        let base_output = self.base.receive(ctx, &choices).await?;
+
+        let seeds: [Block; CSP] = base_output
+            .msgs
+            .try_into()
+            .expect("seeds should be CSP length");
+
+        let ext_sender = ext_sender.setup(delta, seeds);
+
+        self.state = State::Extension(ext_sender);
+
+        Ok(())
+    }
+
+    /// Performs OT extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), SenderError> {
+        self.extend_deferred(ctx, count).await?;
+        self.check(ctx).await
+    }
+
+    /// Performs OT extension, deferring the consistency check.
+    ///
+    /// See [`Sender::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), SenderError> {
+        timeout(
+            Phase::Extension,
+            self.extension_timeout,
+            self._extend_deferred(ctx, count),
+        )
+        .await?
+    }
+
+    async fn _extend_deferred<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), SenderError> {
+        let mut ext_sender =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        // The OTs sacrificed by the consistency check only need to be added once per check.
+        let count = if self.unchecked {
+            round_ot_count(count)
+        } else {
+            pad_ot_count(count)
+        };
+
+        let StartExtend {
+            count: receiver_count,
+        } = ctx.io_mut().expect_next().await?;
+
+        if count != receiver_count {
+            return Err(SenderError::ConfigError(
+                "sender and receiver count mismatch".to_string(),
+            ));
+        }
+
+        let expected_us = extension_message_size(ext_sender.config().k(), count);
+        let mut extend = Extend {
+            us: Vec::with_capacity(expected_us),
+        };
+
+        // Receive the corrections from the receiver.
+        while extend.us.len() < expected_us {
+            let Extend { us: chunk } = ctx.io_mut().expect_next().await?;
+
+            extend.us.extend(chunk);
+        }
+
+        // Extend the OTs.
+        let ext_sender =
+            Backend::spawn(move || ext_sender.extend(count, extend).map(|_| ext_sender)).await?;
+
+        self.state = State::Extension(ext_sender);
+        self.unchecked = true;
+
+        Ok(())
+    }
+
+    /// Performs the consistency check for all deferred extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError> {
+        timeout(Phase::Extension, self.extension_timeout, self._check(ctx)).await?
+    }
+
+    async fn _check<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError> {
+        let mut ext_sender =
+            std::mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        // Sample chi_seed with coin-toss.
+        let seed: Block = thread_rng().gen();
+        let chi_seed = cointoss::cointoss_receiver(ctx, vec![seed]).await?[0];
+        let chi_seed = self.tag.tag().bind_seed("softspoken/check", chi_seed);
+
+        // Receive the receiver's check.
+        let receiver_check = ctx.io_mut().expect_next().await?;
+
+        // Check consistency of extension.
+        let ext_sender = Backend::spawn(move || {
+            ext_sender
+                .check(chi_seed, receiver_check)
+                .map(|_| ext_sender)
+        })
+        .await?;
+
+        self.state = State::Extension(ext_sender);
+        self.unchecked = false;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> OTSetup<Ctx> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block> + Send + 'static,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        let delta = Block::random(&mut thread_rng());
+
+        timeout(Phase::BaseOT, self.base_ot_timeout, async {
+            self.tag.bind(ctx).await?;
+            self.base.setup(ctx).await?;
+            self._setup_with_delta(ctx, delta)
+                .await
+                .map_err(OTError::from)
+        })
+        .await?
+    }
+}
+
+impl<BaseOT> Allocate for Sender<BaseOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> Preprocess<Ctx> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block> + Send + 'static,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        if count == 0 {
+            return Ok(());
+        }
+
+        self.extend(ctx, count).await.map_err(OTError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> RandomCOTSender<Ctx, Block> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn send_random_correlated(
+        &mut self,
+        _ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        let sender = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?;
+
+        sender
+            .random_correlated(count)
+            .map_err(SenderError::from)
+            .map_err(OTError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> RerandomizeCOTSender<Ctx, Block> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn rerandomize_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        new_delta: Block,
+        output: RCOTSenderOutput<Block>,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        let delta = self.delta()?;
+
+        rerandomize::send(ctx, delta, new_delta, output).await
+    }
+}
+
+impl<BaseOT> Ready for Sender<BaseOT> {
+    fn is_ready(&self) -> bool {
+        self.state.is_extension()
+    }
+}
+
+impl<BaseOT> Poison for Sender<BaseOT> {
+    fn is_poisoned(&self) -> bool {
+        self.state.is_error()
+    }
+}
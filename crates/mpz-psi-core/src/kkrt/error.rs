@@ -0,0 +1,27 @@
+//! Errors that can occur when using the KKRT protocol.
+
+use mpz_ot_core::ferret::cuckoo::CuckooHashError;
+
+/// Errors that can occur when using the KKRT sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("the set contains duplicate items")]
+    DuplicateItem,
+}
+
+/// Errors that can occur when using the KKRT receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("the set contains duplicate items")]
+    DuplicateItem,
+    #[error("the set is too large: {0} items")]
+    TooManyItems(usize),
+    #[error("failed to insert the items into the cuckoo hash table: {0}")]
+    CuckooHash(#[from] CuckooHashError),
+    #[error("invalid number of OPRF outputs: expected {expected}, got {actual}")]
+    InvalidOPRFOutputs { expected: usize, actual: usize },
+    #[error("invalid number of OPRF output sets: expected {expected}, got {actual}")]
+    InvalidOutputs { expected: usize, actual: usize },
+}
@@ -0,0 +1,236 @@
+//! Private set intersection based on the batched OPRF of
+//! [KKRT](https://eprint.iacr.org/2016/799), secure against semi-honest adversaries.
+//!
+//! The receiver places its items into a Cuckoo hash table with [`CUCKOO_HASH_NUM`] hash functions,
+//! and evaluates one OPRF instance for each bin on the item in it, or on a random input if the bin
+//! is empty. The item is tweaked with the hash function which placed it, so that items placed by
+//! different hash functions into the same bin can not match.
+//!
+//! The sender evaluates the OPRF instance of each bin one of its items could have been placed in,
+//! and sends the outputs, grouped by hash function and shuffled. An item of the receiver is in the
+//! intersection if the output of its bin is among the outputs sent for its hash function.
+//!
+//! Both sets must not contain duplicates. The sender learns nothing but the size of the receiver's
+//! set, and the receiver learns the intersection and the size of the sender's set.
+
+pub mod error;
+pub mod msgs;
+pub mod receiver;
+pub mod sender;
+
+use std::sync::Arc;
+
+use mpz_core::{aes::AesEncryptor, prg::Prg, Block};
+use mpz_ot_core::ferret::cuckoo::CuckooHash;
+use rand::SeedableRng;
+
+pub use error::{ReceiverError, SenderError};
+pub use mpz_ot_core::ferret::CUCKOO_HASH_NUM;
+pub use receiver::{state as receiver_state, Receiver};
+pub use sender::Sender;
+
+/// Public parameters of an execution, derived from a seed chosen by the receiver.
+pub(crate) struct Params {
+    cuckoo: CuckooHash,
+    tweaks: [AesEncryptor; CUCKOO_HASH_NUM],
+}
+
+impl Params {
+    pub(crate) fn new(seed: Block) -> Self {
+        let mut prg = Prg::from_seed(seed);
+
+        let hashes = Arc::new(std::array::from_fn(|_| {
+            AesEncryptor::new(prg.random_block())
+        }));
+        let tweaks = std::array::from_fn(|_| AesEncryptor::new(prg.random_block()));
+
+        Self {
+            cuckoo: CuckooHash::new(hashes),
+            tweaks,
+        }
+    }
+
+    /// Returns the Cuckoo hash.
+    pub(crate) fn cuckoo(&self) -> &CuckooHash {
+        &self.cuckoo
+    }
+
+    /// Returns the OPRF input of an item which is placed by the hash function with index
+    /// `hash_index`.
+    pub(crate) fn input(&self, item: Block, hash_index: usize) -> Block {
+        self.tweaks[hash_index].encrypt_block(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_oprf_core::kkrt::{
+        receiver_state, sender_state, Receiver as OPRFReceiver, ReceiverOutput,
+        Sender as OPRFSender, BASE_OT_COUNT,
+    };
+    use rand::{seq::SliceRandom, Rng};
+    use rand_chacha::ChaCha12Rng;
+    use rstest::*;
+
+    use msgs::SenderPayload;
+
+    fn oprf(
+        rng: &mut impl Rng,
+    ) -> (
+        OPRFSender<sender_state::Extension>,
+        OPRFReceiver<receiver_state::Extension>,
+    ) {
+        let keys: Vec<[Block; 2]> = (0..BASE_OT_COUNT).map(|_| rng.gen()).collect();
+        let choices: Vec<bool> = (0..BASE_OT_COUNT).map(|_| rng.gen()).collect();
+        let chosen = keys
+            .iter()
+            .zip(&choices)
+            .map(|(keys, choice)| keys[*choice as usize])
+            .collect();
+
+        (
+            OPRFSender::new().setup(choices, chosen).unwrap(),
+            OPRFReceiver::new().setup(keys).unwrap(),
+        )
+    }
+
+    fn random_blocks(rng: &mut impl Rng, count: usize) -> Vec<Block> {
+        (0..count).map(|_| rng.gen()).collect()
+    }
+
+    /// Runs the protocol, evaluating the OPRF with the provided sender and receiver.
+    fn run(
+        rng: &mut ChaCha12Rng,
+        oprf_sender: &mut OPRFSender<sender_state::Extension>,
+        oprf_receiver: &mut OPRFReceiver<receiver_state::Extension>,
+        sender_items: &[Block],
+        receiver_items: &[Block],
+    ) -> Vec<Block> {
+        let (receiver, payload, inputs) = Receiver::new().receive(receiver_items, rng).unwrap();
+        let sender = Sender::new(payload);
+
+        let (ReceiverOutput { outputs, .. }, extend) = oprf_receiver.receive(&inputs, rng);
+        let keys = oprf_sender.send(extend).unwrap();
+        assert_eq!(keys.len(), sender.count());
+
+        let payload = sender
+            .send(sender_items, |index, input| keys.eval(index, input), rng)
+            .unwrap();
+
+        receiver.finalize(outputs, payload).unwrap()
+    }
+
+    #[rstest]
+    #[case::small(10, 10, 5)]
+    #[case::unbalanced(1000, 50, 20)]
+    #[case::large(2000, 3000, 1000)]
+    #[case::disjoint(100, 100, 0)]
+    #[case::empty_sender(0, 100, 0)]
+    #[case::empty_receiver(100, 0, 0)]
+    fn test_kkrt(
+        #[case] sender_count: usize,
+        #[case] receiver_count: usize,
+        #[case] common: usize,
+    ) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut oprf_sender, mut oprf_receiver) = oprf(&mut rng);
+
+        let shared = random_blocks(&mut rng, common);
+
+        let mut sender_items = random_blocks(&mut rng, sender_count - common);
+        sender_items.extend_from_slice(&shared);
+        sender_items.shuffle(&mut rng);
+
+        let mut receiver_items = random_blocks(&mut rng, receiver_count - common);
+        receiver_items.extend_from_slice(&shared);
+        receiver_items.shuffle(&mut rng);
+
+        let intersection = run(
+            &mut rng,
+            &mut oprf_sender,
+            &mut oprf_receiver,
+            &sender_items,
+            &receiver_items,
+        );
+
+        // The intersection is in the order of the receiver's items.
+        let expected: Vec<Block> = receiver_items
+            .iter()
+            .copied()
+            .filter(|item| shared.contains(item))
+            .collect();
+
+        assert_eq!(intersection, expected);
+    }
+
+    #[test]
+    fn test_kkrt_wrong_base_ots() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut oprf_sender, _) = oprf(&mut rng);
+        let (_, mut oprf_receiver) = oprf(&mut rng);
+
+        let items = random_blocks(&mut rng, 100);
+
+        // An OPRF sender which does not match the receiver yields an empty intersection.
+        assert!(run(
+            &mut rng,
+            &mut oprf_sender,
+            &mut oprf_receiver,
+            &items,
+            &items
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_kkrt_duplicate_items() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let mut items = random_blocks(&mut rng, 10);
+        let (_, payload, _) = Receiver::new().receive(&items, &mut rng).unwrap();
+
+        items.push(items[0]);
+
+        assert!(matches!(
+            Receiver::new().receive(&items, &mut rng),
+            Err(ReceiverError::DuplicateItem)
+        ));
+        assert!(matches!(
+            Sender::new(payload).send(&items, |_, input| input, &mut rng),
+            Err(SenderError::DuplicateItem)
+        ));
+    }
+
+    #[test]
+    fn test_kkrt_invalid_outputs() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+        let items = random_blocks(&mut rng, 10);
+
+        let (receiver, _, inputs) = Receiver::new().receive(&items, &mut rng).unwrap();
+
+        assert!(matches!(
+            receiver.finalize(
+                vec![Block::ZERO; inputs.len() - 1],
+                SenderPayload {
+                    outputs: vec![Vec::new(); CUCKOO_HASH_NUM],
+                }
+            ),
+            Err(ReceiverError::InvalidOPRFOutputs { .. })
+        ));
+
+        let (receiver, _, inputs) = Receiver::new().receive(&items, &mut rng).unwrap();
+
+        assert!(matches!(
+            receiver.finalize(
+                vec![Block::ZERO; inputs.len()],
+                SenderPayload {
+                    outputs: vec![Vec::new(); CUCKOO_HASH_NUM - 1],
+                }
+            ),
+            Err(ReceiverError::InvalidOutputs { .. })
+        ));
+    }
+}
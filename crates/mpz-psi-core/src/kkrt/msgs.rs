@@ -0,0 +1,20 @@
+//! Messages for the KKRT protocol.
+
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+/// The receiver's Cuckoo hash table parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiverPayload {
+    /// Seed of the Cuckoo hash functions.
+    pub seed: Block,
+    /// The length of the receiver's Cuckoo hash table.
+    pub table_len: usize,
+}
+
+/// The sender's OPRF outputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SenderPayload {
+    /// The OPRF outputs of the sender's items for each hash function, shuffled.
+    pub outputs: Vec<Vec<[u8; 16]>>,
+}
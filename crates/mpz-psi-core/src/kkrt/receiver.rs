@@ -0,0 +1,184 @@
+//! KKRT receiver.
+
+use std::collections::{HashMap, HashSet};
+
+use mpz_core::Block;
+use rand::Rng;
+
+use crate::kkrt::{
+    error::ReceiverError,
+    msgs::{ReceiverPayload, SenderPayload},
+    Params, CUCKOO_HASH_NUM,
+};
+
+/// Number of attempts to place the items into a Cuckoo hash table.
+const CUCKOO_ATTEMPTS: usize = 8;
+
+/// KKRT receiver.
+#[derive(Debug, Default)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Receiver {
+    /// Creates a new receiver.
+    pub fn new() -> Self {
+        Receiver {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Places the receiver's items into a Cuckoo hash table.
+    ///
+    /// Returns the receiver in the sent state, the payload for the sender and the inputs of the
+    /// OPRF instances, one for each bin of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The receiver's set.
+    /// * `rng` - The random number generator.
+    pub fn receive<R: Rng>(
+        self,
+        items: &[Block],
+        rng: &mut R,
+    ) -> Result<(Receiver<state::Sent>, ReceiverPayload, Vec<Block>), ReceiverError> {
+        if items.len() > u32::MAX as usize {
+            return Err(ReceiverError::TooManyItems(items.len()));
+        }
+
+        let mut unique = HashSet::with_capacity(items.len());
+        if !items.iter().all(|item| unique.insert(item.to_bytes())) {
+            return Err(ReceiverError::DuplicateItem);
+        }
+
+        // Insertion fails with a small probability, in which case the items are placed again
+        // with new hash functions.
+        let mut attempt = 0;
+        let (seed, params, table) = loop {
+            let seed: Block = rng.gen();
+            let params = Params::new(seed);
+            match params.cuckoo().insert_blocks(items) {
+                Ok(table) => break (seed, params, table),
+                Err(err) if attempt + 1 == CUCKOO_ATTEMPTS => return Err(err.into()),
+                Err(_) => attempt += 1,
+            }
+        };
+
+        // Empty bins get random inputs.
+        let inputs: Vec<Block> = table
+            .iter()
+            .map(|item| match item {
+                Some(item) => params.input(items[item.value() as usize], item.hash_index()),
+                None => rng.gen(),
+            })
+            .collect();
+
+        Ok((
+            Receiver {
+                state: state::Sent {
+                    items: items.to_vec(),
+                    table: table
+                        .into_iter()
+                        .map(|item| item.map(|item| (item.value() as usize, item.hash_index())))
+                        .collect(),
+                },
+            },
+            ReceiverPayload {
+                seed,
+                table_len: inputs.len(),
+            },
+            inputs,
+        ))
+    }
+}
+
+impl Receiver<state::Sent> {
+    /// Computes the intersection from the sender's OPRF outputs.
+    ///
+    /// Returns the items of the receiver which are in the intersection, in the order they were
+    /// provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `oprf_outputs` - The outputs of the OPRF instances.
+    /// * `payload` - The sender's OPRF outputs.
+    pub fn finalize(
+        self,
+        oprf_outputs: Vec<Block>,
+        payload: SenderPayload,
+    ) -> Result<Vec<Block>, ReceiverError> {
+        let state::Sent { items, table } = self.state;
+
+        if oprf_outputs.len() != table.len() {
+            return Err(ReceiverError::InvalidOPRFOutputs {
+                expected: table.len(),
+                actual: oprf_outputs.len(),
+            });
+        }
+
+        if payload.outputs.len() != CUCKOO_HASH_NUM {
+            return Err(ReceiverError::InvalidOutputs {
+                expected: CUCKOO_HASH_NUM,
+                actual: payload.outputs.len(),
+            });
+        }
+
+        // The OPRF output of each bin, indexed by the hash function which placed the item.
+        let mut outputs = vec![HashMap::new(); CUCKOO_HASH_NUM];
+        for (item, output) in table.into_iter().zip(oprf_outputs) {
+            if let Some((index, hash_index)) = item {
+                outputs[hash_index].insert(output.to_bytes(), index);
+            }
+        }
+
+        let mut found = vec![false; items.len()];
+        for (outputs, sender_outputs) in outputs.iter().zip(payload.outputs) {
+            for output in sender_outputs {
+                if let Some(index) = outputs.get(&output) {
+                    found[*index] = true;
+                }
+            }
+        }
+
+        Ok(items
+            .into_iter()
+            .zip(found)
+            .filter_map(|(item, found)| found.then_some(item))
+            .collect())
+    }
+}
+
+/// Receiver state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Sent {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after placing its items.
+    pub struct Sent {
+        /// The receiver's items.
+        pub(super) items: Vec<Block>,
+        /// The index of the item in each bin and the hash function which placed it.
+        pub(super) table: Vec<Option<(usize, usize)>>,
+    }
+
+    impl State for Sent {}
+
+    opaque_debug::implement!(Sent);
+}
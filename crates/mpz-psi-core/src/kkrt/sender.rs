@@ -0,0 +1,89 @@
+//! KKRT sender.
+
+use std::collections::HashSet;
+
+use mpz_core::Block;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::kkrt::{
+    error::SenderError,
+    msgs::{ReceiverPayload, SenderPayload},
+    Params, CUCKOO_HASH_NUM,
+};
+
+/// KKRT sender.
+pub struct Sender {
+    params: Params,
+    table_len: usize,
+}
+
+opaque_debug::implement!(Sender);
+
+impl Sender {
+    /// Creates a new sender from the receiver's payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The receiver's Cuckoo hash table parameters.
+    pub fn new(payload: ReceiverPayload) -> Self {
+        Self {
+            params: Params::new(payload.seed),
+            table_len: payload.table_len,
+        }
+    }
+
+    /// Returns the number of OPRF instances, one for each bin of the receiver's table.
+    pub fn count(&self) -> usize {
+        self.table_len
+    }
+
+    /// Evaluates the OPRF on the sender's items.
+    ///
+    /// Returns the payload for the receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The sender's set.
+    /// * `eval` - Evaluates the OPRF instance with the given index on an input.
+    /// * `rng` - The random number generator used to shuffle the outputs.
+    pub fn send<F, R>(
+        self,
+        items: &[Block],
+        eval: F,
+        rng: &mut R,
+    ) -> Result<SenderPayload, SenderError>
+    where
+        F: Fn(usize, Block) -> Block,
+        R: Rng,
+    {
+        let mut unique = HashSet::with_capacity(items.len());
+        if !items.iter().all(|item| unique.insert(item.to_bytes())) {
+            return Err(SenderError::DuplicateItem);
+        }
+
+        // An empty table means that the receiver's set is empty.
+        if self.table_len == 0 {
+            return Ok(SenderPayload {
+                outputs: vec![Vec::new(); CUCKOO_HASH_NUM],
+            });
+        }
+
+        let outputs = (0..CUCKOO_HASH_NUM)
+            .map(|hash_index| {
+                let mut outputs = items
+                    .iter()
+                    .map(|item| {
+                        let pos = self.params.cuckoo().positions(*item, self.table_len)[hash_index];
+                        eval(pos, self.params.input(*item, hash_index)).to_bytes()
+                    })
+                    .collect::<Vec<_>>();
+
+                // Shuffle the outputs so that they do not reveal the order of the sender's items.
+                outputs.shuffle(rng);
+                outputs
+            })
+            .collect();
+
+        Ok(SenderPayload { outputs })
+    }
+}
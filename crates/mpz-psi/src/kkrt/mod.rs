@@ -0,0 +1,102 @@
+//! Private set intersection based on the batched OPRF of
+//! [KKRT](https://eprint.iacr.org/2016/799), secure against semi-honest adversaries.
+//!
+//! The OPRF is provided by an [`OPRFSender`](mpz_oprf::OPRFSender) and
+//! [`OPRFReceiver`](mpz_oprf::OPRFReceiver), which must be set up before the first execution.
+//! Each execution evaluates a new batch of OPRF instances, one for each bin of the receiver's
+//! Cuckoo hash table.
+//!
+//! # Example
+//!
+//! ```
+//! use mpz_common::executor::test_st_executor;
+//! use mpz_core::Block;
+//! use mpz_oprf::kkrt::{Receiver as OPRFReceiver, Sender as OPRFSender};
+//! use mpz_ot::ideal::ot::ideal_ot;
+//! use mpz_psi::{
+//!     kkrt::{Receiver, Sender},
+//!     PSIReceiver, PSISender,
+//! };
+//! # futures::executor::block_on(async {
+//!
+//! let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+//! let (ot_sender, ot_receiver) = ideal_ot();
+//!
+//! let mut oprf_sender = OPRFSender::new(ot_receiver);
+//! let mut oprf_receiver = OPRFReceiver::new(ot_sender);
+//! futures::try_join!(
+//!     oprf_sender.setup(&mut ctx_sender),
+//!     oprf_receiver.setup(&mut ctx_receiver)
+//! )
+//! .unwrap();
+//!
+//! let mut sender = Sender::new(oprf_sender);
+//! let mut receiver = Receiver::new(oprf_receiver);
+//!
+//! let sender_items = vec![Block::new([1; 16]), Block::new([2; 16])];
+//! let receiver_items = vec![Block::new([2; 16]), Block::new([3; 16])];
+//!
+//! let (_, intersection) = futures::try_join!(
+//!     sender.send(&mut ctx_sender, sender_items),
+//!     receiver.receive(&mut ctx_receiver, receiver_items),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(intersection, vec![Block::new([2; 16])]);
+//! # });
+//! ```
+
+mod receiver;
+mod sender;
+
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::executor::test_st_executor;
+    use mpz_core::{prg::Prg, Block};
+    use mpz_oprf::kkrt::{Receiver as OPRFReceiver, Sender as OPRFSender};
+    use mpz_ot::ideal::ot::ideal_ot;
+    use rand::{Rng, SeedableRng};
+
+    use crate::{PSIReceiver, PSISender};
+
+    #[tokio::test]
+    async fn test_kkrt() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (ot_sender, ot_receiver) = ideal_ot();
+
+        let mut oprf_sender = OPRFSender::new(ot_receiver);
+        let mut oprf_receiver = OPRFReceiver::new(ot_sender);
+        tokio::try_join!(
+            oprf_sender.setup(&mut ctx_sender),
+            oprf_receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let mut sender = Sender::new(oprf_sender);
+        let mut receiver = Receiver::new(oprf_receiver);
+
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let shared: Vec<Block> = (0..50).map(|_| rng.gen()).collect();
+
+        // Run twice to check that a new batch of OPRF instances is used each time.
+        for _ in 0..2 {
+            let mut sender_items: Vec<Block> = (0..100).map(|_| rng.gen()).collect();
+            sender_items.extend_from_slice(&shared);
+            let mut receiver_items: Vec<Block> = (0..200).map(|_| rng.gen()).collect();
+            receiver_items.extend_from_slice(&shared);
+
+            let (_, intersection) = tokio::try_join!(
+                sender.send(&mut ctx_sender, sender_items),
+                receiver.receive(&mut ctx_receiver, receiver_items),
+            )
+            .unwrap();
+
+            assert_eq!(intersection, shared);
+        }
+    }
+}
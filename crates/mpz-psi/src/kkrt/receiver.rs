@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_oprf::OPRFReceiver;
+use mpz_psi_core::kkrt::{msgs::SenderPayload, Receiver as ReceiverCore};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{PSIError, PSIReceiver};
+
+/// KKRT receiver.
+#[derive(Debug)]
+pub struct Receiver<OPRF> {
+    oprf: OPRF,
+}
+
+impl<OPRF> Receiver<OPRF> {
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `oprf` - The OPRF receiver, which must be set up.
+    pub fn new(oprf: OPRF) -> Self {
+        Self { oprf }
+    }
+}
+
+#[async_trait]
+impl<Ctx, OPRF> PSIReceiver<Ctx> for Receiver<OPRF>
+where
+    Ctx: Context,
+    OPRF: OPRFReceiver<Ctx> + Send,
+{
+    async fn receive(&mut self, ctx: &mut Ctx, items: Vec<Block>) -> Result<Vec<Block>, PSIError> {
+        let (receiver, payload, inputs) =
+            Backend::spawn(move || ReceiverCore::new().receive(&items, &mut thread_rng())).await?;
+
+        ctx.io_mut().send(payload).await?;
+
+        let oprf_outputs = self.oprf.receive(ctx, &inputs).await?;
+
+        let payload: SenderPayload = ctx.io_mut().expect_next().await?;
+
+        Ok(receiver.finalize(oprf_outputs, payload)?)
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_oprf::{OPRFKeys, OPRFSender};
+use mpz_psi_core::kkrt::{msgs::ReceiverPayload, Sender as SenderCore};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{PSIError, PSISender};
+
+/// KKRT sender.
+#[derive(Debug)]
+pub struct Sender<OPRF> {
+    oprf: OPRF,
+}
+
+impl<OPRF> Sender<OPRF> {
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `oprf` - The OPRF sender, which must be set up.
+    pub fn new(oprf: OPRF) -> Self {
+        Self { oprf }
+    }
+}
+
+#[async_trait]
+impl<Ctx, OPRF> PSISender<Ctx> for Sender<OPRF>
+where
+    Ctx: Context,
+    OPRF: OPRFSender<Ctx> + Send,
+    OPRF::Keys: Send + 'static,
+{
+    async fn send(&mut self, ctx: &mut Ctx, items: Vec<Block>) -> Result<(), PSIError> {
+        let payload: ReceiverPayload = ctx.io_mut().expect_next().await?;
+        let sender = SenderCore::new(payload);
+
+        let keys = self.oprf.send(ctx, sender.count()).await?;
+
+        let payload = Backend::spawn(move || {
+            sender.send(
+                &items,
+                |index, input| keys.eval(index, input),
+                &mut thread_rng(),
+            )
+        })
+        .await?;
+
+        ctx.io_mut().send(payload).await?;
+
+        Ok(())
+    }
+}
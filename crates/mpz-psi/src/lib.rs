@@ -0,0 +1,61 @@
+//! Implementations of private set intersection (PSI) protocols.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+pub mod kkrt;
+
+use async_trait::async_trait;
+use mpz_core::Block;
+
+/// A private set intersection error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum PSIError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("context error: {0}")]
+    Context(#[from] mpz_common::ContextError),
+    #[error("OPRF error: {0}")]
+    OPRFError(#[from] mpz_oprf::OPRFError),
+    #[error("sender error: {0}")]
+    SenderError(#[from] mpz_psi_core::kkrt::SenderError),
+    #[error("receiver error: {0}")]
+    ReceiverError(#[from] mpz_psi_core::kkrt::ReceiverError),
+}
+
+/// A private set intersection sender.
+///
+/// The sender learns nothing about the receiver's set, apart from its size.
+#[async_trait]
+pub trait PSISender<Ctx> {
+    /// Runs the protocol with the sender's set.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `items` - The sender's set, which must not contain duplicates.
+    async fn send(&mut self, ctx: &mut Ctx, items: Vec<Block>) -> Result<(), PSIError>;
+}
+
+/// A private set intersection receiver.
+///
+/// The receiver learns the intersection of both sets.
+#[async_trait]
+pub trait PSIReceiver<Ctx> {
+    /// Runs the protocol with the receiver's set, returning the intersection.
+    ///
+    /// The intersection is returned in the order of the receiver's items.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `items` - The receiver's set, which must not contain duplicates.
+    async fn receive(&mut self, ctx: &mut Ctx, items: Vec<Block>) -> Result<Vec<Block>, PSIError>;
+}
@@ -0,0 +1,114 @@
+use derive_builder::Builder;
+use mpz_common::codec::CodecKind;
+use mpz_garble::protocol::deap::DEAPConfig;
+use serde::{Deserialize, Serialize};
+
+/// The role a party would like to take in a session.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RolePreference {
+    /// The party must be the leader.
+    Leader,
+    /// The party must be the follower.
+    Follower,
+    /// The party accepts either role.
+    #[default]
+    Any,
+}
+
+/// A protocol component which can be enabled in a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Feature {
+    /// Committed oblivious transfer in both directions.
+    Ot,
+    /// A DEAP garbled circuit VM. Requires [`Feature::Ot`].
+    Garble,
+    /// Oblivious linear function evaluation and share conversion in both directions, see
+    /// [`OLEBuilder`](crate::OLEBuilder).
+    Ole,
+}
+
+/// Session configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct SessionConfig {
+    /// The preferred role of this party.
+    #[builder(default)]
+    role: RolePreference,
+    /// The features requested by this party.
+    ///
+    /// The session only enables the features requested by both parties.
+    #[builder(default = "vec![Feature::Ot, Feature::Garble]")]
+    features: Vec<Feature>,
+    /// The number of OTs to extend in each direction during setup.
+    ///
+    /// The larger of the two parties' values is used.
+    #[builder(default = "0")]
+    ot_count: usize,
+    /// The DEAP configuration used when the garbling feature is enabled.
+    #[builder(default)]
+    deap: DEAPConfig,
+    /// The wire codec preferred by this party, see [`SessionBuilder::connect`].
+    ///
+    /// [`SessionBuilder::connect`]: crate::SessionBuilder::connect
+    #[builder(default = "CodecKind::Bincode")]
+    codec: CodecKind,
+    /// The wire codecs supported by this party.
+    #[builder(default = "CodecKind::ALL.to_vec()")]
+    codecs: Vec<CodecKind>,
+}
+
+impl SessionConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(features) = &self.features {
+            if features.contains(&Feature::Garble) && !features.contains(&Feature::Ot) {
+                return Err("the garble feature requires the OT feature".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+impl SessionConfig {
+    /// Creates a new builder for the session configuration.
+    pub fn builder() -> SessionConfigBuilder {
+        SessionConfigBuilder::default()
+    }
+
+    /// Returns the preferred role of this party.
+    pub fn role(&self) -> RolePreference {
+        self.role
+    }
+
+    /// Returns the features requested by this party.
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Returns the number of OTs to extend in each direction during setup.
+    pub fn ot_count(&self) -> usize {
+        self.ot_count
+    }
+
+    /// Returns the DEAP configuration.
+    pub fn deap(&self) -> &DEAPConfig {
+        &self.deap
+    }
+
+    /// Returns the wire codec preferred by this party.
+    pub fn codec(&self) -> CodecKind {
+        self.codec
+    }
+
+    /// Returns the wire codecs supported by this party.
+    pub fn codecs(&self) -> &[CodecKind] {
+        &self.codecs
+    }
+}
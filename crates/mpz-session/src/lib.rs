@@ -0,0 +1,739 @@
+//! Session setup for two-party computation.
+//!
+//! A [`SessionBuilder`] takes a transport to the peer and negotiates a session with it: both
+//! parties agree on a protocol version, on the features to enable and on their roles. The
+//! builder then sets up the protocol components for the enabled features, which can be taken
+//! out of the returned [`Session`]: the OT instances, a garbled circuit VM, and an
+//! [`OLEBuilder`] for OLE and share conversion instances.
+//!
+//! If the parties talk over a raw byte stream, [`SessionBuilder::connect`] first negotiates the
+//! wire codec, see [`mpz_common::codec`], and frames the stream with it.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use mpz_session::{RolePreference, SessionBuilder, SessionConfig};
+//!
+//! let config = SessionConfig::builder()
+//!     .role(RolePreference::Any)
+//!     .ot_count(1024)
+//!     .build()
+//!     .unwrap();
+//!
+//! let session = SessionBuilder::new(config).build(io).await?;
+//! let mut vm = session.into_vm().await?;
+//!
+//! // ...
+//!
+//! vm.finalize_committed().await?;
+//! ```
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+mod config;
+mod negotiate;
+
+pub use config::{
+    Feature, RolePreference, SessionConfig, SessionConfigBuilder, SessionConfigBuilderError,
+};
+pub use negotiate::{MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+
+use futures::{AsyncRead, AsyncWrite};
+use mpz_common::{
+    codec::{negotiate_codec, CodecError, CodecKind},
+    executor::STExecutor,
+    tag::Tag,
+    Context,
+};
+use mpz_fields::Field;
+use mpz_garble::{
+    config::Role,
+    protocol::deap::{DEAPError, DEAPThread},
+};
+use mpz_ole::rot::{SharedROTReceiver, SharedROTSender};
+use mpz_ot::{chou_orlandi, kos, OTError, OTSetup};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt, Deserialize, IoSink, IoStream, Serialize, SinkExt};
+
+use negotiate::{assign_role, negotiate_features, negotiate_version, Hello};
+
+/// Tag of the OT instances where the leader is the sender.
+const SESSION_OT_LEADER: &str = "mpz-session/ot/leader";
+/// Tag of the OT instances where the follower is the sender.
+const SESSION_OT_FOLLOWER: &str = "mpz-session/ot/follower";
+/// Tag of the random OT instances backing the OLEs where the leader is the sender.
+const SESSION_OLE_LEADER: &str = "mpz-session/ole/leader";
+/// Tag of the random OT instances backing the OLEs where the follower is the sender.
+const SESSION_OLE_FOLLOWER: &str = "mpz-session/ole/follower";
+
+/// The OT sender of a session.
+pub type OTSender = kos::Sender<chou_orlandi::Receiver>;
+/// The OT receiver of a session.
+pub type OTReceiver = kos::Receiver<chou_orlandi::Sender>;
+/// The garbled circuit VM of a session.
+///
+/// The OTs of the VM are committed, so it must be finalized with
+/// [`DEAPThread::finalize_committed`].
+pub type SessionVm<Io> = DEAPThread<STExecutor<Io>, OTSender, OTReceiver>;
+/// The OLE sender of a session.
+pub type OLESender<F> = mpz_ole::rot::OLESender<SharedROTSender<OTSender>, F>;
+/// The OLE receiver of a session.
+pub type OLEReceiver<F> = mpz_ole::rot::OLEReceiver<SharedROTReceiver<OTReceiver>, F>;
+/// The share conversion sender of a session.
+pub type ShareConversionSender<F> = mpz_share_conversion::ShareConversionSender<OLESender<F>, F>;
+/// The share conversion receiver of a session.
+pub type ShareConversionReceiver<F> =
+    mpz_share_conversion::ShareConversionReceiver<OLEReceiver<F>, F>;
+
+/// A session error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SessionError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    OTError(#[from] OTError),
+    #[error(transparent)]
+    CodecError(#[from] CodecError),
+    #[error(transparent)]
+    DEAPError(#[from] DEAPError),
+    #[error(
+        "no common protocol version: versions {min} to {max} are supported, \
+         peer supports {peer_min} to {peer_max}"
+    )]
+    VersionMismatch {
+        min: u32,
+        max: u32,
+        peer_min: u32,
+        peer_max: u32,
+    },
+    #[error("both parties requested the {0} role")]
+    RoleConflict(Role),
+    #[error("both parties sent the same nonce, unable to assign roles")]
+    RoleTie,
+    #[error("feature was not negotiated: {0:?}")]
+    MissingFeature(Feature),
+}
+
+/// Builds a [`Session`] with a peer.
+#[derive(Debug, Default)]
+pub struct SessionBuilder {
+    config: SessionConfig,
+}
+
+impl SessionBuilder {
+    /// Creates a new session builder.
+    pub fn new(config: SessionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Negotiates a session with the peer over the provided transport.
+    ///
+    /// Both parties must call this method concurrently.
+    pub async fn build<Io>(self, io: Io) -> Result<Session<Io>, SessionError>
+    where
+        Io: IoSink + IoStream + Send + Sync + Unpin + 'static,
+    {
+        let Self { config } = self;
+        let mut ctx = STExecutor::new(io);
+
+        let hello = Hello {
+            min_version: MIN_PROTOCOL_VERSION,
+            version: PROTOCOL_VERSION,
+            role: config.role(),
+            features: config.features().to_vec(),
+            ot_count: config.ot_count(),
+            nonce: thread_rng().gen(),
+        };
+
+        ctx.io_mut().send(hello.clone()).await?;
+        let peer: Hello = ctx.io_mut().expect_next().await?;
+
+        let version = negotiate_version(&hello, &peer)?;
+        let role = assign_role(&hello, &peer)?;
+        let features = negotiate_features(&hello.features, &peer.features);
+
+        let ot = if features.contains(&Feature::Ot) {
+            let count = hello.ot_count.max(peer.ot_count);
+            Some(
+                setup_ot(
+                    &mut ctx,
+                    role,
+                    (SESSION_OT_LEADER, SESSION_OT_FOLLOWER),
+                    count,
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+
+        // The random OTs are extended on demand when the OLEs are preprocessed.
+        let ole = if features.contains(&Feature::Ole) {
+            let (sender, receiver) = setup_ot(
+                &mut ctx,
+                role,
+                (SESSION_OLE_LEADER, SESSION_OLE_FOLLOWER),
+                0,
+            )
+            .await?;
+
+            Some(OLEBuilder {
+                rot_sender: SharedROTSender::new(sender),
+                rot_receiver: SharedROTReceiver::new(receiver),
+            })
+        } else {
+            None
+        };
+
+        Ok(Session {
+            version,
+            role,
+            features,
+            codec: None,
+            ctx,
+            ot,
+            ole,
+            config,
+        })
+    }
+
+    /// Negotiates the wire codec and then a session with the peer over a raw byte stream.
+    ///
+    /// Both parties must call this method concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `io` - The raw byte stream to the peer.
+    /// * `frame` - Frames the byte stream with the negotiated codec.
+    pub async fn connect<Raw, Io, F>(
+        self,
+        mut io: Raw,
+        frame: F,
+    ) -> Result<Session<Io>, SessionError>
+    where
+        Raw: AsyncRead + AsyncWrite + Unpin,
+        Io: IoSink + IoStream + Send + Sync + Unpin + 'static,
+        F: FnOnce(Raw, CodecKind) -> Io,
+    {
+        let codec = negotiate_codec(&mut io, self.config.codec(), self.config.codecs()).await?;
+
+        let mut session = self.build(frame(io, codec)).await?;
+        session.codec = Some(codec);
+
+        Ok(session)
+    }
+}
+
+/// Sets up OT in both directions.
+///
+/// Both directions share the same channel, so the leader runs its sender first while the
+/// follower runs its receiver first.
+///
+/// # Arguments
+///
+/// * `ctx` - The session context.
+/// * `role` - The role of this party.
+/// * `tags` - The tags of the directions where the leader and the follower are the sender.
+/// * `count` - The number of OTs to extend in each direction.
+async fn setup_ot<Io>(
+    ctx: &mut STExecutor<Io>,
+    role: Role,
+    (leader_tag, follower_tag): (&'static str, &'static str),
+    count: usize,
+) -> Result<(OTSender, OTReceiver), SessionError>
+where
+    Io: IoSink + IoStream + Send + Sync + Unpin + 'static,
+{
+    // The OTs are committed so that the OT receiver can verify them once the sender reveals its
+    // secrets, which requires the base OT receiver to commit to its choices.
+    // Each direction is tagged with the role of its OT sender.
+    let (sender_tag, receiver_tag) = match role {
+        Role::Leader => (leader_tag, follower_tag),
+        Role::Follower => (follower_tag, leader_tag),
+    };
+    let (sender_tag, receiver_tag) = (Tag::new(sender_tag), Tag::new(receiver_tag));
+
+    let mut sender = kos::Sender::new(
+        sender_tag.clone(),
+        kos::SenderConfig::builder()
+            .sender_commit()
+            .build()
+            .expect("config should be valid"),
+        chou_orlandi::Receiver::new(
+            sender_tag.child("base"),
+            chou_orlandi::ReceiverConfig::builder()
+                .receiver_commit()
+                .build()
+                .expect("config should be valid"),
+        ),
+    );
+    let mut receiver = kos::Receiver::new(
+        receiver_tag.clone(),
+        kos::ReceiverConfig::builder()
+            .sender_commit()
+            .build()
+            .expect("config should be valid"),
+        chou_orlandi::Sender::new(
+            receiver_tag.child("base"),
+            chou_orlandi::SenderConfig::builder()
+                .receiver_commit()
+                .build()
+                .expect("config should be valid"),
+        ),
+    );
+
+    match role {
+        Role::Leader => {
+            sender.setup(ctx).await?;
+            receiver.setup(ctx).await?;
+        }
+        Role::Follower => {
+            receiver.setup(ctx).await?;
+            sender.setup(ctx).await?;
+        }
+    }
+
+    if count > 0 {
+        match role {
+            Role::Leader => {
+                sender.extend(ctx, count).await.map_err(OTError::from)?;
+                receiver.extend(ctx, count).await.map_err(OTError::from)?;
+            }
+            Role::Follower => {
+                receiver.extend(ctx, count).await.map_err(OTError::from)?;
+                sender.extend(ctx, count).await.map_err(OTError::from)?;
+            }
+        }
+    }
+
+    Ok((sender, receiver))
+}
+
+/// Builds the OLE and share conversion instances of a session.
+///
+/// All instances are backed by the same random OTs in each direction, which are extended on
+/// demand when an instance is preprocessed. An instance is paired with the instance of the
+/// peer which has the same tag and the opposite side, e.g. a sender created by this party with
+/// [`new_sender`](Self::new_sender) is paired with a receiver created by the peer with
+/// [`new_receiver`](Self::new_receiver).
+///
+/// The instances run on the session context, see [`Session::ctx_mut`].
+#[derive(Debug, Clone)]
+pub struct OLEBuilder {
+    rot_sender: SharedROTSender<OTSender>,
+    rot_receiver: SharedROTReceiver<OTReceiver>,
+}
+
+impl OLEBuilder {
+    /// Creates a new OLE sender.
+    pub fn new_sender<F>(&self, tag: Tag) -> OLESender<F>
+    where
+        F: Field + Serialize + Deserialize,
+    {
+        OLESender::new(tag, self.rot_sender.clone())
+    }
+
+    /// Creates a new OLE receiver.
+    pub fn new_receiver<F>(&self, tag: Tag) -> OLEReceiver<F>
+    where
+        F: Field + Serialize + Deserialize,
+    {
+        OLEReceiver::new(tag, self.rot_receiver.clone())
+    }
+
+    /// Creates a new share conversion sender.
+    pub fn new_share_conversion_sender<F>(&self, tag: Tag) -> ShareConversionSender<F>
+    where
+        F: Field + Serialize + Deserialize,
+    {
+        let ole_sender = self.new_sender(tag.child("ole"));
+
+        ShareConversionSender::new(tag, ole_sender)
+    }
+
+    /// Creates a new share conversion receiver.
+    pub fn new_share_conversion_receiver<F>(&self, tag: Tag) -> ShareConversionReceiver<F>
+    where
+        F: Field + Serialize + Deserialize,
+    {
+        let ole_receiver = self.new_receiver(tag.child("ole"));
+
+        ShareConversionReceiver::new(tag, ole_receiver)
+    }
+}
+
+/// A negotiated session with a peer.
+pub struct Session<Io> {
+    version: u32,
+    role: Role,
+    features: Vec<Feature>,
+    codec: Option<CodecKind>,
+    ctx: STExecutor<Io>,
+    ot: Option<(OTSender, OTReceiver)>,
+    ole: Option<OLEBuilder>,
+    config: SessionConfig,
+}
+
+impl<Io> std::fmt::Debug for Session<Io> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("version", &self.version)
+            .field("role", &self.role)
+            .field("features", &self.features)
+            .field("codec", &self.codec)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Io> Session<Io> {
+    /// Returns the negotiated protocol version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the role assigned to this party.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Returns the features enabled in this session.
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Returns the negotiated wire codec, or `None` if the transport was framed by the
+    /// application.
+    pub fn codec(&self) -> Option<CodecKind> {
+        self.codec
+    }
+
+    /// Returns `true` if the feature is enabled in this session.
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Returns a mutable reference to the session context.
+    pub fn ctx_mut(&mut self) -> &mut STExecutor<Io> {
+        &mut self.ctx
+    }
+
+    /// Returns a builder for the OLE and share conversion instances of the session.
+    pub fn ole(&self) -> Result<OLEBuilder, SessionError> {
+        self.ole
+            .clone()
+            .ok_or(SessionError::MissingFeature(Feature::Ole))
+    }
+
+    /// Returns the session context and the OT sender and receiver.
+    pub fn into_ot(self) -> Result<(STExecutor<Io>, OTSender, OTReceiver), SessionError> {
+        let (sender, receiver) = self.ot.ok_or(SessionError::MissingFeature(Feature::Ot))?;
+
+        Ok((self.ctx, sender, receiver))
+    }
+
+    /// Returns a garbled circuit VM running on the session.
+    ///
+    /// Both parties must call this method concurrently, as it checks that they use the same
+    /// convention to convert between bytes and values.
+    pub async fn into_vm(self) -> Result<SessionVm<Io>, SessionError>
+    where
+        Io: IoSink + IoStream + Send + Sync + Unpin + 'static,
+    {
+        if !self.has_feature(Feature::Garble) {
+            return Err(SessionError::MissingFeature(Feature::Garble));
+        }
+
+        let role = self.role;
+        let config = self.config.deap().clone();
+        let (ctx, sender, receiver) = self.into_ot()?;
+
+        let mut vm =
+            DEAPThread::new_with_config(role, thread_rng().gen(), config, ctx, sender, receiver);
+        vm.check_endianness().await?;
+
+        Ok(vm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::circuits::AES128;
+    use mpz_common::{Allocate, Preprocess};
+    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_garble::{Decode, Execute, Memory};
+    use mpz_ole::{OLEReceiver as _, OLESender as _};
+    use mpz_share_conversion::AdditiveToMultiplicative;
+    use serio::channel::duplex;
+    use tokio_util::compat::TokioAsyncReadCompatExt;
+
+    fn config(role: RolePreference, features: &[Feature], ot_count: usize) -> SessionConfig {
+        SessionConfig::builder()
+            .role(role)
+            .features(features.to_vec())
+            .ot_count(ot_count)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_config_garble_requires_ot() {
+        assert!(SessionConfig::builder()
+            .features(vec![Feature::Garble])
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_session_vm() {
+        let (io_a, io_b) = duplex(8);
+        let features = [Feature::Ot, Feature::Garble];
+
+        let (session_a, session_b) = futures::try_join!(
+            SessionBuilder::new(config(RolePreference::Leader, &features, 128)).build(io_a),
+            SessionBuilder::new(config(RolePreference::Any, &features, 0)).build(io_b),
+        )
+        .unwrap();
+
+        assert_eq!(session_a.role(), Role::Leader);
+        assert_eq!(session_b.role(), Role::Follower);
+        assert_eq!(session_a.features(), &features);
+
+        let (mut leader_vm, mut follower_vm) =
+            futures::try_join!(session_a.into_vm(), session_b.into_vm()).unwrap();
+
+        let leader_fut = {
+            let key_ref = leader_vm.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader_vm.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader_vm.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader_vm.assign(&key_ref, [42u8; 16]).unwrap();
+
+            async {
+                leader_vm
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader_vm.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower_vm.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower_vm.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower_vm.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower_vm.assign(&msg_ref, [69u8; 16]).unwrap();
+
+            async {
+                follower_vm
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower_vm.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_result, follower_result);
+
+        let (leader_result, follower_result) = futures::join!(
+            leader_vm.finalize_committed(),
+            follower_vm.finalize_committed()
+        );
+
+        leader_result.unwrap();
+        follower_result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_session_ole() {
+        let (io_a, io_b) = duplex(8);
+        let features = [Feature::Ole];
+
+        let (mut session_a, mut session_b) = futures::try_join!(
+            SessionBuilder::new(config(RolePreference::Leader, &features, 0)).build(io_a),
+            SessionBuilder::new(config(RolePreference::Follower, &features, 0)).build(io_b),
+        )
+        .unwrap();
+
+        assert_eq!(session_a.version(), PROTOCOL_VERSION);
+        assert!(!session_a.has_feature(Feature::Ot));
+        let (ole_a, ole_b) = (session_a.ole().unwrap(), session_b.ole().unwrap());
+
+        let count = 4;
+        let mut rng = thread_rng();
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let b_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        // OLE with the leader as the sender.
+        let mut sender = ole_a.new_sender::<P256>(Tag::new("ole"));
+        let mut receiver = ole_b.new_receiver::<P256>(Tag::new("ole"));
+        sender.alloc(count);
+        receiver.alloc(count);
+
+        let (x_k, y_k) = futures::try_join!(
+            async {
+                sender.preprocess(session_a.ctx_mut()).await?;
+                sender.send(session_a.ctx_mut(), a_k.clone()).await
+            },
+            async {
+                receiver.preprocess(session_b.ctx_mut()).await?;
+                receiver.receive(session_b.ctx_mut(), b_k.clone()).await
+            }
+        )
+        .unwrap();
+
+        a_k.iter()
+            .zip(&b_k)
+            .zip(x_k.into_inner())
+            .zip(y_k.into_inner())
+            .for_each(|(((&a, &b), x), y)| assert_eq!(y, a * b + x));
+
+        // Share conversion with the follower as the sender.
+        let mut sender = ole_b.new_share_conversion_sender::<P256>(Tag::new("a2m"));
+        let mut receiver = ole_a.new_share_conversion_receiver::<P256>(Tag::new("a2m"));
+        sender.alloc(count);
+        receiver.alloc(count);
+
+        let (sender_output, receiver_output) = futures::try_join!(
+            async {
+                sender.preprocess(session_b.ctx_mut()).await?;
+                sender
+                    .to_multiplicative(session_b.ctx_mut(), a_k.clone())
+                    .await
+            },
+            async {
+                receiver.preprocess(session_a.ctx_mut()).await?;
+                receiver
+                    .to_multiplicative(session_a.ctx_mut(), b_k.clone())
+                    .await
+            }
+        )
+        .unwrap();
+
+        a_k.iter()
+            .zip(&b_k)
+            .zip(sender_output)
+            .zip(receiver_output)
+            .for_each(|(((&a, &b), x), y)| assert_eq!(a + b, x * y));
+    }
+
+    #[tokio::test]
+    async fn test_session_connect() {
+        let (raw_a, raw_b) = tokio::io::duplex(8);
+        let (io_a, io_b) = duplex(8);
+
+        let config = |codec| {
+            SessionConfig::builder()
+                .features(vec![Feature::Ot])
+                .codec(codec)
+                .build()
+                .unwrap()
+        };
+
+        let (session_a, session_b) = futures::try_join!(
+            SessionBuilder::new(config(CodecKind::Postcard)).connect(raw_a.compat(), |_, codec| {
+                assert_eq!(codec, CodecKind::Postcard);
+                io_a
+            }),
+            SessionBuilder::new(config(CodecKind::Postcard)).connect(raw_b.compat(), |_, codec| {
+                assert_eq!(codec, CodecKind::Postcard);
+                io_b
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(session_a.codec(), Some(CodecKind::Postcard));
+        assert_eq!(session_b.codec(), Some(CodecKind::Postcard));
+        assert_ne!(session_a.role(), session_b.role());
+    }
+
+    #[tokio::test]
+    async fn test_session_role_conflict() {
+        let (io_a, io_b) = duplex(8);
+        let features = [Feature::Ot];
+
+        let (result_a, result_b) = futures::join!(
+            SessionBuilder::new(config(RolePreference::Follower, &features, 0)).build(io_a),
+            SessionBuilder::new(config(RolePreference::Follower, &features, 0)).build(io_b),
+        );
+
+        assert!(matches!(
+            result_a.unwrap_err(),
+            SessionError::RoleConflict(Role::Follower)
+        ));
+        assert!(matches!(
+            result_b.unwrap_err(),
+            SessionError::RoleConflict(Role::Follower)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_session_feature_negotiation() {
+        let (io_a, io_b) = duplex(8);
+
+        let (session_a, session_b) = futures::try_join!(
+            SessionBuilder::new(config(RolePreference::Any, &[Feature::Ot], 0)).build(io_a),
+            SessionBuilder::new(SessionConfig::default()).build(io_b),
+        )
+        .unwrap();
+
+        assert_ne!(session_a.role(), session_b.role());
+        assert_eq!(session_b.features(), &[Feature::Ot]);
+        assert!(!session_b.has_feature(Feature::Garble));
+
+        assert!(matches!(
+            session_b.into_vm().await,
+            Err(SessionError::MissingFeature(Feature::Garble))
+        ));
+        assert!(session_a.into_ot().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_session_version_mismatch() {
+        let (io, mut peer_io) = duplex(8);
+
+        let peer_fut = async {
+            peer_io
+                .send(Hello {
+                    min_version: PROTOCOL_VERSION + 1,
+                    version: PROTOCOL_VERSION + 1,
+                    role: RolePreference::Any,
+                    features: vec![Feature::Ot],
+                    ot_count: 0,
+                    nonce: [0u8; 32],
+                })
+                .await
+                .unwrap();
+        };
+
+        let (result, _) = futures::join!(SessionBuilder::default().build(io), peer_fut);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            SessionError::VersionMismatch { peer_min, .. } if peer_min == PROTOCOL_VERSION + 1
+        ));
+    }
+}
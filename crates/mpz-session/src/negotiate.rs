@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+
+use mpz_garble::config::Role;
+use serde::{Deserialize, Serialize};
+
+use crate::{Feature, RolePreference, SessionError};
+
+/// The latest version of the session negotiation protocol.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// The oldest version of the session negotiation protocol which is still supported.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The message each party sends to open a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Hello {
+    /// The oldest supported protocol version.
+    pub(crate) min_version: u32,
+    /// The latest supported protocol version.
+    pub(crate) version: u32,
+    pub(crate) role: RolePreference,
+    pub(crate) features: Vec<Feature>,
+    pub(crate) ot_count: usize,
+    /// Random nonce used to break ties when both parties accept either role.
+    pub(crate) nonce: [u8; 32],
+}
+
+/// Returns the latest protocol version supported by both parties.
+pub(crate) fn negotiate_version(local: &Hello, remote: &Hello) -> Result<u32, SessionError> {
+    let version = local.version.min(remote.version);
+
+    if version < local.min_version.max(remote.min_version) {
+        return Err(SessionError::VersionMismatch {
+            min: local.min_version,
+            max: local.version,
+            peer_min: remote.min_version,
+            peer_max: remote.version,
+        });
+    }
+
+    Ok(version)
+}
+
+/// Assigns the role of the local party.
+pub(crate) fn assign_role(local: &Hello, remote: &Hello) -> Result<Role, SessionError> {
+    use RolePreference::*;
+
+    match (local.role, remote.role) {
+        (Leader, Leader) => Err(SessionError::RoleConflict(Role::Leader)),
+        (Follower, Follower) => Err(SessionError::RoleConflict(Role::Follower)),
+        (Leader, _) | (Any, Follower) => Ok(Role::Leader),
+        (Follower, _) | (Any, Leader) => Ok(Role::Follower),
+        (Any, Any) => match local.nonce.cmp(&remote.nonce) {
+            Ordering::Greater => Ok(Role::Leader),
+            Ordering::Less => Ok(Role::Follower),
+            Ordering::Equal => Err(SessionError::RoleTie),
+        },
+    }
+}
+
+/// Returns the features requested by both parties, in the local order.
+pub(crate) fn negotiate_features(local: &[Feature], remote: &[Feature]) -> Vec<Feature> {
+    local
+        .iter()
+        .filter(|feature| remote.contains(feature))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hello(role: RolePreference, nonce: u8) -> Hello {
+        Hello {
+            min_version: MIN_PROTOCOL_VERSION,
+            version: PROTOCOL_VERSION,
+            role,
+            features: vec![Feature::Ot, Feature::Garble],
+            ot_count: 0,
+            nonce: [nonce; 32],
+        }
+    }
+
+    #[test]
+    fn test_negotiate_version() {
+        let versions = |min_version, version| Hello {
+            min_version,
+            version,
+            ..hello(RolePreference::Any, 0)
+        };
+
+        assert_eq!(
+            negotiate_version(&versions(1, 3), &versions(2, 2)).unwrap(),
+            2
+        );
+        assert_eq!(
+            negotiate_version(&versions(2, 2), &versions(1, 3)).unwrap(),
+            2
+        );
+        assert_eq!(
+            negotiate_version(&versions(1, 4), &versions(3, 5)).unwrap(),
+            4
+        );
+        assert!(matches!(
+            negotiate_version(&versions(1, 2), &versions(3, 4)),
+            Err(SessionError::VersionMismatch {
+                peer_min: 3,
+                peer_max: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_assign_role() {
+        use RolePreference::*;
+
+        let cases = [
+            (Leader, Follower),
+            (Leader, Any),
+            (Any, Follower),
+            (Any, Any),
+        ];
+
+        for (a, b) in cases {
+            let (a, b) = (hello(a, 1), hello(b, 0));
+
+            assert_eq!(assign_role(&a, &b).unwrap(), Role::Leader);
+            assert_eq!(assign_role(&b, &a).unwrap(), Role::Follower);
+        }
+
+        assert!(matches!(
+            assign_role(&hello(Leader, 0), &hello(Leader, 1)),
+            Err(SessionError::RoleConflict(Role::Leader))
+        ));
+        assert!(matches!(
+            assign_role(&hello(Follower, 0), &hello(Follower, 1)),
+            Err(SessionError::RoleConflict(Role::Follower))
+        ));
+        assert!(matches!(
+            assign_role(&hello(Any, 0), &hello(Any, 0)),
+            Err(SessionError::RoleTie)
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_features() {
+        assert_eq!(
+            negotiate_features(&[Feature::Ot, Feature::Garble], &[Feature::Ot]),
+            vec![Feature::Ot]
+        );
+        assert!(negotiate_features(&[Feature::Garble], &[Feature::Ot]).is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mpz_core::{prg::Prg, Block};
+use mpz_fields::{gf2_128::Gf2_128, UniformRand};
+use mpz_share_conversion_core::{a2m_convert_sender, gf2_128, m2a_convert};
+use rand::SeedableRng;
+
+fn random_vec(rng: &mut Prg, n: usize) -> Vec<Gf2_128> {
+    (0..n).map(|_| Gf2_128::rand(&mut *rng)).collect()
+}
+
+fn a2m(c: &mut Criterion) {
+    let mut group = c.benchmark_group("a2m_gf2_128");
+    for n in [16, 256, 4096] {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let input = random_vec(&mut rng, n);
+        let ole_input = random_vec(&mut rng, n);
+        let ole_output = random_vec(&mut rng, n);
+
+        group.bench_with_input(BenchmarkId::new("generic", n), &n, |b, _| {
+            b.iter(|| {
+                black_box(
+                    a2m_convert_sender(input.clone(), ole_input.clone(), ole_output.clone())
+                        .unwrap(),
+                )
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("clmul", n), &n, |b, _| {
+            b.iter(|| {
+                black_box(
+                    gf2_128::a2m_convert_sender(
+                        input.clone(),
+                        ole_input.clone(),
+                        ole_output.clone(),
+                    )
+                    .unwrap(),
+                )
+            })
+        });
+    }
+}
+
+fn m2a(c: &mut Criterion) {
+    let mut group = c.benchmark_group("m2a_gf2_128");
+    let n = 4096;
+    let mut rng = Prg::from_seed(Block::ZERO);
+    let shares = random_vec(&mut rng, n);
+
+    group.bench_function(BenchmarkId::new("generic", n), |b| {
+        b.iter(|| black_box(m2a_convert(shares.clone())))
+    });
+
+    group.bench_function(BenchmarkId::new("clmul", n), |b| {
+        b.iter(|| black_box(gf2_128::m2a_convert(shares.clone())))
+    });
+}
+
+criterion_group!(benches, a2m, m2a);
+criterion_main!(benches);
@@ -0,0 +1,221 @@
+//! Share conversion specialized for [`Gf2_128`].
+//!
+//! The generic conversions in this crate multiply with [`Gf2_128`]'s bitwise multiplication and
+//! invert every element with 127 squarings. The functions in this module instead multiply with
+//! carry-less multiplication, see [`Block::gfmul`], and invert the OLE inputs of a batch with a
+//! single field inversion using Montgomery's trick.
+//!
+//! The outputs are identical to those of the generic conversions.
+
+use mpz_core::Block;
+use mpz_fields::gf2_128::Gf2_128;
+
+use crate::{A2MMasks, ErrorKind, ShareConversionError};
+
+/// Converts a field element into a block in the representation used by [`Block::gfmul`].
+#[inline]
+fn to_block(x: Gf2_128) -> Block {
+    Block::new(x.to_inner().to_le_bytes())
+}
+
+/// Converts a block in the representation used by [`Block::gfmul`] into a field element.
+#[inline]
+fn from_block(x: Block) -> Gf2_128 {
+    Gf2_128::new(u128::from_le_bytes(x.to_bytes()))
+}
+
+/// Inverts a single non-zero element, computing `x^(2^128 - 2)`.
+fn inverse(x: Block) -> Block {
+    let mut a = x;
+    let mut out = Block::new(1u128.to_le_bytes());
+    for _ in 0..127 {
+        a = a.gfmul(a);
+        out = out.gfmul(a);
+    }
+    out
+}
+
+/// Inverts all elements of a batch in place.
+///
+/// Zero elements are mapped to zero, like [`Field::inverse`](mpz_fields::Field::inverse).
+fn batch_inverse(elems: &mut [Block]) {
+    let one = Block::new(1u128.to_le_bytes());
+
+    // prefix[i] is the product of all non-zero elements before index i.
+    let mut prefix = Vec::with_capacity(elems.len());
+    let mut acc = one;
+    for &x in elems.iter() {
+        prefix.push(acc);
+        if x != Block::ZERO {
+            acc = acc.gfmul(x);
+        }
+    }
+
+    let mut inv = inverse(acc);
+    for (x, prefix) in elems.iter_mut().zip(prefix).rev() {
+        if *x == Block::ZERO {
+            continue;
+        }
+
+        let x_inv = inv.gfmul(prefix);
+        inv = inv.gfmul(*x);
+        *x = x_inv;
+    }
+}
+
+/// Converts additive sender shares into multiplicative shares.
+///
+/// This is equivalent to [`a2m_convert_sender`](crate::a2m_convert_sender).
+///
+/// # Arguments
+///
+/// * `input` - The sender's input field elements.
+/// * `ole_input` - The input from an OLE sender.
+/// * `ole_output` - The output from an OLE sender.
+///
+/// # Returns
+///
+/// * The additive shares of the sender.
+/// * The masks which have to be sent to the receiver.
+pub fn a2m_convert_sender(
+    input: Vec<Gf2_128>,
+    ole_input: Vec<Gf2_128>,
+    ole_output: Vec<Gf2_128>,
+) -> Result<(Vec<Gf2_128>, A2MMasks<Gf2_128>), ShareConversionError> {
+    if input.len() != ole_output.len() || ole_input.len() != ole_output.len() {
+        return Err(ShareConversionError::new(
+            ErrorKind::UnequalLength,
+            format!(
+                "Vectors have unequal length: {}, {}, {}",
+                input.len(),
+                ole_input.len(),
+                ole_output.len()
+            ),
+        ));
+    }
+
+    let mut ole_input: Vec<Block> = ole_input.into_iter().map(to_block).collect();
+
+    let masks: Vec<Gf2_128> = input
+        .into_iter()
+        .zip(&ole_input)
+        .zip(ole_output)
+        .map(|((i, r), o)| from_block(to_block(i).gfmul(*r) ^ to_block(o)))
+        .collect();
+
+    batch_inverse(&mut ole_input);
+
+    Ok((
+        ole_input.into_iter().map(from_block).collect(),
+        A2MMasks(masks),
+    ))
+}
+
+/// Converts the A2M sender's masks into multiplicative receiver shares.
+///
+/// This is equivalent to [`a2m_convert_receiver`](crate::a2m_convert_receiver).
+///
+/// # Arguments
+///
+/// * `masks` - The masks received by the sender.
+/// * `ole_output` - The output from an OLE receiver.
+///
+/// # Returns
+///
+/// * The additive shares of the receiver.
+pub fn a2m_convert_receiver(
+    masks: A2MMasks<Gf2_128>,
+    ole_output: Vec<Gf2_128>,
+) -> Result<Vec<Gf2_128>, ShareConversionError> {
+    crate::a2m_convert_receiver(masks, ole_output)
+}
+
+/// Converts output field elements of an OLE sender into additive shares.
+///
+/// This is equivalent to [`m2a_convert`](crate::m2a_convert). Negation is the identity in a
+/// field of characteristic 2, so the shares are returned unchanged.
+///
+/// # Arguments
+///
+/// * `shares` - The output from an OLE sender.
+pub fn m2a_convert(shares: Vec<Gf2_128>) -> Vec<Gf2_128> {
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_core::{prg::Prg, Block};
+    use mpz_fields::{Field, UniformRand};
+    use mpz_ole_core::ideal::IdealOLE;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_gfmul_matches_field() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        for _ in 0..64 {
+            let a = Gf2_128::rand(&mut rng);
+            let b = Gf2_128::rand(&mut rng);
+
+            assert_eq!(from_block(to_block(a).gfmul(to_block(b))), a * b);
+        }
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let mut elems: Vec<Gf2_128> = (0..32).map(|_| Gf2_128::rand(&mut rng)).collect();
+        elems[7] = Gf2_128::zero();
+
+        let mut blocks: Vec<Block> = elems.iter().copied().map(to_block).collect();
+        batch_inverse(&mut blocks);
+
+        elems
+            .into_iter()
+            .zip(blocks)
+            .for_each(|(x, inv)| assert_eq!(from_block(inv), x.inverse()));
+    }
+
+    #[test]
+    fn test_a2m_gf2_128() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let mut ole = IdealOLE::default();
+
+        let ole_sender_input: Vec<Gf2_128> = (0..count).map(|_| Gf2_128::rand(&mut rng)).collect();
+        let ole_receiver_input: Vec<Gf2_128> =
+            (0..count).map(|_| Gf2_128::rand(&mut rng)).collect();
+
+        let (ole_sender_output, ole_receiver_output) =
+            ole.generate(&ole_sender_input, &ole_receiver_input);
+
+        let sender_input: Vec<Gf2_128> = (0..count).map(|_| Gf2_128::rand(&mut rng)).collect();
+        let receiver_input: Vec<Gf2_128> = ole_receiver_input;
+
+        let (sender_output, masks) = a2m_convert_sender(
+            sender_input.clone(),
+            ole_sender_input.clone(),
+            ole_sender_output.clone(),
+        )
+        .unwrap();
+
+        let (expected_output, expected_masks) =
+            crate::a2m_convert_sender(sender_input.clone(), ole_sender_input, ole_sender_output)
+                .unwrap();
+
+        assert_eq!(sender_output, expected_output);
+        assert_eq!(masks.0, expected_masks.0);
+
+        let receiver_output = a2m_convert_receiver(masks, ole_receiver_output).unwrap();
+
+        sender_input
+            .iter()
+            .zip(receiver_input)
+            .zip(sender_output)
+            .zip(receiver_output)
+            .for_each(|(((&x, y), a), b)| assert_eq!(x + y, a * b));
+    }
+}
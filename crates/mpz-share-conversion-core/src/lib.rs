@@ -5,6 +5,7 @@
 #![deny(clippy::all)]
 #![deny(unsafe_code)]
 
+pub mod gf2_128;
 pub mod ideal;
 pub mod msgs;
 
@@ -1,4 +1,5 @@
 use core::fmt;
+use mpz_common::{tag::TagError, ContextError};
 use mpz_ole::OLEError;
 use mpz_share_conversion_core::ShareConversionError as ShareConversionCoreError;
 use std::error::Error;
@@ -29,7 +30,9 @@ impl ShareConversionError {
 pub(crate) enum ErrorKind {
     Ole,
     IO,
+    Context,
     ShareConversionCore,
+    Tag,
 }
 
 impl fmt::Display for ShareConversionError {
@@ -37,7 +40,9 @@ impl fmt::Display for ShareConversionError {
         match self.kind {
             ErrorKind::Ole => write!(f, "OLE Error"),
             ErrorKind::IO => write!(f, "IO Error"),
+            ErrorKind::Context => write!(f, "Context Error"),
             ErrorKind::ShareConversionCore => write!(f, "Core Error"),
+            ErrorKind::Tag => write!(f, "Tag Error"),
         }?;
 
         if let Some(source) = self.source.as_ref() {
@@ -65,3 +70,15 @@ impl From<IOError> for ShareConversionError {
         Self::new(ErrorKind::IO, value)
     }
 }
+
+impl From<ContextError> for ShareConversionError {
+    fn from(value: ContextError) -> Self {
+        Self::new(ErrorKind::Context, value)
+    }
+}
+
+impl From<TagError> for ShareConversionError {
+    fn from(value: TagError) -> Self {
+        Self::new(ErrorKind::Tag, value)
+    }
+}
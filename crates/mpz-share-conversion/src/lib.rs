@@ -7,12 +7,14 @@
 mod error;
 #[cfg(feature = "ideal")]
 pub mod ideal;
+mod mixed;
 mod receiver;
 mod sender;
 
 use async_trait::async_trait;
 
 pub use error::ShareConversionError;
+pub use mixed::{Mixed, MixedShare};
 pub use receiver::ShareConversionReceiver;
 pub use sender::ShareConversionSender;
 
@@ -65,24 +67,24 @@ mod tests {
         AdditiveToMultiplicative, MultiplicativeToAdditive, ShareConversionReceiver,
         ShareConversionSender,
     };
-    use mpz_common::executor::test_st_executor;
+    use mpz_common::{executor::test_st_executor, tag::Tag};
     use mpz_core::{prg::Prg, Block};
-    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_fields::{gf2_8::Gf2_8, p256::P256, Field};
     use mpz_ole::ideal::ideal_ole;
     use rand::SeedableRng;
+    use serio::{Deserialize, Serialize};
 
-    #[tokio::test]
-    async fn test_m2a() {
+    async fn m2a<F: Field + Serialize + Deserialize>() {
         let count = 12;
         let mut rng = Prg::from_seed(Block::ZERO);
 
         let (ole_sender, ole_receiver) = ideal_ole();
 
-        let mut sender = ShareConversionSender::new(ole_sender);
-        let mut receiver = ShareConversionReceiver::new(ole_receiver);
+        let mut sender = ShareConversionSender::new(Tag::new("test"), ole_sender);
+        let mut receiver = ShareConversionReceiver::new(Tag::new("test"), ole_receiver);
 
-        let sender_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
-        let receiver_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let sender_input: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
+        let receiver_input: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
 
         let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
 
@@ -100,18 +102,17 @@ mod tests {
             .for_each(|(((&si, ri), so), ro)| assert_eq!(si * ri, so + ro));
     }
 
-    #[tokio::test]
-    async fn test_a2m() {
+    async fn a2m<F: Field + Serialize + Deserialize>() {
         let count = 12;
         let mut rng = Prg::from_seed(Block::ZERO);
 
         let (ole_sender, ole_receiver) = ideal_ole();
 
-        let mut sender = ShareConversionSender::new(ole_sender);
-        let mut receiver = ShareConversionReceiver::new(ole_receiver);
+        let mut sender = ShareConversionSender::new(Tag::new("test"), ole_sender);
+        let mut receiver = ShareConversionReceiver::new(Tag::new("test"), ole_receiver);
 
-        let sender_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
-        let receiver_input: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+        let sender_input: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
+        let receiver_input: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
 
         let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
 
@@ -128,4 +129,24 @@ mod tests {
             .zip(receiver_output)
             .for_each(|(((&si, ri), so), ro)| assert_eq!(si + ri, so * ro));
     }
+
+    #[tokio::test]
+    async fn test_m2a() {
+        m2a::<P256>().await;
+    }
+
+    #[tokio::test]
+    async fn test_a2m() {
+        a2m::<P256>().await;
+    }
+
+    #[tokio::test]
+    async fn test_m2a_gf2_8() {
+        m2a::<Gf2_8>().await;
+    }
+
+    #[tokio::test]
+    async fn test_a2m_gf2_8() {
+        a2m::<Gf2_8>().await;
+    }
 }
@@ -0,0 +1,359 @@
+//! Share conversion over batches with elements from different fields.
+
+use async_trait::async_trait;
+use mpz_common::{try_join, Allocate, Context, Preprocess};
+
+use crate::{AdditiveToMultiplicative, MultiplicativeToAdditive, ShareConversionError};
+
+/// A share from one of two fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MixedShare<F, G> {
+    /// A share from the first field.
+    First(F),
+    /// A share from the second field.
+    Second(G),
+}
+
+/// Share conversion over batches with elements from two different fields.
+///
+/// Wraps a converter for each field and runs the conversions of both fields concurrently, so a
+/// mixed batch only takes a single conversion round instead of one per field.
+///
+/// The outputs are returned in the order of the inputs. Both parties must provide the elements
+/// of each field at the same positions.
+///
+/// Conversions are allocated per field with [`Mixed::alloc_first`] and [`Mixed::alloc_second`],
+/// so `Mixed` does not implement [`Allocate`] or [`Preprocess`]: a count alone does not say which
+/// field the elements of a batch are from.
+#[derive(Debug, Clone)]
+pub struct Mixed<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Mixed<A, B> {
+    /// Creates a new mixed converter.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The converter for the first field.
+    /// * `second` - The converter for the second field.
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns the converters of the first and second field.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// Allocates conversions of elements from the first field.
+    pub fn alloc_first(&mut self, count: usize)
+    where
+        A: Allocate,
+    {
+        self.first.alloc(count);
+    }
+
+    /// Allocates conversions of elements from the second field.
+    pub fn alloc_second(&mut self, count: usize)
+    where
+        B: Allocate,
+    {
+        self.second.alloc(count);
+    }
+}
+
+impl<A, B> Mixed<A, B>
+where
+    A: Send,
+    B: Send,
+{
+    /// Preprocesses the allocated conversions of both fields concurrently.
+    pub async fn preprocess<Ctx>(&mut self, ctx: &mut Ctx) -> Result<(), ShareConversionError>
+    where
+        Ctx: Context,
+        A: Preprocess<Ctx, Error = ShareConversionError>,
+        B: Preprocess<Ctx, Error = ShareConversionError>,
+    {
+        let Self { first, second } = self;
+
+        try_join!(ctx, first.preprocess(ctx), second.preprocess(ctx))??;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, A, B, F, G> MultiplicativeToAdditive<Ctx, MixedShare<F, G>> for Mixed<A, B>
+where
+    Ctx: Context,
+    A: MultiplicativeToAdditive<Ctx, F> + Send,
+    B: MultiplicativeToAdditive<Ctx, G> + Send,
+    F: Send + 'static,
+    G: Send + 'static,
+{
+    async fn to_additive(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: Vec<MixedShare<F, G>>,
+    ) -> Result<Vec<MixedShare<F, G>>, ShareConversionError> {
+        let Self { first, second } = self;
+        let (order, first_inputs, second_inputs) = split(inputs);
+
+        let (first_outputs, second_outputs) = try_join!(
+            ctx,
+            async move {
+                if first_inputs.is_empty() {
+                    return Ok(Vec::new());
+                }
+                first.to_additive(ctx, first_inputs).await
+            },
+            async move {
+                if second_inputs.is_empty() {
+                    return Ok(Vec::new());
+                }
+                second.to_additive(ctx, second_inputs).await
+            }
+        )??;
+
+        Ok(merge(order, first_outputs, second_outputs))
+    }
+}
+
+#[async_trait]
+impl<Ctx, A, B, F, G> AdditiveToMultiplicative<Ctx, MixedShare<F, G>> for Mixed<A, B>
+where
+    Ctx: Context,
+    A: AdditiveToMultiplicative<Ctx, F> + Send,
+    B: AdditiveToMultiplicative<Ctx, G> + Send,
+    F: Send + 'static,
+    G: Send + 'static,
+{
+    async fn to_multiplicative(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: Vec<MixedShare<F, G>>,
+    ) -> Result<Vec<MixedShare<F, G>>, ShareConversionError> {
+        let Self { first, second } = self;
+        let (order, first_inputs, second_inputs) = split(inputs);
+
+        let (first_outputs, second_outputs) = try_join!(
+            ctx,
+            async move {
+                if first_inputs.is_empty() {
+                    return Ok(Vec::new());
+                }
+                first.to_multiplicative(ctx, first_inputs).await
+            },
+            async move {
+                if second_inputs.is_empty() {
+                    return Ok(Vec::new());
+                }
+                second.to_multiplicative(ctx, second_inputs).await
+            }
+        )??;
+
+        Ok(merge(order, first_outputs, second_outputs))
+    }
+}
+
+/// Splits a mixed batch by field, returning for each element whether it is from the first field.
+fn split<F, G>(inputs: Vec<MixedShare<F, G>>) -> (Vec<bool>, Vec<F>, Vec<G>) {
+    let mut order = Vec::with_capacity(inputs.len());
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+
+    for input in inputs {
+        match input {
+            MixedShare::First(input) => {
+                order.push(true);
+                first.push(input);
+            }
+            MixedShare::Second(input) => {
+                order.push(false);
+                second.push(input);
+            }
+        }
+    }
+
+    (order, first, second)
+}
+
+/// Merges the outputs of each field back into the order of the batch.
+fn merge<F, G>(order: Vec<bool>, first: Vec<F>, second: Vec<G>) -> Vec<MixedShare<F, G>> {
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+
+    order
+        .into_iter()
+        .map(|is_first| {
+            if is_first {
+                MixedShare::First(first.next().expect("output count matches input count"))
+            } else {
+                MixedShare::Second(second.next().expect("output count matches input count"))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{ShareConversionReceiver, ShareConversionSender};
+    use mpz_common::{executor::test_st_executor, tag::Tag};
+    use mpz_core::{prg::Prg, Block};
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256, UniformRand};
+    use mpz_ole::ideal::ideal_ole;
+    use rand::{Rng, SeedableRng};
+
+    fn mixed_inputs(rng: &mut Prg, count: usize) -> Vec<MixedShare<P256, Gf2_128>> {
+        (0..count)
+            .map(|_| {
+                if rng.gen::<bool>() {
+                    MixedShare::First(P256::rand(rng))
+                } else {
+                    MixedShare::Second(Gf2_128::rand(rng))
+                }
+            })
+            .collect()
+    }
+
+    /// Uses the same field layout for the receiver inputs as for the sender inputs.
+    fn same_layout(
+        rng: &mut Prg,
+        inputs: &[MixedShare<P256, Gf2_128>],
+    ) -> Vec<MixedShare<P256, Gf2_128>> {
+        inputs
+            .iter()
+            .map(|input| match input {
+                MixedShare::First(_) => MixedShare::First(P256::rand(rng)),
+                MixedShare::Second(_) => MixedShare::Second(Gf2_128::rand(rng)),
+            })
+            .collect()
+    }
+
+    #[derive(Debug, Default)]
+    struct Allocated(usize);
+
+    impl Allocate for Allocated {
+        fn alloc(&mut self, count: usize) {
+            self.0 += count;
+        }
+    }
+
+    #[test]
+    fn test_mixed_alloc() {
+        let mut mixed = Mixed::new(Allocated::default(), Allocated::default());
+
+        mixed.alloc_first(3);
+        mixed.alloc_second(5);
+        mixed.alloc_first(1);
+
+        let (first, second) = mixed.into_inner();
+        assert_eq!(first.0, 4);
+        assert_eq!(second.0, 5);
+    }
+
+    #[tokio::test]
+    async fn test_mixed_m2a() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (p256_sender, p256_receiver) = ideal_ole();
+        let (gf2_sender, gf2_receiver) = ideal_ole();
+
+        let mut sender = Mixed::new(
+            ShareConversionSender::<_, P256>::new(Tag::new("test/p256"), p256_sender),
+            ShareConversionSender::<_, Gf2_128>::new(Tag::new("test/gf2"), gf2_sender),
+        );
+        let mut receiver = Mixed::new(
+            ShareConversionReceiver::<_, P256>::new(Tag::new("test/p256"), p256_receiver),
+            ShareConversionReceiver::<_, Gf2_128>::new(Tag::new("test/gf2"), gf2_receiver),
+        );
+
+        let sender_input = mixed_inputs(&mut rng, 16);
+        let receiver_input = same_layout(&mut rng, &sender_input);
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.to_additive(&mut ctx_sender, sender_input.clone()),
+            receiver.to_additive(&mut ctx_receiver, receiver_input.clone())
+        )
+        .unwrap();
+
+        for (((si, ri), so), ro) in sender_input
+            .into_iter()
+            .zip(receiver_input)
+            .zip(sender_output)
+            .zip(receiver_output)
+        {
+            match (si, ri, so, ro) {
+                (
+                    MixedShare::First(si),
+                    MixedShare::First(ri),
+                    MixedShare::First(so),
+                    MixedShare::First(ro),
+                ) => assert_eq!(si * ri, so + ro),
+                (
+                    MixedShare::Second(si),
+                    MixedShare::Second(ri),
+                    MixedShare::Second(so),
+                    MixedShare::Second(ro),
+                ) => assert_eq!(si * ri, so + ro),
+                _ => panic!("output field does not match input field"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mixed_a2m_single_field() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let (p256_sender, p256_receiver) = ideal_ole();
+        let (gf2_sender, gf2_receiver) = ideal_ole();
+
+        let mut sender = Mixed::new(
+            ShareConversionSender::<_, P256>::new(Tag::new("test/p256"), p256_sender),
+            ShareConversionSender::<_, Gf2_128>::new(Tag::new("test/gf2"), gf2_sender),
+        );
+        let mut receiver = Mixed::new(
+            ShareConversionReceiver::<_, P256>::new(Tag::new("test/p256"), p256_receiver),
+            ShareConversionReceiver::<_, Gf2_128>::new(Tag::new("test/gf2"), gf2_receiver),
+        );
+
+        // A batch which only contains elements of the second field.
+        let sender_input: Vec<MixedShare<P256, Gf2_128>> = (0..8)
+            .map(|_| MixedShare::Second(Gf2_128::rand(&mut rng)))
+            .collect();
+        let receiver_input = same_layout(&mut rng, &sender_input);
+
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.to_multiplicative(&mut ctx_sender, sender_input.clone()),
+            receiver.to_multiplicative(&mut ctx_receiver, receiver_input.clone())
+        )
+        .unwrap();
+
+        for (((si, ri), so), ro) in sender_input
+            .into_iter()
+            .zip(receiver_input)
+            .zip(sender_output)
+            .zip(receiver_output)
+        {
+            let (
+                MixedShare::Second(si),
+                MixedShare::Second(ri),
+                MixedShare::Second(so),
+                MixedShare::Second(ro),
+            ) = (si, ri, so, ro)
+            else {
+                panic!("output field does not match input field");
+            };
+
+            assert_eq!(si + ri, so * ro);
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{AdditiveToMultiplicative, MultiplicativeToAdditive, ShareConversionError};
 use async_trait::async_trait;
-use mpz_common::{Allocate, Context, Preprocess};
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    Allocate, Context, Preprocess,
+};
 use mpz_fields::Field;
-use mpz_ole::{OLEError, OLEReceiver};
+use mpz_ole::{OLEError, OLEParty, OLEReceiver, OLEShare, TransferId};
 use mpz_share_conversion_core::{a2m_convert_receiver, msgs::Masks, A2MMasks};
 use serio::{stream::IoStreamExt, Deserialize, Serialize};
 use std::marker::PhantomData;
@@ -10,6 +13,7 @@ use std::marker::PhantomData;
 /// Receiver for share conversion.
 #[derive(Debug)]
 pub struct ShareConversionReceiver<T, F> {
+    tag: TagBinding,
     ole_receiver: T,
     _pd: PhantomData<F>,
 }
@@ -17,6 +21,7 @@ pub struct ShareConversionReceiver<T, F> {
 impl<T: Clone, F> Clone for ShareConversionReceiver<T, F> {
     fn clone(&self) -> Self {
         Self {
+            tag: self.tag.clone(),
             ole_receiver: self.ole_receiver.clone(),
             _pd: PhantomData,
         }
@@ -24,9 +29,10 @@ impl<T: Clone, F> Clone for ShareConversionReceiver<T, F> {
 }
 
 impl<T, F> ShareConversionReceiver<T, F> {
-    /// Creates a new receiver.
-    pub fn new(ole_receiver: T) -> Self {
+    /// Creates a new receiver for the purpose given by `tag`.
+    pub fn new(tag: Tag, ole_receiver: T) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Receiver),
             ole_receiver,
             _pd: PhantomData,
         }
@@ -53,6 +59,8 @@ where
     type Error = ShareConversionError;
 
     async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), ShareConversionError> {
+        self.tag.bind(ctx).await?;
+
         self.ole_receiver
             .preprocess(ctx)
             .await
@@ -72,9 +80,12 @@ where
         ctx: &mut Ctx,
         inputs: Vec<F>,
     ) -> Result<Vec<F>, ShareConversionError> {
+        self.tag.bind(ctx).await?;
+
         self.ole_receiver
             .receive(ctx, inputs)
             .await
+            .map(OLEShare::into_inner)
             .map_err(ShareConversionError::from)
     }
 }
@@ -91,11 +102,19 @@ where
         ctx: &mut Ctx,
         inputs: Vec<F>,
     ) -> Result<Vec<F>, ShareConversionError> {
+        self.tag.bind(ctx).await?;
+
         let ole_output = self.ole_receiver.receive(ctx, inputs).await?;
 
         let channel = ctx.io_mut();
-        let masks: A2MMasks<F> = channel.expect_next::<Masks<F>>().await?.into();
+        let (id, masks) = channel.expect_next::<(TransferId, Masks<F>)>().await?;
+
+        ole_output
+            .verify_counterpart(id, OLEParty::Sender, masks.masks.len())
+            .map_err(OLEError::from)?;
+
+        let masks: A2MMasks<F> = masks.into();
 
-        a2m_convert_receiver(masks, ole_output).map_err(ShareConversionError::from)
+        a2m_convert_receiver(masks, ole_output.into_inner()).map_err(ShareConversionError::from)
     }
 }
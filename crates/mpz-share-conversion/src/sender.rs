@@ -1,6 +1,9 @@
 use crate::{AdditiveToMultiplicative, MultiplicativeToAdditive, ShareConversionError};
 use async_trait::async_trait;
-use mpz_common::{Allocate, Context, Preprocess};
+use mpz_common::{
+    tag::{Side, Tag, TagBinding},
+    Allocate, Context, Preprocess,
+};
 use mpz_fields::Field;
 use mpz_ole::{OLEError, OLESender};
 use mpz_share_conversion_core::{a2m_convert_sender, m2a_convert, msgs::Masks};
@@ -11,6 +14,7 @@ use std::marker::PhantomData;
 /// Sender for share conversion.
 #[derive(Debug)]
 pub struct ShareConversionSender<T, F> {
+    tag: TagBinding,
     ole_sender: T,
     _pd: PhantomData<F>,
 }
@@ -18,6 +22,7 @@ pub struct ShareConversionSender<T, F> {
 impl<T: Clone, F> Clone for ShareConversionSender<T, F> {
     fn clone(&self) -> Self {
         Self {
+            tag: self.tag.clone(),
             ole_sender: self.ole_sender.clone(),
             _pd: PhantomData,
         }
@@ -25,9 +30,10 @@ impl<T: Clone, F> Clone for ShareConversionSender<T, F> {
 }
 
 impl<T, F> ShareConversionSender<T, F> {
-    /// Creates a new sender.
-    pub fn new(ole_sender: T) -> Self {
+    /// Creates a new sender for the purpose given by `tag`.
+    pub fn new(tag: Tag, ole_sender: T) -> Self {
         Self {
+            tag: TagBinding::new(tag, Side::Sender),
             ole_sender,
             _pd: PhantomData,
         }
@@ -54,6 +60,8 @@ where
     type Error = ShareConversionError;
 
     async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), ShareConversionError> {
+        self.tag.bind(ctx).await?;
+
         self.ole_sender
             .preprocess(ctx)
             .await
@@ -73,8 +81,10 @@ where
         ctx: &mut Ctx,
         inputs: Vec<F>,
     ) -> Result<Vec<F>, ShareConversionError> {
+        self.tag.bind(ctx).await?;
+
         let ole_output = self.ole_sender.send(ctx, inputs).await?;
-        Ok(m2a_convert(ole_output))
+        Ok(m2a_convert(ole_output.into_inner()))
     }
 }
 
@@ -90,6 +100,8 @@ where
         ctx: &mut Ctx,
         inputs: Vec<F>,
     ) -> Result<Vec<F>, ShareConversionError> {
+        self.tag.bind(ctx).await?;
+
         let random: Vec<F> = {
             let mut rng = thread_rng();
             (0..inputs.len())
@@ -103,12 +115,14 @@ where
         };
 
         let ole_output = self.ole_sender.send(ctx, random.clone()).await?;
-        let (output, masks) = a2m_convert_sender(inputs, random, ole_output)?;
+        let id = ole_output.id();
+        let (output, masks) = a2m_convert_sender(inputs, random, ole_output.into_inner())?;
 
         let masks: Masks<F> = masks.into();
         let channel = ctx.io_mut();
 
-        channel.send(masks).await?;
+        // The transfer id binds the masks to the OLE they were derived from.
+        channel.send((id, masks)).await?;
 
         Ok(output)
     }
@@ -0,0 +1,46 @@
+//! Adapters between `mpz` and the [swanky](https://github.com/GaloisInc/swanky) ecosystem.
+//!
+//! This crate is not a member of the `mpz` workspace, as swanky is only available over git.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+pub mod ot;
+
+use mpz_core::Block;
+
+pub use ot::{SwankyOTReceiver, SwankyOTSender};
+
+/// Converts a [`Block`] to a swanky block.
+///
+/// The byte layout is preserved, so a block converts back unchanged.
+#[inline]
+pub fn to_swanky(block: Block) -> scuttlebutt::Block {
+    u128::from_le_bytes(block.to_bytes()).into()
+}
+
+/// Converts a swanky block to a [`Block`].
+#[inline]
+pub fn from_swanky(block: scuttlebutt::Block) -> Block {
+    Block::new(u128::from(block).to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_conversion() {
+        let block: Block = rand::random();
+        let converted = to_swanky(block);
+
+        assert_eq!(AsRef::<[u8]>::as_ref(&converted), &block.to_bytes()[..]);
+        assert_eq!(from_swanky(converted), block);
+    }
+}
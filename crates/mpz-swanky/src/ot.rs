@@ -0,0 +1,275 @@
+//! Adapters for oblivious transfer protocols from `ocelot`.
+//!
+//! [`SwankyOTSender`] and [`SwankyOTReceiver`] wrap an `ocelot` OT, together with the swanky
+//! channel it communicates over, and implement [`OTSender`] and [`OTReceiver`]. This allows
+//! codebases which already use `ocelot` to pass their OTs to `mpz` protocols, and migrate one
+//! protocol at a time.
+//!
+//! The wrapped OT communicates over its own channel and not over the thread context. Each
+//! transfer is executed on the CPU backend, as swanky channels are blocking.
+
+use async_trait::async_trait;
+use mpz_common::{cpu::CpuBackend, Context, Poison};
+use mpz_core::Block;
+use mpz_ot::{OTError, OTReceiver, OTReceiverOutput, OTSender, OTSenderOutput, TransferId};
+use ocelot::ot::{Receiver as SwankyReceiver, Sender as SwankySender};
+use scuttlebutt::{AbstractChannel, AesRng};
+
+use crate::{from_swanky, to_swanky};
+
+struct Inner<OT, C> {
+    ot: OT,
+    channel: C,
+    rng: AesRng,
+}
+
+/// An OT sender wrapping an `ocelot` OT sender.
+pub struct SwankyOTSender<OT, C> {
+    inner: Option<Inner<OT, C>>,
+    transfer_id: TransferId,
+}
+
+impl<OT, C> std::fmt::Debug for SwankyOTSender<OT, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwankyOTSender")
+            .field("transfer_id", &self.transfer_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<OT, C> SwankyOTSender<OT, C>
+where
+    OT: SwankySender<Msg = scuttlebutt::Block>,
+    C: AbstractChannel,
+{
+    /// Creates a new sender from an initialized `ocelot` sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `ot` - The `ocelot` sender.
+    /// * `channel` - The channel the sender was initialized with.
+    pub fn new(ot: OT, channel: C) -> Self {
+        Self {
+            inner: Some(Inner {
+                ot,
+                channel,
+                rng: AesRng::new(),
+            }),
+            transfer_id: TransferId::default(),
+        }
+    }
+
+    /// Initializes a new `ocelot` sender over the provided channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to communicate with the receiver over.
+    pub fn init(mut channel: C) -> Result<Self, OTError> {
+        let mut rng = AesRng::new();
+        let ot =
+            OT::init(&mut channel, &mut rng).map_err(|err| OTError::SenderError(Box::new(err)))?;
+
+        Ok(Self {
+            inner: Some(Inner { ot, channel, rng }),
+            transfer_id: TransferId::default(),
+        })
+    }
+
+    /// Returns the wrapped `ocelot` sender and its channel.
+    pub fn into_inner(self) -> Option<(OT, C)> {
+        self.inner.map(|inner| (inner.ot, inner.channel))
+    }
+}
+
+impl<OT, C> Poison for SwankyOTSender<OT, C> {
+    fn is_poisoned(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, C> OTSender<Ctx, [Block; 2]> for SwankyOTSender<OT, C>
+where
+    Ctx: Context,
+    OT: SwankySender<Msg = scuttlebutt::Block> + Send + 'static,
+    C: AbstractChannel + Send + 'static,
+{
+    async fn send(
+        &mut self,
+        _ctx: &mut Ctx,
+        msgs: &[[Block; 2]],
+    ) -> Result<OTSenderOutput, OTError> {
+        // The sender is poisoned if a previous transfer was cancelled.
+        let mut inner = self
+            .inner
+            .take()
+            .ok_or_else(|| OTError::SenderError("sender is poisoned".into()))?;
+
+        let inputs = msgs
+            .iter()
+            .map(|[zero, one]| (to_swanky(*zero), to_swanky(*one)))
+            .collect::<Vec<_>>();
+
+        let (inner, result) = CpuBackend::blocking(move || {
+            let result = inner.ot.send(&mut inner.channel, &inputs, &mut inner.rng);
+            (inner, result)
+        })
+        .await;
+
+        self.inner = Some(inner);
+        result.map_err(|err| OTError::SenderError(Box::new(err)))?;
+
+        Ok(OTSenderOutput {
+            id: self.transfer_id.next(),
+        })
+    }
+}
+
+/// An OT receiver wrapping an `ocelot` OT receiver.
+pub struct SwankyOTReceiver<OT, C> {
+    inner: Option<Inner<OT, C>>,
+    transfer_id: TransferId,
+}
+
+impl<OT, C> std::fmt::Debug for SwankyOTReceiver<OT, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwankyOTReceiver")
+            .field("transfer_id", &self.transfer_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<OT, C> SwankyOTReceiver<OT, C>
+where
+    OT: SwankyReceiver<Msg = scuttlebutt::Block>,
+    C: AbstractChannel,
+{
+    /// Creates a new receiver from an initialized `ocelot` receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `ot` - The `ocelot` receiver.
+    /// * `channel` - The channel the receiver was initialized with.
+    pub fn new(ot: OT, channel: C) -> Self {
+        Self {
+            inner: Some(Inner {
+                ot,
+                channel,
+                rng: AesRng::new(),
+            }),
+            transfer_id: TransferId::default(),
+        }
+    }
+
+    /// Initializes a new `ocelot` receiver over the provided channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to communicate with the sender over.
+    pub fn init(mut channel: C) -> Result<Self, OTError> {
+        let mut rng = AesRng::new();
+        let ot = OT::init(&mut channel, &mut rng)
+            .map_err(|err| OTError::ReceiverError(Box::new(err)))?;
+
+        Ok(Self {
+            inner: Some(Inner { ot, channel, rng }),
+            transfer_id: TransferId::default(),
+        })
+    }
+
+    /// Returns the wrapped `ocelot` receiver and its channel.
+    pub fn into_inner(self) -> Option<(OT, C)> {
+        self.inner.map(|inner| (inner.ot, inner.channel))
+    }
+}
+
+impl<OT, C> Poison for SwankyOTReceiver<OT, C> {
+    fn is_poisoned(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, C> OTReceiver<Ctx, bool, Block> for SwankyOTReceiver<OT, C>
+where
+    Ctx: Context,
+    OT: SwankyReceiver<Msg = scuttlebutt::Block> + Send + 'static,
+    C: AbstractChannel + Send + 'static,
+{
+    async fn receive(
+        &mut self,
+        _ctx: &mut Ctx,
+        choices: &[bool],
+    ) -> Result<OTReceiverOutput<Block>, OTError> {
+        // The receiver is poisoned if a previous transfer was cancelled.
+        let mut inner = self
+            .inner
+            .take()
+            .ok_or_else(|| OTError::ReceiverError("receiver is poisoned".into()))?;
+
+        let choices = choices.to_vec();
+        let (inner, result) = CpuBackend::blocking(move || {
+            let result = inner
+                .ot
+                .receive(&mut inner.channel, &choices, &mut inner.rng);
+            (inner, result)
+        })
+        .await;
+
+        self.inner = Some(inner);
+        let msgs = result.map_err(|err| OTError::ReceiverError(Box::new(err)))?;
+
+        Ok(OTReceiverOutput {
+            id: self.transfer_id.next(),
+            msgs: msgs.into_iter().map(from_swanky).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::executor::DummyExecutor;
+    use ocelot::ot::{ChouOrlandiReceiver, ChouOrlandiSender};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    #[test]
+    fn test_swanky_ot() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let msgs = (0..128)
+            .map(|_| [Block::random(&mut rng), Block::random(&mut rng)])
+            .collect::<Vec<_>>();
+        let choices = (0..128).map(|_| rng.gen()).collect::<Vec<bool>>();
+
+        let (sender_channel, receiver_channel) = scuttlebutt::unix_channel_pair();
+
+        // Swanky channels are blocking, so each party runs on its own thread.
+        let sender = std::thread::spawn({
+            let msgs = msgs.clone();
+            move || {
+                let mut sender =
+                    SwankyOTSender::<ChouOrlandiSender, _>::init(sender_channel).unwrap();
+                futures::executor::block_on(sender.send(&mut DummyExecutor::default(), &msgs))
+                    .unwrap()
+            }
+        });
+
+        let mut receiver =
+            SwankyOTReceiver::<ChouOrlandiReceiver, _>::init(receiver_channel).unwrap();
+        let output =
+            futures::executor::block_on(receiver.receive(&mut DummyExecutor::default(), &choices))
+                .unwrap();
+
+        let sender_output = sender.join().unwrap();
+        assert_eq!(sender_output.id, output.id);
+
+        let expected = msgs
+            .iter()
+            .zip(&choices)
+            .map(|(msgs, choice)| msgs[*choice as usize])
+            .collect::<Vec<_>>();
+        assert_eq!(output.msgs, expected);
+    }
+}
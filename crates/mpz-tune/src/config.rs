@@ -0,0 +1,57 @@
+use derive_builder::Builder;
+
+/// Default number of OTs extended per measurement.
+const DEFAULT_OT_COUNT: usize = 1 << 20;
+
+/// Tuner configuration.
+#[derive(Debug, Clone, Builder)]
+pub struct TunerConfig {
+    /// Number of OTs to extend with KOS per measurement.
+    #[builder(default = "DEFAULT_OT_COUNT")]
+    pub(crate) ot_count: usize,
+    /// Whether to measure Ferret with each of the LPN presets.
+    ///
+    /// Each Ferret extension produces millions of OTs, so this can be disabled on
+    /// memory-constrained machines.
+    #[builder(default = "true")]
+    pub(crate) ferret: bool,
+    /// Number of times each measurement is repeated, the fastest run is kept.
+    #[builder(default = "3")]
+    pub(crate) repetitions: usize,
+    /// Batch sizes, in gates, to measure when streaming garbled circuits.
+    #[builder(default = "vec![256, 1024, 4096, 16384]")]
+    pub(crate) batch_sizes: Vec<usize>,
+}
+
+impl TunerConfig {
+    /// Creates a new builder for the tuner configuration.
+    pub fn builder() -> TunerConfigBuilder {
+        TunerConfigBuilder::default()
+    }
+
+    /// Returns the number of OTs extended with KOS per measurement.
+    pub fn ot_count(&self) -> usize {
+        self.ot_count
+    }
+
+    /// Returns whether Ferret is measured.
+    pub fn ferret(&self) -> bool {
+        self.ferret
+    }
+
+    /// Returns the number of times each measurement is repeated.
+    pub fn repetitions(&self) -> usize {
+        self.repetitions
+    }
+
+    /// Returns the batch sizes measured when streaming garbled circuits.
+    pub fn batch_sizes(&self) -> &[usize] {
+        &self.batch_sizes
+    }
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        TunerConfigBuilder::default().build().unwrap()
+    }
+}
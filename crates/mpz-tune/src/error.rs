@@ -0,0 +1,13 @@
+/// A tuning error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum TuneError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("OT error: {0}")]
+    OTError(#[from] mpz_ot::OTError),
+    #[error("garbling error: {0}")]
+    GarbleError(#[from] mpz_garble_core::GeneratorError),
+    #[error("configuration error: {0}")]
+    ConfigError(String),
+}
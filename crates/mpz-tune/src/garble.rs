@@ -0,0 +1,130 @@
+//! Garbling measurements.
+
+use std::time::Instant;
+
+use futures::try_join;
+use mpz_circuits::Circuit;
+use mpz_common::Context;
+use mpz_garble_core::{
+    encoding_state, ChaChaEncoder, EncodedValue, Encoder, EncryptedGateBatch, Generator,
+};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::{GarbleStrategy, Measurement, TuneError};
+
+/// Encodes the inputs of the circuit.
+fn encode_inputs(
+    encoder: &ChaChaEncoder,
+    circ: &Circuit,
+) -> Vec<EncodedValue<encoding_state::Full>> {
+    circ.inputs()
+        .iter()
+        .map(|value| encoder.encode_by_type(0, &value.value_type()))
+        .collect()
+}
+
+/// Measures the time it takes to garble the circuit `repetitions` times with the given strategy.
+///
+/// # Arguments
+///
+/// * `circ` - The circuit to garble.
+/// * `strategy` - The garbling strategy.
+/// * `repetitions` - The number of times to garble the circuit.
+pub(crate) fn measure_garble(
+    circ: &Circuit,
+    strategy: GarbleStrategy,
+    repetitions: usize,
+) -> Result<Measurement, TuneError> {
+    let encoder = ChaChaEncoder::new([0u8; 32]);
+    let inputs = encode_inputs(&encoder, circ);
+    let mut gen = Generator::default();
+
+    let start = Instant::now();
+    for _ in 0..repetitions {
+        match strategy {
+            GarbleStrategy::Sequential => {
+                let mut iter = gen.generate_batched(circ, encoder.delta(), inputs.clone())?;
+                iter.by_ref().for_each(drop);
+                iter.finish()?;
+            }
+            #[cfg(feature = "parallel")]
+            GarbleStrategy::Parallel => {
+                let mut iter =
+                    gen.generate_batched_parallel(circ, encoder.delta(), inputs.clone())?;
+                iter.by_ref().for_each(drop);
+                iter.finish()?;
+            }
+            #[cfg(not(feature = "parallel"))]
+            GarbleStrategy::Parallel => {
+                return Err(TuneError::ConfigError(
+                    "parallel garbling requires the `parallel` feature".to_string(),
+                ))
+            }
+        }
+    }
+
+    Ok(Measurement {
+        count: circ.and_count() * repetitions,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Measures the time it takes to garble the circuit and stream the encrypted gates to the
+/// evaluator in batches of `batch_size` gates.
+///
+/// # Arguments
+///
+/// * `ctx_generator` - The generator's context.
+/// * `ctx_evaluator` - The evaluator's context.
+/// * `circ` - The circuit to garble.
+/// * `batch_size` - The number of gates sent in each message.
+/// * `repetitions` - The number of times to garble the circuit.
+pub(crate) async fn measure_batch_size<Ctx: Context>(
+    ctx_generator: &mut Ctx,
+    ctx_evaluator: &mut Ctx,
+    circ: &Circuit,
+    batch_size: usize,
+    repetitions: usize,
+) -> Result<Measurement, TuneError> {
+    if batch_size == 0 {
+        return Err(TuneError::ConfigError(
+            "batch size must be non-zero".to_string(),
+        ));
+    }
+
+    let encoder = ChaChaEncoder::new([0u8; 32]);
+    let inputs = encode_inputs(&encoder, circ);
+    let mut gen = Generator::default();
+    let total = circ.and_count() * repetitions;
+
+    let start = Instant::now();
+    try_join!(
+        async {
+            for _ in 0..repetitions {
+                let mut iter = gen.generate_batched(circ, encoder.delta(), inputs.clone())?;
+                iter.set_batch_size(batch_size);
+                for batch in iter.by_ref() {
+                    ctx_generator.io_mut().feed(batch).await?;
+                }
+                iter.finish()?;
+            }
+            ctx_generator.io_mut().flush().await?;
+
+            Ok::<_, TuneError>(())
+        },
+        async {
+            let mut received = 0;
+            while received < total {
+                let batch: EncryptedGateBatch = ctx_evaluator.io_mut().expect_next().await?;
+                received += batch.len();
+            }
+
+            Ok::<_, TuneError>(())
+        }
+    )?;
+
+    Ok(Measurement {
+        count: total,
+        elapsed: start.elapsed(),
+    })
+}
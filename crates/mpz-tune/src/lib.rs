@@ -0,0 +1,220 @@
+//! Tuning harness for picking OT extension and garbling configurations.
+//!
+//! The [`Tuner`] measures the available options on the current machine and, when provided with
+//! contexts connected over a real transport, the current network. It produces a serializable
+//! [`Profile`] recommending the fastest option of each kind, which can be stored and loaded in
+//! production.
+//!
+//! The following are measured:
+//!
+//! - KOS OT extension, including communication.
+//! - Ferret OT extension with each of the LPN presets, including communication. The base COTs
+//!   are provided by an ideal functionality.
+//! - Single-threaded and, with the `parallel` feature, multi-threaded garbling.
+//! - Streaming garbled circuits to the evaluator with different batch sizes.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use mpz_common::executor::test_st_executor;
+//! use mpz_tune::{Tuner, TunerConfig};
+//!
+//! # futures::executor::block_on(async {
+//! let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+//!
+//! let profile = Tuner::new(TunerConfig::default())
+//!     .tune(&mut ctx_a, &mut ctx_b)
+//!     .await
+//!     .unwrap();
+//!
+//! println!("recommended OT extension: {:?}", profile.ot);
+//! # });
+//! ```
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+mod config;
+mod error;
+mod garble;
+mod ot;
+mod profile;
+
+pub use config::{TunerConfig, TunerConfigBuilder, TunerConfigBuilderError};
+pub use error::TuneError;
+pub use profile::{GarbleStrategy, Measurement, OTProtocol, Profile, Report};
+
+use mpz_circuits::{circuits::AES128, Circuit};
+use mpz_common::Context;
+use mpz_ot_core::ferret::LpnType;
+
+/// Measures the available configurations and recommends the fastest.
+#[derive(Debug, Clone)]
+pub struct Tuner {
+    config: TunerConfig,
+}
+
+impl Tuner {
+    /// Creates a new tuner.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The tuner's configuration.
+    pub fn new(config: TunerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Runs all measurements and returns the recommended profile.
+    ///
+    /// Garbling is measured with the AES-128 circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx_a` - A context, acting as the OT sender and garbler.
+    /// * `ctx_b` - A context connected to `ctx_a`, acting as the OT receiver and evaluator.
+    pub async fn tune<Ctx: Context>(
+        &self,
+        ctx_a: &mut Ctx,
+        ctx_b: &mut Ctx,
+    ) -> Result<Profile, TuneError> {
+        self.tune_with_circuit(ctx_a, ctx_b, &AES128).await
+    }
+
+    /// Runs all measurements, garbling the provided circuit, and returns the recommended
+    /// profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx_a` - A context, acting as the OT sender and garbler.
+    /// * `ctx_b` - A context connected to `ctx_a`, acting as the OT receiver and evaluator.
+    /// * `circ` - The circuit to garble.
+    pub async fn tune_with_circuit<Ctx: Context>(
+        &self,
+        ctx_a: &mut Ctx,
+        ctx_b: &mut Ctx,
+        circ: &Circuit,
+    ) -> Result<Profile, TuneError> {
+        if self.config.batch_sizes.is_empty() {
+            return Err(TuneError::ConfigError(
+                "at least one batch size must be provided".to_string(),
+            ));
+        }
+
+        let report = self.measure(ctx_a, ctx_b, circ).await?;
+
+        Profile::from_report(report)
+            .ok_or_else(|| TuneError::ConfigError("no measurements were collected".to_string()))
+    }
+
+    /// Runs all measurements.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx_a` - A context, acting as the OT sender and garbler.
+    /// * `ctx_b` - A context connected to `ctx_a`, acting as the OT receiver and evaluator.
+    /// * `circ` - The circuit to garble.
+    pub async fn measure<Ctx: Context>(
+        &self,
+        ctx_a: &mut Ctx,
+        ctx_b: &mut Ctx,
+        circ: &Circuit,
+    ) -> Result<Report, TuneError> {
+        let repetitions = self.config.repetitions.max(1);
+        let mut report = Report::default();
+
+        let mut kos = ot::measure_kos(ctx_a, ctx_b, self.config.ot_count).await?;
+        for _ in 1..repetitions {
+            kos = kos.best(ot::measure_kos(ctx_a, ctx_b, self.config.ot_count).await?);
+        }
+        report.ot.push((OTProtocol::Kos, kos));
+
+        if self.config.ferret {
+            for lpn_type in [LpnType::Regular, LpnType::Uniform] {
+                let mut ferret = ot::measure_ferret(ctx_a, ctx_b, lpn_type).await?;
+                for _ in 1..repetitions {
+                    ferret = ferret.best(ot::measure_ferret(ctx_a, ctx_b, lpn_type).await?);
+                }
+                report.ot.push((OTProtocol::Ferret(lpn_type), ferret));
+            }
+        }
+
+        report.garble.push((
+            GarbleStrategy::Sequential,
+            garble::measure_garble(circ, GarbleStrategy::Sequential, repetitions)?,
+        ));
+
+        #[cfg(feature = "parallel")]
+        report.garble.push((
+            GarbleStrategy::Parallel,
+            garble::measure_garble(circ, GarbleStrategy::Parallel, repetitions)?,
+        ));
+
+        for &batch_size in &self.config.batch_sizes {
+            let measurement =
+                garble::measure_batch_size(ctx_a, ctx_b, circ, batch_size, repetitions).await?;
+            report.batch_sizes.push((batch_size, measurement));
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::executor::test_st_executor;
+
+    #[tokio::test]
+    async fn test_tuner() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+
+        let config = TunerConfig::builder()
+            .ot_count(1024)
+            .ferret(false)
+            .repetitions(1)
+            .batch_sizes(vec![16, 128])
+            .build()
+            .unwrap();
+
+        let profile = Tuner::new(config)
+            .tune(&mut ctx_a, &mut ctx_b)
+            .await
+            .unwrap();
+
+        assert_eq!(profile.ot, OTProtocol::Kos);
+        assert!([16, 128].contains(&profile.batch_size));
+        assert_eq!(profile.report.batch_sizes.len(), 2);
+        assert!(profile
+            .report
+            .batch_sizes
+            .iter()
+            .all(|(_, m)| m.count == AES128.and_count()));
+    }
+
+    #[tokio::test]
+    async fn test_tuner_no_batch_sizes() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+
+        let config = TunerConfig::builder()
+            .ot_count(1024)
+            .ferret(false)
+            .repetitions(1)
+            .batch_sizes(vec![])
+            .build()
+            .unwrap();
+
+        let err = Tuner::new(config)
+            .tune(&mut ctx_a, &mut ctx_b)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TuneError::ConfigError(_)));
+    }
+}
@@ -0,0 +1,103 @@
+//! OT extension measurements.
+
+use std::time::Instant;
+
+use futures::{try_join, TryFutureExt};
+use mpz_common::{tag::Tag, Context};
+use mpz_core::lpn::LpnParameters;
+use mpz_ot::{
+    chou_orlandi,
+    ferret::{self, FerretConfig, LpnType, LPN_PARAMETERS_REGULAR, LPN_PARAMETERS_UNIFORM},
+    ideal::cot::ideal_rcot,
+    kos::{self, ReceiverConfig, SenderConfig},
+    OTError, OTSetup,
+};
+
+use crate::{Measurement, TuneError};
+
+/// Measures the time it takes to extend `count` OTs with KOS, excluding the base OT setup.
+///
+/// # Arguments
+///
+/// * `ctx_sender` - The sender's context.
+/// * `ctx_receiver` - The receiver's context.
+/// * `count` - The number of OTs to extend.
+pub(crate) async fn measure_kos<Ctx: Context>(
+    ctx_sender: &mut Ctx,
+    ctx_receiver: &mut Ctx,
+    count: usize,
+) -> Result<Measurement, TuneError> {
+    let tag = Tag::new("mpz-tune/kos");
+    let mut sender = kos::Sender::new(
+        tag.clone(),
+        SenderConfig::default(),
+        chou_orlandi::Receiver::new(tag.child("base"), Default::default()),
+    );
+    let mut receiver = kos::Receiver::new(
+        tag.clone(),
+        ReceiverConfig::default(),
+        chou_orlandi::Sender::new(tag.child("base"), Default::default()),
+    );
+
+    try_join!(sender.setup(ctx_sender), receiver.setup(ctx_receiver))?;
+
+    let start = Instant::now();
+    try_join!(
+        sender.extend(ctx_sender, count).map_err(OTError::from),
+        receiver.extend(ctx_receiver, count).map_err(OTError::from)
+    )?;
+
+    Ok(Measurement {
+        count,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Returns the LPN parameters of the preset.
+pub(crate) fn lpn_parameters(lpn_type: LpnType) -> LpnParameters {
+    match lpn_type {
+        LpnType::Regular => LPN_PARAMETERS_REGULAR,
+        LpnType::Uniform => LPN_PARAMETERS_UNIFORM,
+    }
+}
+
+/// Measures the time it takes for both parties to perform a single Ferret extension, including
+/// communication and the consistency check.
+///
+/// The base COTs are provided by an ideal functionality, and the setup is not measured.
+///
+/// # Arguments
+///
+/// * `ctx_sender` - The sender's context.
+/// * `ctx_receiver` - The receiver's context.
+/// * `lpn_type` - The LPN preset.
+pub(crate) async fn measure_ferret<Ctx: Context>(
+    ctx_sender: &mut Ctx,
+    ctx_receiver: &mut Ctx,
+    lpn_type: LpnType,
+) -> Result<Measurement, TuneError> {
+    let tag = Tag::new("mpz-tune/ferret");
+    let config = FerretConfig::builder()
+        .lpn_parameters(lpn_parameters(lpn_type))
+        .lpn_type(lpn_type)
+        .build()
+        .map_err(|err| TuneError::ConfigError(err.to_string()))?;
+
+    let (mut base_sender, base_receiver) = ideal_rcot();
+    let delta = base_sender.delta();
+    let mut sender = ferret::Sender::new(tag.clone(), config.clone(), delta, base_sender);
+    let mut receiver = ferret::Receiver::new(tag, config, base_receiver);
+
+    try_join!(sender.setup(ctx_sender), receiver.setup(ctx_receiver))?;
+
+    let start = Instant::now();
+    try_join!(
+        sender.extend(ctx_sender).map_err(OTError::from),
+        receiver.extend(ctx_receiver).map_err(OTError::from)
+    )?;
+
+    Ok(Measurement {
+        count: sender.remaining(),
+        elapsed: start.elapsed(),
+    })
+}
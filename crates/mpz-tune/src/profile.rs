@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use mpz_ot_core::ferret::LpnType;
+use serde::{Deserialize, Serialize};
+
+/// An OT extension protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OTProtocol {
+    /// KOS15 OT extension.
+    Kos,
+    /// Ferret OT extension with the given LPN preset.
+    Ferret(LpnType),
+}
+
+/// How circuits are garbled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GarbleStrategy {
+    /// Garble on a single thread.
+    Sequential,
+    /// Garble using multiple threads.
+    Parallel,
+}
+
+/// A single measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// Number of items processed, ie. OTs or gates.
+    pub count: usize,
+    /// Time it took to process the items.
+    pub elapsed: Duration,
+}
+
+impl Measurement {
+    /// Returns the number of items processed per second.
+    pub fn throughput(&self) -> f64 {
+        self.count as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Keeps the faster of two measurements, normalized by count.
+    pub(crate) fn best(self, other: Self) -> Self {
+        if other.throughput() > self.throughput() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// Measurements collected while tuning.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// OT extension throughput, in OTs per second.
+    pub ot: Vec<(OTProtocol, Measurement)>,
+    /// Garbling throughput, in AND gates per second.
+    pub garble: Vec<(GarbleStrategy, Measurement)>,
+    /// Garbled circuit streaming throughput for each batch size, in gates per second.
+    pub batch_sizes: Vec<(usize, Measurement)>,
+}
+
+/// A recommended configuration profile.
+///
+/// Profiles are serializable so they can be produced once on the target deployment and loaded
+/// in production.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// The fastest OT extension protocol.
+    pub ot: OTProtocol,
+    /// The fastest garbling strategy.
+    pub garble: GarbleStrategy,
+    /// The batch size, in gates, with the highest streaming throughput.
+    ///
+    /// This can be used as the `batch_size` of the garbled circuit generator configuration.
+    pub batch_size: usize,
+    /// The measurements the recommendation is based on.
+    pub report: Report,
+}
+
+impl Profile {
+    /// Creates a profile from the measurements, recommending the fastest option of each kind.
+    ///
+    /// Returns `None` if any kind has no measurements.
+    pub fn from_report(report: Report) -> Option<Self> {
+        fn fastest<T: Copy>(measurements: &[(T, Measurement)]) -> Option<T> {
+            measurements
+                .iter()
+                .max_by(|(_, a), (_, b)| a.throughput().total_cmp(&b.throughput()))
+                .map(|(option, _)| *option)
+        }
+
+        Some(Self {
+            ot: fastest(&report.ot)?,
+            garble: fastest(&report.garble)?,
+            batch_size: fastest(&report.batch_sizes)?,
+            report,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(count: usize, millis: u64) -> Measurement {
+        Measurement {
+            count,
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_profile_from_report() {
+        let report = Report {
+            ot: vec![
+                (OTProtocol::Kos, measurement(1000, 10)),
+                (
+                    OTProtocol::Ferret(LpnType::Regular),
+                    measurement(10_000, 20),
+                ),
+                (
+                    OTProtocol::Ferret(LpnType::Uniform),
+                    measurement(10_000, 40),
+                ),
+            ],
+            garble: vec![(GarbleStrategy::Sequential, measurement(100, 1))],
+            batch_sizes: vec![(256, measurement(100, 2)), (1024, measurement(100, 1))],
+        };
+
+        let profile = Profile::from_report(report.clone()).unwrap();
+
+        assert_eq!(profile.ot, OTProtocol::Ferret(LpnType::Regular));
+        assert_eq!(profile.garble, GarbleStrategy::Sequential);
+        assert_eq!(profile.batch_size, 1024);
+
+        let bytes = bincode::serialize(&profile).unwrap();
+        assert_eq!(bincode::deserialize::<Profile>(&bytes).unwrap(), profile);
+
+        assert!(Profile::from_report(Report {
+            batch_sizes: vec![],
+            ..report
+        })
+        .is_none());
+    }
+}
@@ -0,0 +1,19 @@
+//! Errors that can occur when using the Gilboa VOLE protocol.
+
+/// Errors that can occur when using the VOLE sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("invalid number of base OT seeds: expected {expected}, got {actual}")]
+    InvalidSeedCount { expected: usize, actual: usize },
+    #[error("invalid number of corrections: expected {expected}, got {actual}")]
+    InvalidCorrectionCount { expected: usize, actual: usize },
+}
+
+/// Errors that can occur when using the VOLE receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("invalid number of base OT seeds: expected {expected}, got {actual}")]
+    InvalidSeedCount { expected: usize, actual: usize },
+}
@@ -0,0 +1,164 @@
+//! A semi-honest subfield VOLE protocol based on [Gilboa](https://link.springer.com/chapter/10.1007/3-540-48405-1_8)
+//! multiplication.
+//!
+//! The sender uses the bits of its global secret `Δ` as choices for one base OT per bit of the
+//! field, receiving a PRG seed for each. The receiver expands both seeds of each pair into
+//! elements of the subfield and sends corrections which allow the sender to compute its share of
+//! `u_i * Δ` for every input `u_i`.
+//!
+//! Each extension requires the receiver to send `F::BIT_SIZE` subfield elements per VOLE, eg.
+//! 128 bits per VOLE for inputs in GF(2) and `Δ` in GF(2^128).
+
+use mpz_core::prg::Prg;
+
+use crate::Subfield;
+
+pub mod error;
+pub mod msgs;
+pub mod receiver;
+pub mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::{state as receiver_state, Receiver};
+pub use sender::{state as sender_state, Sender};
+
+/// Samples `count` subfield elements from the PRG.
+pub(crate) fn sample<F, S: Subfield<F>>(prg: &mut Prg, count: usize) -> Vec<S> {
+    (0..count).map(|_| S::random(prg)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::assert_vole;
+
+    use mpz_core::Block;
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256, Field, UniformRand};
+    use rand::{Rng, SeedableRng};
+    use rstest::*;
+
+    fn setup<F: Field>(
+        delta: F,
+    ) -> (
+        Sender<sender_state::Extension<F>>,
+        Receiver<receiver_state::Extension<F>>,
+    ) {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        let sender = Sender::new();
+        let receiver = Receiver::new();
+
+        let seeds: Vec<[Block; 2]> = (0..F::BIT_SIZE).map(|_| rng.gen()).collect();
+        let choices = sender.base_choices(delta);
+        let chosen: Vec<Block> = seeds
+            .iter()
+            .zip(choices)
+            .map(|(seeds, choice)| seeds[choice as usize])
+            .collect();
+
+        let sender = sender.setup(delta, chosen).unwrap();
+        let receiver = receiver.setup::<F>(seeds).unwrap();
+
+        (sender, receiver)
+    }
+
+    fn test_gilboa<F: Field + Subfield<F>>() {
+        let mut rng = Prg::from_seed(Block::ONES);
+        let delta = F::rand(&mut rng);
+
+        let (mut sender, mut receiver) = setup(delta);
+
+        let inputs: Vec<F> = sample(&mut rng, 100);
+        let (receiver_output, extend) = receiver.extend(&inputs);
+        let sender_output = sender.extend(inputs.len(), extend).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_vole(
+            delta,
+            &inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+
+        let (receiver_output, extend) = receiver.extend_random::<F>(50);
+        let sender_output = sender.extend(50, extend).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_vole(
+            delta,
+            &receiver_output.inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+
+        assert_eq!(sender.count(), 150);
+        assert_eq!(receiver.count(), 150);
+    }
+
+    #[test]
+    fn test_gilboa_p256() {
+        test_gilboa::<P256>();
+    }
+
+    #[test]
+    fn test_gilboa_gf2_128() {
+        test_gilboa::<Gf2_128>();
+    }
+
+    #[test]
+    fn test_gilboa_subfield_gf2() {
+        let mut rng = Prg::from_seed(Block::ONES);
+        let delta = Gf2_128::rand(&mut rng);
+
+        let (mut sender, mut receiver) = setup(delta);
+
+        let inputs: Vec<bool> = (0..100).map(|_| rng.gen()).collect();
+        let (receiver_output, extend) = receiver.extend(&inputs);
+
+        // One bit per bit of delta, instead of one field element.
+        assert_eq!(extend.corrections.len(), Gf2_128::BIT_SIZE * inputs.len());
+
+        let sender_output = sender.extend(inputs.len(), extend).unwrap();
+
+        assert_vole(
+            delta,
+            &inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+
+        let (receiver_output, extend) = receiver.extend_random::<bool>(50);
+        let sender_output = sender.extend(50, extend).unwrap();
+
+        assert_vole(
+            delta,
+            &receiver_output.inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+    }
+
+    #[rstest]
+    #[case::too_few(99)]
+    #[case::too_many(101)]
+    fn test_gilboa_invalid_correction_count(#[case] count: usize) {
+        let mut rng = Prg::from_seed(Block::ONES);
+        let delta = P256::rand(&mut rng);
+
+        let (mut sender, mut receiver) = setup(delta);
+
+        let (_, extend) = receiver.extend_random::<P256>(100);
+        let err = sender.extend(count, extend).unwrap_err();
+
+        assert!(matches!(err, SenderError::InvalidCorrectionCount { .. }));
+    }
+
+    #[test]
+    fn test_gilboa_invalid_seed_count() {
+        let err = Receiver::new()
+            .setup::<P256>(vec![[Block::ZERO; 2]; 3])
+            .unwrap_err();
+
+        assert!(matches!(err, ReceiverError::InvalidSeedCount { .. }));
+    }
+}
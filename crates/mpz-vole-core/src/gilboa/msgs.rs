@@ -0,0 +1,12 @@
+//! Messages for the Gilboa VOLE protocol.
+
+use serde::{Deserialize, Serialize};
+
+/// Corrections sent by the receiver to extend VOLEs.
+///
+/// Contains one vector of subfield corrections for each bit of the field, concatenated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Extend<S> {
+    /// The corrections.
+    pub corrections: Vec<S>,
+}
@@ -0,0 +1,189 @@
+//! Gilboa VOLE receiver.
+
+use mpz_core::{prg::Prg, Block};
+use mpz_fields::Field;
+use rand::SeedableRng;
+
+use crate::{
+    gilboa::{error::ReceiverError, msgs::Extend, sample},
+    RVOLEReceiverOutput, Subfield, TransferId, VOLEReceiverOutput,
+};
+
+/// Gilboa VOLE receiver.
+#[derive(Debug, Default)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Receiver {
+    /// Creates a new Receiver.
+    pub fn new() -> Self {
+        Receiver {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Completes the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - The seeds sent with the base OTs, one pair for each bit of the field.
+    pub fn setup<F: Field>(
+        self,
+        seeds: Vec<[Block; 2]>,
+    ) -> Result<Receiver<state::Extension<F>>, ReceiverError> {
+        if seeds.len() != F::BIT_SIZE {
+            return Err(ReceiverError::InvalidSeedCount {
+                expected: F::BIT_SIZE,
+                actual: seeds.len(),
+            });
+        }
+
+        Ok(Receiver {
+            state: state::Extension {
+                prgs: seeds
+                    .into_iter()
+                    .map(|[s0, s1]| [Prg::from_seed(s0), Prg::from_seed(s1)])
+                    .collect(),
+                rng: Prg::new(),
+                transfer_id: TransferId::default(),
+                counter: 0,
+                _pd: std::marker::PhantomData,
+            },
+        })
+    }
+}
+
+impl<F: Field> Receiver<state::Extension<F>> {
+    /// Returns the number of VOLEs which have been extended.
+    pub fn count(&self) -> usize {
+        self.state.counter
+    }
+
+    /// Extends VOLEs with the provided inputs.
+    ///
+    /// Returns the receiver's output and the corrections to send to the sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The receiver's inputs `u_i`, which are elements of the subfield `S`.
+    pub fn extend<S: Subfield<F>>(&mut self, inputs: &[S]) -> (VOLEReceiverOutput<F>, Extend<S>) {
+        let (values, extend) = self.extend_inner(inputs);
+
+        (
+            VOLEReceiverOutput {
+                id: self.state.transfer_id.next(),
+                values,
+            },
+            extend,
+        )
+    }
+
+    /// Extends VOLEs with random inputs from the subfield `S`.
+    ///
+    /// Returns the receiver's output and the corrections to send to the sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of VOLEs to extend.
+    pub fn extend_random<S: Subfield<F>>(
+        &mut self,
+        count: usize,
+    ) -> (RVOLEReceiverOutput<F, S>, Extend<S>) {
+        let inputs: Vec<S> = sample(&mut self.state.rng, count);
+        let (values, extend) = self.extend_inner(&inputs);
+
+        (
+            RVOLEReceiverOutput {
+                id: self.state.transfer_id.next(),
+                inputs,
+                values,
+            },
+            extend,
+        )
+    }
+
+    fn extend_inner<S: Subfield<F>>(&mut self, inputs: &[S]) -> (Vec<F>, Extend<S>) {
+        let count = inputs.len();
+        let two = F::two_pow(1);
+        let mut values = vec![F::zero(); count];
+        let mut corrections = vec![S::zero(); F::BIT_SIZE * count];
+
+        // The corrections c_j = r_{j,0} - r_{j,1} - u let the sender, holding r_{j,delta_j},
+        // compute a_j = r_{j,0} - delta_j * u for each bit j of delta. The receiver's values
+        // w = sum_j 2^j * r_{j,0} are evaluated in Horner form starting from the most
+        // significant bit.
+        for ([prg_0, prg_1], corrections) in self
+            .state
+            .prgs
+            .iter_mut()
+            .zip(corrections.chunks_exact_mut(count.max(1)))
+            .rev()
+        {
+            let r_0: Vec<S> = sample(prg_0, count);
+            let r_1: Vec<S> = sample(prg_1, count);
+
+            for (((value, c), (r_0, r_1)), &u) in values
+                .iter_mut()
+                .zip(corrections)
+                .zip(r_0.into_iter().zip(r_1))
+                .zip(inputs)
+            {
+                *c = r_0.add(r_1.neg()).add(u.neg());
+                *value = *value * two + r_0.embed();
+            }
+        }
+
+        self.state.counter += count;
+
+        (values, Extend { corrections })
+    }
+}
+
+/// The receiver's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl<F> Sealed for super::Extension<F> {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after the setup phase.
+    ///
+    /// In this state the receiver performs VOLE extension (potentially multiple times).
+    pub struct Extension<F> {
+        /// PRGs seeded with both base OT seeds, one pair for each bit of the field.
+        pub(super) prgs: Vec<[Prg; 2]>,
+        /// RNG used to sample random inputs.
+        pub(super) rng: Prg,
+
+        /// Current transfer id.
+        pub(super) transfer_id: TransferId,
+        /// Number of VOLEs extended.
+        pub(super) counter: usize,
+
+        pub(super) _pd: std::marker::PhantomData<F>,
+    }
+
+    impl<F> State for Extension<F> {}
+
+    impl<F> std::fmt::Debug for Extension<F> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Extension {{ .. }}")
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Gilboa VOLE sender.
+
+use itybity::{GetBit, Lsb0};
+use mpz_core::{prg::Prg, Block};
+use mpz_fields::Field;
+use rand::SeedableRng;
+
+use crate::{
+    gilboa::{error::SenderError, msgs::Extend, sample},
+    Subfield, TransferId, VOLESenderOutput,
+};
+
+/// Gilboa VOLE sender.
+#[derive(Debug, Default)]
+pub struct Sender<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Sender {
+    /// Creates a new Sender.
+    pub fn new() -> Self {
+        Sender {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Returns the choice bits for the base OTs, which are the bits of `delta`.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's global secret.
+    pub fn base_choices<F: Field>(&self, delta: F) -> Vec<bool> {
+        (0..F::BIT_SIZE)
+            .map(|i| GetBit::<Lsb0>::get_bit(&delta, i))
+            .collect()
+    }
+
+    /// Completes the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's global secret.
+    /// * `seeds` - The seeds received with the base OTs, chosen by the bits of `delta`.
+    pub fn setup<F: Field>(
+        self,
+        delta: F,
+        seeds: Vec<Block>,
+    ) -> Result<Sender<state::Extension<F>>, SenderError> {
+        if seeds.len() != F::BIT_SIZE {
+            return Err(SenderError::InvalidSeedCount {
+                expected: F::BIT_SIZE,
+                actual: seeds.len(),
+            });
+        }
+
+        Ok(Sender {
+            state: state::Extension {
+                delta,
+                choices: self.base_choices(delta),
+                prgs: seeds.into_iter().map(Prg::from_seed).collect(),
+                transfer_id: TransferId::default(),
+                counter: 0,
+            },
+        })
+    }
+}
+
+impl<F: Field> Sender<state::Extension<F>> {
+    /// Returns the sender's global secret.
+    pub fn delta(&self) -> F {
+        self.state.delta
+    }
+
+    /// Returns the number of VOLEs which have been extended.
+    pub fn count(&self) -> usize {
+        self.state.counter
+    }
+
+    /// Extends VOLEs using the receiver's corrections.
+    ///
+    /// The receiver's inputs are elements of the subfield `S`.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of VOLEs to extend.
+    /// * `extend` - The receiver's corrections.
+    pub fn extend<S: Subfield<F>>(
+        &mut self,
+        count: usize,
+        extend: Extend<S>,
+    ) -> Result<VOLESenderOutput<F>, SenderError> {
+        let Extend { corrections } = extend;
+
+        let expected = F::BIT_SIZE * count;
+        if corrections.len() != expected {
+            return Err(SenderError::InvalidCorrectionCount {
+                expected,
+                actual: corrections.len(),
+            });
+        }
+
+        let two = F::two_pow(1);
+        let mut values = vec![F::zero(); count];
+
+        // For each bit j of delta, the sender holds a_j = r_{j,0} - delta_j * u. Summing
+        // over the bits gives v = sum_j 2^j * r_{j,0} - delta * u, evaluated in Horner form
+        // starting from the most significant bit.
+        for ((prg, &choice), corrections) in self
+            .state
+            .prgs
+            .iter_mut()
+            .zip(&self.state.choices)
+            .zip(corrections.chunks_exact(count.max(1)))
+            .rev()
+        {
+            let chosen: Vec<S> = sample(prg, count);
+            for ((value, r), &c) in values.iter_mut().zip(chosen).zip(corrections) {
+                let a = if choice { r.add(c) } else { r };
+                *value = *value * two + a.embed();
+            }
+        }
+
+        self.state.counter += count;
+
+        Ok(VOLESenderOutput {
+            id: self.state.transfer_id.next(),
+            values,
+        })
+    }
+}
+
+/// The sender's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl<F> Sealed for super::Extension<F> {}
+    }
+
+    /// The sender's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The sender's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The sender's state after the setup phase.
+    ///
+    /// In this state the sender performs VOLE extension (potentially multiple times).
+    pub struct Extension<F> {
+        /// Sender's global secret.
+        pub(super) delta: F,
+        /// The bits of delta.
+        pub(super) choices: Vec<bool>,
+        /// PRGs seeded with the chosen base OT seeds, one for each bit of delta.
+        pub(super) prgs: Vec<Prg>,
+
+        /// Current transfer id.
+        pub(super) transfer_id: TransferId,
+        /// Number of VOLEs extended.
+        pub(super) counter: usize,
+    }
+
+    impl<F> State for Extension<F> {}
+
+    impl<F> std::fmt::Debug for Extension<F> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Extension {{ .. }}")
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Ideal VOLE functionality.
+
+use mpz_core::{prg::Prg, Block};
+use mpz_fields::Field;
+use rand::SeedableRng;
+
+use crate::{
+    RVOLEReceiverOutput, RVOLESenderOutput, TransferId, VOLEReceiverOutput, VOLESenderOutput,
+};
+
+/// The ideal VOLE functionality.
+#[derive(Debug)]
+pub struct IdealVOLE<F> {
+    delta: F,
+    transfer_id: TransferId,
+    counter: usize,
+    prg: Prg,
+}
+
+impl<F: Field> IdealVOLE<F> {
+    /// Creates a new ideal VOLE functionality.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the PRG.
+    /// * `delta` - The sender's global secret.
+    pub fn new(seed: Block, delta: F) -> Self {
+        IdealVOLE {
+            delta,
+            transfer_id: TransferId::default(),
+            counter: 0,
+            prg: Prg::from_seed(seed),
+        }
+    }
+
+    /// Returns the sender's global secret, delta.
+    pub fn delta(&self) -> F {
+        self.delta
+    }
+
+    /// Sets the sender's global secret, delta.
+    pub fn set_delta(&mut self, delta: F) {
+        self.delta = delta;
+    }
+
+    /// Returns the current transfer id.
+    pub fn transfer_id(&self) -> TransferId {
+        self.transfer_id
+    }
+
+    /// Returns the number of VOLEs executed.
+    pub fn count(&self) -> usize {
+        self.counter
+    }
+
+    /// Executes VOLEs with random receiver inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of VOLEs to execute.
+    pub fn random(&mut self, count: usize) -> (RVOLESenderOutput<F>, RVOLEReceiverOutput<F>) {
+        let inputs: Vec<F> = (0..count).map(|_| F::rand(&mut self.prg)).collect();
+        let (sender_values, receiver_values) = self.correlate(&inputs);
+
+        let id = self.transfer_id.next();
+
+        (
+            RVOLESenderOutput {
+                id,
+                values: sender_values,
+            },
+            RVOLEReceiverOutput {
+                id,
+                inputs,
+                values: receiver_values,
+            },
+        )
+    }
+
+    /// Executes VOLEs with the receiver's chosen inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The receiver's inputs.
+    pub fn chosen(&mut self, inputs: &[F]) -> (VOLESenderOutput<F>, VOLEReceiverOutput<F>) {
+        let (sender_values, receiver_values) = self.correlate(inputs);
+
+        let id = self.transfer_id.next();
+
+        (
+            VOLESenderOutput {
+                id,
+                values: sender_values,
+            },
+            VOLEReceiverOutput {
+                id,
+                values: receiver_values,
+            },
+        )
+    }
+
+    fn correlate(&mut self, inputs: &[F]) -> (Vec<F>, Vec<F>) {
+        let sender_values: Vec<F> = (0..inputs.len()).map(|_| F::rand(&mut self.prg)).collect();
+        let receiver_values = sender_values
+            .iter()
+            .zip(inputs)
+            .map(|(&v, &u)| v + u * self.delta)
+            .collect();
+
+        self.counter += inputs.len();
+
+        (sender_values, receiver_values)
+    }
+}
+
+impl<F: Field> Default for IdealVOLE<F> {
+    fn default() -> Self {
+        let mut prg = Prg::from_seed(Block::ZERO);
+        let delta = F::rand(&mut prg);
+        Self::new(Block::ONES, delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::assert_vole, Subfield};
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256};
+
+    fn test_ideal_vole<F: Field + Subfield<F>>() {
+        let mut ideal = IdealVOLE::<F>::default();
+        let delta = ideal.delta();
+
+        let (sender_output, receiver_output) = ideal.random(100);
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_vole(
+            delta,
+            &receiver_output.inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+
+        let inputs: Vec<F> = (0..100).map(|i| F::two_pow(i)).collect();
+        let (sender_output, receiver_output) = ideal.chosen(&inputs);
+
+        assert_vole(
+            delta,
+            &inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+        assert_eq!(ideal.count(), 200);
+    }
+
+    #[test]
+    fn test_ideal_vole_p256() {
+        test_ideal_vole::<P256>();
+    }
+
+    #[test]
+    fn test_ideal_vole_gf2_128() {
+        test_ideal_vole::<Gf2_128>();
+    }
+}
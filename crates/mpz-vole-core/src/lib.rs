@@ -0,0 +1,96 @@
+//! Low-level crate containing core functionalities for vector oblivious linear evaluation (VOLE)
+//! protocols.
+//!
+//! A VOLE over a field `F` gives the sender a global secret `Δ` and values `v_i`, and the receiver
+//! inputs `u_i` and values `w_i`, such that `w_i = v_i + u_i * Δ`. In a subfield VOLE the inputs
+//! `u_i` are elements of a [`Subfield`] of `F`.
+//!
+//! This crate is not intended to be used directly. Instead, use the higher-level APIs provided by
+//! the `mpz-vole` crate.
+//!
+//! # ⚠️ Warning ⚠️
+//!
+//! Some implementations make assumptions about invariants which may not be checked if using these
+//! low-level APIs naively. Failing to uphold these invariants may result in security vulnerabilities.
+//!
+//! USE AT YOUR OWN RISK.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+use serde::{Deserialize, Serialize};
+
+pub mod gilboa;
+pub mod ideal;
+pub mod subfield;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test;
+
+pub use subfield::Subfield;
+
+/// A VOLE transfer identifier.
+///
+/// Multiple transfers may be batched together under the same transfer ID.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct TransferId(u64);
+
+impl std::fmt::Display for TransferId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TransferId({})", self.0)
+    }
+}
+
+impl TransferId {
+    /// Returns the current transfer ID, incrementing `self` in-place.
+    pub(crate) fn next(&mut self) -> Self {
+        let id = *self;
+        self.0 += 1;
+        id
+    }
+}
+
+/// The output the sender receives from the VOLE functionality.
+#[derive(Debug)]
+pub struct VOLESenderOutput<F> {
+    /// The transfer id.
+    pub id: TransferId,
+    /// The sender's values `v_i`.
+    pub values: Vec<F>,
+}
+
+/// The output the receiver receives from the VOLE functionality.
+#[derive(Debug)]
+pub struct VOLEReceiverOutput<F> {
+    /// The transfer id.
+    pub id: TransferId,
+    /// The receiver's values `w_i`.
+    pub values: Vec<F>,
+}
+
+/// The output the sender receives from the random VOLE functionality.
+#[derive(Debug)]
+pub struct RVOLESenderOutput<F> {
+    /// The transfer id.
+    pub id: TransferId,
+    /// The sender's values `v_i`.
+    pub values: Vec<F>,
+}
+
+/// The output the receiver receives from the random VOLE functionality.
+#[derive(Debug)]
+pub struct RVOLEReceiverOutput<F, S = F> {
+    /// The transfer id.
+    pub id: TransferId,
+    /// The receiver's random inputs `u_i`.
+    pub inputs: Vec<S>,
+    /// The receiver's values `w_i`.
+    pub values: Vec<F>,
+}
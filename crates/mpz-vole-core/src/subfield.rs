@@ -0,0 +1,86 @@
+//! Subfields over which the receiver's inputs of a VOLE are defined.
+
+use std::fmt::Debug;
+
+use mpz_fields::{
+    curve25519::Curve25519Scalar, gf2_128::Gf2_128, gf2_8::Gf2_8, p256::P256, p384::P384, Field,
+};
+use rand::Rng;
+
+/// A subfield of the field `F`.
+///
+/// In a subfield VOLE the receiver's inputs `u_i` are elements of the subfield, while the sender's
+/// global secret `Δ` and the values `v_i` and `w_i` are elements of `F`. Every field is a subfield
+/// of itself, and `bool` is the subfield GF(2) of [`Gf2_128`].
+pub trait Subfield<F>: Copy + Debug + PartialEq + Send + Sync + Unpin + 'static {
+    /// Returns the additive identity.
+    fn zero() -> Self;
+
+    /// Returns the sum of two elements.
+    fn add(self, rhs: Self) -> Self;
+
+    /// Returns the additive inverse.
+    fn neg(self) -> Self;
+
+    /// Samples a uniformly random element.
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// Embeds the element into `F`.
+    fn embed(self) -> F;
+}
+
+macro_rules! impl_subfield_self {
+    ($($field:ty),*) => {
+        $(
+            impl Subfield<$field> for $field {
+                fn zero() -> Self {
+                    <$field as Field>::zero()
+                }
+
+                fn add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+
+                fn neg(self) -> Self {
+                    -self
+                }
+
+                fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    <$field as mpz_fields::UniformRand>::rand(rng)
+                }
+
+                fn embed(self) -> $field {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_subfield_self!(P256, P384, Curve25519Scalar, Gf2_128, Gf2_8);
+
+impl Subfield<Gf2_128> for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn add(self, rhs: Self) -> Self {
+        self ^ rhs
+    }
+
+    fn neg(self) -> Self {
+        self
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        rng.gen()
+    }
+
+    fn embed(self) -> Gf2_128 {
+        if self {
+            <Gf2_128 as Field>::one()
+        } else {
+            <Gf2_128 as Field>::zero()
+        }
+    }
+}
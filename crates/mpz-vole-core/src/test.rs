@@ -0,0 +1,29 @@
+//! Test utilities.
+
+use mpz_fields::Field;
+
+use crate::Subfield;
+
+/// Asserts that the values are a valid VOLE correlation, ie `w_i = v_i + u_i * Δ`.
+///
+/// # Arguments
+///
+/// * `delta` - The sender's global secret.
+/// * `inputs` - The receiver's inputs `u_i`.
+/// * `sender_values` - The sender's values `v_i`.
+/// * `receiver_values` - The receiver's values `w_i`.
+pub fn assert_vole<F: Field, S: Subfield<F>>(
+    delta: F,
+    inputs: &[S],
+    sender_values: &[F],
+    receiver_values: &[F],
+) {
+    assert_eq!(inputs.len(), sender_values.len());
+    assert_eq!(inputs.len(), receiver_values.len());
+
+    assert!(inputs
+        .iter()
+        .zip(sender_values)
+        .zip(receiver_values)
+        .all(|((&u, &v), &w)| w == v + u.embed() * delta));
+}
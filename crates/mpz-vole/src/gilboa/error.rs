@@ -0,0 +1,47 @@
+use crate::VOLEError;
+
+/// A Gilboa VOLE sender error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_vole_core::gilboa::SenderError),
+    #[error(transparent)]
+    BaseOTError(#[from] mpz_ot::OTError),
+    #[error("{0}")]
+    StateError(String),
+}
+
+impl From<SenderError> for VOLEError {
+    fn from(err: SenderError) -> Self {
+        match err {
+            SenderError::IOError(e) => e.into(),
+            e => VOLEError::SenderError(Box::new(e)),
+        }
+    }
+}
+
+/// A Gilboa VOLE receiver error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    CoreError(#[from] mpz_vole_core::gilboa::ReceiverError),
+    #[error(transparent)]
+    BaseOTError(#[from] mpz_ot::OTError),
+    #[error("{0}")]
+    StateError(String),
+}
+
+impl From<ReceiverError> for VOLEError {
+    fn from(err: ReceiverError) -> Self {
+        match err {
+            ReceiverError::IOError(e) => e.into(),
+            e => VOLEError::ReceiverError(Box::new(e)),
+        }
+    }
+}
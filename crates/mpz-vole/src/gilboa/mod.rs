@@ -0,0 +1,221 @@
+//! An implementation of a semi-honest subfield VOLE protocol based on Gilboa multiplication.
+//!
+//! See [`mpz_vole_core::gilboa`] for details.
+//!
+//! # Examples
+//!
+//! ```
+//! use mpz_common::executor::test_st_executor;
+//! use mpz_core::Block;
+//! use mpz_fields::{p256::P256, Field};
+//! use mpz_ot::ideal::ot::ideal_ot;
+//! use mpz_vole::{
+//!     gilboa::{Receiver, Sender},
+//!     VOLEReceiver, VOLESender, VOLESetup,
+//! };
+//!
+//! # futures::executor::block_on(async {
+//! let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+//! let (base_sender, base_receiver) = ideal_ot::<[Block; 2], Block>();
+//!
+//! let mut sender = Sender::<_, P256>::new(base_receiver);
+//! let mut receiver = Receiver::<_, P256>::new(base_sender);
+//!
+//! // Perform the setup.
+//! futures::try_join!(
+//!     sender.setup(&mut ctx_sender),
+//!     receiver.setup(&mut ctx_receiver)
+//! ).unwrap();
+//!
+//! // Perform the VOLE.
+//! let inputs = vec![P256::one(), P256::two_pow(1)];
+//!
+//! let (output_sender, output_receiver) = futures::try_join!(
+//!     sender.send(&mut ctx_sender, inputs.len()),
+//!     receiver.receive(&mut ctx_receiver, &inputs)
+//! ).unwrap();
+//!
+//! let delta = sender.delta().unwrap();
+//! for ((v, w), u) in output_sender.values.iter().zip(&output_receiver.values).zip(&inputs) {
+//!     assert_eq!(*w, *v + *u * delta);
+//! }
+//! # });
+//! ```
+
+mod error;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub use mpz_vole_core::gilboa::msgs;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::{executor::test_st_executor, Context};
+    use mpz_core::Block;
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256, Field};
+    use mpz_ot::ideal::ot::{ideal_ot, IdealOTReceiver, IdealOTSender};
+    use mpz_vole_core::test::assert_vole;
+    use rand::{thread_rng, Rng};
+    use serio::{Deserialize, Serialize};
+
+    use crate::{
+        RandomVOLEReceiver, RandomVOLESender, Subfield, VOLEReceiver, VOLESender, VOLESetup,
+    };
+
+    async fn setup<Ctx: Context, F: Field + Subfield<F> + Serialize + Deserialize>(
+        ctx_sender: &mut Ctx,
+        ctx_receiver: &mut Ctx,
+    ) -> (
+        Sender<IdealOTReceiver<Block>, F>,
+        Receiver<IdealOTSender<[Block; 2]>, F>,
+    ) {
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(base_receiver);
+        let mut receiver = Receiver::new(base_sender);
+
+        tokio::try_join!(sender.setup(ctx_sender), receiver.setup(ctx_receiver)).unwrap();
+
+        (sender, receiver)
+    }
+
+    async fn test_gilboa<F: Field + Subfield<F> + Serialize + Deserialize>() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup::<_, F>(&mut ctx_sender, &mut ctx_receiver).await;
+        let delta = sender.delta().unwrap();
+
+        let inputs: Vec<F> = {
+            let mut rng = thread_rng();
+            (0..64).map(|_| F::rand(&mut rng)).collect()
+        };
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send(&mut ctx_sender, inputs.len()),
+            receiver.receive(&mut ctx_receiver, &inputs)
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_vole(
+            delta,
+            &inputs,
+            &output_sender.values,
+            &output_receiver.values,
+        );
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send_random(&mut ctx_sender, 64),
+            receiver.receive_random(&mut ctx_receiver, 64)
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_vole(
+            delta,
+            &output_receiver.inputs,
+            &output_sender.values,
+            &output_receiver.values,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gilboa_p256() {
+        test_gilboa::<P256>().await;
+    }
+
+    #[tokio::test]
+    async fn test_gilboa_gf2_128() {
+        test_gilboa::<Gf2_128>().await;
+    }
+
+    #[tokio::test]
+    async fn test_gilboa_subfield_gf2() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot::<[Block; 2], Block>();
+
+        let mut sender = Sender::<_, Gf2_128, bool>::new(base_receiver);
+        let mut receiver = Receiver::<_, Gf2_128, bool>::new(base_sender);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+        let delta = sender.delta().unwrap();
+
+        let inputs: Vec<bool> = {
+            let mut rng = thread_rng();
+            (0..64).map(|_| rng.gen()).collect()
+        };
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send(&mut ctx_sender, inputs.len()),
+            receiver.receive(&mut ctx_receiver, &inputs)
+        )
+        .unwrap();
+
+        assert_vole(
+            delta,
+            &inputs,
+            &output_sender.values,
+            &output_receiver.values,
+        );
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send_random(&mut ctx_sender, 64),
+            receiver.receive_random(&mut ctx_receiver, 64)
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_vole(
+            delta,
+            &output_receiver.inputs,
+            &output_sender.values,
+            &output_receiver.values,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gilboa_setup_with_delta() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (base_sender, base_receiver) = ideal_ot::<[Block; 2], Block>();
+
+        let mut sender = Sender::<_, P256>::new(base_receiver);
+        let mut receiver = Receiver::<_, P256>::new(base_sender);
+
+        let delta = P256::two_pow(7);
+        tokio::try_join!(
+            async {
+                sender
+                    .setup_with_delta(&mut ctx_sender, delta)
+                    .await
+                    .map_err(crate::VOLEError::from)
+            },
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        assert_eq!(sender.delta().unwrap(), delta);
+
+        let inputs = vec![P256::one(); 8];
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send(&mut ctx_sender, inputs.len()),
+            receiver.receive(&mut ctx_receiver, &inputs)
+        )
+        .unwrap();
+
+        assert_vole(
+            delta,
+            &inputs,
+            &output_sender.values,
+            &output_receiver.values,
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::{marker::PhantomData, mem};
+
+use async_trait::async_trait;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_fields::Field;
+use mpz_ot::{OTSender, OTSetup};
+use mpz_vole_core::gilboa::{receiver_state as state, Receiver as ReceiverCore};
+use rand::{thread_rng, Rng};
+use serio::{Deserialize, Serialize, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    gilboa::ReceiverError, RVOLEReceiverOutput, RandomVOLEReceiver, Subfield, VOLEError,
+    VOLEReceiver, VOLEReceiverOutput, VOLESetup,
+};
+
+#[derive(Debug)]
+pub(crate) enum State<F> {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(Box<ReceiverCore<state::Extension<F>>>),
+    Error,
+}
+
+/// Gilboa VOLE receiver.
+///
+/// The receiver's inputs are elements of the subfield `S`, which defaults to `F`.
+#[derive(Debug)]
+pub struct Receiver<BaseOT, F, S = F> {
+    state: State<F>,
+    base: BaseOT,
+    _pd: PhantomData<fn() -> S>,
+}
+
+impl<BaseOT, F, S> Receiver<BaseOT, F, S>
+where
+    BaseOT: Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base OT sender.
+    pub fn new(base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(ReceiverCore::new()),
+            base,
+            _pd: PhantomData,
+        }
+    }
+
+    fn take_extension(&mut self) -> Result<Box<ReceiverCore<state::Extension<F>>>, ReceiverError> {
+        let State::Extension(receiver) = mem::replace(&mut self.state, State::Error) else {
+            return Err(ReceiverError::StateError(
+                "receiver is not in the extension state".to_string(),
+            ));
+        };
+
+        Ok(receiver)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT, F, S> VOLESetup<Ctx> for Receiver<BaseOT, F, S>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTSender<Ctx, [Block; 2]> + Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), VOLEError> {
+        let receiver = match mem::replace(&mut self.state, State::Error) {
+            State::Initialized(receiver) => receiver,
+            State::Extension(receiver) => {
+                self.state = State::Extension(receiver);
+                return Ok(());
+            }
+            State::Error => {
+                return Err(ReceiverError::StateError(
+                    "receiver is not in the initialized state".to_string(),
+                )
+                .into())
+            }
+        };
+
+        self.base.setup(ctx).await.map_err(ReceiverError::from)?;
+
+        let seeds: Vec<[Block; 2]> = {
+            let mut rng = thread_rng();
+            (0..F::BIT_SIZE).map(|_| rng.gen()).collect()
+        };
+
+        self.base
+            .send(ctx, &seeds)
+            .await
+            .map_err(ReceiverError::from)?;
+
+        let receiver = receiver.setup::<F>(seeds).map_err(ReceiverError::from)?;
+
+        self.state = State::Extension(Box::new(receiver));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT, F, S> VOLEReceiver<Ctx, F, S> for Receiver<BaseOT, F, S>
+where
+    Ctx: Context,
+    BaseOT: Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    async fn receive(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: &[S],
+    ) -> Result<VOLEReceiverOutput<F>, VOLEError> {
+        let mut receiver = self.take_extension()?;
+
+        let inputs = inputs.to_vec();
+        let (receiver, (output, extend)) = Backend::spawn(move || {
+            let output = receiver.extend(&inputs);
+            (receiver, output)
+        })
+        .await;
+
+        self.state = State::Extension(receiver);
+
+        ctx.io_mut().send(extend).await?;
+
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT, F, S> RandomVOLEReceiver<Ctx, F, S> for Receiver<BaseOT, F, S>
+where
+    Ctx: Context,
+    BaseOT: Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    async fn receive_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RVOLEReceiverOutput<F, S>, VOLEError> {
+        let mut receiver = self.take_extension()?;
+
+        let (receiver, (output, extend)) = Backend::spawn(move || {
+            let output = receiver.extend_random(count);
+            (receiver, output)
+        })
+        .await;
+
+        self.state = State::Extension(receiver);
+
+        ctx.io_mut().send(extend).await?;
+
+        Ok(output)
+    }
+}
@@ -0,0 +1,174 @@
+use std::{marker::PhantomData, mem};
+
+use async_trait::async_trait;
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_fields::Field;
+use mpz_ot::{OTReceiver, OTSetup};
+use mpz_vole_core::gilboa::{msgs::Extend, sender_state as state, Sender as SenderCore};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt as _, Deserialize, Serialize};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    gilboa::SenderError, RVOLESenderOutput, RandomVOLESender, Subfield, VOLEError, VOLESender,
+    VOLESenderOutput, VOLESetup,
+};
+
+#[derive(Debug)]
+pub(crate) enum State<F> {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(Box<SenderCore<state::Extension<F>>>),
+    Error,
+}
+
+/// Gilboa VOLE sender.
+///
+/// The receiver's inputs are elements of the subfield `S`, which defaults to `F`.
+#[derive(Debug)]
+pub struct Sender<BaseOT, F, S = F> {
+    state: State<F>,
+    base: BaseOT,
+    _pd: PhantomData<fn() -> S>,
+}
+
+impl<BaseOT, F, S> Sender<BaseOT, F, S>
+where
+    BaseOT: Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base OT receiver.
+    pub fn new(base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(SenderCore::new()),
+            base,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Returns the sender's global secret.
+    pub fn delta(&self) -> Result<F, SenderError> {
+        match &self.state {
+            State::Extension(sender) => Ok(sender.delta()),
+            _ => Err(SenderError::StateError(
+                "sender is not in the extension state".to_string(),
+            )),
+        }
+    }
+
+    /// Performs the base OT setup with the provided delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `delta` - The sender's global secret.
+    pub async fn setup_with_delta<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        delta: F,
+    ) -> Result<(), SenderError>
+    where
+        BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block>,
+    {
+        let State::Initialized(sender) = mem::replace(&mut self.state, State::Error) else {
+            return Err(SenderError::StateError(
+                "sender is not in the initialized state".to_string(),
+            ));
+        };
+
+        self.base.setup(ctx).await?;
+
+        let choices = sender.base_choices(delta);
+        let seeds = self.base.receive(ctx, &choices).await?.msgs;
+
+        let sender = sender.setup(delta, seeds)?;
+
+        self.state = State::Extension(Box::new(sender));
+
+        Ok(())
+    }
+
+    async fn extend<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<VOLESenderOutput<F>, SenderError> {
+        let State::Extension(mut sender) = mem::replace(&mut self.state, State::Error) else {
+            return Err(SenderError::StateError(
+                "sender is not in the extension state".to_string(),
+            ));
+        };
+
+        let extend: Extend<S> = ctx.io_mut().expect_next().await?;
+
+        let (sender, output) =
+            Backend::spawn(move || sender.extend(count, extend).map(|output| (sender, output)))
+                .await?;
+
+        self.state = State::Extension(sender);
+
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT, F, S> VOLESetup<Ctx> for Sender<BaseOT, F, S>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block> + Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), VOLEError> {
+        if matches!(self.state, State::Extension(_)) {
+            return Ok(());
+        }
+
+        let delta = F::rand(&mut thread_rng());
+
+        self.setup_with_delta(ctx, delta)
+            .await
+            .map_err(VOLEError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT, F, S> VOLESender<Ctx, F> for Sender<BaseOT, F, S>
+where
+    Ctx: Context,
+    BaseOT: Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    async fn send(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<VOLESenderOutput<F>, VOLEError> {
+        self.extend(ctx, count).await.map_err(VOLEError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT, F, S> RandomVOLESender<Ctx, F> for Sender<BaseOT, F, S>
+where
+    Ctx: Context,
+    BaseOT: Send,
+    F: Field + Serialize + Deserialize,
+    S: Subfield<F> + Serialize + Deserialize,
+{
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RVOLESenderOutput<F>, VOLEError> {
+        let VOLESenderOutput { id, values } = self.extend(ctx, count).await?;
+
+        Ok(RVOLESenderOutput { id, values })
+    }
+}
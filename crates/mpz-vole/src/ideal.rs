@@ -0,0 +1,158 @@
+//! Ideal VOLE functionality.
+
+use async_trait::async_trait;
+
+use mpz_common::{
+    ideal::{ideal_f2p, Alice, Bob},
+    Context,
+};
+use mpz_fields::Field;
+use mpz_vole_core::ideal::IdealVOLE;
+
+use crate::{
+    RVOLEReceiverOutput, RVOLESenderOutput, RandomVOLEReceiver, RandomVOLESender, VOLEError,
+    VOLEReceiver, VOLEReceiverOutput, VOLESender, VOLESenderOutput, VOLESetup,
+};
+
+fn vole<F: Field>(
+    f: &mut IdealVOLE<F>,
+    sender_count: usize,
+    receiver_inputs: Vec<F>,
+) -> (VOLESenderOutput<F>, VOLEReceiverOutput<F>) {
+    assert_eq!(sender_count, receiver_inputs.len());
+
+    f.chosen(&receiver_inputs)
+}
+
+fn rvole<F: Field>(
+    f: &mut IdealVOLE<F>,
+    sender_count: usize,
+    receiver_count: usize,
+) -> (RVOLESenderOutput<F>, RVOLEReceiverOutput<F>) {
+    assert_eq!(sender_count, receiver_count);
+
+    f.random(sender_count)
+}
+
+/// Returns an ideal VOLE sender and receiver.
+pub fn ideal_vole<F: Field>() -> (IdealVOLESender<F>, IdealVOLEReceiver<F>) {
+    let (alice, bob) = ideal_f2p(IdealVOLE::default());
+    (IdealVOLESender(alice), IdealVOLEReceiver(bob))
+}
+
+/// Ideal VOLE sender.
+#[derive(Debug, Clone)]
+pub struct IdealVOLESender<F>(Alice<IdealVOLE<F>>);
+
+impl<F: Field> IdealVOLESender<F> {
+    /// Returns the sender's global secret.
+    pub fn delta(&mut self) -> F {
+        self.0.get_mut().delta()
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context, F: Field> VOLESetup<Ctx> for IdealVOLESender<F> {
+    async fn setup(&mut self, _ctx: &mut Ctx) -> Result<(), VOLEError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context, F: Field> VOLESender<Ctx, F> for IdealVOLESender<F> {
+    async fn send(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<VOLESenderOutput<F>, VOLEError> {
+        Ok(self.0.call(ctx, count, vole).await)
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context, F: Field> RandomVOLESender<Ctx, F> for IdealVOLESender<F> {
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RVOLESenderOutput<F>, VOLEError> {
+        Ok(self.0.call(ctx, count, rvole).await)
+    }
+}
+
+/// Ideal VOLE receiver.
+#[derive(Debug, Clone)]
+pub struct IdealVOLEReceiver<F>(Bob<IdealVOLE<F>>);
+
+#[async_trait]
+impl<Ctx: Context, F: Field> VOLESetup<Ctx> for IdealVOLEReceiver<F> {
+    async fn setup(&mut self, _ctx: &mut Ctx) -> Result<(), VOLEError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context, F: Field> VOLEReceiver<Ctx, F> for IdealVOLEReceiver<F> {
+    async fn receive(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: &[F],
+    ) -> Result<VOLEReceiverOutput<F>, VOLEError> {
+        Ok(self.0.call(ctx, inputs.to_vec(), vole).await)
+    }
+}
+
+#[async_trait]
+impl<Ctx: Context, F: Field> RandomVOLEReceiver<Ctx, F> for IdealVOLEReceiver<F> {
+    async fn receive_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RVOLEReceiverOutput<F>, VOLEError> {
+        Ok(self.0.call(ctx, count, rvole).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_common::executor::test_st_executor;
+    use mpz_fields::p256::P256;
+    use mpz_vole_core::test::assert_vole;
+
+    #[tokio::test]
+    async fn test_ideal_vole() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = ideal_vole::<P256>();
+        let delta = sender.delta();
+
+        let inputs: Vec<P256> = (0..10).map(P256::two_pow).collect();
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.send(&mut ctx_sender, inputs.len()),
+            receiver.receive(&mut ctx_receiver, &inputs)
+        )
+        .unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_vole(
+            delta,
+            &inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+
+        let (sender_output, receiver_output) = tokio::try_join!(
+            sender.send_random(&mut ctx_sender, 10),
+            receiver.receive_random(&mut ctx_receiver, 10)
+        )
+        .unwrap();
+
+        assert_vole(
+            delta,
+            &receiver_output.inputs,
+            &sender_output.values,
+            &receiver_output.values,
+        );
+    }
+}
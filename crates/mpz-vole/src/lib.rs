@@ -0,0 +1,119 @@
+//! Implementations of vector oblivious linear evaluation (VOLE) protocols.
+//!
+//! A VOLE over a field `F` gives the sender a global secret `Δ` and values `v_i`, and the receiver
+//! inputs `u_i` and values `w_i`, such that `w_i = v_i + u_i * Δ`. In a subfield VOLE the
+//! receiver's inputs are elements of a [`Subfield`] of `F`.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+pub mod gilboa;
+#[cfg(any(test, feature = "ideal"))]
+pub mod ideal;
+
+use async_trait::async_trait;
+
+pub use mpz_vole_core::{
+    RVOLEReceiverOutput, RVOLESenderOutput, Subfield, TransferId, VOLEReceiverOutput,
+    VOLESenderOutput,
+};
+
+/// A VOLE error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum VOLEError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("context error: {0}")]
+    Context(#[from] mpz_common::ContextError),
+    #[error("sender error: {0}")]
+    SenderError(Box<dyn std::error::Error + Send + Sync>),
+    #[error("receiver error: {0}")]
+    ReceiverError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// A VOLE protocol that needs to perform a one-time setup.
+#[async_trait]
+pub trait VOLESetup<Ctx> {
+    /// Runs any one-time setup for the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), VOLEError>;
+}
+
+/// A VOLE sender.
+#[async_trait]
+pub trait VOLESender<Ctx, F> {
+    /// Obliviously evaluates the receiver's inputs.
+    ///
+    /// Returns the sender's values `v_i`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of VOLEs to evaluate.
+    async fn send(&mut self, ctx: &mut Ctx, count: usize)
+        -> Result<VOLESenderOutput<F>, VOLEError>;
+}
+
+/// A random VOLE sender.
+#[async_trait]
+pub trait RandomVOLESender<Ctx, F> {
+    /// Obliviously evaluates random receiver inputs.
+    ///
+    /// Returns the sender's values `v_i`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of VOLEs to evaluate.
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RVOLESenderOutput<F>, VOLEError>;
+}
+
+/// A VOLE receiver.
+#[async_trait]
+pub trait VOLEReceiver<Ctx, F, S = F> {
+    /// Obliviously evaluates the inputs with the sender's global secret.
+    ///
+    /// Returns the receiver's values `w_i`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `inputs` - The receiver's inputs `u_i`.
+    async fn receive(
+        &mut self,
+        ctx: &mut Ctx,
+        inputs: &[S],
+    ) -> Result<VOLEReceiverOutput<F>, VOLEError>;
+}
+
+/// A random VOLE receiver.
+#[async_trait]
+pub trait RandomVOLEReceiver<Ctx, F, S = F> {
+    /// Obliviously evaluates random inputs with the sender's global secret.
+    ///
+    /// Returns the receiver's random inputs `u_i` and values `w_i`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of VOLEs to evaluate.
+    async fn receive_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RVOLEReceiverOutput<F, S>, VOLEError>;
+}
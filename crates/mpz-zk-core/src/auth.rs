@@ -0,0 +1,460 @@
+//! Information-theoretic MACs (IT-MACs) over bits.
+//!
+//! A bit `x` is authenticated by a global correlation `Δ` when the holder of `x` knows a MAC
+//! `M = K + x * Δ` and the other party knows the key `K`. Random authenticated bits are exactly
+//! random COTs: the COT receiver holds the choice bit and its message as the MAC, the COT sender
+//! holds its message as the key.
+//!
+//! Authenticated bits are linear, so XOR and the addition of public constants are local
+//! operations. AND gates require an [`AuthTriple`], which can either be checked directly in the
+//! style of QuickSilver or consumed to multiply two other authenticated bits.
+
+use std::ops::BitXor;
+
+use mpz_core::Block;
+use mpz_ot_core::{RCOTReceiverOutput, RCOTSenderOutput};
+use serde::{Deserialize, Serialize};
+
+/// Errors that can occur when using authenticated bits.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum AuthError {
+    #[error("invalid number of COTs: sender has {sender}, receiver has {receiver}")]
+    CountMismatch { sender: usize, receiver: usize },
+    #[error("invalid MAC for authenticated bit")]
+    InvalidMac,
+}
+
+/// Returns `block` if `bit` is set, otherwise zero.
+#[inline]
+pub(crate) fn select(bit: bool, block: Block) -> Block {
+    if bit {
+        block
+    } else {
+        Block::ZERO
+    }
+}
+
+/// A bit and its MAC, held by the party being authenticated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthBit {
+    bit: bool,
+    mac: Block,
+}
+
+impl AuthBit {
+    /// Creates a new authenticated bit.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit` - The bit.
+    /// * `mac` - The MAC of the bit.
+    pub fn new(bit: bool, mac: Block) -> Self {
+        Self { bit, mac }
+    }
+
+    /// Returns an authenticated public constant.
+    ///
+    /// The corresponding key is [`AuthKey::constant`].
+    pub fn constant(bit: bool) -> Self {
+        Self {
+            bit,
+            mac: Block::ZERO,
+        }
+    }
+
+    /// Returns the bit.
+    pub fn bit(&self) -> bool {
+        self.bit
+    }
+
+    /// Returns the MAC.
+    pub fn mac(&self) -> Block {
+        self.mac
+    }
+
+    /// Adds a public constant to the bit.
+    ///
+    /// The key holder must call [`AuthKey::xor_const`] with the same constant.
+    pub fn xor_const(self, value: bool) -> Self {
+        Self {
+            bit: self.bit ^ value,
+            mac: self.mac,
+        }
+    }
+
+    /// Converts a random authenticated bit into an authenticated `bit`.
+    ///
+    /// Returns the authenticated bit and the correction which must be sent to the key holder,
+    /// see [`AuthKey::fix`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bit` - The bit to authenticate.
+    pub fn fix(self, bit: bool) -> (Self, bool) {
+        (Self { bit, mac: self.mac }, bit ^ self.bit)
+    }
+
+    /// Converts random COTs into random authenticated bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `cot` - The random COTs received from the key holder.
+    pub fn from_rcot(cot: RCOTReceiverOutput<bool, Block>) -> Vec<Self> {
+        let RCOTReceiverOutput { choices, msgs, .. } = cot;
+        choices
+            .into_iter()
+            .zip(msgs)
+            .map(|(bit, mac)| Self { bit, mac })
+            .collect()
+    }
+}
+
+impl BitXor for AuthBit {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self {
+        Self {
+            bit: self.bit ^ rhs.bit,
+            mac: self.mac ^ rhs.mac,
+        }
+    }
+}
+
+/// The key of an authenticated bit, held by the party verifying it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthKey {
+    key: Block,
+}
+
+impl AuthKey {
+    /// Creates a new key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key.
+    pub fn new(key: Block) -> Self {
+        Self { key }
+    }
+
+    /// Returns the key of an authenticated public constant.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit` - The constant.
+    /// * `delta` - The global correlation.
+    pub fn constant(bit: bool, delta: Block) -> Self {
+        Self {
+            key: select(bit, delta),
+        }
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> Block {
+        self.key
+    }
+
+    /// Adds a public constant to the authenticated bit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The constant.
+    /// * `delta` - The global correlation.
+    pub fn xor_const(self, value: bool, delta: Block) -> Self {
+        Self {
+            key: self.key ^ select(value, delta),
+        }
+    }
+
+    /// Adjusts the key of a random authenticated bit with the correction from [`AuthBit::fix`].
+    ///
+    /// # Arguments
+    ///
+    /// * `correction` - The correction sent by the bit holder.
+    /// * `delta` - The global correlation.
+    pub fn fix(self, correction: bool, delta: Block) -> Self {
+        self.xor_const(correction, delta)
+    }
+
+    /// Verifies an opened bit and its MAC against this key.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit` - The opened bit.
+    /// * `mac` - The MAC of the opened bit.
+    /// * `delta` - The global correlation.
+    pub fn verify(&self, bit: bool, mac: Block, delta: Block) -> Result<(), AuthError> {
+        if mac == self.key ^ select(bit, delta) {
+            Ok(())
+        } else {
+            Err(AuthError::InvalidMac)
+        }
+    }
+
+    /// Converts random COTs into the keys of random authenticated bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `cot` - The random COTs sent to the bit holder.
+    pub fn from_rcot(cot: RCOTSenderOutput<Block>) -> Vec<Self> {
+        cot.msgs.into_iter().map(|key| Self { key }).collect()
+    }
+}
+
+impl BitXor for AuthKey {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self {
+        Self {
+            key: self.key ^ rhs.key,
+        }
+    }
+}
+
+/// A share of an authenticated bit `x = x_0 + x_1` between two parties.
+///
+/// Each party holds its own share of the bit with a MAC under the other party's global
+/// correlation, and the key for the other party's share under its own global correlation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthShare {
+    share: AuthBit,
+    key: AuthKey,
+}
+
+impl AuthShare {
+    /// Creates a new share.
+    ///
+    /// # Arguments
+    ///
+    /// * `share` - This party's share of the bit, authenticated by the other party.
+    /// * `key` - The key for the other party's share.
+    pub fn new(share: AuthBit, key: AuthKey) -> Self {
+        Self { share, key }
+    }
+
+    /// Returns this party's share of the bit.
+    pub fn share(&self) -> AuthBit {
+        self.share
+    }
+
+    /// Returns the key for the other party's share.
+    pub fn key(&self) -> AuthKey {
+        self.key
+    }
+
+    /// Converts random COTs in both directions into random authenticated shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `received` - The random COTs received from the other party.
+    /// * `sent` - The random COTs sent to the other party.
+    pub fn from_rcot(
+        received: RCOTReceiverOutput<bool, Block>,
+        sent: RCOTSenderOutput<Block>,
+    ) -> Result<Vec<Self>, AuthError> {
+        if received.msgs.len() != sent.msgs.len() {
+            return Err(AuthError::CountMismatch {
+                sender: sent.msgs.len(),
+                receiver: received.msgs.len(),
+            });
+        }
+
+        Ok(AuthBit::from_rcot(received)
+            .into_iter()
+            .zip(AuthKey::from_rcot(sent))
+            .map(|(share, key)| Self { share, key })
+            .collect())
+    }
+}
+
+impl BitXor for AuthShare {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self {
+        Self {
+            share: self.share ^ rhs.share,
+            key: self.key ^ rhs.key,
+        }
+    }
+}
+
+/// Authenticated bits `a`, `b` and `c` which should satisfy `c = a * b`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthTriple<T> {
+    /// The first input.
+    pub a: T,
+    /// The second input.
+    pub b: T,
+    /// The product.
+    pub c: T,
+}
+
+impl AuthTriple<AuthBit> {
+    /// Returns the terms `[A_0, A_1]` of the QuickSilver multiplication check.
+    ///
+    /// If `c = a * b` then `K_a * K_b + K_c * Δ = A_0 + A_1 * Δ`, where the left hand side is
+    /// computed by the key holder.
+    pub fn check_terms(&self) -> [Block; 2] {
+        let Self { a, b, c } = self;
+        let a0 = a.mac.gfmul(b.mac);
+        let a1 = select(a.bit, b.mac) ^ select(b.bit, a.mac) ^ c.mac;
+        [a0, a1]
+    }
+
+    /// Multiplies `x` and `y` by consuming the triple.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - The opened value of `x + a`.
+    /// * `e` - The opened value of `y + b`.
+    pub fn multiply(self, d: bool, e: bool) -> AuthBit {
+        let Self { a, b, c } = self;
+        AuthBit {
+            bit: c.bit ^ (d & b.bit) ^ (e & a.bit) ^ (d & e),
+            mac: c.mac ^ select(d, b.mac) ^ select(e, a.mac),
+        }
+    }
+}
+
+impl AuthTriple<AuthKey> {
+    /// Returns the expected value `K_a * K_b + K_c * Δ` of the QuickSilver multiplication check.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The global correlation.
+    pub fn check_term(&self, delta: Block) -> Block {
+        let Self { a, b, c } = self;
+        a.key.gfmul(b.key) ^ c.key.gfmul(delta)
+    }
+
+    /// Multiplies `x` and `y` by consuming the triple.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - The opened value of `x + a`.
+    /// * `e` - The opened value of `y + b`.
+    /// * `delta` - The global correlation.
+    pub fn multiply(self, d: bool, e: bool, delta: Block) -> AuthKey {
+        let Self { a, b, c } = self;
+        AuthKey {
+            key: c.key ^ select(d, b.key) ^ select(e, a.key) ^ select(d & e, delta),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_core::prg::Prg;
+    use mpz_ot_core::ideal::cot::IdealCOT;
+    use rand::{Rng, SeedableRng};
+
+    fn setup(count: usize) -> (Block, Vec<AuthBit>, Vec<AuthKey>) {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let delta: Block = rng.gen();
+        let mut cot = IdealCOT::new(rng.gen(), delta);
+
+        let (sender_cot, receiver_cot) = cot.random_correlated(count);
+
+        (
+            delta,
+            AuthBit::from_rcot(receiver_cot),
+            AuthKey::from_rcot(sender_cot),
+        )
+    }
+
+    fn assert_auth(bit: AuthBit, key: AuthKey, delta: Block) {
+        key.verify(bit.bit(), bit.mac(), delta).unwrap();
+    }
+
+    #[test]
+    fn test_auth_linear() {
+        let (delta, bits, keys) = setup(2);
+
+        let (x, d) = bits[0].fix(true);
+        let kx = keys[0].fix(d, delta);
+        assert!(x.bit());
+        assert_auth(x, kx, delta);
+
+        assert_auth(x ^ bits[1], kx ^ keys[1], delta);
+        assert_auth(x.xor_const(true), kx.xor_const(true, delta), delta);
+        assert_auth(
+            AuthBit::constant(true),
+            AuthKey::constant(true, delta),
+            delta,
+        );
+
+        assert!(matches!(
+            kx.verify(!x.bit(), x.mac(), delta),
+            Err(AuthError::InvalidMac)
+        ));
+    }
+
+    #[test]
+    fn test_auth_triple() {
+        let (delta, bits, keys) = setup(5);
+
+        // Authenticates a = r_0, b = r_1, c = a * b and the inputs x = 1, y = 1.
+        let (c, d_c) = bits[2].fix(bits[0].bit() & bits[1].bit());
+        let (x, d_x) = bits[3].fix(true);
+        let (y, d_y) = bits[4].fix(true);
+        let triple = AuthTriple {
+            a: bits[0],
+            b: bits[1],
+            c,
+        };
+        let key_triple = AuthTriple {
+            a: keys[0],
+            b: keys[1],
+            c: keys[2].fix(d_c, delta),
+        };
+        let kx = keys[3].fix(d_x, delta);
+        let ky = keys[4].fix(d_y, delta);
+        assert_auth(x, kx, delta);
+        assert_auth(y, ky, delta);
+
+        let [a0, a1] = triple.check_terms();
+        assert_eq!(key_triple.check_term(delta), a0 ^ a1.gfmul(delta));
+
+        let d = x.bit() ^ triple.a.bit();
+        let e = y.bit() ^ triple.b.bit();
+        let z = triple.multiply(d, e);
+        let kz = key_triple.multiply(d, e, delta);
+
+        assert!(z.bit());
+        assert_auth(z, kz, delta);
+    }
+
+    #[test]
+    fn test_auth_share_from_rcot() {
+        let mut rng = Prg::from_seed(Block::ONES);
+        let (delta_0, delta_1): (Block, Block) = (rng.gen(), rng.gen());
+        let mut cot_0 = IdealCOT::new(rng.gen(), delta_0);
+        let mut cot_1 = IdealCOT::new(rng.gen(), delta_1);
+
+        let (sent_0, received_1) = cot_0.random_correlated(8);
+        let (sent_1, received_0) = cot_1.random_correlated(8);
+
+        let shares_0 = AuthShare::from_rcot(received_0, sent_0).unwrap();
+        let shares_1 = AuthShare::from_rcot(received_1, sent_1).unwrap();
+
+        for (s0, s1) in shares_0.into_iter().zip(shares_1) {
+            assert_auth(s0.share(), s1.key(), delta_1);
+            assert_auth(s1.share(), s0.key(), delta_0);
+        }
+
+        let (sent, _) = cot_0.random_correlated(1);
+        let (_, received) = cot_1.random_correlated(2);
+        assert!(matches!(
+            AuthShare::from_rcot(received, sent),
+            Err(AuthError::CountMismatch {
+                sender: 1,
+                receiver: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+//! Define ideal functionalities of zero-knowledge building blocks.
+
+pub mod vope;
+
+pub use vope::IdealVOPE;
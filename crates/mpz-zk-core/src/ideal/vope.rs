@@ -0,0 +1,123 @@
+//! Ideal Vector Oblivious Polynomial Evaluation functionality.
+
+use mpz_core::{prg::Prg, Block};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{VOPEProverOutput, VOPEVerifierOutput};
+
+/// The ideal VOPE functionality.
+///
+/// For every polynomial the prover receives random coefficients `A_0, ..., A_d` and the verifier
+/// receives the evaluation `B = A_0 + A_1 * Δ + ... + A_d * Δ^d` at the global correlation `Δ`.
+#[derive(Debug)]
+pub struct IdealVOPE {
+    delta: Block,
+    counter: usize,
+    prg: Prg,
+}
+
+impl IdealVOPE {
+    /// Creates a new ideal VOPE functionality.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the PRG.
+    /// * `delta` - The correlation.
+    pub fn new(seed: Block, delta: Block) -> Self {
+        IdealVOPE {
+            delta,
+            counter: 0,
+            prg: Prg::from_seed(seed),
+        }
+    }
+
+    /// Returns the correlation, delta.
+    pub fn delta(&self) -> Block {
+        self.delta
+    }
+
+    /// Sets the correlation, delta.
+    pub fn set_delta(&mut self, delta: Block) {
+        self.delta = delta;
+    }
+
+    /// Returns the number of polynomial evaluations executed.
+    pub fn count(&self) -> usize {
+        self.counter
+    }
+
+    /// Executes random polynomial evaluations.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of polynomials.
+    /// * `degree` - The degree of the polynomials.
+    pub fn random(
+        &mut self,
+        count: usize,
+        degree: usize,
+    ) -> (VOPEProverOutput, VOPEVerifierOutput) {
+        let coeffs: Vec<Vec<Block>> = (0..count)
+            .map(|_| {
+                let mut coeffs = vec![Block::ZERO; degree + 1];
+                self.prg.random_blocks(&mut coeffs);
+                coeffs
+            })
+            .collect();
+
+        let evals = coeffs
+            .iter()
+            .map(|coeffs| eval(coeffs, self.delta))
+            .collect();
+
+        self.counter += count;
+
+        (VOPEProverOutput { coeffs }, VOPEVerifierOutput { evals })
+    }
+}
+
+impl Default for IdealVOPE {
+    fn default() -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        Self::new(rng.gen(), rng.gen())
+    }
+}
+
+/// Evaluates the polynomial with the given coefficients, lowest degree first, at `x`.
+fn eval(coeffs: &[Block], x: Block) -> Block {
+    coeffs
+        .iter()
+        .rev()
+        .fold(Block::ZERO, |acc, coeff| acc.gfmul(x) ^ *coeff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ideal_vope() {
+        let mut ideal = IdealVOPE::default();
+        let delta = ideal.delta();
+
+        let (VOPEProverOutput { coeffs }, VOPEVerifierOutput { evals }) = ideal.random(10, 3);
+
+        assert_eq!(coeffs.len(), 10);
+        assert_eq!(evals.len(), 10);
+        assert_eq!(ideal.count(), 10);
+
+        for (coeffs, eval) in coeffs.iter().zip(evals) {
+            assert_eq!(coeffs.len(), 4);
+
+            let mut power = Block::ONE;
+            let mut expected = Block::ZERO;
+            for coeff in coeffs {
+                expected ^= coeff.gfmul(power);
+                power = power.gfmul(delta);
+            }
+
+            assert_eq!(eval, expected);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Low-level crate containing core functionalities for zero-knowledge proof protocols.
+//!
+//! This crate is not intended to be used directly. Instead, use the higher-level APIs provided by
+//! the `mpz-zk` crate.
+//!
+//! # ⚠️ Warning ⚠️
+//!
+//! Some implementations make assumptions about invariants which may not be checked if using these
+//! low-level APIs naively. Failing to uphold these invariants may result in security vulnerabilities.
+//!
+//! USE AT YOUR OWN RISK.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+pub mod auth;
+pub mod ideal;
+pub mod quicksilver;
+
+use mpz_core::Block;
+
+/// The output the prover receives from the VOPE functionality.
+#[derive(Debug)]
+pub struct VOPEProverOutput {
+    /// The coefficients of each polynomial, lowest degree first.
+    pub coeffs: Vec<Vec<Block>>,
+}
+
+/// The output the verifier receives from the VOPE functionality.
+#[derive(Debug)]
+pub struct VOPEVerifierOutput {
+    /// The evaluation of each polynomial at the global correlation.
+    pub evals: Vec<Block>,
+}
@@ -0,0 +1,33 @@
+//! Errors that can occur when using the QuickSilver protocol.
+
+use mpz_circuits::{types::TypeError, CircuitError};
+
+/// Errors that can occur when using the QuickSilver prover.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ProverError {
+    #[error(transparent)]
+    CircuitError(#[from] CircuitError),
+    #[error(transparent)]
+    TypeError(#[from] TypeError),
+    #[error("invalid number of COTs: expected {expected}, got {actual}")]
+    InvalidCOTCount { expected: usize, actual: usize },
+}
+
+/// Errors that can occur when using the QuickSilver verifier.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum VerifierError {
+    #[error(transparent)]
+    TypeError(#[from] TypeError),
+    #[error("invalid number of COTs: expected {expected}, got {actual}")]
+    InvalidCOTCount { expected: usize, actual: usize },
+    #[error("invalid number of corrections: expected {expected}, got {actual}")]
+    InvalidCorrectionCount { expected: usize, actual: usize },
+    #[error("invalid number of output bits: expected {expected}, got {actual}")]
+    InvalidOutputCount { expected: usize, actual: usize },
+    #[error("invalid MAC for output bit {0}")]
+    InvalidOutputMac(usize),
+    #[error("multiplication check failed")]
+    InconsistentMultiplication,
+}
@@ -0,0 +1,149 @@
+//! A zero-knowledge proof of circuit satisfiability based on
+//! [QuickSilver](https://eprint.iacr.org/2021/076) over binary circuits.
+//!
+//! The prover (COT receiver) holds an IT-MAC `M = K + x * Δ` for every wire `x`, while the
+//! verifier (COT sender) holds the key `K` and the global correlation `Δ`. XOR, INV and constant
+//! gates are evaluated locally. For every AND gate the prover commits to the output bit by sending
+//! a single correction bit, and all AND gates are then checked at once against a random challenge
+//! `χ` from the verifier.
+//!
+//! The protocol requires [`cot_count`] random COTs per proof.
+
+use mpz_circuits::Circuit;
+use mpz_core::Block;
+
+pub mod error;
+pub mod msgs;
+pub mod prover;
+pub mod verifier;
+
+pub use error::{ProverError, VerifierError};
+pub use prover::{state as prover_state, Prover};
+pub use verifier::{state as verifier_state, Verifier};
+
+/// Number of COTs used to mask the multiplication check.
+pub(crate) const MASK_SIZE: usize = 128;
+
+/// Returns the number of random COTs required to prove the circuit.
+///
+/// One for every input bit, one for every AND gate, and 128 to mask the multiplication check.
+pub fn cot_count(circ: &Circuit) -> usize {
+    circ.inputs().iter().map(|input| input.len()).sum::<usize>() + circ.and_count() + MASK_SIZE
+}
+
+/// Returns the `i`-th element of the monomial basis of `GF(2^128)`.
+#[inline]
+pub(crate) fn basis(i: usize) -> Block {
+    Block::new((1u128 << i).to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::{circuits::AES128, types::Value};
+    use mpz_core::prg::Prg;
+    use mpz_ot_core::ideal::cot::IdealCOT;
+    use rand::{Rng, SeedableRng};
+    use rstest::*;
+
+    use msgs::{Challenge, Commit};
+
+    fn setup(
+        witness: Vec<Value>,
+    ) -> (
+        Prover<prover_state::Committed>,
+        Commit,
+        Verifier<verifier_state::Initialized>,
+        mpz_ot_core::RCOTSenderOutput<Block>,
+    ) {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let delta: Block = rng.gen();
+        let mut cot = IdealCOT::new(rng.gen(), delta);
+
+        let (sender_cot, receiver_cot) = cot.random_correlated(cot_count(&AES128));
+
+        let (prover, commit) = Prover::new()
+            .commit(&AES128, witness, receiver_cot)
+            .unwrap();
+
+        (prover, commit, Verifier::new(delta), sender_cot)
+    }
+
+    fn witness() -> Vec<Value> {
+        let mut rng = Prg::from_seed(Block::ONES);
+        let key: [u8; 16] = rng.gen();
+        let msg: [u8; 16] = rng.gen();
+
+        vec![key.into(), msg.into()]
+    }
+
+    #[test]
+    fn test_quicksilver() {
+        let witness = witness();
+        let expected = AES128.evaluate(&witness).unwrap();
+
+        let (prover, commit, verifier, cot) = setup(witness);
+        assert_eq!(prover.outputs(), expected.as_slice());
+
+        let verifier = verifier.receive_commit(&AES128, cot, commit).unwrap();
+
+        let challenge = Challenge {
+            chi: Prg::new().gen(),
+        };
+        let proof = prover.prove(challenge);
+        let outputs = verifier.verify(challenge, proof).unwrap();
+
+        assert_eq!(outputs, expected);
+    }
+
+    #[test]
+    fn test_quicksilver_invalid_multiplication() {
+        let (prover, mut commit, verifier, cot) = setup(witness());
+
+        // Flip the committed output of the first AND gate.
+        let input_len: usize = AES128.inputs().iter().map(|input| input.len()).sum();
+        commit.corrections[input_len] = !commit.corrections[input_len];
+
+        let verifier = verifier.receive_commit(&AES128, cot, commit).unwrap();
+
+        let challenge = Challenge {
+            chi: Prg::new().gen(),
+        };
+        let proof = prover.prove(challenge);
+        let err = verifier.verify(challenge, proof).unwrap_err();
+
+        assert!(matches!(err, VerifierError::InconsistentMultiplication));
+    }
+
+    #[test]
+    fn test_quicksilver_invalid_output() {
+        let (prover, commit, verifier, cot) = setup(witness());
+
+        let verifier = verifier.receive_commit(&AES128, cot, commit).unwrap();
+
+        let challenge = Challenge {
+            chi: Prg::new().gen(),
+        };
+        let mut proof = prover.prove(challenge);
+        proof.outputs[0] = !proof.outputs[0];
+
+        let err = verifier.verify(challenge, proof).unwrap_err();
+
+        assert!(matches!(err, VerifierError::InvalidOutputMac(0)));
+    }
+
+    #[rstest]
+    #[case::too_few(-1)]
+    #[case::too_many(1)]
+    fn test_quicksilver_invalid_correction_count(#[case] diff: isize) {
+        let (_, mut commit, verifier, cot) = setup(witness());
+
+        let len = (commit.corrections.len() as isize + diff) as usize;
+        commit.corrections.resize(len, false);
+
+        let err = verifier.receive_commit(&AES128, cot, commit).unwrap_err();
+
+        assert!(matches!(err, VerifierError::InvalidCorrectionCount { .. }));
+    }
+}
@@ -0,0 +1,31 @@
+//! Messages for the QuickSilver protocol.
+
+use mpz_core::Block;
+use serde::{Deserialize, Serialize};
+
+/// Commitment to the witness sent by the prover.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Commit {
+    /// Corrections for the input bits followed by the AND gate outputs, in circuit order.
+    pub corrections: Vec<bool>,
+}
+
+/// Challenge sent by the verifier for the batched multiplication check.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Challenge {
+    /// The random challenge `χ`.
+    pub chi: Block,
+}
+
+/// Proof sent by the prover in response to the challenge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proof {
+    /// Masked constant term of the multiplication check.
+    pub u: Block,
+    /// Masked linear term of the multiplication check.
+    pub v: Block,
+    /// The output bits of the circuit.
+    pub outputs: Vec<bool>,
+    /// The MACs of the output bits.
+    pub output_macs: Vec<Block>,
+}
@@ -0,0 +1,228 @@
+//! QuickSilver prover.
+
+use itybity::IntoBits;
+use mpz_circuits::{
+    types::{TypeError, Value},
+    Circuit, CircuitError, Gate,
+};
+use mpz_core::Block;
+use mpz_ot_core::RCOTReceiverOutput;
+
+use crate::{
+    auth::{select, AuthBit, AuthTriple},
+    quicksilver::{
+        basis, cot_count,
+        error::ProverError,
+        msgs::{Challenge, Commit, Proof},
+        MASK_SIZE,
+    },
+};
+
+/// QuickSilver prover.
+#[derive(Debug, Default)]
+pub struct Prover<T: state::State = state::Initialized> {
+    state: T,
+}
+
+impl Prover {
+    /// Creates a new Prover.
+    pub fn new() -> Self {
+        Prover {
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Evaluates the circuit on the witness and commits to every input bit and AND gate output.
+    ///
+    /// Returns the prover in the committed state and the commitment to send to the verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit.
+    /// * `inputs` - The witness, one value per circuit input.
+    /// * `cot` - Random COTs received from the verifier, see [`cot_count`].
+    pub fn commit(
+        self,
+        circ: &Circuit,
+        inputs: Vec<Value>,
+        cot: RCOTReceiverOutput<bool, Block>,
+    ) -> Result<(Prover<state::Committed>, Commit), ProverError> {
+        if inputs.len() != circ.inputs().len() {
+            return Err(CircuitError::InvalidInputCount(
+                circ.inputs().len(),
+                inputs.len(),
+            ))?;
+        }
+
+        let expected = cot_count(circ);
+        if cot.choices.len() != expected || cot.msgs.len() != expected {
+            return Err(ProverError::InvalidCOTCount {
+                expected,
+                actual: cot.choices.len().min(cot.msgs.len()),
+            });
+        }
+
+        let mut cots = AuthBit::from_rcot(cot).into_iter();
+        let mut wires = vec![AuthBit::default(); circ.feed_count()];
+        let mut corrections = Vec::with_capacity(expected - MASK_SIZE);
+
+        for (value, input) in inputs.into_iter().zip(circ.inputs()) {
+            if value.value_type() != input.value_type() {
+                return Err(TypeError::UnexpectedType {
+                    expected: input.value_type(),
+                    actual: value.value_type(),
+                })?;
+            }
+
+            for (bit, node) in value.into_iter_lsb0().zip(input.iter()) {
+                let (wire, correction) = cots.next().expect("COT count was checked").fix(bit);
+                corrections.push(correction);
+                wires[node.id()] = wire;
+            }
+        }
+
+        let mut terms = Vec::with_capacity(circ.and_count());
+        for gate in circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    wires[z.id()] = wires[x.id()] ^ wires[y.id()];
+                }
+                Gate::Inv { x, z } => {
+                    wires[z.id()] = wires[x.id()].xor_const(true);
+                }
+                Gate::Const { z, value } => {
+                    wires[z.id()] = AuthBit::constant(*value);
+                }
+                Gate::And { x, y, z } => {
+                    let a = wires[x.id()];
+                    let b = wires[y.id()];
+
+                    let (c, correction) = cots
+                        .next()
+                        .expect("COT count was checked")
+                        .fix(a.bit() & b.bit());
+                    corrections.push(correction);
+                    wires[z.id()] = c;
+
+                    terms.push(AuthTriple { a, b, c }.check_terms());
+                }
+            }
+        }
+
+        // Random mask for the multiplication check, assembled from the remaining COTs.
+        let (mask, mask_mac) =
+            cots.enumerate()
+                .fold((Block::ZERO, Block::ZERO), |(mask, mac), (i, r)| {
+                    (
+                        mask ^ select(r.bit(), basis(i)),
+                        mac ^ r.mac().gfmul(basis(i)),
+                    )
+                });
+
+        let mut outputs = Vec::with_capacity(circ.outputs().len());
+        let mut output_bits = Vec::new();
+        let mut output_macs = Vec::new();
+        for output in circ.outputs() {
+            let bits: Vec<bool> = output.iter().map(|node| wires[node.id()].bit()).collect();
+            output_macs.extend(output.iter().map(|node| wires[node.id()].mac()));
+            outputs.push(output.from_bin_repr(&bits)?);
+            output_bits.extend(bits);
+        }
+
+        Ok((
+            Prover {
+                state: state::Committed {
+                    terms,
+                    mask,
+                    mask_mac,
+                    outputs,
+                    output_bits,
+                    output_macs,
+                },
+            },
+            Commit { corrections },
+        ))
+    }
+}
+
+impl Prover<state::Committed> {
+    /// Returns the outputs of the circuit.
+    pub fn outputs(&self) -> &[Value] {
+        &self.state.outputs
+    }
+
+    /// Responds to the verifier's challenge, proving that every AND gate is consistent.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The verifier's challenge.
+    pub fn prove(self, challenge: Challenge) -> Proof {
+        let state::Committed {
+            terms,
+            mask,
+            mask_mac,
+            output_bits,
+            output_macs,
+            ..
+        } = self.state;
+
+        let mut u = mask_mac;
+        let mut v = mask;
+        let mut power = challenge.chi;
+        for [a0, a1] in terms {
+            u ^= a0.gfmul(power);
+            v ^= a1.gfmul(power);
+            power = power.gfmul(challenge.chi);
+        }
+
+        Proof {
+            u,
+            v,
+            outputs: output_bits,
+            output_macs,
+        }
+    }
+}
+
+/// The prover's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Committed {}
+    }
+
+    /// The prover's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The prover's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The prover's state after committing to the witness.
+    pub struct Committed {
+        /// The constant and linear terms of each AND gate's check.
+        pub(super) terms: Vec<[Block; 2]>,
+        /// Mask for the linear term of the multiplication check.
+        pub(super) mask: Block,
+        /// MAC of the mask.
+        pub(super) mask_mac: Block,
+        /// The outputs of the circuit.
+        pub(super) outputs: Vec<Value>,
+        /// The output bits.
+        pub(super) output_bits: Vec<bool>,
+        /// The MACs of the output bits.
+        pub(super) output_macs: Vec<Block>,
+    }
+
+    impl State for Committed {}
+
+    opaque_debug::implement!(Committed);
+}
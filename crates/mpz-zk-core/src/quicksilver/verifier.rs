@@ -0,0 +1,228 @@
+//! QuickSilver verifier.
+
+use mpz_circuits::{
+    types::{BinaryRepr, Value},
+    Circuit, Gate,
+};
+use mpz_core::Block;
+use mpz_ot_core::RCOTSenderOutput;
+
+use crate::{
+    auth::{AuthKey, AuthTriple},
+    quicksilver::{
+        basis, cot_count,
+        error::VerifierError,
+        msgs::{Challenge, Commit, Proof},
+        MASK_SIZE,
+    },
+};
+
+/// QuickSilver verifier.
+#[derive(Debug)]
+pub struct Verifier<T: state::State = state::Initialized> {
+    delta: Block,
+    state: T,
+}
+
+impl Verifier {
+    /// Creates a new Verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The COT sender's global correlation.
+    pub fn new(delta: Block) -> Self {
+        Verifier {
+            delta,
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Receives the prover's commitment to the witness.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit.
+    /// * `cot` - Random COTs sent to the prover, see [`cot_count`].
+    /// * `commit` - The prover's commitment.
+    pub fn receive_commit(
+        self,
+        circ: &Circuit,
+        cot: RCOTSenderOutput<Block>,
+        commit: Commit,
+    ) -> Result<Verifier<state::Committed>, VerifierError> {
+        let expected = cot_count(circ);
+        if cot.msgs.len() != expected {
+            return Err(VerifierError::InvalidCOTCount {
+                expected,
+                actual: cot.msgs.len(),
+            });
+        }
+
+        let Commit { corrections } = commit;
+        if corrections.len() != expected - MASK_SIZE {
+            return Err(VerifierError::InvalidCorrectionCount {
+                expected: expected - MASK_SIZE,
+                actual: corrections.len(),
+            });
+        }
+
+        let delta = self.delta;
+        let mut keys = AuthKey::from_rcot(cot).into_iter();
+        let mut corrections = corrections.into_iter();
+        // Adjusts the next random key with the next correction.
+        let mut next_key = || {
+            let key = keys.next().expect("COT count was checked");
+            let d = corrections.next().expect("correction count was checked");
+            key.fix(d, delta)
+        };
+
+        let mut wires = vec![AuthKey::default(); circ.feed_count()];
+        for node in circ.inputs().iter().flat_map(|input| input.iter()) {
+            wires[node.id()] = next_key();
+        }
+
+        let mut terms = Vec::with_capacity(circ.and_count());
+        for gate in circ.gates() {
+            match gate {
+                Gate::Xor { x, y, z } => {
+                    wires[z.id()] = wires[x.id()] ^ wires[y.id()];
+                }
+                Gate::Inv { x, z } => {
+                    wires[z.id()] = wires[x.id()].xor_const(true, delta);
+                }
+                Gate::Const { z, value } => {
+                    wires[z.id()] = AuthKey::constant(*value, delta);
+                }
+                Gate::And { x, y, z } => {
+                    let a = wires[x.id()];
+                    let b = wires[y.id()];
+                    let c = next_key();
+                    wires[z.id()] = c;
+
+                    terms.push(AuthTriple { a, b, c }.check_term(delta));
+                }
+            }
+        }
+
+        let mask_key = keys
+            .enumerate()
+            .fold(Block::ZERO, |acc, (i, key)| acc ^ key.key().gfmul(basis(i)));
+
+        let output_keys = circ
+            .outputs()
+            .iter()
+            .flat_map(|output| output.iter().map(|node| wires[node.id()]))
+            .collect();
+
+        Ok(Verifier {
+            delta,
+            state: state::Committed {
+                terms,
+                mask_key,
+                outputs: circ.outputs().to_vec(),
+                output_keys,
+            },
+        })
+    }
+}
+
+impl Verifier<state::Committed> {
+    /// Verifies the prover's proof, returning the outputs of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The challenge sent to the prover.
+    /// * `proof` - The prover's proof.
+    pub fn verify(self, challenge: Challenge, proof: Proof) -> Result<Vec<Value>, VerifierError> {
+        let delta = self.delta;
+        let state::Committed {
+            terms,
+            mask_key,
+            outputs,
+            output_keys,
+        } = self.state;
+        let Proof {
+            u,
+            v,
+            outputs: output_bits,
+            output_macs,
+        } = proof;
+
+        let mut w = mask_key;
+        let mut power = challenge.chi;
+        for b in terms {
+            w ^= b.gfmul(power);
+            power = power.gfmul(challenge.chi);
+        }
+
+        if w != u ^ v.gfmul(delta) {
+            return Err(VerifierError::InconsistentMultiplication);
+        }
+
+        if output_bits.len() != output_keys.len() || output_macs.len() != output_keys.len() {
+            return Err(VerifierError::InvalidOutputCount {
+                expected: output_keys.len(),
+                actual: output_bits.len().min(output_macs.len()),
+            });
+        }
+
+        for (i, ((&bit, &mac), &key)) in output_bits
+            .iter()
+            .zip(&output_macs)
+            .zip(&output_keys)
+            .enumerate()
+        {
+            key.verify(bit, mac, delta)
+                .map_err(|_| VerifierError::InvalidOutputMac(i))?;
+        }
+
+        let mut bits = output_bits.as_slice();
+        outputs
+            .iter()
+            .map(|output| {
+                let (value, rest) = bits.split_at(output.len());
+                bits = rest;
+                output.from_bin_repr(value).map_err(VerifierError::from)
+            })
+            .collect()
+    }
+}
+
+/// The verifier's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Committed {}
+    }
+
+    /// The verifier's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The verifier's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The verifier's state after receiving the prover's commitment.
+    pub struct Committed {
+        /// The expected value `A_0 + A_1 * Δ` of each AND gate's check.
+        pub(super) terms: Vec<Block>,
+        /// Key of the mask for the multiplication check.
+        pub(super) mask_key: Block,
+        /// The output types of the circuit.
+        pub(super) outputs: Vec<BinaryRepr>,
+        /// The keys of the output bits.
+        pub(super) output_keys: Vec<AuthKey>,
+    }
+
+    impl State for Committed {}
+
+    opaque_debug::implement!(Committed);
+}
@@ -0,0 +1,96 @@
+//! Conversion of garbled circuit encodings into authenticated values.
+//!
+//! The generator of a garbled circuit becomes the verifier and the evaluator becomes the prover,
+//! see [`mpz_garble_core::bridge`]. The converted values are authenticated under the delta of the
+//! encodings, so they can only be combined with a proof whose random COTs use the same delta.
+
+use mpz_common::Context;
+use mpz_garble_core::{bridge, encoding_state, Decoding, EncodedValue};
+use mpz_zk_core::auth::{AuthBit, AuthKey};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::ZKError;
+
+/// Converts the encodings of values into IT-MAC keys, as the generator.
+///
+/// The decodings of the values are sent to the evaluator, which learns the values.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `values` - The full encodings of the values.
+pub async fn convert_full<Ctx: Context>(
+    ctx: &mut Ctx,
+    values: &[EncodedValue<encoding_state::Full>],
+) -> Result<Vec<Vec<AuthKey>>, ZKError> {
+    let decodings: Vec<Decoding> = values.iter().map(|value| value.decoding()).collect();
+    ctx.io_mut().send(decodings).await?;
+
+    Ok(values.iter().map(bridge::encoding_keys).collect())
+}
+
+/// Converts the encodings of values into IT-MAC authenticated bits, as the evaluator.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `values` - The active encodings of the values.
+pub async fn convert_active<Ctx: Context>(
+    ctx: &mut Ctx,
+    values: &[EncodedValue<encoding_state::Active>],
+) -> Result<Vec<Vec<AuthBit>>, ZKError> {
+    let decodings: Vec<Decoding> = ctx.io_mut().expect_next().await?;
+
+    if decodings.len() != values.len() {
+        return Err(ZKError::BridgeError(format!(
+            "expected {} decodings, got {}",
+            values.len(),
+            decodings.len()
+        )));
+    }
+
+    values
+        .iter()
+        .zip(&decodings)
+        .map(|(value, decoding)| Ok(bridge::encoding_bits(value, decoding)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_common::executor::test_st_executor;
+    use mpz_garble_core::{ChaChaEncoder, Encoder};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bridge() {
+        let (mut ctx_gen, mut ctx_ev) = test_st_executor(8);
+        let encoder = ChaChaEncoder::new([0u8; 32]);
+        let delta = *encoder.delta();
+
+        let full: Vec<EncodedValue<encoding_state::Full>> = vec![
+            encoder.encode::<u32>(0).into(),
+            encoder.encode::<[u8; 2]>(1),
+        ];
+        let active = vec![
+            full[0].select(42u32).unwrap(),
+            full[1].select([7u8, 8]).unwrap(),
+        ];
+
+        let (keys, bits) = tokio::try_join!(
+            convert_full(&mut ctx_gen, &full),
+            convert_active(&mut ctx_ev, &active)
+        )
+        .unwrap();
+
+        assert_eq!(bits[0].len(), 32);
+        assert_eq!(bits[1].len(), 16);
+        assert!(bits[0][1].bit() && bits[0][3].bit() && bits[0][5].bit());
+        for (keys, bits) in keys.iter().zip(&bits) {
+            for (key, bit) in keys.iter().zip(bits) {
+                key.verify(bit.bit(), bit.mac(), delta).unwrap();
+            }
+        }
+    }
+}
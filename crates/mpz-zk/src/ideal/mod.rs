@@ -0,0 +1,3 @@
+//! Ideal implementations of zero-knowledge building blocks.
+
+pub mod vope;
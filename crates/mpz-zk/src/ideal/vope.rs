@@ -0,0 +1,116 @@
+//! Ideal functionality for vector oblivious polynomial evaluation.
+
+use mpz_common::{
+    ideal::{ideal_f2p_with_latency, Alice, Bob},
+    Context,
+};
+use mpz_core::Block;
+use mpz_zk_core::{ideal::IdealVOPE, VOPEProverOutput, VOPEVerifierOutput};
+
+fn vope(
+    f: &mut IdealVOPE,
+    verifier: (usize, usize),
+    prover: (usize, usize),
+) -> (VOPEVerifierOutput, VOPEProverOutput) {
+    assert_eq!(verifier, prover);
+
+    let (count, degree) = verifier;
+    let (prover_output, verifier_output) = f.random(count, degree);
+
+    (verifier_output, prover_output)
+}
+
+/// The number of communication rounds consumed by each call to the VOPE functionality, which is
+/// non-interactive once set up.
+const VOPE_ROUNDS: usize = 0;
+
+/// Returns an ideal VOPE prover and verifier.
+pub fn ideal_vope() -> (IdealVOPEProver, IdealVOPEVerifier) {
+    let (alice, bob) = ideal_f2p_with_latency(IdealVOPE::default(), VOPE_ROUNDS);
+    (IdealVOPEProver(bob), IdealVOPEVerifier(alice))
+}
+
+/// Ideal VOPE prover.
+#[derive(Debug, Clone)]
+pub struct IdealVOPEProver(Bob<IdealVOPE>);
+
+impl IdealVOPEProver {
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+
+    /// Receives the coefficients of random polynomials.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of polynomials.
+    /// * `degree` - The degree of the polynomials.
+    pub async fn random_vope<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+        degree: usize,
+    ) -> VOPEProverOutput {
+        self.0.call(ctx, (count, degree), vope).await
+    }
+}
+
+/// Ideal VOPE verifier.
+#[derive(Debug, Clone)]
+pub struct IdealVOPEVerifier(Alice<IdealVOPE>);
+
+impl IdealVOPEVerifier {
+    /// Returns the global correlation, delta.
+    pub fn delta(&mut self) -> Block {
+        self.0.get_mut().delta()
+    }
+
+    /// Returns the number of communication rounds consumed by the functionality.
+    pub fn rounds(&self) -> usize {
+        self.0.rounds()
+    }
+
+    /// Receives the evaluations of random polynomials at the global correlation.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of polynomials.
+    /// * `degree` - The degree of the polynomials.
+    pub async fn random_vope<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+        degree: usize,
+    ) -> VOPEVerifierOutput {
+        self.0.call(ctx, (count, degree), vope).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpz_common::executor::test_st_executor;
+
+    #[tokio::test]
+    async fn test_ideal_vope() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut prover, mut verifier) = ideal_vope();
+
+        let delta = verifier.delta();
+
+        let (VOPEProverOutput { coeffs }, VOPEVerifierOutput { evals }) = tokio::join!(
+            prover.random_vope(&mut ctx_a, 10, 2),
+            verifier.random_vope(&mut ctx_b, 10, 2)
+        );
+
+        assert_eq!(coeffs.len(), 10);
+        assert!(coeffs.iter().zip(evals).all(|(coeffs, eval)| {
+            eval == coeffs[0] ^ coeffs[1].gfmul(delta) ^ coeffs[2].gfmul(delta.gfmul(delta))
+        }));
+        assert_eq!(prover.rounds(), 0);
+        assert_eq!(verifier.rounds(), 0);
+    }
+}
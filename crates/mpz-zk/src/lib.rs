@@ -0,0 +1,35 @@
+//! Implementations of zero-knowledge proof protocols.
+
+#![deny(
+    unsafe_code,
+    missing_docs,
+    unused_imports,
+    unused_must_use,
+    unreachable_pub,
+    clippy::all
+)]
+
+pub mod bridge;
+#[cfg(any(test, feature = "ideal"))]
+pub mod ideal;
+pub mod quicksilver;
+
+/// A zero-knowledge proof error.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ZKError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("context error: {0}")]
+    Context(#[from] mpz_common::ContextError),
+    #[error("OT error: {0}")]
+    OTError(#[from] mpz_ot::OTError),
+    #[error("prover error: {0}")]
+    ProverError(#[from] mpz_zk_core::quicksilver::ProverError),
+    #[error("verifier error: {0}")]
+    VerifierError(#[from] mpz_zk_core::quicksilver::VerifierError),
+    #[error("encoding error: {0}")]
+    ValueError(#[from] mpz_garble_core::ValueError),
+    #[error("bridge error: {0}")]
+    BridgeError(String),
+}
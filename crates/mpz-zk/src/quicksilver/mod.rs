@@ -0,0 +1,107 @@
+//! A zero-knowledge proof of circuit satisfiability based on
+//! [QuickSilver](https://eprint.iacr.org/2021/076) over binary circuits.
+//!
+//! The prover is the receiver of random COTs and the verifier is the sender. Each AND gate costs
+//! one COT and one bit of communication.
+//!
+//! # Example
+//!
+//! ```
+//! use mpz_circuits::{circuits::AES128, types::Value};
+//! use mpz_common::executor::test_st_executor;
+//! use mpz_ot::ideal::cot::ideal_rcot;
+//! use mpz_zk::quicksilver::{Prover, Verifier};
+//! # futures::executor::block_on(async {
+//!
+//! let (mut ctx_prover, mut ctx_verifier) = test_st_executor(8);
+//! let (mut rcot_sender, rcot_receiver) = ideal_rcot();
+//! let delta = rcot_sender.delta();
+//!
+//! let mut prover = Prover::new(rcot_receiver);
+//! let mut verifier = Verifier::new(delta, rcot_sender);
+//!
+//! let witness: Vec<Value> = vec![[0u8; 16].into(), [42u8; 16].into()];
+//!
+//! let (prover_output, verifier_output) = futures::try_join!(
+//!     prover.prove(&mut ctx_prover, AES128.clone(), witness),
+//!     verifier.verify(&mut ctx_verifier, AES128.clone()),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(prover_output, verifier_output);
+//! # });
+//! ```
+
+mod prover;
+mod verifier;
+
+pub use prover::Prover;
+pub use verifier::Verifier;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::{circuits::AES128, types::Value};
+    use mpz_common::executor::test_st_executor;
+    use mpz_core::{prg::Prg, Block};
+    use mpz_ot::ideal::cot::ideal_rcot;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ZKError;
+    use mpz_zk_core::quicksilver::VerifierError;
+
+    fn witness() -> Vec<Value> {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let key: [u8; 16] = rng.gen();
+        let msg: [u8; 16] = rng.gen();
+
+        vec![key.into(), msg.into()]
+    }
+
+    #[tokio::test]
+    async fn test_quicksilver() {
+        let (mut ctx_prover, mut ctx_verifier) = test_st_executor(8);
+        let (mut rcot_sender, rcot_receiver) = ideal_rcot();
+        let delta = rcot_sender.delta();
+
+        let mut prover = Prover::new(rcot_receiver);
+        let mut verifier = Verifier::new(delta, rcot_sender);
+
+        let witness = witness();
+        let expected = AES128.evaluate(&witness).unwrap();
+
+        // Prove twice to check that the COTs are consumed correctly.
+        for _ in 0..2 {
+            let (prover_output, verifier_output) = tokio::try_join!(
+                prover.prove(&mut ctx_prover, AES128.clone(), witness.clone()),
+                verifier.verify(&mut ctx_verifier, AES128.clone()),
+            )
+            .unwrap();
+
+            assert_eq!(prover_output, expected);
+            assert_eq!(verifier_output, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quicksilver_wrong_delta() {
+        let (mut ctx_prover, mut ctx_verifier) = test_st_executor(8);
+        let (mut rcot_sender, rcot_receiver) = ideal_rcot();
+        let delta = rcot_sender.delta() ^ Block::ONE;
+
+        let mut prover = Prover::new(rcot_receiver);
+        let mut verifier = Verifier::new(delta, rcot_sender);
+
+        let (prover_output, verifier_output) = tokio::join!(
+            prover.prove(&mut ctx_prover, AES128.clone(), witness()),
+            verifier.verify(&mut ctx_verifier, AES128.clone()),
+        );
+
+        assert!(prover_output.is_ok());
+        assert!(matches!(
+            verifier_output.unwrap_err(),
+            ZKError::VerifierError(VerifierError::InconsistentMultiplication)
+        ));
+    }
+}
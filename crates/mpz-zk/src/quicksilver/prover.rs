@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use mpz_circuits::{types::Value, Circuit};
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot::RandomCOTReceiver;
+use mpz_zk_core::quicksilver::{cot_count, msgs::Challenge, Prover as ProverCore};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::ZKError;
+
+/// QuickSilver prover.
+#[derive(Debug)]
+pub struct Prover<RCOT> {
+    rcot: RCOT,
+}
+
+impl<RCOT> Prover<RCOT> {
+    /// Creates a new Prover.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcot` - The random COT receiver.
+    pub fn new(rcot: RCOT) -> Self {
+        Self { rcot }
+    }
+
+    /// Proves knowledge of a witness for the circuit, returning the outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `circ` - The circuit.
+    /// * `inputs` - The witness, one value per circuit input.
+    pub async fn prove<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: Vec<Value>,
+    ) -> Result<Vec<Value>, ZKError>
+    where
+        RCOT: RandomCOTReceiver<Ctx, bool, Block>,
+    {
+        let cot = self
+            .rcot
+            .receive_random_correlated(ctx, cot_count(&circ))
+            .await?;
+
+        let (prover, commit) =
+            Backend::spawn(move || ProverCore::new().commit(&circ, inputs, cot)).await?;
+
+        ctx.io_mut().send(commit).await?;
+
+        let challenge: Challenge = ctx.io_mut().expect_next().await?;
+        let outputs = prover.outputs().to_vec();
+
+        let proof = Backend::spawn(move || prover.prove(challenge)).await;
+
+        ctx.io_mut().send(proof).await?;
+
+        Ok(outputs)
+    }
+}
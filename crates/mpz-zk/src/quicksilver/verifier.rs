@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use mpz_circuits::{types::Value, Circuit};
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot::RandomCOTSender;
+use mpz_zk_core::quicksilver::{
+    cot_count,
+    msgs::{Challenge, Commit, Proof},
+    Verifier as VerifierCore,
+};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::ZKError;
+
+/// QuickSilver verifier.
+#[derive(Debug)]
+pub struct Verifier<RCOT> {
+    delta: Block,
+    rcot: RCOT,
+}
+
+impl<RCOT> Verifier<RCOT> {
+    /// Creates a new Verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The global correlation of the random COT sender.
+    /// * `rcot` - The random COT sender.
+    pub fn new(delta: Block, rcot: RCOT) -> Self {
+        Self { delta, rcot }
+    }
+
+    /// Verifies the prover's proof for the circuit, returning the outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `circ` - The circuit.
+    pub async fn verify<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+    ) -> Result<Vec<Value>, ZKError>
+    where
+        RCOT: RandomCOTSender<Ctx, Block>,
+    {
+        let cot = self
+            .rcot
+            .send_random_correlated(ctx, cot_count(&circ))
+            .await?;
+
+        let commit: Commit = ctx.io_mut().expect_next().await?;
+
+        let verifier = VerifierCore::new(self.delta);
+        let verifier = Backend::spawn(move || verifier.receive_commit(&circ, cot, commit)).await?;
+
+        // The challenge must only be sampled after the prover has committed.
+        let challenge = Challenge {
+            chi: thread_rng().gen(),
+        };
+        ctx.io_mut().send(challenge).await?;
+
+        let proof: Proof = ctx.io_mut().expect_next().await?;
+
+        let outputs = Backend::spawn(move || verifier.verify(challenge, proof)).await?;
+
+        Ok(outputs)
+    }
+}